@@ -25,16 +25,23 @@ use risingwave_rpc_client::ComputeClient;
 use tonic::Streaming;
 
 use crate::exchange_source::ExchangeSource;
+use crate::executor::ExecutorActualsCollector;
 
 /// Use grpc client as the source.
 pub struct GrpcExchangeSource {
     stream: Streaming<GetDataResponse>,
 
     task_output_id: TaskOutputId,
+
+    /// Collects the actuals of the remote executors sent back in the local execution mode.
+    actuals_collector: Option<ExecutorActualsCollector>,
 }
 
 impl GrpcExchangeSource {
-    pub async fn create(exchange_source: ProstExchangeSource) -> Result<Self> {
+    pub async fn create(
+        exchange_source: ProstExchangeSource,
+        actuals_collector: Option<ExecutorActualsCollector>,
+    ) -> Result<Self> {
         let addr = exchange_source.get_host()?.into();
         let task_output_id = exchange_source.get_task_output_id()?.clone();
         let task_id = task_output_id.get_task_id()?.clone();
@@ -49,6 +56,7 @@ impl GrpcExchangeSource {
                     task_id: Some(task_id),
                     plan: plan.plan,
                     epoch: plan.epoch,
+                    collect_actuals: plan.collect_actuals,
                 };
                 client.execute(execute_request).await?
             }
@@ -57,6 +65,7 @@ impl GrpcExchangeSource {
         let source = Self {
             stream,
             task_output_id,
+            actuals_collector,
         };
         Ok(source)
     }
@@ -75,11 +84,20 @@ impl ExchangeSource for GrpcExchangeSource {
 
     fn take_data(&mut self) -> Self::TakeDataFuture<'_> {
         async {
-            let res = match self.stream.next().await {
-                None => return Ok(None),
-                Some(r) => r,
+            let task_data = loop {
+                let res = match self.stream.next().await {
+                    None => return Ok(None),
+                    Some(r) => r,
+                };
+                let task_data = res?;
+                match &self.actuals_collector {
+                    // The message after the last chunk carries the actuals instead of data.
+                    Some(collector) if task_data.record_batch.is_none() => {
+                        collector.merge_protobuf(&task_data.actuals)
+                    }
+                    _ => break task_data,
+                }
             };
-            let task_data = res?;
             let data = DataChunk::from_protobuf(task_data.get_record_batch()?)?.compact()?;
             trace!(
                 "Receiver taskOutput = {:?}, data = {:?}",
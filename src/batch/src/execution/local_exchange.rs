@@ -115,6 +115,7 @@ mod tests {
                 tx.send(Ok(GetDataResponse {
                     status: None,
                     record_batch: Some(DataChunk::default()),
+                    actuals: vec![],
                 }))
                 .await
                 .unwrap();
@@ -164,7 +165,9 @@ mod tests {
             host: Some(HostAddr::from(addr).to_protobuf()),
             local_execute_plan: None,
         };
-        let mut src = GrpcExchangeSource::create(exchange_source).await.unwrap();
+        let mut src = GrpcExchangeSource::create(exchange_source, None)
+            .await
+            .unwrap();
         for _ in 0..3 {
             assert!(src.take_data().await.unwrap().is_some());
         }
@@ -187,7 +190,7 @@ mod tests {
             host: Some(addr.to_protobuf()),
             local_execute_plan: None,
         };
-        let res = GrpcExchangeSource::create(exchange_source).await;
+        let res = GrpcExchangeSource::create(exchange_source, None).await;
         assert!(res.is_err());
     }
 }
@@ -18,6 +18,7 @@ use itertools::Itertools;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{Field, Schema};
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::util::chunk_coalesce::{DataChunkBuilder, SlicedDataChunk};
 use risingwave_common::util::select_all;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::ExchangeSource as ProstExchangeSource;
@@ -26,7 +27,7 @@ use risingwave_pb::plan_common::Field as NodeField;
 use crate::exchange_source::ExchangeSourceImpl;
 use crate::execution::grpc_exchange::GrpcExchangeSource;
 use crate::execution::local_exchange::LocalExchangeSource;
-use crate::executor::ExecutorBuilder;
+use crate::executor::{ExecutorActualsCollector, ExecutorBuilder};
 use crate::task::{BatchTaskContext, TaskId};
 
 pub type ExchangeExecutor<C> = GenericExchangeExecutor<C>;
@@ -38,6 +39,9 @@ pub struct GenericExchangeExecutor<C> {
     schema: Schema,
     task_id: TaskId,
     identity: String,
+
+    /// The number of rows in each output chunk. 0 to output the chunks as received.
+    chunk_size: usize,
 }
 
 /// `CreateSource` determines the right type of `ExchangeSource` to create.
@@ -50,8 +54,17 @@ pub trait CreateSource: Send {
     ) -> Result<ExchangeSourceImpl>;
 }
 
-#[derive(Clone)]
-pub struct DefaultCreateSource {}
+#[derive(Clone, Default)]
+pub struct DefaultCreateSource {
+    /// Collects the actuals of the remote executors, for `EXPLAIN ANALYZE`.
+    actuals_collector: Option<ExecutorActualsCollector>,
+}
+
+impl DefaultCreateSource {
+    pub fn new(actuals_collector: Option<ExecutorActualsCollector>) -> Self {
+        Self { actuals_collector }
+    }
+}
 
 #[async_trait::async_trait]
 impl CreateSource for DefaultCreateSource {
@@ -80,7 +93,8 @@ impl CreateSource for DefaultCreateSource {
             );
 
             Ok(ExchangeSourceImpl::Grpc(
-                GrpcExchangeSource::create(prost_source.clone()).await?,
+                GrpcExchangeSource::create(prost_source.clone(), self.actuals_collector.clone())
+                    .await?,
             ))
         }
     }
@@ -105,7 +119,10 @@ impl BoxedExecutorBuilder for GenericExchangeExecutorBuilder {
 
         ensure!(!node.get_sources().is_empty());
         let prost_sources: Vec<ProstExchangeSource> = node.get_sources().to_vec();
-        let source_creators = vec![DefaultCreateSource {}; prost_sources.len()];
+        let source_creators = vec![
+            DefaultCreateSource::new(source.actuals_collector().cloned());
+            prost_sources.len()
+        ];
         let mut sources: Vec<ExchangeSourceImpl> = vec![];
 
         for (prost_source, source_creator) in prost_sources.iter().zip_eq(source_creators) {
@@ -123,6 +140,7 @@ impl BoxedExecutorBuilder for GenericExchangeExecutorBuilder {
             schema: Schema { fields },
             task_id: source.task_id.clone(),
             identity: source.plan_node().get_identity().clone(),
+            chunk_size: node.chunk_size as usize,
         }))
     }
 }
@@ -152,9 +170,28 @@ impl<C: BatchTaskContext> GenericExchangeExecutor<C> {
         )
         .boxed();
 
+        if self.chunk_size == 0 {
+            while let Some(data_chunk) = stream.next().await {
+                let data_chunk = data_chunk?;
+                yield data_chunk
+            }
+            return Ok(());
+        }
+
+        // Rechunk the received chunks into chunks of `chunk_size` rows.
+        let mut chunk_builder = DataChunkBuilder::new(self.schema.data_types(), self.chunk_size);
         while let Some(data_chunk) = stream.next().await {
-            let data_chunk = data_chunk?;
-            yield data_chunk
+            let mut remaining = Some(SlicedDataChunk::new_checked(data_chunk?)?);
+            while let Some(sliced_chunk) = remaining {
+                let (remaining_chunk, output) = chunk_builder.append_chunk(sliced_chunk)?;
+                if let Some(output_chunk) = output {
+                    yield output_chunk
+                }
+                remaining = remaining_chunk;
+            }
+        }
+        if let Some(output_chunk) = chunk_builder.consume_all()? {
+            yield output_chunk
         }
     }
 }
@@ -218,6 +255,7 @@ mod tests {
             },
             task_id: TaskId::default(),
             identity: "GenericExchangeExecutor2".to_string(),
+            chunk_size: 0,
         });
 
         let mut stream = executor.execute();
@@ -232,4 +270,44 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_exchange_chunk_size() {
+        let context = ComputeNodeContext::new_for_test();
+        let mut sources = vec![];
+        for _ in 0..2 {
+            let chunk = DataChunk::new(
+                vec![Column::new(Arc::new(
+                    array_nonnull! { I32Array, [1, 2, 3] }.into(),
+                ))],
+                3,
+            );
+            let fake_exchange_source = FakeExchangeSource::new(vec![Some(chunk); 5]);
+            let fake_create_source = FakeCreateSource::new(fake_exchange_source);
+            let source = fake_create_source
+                .create_source(context.clone(), &ProstExchangeSource::default())
+                .await
+                .unwrap();
+            sources.push(source);
+        }
+
+        let executor = Box::new(GenericExchangeExecutor::<ComputeNodeContext> {
+            sources,
+            context,
+            schema: Schema {
+                fields: vec![Field::unnamed(DataType::Int32)],
+            },
+            task_id: TaskId::default(),
+            identity: "GenericExchangeExecutor2".to_string(),
+            chunk_size: 4,
+        });
+
+        // 30 rows are rechunked into 7 chunks of 4 rows and the last one of 2 rows.
+        let mut stream = executor.execute();
+        let mut cardinalities = vec![];
+        while let Some(chunk) = stream.next().await {
+            cardinalities.push(chunk.unwrap().cardinality());
+        }
+        assert_eq!(cardinalities, [vec![4; 7], vec![2]].concat());
+    }
 }
@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::io::SeekFrom;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::vec;
 
+use anyhow::anyhow;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
+use prost::Message;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{Field, Schema};
@@ -28,10 +32,15 @@ use risingwave_common::hash::{
 };
 use risingwave_common::types::DataType;
 use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
+use risingwave_common::util::hash_util::CRC32FastBuilder;
 use risingwave_expr::vector_op::agg::{AggStateFactory, BoxedAggState};
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::HashAggNode;
+use risingwave_pb::data::DataChunk as ProstDataChunk;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader};
 
+use crate::executor::monitor::BatchMetrics;
 use crate::executor::{
     BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
 };
@@ -39,6 +48,13 @@ use crate::task::{BatchTaskContext, TaskId};
 
 type AggHashMap<K> = HashMap<K, Vec<BoxedAggState>, PrecomputedBuildHasher>;
 
+/// The spilled rows are partitioned by `SPILL_PARTITION_BITS` bits of the hash of the group key.
+/// Each level of spilling uses the next bits, so that the groups of a spilled partition are
+/// partitioned again if it's still too large.
+const SPILL_PARTITION_BITS: usize = 4;
+const SPILL_PARTITION_NUM: usize = 1 << SPILL_PARTITION_BITS;
+const MAX_SPILL_LEVEL: usize = u64::BITS as usize / SPILL_PARTITION_BITS;
+
 struct HashAggExecutorBuilderDispatcher;
 
 /// A dispatcher to help create specialized hash agg executor.
@@ -59,14 +75,21 @@ pub struct HashAggExecutorBuilder {
     schema: Schema,
     task_id: TaskId,
     identity: String,
+    spill_group_count: usize,
+    spill_memory_bytes: usize,
+    metrics: Arc<BatchMetrics>,
 }
 
 impl HashAggExecutorBuilder {
+    #[allow(clippy::too_many_arguments)]
     fn deserialize(
         hash_agg_node: &HashAggNode,
         child: BoxedExecutor,
         task_id: TaskId,
         identity: String,
+        spill_group_count: usize,
+        spill_memory_bytes: usize,
+        metrics: Arc<BatchMetrics>,
     ) -> Result<BoxedExecutor> {
         let group_key_columns = hash_agg_node
             .get_group_key()
@@ -104,6 +127,9 @@ impl HashAggExecutorBuilder {
             schema: Schema { fields },
             task_id,
             identity,
+            spill_group_count,
+            spill_memory_bytes,
+            metrics,
         };
 
         Ok(HashAggExecutorBuilderDispatcher::dispatch_by_kind(
@@ -127,16 +153,25 @@ impl BoxedExecutorBuilder for HashAggExecutorBuilder {
         )?;
 
         let identity = source.plan_node().get_identity().clone();
+        let config = source.context().config();
         Self::deserialize(
             hash_agg_node,
             inputs.remove(0),
             source.task_id.clone(),
             identity,
+            config.hash_agg_spill_group_count,
+            config.hash_agg_spill_memory_bytes,
+            source.context().stats(),
         )
     }
 }
 
 /// `HashAggExecutor` implements the hash aggregate algorithm.
+///
+/// At most `spill_group_count` groups of `spill_memory_bytes` estimated bytes are kept in memory.
+/// Once exceeded, the input rows of the other groups are spilled to temporary files, partitioned by
+/// the hash of the group key. After the in-memory groups are output, each spilled partition is
+/// aggregated by another `HashAggExecutor`, which may spill again with the next bits of the hash.
 pub(crate) struct HashAggExecutor<K> {
     /// factories to construct aggregator for each groups
     agg_factories: Vec<AggStateFactory>,
@@ -148,6 +183,13 @@ pub(crate) struct HashAggExecutor<K> {
     group_key_types: Vec<DataType>,
     schema: Schema,
     identity: String,
+    /// The maximum number of groups kept in memory
+    spill_group_count: usize,
+    /// The maximum estimated bytes of the groups kept in memory
+    spill_memory_bytes: usize,
+    /// How many times the input rows have been spilled
+    spill_level: usize,
+    metrics: Arc<BatchMetrics>,
     _phantom: PhantomData<K>,
 }
 
@@ -160,6 +202,10 @@ impl<K> HashAggExecutor<K> {
             group_key_types: builder.group_key_types,
             schema: builder.schema,
             identity: builder.identity,
+            spill_group_count: builder.spill_group_count,
+            spill_memory_bytes: builder.spill_memory_bytes,
+            spill_level: 0,
+            metrics: builder.metrics,
             _phantom: PhantomData,
         }
     }
@@ -184,31 +230,78 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
     async fn do_execute(self: Box<Self>) {
         // hash map for each agg groups
         let mut groups = AggHashMap::<K>::default();
+        // The estimated bytes of `groups`. The heap allocations of the agg states are not counted.
+        let mut groups_bytes = 0;
+        let states_bytes = std::mem::size_of::<Vec<BoxedAggState>>()
+            + self.agg_factories.len() * std::mem::size_of::<BoxedAggState>();
+        // The spilled input rows of the groups not in `groups`, created on the first spill.
+        let mut spill_files: Option<Vec<SpillFile>> = None;
+        // The last level can't partition the rows any more, so it never spills.
+        let can_spill = self.spill_level < MAX_SPILL_LEVEL;
+        let child_schema = self.child.schema().clone();
 
         // consume all chunks to compute the agg result
         #[for_await]
         for chunk in self.child.execute() {
             let chunk = chunk?.compact()?;
             let keys = K::build(self.group_key_columns.as_slice(), &chunk)?;
+            let mut spilled = vec![false; chunk.capacity()];
             for (row_id, key) in keys.into_iter().enumerate() {
-                let mut err_flag = Ok(());
-                let states: &mut Vec<BoxedAggState> = groups.entry(key).or_insert_with(|| {
-                    self.agg_factories
-                        .iter()
-                        .map(AggStateFactory::create_agg_state)
-                        .collect::<Result<Vec<_>>>()
-                        .unwrap_or_else(|x| {
-                            err_flag = Err(x);
-                            vec![]
-                        })
-                });
-                err_flag?;
+                let full = can_spill
+                    && (groups.len() >= self.spill_group_count
+                        || groups_bytes >= self.spill_memory_bytes);
+                let states = match groups.entry(key) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(_) if full => {
+                        spilled[row_id] = true;
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        groups_bytes += entry.key().estimated_size() + states_bytes;
+                        entry.insert(
+                            self.agg_factories
+                                .iter()
+                                .map(AggStateFactory::create_agg_state)
+                                .collect::<Result<Vec<_>>>()?,
+                        )
+                    }
+                };
 
                 // TODO: currently not a vectorized implementation
                 states
                     .iter_mut()
                     .for_each(|state| state.update_single(&chunk, row_id).unwrap());
             }
+
+            if spilled.iter().any(|&spilled| spilled) {
+                if spill_files.is_none() {
+                    let mut files = Vec::with_capacity(SPILL_PARTITION_NUM);
+                    for _ in 0..SPILL_PARTITION_NUM {
+                        files.push(SpillFile::new().await?);
+                    }
+                    spill_files = Some(files);
+                }
+                let spill_files = spill_files.as_mut().unwrap();
+                let hash_codes =
+                    chunk.get_hash_values(&self.group_key_columns, CRC32FastBuilder)?;
+                let shift = self.spill_level * SPILL_PARTITION_BITS;
+                for (partition, spill_file) in spill_files.iter_mut().enumerate() {
+                    let visibility = spilled
+                        .iter()
+                        .zip_eq(&hash_codes)
+                        .map(|(&spilled, hash_code)| {
+                            spilled
+                                && (hash_code.hash_code() >> shift) as usize % SPILL_PARTITION_NUM
+                                    == partition
+                        })
+                        .collect();
+                    let partition_chunk = chunk.with_visibility(visibility).compact()?;
+                    if partition_chunk.cardinality() > 0 {
+                        let bytes = spill_file.write(&partition_chunk).await?;
+                        self.metrics.hash_agg_spill_bytes.inc_by(bytes as u64);
+                    }
+                }
+            }
         }
 
         // generate output data chunks
@@ -255,13 +348,123 @@ impl<K: HashKey + Send + Sync> HashAggExecutor<K> {
             let output = DataChunk::new(columns, array_len);
             yield output;
         }
+
+        // Aggregate the spilled partitions one by one.
+        for spill_file in spill_files.into_iter().flatten() {
+            if spill_file.is_empty() {
+                continue;
+            }
+            let partition_agg = Box::new(HashAggExecutor::<K> {
+                agg_factories: self.agg_factories.clone(),
+                group_key_columns: self.group_key_columns.clone(),
+                child: Box::new(SpilledExecutor {
+                    schema: child_schema.clone(),
+                    spill_file,
+                }),
+                group_key_types: self.group_key_types.clone(),
+                schema: self.schema.clone(),
+                identity: self.identity.clone(),
+                spill_group_count: self.spill_group_count,
+                spill_memory_bytes: self.spill_memory_bytes,
+                spill_level: self.spill_level + 1,
+                metrics: self.metrics.clone(),
+                _phantom: PhantomData,
+            });
+            #[for_await]
+            for chunk in partition_agg.execute() {
+                yield chunk?;
+            }
+        }
+    }
+}
+
+/// A temporary file holding the spilled chunks, which is deleted on drop.
+struct SpillFile {
+    file: File,
+    chunk_count: usize,
+}
+
+impl SpillFile {
+    async fn new() -> Result<Self> {
+        let file = tokio::task::spawn_blocking(tempfile::tempfile)
+            .await
+            .map_err(|e| anyhow!("failed to create spill file: {}", e))?
+            .map_err(|e| anyhow!("failed to create spill file: {}", e))?;
+        Ok(Self {
+            file: File::from_std(file),
+            chunk_count: 0,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.chunk_count == 0
+    }
+
+    /// Appends `chunk` to the file, and returns the number of bytes written.
+    async fn write(&mut self, chunk: &DataChunk) -> Result<usize> {
+        let encoded = chunk.to_protobuf().encode_to_vec();
+        let mut buf = Vec::with_capacity(encoded.len() + 4);
+        buf.extend_from_slice(&(encoded.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&encoded);
+        self.file
+            .write_all(&buf)
+            .await
+            .map_err(|e| anyhow!("failed to write spill file: {}", e))?;
+        self.chunk_count += 1;
+        Ok(buf.len())
+    }
+}
+
+/// Reads the spilled chunks of a partition back as the input of [`HashAggExecutor`].
+struct SpilledExecutor {
+    schema: Schema,
+    spill_file: SpillFile,
+}
+
+impl Executor for SpilledExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        "SpilledExecutor"
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl SpilledExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let SpillFile {
+            mut file,
+            chunk_count,
+        } = self.spill_file;
+        let read_err = |e: std::io::Error| anyhow!("failed to read spill file: {}", e);
+        file.flush().await.map_err(read_err)?;
+        file.seek(SeekFrom::Start(0)).await.map_err(read_err)?;
+        let mut reader = BufReader::new(file);
+        // Read the chunks written before from the beginning of the file.
+        for _ in 0..chunk_count {
+            let len = reader.read_u32_le().await.map_err(read_err)?;
+            let mut encoded = vec![0; len as usize];
+            reader.read_exact(&mut encoded).await.map_err(read_err)?;
+            let prost = ProstDataChunk::decode(encoded.as_slice())
+                .map_err(|e| anyhow!("failed to decode spilled chunk: {}", e))?;
+            yield DataChunk::from_protobuf(&prost)?;
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::Row;
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::test_prelude::DataChunkTestExt;
+    use risingwave_common::types::ScalarImpl;
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType as ProstDataType;
     use risingwave_pb::expr::agg_call::{Arg, Type};
@@ -324,6 +527,9 @@ mod tests {
             Box::new(src_exec),
             TaskId::default(),
             "HashAggExecutor".to_string(),
+            usize::MAX,
+            usize::MAX,
+            Arc::new(BatchMetrics::unused()),
         )
         .unwrap();
 
@@ -391,6 +597,9 @@ mod tests {
             Box::new(src_exec),
             TaskId::default(),
             "HashAggExecutor".to_string(),
+            usize::MAX,
+            usize::MAX,
+            Arc::new(BatchMetrics::unused()),
         )
         .unwrap();
         let schema = Schema {
@@ -406,4 +615,84 @@ mod tests {
         );
         diff_executor_output(actual_exec, Box::new(expect_exec)).await;
     }
+
+    #[tokio::test]
+    async fn execute_spilled() {
+        // Only one group is kept in memory, the others are spilled.
+        execute_spilled_with_budget(1, usize::MAX).await;
+        execute_spilled_with_budget(usize::MAX, 1).await;
+    }
+
+    async fn execute_spilled_with_budget(spill_group_count: usize, spill_memory_bytes: usize) {
+        let t32 = DataType::Int32;
+        let src_exec = MockExecutor::with_chunk(
+            DataChunk::from_pretty(
+                "i i
+                 0 1
+                 1 1
+                 2 1
+                 1 2
+                 3 1
+                 0 2
+                 2 3
+                 3 2",
+            ),
+            Schema {
+                fields: vec![Field::unnamed(t32.clone()), Field::unnamed(t32)],
+            },
+        );
+
+        let agg_call = AggCall {
+            r#type: Type::Sum as i32,
+            args: vec![Arg {
+                input: Some(InputRefExpr { column_idx: 1 }),
+                r#type: Some(ProstDataType {
+                    type_name: TypeName::Int32 as i32,
+                    ..Default::default()
+                }),
+            }],
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Int64 as i32,
+                ..Default::default()
+            }),
+            distinct: false,
+            order_by_fields: vec![],
+            filter: None,
+        };
+
+        let agg_prost = HashAggNode {
+            group_key: vec![0],
+            agg_calls: vec![agg_call],
+        };
+
+        let metrics = Arc::new(BatchMetrics::unused());
+        let actual_exec = HashAggExecutorBuilder::deserialize(
+            &agg_prost,
+            Box::new(src_exec),
+            TaskId::default(),
+            "HashAggExecutor".to_string(),
+            spill_group_count,
+            spill_memory_bytes,
+            metrics.clone(),
+        )
+        .unwrap();
+
+        let mut rows = vec![];
+        let mut stream = actual_exec.execute();
+        while let Some(chunk) = stream.next().await {
+            rows.extend(chunk.unwrap().rows().map(|row| row.to_owned_row()));
+        }
+        rows.sort();
+        assert_eq!(
+            rows,
+            [(0, 3), (1, 3), (2, 4), (3, 3)]
+                .into_iter()
+                .map(|(k, sum)| Row(vec![
+                    Some(ScalarImpl::Int32(k)),
+                    Some(ScalarImpl::Int64(sum))
+                ]))
+                .collect::<Vec<_>>()
+        );
+        assert!(metrics.hash_agg_spill_bytes.get() > 0);
+    }
 }
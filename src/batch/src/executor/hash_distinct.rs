@@ -0,0 +1,225 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::hash::{
+    calc_hash_key_kind, HashKey, HashKeyDispatcher, PrecomputedBuildHasher,
+};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+struct HashDistinctExecutorBuilderDispatcher;
+
+/// A dispatcher to help create specialized hash distinct executor.
+impl HashKeyDispatcher for HashDistinctExecutorBuilderDispatcher {
+    type Input = HashDistinctExecutorBuilder;
+    type Output = BoxedExecutor;
+
+    fn dispatch<K: HashKey>(input: HashDistinctExecutorBuilder) -> Self::Output {
+        Box::new(HashDistinctExecutor::<K>::new(input))
+    }
+}
+
+pub struct HashDistinctExecutorBuilder {
+    distinct_key: Vec<usize>,
+    child: BoxedExecutor,
+    schema: Schema,
+    identity: String,
+}
+
+impl HashDistinctExecutorBuilder {
+    fn build(distinct_key: Vec<usize>, child: BoxedExecutor, identity: String) -> BoxedExecutor {
+        let distinct_key_types = distinct_key
+            .iter()
+            .map(|&i| child.schema().fields[i].data_type())
+            .collect_vec();
+        let hash_key_kind = calc_hash_key_kind(&distinct_key_types);
+        let schema = Schema {
+            fields: distinct_key_types.into_iter().map(Field::unnamed).collect(),
+        };
+
+        let builder = HashDistinctExecutorBuilder {
+            distinct_key,
+            child,
+            schema,
+            identity,
+        };
+        HashDistinctExecutorBuilderDispatcher::dispatch_by_kind(hash_key_kind, builder)
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for HashDistinctExecutorBuilder {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+        mut inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        ensure!(
+            inputs.len() == 1,
+            "HashDistinctExecutor should have 1 child!"
+        );
+
+        let hash_distinct_node = try_match_expand!(
+            source.plan_node().get_node_body().unwrap(),
+            NodeBody::HashDistinct
+        )?;
+
+        let distinct_key = hash_distinct_node
+            .get_distinct_key()
+            .iter()
+            .map(|&x| x as usize)
+            .collect_vec();
+
+        Ok(Self::build(
+            distinct_key,
+            inputs.remove(0),
+            source.plan_node().get_identity().clone(),
+        ))
+    }
+}
+
+/// `HashDistinctExecutor` outputs the distinct rows of the `distinct_key` columns. The rows are
+/// emitted as soon as they are first seen, so unlike [`super::HashAggExecutor`], it doesn't need
+/// to consume the whole input before yielding anything.
+///
+/// Like `GROUP BY`, the nulls are not distinct from each other.
+pub(crate) struct HashDistinctExecutor<K> {
+    /// Column indexes of the rows to deduplicate
+    distinct_key: Vec<usize>,
+    child: BoxedExecutor,
+    schema: Schema,
+    identity: String,
+    _phantom: PhantomData<K>,
+}
+
+impl<K> HashDistinctExecutor<K> {
+    fn new(builder: HashDistinctExecutorBuilder) -> Self {
+        HashDistinctExecutor {
+            distinct_key: builder.distinct_key,
+            child: builder.child,
+            schema: builder.schema,
+            identity: builder.identity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: HashKey + Send + Sync> Executor for HashDistinctExecutor<K> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl<K: HashKey + Send + Sync> HashDistinctExecutor<K> {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        let mut seen = HashSet::<K, PrecomputedBuildHasher>::default();
+
+        #[for_await]
+        for chunk in self.child.execute() {
+            let chunk = chunk?.compact()?;
+            let keys = K::build(&self.distinct_key, &chunk)?;
+            // Only the rows seen for the first time are visible.
+            let visibility = keys.into_iter().map(|key| seen.insert(key)).collect();
+            let chunk = chunk
+                .with_visibility(visibility)
+                .reorder_columns(&self.distinct_key)
+                .compact()?;
+            if chunk.cardinality() > 0 {
+                yield chunk;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::DataChunkTestExt;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_hash_distinct() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Int32),
+                Field::unnamed(DataType::Float64),
+            ],
+        };
+        let mut child = MockExecutor::new(schema);
+        child.add(DataChunk::from_pretty(
+            "i i F
+             1 1 7.1
+             1 . 8.2
+             1 1 9.3
+             2 . 1.4",
+        ));
+        child.add(DataChunk::from_pretty(
+            "i i F
+             1 . 3.5
+             2 2 4.6
+             2 . 5.7",
+        ));
+
+        let executor =
+            HashDistinctExecutorBuilder::build(vec![0, 1], Box::new(child), "HashDistinct".into());
+        assert_eq!(
+            executor.schema().data_types(),
+            vec![DataType::Int32, DataType::Int32]
+        );
+
+        // The nulls are not distinct from each other.
+        let mut stream = executor.execute();
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i i
+                 1 1
+                 1 .
+                 2 ."
+            )
+        );
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i i
+                 2 2"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+}
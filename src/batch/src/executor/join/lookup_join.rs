@@ -25,6 +25,7 @@ use risingwave_common::types::{
     DataType, Datum, ParallelUnitId, ScalarImpl, ToOwnedDatum, VirtualNode,
 };
 use risingwave_common::util::chunk_coalesce::{DataChunkBuilder, SlicedDataChunk};
+use risingwave_common::util::epoch::INVALID_EPOCH;
 use risingwave_common::util::scan_range::ScanRange;
 use risingwave_common::util::worker_util::get_pu_to_worker_mapping;
 use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
@@ -92,6 +93,8 @@ pub struct ProbeSideSource<C> {
     epoch: u64,
     pu_to_worker_mapping: HashMap<ParallelUnitId, WorkerNode>,
     pu_to_scan_range_mapping: HashMap<ParallelUnitId, Vec<(ScanRange, VirtualNode)>>,
+    /// The number of rows in each chunk of the probe side scans. 0 for the default.
+    chunk_size: u32,
 }
 
 /// Used to build the executor for the probe side
@@ -141,6 +144,8 @@ impl<C: BatchTaskContext> ProbeSideSource<C> {
             column_ids: self.probe_side_column_ids.clone(),
             scan_ranges,
             vnode_bitmap: Some(vnode_bitmap.finish().to_protobuf()),
+            as_of_epoch: INVALID_EPOCH,
+            chunk_size: self.chunk_size,
         });
 
         Ok(row_seq_scan_node)
@@ -157,6 +162,7 @@ impl<C: BatchTaskContext> ProbeSideSource<C> {
                 root: Some(PlanNode {
                     children: vec![],
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: 0,
                     node_body: Some(self.create_row_seq_scan_node(id)?),
                 }),
                 exchange_info: Some(ExchangeInfo {
@@ -165,6 +171,7 @@ impl<C: BatchTaskContext> ProbeSideSource<C> {
                 }),
             }),
             epoch: self.epoch,
+            collect_actuals: false,
         };
 
         let prost_exchange_source = ProstExchangeSource {
@@ -241,11 +248,13 @@ impl<C: BatchTaskContext> ProbeSideSourceBuilder for ProbeSideSource<C> {
         let exchange_node = NodeBody::Exchange(ExchangeNode {
             sources,
             input_schema: self.probe_side_schema.to_prost(),
+            chunk_size: self.chunk_size,
         });
 
         let plan_node = PlanNode {
             children: vec![],
             identity: "LookupJoinExchangeExecutor".to_string(),
+            plan_node_id: 0,
             node_body: Some(exchange_node),
         };
 
@@ -669,6 +678,12 @@ impl BoxedExecutorBuilder for LookupJoinExecutorBuilder {
             epoch: source.epoch(),
             pu_to_worker_mapping: get_pu_to_worker_mapping(lookup_join_node.get_worker_nodes()),
             pu_to_scan_range_mapping: HashMap::new(),
+            chunk_size: lookup_join_node.chunk_size,
+        };
+
+        let chunk_builder = match lookup_join_node.chunk_size {
+            0 => DataChunkBuilder::with_default_size(original_schema.data_types()),
+            chunk_size => DataChunkBuilder::new(original_schema.data_types(), chunk_size as usize),
         };
 
         Ok(Box::new(LookupJoinExecutor {
@@ -680,7 +695,7 @@ impl BoxedExecutorBuilder for LookupJoinExecutorBuilder {
             probe_side_source,
             probe_side_key_types,
             probe_side_key_idxs,
-            chunk_builder: DataChunkBuilder::with_default_size(original_schema.data_types()),
+            chunk_builder,
             schema: actual_schema,
             output_indices,
             last_chunk: None,
@@ -783,10 +798,12 @@ mod tests {
             OrderPair {
                 column_idx: 0,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
             OrderPair {
                 column_idx: 1,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
         ];
 
@@ -12,7 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use futures::TryStreamExt;
 use futures_async_stream::try_stream;
 use itertools::{repeat_n, Itertools};
 use risingwave_common::array::data_chunk_iter::RowRef;
@@ -33,6 +32,11 @@ use crate::executor::{
 };
 use crate::task::BatchTaskContext;
 
+/// The maximum number of rows buffered from the left child. The left child is fully buffered and
+/// joined with every chunk of the right child, so the join is rejected when it's too large, instead
+/// of exhausting the memory.
+const MAX_LEFT_ROW_COUNT: usize = 1 << 22;
+
 /// Nested loop join executor.
 ///
 ///
@@ -40,6 +44,9 @@ use crate::task::BatchTaskContext;
 /// 1. Iterate tuple from left child.
 /// 2. Concatenated with right chunk, eval expression and get visibility bitmap
 /// 3. Create new chunk with visibility bitmap and yield to upper.
+///
+/// It's used for the joins without equal conditions, which can't be hash joined. The left child
+/// is buffered in memory, with at most [`MAX_LEFT_ROW_COUNT`] rows.
 pub struct NestedLoopJoinExecutor {
     /// Expression to eval join condition
     join_expr: BoxedExpression,
@@ -58,6 +65,8 @@ pub struct NestedLoopJoinExecutor {
     right_child: BoxedExecutor,
     /// Identity string of the executor
     identity: String,
+    /// The maximum number of rows buffered from the left child.
+    max_left_row_count: usize,
 }
 
 impl Executor for NestedLoopJoinExecutor {
@@ -83,7 +92,20 @@ impl NestedLoopJoinExecutor {
         let mut chunk_builder = DataChunkBuilder::with_default_size(data_types);
 
         // Cache the outputs of left child
-        let left = self.left_child.execute().try_collect().await?;
+        let mut left = vec![];
+        let mut left_row_count = 0;
+        #[for_await]
+        for chunk in self.left_child.execute() {
+            let chunk = chunk?;
+            left_row_count += chunk.cardinality();
+            ensure!(
+                left_row_count <= self.max_left_row_count,
+                "The left side of nested loop join has more than {} rows, which is too large to \
+                 be buffered in memory. Consider adding an equal condition to the join.",
+                self.max_left_row_count
+            );
+            left.push(chunk);
+        }
 
         // Get the joined stream
         let stream = match self.join_type {
@@ -222,6 +244,7 @@ impl NestedLoopJoinExecutor {
             left_child,
             right_child,
             identity,
+            max_left_row_count: MAX_LEFT_ROW_COUNT,
         }
     }
 }
@@ -486,6 +509,7 @@ impl NestedLoopJoinExecutor {
 }
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
     use risingwave_common::array::*;
     use risingwave_common::catalog::{Field, Schema};
     use risingwave_common::types::DataType;
@@ -496,12 +520,13 @@ mod tests {
     use crate::executor::join::nested_loop_join::NestedLoopJoinExecutor;
     use crate::executor::join::JoinType;
     use crate::executor::test_utils::{diff_executor_output, MockExecutor};
-    use crate::executor::BoxedExecutor;
+    use crate::executor::{BoxedExecutor, Executor};
 
     struct TestFixture {
         left_types: Vec<DataType>,
         right_types: Vec<DataType>,
         join_type: JoinType,
+        cond_type: Type,
     }
 
     /// Sql for creating test data:
@@ -525,6 +550,7 @@ mod tests {
                 left_types: vec![DataType::Int32, DataType::Float32],
                 right_types: vec![DataType::Int32, DataType::Float64],
                 join_type,
+                cond_type: Type::Equal,
             }
         }
 
@@ -593,6 +619,10 @@ mod tests {
         }
 
         fn create_join_executor(&self) -> BoxedExecutor {
+            Box::new(self.create_nested_loop_join_executor())
+        }
+
+        fn create_nested_loop_join_executor(&self) -> NestedLoopJoinExecutor {
             let join_type = self.join_type;
 
             let left_child = self.create_left_executor();
@@ -604,9 +634,9 @@ mod tests {
                 _ => vec![0, 1, 2, 3],
             };
 
-            Box::new(NestedLoopJoinExecutor::new(
+            NestedLoopJoinExecutor::new(
                 new_binary_expr(
-                    Type::Equal,
+                    self.cond_type,
                     DataType::Boolean,
                     Box::new(InputRefExpression::new(DataType::Int32, 0)),
                     Box::new(InputRefExpression::new(DataType::Int32, 2)),
@@ -616,7 +646,7 @@ mod tests {
                 left_child,
                 right_child,
                 "NestedLoopJoinExecutor".into(),
-            ))
+            )
         }
 
         async fn do_test(&self, expected: DataChunk) {
@@ -780,4 +810,40 @@ mod tests {
 
         test_fixture.do_test(expected_chunk).await;
     }
+
+    /// sql: select * from t1, t2 where t1.v1 > t2.v1
+    #[tokio::test]
+    async fn test_inner_join_non_equal() {
+        let test_fixture = TestFixture {
+            cond_type: Type::GreaterThan,
+            ..TestFixture::with_join_type(JoinType::Inner)
+        };
+
+        let expected_chunk = DataChunk::from_pretty(
+            "i f   i F
+             3 3.9 2 6.1
+             3 6.6 2 6.1
+             4 0.7 2 6.1
+             4 0.7 3 8.9
+             6 5.5 2 6.1
+             6 5.5 3 8.9
+             6 5.6 2 6.1
+             6 5.6 3 8.9
+             8 7.0 2 6.1
+             8 7.0 3 8.9
+             8 7.0 6 3.4",
+        );
+
+        test_fixture.do_test(expected_chunk).await;
+    }
+
+    #[tokio::test]
+    async fn test_left_too_large() {
+        let test_fixture = TestFixture::with_join_type(JoinType::Inner);
+        let mut join_executor = test_fixture.create_nested_loop_join_executor();
+        join_executor.max_left_row_count = 4;
+
+        let mut stream = Box::new(join_executor).execute();
+        assert!(stream.next().await.unwrap().is_err());
+    }
 }
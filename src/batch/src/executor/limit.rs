@@ -374,6 +374,32 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_limit_executor_offset_beyond_row_count() {
+        let col = create_column((0..18).map(Some).collect_vec().as_slice()).unwrap();
+        let schema = Schema {
+            fields: vec![Field::unnamed(DataType::Int32)],
+        };
+        let mut mock_executor = MockExecutor::new(schema);
+        DataChunk::rechunk(&[DataChunk::new(vec![col], 18)], 3)
+            .unwrap()
+            .into_iter()
+            .for_each(|x| mock_executor.add(x));
+        let limit_executor = Box::new(LimitExecutor {
+            child: Box::new(mock_executor),
+            limit: 5,
+            offset: 20,
+            identity: "LimitExecutor2".to_string(),
+        });
+
+        let mut cardinality = 0;
+        #[for_await]
+        for chunk in limit_executor.execute() {
+            cardinality += chunk.unwrap().cardinality();
+        }
+        assert_eq!(cardinality, 0);
+    }
+
     #[tokio::test]
     async fn test_limit_executor_with_visibility() {
         let tot_row = 6;
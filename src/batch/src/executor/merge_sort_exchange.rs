@@ -37,7 +37,7 @@ pub type MergeSortExchangeExecutor<C> = MergeSortExchangeExecutorImpl<DefaultCre
 /// `MergeSortExchangeExecutor2` takes inputs from multiple sources and
 /// The outputs of all the sources have been sorted in the same way.
 ///
-/// The size of the output is determined both by `K_PROCESSING_WINDOW_SIZE`.
+/// The size of the output is determined by `chunk_size`.
 pub struct MergeSortExchangeExecutorImpl<CS, C> {
     context: C,
     /// keeps one data chunk of each source if any
@@ -51,6 +51,8 @@ pub struct MergeSortExchangeExecutorImpl<CS, C> {
     schema: Schema,
     task_id: TaskId,
     identity: String,
+    /// The number of rows in each output chunk.
+    chunk_size: usize,
 }
 
 impl<CS: 'static + Send + CreateSource, C: BatchTaskContext> MergeSortExchangeExecutorImpl<CS, C> {
@@ -101,9 +103,9 @@ impl<CS: 'static + Send + CreateSource, C: BatchTaskContext> Executor
         self.do_execute()
     }
 }
-/// Everytime `execute` is called, it tries to produce a chunk of size
-/// `K_PROCESSING_WINDOW_SIZE`. It is possible that the chunk's size is smaller than the
-/// `K_PROCESSING_WINDOW_SIZE` as the executor runs out of input from `sources`.
+/// Everytime `execute` is called, it tries to produce a chunk of size `chunk_size`. It is possible
+/// that the chunk's size is smaller than the `chunk_size` as the executor runs out of input from
+/// `sources`.
 impl<CS: 'static + Send + CreateSource, C: BatchTaskContext> MergeSortExchangeExecutorImpl<CS, C> {
     #[try_stream(boxed, ok = DataChunk, error = RwError)]
     async fn do_execute(mut self: Box<Self>) {
@@ -127,17 +129,13 @@ impl<CS: 'static + Send + CreateSource, C: BatchTaskContext> MergeSortExchangeEx
         while !self.min_heap.is_empty() {
             // It is possible that we cannot produce this much as
             // we may run out of input data chunks from sources.
-            let mut want_to_produce = K_PROCESSING_WINDOW_SIZE;
+            let mut want_to_produce = self.chunk_size;
 
             let mut builders: Vec<_> = self
                 .schema()
                 .fields
                 .iter()
-                .map(|field| {
-                    field
-                        .data_type
-                        .create_array_builder(K_PROCESSING_WINDOW_SIZE)
-                })
+                .map(|field| field.data_type.create_array_builder(self.chunk_size))
                 .collect();
             let mut array_len = 0;
             while want_to_produce > 0 && !self.min_heap.is_empty() {
@@ -205,7 +203,10 @@ impl BoxedExecutorBuilder for MergeSortExchangeExecutorBuilder {
 
         let exchange_node = sort_merge_node.get_exchange()?;
         let proto_sources: Vec<ProstExchangeSource> = exchange_node.get_sources().to_vec();
-        let source_creators = vec![DefaultCreateSource {}; proto_sources.len()];
+        let source_creators = vec![
+            DefaultCreateSource::new(source.actuals_collector().cloned());
+            proto_sources.len()
+        ];
         ensure!(!exchange_node.get_sources().is_empty());
         let fields = exchange_node
             .get_input_schema()
@@ -225,6 +226,10 @@ impl BoxedExecutorBuilder for MergeSortExchangeExecutorBuilder {
             schema: Schema { fields },
             task_id: source.task_id.clone(),
             identity: source.plan_node().get_identity().clone(),
+            chunk_size: match exchange_node.chunk_size {
+                0 => K_PROCESSING_WINDOW_SIZE,
+                chunk_size => chunk_size as usize,
+            },
         }))
     }
 }
@@ -264,6 +269,7 @@ mod tests {
         let order_pairs = Arc::new(vec![OrderPair {
             column_idx: 0,
             order_type: OrderType::Ascending,
+            nulls_first: false,
         }]);
 
         let executor = Box::new(MergeSortExchangeExecutorImpl::<
@@ -282,6 +288,7 @@ mod tests {
             },
             task_id: TaskId::default(),
             identity: "MergeSortExchangeExecutor2".to_string(),
+            chunk_size: K_PROCESSING_WINDOW_SIZE,
         });
 
         let mut stream = executor.execute();
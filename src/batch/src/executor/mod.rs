@@ -17,6 +17,7 @@ mod expand;
 mod filter;
 mod generic_exchange;
 mod hash_agg;
+mod hash_distinct;
 mod hop_window;
 mod insert;
 mod join;
@@ -33,6 +34,7 @@ mod table_function;
 pub mod test_utils;
 mod top_n;
 mod trace;
+mod union;
 mod update;
 mod values;
 
@@ -43,6 +45,7 @@ pub use filter::*;
 use futures::stream::BoxStream;
 pub use generic_exchange::*;
 pub use hash_agg::*;
+pub use hash_distinct::*;
 pub use hop_window::*;
 pub use insert::*;
 pub use join::*;
@@ -62,6 +65,7 @@ pub use sort_agg::*;
 pub use table_function::*;
 pub use top_n::*;
 pub use trace::*;
+pub use union::*;
 pub use update::*;
 pub use values::*;
 
@@ -107,6 +111,8 @@ pub struct ExecutorBuilder<'a, C> {
     pub task_id: &'a TaskId,
     context: C,
     epoch: u64,
+    /// Collects the actuals of the executors if set, for `EXPLAIN ANALYZE`.
+    actuals_collector: Option<ExecutorActualsCollector>,
 }
 
 macro_rules! build_executor {
@@ -128,12 +134,28 @@ impl<'a, C: Clone> ExecutorBuilder<'a, C> {
             task_id,
             context,
             epoch,
+            actuals_collector: None,
         }
     }
 
+    #[must_use]
+    pub fn with_actuals_collector(mut self, collector: ExecutorActualsCollector) -> Self {
+        self.actuals_collector = Some(collector);
+        self
+    }
+
     #[must_use]
     pub fn clone_for_plan(&self, plan_node: &'a PlanNode) -> Self {
-        ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch)
+        let builder =
+            ExecutorBuilder::new(plan_node, self.task_id, self.context.clone(), self.epoch);
+        match &self.actuals_collector {
+            Some(collector) => builder.with_actuals_collector(collector.clone()),
+            None => builder,
+        }
+    }
+
+    pub fn actuals_collector(&self) -> Option<&ExecutorActualsCollector> {
+        self.actuals_collector.as_ref()
     }
 
     pub fn plan_node(&self) -> &PlanNode {
@@ -186,6 +208,7 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::HashJoin => HashJoinExecutorBuilder,
             NodeBody::SortMergeJoin => SortMergeJoinExecutor,
             NodeBody::HashAgg => HashAggExecutorBuilder,
+            NodeBody::HashDistinct => HashDistinctExecutorBuilder,
             NodeBody::MergeSortExchange => MergeSortExchangeExecutorBuilder,
             NodeBody::TableFunction => TableFunctionExecutorBuilder,
             NodeBody::HopWindow => HopWindowExecutor,
@@ -193,10 +216,17 @@ impl<'a, C: BatchTaskContext> ExecutorBuilder<'a, C> {
             NodeBody::Expand => ExpandExecutor,
             NodeBody::LookupJoin => LookupJoinExecutorBuilder,
             NodeBody::ProjectSet => ProjectSetExecutor,
+            NodeBody::Union => UnionExecutor,
         }
         .await?;
         let input_desc = real_executor.identity().to_string();
-        Ok(Box::new(TraceExecutor::new(real_executor, input_desc)) as BoxedExecutor)
+        let trace_executor = TraceExecutor::new(real_executor, input_desc);
+        let trace_executor = match &self.actuals_collector {
+            Some(collector) => trace_executor
+                .with_actuals_collector(self.plan_node.plan_node_id, collector.clone()),
+            None => trace_executor,
+        };
+        Ok(Box::new(trace_executor) as BoxedExecutor)
     }
 }
 
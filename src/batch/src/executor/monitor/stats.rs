@@ -13,11 +13,13 @@
 // limitations under the License.
 //
 use prometheus::{
-    exponential_buckets, histogram_opts, register_histogram_with_registry, Histogram, Registry,
+    exponential_buckets, histogram_opts, register_histogram_with_registry,
+    register_int_counter_with_registry, Histogram, IntCounter, Registry,
 };
 
 pub struct BatchMetrics {
     pub row_seq_scan_next_duration: Histogram,
+    pub hash_agg_spill_bytes: IntCounter,
 }
 
 impl BatchMetrics {
@@ -29,8 +31,16 @@ impl BatchMetrics {
         );
         let row_seq_scan_next_duration = register_histogram_with_registry!(opts, registry).unwrap();
 
+        let hash_agg_spill_bytes = register_int_counter_with_registry!(
+            "batch_hash_agg_spill_bytes",
+            "Total bytes of the input rows spilled to disk by hash agg",
+            registry
+        )
+        .unwrap();
+
         Self {
             row_seq_scan_next_duration,
+            hash_agg_spill_bytes,
         }
     }
 
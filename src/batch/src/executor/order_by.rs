@@ -305,10 +305,12 @@ mod tests {
             OrderPair {
                 column_idx: 1,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
             OrderPair {
                 column_idx: 0,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
         ];
 
@@ -362,10 +364,12 @@ mod tests {
             OrderPair {
                 column_idx: 1,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
             OrderPair {
                 column_idx: 0,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
         ];
         let order_by_executor = Box::new(OrderByExecutor::new(
@@ -418,10 +422,12 @@ mod tests {
             OrderPair {
                 column_idx: 1,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
             OrderPair {
                 column_idx: 0,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
         ];
         let order_by_executor = Box::new(OrderByExecutor::new(
@@ -23,6 +23,8 @@ use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{ColumnDesc, ColumnId, OrderedColumnDesc, Schema, TableId};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::{DataType, Datum, ScalarImpl};
+use risingwave_common::util::chunk_coalesce::MAX_CHUNK_SIZE;
+use risingwave_common::util::epoch::INVALID_EPOCH;
 use risingwave_common::util::select_all;
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
@@ -75,7 +77,7 @@ impl<S: StateStore> RowSeqScanExecutor<S> {
 pub struct RowSeqScanExecutorBuilder {}
 
 impl RowSeqScanExecutorBuilder {
-    // TODO: decide the chunk size for row seq scan
+    /// The chunk size used if the plan node doesn't give one.
     pub const DEFAULT_CHUNK_SIZE: usize = 1024;
 }
 
@@ -201,6 +203,23 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             None => Distribution::all_vnodes(dist_key_indices),
         };
 
+        let chunk_size = match seq_scan_node.chunk_size {
+            0 => RowSeqScanExecutorBuilder::DEFAULT_CHUNK_SIZE,
+            chunk_size => chunk_size as usize,
+        };
+        ensure!(
+            chunk_size <= MAX_CHUNK_SIZE,
+            "chunk size {} of row sequential scan exceeds the limit {}",
+            chunk_size,
+            MAX_CHUNK_SIZE
+        );
+
+        // Read the historical snapshot given by `AS OF` if any.
+        let epoch = match seq_scan_node.as_of_epoch {
+            INVALID_EPOCH => source.epoch,
+            as_of_epoch => as_of_epoch,
+        };
+
         dispatch_state_store!(source.context().try_get_state_store()?, state_store, {
             let batch_stats = source.context().stats();
             let table = StorageTable::new_partial(
@@ -215,11 +234,11 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             let keyspace = Keyspace::table_root(state_store.clone(), &table_id);
 
             if seq_scan_node.scan_ranges.is_empty() {
-                let iter = table.batch_dedup_pk_iter(source.epoch, &pk_descs).await?;
+                let iter = table.batch_dedup_pk_iter(epoch, &pk_descs).await?;
                 return Ok(Box::new(RowSeqScanExecutor::new(
                     table.schema().clone(),
                     vec![ScanType::TableScan(iter)],
-                    RowSeqScanExecutorBuilder::DEFAULT_CHUNK_SIZE,
+                    chunk_size,
                     source.plan_node().get_identity().clone(),
                     batch_stats,
                 )));
@@ -240,18 +259,14 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
                             unreachable!()
                         } else if pk_prefix_value.size() == pk_len {
                             let row = {
-                                keyspace.state_store().wait_epoch(source.epoch).await?;
-                                table.get_row(&pk_prefix_value, source.epoch).await?
+                                keyspace.state_store().wait_epoch(epoch).await?;
+                                table.get_row(&pk_prefix_value, epoch).await?
                             };
                             ScanType::PointGet(row)
                         } else {
                             assert!(pk_prefix_value.size() < pk_len);
                             let iter = table
-                                .batch_iter_with_pk_bounds(
-                                    source.epoch,
-                                    &pk_prefix_value,
-                                    next_col_bounds,
-                                )
+                                .batch_iter_with_pk_bounds(epoch, &pk_prefix_value, next_col_bounds)
                                 .await?;
                             ScanType::RangeScan(iter)
                         };
@@ -266,7 +281,7 @@ impl BoxedExecutorBuilder for RowSeqScanExecutorBuilder {
             Ok(Box::new(RowSeqScanExecutor::new(
                 table.schema().clone(),
                 scan_types?,
-                RowSeqScanExecutorBuilder::DEFAULT_CHUNK_SIZE,
+                chunk_size,
                 source.plan_node().get_identity().clone(),
                 batch_stats,
             )))
@@ -214,10 +214,12 @@ mod tests {
             OrderPair {
                 column_idx: 1,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
             OrderPair {
                 column_idx: 0,
                 order_type: OrderType::Ascending,
+                nulls_first: false,
             },
         ];
         let top_n_executor = Box::new(TopNExecutor::new(
@@ -12,27 +12,103 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use futures::stream::StreamExt;
 use futures_async_stream::try_stream;
+use parking_lot::Mutex;
 use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::RwError;
+use risingwave_pb::task_service::ExecutorActuals as ProstExecutorActuals;
 use tracing::event;
 use tracing_futures::Instrument;
 
 use crate::executor::{BoxedDataChunkStream, BoxedExecutor, Executor};
 
+/// The rows produced by an executor and the time spent in it, including the time spent in its
+/// inputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExecutorActuals {
+    pub rows: usize,
+    pub elapsed: Duration,
+}
+
+/// Collects the [`ExecutorActuals`] of the executors built from the plan nodes, by plan node id.
+/// Used by `EXPLAIN ANALYZE`.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutorActualsCollector {
+    inner: Arc<Mutex<HashMap<i32, ExecutorActuals>>>,
+}
+
+impl ExecutorActualsCollector {
+    fn add(&self, plan_node_id: i32, rows: usize, elapsed: Duration) {
+        let mut inner = self.inner.lock();
+        let actuals = inner.entry(plan_node_id).or_default();
+        actuals.rows += rows;
+        actuals.elapsed += elapsed;
+    }
+
+    /// Returns the actuals of the plan node, or `None` if it's not executed in this process or
+    /// reported by a remote task.
+    pub fn get(&self, plan_node_id: i32) -> Option<ExecutorActuals> {
+        self.inner.lock().get(&plan_node_id).copied()
+    }
+
+    /// Adds the actuals reported by a remote task. The elapsed time of a plan node running in
+    /// several tasks is summed up, so it may exceed the wall-clock time of the query.
+    pub fn merge_protobuf(&self, actuals: &[ProstExecutorActuals]) {
+        for actuals in actuals {
+            self.add(
+                actuals.plan_node_id,
+                actuals.rows as usize,
+                Duration::from_nanos(actuals.elapsed_nanos),
+            );
+        }
+    }
+
+    pub fn to_protobuf(&self) -> Vec<ProstExecutorActuals> {
+        self.inner
+            .lock()
+            .iter()
+            .map(|(plan_node_id, actuals)| ProstExecutorActuals {
+                plan_node_id: *plan_node_id,
+                rows: actuals.rows as u64,
+                elapsed_nanos: actuals.elapsed.as_nanos() as u64,
+            })
+            .collect()
+    }
+}
+
 /// If tracing is enabled, we build a [`TraceExecutor`] on top of the underlying executor.
 /// So the duration of performance-critical operations will be traced, such as open/next/close.
 pub struct TraceExecutor {
     child: BoxedExecutor,
     /// Description of input executor
     input_desc: String,
+    /// Where to record the actuals of the executor, with its plan node id
+    actuals: Option<(i32, ExecutorActualsCollector)>,
 }
 
 impl TraceExecutor {
     pub fn new(child: BoxedExecutor, input_desc: String) -> Self {
-        Self { child, input_desc }
+        Self {
+            child,
+            input_desc,
+            actuals: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_actuals_collector(
+        mut self,
+        plan_node_id: i32,
+        collector: ExecutorActualsCollector,
+    ) -> Self {
+        self.actuals = Some((plan_node_id, collector));
+        self
     }
 }
 
@@ -56,19 +132,75 @@ impl TraceExecutor {
         let input_desc = self.input_desc.as_str();
         let span_name = format!("{input_desc}_next");
         let mut child_stream = self.child.execute();
-        while let Some(chunk) = child_stream
-            .next()
-            .instrument(tracing::trace_span!(
-                "next",
-                otel.name = span_name.as_str(),
-                next = input_desc,
-            ))
-            .await
-        {
-            let chunk = chunk?;
+        loop {
+            let start_time = Instant::now();
+            let chunk = child_stream
+                .next()
+                .instrument(tracing::trace_span!(
+                    "next",
+                    otel.name = span_name.as_str(),
+                    next = input_desc,
+                ))
+                .await;
+            // Recorded on each call, as the stream may not be polled to the end, e.g. under a
+            // limit.
+            if let Some((plan_node_id, collector)) = &self.actuals {
+                let rows = match &chunk {
+                    Some(Ok(chunk)) => chunk.cardinality(),
+                    _ => 0,
+                };
+                collector.add(*plan_node_id, rows, start_time.elapsed());
+            }
+            let chunk = match chunk {
+                Some(chunk) => chunk?,
+                None => break,
+            };
             event!(tracing::Level::TRACE, prev = %input_desc, msg = "chunk", "input = \n{:#?}", 
                 chunk);
             yield chunk;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::{DataChunk, DataChunkTestExt};
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    #[tokio::test]
+    async fn test_trace_executor_actuals() {
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int32)]);
+        let mut mock_executor = MockExecutor::new(schema);
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             1
+             2",
+        ));
+        mock_executor.add(DataChunk::from_pretty(
+            "i
+             3",
+        ));
+
+        let collector = ExecutorActualsCollector::default();
+        let trace_executor = TraceExecutor::new(Box::new(mock_executor), "Mock".to_string())
+            .with_actuals_collector(1, collector.clone());
+        let mut stream = Box::new(trace_executor).execute();
+        while let Some(chunk) = stream.next().await {
+            chunk.unwrap();
+        }
+
+        assert_eq!(collector.get(1).unwrap().rows, 3);
+        assert_eq!(collector.get(2), None);
+
+        // The actuals reported by another task are added up.
+        let remote = ExecutorActualsCollector::default();
+        remote.merge_protobuf(&collector.to_protobuf());
+        remote.merge_protobuf(&collector.to_protobuf());
+        assert_eq!(remote.get(1).unwrap().rows, 6);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use futures_async_stream::try_stream;
+use risingwave_common::array::DataChunk;
+use risingwave_common::catalog::Schema;
+use risingwave_common::error::{Result, RwError};
+use risingwave_pb::batch_plan::plan_node::NodeBody;
+
+use crate::executor::{
+    BoxedDataChunkStream, BoxedExecutor, BoxedExecutorBuilder, Executor, ExecutorBuilder,
+};
+use crate::task::BatchTaskContext;
+
+/// Union executor, which yields the chunks of all inputs one by one, i.e. `UNION ALL`.
+///
+/// TODO: it's only reachable by a hand-written plan for now. The binder rejects set operations,
+/// and there is no `LogicalUnion` to plan them into a `UnionNode` yet.
+pub struct UnionExecutor {
+    inputs: Vec<BoxedExecutor>,
+    schema: Schema,
+    identity: String,
+}
+
+impl UnionExecutor {
+    pub fn new(inputs: Vec<BoxedExecutor>, identity: String) -> Result<Self> {
+        ensure!(
+            !inputs.is_empty(),
+            "UnionExecutor should have at least 1 child!"
+        );
+        let schema = inputs[0].schema().clone();
+        for input in &inputs[1..] {
+            ensure!(
+                input.schema().data_types() == schema.data_types(),
+                "The inputs of UnionExecutor have different schemas: {:?} and {:?}",
+                schema.data_types(),
+                input.schema().data_types()
+            );
+        }
+        Ok(Self {
+            inputs,
+            schema,
+            identity,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl BoxedExecutorBuilder for UnionExecutor {
+    async fn new_boxed_executor<C: BatchTaskContext>(
+        source: &ExecutorBuilder<C>,
+        inputs: Vec<BoxedExecutor>,
+    ) -> Result<BoxedExecutor> {
+        let _union_node =
+            try_match_expand!(source.plan_node().get_node_body().unwrap(), NodeBody::Union)?;
+
+        Ok(Box::new(Self::new(
+            inputs,
+            source.plan_node().get_identity().clone(),
+        )?))
+    }
+}
+
+impl Executor for UnionExecutor {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    fn execute(self: Box<Self>) -> BoxedDataChunkStream {
+        self.do_execute()
+    }
+}
+
+impl UnionExecutor {
+    #[try_stream(boxed, ok = DataChunk, error = RwError)]
+    async fn do_execute(self: Box<Self>) {
+        for input in self.inputs {
+            #[for_await]
+            for data_chunk in input.execute() {
+                let data_chunk = data_chunk?;
+                // The inputs should never yield empty chunks, but skip them anyway to keep the
+                // invariant of `Executor::execute`.
+                if data_chunk.cardinality() > 0 {
+                    yield data_chunk;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use risingwave_common::array::DataChunkTestExt;
+    use risingwave_common::catalog::Field;
+    use risingwave_common::types::DataType;
+
+    use super::*;
+    use crate::executor::test_utils::MockExecutor;
+
+    fn mock_executor(data_types: &[DataType], chunks: &[&str]) -> BoxedExecutor {
+        let schema = Schema::new(data_types.iter().cloned().map(Field::unnamed).collect());
+        let mut executor = MockExecutor::new(schema);
+        for chunk in chunks {
+            executor.add(DataChunk::from_pretty(chunk));
+        }
+        Box::new(executor)
+    }
+
+    #[tokio::test]
+    async fn test_union() {
+        let data_types = [DataType::Int32, DataType::Float32];
+        let left = mock_executor(
+            &data_types,
+            &[
+                "i f
+                 1 6.1
+                 2 8.4",
+                "i f
+                 3 3.9",
+            ],
+        );
+        let right = mock_executor(
+            &data_types,
+            &["i f
+               4 0.7
+               5 5.5"],
+        );
+
+        let union_executor =
+            Box::new(UnionExecutor::new(vec![left, right], "UnionExecutor".to_string()).unwrap());
+        assert_eq!(union_executor.schema().data_types(), data_types);
+
+        let mut stream = union_executor.execute();
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i f
+                 1 6.1
+                 2 8.4"
+            )
+        );
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i f
+                 3 3.9"
+            )
+        );
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            DataChunk::from_pretty(
+                "i f
+                 4 0.7
+                 5 5.5"
+            )
+        );
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn test_union_mismatched_schema() {
+        let left = mock_executor(&[DataType::Int32], &[]);
+        let right = mock_executor(&[DataType::Int64], &[]);
+        assert!(UnionExecutor::new(vec![left, right], "UnionExecutor".to_string()).is_err());
+    }
+}
@@ -24,6 +24,7 @@ use risingwave_pb::task_service::{
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
+use crate::executor::ExecutorActualsCollector;
 use crate::rpc::service::exchange::GrpcExchangeWriter;
 use crate::task::{BatchEnvironment, BatchManager, BatchTaskExecution, ComputeNodeContext};
 
@@ -104,6 +105,7 @@ impl TaskService for BatchServiceImpl {
             task_id,
             plan,
             epoch,
+            collect_actuals,
         } = req.into_inner();
         let task_id = task_id.expect("no task id found");
         let plan = plan.expect("no plan found").clone();
@@ -113,7 +115,11 @@ impl TaskService for BatchServiceImpl {
             plan,
             task_id
         );
-        let task = BatchTaskExecution::new(&task_id, plan, context, epoch)?;
+        let mut task = BatchTaskExecution::new(&task_id, plan, context, epoch)?;
+        let actuals_collector = collect_actuals.then(ExecutorActualsCollector::default);
+        if let Some(collector) = &actuals_collector {
+            task = task.with_actuals_collector(collector.clone());
+        }
         let task = Arc::new(task);
 
         if let Err(e) = task.clone().async_execute().await {
@@ -141,6 +147,17 @@ impl TaskService for BatchServiceImpl {
         let (tx, rx) = tokio::sync::mpsc::channel(LOCAL_EXECUTE_BUFFER_SIZE);
         let mut writer = GrpcExchangeWriter::new(tx.clone());
         output.take_data(&mut writer).await?;
+        // Send the actuals after the last chunk, as the executors have all finished by now.
+        if let Some(collector) = actuals_collector {
+            let resp = GetDataResponse {
+                status: Default::default(),
+                record_batch: None,
+                actuals: collector.to_protobuf(),
+            };
+            if tx.send(Ok(resp)).await.is_err() {
+                warn!("failed to send the actuals of Task {:?}", task_id);
+            }
+        }
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 }
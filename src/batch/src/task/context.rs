@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use risingwave_common::catalog::SysCatalogReaderRef;
+use risingwave_common::config::BatchConfig;
 use risingwave_common::error::ErrorCode::InternalError;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
@@ -62,6 +63,8 @@ pub trait BatchTaskContext: Clone + Send + Sync + 'static {
     }
 
     fn stats(&self) -> Arc<BatchMetrics>;
+
+    fn config(&self) -> BatchConfig;
 }
 
 /// Batch task context on compute node.
@@ -96,6 +99,10 @@ impl BatchTaskContext for ComputeNodeContext {
     fn stats(&self) -> Arc<BatchMetrics> {
         self.env.stats()
     }
+
+    fn config(&self) -> BatchConfig {
+        self.env.config().clone()
+    }
 }
 
 impl ComputeNodeContext {
@@ -30,7 +30,7 @@ use tokio::sync::oneshot::{Receiver, Sender};
 use tracing_futures::Instrument;
 
 use crate::error::BatchError;
-use crate::executor::{BoxedExecutor, ExecutorBuilder};
+use crate::executor::{BoxedExecutor, ExecutorActualsCollector, ExecutorBuilder};
 use crate::rpc::service::exchange::ExchangeWriter;
 use crate::task::channel::{create_output_channel, ChanReceiverImpl, ChanSenderImpl};
 use crate::task::BatchTaskContext;
@@ -128,6 +128,7 @@ impl TaskOutput {
                     let resp = GetDataResponse {
                         status: Default::default(),
                         record_batch: Some(pb),
+                        actuals: vec![],
                     };
                     writer.write(resp).await?;
                 }
@@ -185,6 +186,9 @@ pub struct BatchTaskExecution<C> {
     shutdown_tx: Mutex<Option<Sender<u64>>>,
 
     epoch: u64,
+
+    /// Where to record the actuals of the executors, for `EXPLAIN ANALYZE`.
+    actuals_collector: Option<ExecutorActualsCollector>,
 }
 
 impl<C: BatchTaskContext> BatchTaskExecution<C> {
@@ -203,9 +207,16 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
             failure: Arc::new(Mutex::new(None)),
             epoch,
             shutdown_tx: Mutex::new(None),
+            actuals_collector: None,
         })
     }
 
+    #[must_use]
+    pub fn with_actuals_collector(mut self, collector: ExecutorActualsCollector) -> Self {
+        self.actuals_collector = Some(collector);
+        self
+    }
+
     pub fn get_task_id(&self) -> &TaskId {
         &self.task_id
     }
@@ -224,17 +235,17 @@ impl<C: BatchTaskContext> BatchTaskExecution<C> {
         );
         *self.state.lock() = TaskStatus::Running;
 
+        let mut builder = ExecutorBuilder::new(
+            self.plan.root.as_ref().unwrap(),
+            &self.task_id,
+            self.context.clone(),
+            self.epoch,
+        );
+        if let Some(collector) = &self.actuals_collector {
+            builder = builder.with_actuals_collector(collector.clone());
+        }
         let exec = DEBUG_CONTEXT
-            .scope(
-                DebugContext::BatchQuery,
-                ExecutorBuilder::new(
-                    self.plan.root.as_ref().unwrap(),
-                    &self.task_id,
-                    self.context.clone(),
-                    self.epoch,
-                )
-                .build(),
-            )
+            .scope(DebugContext::BatchQuery, builder.build())
             .await?;
 
         let (sender, receivers) = create_output_channel(self.plan.get_exchange_info()?)?;
@@ -224,6 +224,7 @@ mod tests {
             root: Some(PlanNode {
                 children: vec![],
                 identity: "".to_string(),
+                plan_node_id: 0,
                 node_body: Some(NodeBody::Values(ValuesNode {
                     tuples: vec![],
                     fields: vec![],
@@ -260,6 +261,7 @@ mod tests {
             root: Some(PlanNode {
                 children: vec![],
                 identity: "".to_string(),
+                plan_node_id: 0,
                 node_body: Some(NodeBody::TableFunction(TableFunctionNode {
                     table_function: Some(TableFunction {
                         function_type: Type::Generate as i32,
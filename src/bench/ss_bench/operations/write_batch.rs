@@ -99,7 +99,13 @@ impl Operations {
         if opts.compact_level_after_write > 0 {
             if let Some((compact_context, local_version_manager)) = context {
                 if let Some(task) = self.meta_client.get_compact_task().await {
-                    Compactor::compact(compact_context.clone(), task).await;
+                    Compactor::compact(
+                        compact_context.clone(),
+                        task,
+                        Arc::default(),
+                        tokio::sync::oneshot::channel().1,
+                    )
+                    .await;
                     // FIXME: A workaround to ensure the version after compaction is available
                     // locally. Notice now multiple tasks are trying to pin_version, which breaks
                     // the assumption required by LocalVersionManager. It may result in some pinned
@@ -223,6 +223,14 @@ impl DataChunk {
         &self.columns
     }
 
+    /// The estimated number of bytes of the columns, including the invisible rows.
+    pub fn estimated_size(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| column.array_ref().estimated_size())
+            .sum()
+    }
+
     pub fn to_protobuf(&self) -> ProstDataChunk {
         assert!(
             matches!(self.vis2, Vis::Compact(_)),
@@ -172,6 +172,12 @@ pub trait Array: std::fmt::Debug + Send + Sync + Sized + 'static + Into<ArrayImp
     /// Serialize to protobuf
     fn to_protobuf(&self) -> ProstArray;
 
+    /// The estimated number of bytes of the values and the null bitmap of the array. It's cheap
+    /// to compute, and is exact for the arrays of fixed-size values.
+    fn estimated_size(&self) -> usize {
+        self.len() * std::mem::size_of::<Self::OwnedItem>() + self.null_bitmap().len() / 8
+    }
+
     /// Get the null `Bitmap` from `Array`.
     fn null_bitmap(&self) -> &Bitmap;
 
@@ -485,6 +491,13 @@ macro_rules! impl_array {
                 }
             }
 
+            /// The estimated number of bytes of the array, see [`Array::estimated_size`].
+            pub fn estimated_size(&self) -> usize {
+                match self {
+                    $( Self::$variant_name(inner) => inner.estimated_size(), )*
+                }
+            }
+
             pub fn to_protobuf(&self) -> ProstArray {
                 match self {
                     $( Self::$variant_name(inner) => inner.to_protobuf(), )*
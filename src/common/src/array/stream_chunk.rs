@@ -142,6 +142,11 @@ impl StreamChunk {
         self.data.columns()
     }
 
+    /// The estimated number of bytes of the ops and the columns, including the invisible rows.
+    pub fn estimated_size(&self) -> usize {
+        self.ops.len() * std::mem::size_of::<Op>() + self.data.estimated_size()
+    }
+
     pub fn column_at(&self, index: usize) -> &Column {
         self.data.column_at(index)
     }
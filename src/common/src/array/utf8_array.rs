@@ -61,6 +61,10 @@ impl Array for Utf8Array {
         self.offset.len() - 1
     }
 
+    fn estimated_size(&self) -> usize {
+        self.data.len() + self.offset.len() * size_of::<usize>() + self.bitmap.len() / 8
+    }
+
     fn iter(&self) -> ArrayIterator<'_, Self> {
         ArrayIterator::new(self)
     }
@@ -319,6 +323,12 @@ mod tests {
         builder.finish().unwrap();
     }
 
+    #[test]
+    fn test_utf8_estimated_size() {
+        let array = Utf8Array::from_slice(&[Some("foo"), None, Some("barbaz")]).unwrap();
+        assert_eq!(array.estimated_size(), 9 + 4 * size_of::<usize>());
+    }
+
     #[test]
     fn test_utf8_partial_writer() -> Result<()> {
         let builder = Utf8ArrayBuilder::new(0);
@@ -76,6 +76,15 @@ impl Default for ServerConfig {
 pub struct BatchConfig {
     // #[serde(default = "default::chunk_size")]
     // pub chunk_size: u32,
+    /// The number of groups a hash agg keeps in memory. Once exceeded, the input rows of the other
+    /// groups are spilled to disk and aggregated later.
+    #[serde(default = "default::hash_agg_spill_group_count")]
+    pub hash_agg_spill_group_count: usize,
+
+    /// The estimated bytes of the groups a hash agg keeps in memory. Once exceeded, the input rows
+    /// of the other groups are spilled to disk and aggregated later.
+    #[serde(default = "default::hash_agg_spill_memory_bytes")]
+    pub hash_agg_spill_memory_bytes: usize,
 }
 
 impl Default for BatchConfig {
@@ -95,8 +104,26 @@ pub struct StreamingConfig {
     #[serde(default = "default::in_flight_barrier_nums")]
     pub in_flight_barrier_nums: usize,
 
+    /// The number of barriers between two checkpoints, i.e., data is synced and committed every
+    /// `checkpoint_frequency` barriers.
+    #[serde(default = "default::checkpoint_frequency")]
+    pub checkpoint_frequency: usize,
+
     #[serde(default = "default::worker_node_parallelism")]
     pub worker_node_parallelism: usize,
+
+    /// The number of rows read from the snapshot in each batch when backfilling a new mview.
+    /// Barriers can only be processed between the batches, so a smaller batch keeps the creation
+    /// more responsive, at the cost of more snapshot reads.
+    #[serde(default = "default::backfill_batch_size")]
+    pub backfill_batch_size: usize,
+
+    /// The capacity of the channels between the actors, in messages. A larger buffer reduces the
+    /// context switches of high-throughput actors, while a smaller one saves memory when there are
+    /// many channels on a node. See the `stream_actor_output_buffer_fullness` metric for tuning.
+    /// Must be positive.
+    #[serde(default = "default::local_output_channel_size")]
+    pub local_output_channel_size: usize,
 }
 
 impl Default for StreamingConfig {
@@ -171,6 +198,15 @@ pub struct StorageConfig {
     /// Capacity of sstable meta cache.
     #[serde(default = "default::compactor_memory_limit_mb")]
     pub compactor_memory_limit_mb: usize,
+
+    /// Whether to verify the checksum of each block read from the object store. Disabling it saves
+    /// some CPU, but corrupted blocks are no longer detected.
+    #[serde(default = "default::verify_block_checksum")]
+    pub verify_block_checksum: bool,
+
+    /// Number of SSTs an iterator opens concurrently when it's created.
+    #[serde(default = "default::iter_open_sstable_concurrency")]
+    pub iter_open_sstable_concurrency: usize,
 }
 
 impl Default for StorageConfig {
@@ -286,10 +322,30 @@ mod default {
         40
     }
 
+    pub fn checkpoint_frequency() -> usize {
+        1
+    }
+
     pub fn share_buffer_upload_concurrency() -> usize {
         8
     }
 
+    pub fn hash_agg_spill_group_count() -> usize {
+        1 << 20
+    }
+
+    pub fn hash_agg_spill_memory_bytes() -> usize {
+        256 << 20
+    }
+
+    pub fn backfill_batch_size() -> usize {
+        1024
+    }
+
+    pub fn local_output_channel_size() -> usize {
+        16
+    }
+
     pub fn worker_node_parallelism() -> usize {
         num_cpus::get()
     }
@@ -297,6 +353,14 @@ mod default {
     pub fn compactor_memory_limit_mb() -> usize {
         512
     }
+
+    pub fn verify_block_checksum() -> bool {
+        true
+    }
+
+    pub fn iter_open_sstable_concurrency() -> usize {
+        16
+    }
 }
 
 pub mod constant {
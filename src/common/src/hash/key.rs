@@ -27,7 +27,7 @@ use crate::array::{
 };
 use crate::types::{
     DataType, Datum, Decimal, IntervalUnit, NaiveDateTimeWrapper, NaiveDateWrapper,
-    NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarRef, ToOwnedDatum, VirtualNode,
+    NaiveTimeWrapper, OrderedF32, OrderedF64, ScalarImpl, ScalarRef, ToOwnedDatum, VirtualNode,
     VIRTUAL_NODE_COUNT,
 };
 use crate::util::hash_util::CRC32FastBuilder;
@@ -143,6 +143,11 @@ pub trait HashKey: Clone + Debug + Hash + Eq + Sized + Send + Sync + 'static {
     fn deserialize_to_builders(self, array_builders: &mut [ArrayBuilderImpl]) -> ArrayResult<()>;
 
     fn has_null(&self) -> bool;
+
+    /// The estimated number of bytes the key takes in memory, including its heap allocations.
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
 }
 
 /// Designed for hash keys with at most `N` serialized bytes.
@@ -655,6 +660,22 @@ impl HashKey for SerializedKey {
     fn has_null(&self) -> bool {
         self.has_null
     }
+
+    /// Only the strings are counted for the heap allocations of the datums.
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self
+                .key
+                .iter()
+                .map(|datum| {
+                    std::mem::size_of::<Datum>()
+                        + match datum {
+                            Some(ScalarImpl::Utf8(s)) => s.len(),
+                            _ => 0,
+                        }
+                })
+                .sum::<usize>()
+    }
 }
 
 #[cfg(test)]
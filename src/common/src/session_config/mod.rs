@@ -19,10 +19,11 @@ use std::str::FromStr;
 pub use query_mode::QueryMode;
 
 use crate::error::{ErrorCode, RwError};
+use crate::util::chunk_coalesce::MAX_CHUNK_SIZE;
 
 // This is a hack, &'static str is not allowed as a const generics argument.
 // TODO: refine this using the adt_const_params feature.
-const CONFIG_KEYS: [&str; 7] = [
+const CONFIG_KEYS: [&str; 12] = [
     "RW_IMPLICIT_FLUSH",
     "QUERY_MODE",
     "RW_FORCE_DELTA_JOIN",
@@ -30,6 +31,11 @@ const CONFIG_KEYS: [&str; 7] = [
     "APPLICATION_NAME",
     "DATE_STYLE",
     "RW_BATCH_ENABLE_LOOKUP_JOIN",
+    "RW_STREAMING_ENABLE_REARRANGED_CHAIN",
+    "RW_STREAMING_ENABLE_SALTED_AGG",
+    "RW_STREAMING_JOIN_STATE_RETENTION",
+    "RW_BATCH_CHUNK_SIZE",
+    "RW_EXPLAIN_ANALYZE_TIMING",
 ];
 const IMPLICIT_FLUSH: usize = 0;
 const QUERY_MODE: usize = 1;
@@ -38,6 +44,11 @@ const EXTRA_FLOAT_DIGITS: usize = 3;
 const APPLICATION_NAME: usize = 4;
 const DATE_STYLE: usize = 5;
 const BATCH_ENABLE_LOOKUP_JOIN: usize = 6;
+const STREAMING_ENABLE_REARRANGED_CHAIN: usize = 7;
+const STREAMING_ENABLE_SALTED_AGG: usize = 8;
+const STREAMING_JOIN_STATE_RETENTION: usize = 9;
+const BATCH_CHUNK_SIZE: usize = 10;
+const EXPLAIN_ANALYZE_TIMING: usize = 11;
 
 trait ConfigEntry: Default + FromStr<Err = RwError> {
     fn entry_name() -> &'static str;
@@ -157,6 +168,11 @@ type ExtraFloatDigit = ConfigI32<EXTRA_FLOAT_DIGITS, 1>;
 // TODO: We should use more specified type here.
 type DateStyle = ConfigString<DATE_STYLE>;
 type BatchEnableLookupJoin = ConfigBool<BATCH_ENABLE_LOOKUP_JOIN, false>;
+type StreamingEnableRearrangedChain = ConfigBool<STREAMING_ENABLE_REARRANGED_CHAIN, true>;
+type StreamingEnableSaltedAgg = ConfigBool<STREAMING_ENABLE_SALTED_AGG, false>;
+type StreamingJoinStateRetention = ConfigI32<STREAMING_JOIN_STATE_RETENTION, -1>;
+type BatchChunkSize = ConfigI32<BATCH_CHUNK_SIZE, 1024>;
+type ExplainAnalyzeTiming = ConfigBool<EXPLAIN_ANALYZE_TIMING, true>;
 
 #[derive(Default)]
 pub struct ConfigMap {
@@ -183,6 +199,28 @@ pub struct ConfigMap {
 
     /// To force the usage of lookup join instead of hash join in batch execution
     batch_enable_lookup_join: BatchEnableLookupJoin,
+
+    /// To use the rearranged chain when creating mviews, which processes the barriers while
+    /// backfilling the snapshot of the upstream.
+    streaming_enable_rearranged_chain: StreamingEnableRearrangedChain,
+
+    /// To spread the rows of a hot group key over several actors in streaming hash aggregation,
+    /// by partially aggregating them with a salt before the final aggregation.
+    streaming_enable_salted_agg: StreamingEnableSaltedAgg,
+
+    /// How far below the watermark of the first join key the rows are kept in the state of
+    /// streaming hash joins, in seconds for a time key, or in its own unit for an integer key.
+    /// Negative means the rows are kept forever.
+    streaming_join_state_retention: StreamingJoinStateRetention,
+
+    /// The number of rows in each chunk produced by batch scans, exchanges and lookup joins.
+    /// Larger chunks have less per-chunk overhead, while smaller chunks use less memory for wide
+    /// rows.
+    batch_chunk_size: BatchChunkSize,
+
+    /// To show the time spent in each operator by `EXPLAIN ANALYZE`. Turning it off makes the
+    /// output deterministic.
+    explain_analyze_timing: ExplainAnalyzeTiming,
 }
 
 impl ConfigMap {
@@ -201,6 +239,24 @@ impl ConfigMap {
             self.date_style = val.parse()?;
         } else if key.eq_ignore_ascii_case(BatchEnableLookupJoin::entry_name()) {
             self.batch_enable_lookup_join = val.parse()?;
+        } else if key.eq_ignore_ascii_case(StreamingEnableRearrangedChain::entry_name()) {
+            self.streaming_enable_rearranged_chain = val.parse()?;
+        } else if key.eq_ignore_ascii_case(StreamingEnableSaltedAgg::entry_name()) {
+            self.streaming_enable_salted_agg = val.parse()?;
+        } else if key.eq_ignore_ascii_case(StreamingJoinStateRetention::entry_name()) {
+            self.streaming_join_state_retention = val.parse()?;
+        } else if key.eq_ignore_ascii_case(BatchChunkSize::entry_name()) {
+            let batch_chunk_size: BatchChunkSize = val.parse()?;
+            if *batch_chunk_size < 1 || *batch_chunk_size as usize > MAX_CHUNK_SIZE {
+                return Err(ErrorCode::InvalidConfigValue {
+                    config_entry: BatchChunkSize::entry_name().to_string(),
+                    config_value: val.to_string(),
+                }
+                .into());
+            }
+            self.batch_chunk_size = batch_chunk_size;
+        } else if key.eq_ignore_ascii_case(ExplainAnalyzeTiming::entry_name()) {
+            self.explain_analyze_timing = val.parse()?;
         } else {
             return Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into());
         }
@@ -208,6 +264,12 @@ impl ConfigMap {
         Ok(())
     }
 
+    /// Resets the config entry `key` to its default value.
+    pub fn reset(&mut self, key: &str) -> Result<(), RwError> {
+        let default = ConfigMap::default().get(key)?;
+        self.set(key, &default)
+    }
+
     pub fn get(&self, key: &str) -> Result<String, RwError> {
         if key.eq_ignore_ascii_case(ImplicitFlush::entry_name()) {
             Ok(self.implicit_flush.to_string())
@@ -223,6 +285,16 @@ impl ConfigMap {
             Ok(self.date_style.to_string())
         } else if key.eq_ignore_ascii_case(BatchEnableLookupJoin::entry_name()) {
             Ok(self.batch_enable_lookup_join.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingEnableRearrangedChain::entry_name()) {
+            Ok(self.streaming_enable_rearranged_chain.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingEnableSaltedAgg::entry_name()) {
+            Ok(self.streaming_enable_salted_agg.to_string())
+        } else if key.eq_ignore_ascii_case(StreamingJoinStateRetention::entry_name()) {
+            Ok(self.streaming_join_state_retention.to_string())
+        } else if key.eq_ignore_ascii_case(BatchChunkSize::entry_name()) {
+            Ok(self.batch_chunk_size.to_string())
+        } else if key.eq_ignore_ascii_case(ExplainAnalyzeTiming::entry_name()) {
+            Ok(self.explain_analyze_timing.to_string())
         } else {
             Err(ErrorCode::UnrecognizedConfigurationParameter(key.to_string()).into())
         }
@@ -265,6 +337,31 @@ impl ConfigMap {
                 setting : self.batch_enable_lookup_join.to_string(),
                 description : String::from("To enable the usage of lookup join instead of hash join when possible for local batch execution")
             },
+            VariableInfo{
+                name : StreamingEnableRearrangedChain::entry_name().to_lowercase(),
+                setting : self.streaming_enable_rearranged_chain.to_string(),
+                description : String::from("To process the barriers while backfilling the snapshot of the upstream when creating mviews")
+            },
+            VariableInfo{
+                name : StreamingEnableSaltedAgg::entry_name().to_lowercase(),
+                setting : self.streaming_enable_salted_agg.to_string(),
+                description : String::from("To partially aggregate the rows of each group key over several actors in streaming hash aggregation, for skewed group keys")
+            },
+            VariableInfo{
+                name : StreamingJoinStateRetention::entry_name().to_lowercase(),
+                setting : self.streaming_join_state_retention.to_string(),
+                description : String::from("How far below the watermark of the first join key the rows are kept in the state of streaming hash joins. Negative means forever")
+            },
+            VariableInfo{
+                name : BatchChunkSize::entry_name().to_lowercase(),
+                setting : self.batch_chunk_size.to_string(),
+                description : String::from("The number of rows in each chunk produced by batch scans, exchanges and lookup joins, from 1 to 65536")
+            },
+            VariableInfo{
+                name : ExplainAnalyzeTiming::entry_name().to_lowercase(),
+                setting : self.explain_analyze_timing.to_string(),
+                description : String::from("To show the time spent in each operator by EXPLAIN ANALYZE")
+            },
         ]
     }
 
@@ -295,4 +392,24 @@ impl ConfigMap {
     pub fn get_batch_enable_lookup_join(&self) -> bool {
         *self.batch_enable_lookup_join
     }
+
+    pub fn get_streaming_enable_rearranged_chain(&self) -> bool {
+        *self.streaming_enable_rearranged_chain
+    }
+
+    pub fn get_streaming_enable_salted_agg(&self) -> bool {
+        *self.streaming_enable_salted_agg
+    }
+
+    pub fn get_streaming_join_state_retention(&self) -> i32 {
+        *self.streaming_join_state_retention
+    }
+
+    pub fn get_batch_chunk_size(&self) -> usize {
+        *self.batch_chunk_size as usize
+    }
+
+    pub fn get_explain_analyze_timing(&self) -> bool {
+        *self.explain_analyze_timing
+    }
 }
@@ -23,9 +23,12 @@ use crate::error::RwError;
 
 #[derive(Copy, Default, Debug, Clone, PartialEq, Eq)]
 pub enum QueryMode {
+    /// Let the optimizer choose the mode from the plan of each query.
+    #[default]
+    Auto,
+
     Local,
 
-    #[default]
     Distributed,
 }
 
@@ -39,7 +42,9 @@ impl FromStr for QueryMode {
     type Err = RwError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.eq_ignore_ascii_case("local") {
+        if s.eq_ignore_ascii_case("auto") {
+            Ok(Self::Auto)
+        } else if s.eq_ignore_ascii_case("local") {
             Ok(Self::Local)
         } else if s.eq_ignore_ascii_case("distributed") {
             Ok(Self::Distributed)
@@ -55,6 +60,7 @@ impl FromStr for QueryMode {
 impl std::fmt::Display for QueryMode {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::Auto => write!(f, "auto"),
             Self::Local => write!(f, "local"),
             Self::Distributed => write!(f, "distributed"),
         }
@@ -67,6 +73,7 @@ mod tests {
 
     #[test]
     fn parse_query_mode() {
+        assert_eq!("auto".parse::<QueryMode>().unwrap(), QueryMode::Auto);
         assert_eq!("local".parse::<QueryMode>().unwrap(), QueryMode::Local);
         assert_eq!("Local".parse::<QueryMode>().unwrap(), QueryMode::Local);
         assert_eq!(
@@ -25,6 +25,8 @@ use crate::error::RwError;
 use crate::types::{DataType, Datum, DatumRef};
 
 pub const DEFAULT_CHUNK_BUFFER_SIZE: usize = 2048;
+/// The largest chunk size that can be configured for batch executors.
+pub const MAX_CHUNK_SIZE: usize = 1 << 16;
 
 /// A [`SlicedDataChunk`] is a [`DataChunk`] with offset.
 pub struct SlicedDataChunk {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use itertools::Itertools;
+use serde::Serialize;
 
 use crate::array::{ArrayImpl, DataChunk, Row};
 use crate::error::Result;
@@ -34,17 +35,32 @@ pub fn is_type_encodable(t: DataType) -> bool {
     )
 }
 
-fn encode_value(value: Option<ScalarRefImpl>, order: &OrderType) -> Result<Vec<u8>> {
+fn encode_value(
+    value: Option<ScalarRefImpl>,
+    order: &OrderType,
+    nulls_first: bool,
+) -> Result<Vec<u8>> {
     let mut serializer = memcomparable::Serializer::new(vec![]);
     serializer.set_reverse(order == &OrderType::Descending);
-    serialize_datum_ref_into(&value, &mut serializer)?;
+    if nulls_first == order.default_nulls_first() {
+        serialize_datum_ref_into(&value, &mut serializer)?;
+    } else {
+        // Flip the null tag so that nulls go to the other end.
+        match value {
+            Some(value) => {
+                1u8.serialize(&mut serializer)?;
+                value.serialize(&mut serializer)?;
+            }
+            None => 0u8.serialize(&mut serializer)?,
+        }
+    }
     Ok(serializer.into_inner())
 }
 
-fn encode_array(array: &ArrayImpl, order: &OrderType) -> Result<Vec<Vec<u8>>> {
+fn encode_array(array: &ArrayImpl, order: &OrderType, nulls_first: bool) -> Result<Vec<Vec<u8>>> {
     let mut data = Vec::with_capacity(array.len());
     for datum in array.iter() {
-        data.push(encode_value(datum, order)?);
+        data.push(encode_value(datum, order, nulls_first)?);
     }
     Ok(data)
 }
@@ -52,12 +68,17 @@ fn encode_array(array: &ArrayImpl, order: &OrderType) -> Result<Vec<Vec<u8>>> {
 /// This function is used to accelerate the comparison of tuples. It takes datachunk and
 /// user-defined order as input, yield encoded binary string with order preserved for each tuple in
 /// the datachunk.
-///
-/// TODO: specify the order for `NULL`.
 pub fn encode_chunk(chunk: &DataChunk, order_pairs: &[OrderPair]) -> Vec<Vec<u8>> {
     let encoded_columns = order_pairs
         .iter()
-        .map(|o| encode_array(chunk.column_at(o.column_idx).array_ref(), &o.order_type).unwrap())
+        .map(|o| {
+            encode_array(
+                chunk.column_at(o.column_idx).array_ref(),
+                &o.order_type,
+                o.nulls_first,
+            )
+            .unwrap()
+        })
         .collect_vec();
 
     let mut encoded_chunk = vec![vec![]; chunk.capacity()];
@@ -74,8 +95,14 @@ pub fn encode_row(row: &Row, order_pairs: &[OrderPair]) -> Vec<u8> {
     let mut encoded_row = vec![];
     order_pairs.iter().for_each(|o| {
         let value = row[o.column_idx].as_ref();
-        encoded_row
-            .extend(encode_value(value.map(|x| x.as_scalar_ref_impl()), &o.order_type).unwrap());
+        encoded_row.extend(
+            encode_value(
+                value.map(|x| x.as_scalar_ref_impl()),
+                &o.order_type,
+                o.nulls_first,
+            )
+            .unwrap(),
+        );
     });
     encoded_row
 }
@@ -111,11 +138,13 @@ mod tests {
         let encoded_v10 = encode_value(
             v10_cloned.as_ref().map(|x| x.as_scalar_ref_impl()),
             &OrderType::Ascending,
+            false,
         )
         .unwrap();
         let encoded_v11 = encode_value(
             v11_cloned.as_ref().map(|x| x.as_scalar_ref_impl()),
             &OrderType::Descending,
+            true,
         )
         .unwrap();
         let concated_encoded_row1 = encoded_v10
@@ -154,4 +183,29 @@ mod tests {
         let encoded_chunk = encode_chunk(&chunk, &order_pairs);
         assert_eq!(&encoded_chunk, &[encoded_row1, encoded_row2]);
     }
+
+    #[test]
+    fn test_encode_row_with_nulls() {
+        let row = |v0: Option<i32>, v1: Option<i32>| {
+            Row::new(vec![v0.map(ScalarImpl::Int32), v1.map(ScalarImpl::Int32)])
+        };
+        // Listed in the order of `0 ASC NULLS FIRST, 1 DESC NULLS LAST`.
+        let rows = [
+            row(None, Some(2)),
+            row(None, None),
+            row(Some(1), Some(1)),
+            row(Some(1), None),
+        ];
+        let order_pairs = vec![
+            OrderPair::new(0, OrderType::Ascending).with_nulls_first(true),
+            OrderPair::new(1, OrderType::Descending).with_nulls_first(false),
+        ];
+        let encoded_rows = rows
+            .iter()
+            .map(|row| encode_row(row, &order_pairs))
+            .collect_vec();
+        for (lhs, rhs) in encoded_rows.iter().tuple_windows() {
+            assert!(lhs < rhs);
+        }
+    }
 }
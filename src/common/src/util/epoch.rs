@@ -66,6 +66,15 @@ impl Epoch {
             .as_millis() as u64
     }
 
+    /// Returns the first epoch at the system time `time`, or `INVALID_EPOCH` if it's earlier than
+    /// the singularity date.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        match time.duration_since(*UNIX_SINGULARITY_DATE_EPOCH) {
+            Ok(duration) => Self((duration.as_millis() as u64) << EPOCH_PHYSICAL_SHIFT_BITS),
+            Err(_) => Self(INVALID_EPOCH),
+        }
+    }
+
     /// Returns the epoch in real system time.
     pub fn as_system_time(&self) -> SystemTime {
         *UNIX_SINGULARITY_DATE_EPOCH + Duration::from_millis(self.physical_time())
@@ -45,27 +45,42 @@ impl OrderType {
             OrderType::Descending => ProstOrderType::Descending,
         }
     }
+
+    /// Whether nulls go first by default, i.e. `ASC NULLS LAST` and `DESC NULLS FIRST` as in
+    /// the SQL standard.
+    pub fn default_nulls_first(&self) -> bool {
+        *self == OrderType::Descending
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OrderPair {
     pub column_idx: usize,
     pub order_type: OrderType,
+    pub nulls_first: bool,
 }
 
 impl OrderPair {
+    /// Creates an order pair with the default null ordering of `order_type`.
     pub fn new(column_idx: usize, order_type: OrderType) -> Self {
         Self {
             column_idx,
             order_type,
+            nulls_first: order_type.default_nulls_first(),
         }
     }
 
+    pub fn with_nulls_first(mut self, nulls_first: bool) -> Self {
+        self.nulls_first = nulls_first;
+        self
+    }
+
     pub fn from_prost(column_order: &ColumnOrder) -> Self {
         let order_type: ProstOrderType = ProstOrderType::from_i32(column_order.order_type).unwrap();
         OrderPair {
             order_type: OrderType::from_prost(&order_type),
             column_idx: column_order.index as usize,
+            nulls_first: column_order.nulls_first,
         }
     }
 
@@ -73,6 +88,7 @@ impl OrderPair {
         ColumnOrder {
             order_type: self.order_type.to_prost() as i32,
             index: self.column_idx as u32,
+            nulls_first: self.nulls_first,
         }
     }
 }
@@ -164,21 +180,34 @@ impl PartialOrd for DescOrderedRow {
     }
 }
 
-fn compare_values<'a, T>(lhs: Option<&T>, rhs: Option<&T>, order_type: &'a OrderType) -> Ordering
+/// Compares two values. The direction only applies to non-null values, while nulls go first or
+/// last as told by `nulls_first`.
+fn compare_values<'a, T>(
+    lhs: Option<&T>,
+    rhs: Option<&T>,
+    order_type: &'a OrderType,
+    nulls_first: bool,
+) -> Ordering
 where
     T: Ord,
 {
-    let ord = match (lhs, rhs) {
-        (Some(l), Some(r)) => l.cmp(r),
-        (None, None) => Ordering::Equal,
-        // TODO(yuchao): `null first` / `null last` is not supported yet.
-        (Some(_), None) => Ordering::Less,
-        (None, Some(_)) => Ordering::Greater,
-    };
-    if *order_type == OrderType::Descending {
-        ord.reverse()
+    let null_ord = if nulls_first {
+        Ordering::Less
     } else {
-        ord
+        Ordering::Greater
+    };
+    match (lhs, rhs) {
+        (Some(l), Some(r)) => {
+            let ord = l.cmp(r);
+            if *order_type == OrderType::Descending {
+                ord.reverse()
+            } else {
+                ord
+            }
+        }
+        (None, None) => Ordering::Equal,
+        (Some(_), None) => null_ord.reverse(),
+        (None, Some(_)) => null_ord,
     }
 }
 
@@ -190,10 +219,10 @@ pub fn compare_rows(lhs: &Row, rhs: &Row, order_pairs: &[OrderPair]) -> Result<O
         macro_rules! gen_match {
             ($lhs: ident, $rhs: ident, [$( $tt: ident), *]) => {
                 match ($lhs, $rhs) {
-                    $((Some(ScalarImpl::$tt(l)), Some(ScalarImpl::$tt(r))) => Ok(compare_values(Some(l), Some(r), &order_pair.order_type)),)*
-                    $((Some(ScalarImpl::$tt(l)), None) => Ok(compare_values(Some(l), None, &order_pair.order_type)),)*
-                    $((None, Some(ScalarImpl::$tt(r))) => Ok(compare_values(None, Some(r), &order_pair.order_type)),)*
-                    (None, None) => Ok(compare_values::<()>(None, None, &order_pair.order_type)),
+                    $((Some(ScalarImpl::$tt(l)), Some(ScalarImpl::$tt(r))) => Ok(compare_values(Some(l), Some(r), &order_pair.order_type, order_pair.nulls_first)),)*
+                    $((Some(ScalarImpl::$tt(l)), None) => Ok(compare_values(Some(l), None, &order_pair.order_type, order_pair.nulls_first)),)*
+                    $((None, Some(ScalarImpl::$tt(r))) => Ok(compare_values(None, Some(r), &order_pair.order_type, order_pair.nulls_first)),)*
+                    (None, None) => Ok(compare_values::<()>(None, None, &order_pair.order_type, order_pair.nulls_first)),
                     (Some(l), Some(r)) => Err(InternalError(format!("Unmatched scalar types, lhs is: {:?}, rhs is: {:?}", l, r))),
                     (l, r) => Err(InternalError(format!("Unsupported types, lhs is: {:?}, rhs is: {:?}", l, r))),
                 }?
@@ -232,6 +261,7 @@ fn compare_values_in_array<'a, T>(
     rhs_array: &'a T,
     rhs_idx: usize,
     order_type: &'a OrderType,
+    nulls_first: bool,
 ) -> Ordering
 where
     T: Array,
@@ -241,6 +271,7 @@ where
         lhs_array.value_at(lhs_idx).as_ref(),
         rhs_array.value_at(rhs_idx).as_ref(),
         order_type,
+        nulls_first,
     )
 }
 
@@ -257,7 +288,7 @@ pub fn compare_rows_in_chunk(
         macro_rules! gen_match {
             ($lhs: ident, $rhs: ident, [$( $tt: ident), *]) => {
                 match ($lhs, $rhs) {
-                    $((ArrayImpl::$tt(lhs_inner), ArrayImpl::$tt(rhs_inner)) => Ok(compare_values_in_array(lhs_inner, lhs_idx, rhs_inner, rhs_idx, &order_pair.order_type)),)*
+                    $((ArrayImpl::$tt(lhs_inner), ArrayImpl::$tt(rhs_inner)) => Ok(compare_values_in_array(lhs_inner, lhs_idx, rhs_inner, rhs_idx, &order_pair.order_type, order_pair.nulls_first)),)*
                     (l_arr, r_arr) => Err(InternalError(format!("Unmatched array types, lhs array is: {}, rhs array is: {}", l_arr.get_ident(), r_arr.get_ident()))),
                 }?
             }
@@ -292,6 +323,8 @@ pub fn compare_rows_in_chunk(
 mod tests {
     use std::cmp::Ordering;
 
+    use itertools::Itertools;
+
     use super::{compare_rows, OrderPair, OrderType};
     use crate::array::{DataChunk, Row};
     use crate::types::{DataType, ScalarImpl};
@@ -353,4 +386,48 @@ mod tests {
             compare_rows_in_chunk(&chunk, 0, &chunk, 1, &order_pairs).unwrap()
         );
     }
+
+    #[test]
+    fn test_compare_rows_with_nulls() {
+        let row = |v0: Option<i32>, v1: Option<i32>| {
+            Row::new(vec![v0.map(ScalarImpl::Int32), v1.map(ScalarImpl::Int32)])
+        };
+        let rows = [
+            row(Some(1), Some(1)),
+            row(Some(1), None),
+            row(None, Some(2)),
+            row(None, None),
+        ];
+        let chunk = DataChunk::from_rows(&rows, &[DataType::Int32, DataType::Int32]).unwrap();
+
+        // `0 ASC NULLS FIRST, 1 DESC NULLS LAST`
+        let order_pairs = vec![
+            OrderPair::new(0, OrderType::Ascending).with_nulls_first(true),
+            OrderPair::new(1, OrderType::Descending).with_nulls_first(false),
+        ];
+        let expected = [2, 3, 0, 1];
+        for (i, j) in expected.iter().tuple_windows() {
+            assert_eq!(
+                Ordering::Less,
+                compare_rows(&rows[*i], &rows[*j], &order_pairs).unwrap()
+            );
+            assert_eq!(
+                Ordering::Less,
+                compare_rows_in_chunk(&chunk, *i, &chunk, *j, &order_pairs).unwrap()
+            );
+        }
+
+        // The defaults are `0 ASC NULLS LAST, 1 DESC NULLS FIRST`.
+        let order_pairs = vec![
+            OrderPair::new(0, OrderType::Ascending),
+            OrderPair::new(1, OrderType::Descending),
+        ];
+        let expected = [1, 0, 3, 2];
+        for (i, j) in expected.iter().tuple_windows() {
+            assert_eq!(
+                Ordering::Less,
+                compare_rows(&rows[*i], &rows[*j], &order_pairs).unwrap()
+            );
+        }
+    }
 }
@@ -93,7 +93,7 @@ impl ComputeObserverNode {
         match operation {
             Operation::Add | Operation::Update => {
                 let slice_transform = if table_catalog.read_pattern_prefix_column < 1 {
-                    // for now frontend had not infer the table_id_to_slice_transform, so we use
+                    // the tables without a read pattern inferred by the frontend use
                     // FullKeySliceTransform
                     SliceTransformImpl::FullKey(FullKeySliceTransform::default())
                 } else {
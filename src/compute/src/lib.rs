@@ -37,7 +37,9 @@ pub mod compute_observer;
 pub mod rpc;
 pub mod server;
 
-use clap::Parser;
+use std::collections::HashMap;
+
+use clap::{ArgEnum, Parser};
 
 /// Command-line arguments for compute-node.
 #[derive(Parser, Debug)]
@@ -73,6 +75,28 @@ pub struct ComputeNodeOpts {
     /// Enable reporting tracing information to jaeger
     #[clap(long)]
     pub enable_jaeger_tracing: bool,
+
+    /// The number of parallel units to register to meta. Overrides `worker_node_parallelism` in
+    /// the config, which defaults to the number of CPU cores.
+    #[clap(long)]
+    pub parallelism: Option<usize>,
+
+    /// Comma separated `key=value` labels of the compute node, e.g. `disk=nvme,zone=a`. Used to
+    /// select workers when scheduling materialized views.
+    #[clap(long, default_value = "", parse(try_from_str = parse_labels))]
+    pub labels: HashMap<String, String>,
+
+    /// The workload to take. A `serving` compute node only runs batch queries, and is preferred
+    /// by them, while a `streaming` one only runs streaming actors.
+    #[clap(long, arg_enum, default_value_t = Role::Both)]
+    pub role: Role,
+}
+
+#[derive(Copy, Clone, Debug, ArgEnum)]
+pub enum Role {
+    Both,
+    Streaming,
+    Serving,
 }
 
 use std::future::Future;
@@ -106,3 +130,17 @@ pub fn start(opts: ComputeNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>>
         }
     })
 }
+
+/// Parse labels in the form of `key1=value1,key2=value2`.
+fn parse_labels(labels: &str) -> Result<HashMap<String, String>, String> {
+    labels
+        .split(',')
+        .filter(|label| !label.is_empty())
+        .map(|label| {
+            let (key, value) = label
+                .split_once('=')
+                .ok_or_else(|| format!("invalid label `{}`, should be `key=value`", label))?;
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
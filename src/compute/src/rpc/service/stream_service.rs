@@ -154,8 +154,14 @@ impl StreamService for StreamServiceImpl {
         let req = request.into_inner();
         let collect_result = self.mgr.collect_barrier(req.prev_epoch).await;
         // Must finish syncing data written in the epoch before respond back to ensure persistency
-        // of the state.
-        let synced_sstables = self.mgr.sync_epoch(req.prev_epoch).await;
+        // of the state. For non-checkpoint barriers, the data will be synced by the next checkpoint
+        // barrier.
+        let synced_sstables = if req.checkpoint {
+            self.mgr.sync_epoch(req.prev_epoch).await
+        } else {
+            self.mgr.skip_sync_epoch(req.prev_epoch);
+            vec![]
+        };
 
         Ok(Response::new(BarrierCompleteResponse {
             request_id: req.request_id,
@@ -163,9 +169,10 @@ impl StreamService for StreamServiceImpl {
             create_mview_progress: collect_result.create_mview_progress,
             synced_sstables: synced_sstables
                 .into_iter()
-                .map(|(compaction_group_id, sst)| GroupedSstableInfo {
+                .map(|(epoch, (compaction_group_id, sst))| GroupedSstableInfo {
                     compaction_group_id,
                     sst: Some(sst),
+                    epoch,
                 })
                 .collect_vec(),
         }))
@@ -26,7 +26,7 @@ use risingwave_common::monitor::process_linux::monitor_process;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::metrics_manager::MetricsManager;
 use risingwave_common_service::observer_manager::ObserverManager;
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerRole, WorkerType};
 use risingwave_pb::stream_service::stream_service_server::StreamServiceServer;
 use risingwave_pb::task_service::exchange_service_server::ExchangeServiceServer;
 use risingwave_pb::task_service::task_service_server::TaskServiceServer;
@@ -50,10 +50,19 @@ use crate::compute_observer::observer_manager::ComputeObserverNode;
 use crate::rpc::service::exchange_metrics::ExchangeServiceMetrics;
 use crate::rpc::service::exchange_service::ExchangeServiceImpl;
 use crate::rpc::service::stream_service::StreamServiceImpl;
-use crate::ComputeNodeOpts;
+use crate::{ComputeNodeOpts, Role};
 
 fn load_config(opts: &ComputeNodeOpts) -> ComputeNodeConfig {
-    risingwave_common::config::load_config(&opts.config_path)
+    let mut config: ComputeNodeConfig = risingwave_common::config::load_config(&opts.config_path);
+    if let Some(parallelism) = opts.parallelism {
+        config.streaming.worker_node_parallelism = parallelism;
+    }
+    // Channels of zero capacity can't be created.
+    assert!(
+        config.streaming.local_output_channel_size > 0,
+        "streaming.local_output_channel_size must be positive"
+    );
+    config
 }
 
 fn get_compile_mode() -> &'static str {
@@ -86,6 +95,12 @@ pub async fn compute_node_serve(
             WorkerType::ComputeNode,
             &client_addr,
             config.streaming.worker_node_parallelism,
+            opts.labels.clone(),
+            match opts.role {
+                Role::Both => WorkerRole::Both,
+                Role::Streaming => WorkerRole::Streaming,
+                Role::Serving => WorkerRole::Serving,
+            },
         )
         .await
         .unwrap();
@@ -71,6 +71,12 @@ macro_rules! impl_split {
             fn restore_from_bytes(bytes: &[u8]) -> Result<Self> {
                 SplitImpl::try_from(&ConnectorSplit::decode(bytes)?)
             }
+
+            fn parent_ids(&self) -> Vec<String> {
+                match self {
+                    $( Self::$variant_name(inner) => inner.parent_ids(), )*
+                }
+            }
         }
 
         impl SplitImpl {
@@ -81,10 +87,10 @@ macro_rules! impl_split {
                     .to_string()
             }
 
-            pub fn update(&self, start_offset: String) -> Self {
-                match self {
-                    $( Self::$variant_name(inner) => Self::$variant_name(inner.copy_with_offset(start_offset)), )*
-                }
+            pub fn update(&self, start_offset: String) -> anyhow::Result<Self> {
+                Ok(match self {
+                    $( Self::$variant_name(inner) => Self::$variant_name(inner.copy_with_offset(start_offset)?), )*
+                })
             }
         }
     }
@@ -0,0 +1,223 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use risingwave_common::array::{Op, Row, StreamChunk};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{DataType, ScalarImpl};
+
+use crate::sink::{Result, Sink, SinkConfig, SinkError, SinkImpl};
+
+/// The prefix of the properties that configure the dead-letter sink. For example,
+/// `dead_letter.sink_type = 'kafka'` sets the type of the dead-letter sink.
+pub const DEAD_LETTER_PREFIX: &str = "dead_letter.";
+
+/// The name of the column appended to the dead letters, which holds the error message.
+pub const DEAD_LETTER_ERROR_COLUMN: &str = "error";
+
+#[derive(Clone, Debug)]
+pub struct DeadLetterConfig {
+    pub sink: Box<SinkConfig>,
+    pub dead_letter: Box<SinkConfig>,
+}
+
+impl DeadLetterConfig {
+    /// Splits `properties` into the ones of the sink and the ones of the dead-letter sink. Returns
+    /// `None` if no dead-letter sink is configured.
+    pub fn split_properties(
+        properties: HashMap<String, String>,
+    ) -> Option<(HashMap<String, String>, HashMap<String, String>)> {
+        let (dead_letter, sink): (HashMap<_, _>, HashMap<_, _>) = properties
+            .into_iter()
+            .partition(|(k, _)| k.starts_with(DEAD_LETTER_PREFIX));
+        if dead_letter.is_empty() {
+            return None;
+        }
+        let dead_letter = dead_letter
+            .into_iter()
+            .map(|(k, v)| (k[DEAD_LETTER_PREFIX.len()..].to_string(), v))
+            .collect();
+        Some((sink, dead_letter))
+    }
+}
+
+/// Wraps a sink so that the rows it fails to write are diverted to a dead-letter sink, instead of
+/// failing the whole chunk. Each dead letter is the original row with the error message appended.
+///
+/// When a chunk fails because of its rows, see [`SinkError::is_caused_by_rows`], nothing of it is
+/// written, and it's written again row by row to find the failed rows. Other errors are returned
+/// as is.
+#[derive(Debug)]
+pub struct DeadLetterSink {
+    sink: SinkImpl,
+    dead_letter: SinkImpl,
+}
+
+impl DeadLetterSink {
+    pub fn new(sink: SinkImpl, dead_letter: SinkImpl) -> Self {
+        Self { sink, dead_letter }
+    }
+}
+
+#[async_trait]
+impl Sink for DeadLetterSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        let e = match self.sink.write_batch(chunk.clone(), schema).await {
+            Ok(()) => return Ok(()),
+            Err(e) if e.is_caused_by_rows() => e,
+            Err(e) => return Err(e),
+        };
+        tracing::warn!("failed to write chunk to sink, retry row by row: {}", e);
+
+        let data_types = schema.data_types();
+        let mut dead_letters = vec![];
+        for rows in group_rows(&chunk) {
+            let row_chunk = StreamChunk::from_rows(&rows, &data_types)
+                .map_err(|e| SinkError::DeadLetter(e.to_string()))?;
+            match self.sink.write_batch(row_chunk, schema).await {
+                Ok(()) => {}
+                Err(e) if e.is_caused_by_rows() => {
+                    let error = e.to_string();
+                    dead_letters.extend(rows.into_iter().map(|(_, Row(mut datums))| {
+                        datums.push(Some(ScalarImpl::Utf8(error.clone())));
+                        (Op::Insert, Row(datums))
+                    }));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if dead_letters.is_empty() {
+            return Ok(());
+        }
+
+        let mut fields = schema.fields().to_vec();
+        fields.push(Field::with_name(
+            DataType::Varchar,
+            DEAD_LETTER_ERROR_COLUMN,
+        ));
+        let dead_letter_schema = Schema::new(fields);
+        let dead_letter_chunk =
+            StreamChunk::from_rows(&dead_letters, &dead_letter_schema.data_types())
+                .map_err(|e| SinkError::DeadLetter(e.to_string()))?;
+        self.dead_letter
+            .write_batch(dead_letter_chunk, &dead_letter_schema)
+            .await
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        self.sink.begin_epoch(epoch).await?;
+        self.dead_letter.begin_epoch(epoch).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.sink.commit().await?;
+        self.dead_letter.commit().await
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        self.sink.abort().await?;
+        self.dead_letter.abort().await
+    }
+}
+
+/// Splits the visible rows of `chunk` into the smallest groups that can be written on their own,
+/// i.e. single rows, except that an `UpdateDelete` is kept with its following `UpdateInsert`.
+fn group_rows(chunk: &StreamChunk) -> Vec<Vec<(Op, Row)>> {
+    let mut groups: Vec<Vec<(Op, Row)>> = vec![];
+    for (op, row) in chunk.rows() {
+        let row = row.to_owned_row();
+        match groups.last_mut() {
+            Some(group)
+                if op == Op::UpdateInsert && group.last().unwrap().0 == Op::UpdateDelete =>
+            {
+                group.push((op, row))
+            }
+            _ => groups.push(vec![(op, row)]),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_common::array;
+    use risingwave_common::array::column::Column;
+    use risingwave_common::array::{ArrayImpl, I32Array};
+
+    use super::*;
+
+    #[test]
+    fn test_split_properties() {
+        let properties = HashMap::from([
+            ("sink_type".to_string(), "mysql".to_string()),
+            ("dead_letter.sink_type".to_string(), "kafka".to_string()),
+        ]);
+        let (sink, dead_letter) = DeadLetterConfig::split_properties(properties).unwrap();
+        assert_eq!(sink.get("sink_type").unwrap(), "mysql");
+        assert_eq!(dead_letter.get("sink_type").unwrap(), "kafka");
+
+        let properties = HashMap::from([("sink_type".to_string(), "mysql".to_string())]);
+        assert!(DeadLetterConfig::split_properties(properties).is_none());
+    }
+
+    #[test]
+    fn test_caused_by_rows() {
+        assert!(SinkError::JsonParse("unsupported type".to_string()).is_caused_by_rows());
+        // The producer may have sent a part of the chunk.
+        assert!(!SinkError::Kafka(rdkafka::error::KafkaError::Canceled).is_caused_by_rows());
+
+        let server_error = |code| {
+            SinkError::MySQLInner(mysql_async::Error::Server(mysql_async::ServerError {
+                code,
+                message: String::new(),
+                state: String::new(),
+            }))
+        };
+        // Duplicate key.
+        assert!(server_error(1062).is_caused_by_rows());
+        // Not-null violation.
+        assert!(server_error(1048).is_caused_by_rows());
+        // Deadlock and lock wait timeout roll back the transaction.
+        assert!(!server_error(1213).is_caused_by_rows());
+        assert!(!server_error(1205).is_caused_by_rows());
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::UpdateDelete, Op::UpdateInsert, Op::Delete],
+            vec![Column::new(Arc::new(ArrayImpl::from(array!(
+                I32Array,
+                [Some(1), Some(2), Some(3), Some(4)]
+            ))))],
+            None,
+        );
+        let groups = group_rows(&chunk);
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| group.iter().map(|(op, _)| *op).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![
+                vec![Op::Insert],
+                vec![Op::UpdateDelete, Op::UpdateInsert],
+                vec![Op::Delete]
+            ]
+        );
+    }
+}
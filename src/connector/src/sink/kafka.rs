@@ -175,17 +175,25 @@ impl KafkaSink {
         )
     }
 
+    /// Sends the inserted rows of the chunk. All rows are serialized before any is sent, so a
+    /// malformed row fails the chunk without sending the others, and the chunk can be written
+    /// again, e.g. row by row by the dead-letter sink, without duplicates.
     async fn append_only(&self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
-        for (op, row) in chunk.rows() {
-            if op == Op::Insert {
-                let record = Value::Object(record_to_json(row, schema.fields.clone())?).to_string();
-                self.send(
-                    BaseRecord::to(self.config.topic.as_str())
-                        .key(self.gen_message_key().as_bytes())
-                        .payload(record.as_bytes()),
-                )
-                .await?;
-            }
+        let records: Vec<String> = chunk
+            .rows()
+            .filter(|(op, _)| *op == Op::Insert)
+            .map(|(_, row)| {
+                Ok(Value::Object(record_to_json(row, schema.fields.clone())?).to_string())
+            })
+            .try_collect()?;
+        let key = self.gen_message_key();
+        for record in &records {
+            self.send(
+                BaseRecord::to(self.config.topic.as_str())
+                    .key(key.as_bytes())
+                    .payload(record.as_bytes()),
+            )
+            .await?;
         }
         Ok(())
     }
@@ -329,7 +337,7 @@ fn datum_to_json_object(field: &Field, datum: DatumRef) -> ArrayResult<Value> {
     Ok(value)
 }
 
-fn record_to_json(row: RowRef, schema: Vec<Field>) -> Result<Map<String, Value>> {
+pub(crate) fn record_to_json(row: RowRef, schema: Vec<Field>) -> Result<Map<String, Value>> {
     let mut mappings = Map::with_capacity(schema.len());
     for (field, datum_ref) in schema.iter().zip_eq(row.values()) {
         let key = field.name.clone();
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod dead_letter;
 pub mod kafka;
 pub mod mysql;
+pub mod pulsar;
 pub mod redis;
 
 use std::collections::HashMap;
@@ -27,8 +29,10 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 pub use tracing;
 
+use crate::sink::dead_letter::{DeadLetterConfig, DeadLetterSink};
 use crate::sink::kafka::{KafkaConfig, KafkaSink, KAFKA_SINK};
-use crate::sink::mysql::{MySQLConfig, MySQLSink};
+use crate::sink::mysql::{MySQLConfig, MySQLSink, MYSQL_SINK};
+use crate::sink::pulsar::{PulsarConfig, PulsarSink, PULSAR_SINK};
 use crate::sink::redis::{RedisConfig, RedisSink};
 
 #[async_trait]
@@ -52,6 +56,8 @@ pub enum SinkConfig {
     Mysql(MySQLConfig),
     Redis(RedisConfig),
     Kafka(KafkaConfig),
+    Pulsar(PulsarConfig),
+    DeadLetter(DeadLetterConfig),
 }
 
 #[derive(Clone, Debug, EnumAsInner, Serialize, Deserialize)]
@@ -59,11 +65,20 @@ pub enum SinkState {
     Kafka,
     Mysql,
     Redis,
+    Pulsar,
 }
 
 impl SinkConfig {
     pub fn from_hashmap(properties: HashMap<String, String>) -> RwResult<Self> {
         const SINK_TYPE_KEY: &str = "sink_type";
+        if let Some((properties, dead_letter_properties)) =
+            DeadLetterConfig::split_properties(properties.clone())
+        {
+            return Ok(SinkConfig::DeadLetter(DeadLetterConfig {
+                sink: Box::new(Self::from_hashmap(properties)?),
+                dead_letter: Box::new(Self::from_hashmap(dead_letter_properties)?),
+            }));
+        }
         let sink_type = properties.get(SINK_TYPE_KEY).ok_or_else(|| {
             RwError::from(ErrorCode::InvalidConfigValue {
                 config_entry: SINK_TYPE_KEY.to_string(),
@@ -72,6 +87,8 @@ impl SinkConfig {
         })?;
         match sink_type.to_lowercase().as_str() {
             KAFKA_SINK => Ok(SinkConfig::Kafka(KafkaConfig::from_hashmap(properties)?)),
+            MYSQL_SINK => Ok(SinkConfig::Mysql(MySQLConfig::from_hashmap(properties)?)),
+            PULSAR_SINK => Ok(SinkConfig::Pulsar(PulsarConfig::from_hashmap(properties)?)),
             _ => unimplemented!(),
         }
     }
@@ -82,9 +99,12 @@ pub enum SinkImpl {
     MySQL(Box<MySQLSink>),
     Redis(Box<RedisSink>),
     Kafka(Box<KafkaSink>),
+    Pulsar(Box<PulsarSink>),
+    DeadLetter(Box<DeadLetterSink>),
 }
 
 impl SinkImpl {
+    #[async_recursion::async_recursion]
     pub async fn new(cfg: SinkConfig) -> RwResult<Self> {
         Ok(match cfg {
             SinkConfig::Mysql(cfg) => {
@@ -96,6 +116,13 @@ impl SinkImpl {
             SinkConfig::Kafka(cfg) => {
                 SinkImpl::Kafka(Box::new(KafkaSink::new(cfg).map_err(RwError::from)?))
             }
+            SinkConfig::Pulsar(cfg) => {
+                SinkImpl::Pulsar(Box::new(PulsarSink::new(cfg).await.map_err(RwError::from)?))
+            }
+            SinkConfig::DeadLetter(cfg) => SinkImpl::DeadLetter(Box::new(DeadLetterSink::new(
+                Self::new(*cfg.sink).await?,
+                Self::new(*cfg.dead_letter).await?,
+            ))),
         })
     }
 }
@@ -107,6 +134,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySQL(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Redis(sink) => sink.write_batch(chunk, schema).await,
             SinkImpl::Kafka(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::Pulsar(sink) => sink.write_batch(chunk, schema).await,
+            SinkImpl::DeadLetter(sink) => sink.write_batch(chunk, schema).await,
         }
     }
 
@@ -115,6 +144,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySQL(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Redis(sink) => sink.begin_epoch(epoch).await,
             SinkImpl::Kafka(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::Pulsar(sink) => sink.begin_epoch(epoch).await,
+            SinkImpl::DeadLetter(sink) => sink.begin_epoch(epoch).await,
         }
     }
 
@@ -123,6 +154,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySQL(sink) => sink.commit().await,
             SinkImpl::Redis(sink) => sink.commit().await,
             SinkImpl::Kafka(sink) => sink.commit().await,
+            SinkImpl::Pulsar(sink) => sink.commit().await,
+            SinkImpl::DeadLetter(sink) => sink.commit().await,
         }
     }
 
@@ -131,6 +164,8 @@ impl Sink for SinkImpl {
             SinkImpl::MySQL(sink) => sink.abort().await,
             SinkImpl::Redis(sink) => sink.abort().await,
             SinkImpl::Kafka(sink) => sink.abort().await,
+            SinkImpl::Pulsar(sink) => sink.abort().await,
+            SinkImpl::DeadLetter(sink) => sink.abort().await,
         }
     }
 }
@@ -145,10 +180,31 @@ pub enum SinkError {
     MySQLInner(#[from] mysql_async::Error),
     #[error("Kafka error: {0}")]
     Kafka(#[from] rdkafka::error::KafkaError),
+    #[error("Pulsar error: {0}")]
+    Pulsar(#[from] ::pulsar::Error),
     #[error("Json parse error: {0}")]
     JsonParse(String),
     #[error("config error: {0}")]
     Config(String),
+    #[error("dead letter error: {0}")]
+    DeadLetter(String),
+}
+
+impl SinkError {
+    /// Whether the error is caused by the written rows rather than the sink, e.g. a malformed row
+    /// or a row rejected by the server for its data. The sinks write a chunk atomically on such
+    /// errors, so only they are diverted to the dead-letter sink. Other errors, e.g. of the
+    /// connection or a deadlock, may leave a chunk partially written or roll back the whole
+    /// epoch, and fail the epoch instead.
+    pub fn is_caused_by_rows(&self) -> bool {
+        match self {
+            SinkError::MySQL(_) | SinkError::JsonParse(_) => true,
+            SinkError::MySQLInner(mysql_async::Error::Server(e)) => {
+                mysql::is_row_error_code(e.code)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl From<SinkError> for RwError {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 
 use async_trait::async_trait;
@@ -25,6 +26,38 @@ use risingwave_common::types::{Datum, Decimal, ScalarImpl};
 
 use crate::sink::{Result, Sink, SinkError};
 
+pub const MYSQL_SINK: &str = "mysql";
+
+/// MySQL error code of `ER_NET_PACKET_TOO_LARGE`.
+const ER_NET_PACKET_TOO_LARGE: u16 = 1153;
+
+/// MySQL error codes of the statements rejected for the data of the rows, which fail only the
+/// statement: duplicate keys (`ER_DUP_ENTRY`, `ER_DUP_ENTRY_WITH_KEY_NAME`), truncations
+/// (`ER_WARN_DATA_OUT_OF_RANGE`, `WARN_DATA_TRUNCATED`, `ER_TRUNCATED_WRONG_VALUE`,
+/// `ER_TRUNCATED_WRONG_VALUE_FOR_FIELD`, `ER_DATA_TOO_LONG`), not-null violations
+/// (`ER_BAD_NULL_ERROR`, `ER_NO_DEFAULT_FOR_FIELD`) and check constraint violations
+/// (`ER_CHECK_CONSTRAINT_VIOLATED`). Other errors, e.g. a deadlock or a lock wait timeout, may
+/// roll back the whole transaction.
+const ROW_ERROR_CODES: &[u16] = &[1062, 1586, 1264, 1265, 1292, 1366, 1406, 1048, 1364, 3819];
+
+/// Whether a MySQL server error is caused by the data of the written rows.
+pub(crate) fn is_row_error_code(code: u16) -> bool {
+    ROW_ERROR_CODES.contains(&code)
+}
+
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// How inserted rows are written to MySQL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MySQLInsertMode {
+    /// `INSERT`, which fails on duplicate keys.
+    Insert,
+    /// `REPLACE`, which deletes the existing row with the same key first.
+    Replace,
+    /// `INSERT ... ON DUPLICATE KEY UPDATE`, which updates the existing row with the same key.
+    Upsert,
+}
+
 #[derive(Clone, Debug)]
 pub struct MySQLConfig {
     pub endpoint: String,
@@ -32,6 +65,54 @@ pub struct MySQLConfig {
     pub database: Option<String>,
     pub user: Option<String>,
     pub password: Option<String>,
+    /// The max number of rows in one multi-row `INSERT` statement.
+    pub batch_size: usize,
+    pub insert_mode: MySQLInsertMode,
+}
+
+impl MySQLConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let get = |key: &str| {
+            values
+                .get(key)
+                .cloned()
+                .ok_or_else(|| SinkError::Config(format!("{} must be set", key)))
+        };
+        let batch_size = match values.get("mysql.batch_size") {
+            None => DEFAULT_BATCH_SIZE,
+            Some(batch_size) => match batch_size.parse() {
+                Ok(batch_size) if batch_size > 0 => batch_size,
+                _ => {
+                    return Err(SinkError::Config(
+                        "mysql.batch_size must be a positive integer".to_string(),
+                    ))
+                }
+            },
+        };
+        let insert_mode =
+            match values.get("mysql.insert_mode").map(|s| s.to_lowercase()) {
+                None => MySQLInsertMode::Insert,
+                Some(mode) => match mode.as_str() {
+                    "insert" => MySQLInsertMode::Insert,
+                    "replace" => MySQLInsertMode::Replace,
+                    "upsert" => MySQLInsertMode::Upsert,
+                    _ => return Err(SinkError::Config(
+                        "mysql.insert_mode must be set to \"insert\", \"replace\" or \"upsert\""
+                            .to_string(),
+                    )),
+                },
+            };
+
+        Ok(MySQLConfig {
+            endpoint: get("mysql.endpoint")?,
+            table: get("mysql.table")?,
+            database: values.get("mysql.database").cloned(),
+            user: values.get("mysql.user").cloned(),
+            password: values.get("mysql.password").cloned(),
+            batch_size,
+            insert_mode,
+        })
+    }
 }
 
 // Primitive design of MySQLSink
@@ -41,7 +122,9 @@ pub struct MySQLSink {
     cfg: MySQLConfig,
 
     conn: Conn,
-    chunk_cache: Vec<(StreamChunk, Schema)>,
+    /// Whether a transaction is open for the current epoch. The statements of each chunk are
+    /// executed in it when written, and committed on commit.
+    in_transaction: bool,
 }
 
 impl MySQLSink {
@@ -64,7 +147,7 @@ impl MySQLSink {
         Ok(Self {
             cfg,
             conn,
-            chunk_cache: vec![],
+            in_transaction: false,
         })
     }
 
@@ -111,7 +194,9 @@ impl TryFrom<Datum> for MySQLValue {
                 ScalarImpl::Float64(v) => Ok(MySQLValue(f64::from(v).into())),
                 ScalarImpl::Bool(v) => Ok(MySQLValue(v.into())),
                 ScalarImpl::Decimal(Decimal::Normalized(v)) => Ok(MySQLValue(v.into())),
-                ScalarImpl::Decimal(_) => panic!("NaN, -inf, +inf are not supported by MySQL"),
+                ScalarImpl::Decimal(_) => Err(SinkError::MySQL(
+                    "NaN, -inf, +inf are not supported by MySQL".into(),
+                )),
                 ScalarImpl::Utf8(v) => Ok(MySQLValue(v.into())),
                 ScalarImpl::NaiveDate(v) => Ok(MySQLValue(format!("{}", v).into())),
                 ScalarImpl::NaiveTime(v) => Ok(MySQLValue(format!("{}", v).into())),
@@ -127,8 +212,34 @@ impl TryFrom<Datum> for MySQLValue {
 
 #[async_trait]
 impl Sink for MySQLSink {
+    /// Executes the statements of the chunk in the transaction of the epoch, so that the rows
+    /// rejected by the server fail the chunk they belong to rather than the commit. The chunk is
+    /// written atomically: if any statement fails, the ones before are rolled back to the savepoint
+    /// set before the chunk.
     async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
-        self.chunk_cache.push((chunk, schema.clone()));
+        let stmts = build_mysql_stmts(&chunk, schema, &self.cfg)?;
+        if !self.in_transaction {
+            self.conn.query_drop("START TRANSACTION").await?;
+            self.in_transaction = true;
+        }
+
+        self.conn
+            .query_drop(format!("SAVEPOINT {}", CHUNK_SAVEPOINT))
+            .await?;
+        for stmt in &stmts {
+            // TODO by doc, exec_drop will simply exec query and drop the result, we may check and
+            // retry for jitter or other reasons
+            if let Err(e) = self.conn.exec_drop(stmt, Params::Empty).await {
+                let e = map_mysql_error(e);
+                // Other errors fail the epoch, and the transaction is rolled back on abort.
+                if e.is_caused_by_rows() {
+                    self.conn
+                        .query_drop(format!("ROLLBACK TO SAVEPOINT {}", CHUNK_SAVEPOINT))
+                        .await?;
+                }
+                return Err(e);
+            }
+        }
         Ok(())
     }
 
@@ -137,27 +248,67 @@ impl Sink for MySQLSink {
     }
 
     async fn commit(&mut self) -> Result<()> {
-        let mut txn = self.conn.start_transaction(TxOpts::default()).await?;
-        for (chunk, schema) in &self.chunk_cache {
-            write_to_mysql(&mut txn, chunk, schema, &self.cfg).await?;
+        if self.in_transaction {
+            self.in_transaction = false;
+            self.conn.query_drop("COMMIT").await?;
         }
-        txn.commit().await?;
-
-        self.chunk_cache.clear();
         Ok(())
     }
 
     async fn abort(&mut self) -> Result<()> {
+        if self.in_transaction {
+            self.in_transaction = false;
+            self.conn.query_drop("ROLLBACK").await?;
+        }
         Ok(())
     }
 }
 
-async fn write_to_mysql<'a>(
-    txn: &mut Transaction<'a>,
+/// The savepoint set before writing each chunk, to roll back the chunk on failure.
+const CHUNK_SAVEPOINT: &str = "rw_sink_chunk";
+
+fn map_mysql_error(e: mysql_async::Error) -> SinkError {
+    match &e {
+        mysql_async::Error::Server(ServerError { code, .. })
+            if *code == ER_NET_PACKET_TOO_LARGE =>
+        {
+            SinkError::MySQL(format!(
+                "statement exceeds max_allowed_packet of the MySQL server, consider a smaller \
+                 mysql.batch_size or a larger max_allowed_packet: {}",
+                e
+            ))
+        }
+        _ => e.into(),
+    }
+}
+
+/// Builds a statement that writes the `rows` of values, i.e. `(1,'a')`, in one go.
+fn build_insert_stmt(rows: &[String], schema: &Schema, config: &MySQLConfig) -> String {
+    match config.insert_mode {
+        MySQLInsertMode::Insert => {
+            format!("INSERT INTO {} VALUES {};", &config.table, rows.join(","))
+        }
+        MySQLInsertMode::Replace => {
+            format!("REPLACE INTO {} VALUES {};", &config.table, rows.join(","))
+        }
+        MySQLInsertMode::Upsert => format!(
+            "INSERT INTO {} VALUES {} ON DUPLICATE KEY UPDATE {};",
+            &config.table,
+            rows.join(","),
+            schema
+                .names()
+                .iter()
+                .map(|c| format!("{}=VALUES({})", c, c))
+                .join(",")
+        ),
+    }
+}
+
+fn build_mysql_stmts(
     chunk: &StreamChunk,
     schema: &Schema,
     config: &MySQLConfig,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     // Closure that takes an idx to create a vector of MySQLValues from a StreamChunk 'row'.
     let values = |idx| -> Result<Vec<MySQLValue>> {
         chunk
@@ -180,15 +331,26 @@ async fn write_to_mysql<'a>(
             .collect::<Vec<String>>()
     };
 
+    let mut stmts = vec![];
+    // Consecutive inserted rows are batched into one statement.
+    let mut insert_rows = vec![];
     let mut iter = chunk.ops().iter().enumerate();
     while let Some((idx, op)) = iter.next() {
+        if *op == Insert {
+            insert_rows.push(format!("({})", join(values(idx)?, ",")));
+            if insert_rows.len() >= config.batch_size {
+                stmts.push(build_insert_stmt(&insert_rows, schema, config));
+                insert_rows.clear();
+            }
+            continue;
+        }
+        if !insert_rows.is_empty() {
+            stmts.push(build_insert_stmt(&insert_rows, schema, config));
+            insert_rows.clear();
+        }
+
         // Get SQL statement
         let stmt = match *op {
-            Insert => format!(
-                "INSERT INTO {} VALUES ({});",
-                &config.table,
-                join(values(idx)?, ",")
-            ),
             Delete => format!(
                 "DELETE FROM {} WHERE ({});",
                 &config.table,
@@ -210,12 +372,13 @@ async fn write_to_mysql<'a>(
             }
             _ => return Err(SinkError::MySQL("Unsupported operation".into())),
         };
-        // TODO by doc, exec_drop will simply exec query and drop the result, we may check and retry
-        // for jitter or other reasons
-        txn.exec_drop(stmt, Params::Empty).await?;
+        stmts.push(stmt);
+    }
+    if !insert_rows.is_empty() {
+        stmts.push(build_insert_stmt(&insert_rows, schema, config));
     }
 
-    Ok(())
+    Ok(stmts)
 }
 
 #[cfg(test)]
@@ -292,6 +455,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_build_mysql_stmts() {
+        let config = MySQLConfig::from_hashmap(HashMap::from([
+            ("mysql.endpoint".to_string(), "127.0.0.1:3306".to_string()),
+            ("mysql.table".to_string(), "t".to_string()),
+            ("mysql.batch_size".to_string(), "2".to_string()),
+            ("mysql.insert_mode".to_string(), "upsert".to_string()),
+        ]))
+        .unwrap();
+        let schema = Schema::new(vec![Field::with_name(DataType::Int32, "v1")]);
+        let chunk = StreamChunk::new(
+            vec![Op::Insert, Op::Insert, Op::Insert, Op::Delete, Op::Insert],
+            vec![Column::new(Arc::new(ArrayImpl::from(array!(
+                I32Array,
+                [Some(1), Some(2), Some(3), Some(4), Some(5)]
+            ))))],
+            None,
+        );
+        assert_eq!(
+            build_mysql_stmts(&chunk, &schema, &config).unwrap(),
+            vec![
+                "INSERT INTO t VALUES (1),(2) ON DUPLICATE KEY UPDATE v1=VALUES(v1);",
+                "INSERT INTO t VALUES (3) ON DUPLICATE KEY UPDATE v1=VALUES(v1);",
+                "DELETE FROM t WHERE (v1=4);",
+                "INSERT INTO t VALUES (5) ON DUPLICATE KEY UPDATE v1=VALUES(v1);",
+            ]
+        );
+    }
+
     #[ignore]
     #[tokio::test]
     async fn test_drop() -> Result<()> {
@@ -301,6 +493,8 @@ mod test {
             database: Some("test".into()),
             user: Some("root".into()),
             password: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            insert_mode: MySQLInsertMode::Insert,
         };
         let mut sink = MySQLSink::new(config.clone()).await?;
 
@@ -0,0 +1,157 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+use futures::future::try_join_all;
+use pulsar::producer::SendFuture;
+use pulsar::{Authentication, Producer, Pulsar, TokioExecutor};
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::catalog::Schema;
+use serde_json::Value;
+
+use super::kafka::record_to_json;
+use super::{Sink, SinkError};
+use crate::sink::Result;
+
+pub const PULSAR_SINK: &str = "pulsar";
+
+#[derive(Debug, Clone)]
+pub struct PulsarConfig {
+    pub service_url: String,
+    pub topic: String,
+    // Optional. If set, the sink authenticates to the cluster with this JWT token.
+    pub auth_token: Option<String>,
+}
+
+impl PulsarConfig {
+    pub fn from_hashmap(values: HashMap<String, String>) -> Result<Self> {
+        let service_url = values
+            .get("pulsar.service.url")
+            .ok_or_else(|| SinkError::Config("pulsar.service.url must be set".to_string()))?;
+        let topic = values
+            .get("pulsar.topic")
+            .ok_or_else(|| SinkError::Config("pulsar.topic must be set".to_string()))?;
+
+        Ok(PulsarConfig {
+            service_url: service_url.to_string(),
+            topic: topic.to_string(),
+            auth_token: values.get("pulsar.auth.token").cloned(),
+        })
+    }
+}
+
+/// Sinks the inserted rows of a stream to a Pulsar topic as JSON messages.
+///
+/// The Pulsar client we use doesn't support transactions, so the sink provides at-least-once
+/// delivery: `commit` waits until all messages of the epoch are acknowledged by the broker, while
+/// `abort` can't retract the messages that have already been sent, and they may be sent again
+/// after recovery.
+pub struct PulsarSink {
+    pub config: PulsarConfig,
+    producer: Producer<TokioExecutor>,
+    /// Receipts of the messages sent in the current epoch.
+    pending: Vec<SendFuture>,
+}
+
+impl PulsarSink {
+    pub async fn new(config: PulsarConfig) -> Result<Self> {
+        let mut builder = Pulsar::builder(config.service_url.as_str(), TokioExecutor);
+        if let Some(token) = &config.auth_token {
+            builder = builder.with_auth(Authentication {
+                name: "token".to_string(),
+                data: token.clone().into_bytes(),
+            });
+        }
+        let pulsar: Pulsar<_> = builder.build().await?;
+        let producer = pulsar
+            .producer()
+            .with_topic(config.topic.as_str())
+            .build()
+            .await?;
+
+        Ok(PulsarSink {
+            config,
+            producer,
+            pending: vec![],
+        })
+    }
+
+    async fn append_only(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        for (op, row) in chunk.rows() {
+            if op == Op::Insert {
+                let record = Value::Object(record_to_json(row, schema.fields.clone())?).to_string();
+                let receipt = self.producer.send(record.into_bytes()).await?;
+                self.pending.push(receipt);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Sink for PulsarSink {
+    async fn write_batch(&mut self, chunk: StreamChunk, schema: &Schema) -> Result<()> {
+        self.append_only(chunk, schema).await
+    }
+
+    async fn begin_epoch(&mut self, epoch: u64) -> Result<()> {
+        tracing::debug!("begin epoch {:?}", epoch);
+        Ok(())
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        try_join_all(self.pending.drain(..)).await?;
+        Ok(())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        // Sent messages can't be retracted without transactions.
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl Debug for PulsarSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PulsarSink")
+            .field("config", &self.config)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+
+    use super::*;
+
+    #[test]
+    fn test_pulsar_config() {
+        let config = PulsarConfig::from_hashmap(hashmap! {
+            "pulsar.service.url".to_string() => "pulsar://localhost:6650".to_string(),
+            "pulsar.topic".to_string() => "t".to_string(),
+        })
+        .unwrap();
+        assert_eq!(config.topic, "t");
+        assert!(config.auth_token.is_none());
+
+        assert!(PulsarConfig::from_hashmap(hashmap! {
+            "pulsar.topic".to_string() => "t".to_string(),
+        })
+        .is_err());
+    }
+}
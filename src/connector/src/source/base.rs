@@ -35,6 +35,10 @@ use crate::source::kinesis::enumerator::client::KinesisSplitEnumerator;
 use crate::source::kinesis::source::reader::KinesisMultiSplitReader;
 use crate::source::kinesis::split::KinesisSplit;
 use crate::source::kinesis::{KinesisProperties, KINESIS_CONNECTOR};
+use crate::source::mysql_cdc::source::reader::MySqlCdcSplitReader;
+use crate::source::mysql_cdc::{
+    MySqlCdcProperties, MySqlCdcSplit, MySqlCdcSplitEnumerator, MYSQL_CDC_CONNECTOR,
+};
 use crate::source::nexmark::source::reader::NexmarkSplitReader;
 use crate::source::nexmark::{
     NexmarkProperties, NexmarkSplit, NexmarkSplitEnumerator, NEXMARK_CONNECTOR,
@@ -81,6 +85,7 @@ pub enum SplitImpl {
     Kinesis(KinesisSplit),
     Nexmark(NexmarkSplit),
     Datagen(DatagenSplit),
+    MySqlCdc(MySqlCdcSplit),
 }
 
 pub enum SplitReaderImpl {
@@ -90,6 +95,7 @@ pub enum SplitReaderImpl {
     Nexmark(Box<NexmarkSplitReader>),
     Pulsar(Box<PulsarSplitReader>),
     Datagen(Box<DatagenSplitReader>),
+    MySqlCdc(Box<MySqlCdcSplitReader>),
 }
 
 pub enum SplitEnumeratorImpl {
@@ -98,6 +104,7 @@ pub enum SplitEnumeratorImpl {
     Kinesis(KinesisSplitEnumerator),
     Nexmark(NexmarkSplitEnumerator),
     Datagen(DatagenSplitEnumerator),
+    MySqlCdc(MySqlCdcSplitEnumerator),
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -107,6 +114,7 @@ pub enum ConnectorProperties {
     Kinesis(KinesisProperties),
     Nexmark(NexmarkProperties),
     Datagen(DatagenProperties),
+    MySqlCdc(MySqlCdcProperties),
     S3(S3Properties),
     Dummy(()),
 }
@@ -118,6 +126,7 @@ impl_connector_properties! {
     { Kinesis, KINESIS_CONNECTOR },
     { Nexmark, NEXMARK_CONNECTOR },
     { Datagen, DATAGEN_CONNECTOR },
+    { MySqlCdc, MYSQL_CDC_CONNECTOR },
     { S3, S3_CONNECTOR }
 }
 
@@ -127,7 +136,8 @@ impl_split_enumerator! {
     { Pulsar, PulsarSplitEnumerator },
     { Kinesis, KinesisSplitEnumerator },
     { Nexmark, NexmarkSplitEnumerator },
-    { Datagen, DatagenSplitEnumerator }
+    { Datagen, DatagenSplitEnumerator },
+    { MySqlCdc, MySqlCdcSplitEnumerator }
 }
 
 impl_split! {
@@ -136,7 +146,8 @@ impl_split! {
     { Pulsar, PULSAR_CONNECTOR, PulsarSplit },
     { Kinesis, KINESIS_CONNECTOR, KinesisSplit },
     { Nexmark, NEXMARK_CONNECTOR, NexmarkSplit },
-    { Datagen, DATAGEN_CONNECTOR, DatagenSplit }
+    { Datagen, DATAGEN_CONNECTOR, DatagenSplit },
+    { MySqlCdc, MYSQL_CDC_CONNECTOR, MySqlCdcSplit }
 }
 
 impl_split_reader! {
@@ -146,6 +157,7 @@ impl_split_reader! {
     { Kinesis, KinesisMultiSplitReader },
     { Nexmark, NexmarkSplitReader },
     { Datagen, DatagenSplitReader },
+    { MySqlCdc, MySqlCdcSplitReader },
     { Dummy, DummySplitReader }
 }
 
@@ -171,6 +183,12 @@ pub trait SplitMetaData: Sized {
     fn id(&self) -> String;
     fn encode_to_bytes(&self) -> Bytes;
     fn restore_from_bytes(bytes: &[u8]) -> Result<Self>;
+
+    /// The ids of the splits whose data precedes this split's, e.g. the parent shards of a split
+    /// or merged Kinesis shard. The split should be read after its parents are drained.
+    fn parent_ids(&self) -> Vec<String> {
+        vec![]
+    }
 }
 
 /// [`ConnectorState`] maintains the consuming splits' info. In specific split readers,
@@ -55,12 +55,12 @@ impl KafkaSplit {
         }
     }
 
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
-        Self::new(
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        Ok(Self::new(
             self.partition,
-            Some(start_offset.as_str().parse::<i64>().unwrap()),
+            Some(start_offset.as_str().parse::<i64>()?),
             self.stop_offset,
             self.topic.clone(),
-        )
+        ))
     }
 }
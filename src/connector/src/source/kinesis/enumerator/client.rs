@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use aws_sdk_kinesis::model::Shard;
 use aws_sdk_kinesis::Client as kinesis_client;
@@ -24,9 +24,47 @@ use crate::source::SplitEnumerator;
 pub struct KinesisSplitEnumerator {
     stream_name: String,
     client: kinesis_client,
+    /// Where to start reading the shards listed for the first time.
+    start_position: KinesisOffset,
+    /// Whether the shards have been listed, after which the new shards must be the children of
+    /// split or merged shards, and are read from the earliest so that no record is lost.
+    listed: bool,
 }
 
-impl KinesisSplitEnumerator {}
+impl KinesisSplitEnumerator {
+    fn start_position(properties: &KinesisProperties) -> Result<KinesisOffset> {
+        let start_position = match properties
+            .scan_startup_mode
+            .as_ref()
+            .map(|s| s.to_lowercase())
+            .as_deref()
+        {
+            None | Some("earliest") | Some("trim_horizon") => KinesisOffset::Earliest,
+            Some("latest") => KinesisOffset::Latest,
+            Some("sequence_number") => {
+                let sequence_number =
+                    properties
+                        .scan_startup_sequence_number
+                        .clone()
+                        .ok_or_else(|| {
+                            anyhow!(
+                                "properties `{}` must be set with startup mode `sequence_number`",
+                                KINESIS_SCAN_STARTUP_SEQUENCE_NUMBER
+                            )
+                        })?;
+                KinesisOffset::AtSequenceNumber(sequence_number)
+            }
+            Some(mode) => {
+                return Err(anyhow!(
+                    "properties `{}` only support earliest, latest and sequence_number, got {}",
+                    KINESIS_SCAN_STARTUP_MODE,
+                    mode
+                ));
+            }
+        };
+        Ok(start_position)
+    }
+}
 
 #[async_trait]
 impl SplitEnumerator for KinesisSplitEnumerator {
@@ -34,11 +72,14 @@ impl SplitEnumerator for KinesisSplitEnumerator {
     type Split = KinesisSplit;
 
     async fn new(properties: KinesisProperties) -> Result<Self> {
+        let start_position = Self::start_position(&properties)?;
         let client = build_client(properties.clone()).await?;
         let stream_name = properties.stream_name.clone();
         Ok(Self {
             stream_name,
             client,
+            start_position,
+            listed: false,
         })
     }
 
@@ -69,12 +110,24 @@ impl SplitEnumerator for KinesisSplitEnumerator {
                 None => break,
             }
         }
+        let start_position = if self.listed {
+            KinesisOffset::Earliest
+        } else {
+            self.start_position.clone()
+        };
+        self.listed = true;
         Ok(shard_collect
             .into_iter()
             .map(|x| KinesisSplit {
                 shard_id: x.shard_id().unwrap_or_default().to_string(),
-                start_position: KinesisOffset::None,
+                start_position: start_position.clone(),
                 end_position: KinesisOffset::None,
+                parent_shard_ids: x
+                    .parent_shard_id()
+                    .into_iter()
+                    .chain(x.adjacent_parent_shard_id())
+                    .map(String::from)
+                    .collect(),
             })
             .collect())
     }
@@ -86,6 +139,41 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_start_position() {
+        let properties = |mode: Option<&str>, sequence_number: Option<&str>| KinesisProperties {
+            stream_name: "kinesis_debug".to_string(),
+            stream_region: "cn-northwest-1".to_string(),
+            endpoint: None,
+            credentials_access_key: None,
+            credentials_secret_access_key: None,
+            session_token: None,
+            assume_role_arn: None,
+            assume_role_external_id: None,
+            scan_startup_mode: mode.map(String::from),
+            scan_startup_sequence_number: sequence_number.map(String::from),
+        };
+        let start_position = |mode, sequence_number| {
+            KinesisSplitEnumerator::start_position(&properties(mode, sequence_number))
+        };
+
+        assert_eq!(start_position(None, None).unwrap(), KinesisOffset::Earliest);
+        assert_eq!(
+            start_position(Some("trim_horizon"), None).unwrap(),
+            KinesisOffset::Earliest
+        );
+        assert_eq!(
+            start_position(Some("Latest"), None).unwrap(),
+            KinesisOffset::Latest
+        );
+        assert_eq!(
+            start_position(Some("sequence_number"), Some("42")).unwrap(),
+            KinesisOffset::AtSequenceNumber("42".to_string())
+        );
+        assert!(start_position(Some("sequence_number"), None).is_err());
+        assert!(start_position(Some("timestamp"), None).is_err());
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_kinesis_split_enumerator() -> Result<()> {
@@ -98,6 +186,8 @@ mod tests {
         let mut enumerator = KinesisSplitEnumerator {
             stream_name,
             client,
+            start_position: KinesisOffset::Earliest,
+            listed: false,
         };
         let list_splits_resp = enumerator.list_splits().await?;
         println!("{:#?}", list_splits_resp);
@@ -27,6 +27,8 @@ const KINESIS_CREDENTIALS_SECRET_ACCESS_KEY: &str = "kinesis.credentials.secret"
 const KINESIS_CREDENTIALS_SESSION_TOKEN: &str = "kinesis.credentials.session_token";
 const KINESIS_ASSUMEROLE_ARN: &str = "kinesis.assumerole.arn";
 const KINESIS_ASSUMEROLE_EXTERNAL_ID: &str = "kinesis.assumerole.external_id";
+const KINESIS_SCAN_STARTUP_MODE: &str = "kinesis.scan.startup.mode";
+const KINESIS_SCAN_STARTUP_SEQUENCE_NUMBER: &str = "kinesis.scan.startup.sequence_number";
 
 use serde::Deserialize;
 
@@ -50,4 +52,10 @@ pub struct KinesisProperties {
     pub assume_role_arn: Option<String>,
     #[serde(rename = "kinesis.assumerole.external_id")]
     pub assume_role_external_id: Option<String>,
+    /// Where to start reading the shards: `earliest` (the trim horizon, by default), `latest`, or
+    /// `sequence_number` at `kinesis.scan.startup.sequence_number`.
+    #[serde(rename = "kinesis.scan.startup.mode")]
+    pub scan_startup_mode: Option<String>,
+    #[serde(rename = "kinesis.scan.startup.sequence_number")]
+    pub scan_startup_sequence_number: Option<String>,
 }
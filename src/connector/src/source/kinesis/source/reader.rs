@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use core::result::Result::Ok;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::{anyhow, Result};
@@ -40,6 +41,8 @@ pub struct KinesisMultiSplitReader {
     properties: KinesisProperties,
     message_cache: Arc<Mutex<Vec<SourceMessage>>>,
     consumer_handler: Option<JoinHandle<()>>,
+    /// Set once all the shards are read, e.g. closed by a resharding.
+    finished: Arc<AtomicBool>,
 }
 
 impl Drop for KinesisMultiSplitReader {
@@ -76,14 +79,20 @@ impl KinesisSplitReader {
         })
     }
 
-    pub async fn next(&mut self) -> Result<Vec<SourceMessage>> {
+    /// Returns the next records of the shard, or `None` if the shard is closed by a split or
+    /// merge and all of its records are read. The records of the shard's children are read by the
+    /// splits discovered after the resharding.
+    pub async fn next(&mut self) -> Result<Option<Vec<SourceMessage>>> {
         if self.shard_iter.is_none() {
             self.new_shard_iter().await?;
         }
-        assert!(self.shard_iter.is_some());
         loop {
+            if self.shard_iter.is_none() {
+                return Ok(None);
+            }
             match self.get_records().await {
                 Ok(resp) => {
+                    // There's no next iterator once the end of a closed shard is reached.
                     self.shard_iter = resp.next_shard_iterator().map(String::from);
                     let chunk = resp
                         .records()
@@ -97,11 +106,13 @@ impl KinesisSplitReader {
                         })
                         .collect::<Vec<SourceMessage>>();
                     if chunk.is_empty() {
-                        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                        if self.shard_iter.is_some() {
+                            tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                        }
                         continue;
                     }
                     self.latest_offset = Some(chunk.last().unwrap().offset.clone());
-                    return Ok(chunk);
+                    return Ok(Some(chunk));
                 }
                 Err(e) => match e {
                     SdkError::ServiceError { err, .. } if err.is_expired_iterator_exception() => {
@@ -124,9 +135,13 @@ impl KinesisSplitReader {
         } else {
             match &self.start_position {
                 KinesisOffset::Earliest => (None, ShardIteratorType::TrimHorizon),
+                KinesisOffset::Latest => (None, ShardIteratorType::Latest),
                 KinesisOffset::SequenceNumber(seq) => {
                     (Some(seq.clone()), ShardIteratorType::AfterSequenceNumber)
                 }
+                KinesisOffset::AtSequenceNumber(seq) => {
+                    (Some(seq.clone()), ShardIteratorType::AtSequenceNumber)
+                }
                 _ => unreachable!(),
             }
         };
@@ -161,7 +176,11 @@ impl KinesisSplitReader {
 async fn split_reader_into_stream(mut reader: KinesisSplitReader) {
     loop {
         match reader.next().await {
-            Ok(chunk) => yield chunk,
+            Ok(Some(chunk)) => yield chunk,
+            Ok(None) => {
+                log::info!("kinesis shard {} is closed", reader.shard_id);
+                break;
+            }
             Err(e) => {
                 log::error!("hang up kinesis reader due to polling error: {}", e);
                 drop(reader);
@@ -195,6 +214,7 @@ impl SplitReader for KinesisMultiSplitReader {
             properties,
             message_cache: Arc::new(Mutex::new(Vec::new())),
             consumer_handler: None,
+            finished: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -212,6 +232,7 @@ impl SplitReader for KinesisMultiSplitReader {
             )
             .await;
             let cache = Arc::clone(&self.message_cache);
+            let finished = Arc::clone(&self.finished);
 
             self.consumer_handler = Some(tokio::spawn(async move {
                 let join_stream = split_readers
@@ -232,12 +253,18 @@ impl SplitReader for KinesisMultiSplitReader {
                         }
                     }
                 }
+                finished.store(true, Ordering::Release);
             }));
             log::info!("launch kinesis reader with splits: {:?}", self.splits);
         }
         loop {
+            // Checked before the cache, so that the messages cached before finishing are returned.
+            let finished = self.finished.load(Ordering::Acquire);
             let mut cache_lock = self.message_cache.lock().await;
             if cache_lock.is_empty() {
+                if finished {
+                    return Ok(None);
+                }
                 drop(cache_lock);
                 tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
                 continue;
@@ -273,6 +300,8 @@ mod tests {
             endpoint: None,
             session_token: None,
             assume_role_external_id: None,
+            scan_startup_mode: None,
+            scan_startup_sequence_number: None,
         };
 
         let mut trim_horizen_reader = KinesisSplitReader::new(
@@ -281,6 +310,7 @@ mod tests {
                 shard_id: "shardId-000000000001".to_string(),
                 start_position: KinesisOffset::Earliest,
                 end_position: KinesisOffset::None,
+                parent_shard_ids: vec![],
             },
         )
         .await?;
@@ -295,6 +325,7 @@ mod tests {
                     "49629139817504901062972448413535783695568426186596941842".to_string(),
                 ),
                 end_position: KinesisOffset::None,
+                parent_shard_ids: vec![],
             },
         )
         .await?;
@@ -324,6 +355,8 @@ mod tests {
             endpoint: None,
             session_token: None,
             assume_role_external_id: None,
+            scan_startup_mode: None,
+            scan_startup_sequence_number: None,
         };
 
         let splits = vec!["shardId-000000000000", "shardId-000000000001"]
@@ -333,6 +366,7 @@ mod tests {
                     shard_id: split.to_string(),
                     start_position: KinesisOffset::Earliest,
                     end_position: KinesisOffset::None,
+                    parent_shard_ids: vec![],
                 })
             })
             .collect::<Vec<_>>();
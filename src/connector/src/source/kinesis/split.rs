@@ -22,7 +22,10 @@ use crate::source::SplitMetaData;
 pub enum KinesisOffset {
     Earliest,
     Latest,
+    /// Start after the record with the sequence number, i.e. the last record consumed.
     SequenceNumber(String),
+    /// Start at the record with the sequence number, given by the startup options.
+    AtSequenceNumber(String),
     Timestamp(i64),
     None,
 }
@@ -32,6 +35,10 @@ pub struct KinesisSplit {
     pub(crate) shard_id: String,
     pub(crate) start_position: KinesisOffset,
     pub(crate) end_position: KinesisOffset,
+    /// The shards this shard is split or merged from, persisted with the split so that the
+    /// lineage survives the expiry of the parent shards.
+    #[serde(default)]
+    pub(crate) parent_shard_ids: Vec<String>,
 }
 
 impl SplitMetaData for KinesisSplit {
@@ -46,6 +53,10 @@ impl SplitMetaData for KinesisSplit {
     fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
         serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
     }
+
+    fn parent_ids(&self) -> Vec<String> {
+        self.parent_shard_ids.clone()
+    }
 }
 
 impl KinesisSplit {
@@ -58,19 +69,49 @@ impl KinesisSplit {
             shard_id,
             start_position,
             end_position,
+            parent_shard_ids: vec![],
         }
     }
 
-    pub fn copy_with_offset(&self, start_offset: String) -> Self {
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
         let start_offset = if start_offset.is_empty() {
             KinesisOffset::Earliest
         } else {
             KinesisOffset::SequenceNumber(start_offset)
         };
-        Self::new(
-            self.shard_id.clone(),
-            start_offset,
-            self.end_position.clone(),
+        Ok(Self {
+            shard_id: self.shard_id.clone(),
+            start_position: start_offset,
+            end_position: self.end_position.clone(),
+            parent_shard_ids: self.parent_shard_ids.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_split_without_parents() {
+        let split = KinesisSplit::restore_from_bytes(
+            br#"{"shard_id":"shardId-0","start_position":"Earliest","end_position":"None"}"#,
         )
+        .unwrap();
+        assert!(split.parent_ids().is_empty());
+
+        let split = KinesisSplit {
+            parent_shard_ids: vec!["shardId-0".to_string()],
+            ..split
+        };
+        let restored = KinesisSplit::restore_from_bytes(&split.encode_to_bytes()).unwrap();
+        assert_eq!(restored.parent_ids(), vec!["shardId-0".to_string()]);
+        assert_eq!(
+            restored
+                .copy_with_offset("1".to_string())
+                .unwrap()
+                .parent_ids(),
+            vec!["shardId-0".to_string()]
+        );
     }
 }
@@ -0,0 +1,41 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use async_trait::async_trait;
+
+use crate::source::mysql_cdc::{MySqlCdcProperties, MySqlCdcSplit};
+use crate::source::SplitEnumerator;
+
+/// A MySQL CDC source reads one table, so there is always one split, which starts with a
+/// snapshot.
+#[derive(Debug, Clone)]
+pub struct MySqlCdcSplitEnumerator {
+    table_name: String,
+}
+
+#[async_trait]
+impl SplitEnumerator for MySqlCdcSplitEnumerator {
+    type Properties = MySqlCdcProperties;
+    type Split = MySqlCdcSplit;
+
+    async fn new(properties: MySqlCdcProperties) -> anyhow::Result<Self> {
+        Ok(Self {
+            table_name: format!("{}.{}", properties.database_name, properties.table_name),
+        })
+    }
+
+    async fn list_splits(&mut self) -> anyhow::Result<Vec<MySqlCdcSplit>> {
+        Ok(vec![MySqlCdcSplit::new(self.table_name.clone(), None)])
+    }
+}
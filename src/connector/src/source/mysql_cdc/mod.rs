@@ -0,0 +1,59 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A CDC source of a MySQL table, which takes a consistent snapshot of the table and then tails
+//! the binlog from the position of the snapshot.
+//!
+//! The changes are emitted as Debezium-compatible JSON, so the source is created with
+//! `ROW FORMAT DEBEZIUM_JSON`, and the op column of the chunks comes from the Debezium parser.
+//! See `docs/data-source.md` for how the binlog position is checkpointed.
+
+pub mod enumerator;
+pub mod source;
+pub mod split;
+
+pub use enumerator::*;
+use serde::Deserialize;
+pub use split::*;
+
+pub const MYSQL_CDC_CONNECTOR: &str = "mysql-cdc";
+
+/// The server id of the reader, which must be unique among the replicas of the upstream.
+const DEFAULT_SERVER_ID: &str = "5400";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MySqlCdcProperties {
+    #[serde(rename = "mysql.hostname")]
+    pub hostname: String,
+    #[serde(rename = "mysql.port", default = "default_port")]
+    pub port: String,
+    #[serde(rename = "mysql.user")]
+    pub user: String,
+    #[serde(rename = "mysql.password")]
+    pub password: Option<String>,
+    #[serde(rename = "mysql.database.name")]
+    pub database_name: String,
+    #[serde(rename = "mysql.table.name")]
+    pub table_name: String,
+    #[serde(rename = "mysql.server.id", default = "default_server_id")]
+    pub server_id: String,
+}
+
+fn default_port() -> String {
+    "3306".to_string()
+}
+
+fn default_server_id() -> String {
+    DEFAULT_SERVER_ID.to_string()
+}
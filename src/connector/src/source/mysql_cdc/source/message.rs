@@ -0,0 +1,182 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use itertools::Itertools;
+use mysql_async::Value;
+use risingwave_common::types::Decimal;
+use serde_json::json;
+
+use crate::source::SourceMessage;
+
+pub const DEBEZIUM_READ_OP: &str = "r";
+pub const DEBEZIUM_CREATE_OP: &str = "c";
+pub const DEBEZIUM_UPDATE_OP: &str = "u";
+pub const DEBEZIUM_DELETE_OP: &str = "d";
+
+/// A column of the upstream table, with its type as in `information_schema.COLUMNS.DATA_TYPE`.
+#[derive(Clone, Debug)]
+pub struct MySqlColumn {
+    pub name: String,
+    pub data_type: String,
+}
+
+/// Converts a row of the upstream table to the JSON object of the Debezium format.
+pub fn row_to_json(
+    columns: &[MySqlColumn],
+    values: Vec<Value>,
+) -> anyhow::Result<BTreeMap<String, serde_json::Value>> {
+    if columns.len() != values.len() {
+        return Err(anyhow!(
+            "expect {} columns, got {}, the schema of the upstream table may have changed",
+            columns.len(),
+            values.len()
+        ));
+    }
+    columns
+        .iter()
+        .zip_eq(values)
+        .map(|(column, value)| {
+            let value = value_to_json(value, &column.data_type).map_err(|e| {
+                anyhow!(
+                    "failed to convert the value of column {}: {}",
+                    column.name,
+                    e
+                )
+            })?;
+            Ok((column.name.clone(), value))
+        })
+        .collect()
+}
+
+fn value_to_json(value: Value, data_type: &str) -> anyhow::Result<serde_json::Value> {
+    let value = match value {
+        Value::NULL => serde_json::Value::Null,
+        Value::Int(v) => v.into(),
+        Value::UInt(v) => v.into(),
+        Value::Float(v) => (v as f64).into(),
+        Value::Double(v) => v.into(),
+        // Decimals are sent as text in both the binary protocol and the binlog, which is kept as
+        // is to not lose precision.
+        Value::Bytes(bytes) if data_type == "decimal" => {
+            let text = String::from_utf8(bytes)?;
+            text.parse::<Decimal>()
+                .map_err(|e| anyhow!("invalid decimal {}: {}", text, e))?;
+            text.into()
+        }
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned().into(),
+        Value::Date(year, month, day, ..) if data_type == "date" => {
+            format!("{:04}-{:02}-{:02}", year, month, day).into()
+        }
+        Value::Date(year, month, day, hour, minute, second, micros) => format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}",
+            year, month, day, hour, minute, second, micros
+        )
+        .into(),
+        Value::Time(is_negative, days, hours, minutes, seconds, micros) => format!(
+            "{}{:02}:{:02}:{:02}.{:06}",
+            if is_negative { "-" } else { "" },
+            days * 24 + hours as u32,
+            minutes,
+            seconds,
+            micros
+        )
+        .into(),
+    };
+    Ok(value)
+}
+
+/// Encodes a change of a row as a Debezium JSON message. The offset is set later, when the
+/// transaction of the change commits.
+pub fn debezium_message(
+    split_id: &str,
+    op: &str,
+    before: Option<BTreeMap<String, serde_json::Value>>,
+    after: Option<BTreeMap<String, serde_json::Value>>,
+    ts_ms: i64,
+) -> SourceMessage {
+    let event = json!({
+        "payload": {
+            "before": before,
+            "after": after,
+            "op": op,
+            "ts_ms": ts_ms,
+        }
+    });
+    SourceMessage {
+        payload: Some(Bytes::from(event.to_string())),
+        offset: String::new(),
+        split_id: split_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_to_json() {
+        let columns = vec![
+            MySqlColumn {
+                name: "id".to_string(),
+                data_type: "int".to_string(),
+            },
+            MySqlColumn {
+                name: "price".to_string(),
+                data_type: "decimal".to_string(),
+            },
+            MySqlColumn {
+                name: "created_at".to_string(),
+                data_type: "datetime".to_string(),
+            },
+            MySqlColumn {
+                name: "name".to_string(),
+                data_type: "varchar".to_string(),
+            },
+        ];
+        let row = row_to_json(
+            &columns,
+            vec![
+                Value::Int(1),
+                Value::Bytes(b"1.50".to_vec()),
+                Value::Date(2022, 8, 1, 12, 30, 0, 0),
+                Value::NULL,
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(row).unwrap(),
+            json!({
+                "id": 1,
+                "price": "1.50",
+                "created_at": "2022-08-01 12:30:00.000000",
+                "name": null,
+            })
+        );
+
+        assert!(row_to_json(&columns, vec![Value::Int(1)]).is_err());
+
+        // Decimals beyond the precision of f64 are kept.
+        let row = row_to_json(
+            &columns[1..2],
+            vec![Value::Bytes(b"12345678901234567890.123".to_vec())],
+        )
+        .unwrap();
+        assert_eq!(row["price"], json!("12345678901234567890.123"));
+        assert!(row_to_json(&columns[1..2], vec![Value::Bytes(b"1.2.3".to_vec())]).is_err());
+    }
+}
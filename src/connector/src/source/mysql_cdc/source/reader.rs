@@ -0,0 +1,375 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::mem;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use mysql_async::binlog::events::EventData;
+use mysql_async::binlog::row::BinlogRow;
+use mysql_async::binlog::value::BinlogValue;
+use mysql_async::prelude::*;
+use mysql_async::{BinlogRequest, Conn, Opts, OptsBuilder, Row};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::message::*;
+use crate::source::mysql_cdc::{BinlogPosition, MySqlCdcProperties};
+use crate::source::{Column, ConnectorState, SourceMessage, SplitImpl, SplitReader};
+
+/// The max number of rows of the snapshot in a batch.
+const SNAPSHOT_BATCH_SIZE: usize = 1024;
+const CHANNEL_SIZE: usize = 16;
+
+/// Reads the changes of a MySQL table in a background task: first a consistent snapshot of the
+/// table, and then the binlog from the position of the snapshot.
+///
+/// A batch returned by `next` always ends at a transaction boundary, with the offset of its
+/// messages set to the binlog position after the commit, so recovering from the checkpointed
+/// offset neither misses nor repeats any change.
+pub struct MySqlCdcSplitReader {
+    rx: mpsc::Receiver<Result<Vec<SourceMessage>>>,
+    handle: JoinHandle<()>,
+}
+
+struct MySqlCdcReaderInner {
+    opts: Opts,
+    server_id: u32,
+    database_name: String,
+    table_name: String,
+    split_id: String,
+    columns: Vec<MySqlColumn>,
+    tx: mpsc::Sender<Result<Vec<SourceMessage>>>,
+}
+
+#[async_trait]
+impl SplitReader for MySqlCdcSplitReader {
+    type Properties = MySqlCdcProperties;
+
+    async fn new(
+        properties: MySqlCdcProperties,
+        state: ConnectorState,
+        _columns: Option<Vec<Column>>,
+    ) -> Result<Self> {
+        let split = state
+            .unwrap()
+            .into_iter()
+            .find_map(|split| match split {
+                SplitImpl::MySqlCdc(split) => Some(split),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("no MySQL CDC split is assigned"))?;
+
+        let opts: Opts = OptsBuilder::default()
+            .ip_or_hostname(properties.hostname.clone())
+            .tcp_port(properties.port.parse()?)
+            .user(Some(properties.user.clone()))
+            .pass(properties.password.clone())
+            .db_name(Some(properties.database_name.clone()))
+            .into();
+
+        // The binlog doesn't carry the column names, so they are fetched ahead.
+        let mut conn = Conn::new(opts.clone()).await?;
+        let columns: Vec<(String, String)> = conn
+            .exec(
+                "SELECT COLUMN_NAME, DATA_TYPE FROM information_schema.COLUMNS \
+                 WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+                (&properties.database_name, &properties.table_name),
+            )
+            .await?;
+        conn.disconnect().await?;
+        if columns.is_empty() {
+            return Err(anyhow!(
+                "table {}.{} is not found",
+                properties.database_name,
+                properties.table_name
+            ));
+        }
+
+        let (tx, rx) = mpsc::channel(CHANNEL_SIZE);
+        let inner = MySqlCdcReaderInner {
+            opts,
+            server_id: properties.server_id.parse()?,
+            database_name: properties.database_name,
+            table_name: properties.table_name,
+            split_id: split.table_name,
+            columns: columns
+                .into_iter()
+                .map(|(name, data_type)| MySqlColumn {
+                    name,
+                    data_type: data_type.to_lowercase(),
+                })
+                .collect(),
+            tx,
+        };
+        let handle = tokio::spawn(async move {
+            if let Err(e) = inner.run(split.start_position).await {
+                let _ = inner.tx.send(Err(e)).await;
+            }
+        });
+
+        Ok(Self { rx, handle })
+    }
+
+    async fn next(&mut self) -> Result<Option<Vec<SourceMessage>>> {
+        match self.rx.recv().await {
+            Some(batch) => batch.map(Some),
+            None => Err(anyhow!("the MySQL CDC reader task exited")),
+        }
+    }
+}
+
+impl Drop for MySqlCdcSplitReader {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl MySqlCdcReaderInner {
+    async fn run(&self, start_position: Option<BinlogPosition>) -> Result<()> {
+        let position = match start_position {
+            Some(position) => position,
+            None => self.snapshot().await?,
+        };
+        self.tail_binlog(position).await
+    }
+
+    async fn send(&self, batch: Vec<SourceMessage>) -> Result<()> {
+        self.tx
+            .send(Ok(batch))
+            .await
+            .map_err(|_| anyhow!("the MySQL CDC reader is dropped"))
+    }
+
+    /// Reads all the rows of the table in a consistent snapshot, and returns the binlog position
+    /// of the snapshot.
+    ///
+    /// Only the last batch has its offset set to the position, so if the snapshot is not done at
+    /// a checkpoint, the recovery takes the snapshot again.
+    async fn snapshot(&self) -> Result<BinlogPosition> {
+        let mut conn = Conn::new(self.opts.clone()).await?;
+
+        // Writes are blocked until the snapshot starts, so that the binlog position is exactly
+        // where the snapshot is.
+        conn.query_drop("FLUSH TABLES WITH READ LOCK").await?;
+        conn.query_drop("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+            .await?;
+        conn.query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT")
+            .await?;
+        let status: Option<Row> = conn.query_first("SHOW MASTER STATUS").await?;
+        conn.query_drop("UNLOCK TABLES").await?;
+        let position = status
+            .and_then(|status| Some(BinlogPosition::new(status.get(0)?, status.get(1)?)))
+            .ok_or_else(|| anyhow!("the binlog of the upstream is not enabled"))?;
+
+        let columns = self
+            .columns
+            .iter()
+            .map(|column| format!("`{}`", column.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            "SELECT {} FROM `{}`.`{}`",
+            columns, self.database_name, self.table_name
+        );
+        let ts_ms = chrono::Utc::now().timestamp_millis();
+
+        // A full batch is held back until the next one, so that there is always a non-empty
+        // batch left to carry the position.
+        let mut pending = None;
+        let mut batch = Vec::with_capacity(SNAPSHOT_BATCH_SIZE);
+        let mut result = conn.exec_iter(query, ()).await?;
+        while let Some(row) = result.next().await? {
+            let after = row_to_json(&self.columns, row.unwrap())?;
+            batch.push(debezium_message(
+                &self.split_id,
+                DEBEZIUM_READ_OP,
+                None,
+                Some(after),
+                ts_ms,
+            ));
+            if batch.len() == SNAPSHOT_BATCH_SIZE {
+                let full = mem::replace(&mut batch, Vec::with_capacity(SNAPSHOT_BATCH_SIZE));
+                if let Some(pending) = pending.replace(full) {
+                    self.send(pending).await?;
+                }
+            }
+        }
+        drop(result);
+        conn.query_drop("COMMIT").await?;
+        conn.disconnect().await?;
+
+        let last = if batch.is_empty() {
+            pending
+        } else {
+            if let Some(pending) = pending {
+                self.send(pending).await?;
+            }
+            Some(batch)
+        };
+        if let Some(mut last) = last {
+            for message in &mut last {
+                message.offset = position.to_string();
+            }
+            self.send(last).await?;
+        }
+
+        Ok(position)
+    }
+
+    /// Tails the binlog from `position`, which must be at a transaction boundary. The changes of
+    /// the table in a transaction are sent in one batch when the transaction commits.
+    async fn tail_binlog(&self, mut position: BinlogPosition) -> Result<()> {
+        let conn = Conn::new(self.opts.clone()).await?;
+        let request = BinlogRequest::new(self.server_id)
+            .with_filename(position.filename.as_bytes().to_vec())
+            .with_pos(position.position);
+        let mut stream = conn.get_binlog_stream(request).await?;
+
+        let mut transaction = vec![];
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            let log_pos = event.header().log_pos() as u64;
+            let ts_ms = event.header().timestamp() as i64 * 1000;
+            match event.read_data()? {
+                Some(EventData::RotateEvent(rotate)) => {
+                    position = BinlogPosition::new(rotate.name().to_string(), rotate.position());
+                }
+                Some(EventData::RowsEvent(rows_event)) => {
+                    let tme = stream.get_tme(rows_event.table_id()).ok_or_else(|| {
+                        anyhow!("table map of table {} is not found", rows_event.table_id())
+                    })?;
+                    if tme.database_name() != self.database_name
+                        || tme.table_name() != self.table_name
+                    {
+                        continue;
+                    }
+                    for row in rows_event.rows(tme) {
+                        let (before, after) = row?;
+                        let before = before.map(|row| self.binlog_row_to_json(row)).transpose()?;
+                        let after = after.map(|row| self.binlog_row_to_json(row)).transpose()?;
+                        let op = match (&before, &after) {
+                            (None, Some(_)) => DEBEZIUM_CREATE_OP,
+                            (Some(_), Some(_)) => DEBEZIUM_UPDATE_OP,
+                            (Some(_), None) => DEBEZIUM_DELETE_OP,
+                            (None, None) => continue,
+                        };
+                        transaction.push(debezium_message(
+                            &self.split_id,
+                            op,
+                            before,
+                            after,
+                            ts_ms,
+                        ));
+                    }
+                }
+                // An XID event commits a transaction of a transactional engine, and a `COMMIT`
+                // query commits one of a non-transactional engine.
+                Some(EventData::XidEvent(_)) => {
+                    position.position = log_pos;
+                    self.commit(&mut transaction, &position).await?;
+                }
+                Some(EventData::QueryEvent(query)) if query.query() == "COMMIT" => {
+                    position.position = log_pos;
+                    self.commit(&mut transaction, &position).await?;
+                }
+                _ => {}
+            }
+        }
+
+        Err(anyhow!("the binlog stream of the upstream is closed"))
+    }
+
+    async fn commit(
+        &self,
+        transaction: &mut Vec<SourceMessage>,
+        position: &BinlogPosition,
+    ) -> Result<()> {
+        if transaction.is_empty() {
+            return Ok(());
+        }
+        let mut batch = mem::take(transaction);
+        for message in &mut batch {
+            message.offset = position.to_string();
+        }
+        self.send(batch).await
+    }
+
+    fn binlog_row_to_json(&self, row: BinlogRow) -> Result<BTreeMap<String, serde_json::Value>> {
+        let values = row
+            .unwrap()
+            .into_iter()
+            .map(|value| match value {
+                BinlogValue::Value(value) => Ok(value),
+                _ => Err(anyhow!(
+                    "JSON columns are not supported by the MySQL CDC source"
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        row_to_json(&self.columns, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(split_id: &str) -> SourceMessage {
+        debezium_message(split_id, DEBEZIUM_CREATE_OP, None, Some(BTreeMap::new()), 0)
+    }
+
+    #[tokio::test]
+    async fn test_commit() {
+        let (tx, mut rx) = mpsc::channel(CHANNEL_SIZE);
+        let inner = MySqlCdcReaderInner {
+            opts: OptsBuilder::default().into(),
+            server_id: 1,
+            database_name: "db".to_string(),
+            table_name: "t".to_string(),
+            split_id: "t".to_string(),
+            columns: vec![],
+            tx,
+        };
+        let position = BinlogPosition::new("binlog.000001".to_string(), 42);
+
+        // A transaction without changes of the table is not sent.
+        inner.commit(&mut vec![], &position).await.unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let mut transaction = vec![message("t"), message("t")];
+        inner.commit(&mut transaction, &position).await.unwrap();
+        assert!(transaction.is_empty());
+        let batch = rx.recv().await.unwrap().unwrap();
+        assert_eq!(batch.len(), 2);
+        for message in batch {
+            assert_eq!(BinlogPosition::parse(&message.offset).unwrap(), position);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_next() {
+        let (tx, rx) = mpsc::channel(CHANNEL_SIZE);
+        let handle = tokio::spawn(async move {
+            tx.send(Ok(vec![message("t")])).await.unwrap();
+            tx.send(Err(anyhow!("binlog stream error"))).await.unwrap();
+        });
+        let mut reader = MySqlCdcSplitReader { rx, handle };
+        assert_eq!(reader.next().await.unwrap().unwrap().len(), 1);
+        assert!(reader.next().await.is_err());
+        // The exit of the reader task is an error instead of the end of the source.
+        assert!(reader.next().await.is_err());
+    }
+}
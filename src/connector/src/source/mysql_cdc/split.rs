@@ -0,0 +1,122 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::source::SplitMetaData;
+
+/// A position in the binlog, at a transaction boundary.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BinlogPosition {
+    pub filename: String,
+    pub position: u64,
+}
+
+impl BinlogPosition {
+    pub fn new(filename: String, position: u64) -> Self {
+        Self { filename, position }
+    }
+
+    /// Parses the offset of the messages, formatted as `<filename>:<position>`.
+    pub fn parse(offset: &str) -> anyhow::Result<Self> {
+        let (filename, position) = offset
+            .rsplit_once(':')
+            .ok_or_else(|| anyhow!("invalid binlog position: {}", offset))?;
+        Ok(Self::new(filename.to_string(), position.parse()?))
+    }
+}
+
+impl std::fmt::Display for BinlogPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.filename, self.position)
+    }
+}
+
+/// The only split of a MySQL CDC source, i.e. the table.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Hash)]
+pub struct MySqlCdcSplit {
+    pub(crate) table_name: String,
+    /// Where to continue tailing the binlog, or `None` if the snapshot is not done yet.
+    pub(crate) start_position: Option<BinlogPosition>,
+}
+
+impl SplitMetaData for MySqlCdcSplit {
+    fn id(&self) -> String {
+        self.table_name.clone()
+    }
+
+    fn encode_to_bytes(&self) -> Bytes {
+        Bytes::from(serde_json::to_string(self).unwrap())
+    }
+
+    fn restore_from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| anyhow!(e))
+    }
+}
+
+impl MySqlCdcSplit {
+    pub fn new(table_name: String, start_position: Option<BinlogPosition>) -> Self {
+        Self {
+            table_name,
+            start_position,
+        }
+    }
+
+    /// The messages of the snapshot have an empty offset until the last batch, so the snapshot
+    /// is taken again if it is not done at the checkpoint.
+    pub fn copy_with_offset(&self, start_offset: String) -> anyhow::Result<Self> {
+        let start_position = if start_offset.is_empty() {
+            None
+        } else {
+            Some(BinlogPosition::parse(&start_offset)?)
+        };
+        Ok(Self::new(self.table_name.clone(), start_position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_with_offset() {
+        let split = MySqlCdcSplit::new("db.t".to_string(), None);
+        let split = split
+            .copy_with_offset("mysql-bin.000003:1234".to_string())
+            .unwrap();
+        assert_eq!(
+            split.start_position,
+            Some(BinlogPosition::new("mysql-bin.000003".to_string(), 1234))
+        );
+        assert_eq!(
+            split
+                .copy_with_offset("".to_string())
+                .unwrap()
+                .start_position,
+            None
+        );
+        assert!(split
+            .copy_with_offset("mysql-bin.000003".to_string())
+            .is_err());
+        assert!(split
+            .copy_with_offset("mysql-bin.000003:x".to_string())
+            .is_err());
+        assert_eq!(
+            MySqlCdcSplit::restore_from_bytes(&split.encode_to_bytes()).unwrap(),
+            split
+        );
+    }
+}
@@ -13,9 +13,10 @@
 // limitations under the License.
 
 use anyhow::{anyhow, bail, Result};
+use http::header::AUTHORIZATION;
 use http::{Response, StatusCode};
 use hyper::body::Buf;
-use hyper::{Body, Client, Uri};
+use hyper::{Body, Client, Request, Uri};
 use serde_derive::{Deserialize, Serialize};
 
 use crate::source::pulsar::topic::Topic;
@@ -23,12 +24,14 @@ use crate::source::pulsar::topic::Topic;
 #[derive(Debug, Default)]
 pub struct PulsarAdminClient {
     pub(crate) base_path: String,
+    pub(crate) auth_token: Option<String>,
 }
 
 impl PulsarAdminClient {
-    pub fn new(base_path: String) -> Self {
+    pub fn new(base_path: String, auth_token: Option<String>) -> Self {
         Self {
             base_path: base_path.trim_end_matches('/').to_string(),
+            auth_token,
         }
     }
 }
@@ -64,7 +67,14 @@ impl PulsarAdminClient {
         );
 
         let url: Uri = url.parse()?;
-        client.get(url).await.map_err(|e| anyhow!(e))
+        let mut request = Request::get(url);
+        if let Some(token) = &self.auth_token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        client
+            .request(request.body(Body::empty())?)
+            .await
+            .map_err(|e| anyhow!(e))
     }
 
     pub async fn get<T>(&self, topic: &Topic, api: &str) -> Result<T>
@@ -137,7 +147,7 @@ mod test {
         )
         .await;
 
-        let client = PulsarAdminClient::new(server.uri());
+        let client = PulsarAdminClient::new(server.uri(), None);
 
         let topic = parse_topic("public/default/t2").unwrap();
 
@@ -145,4 +155,31 @@ mod test {
 
         assert_eq!(meta.partitions, 3);
     }
+
+    #[tokio::test]
+    async fn test_get_topic_metadata_with_auth_token() {
+        use wiremock::matchers::{header, method, path};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/admin/v2/persistent/public/default/t2/partitions"))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("{\"partitions\":3}")
+                    .append_header("content-type", "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let topic = parse_topic("public/default/t2").unwrap();
+
+        let client = PulsarAdminClient::new(server.uri(), Some("token".to_string()));
+        let meta = client.get_topic_metadata(&topic).await.unwrap();
+        assert_eq!(meta.partitions, 3);
+
+        // Without the token, the request doesn't match and gets 404.
+        let client = PulsarAdminClient::new(server.uri(), None);
+        assert!(client.get_topic_metadata(&topic).await.is_err());
+    }
 }
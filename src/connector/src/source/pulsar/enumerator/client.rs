@@ -45,6 +45,7 @@ impl SplitEnumerator for PulsarSplitEnumerator {
     async fn new(properties: PulsarProperties) -> Result<PulsarSplitEnumerator> {
         let topic = properties.topic;
         let admin_url = properties.admin_url;
+        let auth_token = properties.auth_token;
         let parsed_topic = parse_topic(&topic)?;
 
         let mut scan_start_offset = match properties
@@ -68,7 +69,7 @@ impl SplitEnumerator for PulsarSplitEnumerator {
         }
 
         Ok(PulsarSplitEnumerator {
-            admin_client: PulsarAdminClient::new(admin_url),
+            admin_client: PulsarAdminClient::new(admin_url, auth_token),
             topic: parsed_topic,
             start_offset: scan_start_offset,
         })
@@ -146,6 +147,7 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
         assert!(enumerator.list_splits().await.is_err());
@@ -161,6 +163,7 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
         assert!(enumerator.list_splits().await.is_err());
@@ -180,6 +183,7 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
 
@@ -206,6 +210,7 @@ mod test {
             service_url: "pulsar://localhost:6650".to_string(),
             scan_startup_mode: Some("earliest".to_string()),
             time_offset: None,
+            auth_token: None,
         };
         let mut enumerator = PulsarSplitEnumerator::new(prop).await.unwrap();
 
@@ -40,4 +40,8 @@ pub struct PulsarProperties {
 
     #[serde(rename = "pulsar.time.offset")]
     pub time_offset: Option<String>,
+
+    /// The JWT token to authenticate to the cluster and its admin API with, if set.
+    #[serde(rename = "pulsar.auth.token")]
+    pub auth_token: Option<String>,
 }
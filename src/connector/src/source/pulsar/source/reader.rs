@@ -21,7 +21,9 @@ use futures::StreamExt;
 use itertools::Itertools;
 use pulsar::consumer::InitialPosition;
 use pulsar::message::proto::MessageIdData;
-use pulsar::{Consumer, ConsumerBuilder, ConsumerOptions, Pulsar, SubType, TokioExecutor};
+use pulsar::{
+    Authentication, Consumer, ConsumerBuilder, ConsumerOptions, Pulsar, SubType, TokioExecutor,
+};
 use risingwave_common::try_match_expand;
 
 use crate::source::pulsar::split::PulsarSplit;
@@ -99,10 +101,14 @@ impl SplitReader for PulsarSplitReader {
 
         log::debug!("creating consumer for pulsar split topic {}", topic,);
 
-        let pulsar: Pulsar<_> = Pulsar::builder(service_url, TokioExecutor)
-            .build()
-            .await
-            .map_err(|e| anyhow!(e))?;
+        let mut pulsar_builder = Pulsar::builder(service_url, TokioExecutor);
+        if let Some(token) = &props.auth_token {
+            pulsar_builder = pulsar_builder.with_auth(Authentication {
+                name: "token".to_string(),
+                data: token.clone().into_bytes(),
+            });
+        }
+        let pulsar: Pulsar<_> = pulsar_builder.build().await.map_err(|e| anyhow!(e))?;
 
         let builder: ConsumerBuilder<TokioExecutor> = pulsar
             .consumer()
@@ -20,3 +20,5 @@ mod sst_dump;
 pub use sst_dump::*;
 mod trigger_manual_compaction;
 pub use trigger_manual_compaction::*;
+mod compaction_tasks;
+pub use compaction_tasks::*;
@@ -0,0 +1,63 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::MetaServiceOpts;
+
+pub async fn list_compaction_tasks() -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    let tasks = meta_client.list_compaction_tasks().await?;
+    for task in tasks {
+        let compact_task = match task.compact_task {
+            Some(compact_task) => compact_task,
+            None => {
+                println!(
+                    "an assignment to compactor {} without the task",
+                    task.context_id
+                );
+                continue;
+            }
+        };
+        print!(
+            "task {} of compaction group {} to level {}, assigned to compactor {}",
+            compact_task.task_id,
+            compact_task.compaction_group_id,
+            compact_task.target_level,
+            task.context_id
+        );
+        match task.progress {
+            Some(progress) => println!(
+                ": read {} bytes, written {} bytes, estimated {} bytes remaining",
+                progress.read_bytes,
+                progress.written_bytes,
+                progress
+                    .total_input_bytes
+                    .saturating_sub(progress.read_bytes)
+            ),
+            None => println!(": no progress reported yet"),
+        }
+    }
+    Ok(())
+}
+
+pub async fn cancel_compaction_task(task_id: u64) -> anyhow::Result<()> {
+    let meta_opts = MetaServiceOpts::from_env()?;
+    let meta_client = meta_opts.create_meta_client().await?;
+    if meta_client.cancel_compaction_task(task_id).await? {
+        println!("Cancelled compaction task {}", task_id);
+    } else {
+        println!("Compaction task {} is not running", task_id);
+    }
+    Ok(())
+}
@@ -15,7 +15,7 @@
 use std::env;
 
 use anyhow::{bail, Result};
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerRole, WorkerType};
 use risingwave_rpc_client::MetaClient;
 
 pub struct MetaServiceOpts {
@@ -56,7 +56,13 @@ risectl requires a full persistent cluster to operate. Please make sure you're n
         let mut client = MetaClient::new(&self.meta_addr).await?;
         // FIXME: don't use 127.0.0.1 for ctl
         let worker_id = client
-            .register(WorkerType::RiseCtl, &"127.0.0.1:2333".parse().unwrap(), 0)
+            .register(
+                WorkerType::RiseCtl,
+                &"127.0.0.1:2333".parse().unwrap(),
+                0,
+                Default::default(),
+                WorkerRole::Unspecified,
+            )
             .await?;
         tracing::info!("registered as RiseCtl worker, worker_id = {}", worker_id);
         // TODO: remove worker node
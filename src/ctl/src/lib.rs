@@ -73,6 +73,13 @@ enum HummockCommands {
         #[clap(short, long = "level", default_value_t = 1)]
         level: u32,
     },
+    /// list the running compaction tasks with their progress
+    ListCompactionTasks,
+    /// cancel a running compaction task, whose input can then be compacted again
+    CancelCompactionTask {
+        /// id of the compaction task to cancel
+        task_id: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -99,6 +106,11 @@ enum MetaCommands {
     Resume,
     /// get cluster info
     ClusterInfo,
+    /// rebalance the source splits of a fragment across its actors
+    RebalanceSplits {
+        /// id of the source fragment
+        fragment_id: u32,
+    },
 }
 
 pub async fn start(opts: CliOpts) -> Result<()> {
@@ -122,6 +134,12 @@ pub async fn start(opts: CliOpts) -> Result<()> {
             ))
             .await??
         }
+        Commands::Hummock(HummockCommands::ListCompactionTasks) => {
+            tokio::spawn(cmd_impl::hummock::list_compaction_tasks()).await??
+        }
+        Commands::Hummock(HummockCommands::CancelCompactionTask { task_id }) => {
+            tokio::spawn(cmd_impl::hummock::cancel_compaction_task(task_id)).await??
+        }
         Commands::Table(TableCommands::Scan { mv_name }) => {
             tokio::spawn(cmd_impl::table::scan(mv_name)).await??
         }
@@ -135,6 +153,9 @@ pub async fn start(opts: CliOpts) -> Result<()> {
         Commands::Meta(MetaCommands::ClusterInfo) => {
             tokio::spawn(cmd_impl::meta::cluster_info()).await??
         }
+        Commands::Meta(MetaCommands::RebalanceSplits { fragment_id }) => {
+            tokio::spawn(cmd_impl::meta::rebalance_splits(fragment_id)).await??
+        }
     }
     Ok(())
 }
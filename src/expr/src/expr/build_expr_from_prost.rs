@@ -14,14 +14,14 @@
 
 use risingwave_common::array::DataChunk;
 use risingwave_common::types::{DataType, ToOwnedDatum};
-use risingwave_pb::expr::expr_node::RexNode;
+use risingwave_pb::expr::expr_node::{RexNode, Type};
 use risingwave_pb::expr::ExprNode;
 
 use crate::expr::expr_binary_bytes::{
     new_ltrim_characters, new_repeat, new_rtrim_characters, new_substr_start, new_to_char,
     new_trim_characters,
 };
-use crate::expr::expr_binary_nonnull::{new_binary_expr, new_like_default};
+use crate::expr::expr_binary_nonnull::{new_binary_expr, new_ilike_default, new_like_default};
 use crate::expr::expr_binary_nullable::new_nullable_binary_expr;
 use crate::expr::expr_case::{CaseExpression, WhenClause};
 use crate::expr::expr_in::InExpression;
@@ -184,7 +184,11 @@ pub fn build_like_expr(prost: &ExprNode) -> Result<BoxedExpression> {
     ensure!(children.len() == 2);
     let expr_ia1 = expr_build_from_prost(&children[0])?;
     let expr_ia2 = expr_build_from_prost(&children[1])?;
-    Ok(new_like_default(expr_ia1, expr_ia2, ret_type))
+    match prost.get_expr_type().unwrap() {
+        Type::Like => Ok(new_like_default(expr_ia1, expr_ia2, ret_type)),
+        Type::ILike => Ok(new_ilike_default(expr_ia1, expr_ia2, ret_type)),
+        tp => bail!("unexpected like expression type: {:?}", tp),
+    }
 }
 
 pub fn build_in_expr(prost: &ExprNode) -> Result<BoxedExpression> {
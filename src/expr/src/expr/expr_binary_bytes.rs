@@ -21,6 +21,7 @@ use super::Expression;
 use crate::expr::template::BinaryBytesExpression;
 use crate::expr::BoxedExpression;
 use crate::vector_op::concat_op::concat_op;
+use crate::vector_op::like::like_escape;
 use crate::vector_op::repeat::repeat;
 use crate::vector_op::substr::*;
 use crate::vector_op::to_char::to_char_timestamp;
@@ -103,7 +104,8 @@ macro_rules! for_all_utf8_utf8_op {
             { new_trim_characters, trim_characters },
             { new_ltrim_characters, ltrim_characters },
             { new_rtrim_characters, rtrim_characters },
-            { new_concat_op, concat_op }
+            { new_concat_op, concat_op },
+            { new_like_escape, like_escape }
         }
     };
 }
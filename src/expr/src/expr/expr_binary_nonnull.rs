@@ -26,8 +26,9 @@ use crate::for_all_cmp_variants;
 use crate::vector_op::arithmetic_op::*;
 use crate::vector_op::bitwise_op::*;
 use crate::vector_op::cmp::*;
+use crate::vector_op::date_trunc::date_trunc_timestamp;
 use crate::vector_op::extract::{extract_from_date, extract_from_timestamp};
-use crate::vector_op::like::like_default;
+use crate::vector_op::like::{ilike_default, like_default};
 use crate::vector_op::position::position;
 use crate::vector_op::round::round_digits;
 use crate::vector_op::tumble::{tumble_start_date, tumble_start_date_time};
@@ -497,6 +498,13 @@ pub fn new_binary_expr(
         )),
         Type::TumbleStart => new_tumble_start(l, r, ret),
         Type::ConcatOp => new_concat_op(l, r, ret),
+        Type::LikeEscape => new_like_escape(l, r, ret),
+        Type::DateTrunc => Box::new(BinaryExpression::<
+            Utf8Array,
+            NaiveDateTimeArray,
+            NaiveDateTimeArray,
+            _,
+        >::new(l, r, ret, date_trunc_timestamp)),
 
         tp => {
             unimplemented!(
@@ -549,6 +557,19 @@ pub fn new_like_default(
     ))
 }
 
+pub fn new_ilike_default(
+    expr_ia1: BoxedExpression,
+    expr_ia2: BoxedExpression,
+    return_type: DataType,
+) -> BoxedExpression {
+    Box::new(BinaryExpression::<Utf8Array, Utf8Array, BoolArray, _>::new(
+        expr_ia1,
+        expr_ia2,
+        return_type,
+        ilike_default,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveDate;
@@ -162,12 +162,12 @@ mod tests {
     use risingwave_pb::data::data_type::TypeName;
     use risingwave_pb::data::DataType as ProstDataType;
     use risingwave_pb::expr::expr_node::RexNode;
-    use risingwave_pb::expr::expr_node::Type::ConcatWs;
+    use risingwave_pb::expr::expr_node::Type::{ConcatOp, ConcatWs};
     use risingwave_pb::expr::{ExprNode, FunctionCall};
 
     use crate::expr::expr_concat_ws::ConcatWsExpression;
     use crate::expr::test_utils::make_input_ref;
-    use crate::expr::Expression;
+    use crate::expr::{build_from_prost, Expression};
 
     pub fn make_concat_ws_function(children: Vec<ExprNode>, ret: TypeName) -> ExprNode {
         ExprNode {
@@ -248,4 +248,58 @@ mod tests {
             assert_eq!(result, expected);
         }
     }
+
+    /// `concat(a, b)` is bound as `concat_ws('', a, b)`, which skips nulls, while `a || b` is null
+    /// if any operand is null.
+    #[test]
+    fn test_concat_and_concat_op_with_null() {
+        let concat_expr = ConcatWsExpression::try_from(&make_concat_ws_function(
+            vec![
+                make_input_ref(0, TypeName::Varchar),
+                make_input_ref(1, TypeName::Varchar),
+                make_input_ref(2, TypeName::Varchar),
+            ],
+            TypeName::Varchar,
+        ))
+        .unwrap();
+        let concat_op_expr = build_from_prost(&ExprNode {
+            expr_type: ConcatOp as i32,
+            return_type: Some(ProstDataType {
+                type_name: TypeName::Varchar as i32,
+                ..Default::default()
+            }),
+            rex_node: Some(RexNode::FuncCall(FunctionCall {
+                children: vec![
+                    make_input_ref(1, TypeName::Varchar),
+                    make_input_ref(2, TypeName::Varchar),
+                ],
+            })),
+        })
+        .unwrap();
+
+        let row_inputs = vec![
+            vec![Some(""), Some("a"), Some("b")],
+            vec![Some(""), Some("a"), None],
+            vec![Some(""), None, None],
+        ];
+        let expected_concat = vec![Some("ab"), Some("a"), Some("")];
+        let expected_concat_op = vec![Some("ab"), None, None];
+
+        for (i, row_input) in row_inputs.iter().enumerate() {
+            let datum_vec: Vec<Datum> = row_input
+                .iter()
+                .map(|e| e.map(|s| s.to_string().to_scalar_value()))
+                .collect();
+            let row = Row::new(datum_vec);
+
+            assert_eq!(
+                concat_expr.eval_row(&row).unwrap(),
+                expected_concat[i].map(|s| s.to_string().to_scalar_value())
+            );
+            assert_eq!(
+                concat_op_expr.eval_row(&row).unwrap(),
+                expected_concat_op[i].map(|s| s.to_string().to_scalar_value())
+            );
+        }
+    }
 }
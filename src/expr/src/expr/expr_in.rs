@@ -90,11 +90,14 @@ impl Expression for InExpression {
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
     use risingwave_common::array::{DataChunk, Row};
     use risingwave_common::test_prelude::DataChunkTestExt;
     use risingwave_common::types::{DataType, Scalar, ScalarImpl};
+    use risingwave_pb::expr::expr_node::Type;
 
     use crate::expr::expr_in::InExpression;
+    use crate::expr::expr_unary::new_unary_expr;
     use crate::expr::{Expression, InputRefExpression};
 
     #[test]
@@ -188,4 +191,21 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_eval_row_not_in_with_null() {
+        let input_ref = Box::new(InputRefExpression::new(DataType::Int32, 0));
+        let data = vec![Some(ScalarImpl::Int32(1)), None];
+        let in_expr = InExpression::new(input_ref, data.into_iter(), DataType::Boolean);
+        let not_in_expr = new_unary_expr(Type::Not, DataType::Boolean, Box::new(in_expr)).unwrap();
+
+        // `x NOT IN (1, NULL)` is false if `x` is 1, and null otherwise.
+        let row_inputs = [Some(1), Some(2), None];
+        let expected = [Some(false), None, None];
+        for (row_input, expected) in row_inputs.into_iter().zip_eq(expected) {
+            let row = Row::new(vec![row_input.map(ScalarImpl::Int32)]);
+            let result = not_in_expr.eval_row(&row).unwrap();
+            assert_eq!(result, expected.map(ScalarImpl::Bool));
+        }
+    }
 }
@@ -435,6 +435,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cast_invalid_str() {
+        let col1 = Column::new(
+            Utf8Array::from_slice(&[Some("1"), Some("abc")])
+                .map(|x| Arc::new(x.into()))
+                .unwrap(),
+        );
+        let data_chunk = DataChunk::new(vec![col1], 2);
+        let return_type = DataType {
+            type_name: TypeName::Int32 as i32,
+            is_nullable: false,
+            ..Default::default()
+        };
+        let expr = ExprNode {
+            expr_type: Type::Cast as i32,
+            return_type: Some(return_type),
+            rex_node: Some(RexNode::FuncCall(FunctionCall {
+                children: vec![make_input_ref(0, TypeName::Varchar)],
+            })),
+        };
+        let vec_executor = build_from_prost(&expr).unwrap();
+        // An invalid input fails the evaluation instead of panicking.
+        assert!(vec_executor.eval(&data_chunk).is_err());
+
+        let row = Row::new(vec![Some("1".to_string().to_scalar_value())]);
+        assert_eq!(
+            vec_executor.eval_row(&row).unwrap(),
+            Some(1i32.to_scalar_value())
+        );
+        let row = Row::new(vec![Some("abc".to_string().to_scalar_value())]);
+        assert!(vec_executor.eval_row(&row).is_err());
+    }
+
     #[test]
     fn test_neg() {
         let mut input = Vec::<Option<i32>>::new();
@@ -100,7 +100,7 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Add
         | Subtract | Multiply | Divide | Modulus | Extract | RoundDigit | TumbleStart
         | Position | BitwiseShiftLeft | BitwiseShiftRight | BitwiseAnd | BitwiseOr | BitwiseXor
-        | ConcatOp => build_binary_expr_prost(prost),
+        | ConcatOp | LikeEscape | DateTrunc => build_binary_expr_prost(prost),
         And | Or | IsDistinctFrom | ArrayAccess => build_nullable_binary_expr_prost(prost),
         ToChar => build_to_char_expr(prost),
         Coalesce => CoalesceExpression::try_from(prost).map(Expression::boxed),
@@ -108,7 +108,7 @@ pub fn build_from_prost(prost: &ExprNode) -> Result<BoxedExpression> {
         Length => build_length_expr(prost),
         Replace => build_replace_expr(prost),
         Overlay => build_overlay_expr(prost),
-        Like => build_like_expr(prost),
+        Like | ILike => build_like_expr(prost),
         Trim => build_trim_expr(prost),
         Ltrim => build_ltrim_expr(prost),
         Rtrim => build_rtrim_expr(prost),
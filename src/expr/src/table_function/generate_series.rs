@@ -61,7 +61,18 @@ where
 
         while cur <= stop {
             builder.append(Some(cur.as_scalar_ref())).unwrap();
-            cur = cur.checked_add(step).ok_or(ExprError::NumericOutOfRange)?;
+            let next = cur
+                .clone()
+                .checked_add(step)
+                .ok_or(ExprError::NumericOutOfRange)?;
+            // A step that doesn't move forward would generate the series forever.
+            if next <= cur.as_scalar_ref() {
+                return Err(ExprError::InvalidParam {
+                    name: "step",
+                    reason: "the step of generate_series must be positive".to_string(),
+                });
+            }
+            cur = next;
         }
 
         Ok(Arc::new(builder.finish()?.into()))
@@ -157,6 +168,27 @@ mod tests {
         generate_series_test_case(0, (DEFAULT_CHUNK_BUFFER_SIZE * 2 + 3) as i32, 1);
     }
 
+    #[test]
+    fn test_generate_series_non_positive_step() {
+        fn to_lit_expr(v: i32) -> BoxedExpression {
+            LiteralExpression::new(DataType::Int32, Some(v.into())).boxed()
+        }
+
+        for step in [0, -1] {
+            let function = GenerateSeries::<I32Array, I32Array> {
+                start: to_lit_expr(0),
+                stop: to_lit_expr(9),
+                step: to_lit_expr(step),
+                _phantom: Default::default(),
+            };
+            let dummy_chunk = DataChunk::new_dummy(1);
+            assert!(matches!(
+                function.eval(&dummy_chunk),
+                Err(ExprError::InvalidParam { name: "step", .. })
+            ));
+        }
+    }
+
     fn generate_series_test_case(start: i32, stop: i32, step: i32) {
         fn to_lit_expr(v: i32) -> BoxedExpression {
             LiteralExpression::new(DataType::Int32, Some(v.into())).boxed()
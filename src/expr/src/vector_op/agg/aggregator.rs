@@ -53,6 +53,7 @@ pub trait Aggregator: Send + 'static {
 
 pub type BoxedAggState = Box<dyn Aggregator>;
 
+#[derive(Clone)]
 pub struct AggStateFactory {
     // When agg func is count(*), the args is empty and input type is None.
     input_type: Option<DataType>,
@@ -0,0 +1,119 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use risingwave_common::types::NaiveDateTimeWrapper;
+
+use crate::{bail, ExprError, Result};
+
+/// The units supported by `date_trunc`, in lower case.
+pub const DATE_TRUNC_UNITS: &[&str] = &[
+    "microseconds",
+    "milliseconds",
+    "second",
+    "minute",
+    "hour",
+    "day",
+    "week",
+    "month",
+    "quarter",
+    "year",
+    "decade",
+    "century",
+    "millennium",
+];
+
+/// Truncates `timestamp` to the precision of `unit`, e.g. `hour` zeroes the minutes and below.
+/// Weeks start on Monday, and centuries and millenniums start with the year `xx01`, the same as
+/// PostgreSQL.
+pub fn date_trunc_timestamp(
+    unit: &str,
+    timestamp: NaiveDateTimeWrapper,
+) -> Result<NaiveDateTimeWrapper> {
+    let ts = timestamp.0;
+    let date = ts.date();
+    let year = date.year();
+    let start_of_year = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1);
+    let truncated = match unit.to_lowercase().as_str() {
+        "microseconds" => ts.with_nanosecond(ts.nanosecond() / 1_000 * 1_000),
+        "milliseconds" => ts.with_nanosecond(ts.nanosecond() / 1_000_000 * 1_000_000),
+        "second" => ts.with_nanosecond(0),
+        "minute" => date.and_hms_opt(ts.hour(), ts.minute(), 0),
+        "hour" => date.and_hms_opt(ts.hour(), 0, 0),
+        "day" => date.and_hms_opt(0, 0, 0),
+        "week" => {
+            let days_from_monday = date.weekday().num_days_from_monday() as i64;
+            (date - Duration::days(days_from_monday)).and_hms_opt(0, 0, 0)
+        }
+        "month" => NaiveDate::from_ymd_opt(year, date.month(), 1).and_then(start_of_day),
+        "quarter" => {
+            NaiveDate::from_ymd_opt(year, (date.month() - 1) / 3 * 3 + 1, 1).and_then(start_of_day)
+        }
+        "year" => start_of_year(year).and_then(start_of_day),
+        "decade" => start_of_year(year.div_euclid(10) * 10).and_then(start_of_day),
+        "century" => start_of_year((year - 1).div_euclid(100) * 100 + 1).and_then(start_of_day),
+        "millennium" => {
+            start_of_year((year - 1).div_euclid(1000) * 1000 + 1).and_then(start_of_day)
+        }
+        _ => bail!("Unsupported unit {} in date_trunc function", unit),
+    };
+    truncated
+        .map(NaiveDateTimeWrapper::new)
+        .ok_or(ExprError::NumericOutOfRange)
+}
+
+fn start_of_day(date: NaiveDate) -> Option<NaiveDateTime> {
+    date.and_hms_opt(0, 0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_trunc_timestamp() {
+        let ts = NaiveDateTimeWrapper::new(
+            NaiveDateTime::parse_from_str("2021-11-24 12:34:56.789012", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap(),
+        );
+        let cases = [
+            ("microseconds", "2021-11-24 12:34:56.789012"),
+            ("milliseconds", "2021-11-24 12:34:56.789"),
+            ("second", "2021-11-24 12:34:56"),
+            ("minute", "2021-11-24 12:34:00"),
+            ("HOUR", "2021-11-24 12:00:00"),
+            ("day", "2021-11-24 00:00:00"),
+            // 2021-11-24 is a Wednesday.
+            ("week", "2021-11-22 00:00:00"),
+            ("month", "2021-11-01 00:00:00"),
+            ("quarter", "2021-10-01 00:00:00"),
+            ("year", "2021-01-01 00:00:00"),
+            ("decade", "2020-01-01 00:00:00"),
+            ("century", "2001-01-01 00:00:00"),
+            ("millennium", "2001-01-01 00:00:00"),
+        ];
+        for (unit, expected) in cases {
+            let expected = NaiveDateTimeWrapper::new(
+                NaiveDateTime::parse_from_str(expected, "%Y-%m-%d %H:%M:%S%.f").unwrap(),
+            );
+            assert_eq!(
+                date_trunc_timestamp(unit, ts).unwrap(),
+                expected,
+                "{}",
+                unit
+            );
+        }
+        assert!(date_trunc_timestamp("fortnight", ts).is_err());
+    }
+}
@@ -17,6 +17,11 @@ use risingwave_common::types::{Decimal, NaiveDateTimeWrapper, NaiveDateWrapper};
 
 use crate::{bail, Result};
 
+/// The units supported by `extract` on a timestamp, in upper case.
+pub const EXTRACT_UNITS: &[&str] = &[
+    "DAY", "MONTH", "YEAR", "DOW", "DOY", "HOUR", "MINUTE", "SECOND",
+];
+
 fn extract_time<T>(time: T, time_unit: &str) -> Result<Decimal>
 where
     T: Timelike,
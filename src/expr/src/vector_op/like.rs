@@ -12,8 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::Result;
+use risingwave_common::array::{BytesGuard, BytesWriter};
 
+use crate::{ExprError, Result};
+
+/// Matches `s` against the LIKE pattern `p`, where `%` matches any sequence of characters, `_`
+/// matches any single character, and `\` escapes the next character of the pattern.
 #[inline(always)]
 pub fn like_default(s: &str, p: &str) -> Result<bool> {
     let (mut px, mut sx) = (0, 0);
@@ -36,6 +40,19 @@ pub fn like_default(s: &str, p: &str) -> Result<bool> {
                     px += 1;
                     continue;
                 }
+                b'\\' => {
+                    if px + 1 == pbytes.len() {
+                        return Err(ExprError::InvalidParam {
+                            name: "pattern",
+                            reason: "LIKE pattern must not end with escape character".to_string(),
+                        });
+                    }
+                    if sx < sbytes.len() && sbytes[sx] == pbytes[px + 1] {
+                        px += 2;
+                        sx += 1;
+                        continue;
+                    }
+                }
                 pc => {
                     if sx < sbytes.len() && sbytes[sx] == pc {
                         px += 1;
@@ -55,9 +72,52 @@ pub fn like_default(s: &str, p: &str) -> Result<bool> {
     Ok(true)
 }
 
+/// The case-insensitive version of [`like_default`].
+#[inline(always)]
+pub fn ilike_default(s: &str, p: &str) -> Result<bool> {
+    like_default(&s.to_lowercase(), &p.to_lowercase())
+}
+
+/// Rewrites the LIKE pattern `p` using the escape character `escape` into one using the default
+/// escape character `\`. An empty `escape` disables escaping.
+#[inline(always)]
+pub fn like_escape(p: &str, escape: &str, dst: BytesWriter) -> Result<BytesGuard> {
+    let mut escape_chars = escape.chars();
+    let escape = match (escape_chars.next(), escape_chars.next()) {
+        (escape, None) => escape,
+        _ => {
+            return Err(ExprError::InvalidParam {
+                name: "escape",
+                reason: "invalid escape string, it must be empty or one character".to_string(),
+            })
+        }
+    };
+
+    let mut writer = dst.begin();
+    let mut chars = p.chars();
+    let mut buf = [0; 4];
+    while let Some(c) = chars.next() {
+        if Some(c) == escape {
+            let escaped = chars.next().ok_or_else(|| ExprError::InvalidParam {
+                name: "pattern",
+                reason: "LIKE pattern must not end with escape character".to_string(),
+            })?;
+            writer.write_ref("\\")?;
+            writer.write_ref(escaped.encode_utf8(&mut buf))?;
+        } else if c == '\\' {
+            writer.write_ref("\\\\")?;
+        } else {
+            writer.write_ref(c.encode_utf8(&mut buf))?;
+        }
+    }
+    writer.finish().map_err(Into::into)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::like_default;
+    use risingwave_common::array::{Array, ArrayBuilder, Utf8ArrayBuilder};
+
+    use super::*;
 
     static CASES: &[(&str, &str, std::option::Option<bool>)] = &[
         (r#"ABCDE"#, r#"%abcde%"#, Some(false)),
@@ -79,6 +139,12 @@ mod tests {
             r#"%yellow%"#,
             Some(true),
         ),
+        (r#"100%"#, r#"100\%"#, Some(true)),
+        (r#"1000"#, r#"100\%"#, Some(false)),
+        (r#"a_c"#, r#"a\_c"#, Some(true)),
+        (r#"abc"#, r#"a\_c"#, Some(false)),
+        (r#"a\c"#, r#"a\\c"#, Some(true)),
+        (r#"abc"#, r#"\a%"#, Some(true)),
     ];
 
     #[test]
@@ -94,4 +160,38 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_like_trailing_escape() {
+        assert!(like_default("abc", r#"abc\"#).is_err());
+    }
+
+    #[test]
+    fn test_ilike() {
+        assert!(ilike_default("ABCDE", "%abcde%").unwrap());
+        assert!(ilike_default("Like, Expression", "like, %").unwrap());
+        assert!(!ilike_default("Like", "lik").unwrap());
+    }
+
+    #[test]
+    fn test_like_escape() {
+        let cases = [
+            ("100!%", "!", r#"100\%"#),
+            ("a!_c!!", "!", r#"a\_c\!"#),
+            (r#"a\b"#, "!", r#"a\\b"#),
+            (r#"a\%"#, "", r#"a\\%"#),
+            (r#"a\%"#, r#"\"#, r#"a\%"#),
+        ];
+        for (pattern, escape, expected) in cases {
+            let writer = Utf8ArrayBuilder::new(1).writer();
+            let guard = like_escape(pattern, escape, writer).unwrap();
+            let array = guard.into_inner().finish().unwrap();
+            assert_eq!(array.value_at(0).unwrap(), expected);
+        }
+
+        let writer = Utf8ArrayBuilder::new(1).writer();
+        assert!(like_escape("abc", "!!", writer).is_err());
+        let writer = Utf8ArrayBuilder::new(1).writer();
+        assert!(like_escape("abc!", "!", writer).is_err());
+    }
 }
@@ -42,6 +42,9 @@ impl ColumnBinding {
 pub enum Clause {
     Where,
     Values,
+    GroupBy,
+    Having,
+    JoinOn,
 }
 
 impl Display for Clause {
@@ -49,6 +52,9 @@ impl Display for Clause {
         match self {
             Clause::Where => write!(f, "WHERE"),
             Clause::Values => write!(f, "VALUES"),
+            Clause::GroupBy => write!(f, "GROUP BY"),
+            Clause::Having => write!(f, "HAVING"),
+            Clause::JoinOn => write!(f, "JOIN conditions"),
         }
     }
 }
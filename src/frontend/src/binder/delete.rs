@@ -15,6 +15,7 @@
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::{Expr, ObjectName};
 
+use super::bind_context::Clause;
 use super::{Binder, BoundBaseTable, BoundTableSource};
 use crate::expr::ExprImpl;
 
@@ -44,10 +45,13 @@ impl Binder {
             .into());
         }
         let table = self.bind_table(&schema_name, &table_name, None)?;
+        self.context.clause = Some(Clause::Where);
+        let selection = selection.map(|expr| self.bind_expr(expr)).transpose()?;
+        self.context.clause = None;
         let delete = BoundDelete {
             table_source,
             table,
-            selection: selection.map(|expr| self.bind_expr(expr)).transpose()?,
+            selection,
         };
         Ok(delete)
     }
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_sqlparser::ast::{BinaryOperator, Expr};
 
 use crate::binder::Binder;
@@ -42,8 +42,18 @@ impl Binder {
             BinaryOperator::GtEq => ExprType::GreaterThanOrEqual,
             BinaryOperator::And => ExprType::And,
             BinaryOperator::Or => ExprType::Or,
-            BinaryOperator::Like => ExprType::Like,
-            BinaryOperator::NotLike => return self.bind_not_like(bound_left, bound_right),
+            BinaryOperator::Like => {
+                return self.bind_like(ExprType::Like, bound_left, bound_right, false)
+            }
+            BinaryOperator::NotLike => {
+                return self.bind_like(ExprType::Like, bound_left, bound_right, true)
+            }
+            BinaryOperator::ILike => {
+                return self.bind_like(ExprType::ILike, bound_left, bound_right, false)
+            }
+            BinaryOperator::NotILike => {
+                return self.bind_like(ExprType::ILike, bound_left, bound_right, true)
+            }
             BinaryOperator::BitwiseOr => ExprType::BitwiseOr,
             BinaryOperator::BitwiseAnd => ExprType::BitwiseAnd,
             BinaryOperator::PGBitwiseXor => ExprType::BitwiseXor,
@@ -60,13 +70,39 @@ impl Binder {
         Ok(FunctionCall::new(func_type, vec![bound_left, bound_right])?.into())
     }
 
-    /// Apply a NOT on top of LIKE.
-    fn bind_not_like(&mut self, left: ExprImpl, right: ExprImpl) -> Result<ExprImpl> {
-        Ok(FunctionCall::new(
-            ExprType::Not,
-            vec![FunctionCall::new(ExprType::Like, vec![left, right])?.into()],
-        )?
-        .into())
+    /// Bind `[NOT] LIKE` and `[NOT] ILIKE`. A constant LIKE pattern without wildcards is bound as
+    /// an equality.
+    fn bind_like(
+        &mut self,
+        func_type: ExprType,
+        left: ExprImpl,
+        right: ExprImpl,
+        negated: bool,
+    ) -> Result<ExprImpl> {
+        let is_exact_pattern = func_type == ExprType::Like
+            && left.return_type() == DataType::Varchar
+            && matches!(
+                &right,
+                ExprImpl::Literal(pattern) if matches!(
+                    pattern.get_data(),
+                    Some(ScalarImpl::Utf8(pattern)) if !pattern.contains(&['%', '_', '\\'][..])
+                )
+            );
+        if is_exact_pattern {
+            let func_type = if negated {
+                ExprType::NotEqual
+            } else {
+                ExprType::Equal
+            };
+            return Ok(FunctionCall::new(func_type, vec![left, right])?.into());
+        }
+
+        let like = FunctionCall::new(func_type, vec![left, right])?.into();
+        if negated {
+            Ok(FunctionCall::new(ExprType::Not, vec![like])?.into())
+        } else {
+            Ok(like)
+        }
     }
 
     /// Bind `||`. Based on the types of the inputs, this can be string concat or array concat.
@@ -17,21 +17,23 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_expr::expr::AggKind;
-use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr};
+use risingwave_expr::vector_op::date_trunc::DATE_TRUNC_UNITS;
+use risingwave_expr::vector_op::extract::EXTRACT_UNITS;
+use risingwave_sqlparser::ast::{Function, FunctionArg, FunctionArgExpr, OrderByExpr, WindowSpec};
 
 use crate::binder::bind_context::Clause;
 use crate::binder::Binder;
 use crate::expr::{
     AggCall, AggOrderBy, AggOrderByExpr, Expr, ExprImpl, ExprType, FunctionCall, Literal,
-    TableFunction, TableFunctionType,
+    TableFunction, TableFunctionType, WindowFunction, WindowFunctionType,
 };
 use crate::optimizer::property::Direction;
 use crate::utils::Condition;
 
 impl Binder {
-    pub(super) fn bind_function(&mut self, f: Function) -> Result<ExprImpl> {
+    pub(super) fn bind_function(&mut self, mut f: Function) -> Result<ExprImpl> {
         let function_name = if f.name.0.len() == 1 {
             f.name.0.get(0).unwrap().real_value()
         } else {
@@ -42,12 +44,8 @@ impl Binder {
             .into());
         };
 
-        if f.over.is_some() {
-            return Err(ErrorCode::NotImplemented(
-                format!("over window function: {}", f.name),
-                3646.into(),
-            )
-            .into());
+        if let Some(window_spec) = f.over.take() {
+            return self.bind_window_function(&function_name, f, window_spec);
         }
 
         // agg calls
@@ -66,6 +64,14 @@ impl Binder {
             return self.bind_agg(f, kind);
         }
 
+        if function_name == "grouping" {
+            return Err(ErrorCode::NotImplemented(
+                "GROUPING function with grouping sets".into(),
+                None.into(),
+            )
+            .into());
+        }
+
         if f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
             return Err(ErrorCode::InvalidInputSyntax(format!(
                     "DISTINCT, ORDER BY or FILTER is only allowed in aggregation functions, but `{}` is not an aggregation function", function_name
@@ -135,6 +141,21 @@ impl Binder {
             }
             "concat_ws" => ExprType::ConcatWs,
             "split_part" => ExprType::SplitPart,
+            "like_escape" => ExprType::LikeEscape,
+            // date/time
+            "date_trunc" => {
+                inputs = Self::rewrite_date_unit("date_trunc", inputs, DATE_TRUNC_UNITS)?;
+                ExprType::DateTrunc
+            }
+            "date_part" => {
+                inputs = Self::rewrite_date_unit("date_part", inputs, EXTRACT_UNITS)?;
+                if !matches!(inputs[0], ExprImpl::Literal(_)) {
+                    // Extract expects the unit in upper case.
+                    inputs[0] = FunctionCall::new(ExprType::Upper, vec![inputs[0].clone()])?.into();
+                }
+                let extract: ExprImpl = FunctionCall::new(ExprType::Extract, inputs)?.into();
+                return extract.cast_explicit(DataType::Float64);
+            }
             "char_length" => ExprType::CharLength,
             "character_length" => ExprType::CharLength,
             "repeat" => ExprType::Repeat,
@@ -173,6 +194,12 @@ impl Binder {
             .map(|arg| self.bind_function_arg(arg))
             .flatten_ok()
             .try_collect()?;
+        if inputs.iter().any(|e| e.has_window_function()) {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "aggregate function calls cannot contain window function calls".to_string(),
+            )
+            .into());
+        }
         if f.distinct {
             match &kind {
                 AggKind::Count if inputs.is_empty() => {
@@ -239,8 +266,15 @@ impl Binder {
             )
             .into());
         }
-        let order_by = AggOrderBy::new(
-            f.order_by
+        let order_by = self.bind_agg_order_by(f.order_by)?;
+        Ok(ExprImpl::AggCall(Box::new(AggCall::new(
+            kind, inputs, f.distinct, order_by, filter,
+        )?)))
+    }
+
+    fn bind_agg_order_by(&mut self, order_by: Vec<OrderByExpr>) -> Result<AggOrderBy> {
+        Ok(AggOrderBy::new(
+            order_by
                 .into_iter()
                 .map(|e| -> Result<AggOrderByExpr> {
                     let expr = self.bind_expr(e.expr)?;
@@ -248,11 +282,9 @@ impl Binder {
                         None | Some(true) => Direction::Asc,
                         Some(false) => Direction::Desc,
                     };
-                    let nulls_first = e.nulls_first.unwrap_or_else(|| match direction {
-                        Direction::Asc => false,
-                        Direction::Desc => true,
-                        Direction::Any => unreachable!(),
-                    });
+                    let nulls_first = e
+                        .nulls_first
+                        .unwrap_or_else(|| direction.default_nulls_first());
                     Ok(AggOrderByExpr {
                         expr,
                         direction,
@@ -260,10 +292,85 @@ impl Binder {
                     })
                 })
                 .try_collect()?,
-        );
-        Ok(ExprImpl::AggCall(Box::new(AggCall::new(
-            kind, inputs, f.distinct, order_by, filter,
-        )?)))
+        ))
+    }
+
+    /// Binds a function with an `OVER` clause. Only `row_number` is supported for now.
+    fn bind_window_function(
+        &mut self,
+        function_name: &str,
+        f: Function,
+        window_spec: WindowSpec,
+    ) -> Result<ExprImpl> {
+        let Ok(function_type) = WindowFunctionType::from_str(function_name) else {
+            return Err(ErrorCode::NotImplemented(
+                format!("over window function: {}", f.name),
+                3646.into(),
+            )
+            .into());
+        };
+        self.ensure_window_function_allowed()?;
+        if !f.args.is_empty() || f.distinct || !f.order_by.is_empty() || f.filter.is_some() {
+            return Err(ErrorCode::InvalidInputSyntax(format!(
+                "window function {} takes no arguments",
+                function_name
+            ))
+            .into());
+        }
+        if window_spec.window_frame.is_some() {
+            return Err(ErrorCode::NotImplemented(
+                "window frame in over window function".to_string(),
+                3646.into(),
+            )
+            .into());
+        }
+
+        let partition_by: Vec<ExprImpl> = window_spec
+            .partition_by
+            .into_iter()
+            .map(|expr| self.bind_expr(expr))
+            .try_collect()?;
+        let order_by = self.bind_agg_order_by(window_spec.order_by)?;
+        if partition_by
+            .iter()
+            .chain(order_by.sort_exprs.iter().map(|e| &e.expr))
+            .any(|e| e.has_window_function())
+        {
+            return Err(ErrorCode::InvalidInputSyntax(
+                "window function calls cannot be nested".to_string(),
+            )
+            .into());
+        }
+        Ok(WindowFunction::new(function_type, partition_by, order_by).into())
+    }
+
+    /// Makes sure the unit of `date_trunc` or `date_part`, i.e. the first argument, is one of
+    /// `units` if it's a constant, and rewrites it to the same case as `units`.
+    fn rewrite_date_unit(
+        function_name: &str,
+        mut inputs: Vec<ExprImpl>,
+        units: &[&str],
+    ) -> Result<Vec<ExprImpl>> {
+        if inputs.len() != 2 {
+            return Err(ErrorCode::BindError(format!(
+                "Function `{}` takes 2 arguments ({} given)",
+                function_name,
+                inputs.len()
+            ))
+            .into());
+        }
+        if let ExprImpl::Literal(unit) = &inputs[0] &&
+        let Some(ScalarImpl::Utf8(unit)) = unit.get_data() {
+            let Some(known_unit) = units.iter().find(|u| u.eq_ignore_ascii_case(unit)) else {
+                return Err(ErrorCode::BindError(format!(
+                    "unit \"{}\" not recognized by function `{}`",
+                    unit, function_name
+                ))
+                .into());
+            };
+            inputs[0] = ExprImpl::literal_varchar(known_unit.to_string());
+        }
+        Ok(inputs)
     }
 
     fn rewrite_concat_to_concat_ws(inputs: Vec<ExprImpl>) -> Result<Vec<ExprImpl>> {
@@ -335,6 +442,22 @@ impl Binder {
         Ok(())
     }
 
+    fn ensure_window_function_allowed(&self) -> Result<()> {
+        if let Some(clause) = self.context.clause {
+            if matches!(
+                clause,
+                Clause::Values | Clause::Where | Clause::GroupBy | Clause::Having | Clause::JoinOn
+            ) {
+                return Err(ErrorCode::InvalidInputSyntax(format!(
+                    "window functions are not allowed in {}",
+                    clause
+                ))
+                .into());
+            }
+        }
+        Ok(())
+    }
+
     pub(in crate::binder) fn bind_function_expr_arg(
         &mut self,
         arg_expr: FunctionArgExpr,
@@ -30,6 +30,9 @@ pub struct BoundInsert {
 
     /// Used as part of an extra `Project` when the column types of `source` query does not match
     /// `table_source`. This does not include a simple `VALUE`. See comments in code for details.
+    ///
+    /// It also puts the columns in the order of `table_source` and fills the columns missing from
+    /// the column list of the insert.
     pub cast_exprs: Vec<ExprImpl>,
 }
 
@@ -37,15 +40,19 @@ impl Binder {
     pub(super) fn bind_insert(
         &mut self,
         source_name: ObjectName,
-        _columns: Vec<Ident>,
+        columns: Vec<Ident>,
         source: Query,
     ) -> Result<BoundInsert> {
         let table_source = self.bind_table_source(source_name)?;
 
-        let expected_types = table_source
-            .columns
+        // Indices of the target columns in `table_source`, in the order of the column list.
+        let target_indices = match columns.is_empty() {
+            true => (0..table_source.columns.len()).collect_vec(),
+            false => Self::bind_insert_columns(&table_source, columns)?,
+        };
+        let expected_types = target_indices
             .iter()
-            .map(|c| c.data_type.clone())
+            .map(|&i| table_source.columns[i].data_type.clone())
             .collect();
 
         // When the column types of `source` query does not match `expected_types`, casting is
@@ -110,6 +117,35 @@ impl Binder {
             }
         };
 
+        // Columns missing from the column list are filled with `NULL`, as column defaults are not
+        // supported yet.
+        let cast_exprs = if target_indices
+            .iter()
+            .copied()
+            .eq(0..table_source.columns.len())
+        {
+            cast_exprs
+        } else {
+            let source_exprs = match cast_exprs.is_empty() {
+                true => source
+                    .data_types()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, t)| InputRef::new(i, t).into())
+                    .collect_vec(),
+                false => cast_exprs,
+            };
+            let mut exprs = table_source
+                .columns
+                .iter()
+                .map(|c| Literal::new(None, c.data_type.clone()).into())
+                .collect_vec();
+            for (expr, &i) in source_exprs.into_iter().zip_eq(&target_indices) {
+                exprs[i] = expr;
+            }
+            exprs
+        };
+
         let insert = BoundInsert {
             table_source,
             source,
@@ -119,6 +155,36 @@ impl Binder {
         Ok(insert)
     }
 
+    /// Resolves the column list of an insert to the indices of the columns in `table_source`.
+    fn bind_insert_columns(
+        table_source: &BoundTableSource,
+        columns: Vec<Ident>,
+    ) -> Result<Vec<usize>> {
+        let mut indices = Vec::with_capacity(columns.len());
+        for column in columns {
+            let name = column.real_value();
+            let index = table_source
+                .columns
+                .iter()
+                .position(|c| c.name == name)
+                .ok_or_else(|| {
+                    ErrorCode::BindError(format!(
+                        "column \"{}\" of table \"{}\" does not exist",
+                        name, table_source.name
+                    ))
+                })?;
+            if indices.contains(&index) {
+                return Err(ErrorCode::BindError(format!(
+                    "column \"{}\" specified more than once",
+                    name
+                ))
+                .into());
+            }
+            indices.push(index);
+        }
+        Ok(indices)
+    }
+
     /// Cast a list of `exprs` to corresponding `expected_types` IN ASSIGNMENT CONTEXT. Make sure
     /// you understand the difference of implicit, assignment and explicit cast before reusing it.
     pub(super) fn cast_on_insert(
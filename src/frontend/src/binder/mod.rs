@@ -70,6 +70,8 @@ pub struct Binder {
     next_subquery_id: usize,
     /// Map the cte's name to its Relation::Subquery.
     cte_to_relation: HashMap<String, (BoundQuery, TableAlias)>,
+    /// The epochs of the historical snapshots read with `AS OF`.
+    as_of_epochs: Vec<u64>,
 }
 
 impl Binder {
@@ -82,6 +84,7 @@ impl Binder {
             lateral_contexts: vec![],
             next_subquery_id: 0,
             cte_to_relation: HashMap::new(),
+            as_of_epochs: vec![],
         }
     }
 
@@ -90,6 +93,12 @@ impl Binder {
         self.bind_statement(stmt)
     }
 
+    /// The epochs of the historical snapshots read with `AS OF`, which should be checked to be
+    /// still available before running the query.
+    pub fn as_of_epochs(&self) -> &[u64] {
+        &self.as_of_epochs
+    }
+
     fn push_context(&mut self) {
         let new_context = std::mem::take(&mut self.context);
         let new_lateral_contexts = std::mem::take(&mut self.lateral_contexts);
@@ -153,7 +153,14 @@ impl Binder {
                 visible_output_num + extra_order_exprs.len() - 1
             }
         };
-        Ok(FieldOrder { index, direct })
+        let nulls_first = order_by_expr
+            .nulls_first
+            .unwrap_or_else(|| direct.default_nulls_first());
+        Ok(FieldOrder {
+            index,
+            direct,
+            nulls_first,
+        })
     }
 
     fn bind_with(&mut self, with: With) -> Result<()> {
@@ -19,6 +19,7 @@ use risingwave_sqlparser::ast::{
     BinaryOperator, Expr, Ident, JoinConstraint, JoinOperator, TableFactor, TableWithJoins, Value,
 };
 
+use crate::binder::bind_context::Clause;
 use crate::binder::{Binder, Relation};
 use crate::expr::{Expr as _, ExprImpl};
 
@@ -101,7 +102,9 @@ impl Binder {
                 return Err(ErrorCode::NotImplemented("Natural join".into(), 1633.into()).into())
             }
             JoinConstraint::On(expr) => {
+                self.context.clause = Some(Clause::JoinOn);
                 let bound_expr = self.bind_expr(expr)?;
+                self.context.clause = None;
                 if bound_expr.return_type() != DataType::Boolean {
                     return Err(ErrorCode::InternalError(format!(
                         "argument of ON must be boolean, not type {:?}",
@@ -258,8 +258,23 @@ impl Binder {
 
     pub(super) fn bind_table_factor(&mut self, table_factor: TableFactor) -> Result<Relation> {
         match table_factor {
-            TableFactor::Table { name, alias, args } => {
-                if args.is_empty() {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            } => {
+                if let Some(as_of) = as_of {
+                    if !args.is_empty() {
+                        return Err(ErrorCode::BindError(
+                            "AS OF is not supported on table functions".to_string(),
+                        )
+                        .into());
+                    }
+                    let mut relation = self.bind_relation_by_name(name, alias)?;
+                    self.bind_as_of(&mut relation, as_of)?;
+                    Ok(relation)
+                } else if args.is_empty() {
                     self.bind_relation_by_name(name, alias)
                 } else {
                     let func_name = &name.0[0].value;
@@ -13,10 +13,13 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use risingwave_common::catalog::{ColumnDesc, PG_CATALOG_SCHEMA_NAME};
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_sqlparser::ast::{ObjectName, TableAlias};
+use risingwave_common::util::epoch::Epoch;
+use risingwave_expr::vector_op::cast::str_to_timestamp;
+use risingwave_sqlparser::ast::{AsOf, ObjectName, TableAlias};
 
 use crate::binder::{Binder, Relation};
 use crate::catalog::source_catalog::SourceCatalog;
@@ -31,6 +34,8 @@ pub struct BoundBaseTable {
     pub table_id: TableId,
     pub table_catalog: TableCatalog,
     pub table_indexes: Vec<Arc<TableCatalog>>,
+    /// The epoch to read the table at given by `AS OF`.
+    pub as_of_epoch: Option<u64>,
 }
 
 /// `BoundTableSource` is used by DML statement on table source like insert, update.
@@ -111,6 +116,7 @@ impl Binder {
                     table_id,
                     table_catalog,
                     table_indexes,
+                    as_of_epoch: None,
                 };
 
                 (Relation::BaseTable(Box::new(table)), columns)
@@ -178,9 +184,32 @@ impl Binder {
             table_id,
             table_catalog,
             table_indexes,
+            as_of_epoch: None,
         })
     }
 
+    /// Binds `AS OF` of the `relation`, so that it's read at the given epoch.
+    pub(super) fn bind_as_of(&mut self, relation: &mut Relation, as_of: AsOf) -> Result<()> {
+        let Relation::BaseTable(table) = relation else {
+            return Err(ErrorCode::BindError(
+                "AS OF is only supported on tables and materialized views".to_string(),
+            )
+            .into());
+        };
+        let epoch = match as_of {
+            AsOf::Epoch(epoch) => epoch,
+            AsOf::Timestamp(timestamp) => {
+                // Timestamps without time zone are taken as UTC.
+                let timestamp = str_to_timestamp(&timestamp)?.0.timestamp_millis();
+                let time = SystemTime::UNIX_EPOCH + Duration::from_millis(timestamp.max(0) as u64);
+                Epoch::from_system_time(time).0
+            }
+        };
+        table.as_of_epoch = Some(epoch);
+        self.as_of_epochs.push(epoch);
+        Ok(())
+    }
+
     pub(crate) fn bind_table_source(&mut self, name: ObjectName) -> Result<BoundTableSource> {
         let (schema_name, source_name) = Self::resolve_table_name(name)?;
         let source = self
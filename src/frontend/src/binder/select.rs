@@ -26,6 +26,9 @@ use crate::binder::{Binder, Relation};
 use crate::catalog::check_valid_column_name;
 use crate::expr::{CorrelatedId, Expr as _, ExprImpl, InputRef};
 
+/// Same as the limit of PostgreSQL, to avoid generating too many grouping sets.
+const MAX_CUBE_ELEMENTS: usize = 12;
+
 #[derive(Debug, Clone)]
 pub struct BoundSelect {
     pub distinct: bool,
@@ -34,6 +37,9 @@ pub struct BoundSelect {
     pub from: Option<Relation>,
     pub where_clause: Option<ExprImpl>,
     pub group_by: Vec<ExprImpl>,
+    /// Grouping sets as indices into `group_by`. Empty if neither `GROUPING SETS`, `ROLLUP` nor
+    /// `CUBE` appears in the GROUP BY clause.
+    pub grouping_sets: Vec<Vec<usize>>,
     pub having: Option<ExprImpl>,
     schema: Schema,
 }
@@ -95,14 +101,14 @@ impl Binder {
         Self::require_bool_clause(&selection, "WHERE")?;
 
         // Bind GROUP BY clause.
-        let group_by = select
-            .group_by
-            .into_iter()
-            .map(|expr| self.bind_expr(expr))
-            .try_collect()?;
+        self.context.clause = Some(Clause::GroupBy);
+        let (group_by, grouping_sets) = self.bind_group_by(select.group_by)?;
+        self.context.clause = None;
 
         // Bind HAVING clause.
+        self.context.clause = Some(Clause::Having);
         let having = select.having.map(|expr| self.bind_expr(expr)).transpose()?;
+        self.context.clause = None;
         Self::require_bool_clause(&having, "HAVING")?;
 
         // Bind SELECT clause.
@@ -125,11 +131,105 @@ impl Binder {
             from,
             where_clause: selection,
             group_by,
+            grouping_sets,
             having,
             schema: Schema { fields },
         })
     }
 
+    /// Binds the GROUP BY clause into distinct group exprs and, when `GROUPING SETS`, `ROLLUP` or
+    /// `CUBE` is used, the grouping sets referencing them.
+    ///
+    /// Multiple items are combined by cross product, e.g. `GROUP BY a, ROLLUP(b, c)` results in
+    /// grouping sets `(a, b, c), (a, b), (a)`.
+    fn bind_group_by(&mut self, group_by: Vec<Expr>) -> Result<(Vec<ExprImpl>, Vec<Vec<usize>>)> {
+        if !group_by.iter().any(|expr| {
+            matches!(
+                expr,
+                Expr::GroupingSets(_) | Expr::Rollup(_) | Expr::Cube(_)
+            )
+        }) {
+            let group_by = group_by
+                .into_iter()
+                .map(|expr| self.bind_expr(expr))
+                .try_collect()?;
+            return Ok((group_by, vec![]));
+        }
+
+        let mut group_exprs = vec![];
+        let mut grouping_sets: Vec<Vec<usize>> = vec![vec![]];
+        for item in group_by {
+            let item_sets: Vec<Vec<usize>> = match item {
+                Expr::GroupingSets(sets) => sets
+                    .into_iter()
+                    .map(|set| self.bind_grouping_set(set, &mut group_exprs))
+                    .try_collect()?,
+                Expr::Rollup(elems) => {
+                    let elems: Vec<_> = elems
+                        .into_iter()
+                        .map(|elem| self.bind_grouping_set(elem, &mut group_exprs))
+                        .try_collect()?;
+                    (0..=elems.len())
+                        .rev()
+                        .map(|len| elems[..len].concat())
+                        .collect()
+                }
+                Expr::Cube(elems) => {
+                    if elems.len() > MAX_CUBE_ELEMENTS {
+                        return Err(ErrorCode::InvalidInputSyntax(format!(
+                            "CUBE is limited to {} elements",
+                            MAX_CUBE_ELEMENTS
+                        ))
+                        .into());
+                    }
+                    let elems: Vec<_> = elems
+                        .into_iter()
+                        .map(|elem| self.bind_grouping_set(elem, &mut group_exprs))
+                        .try_collect()?;
+                    (0..1usize << elems.len())
+                        .rev()
+                        .map(|mask| {
+                            elems
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| mask & (1 << (elems.len() - 1 - i)) != 0)
+                                .flat_map(|(_, elem)| elem.iter().copied())
+                                .collect()
+                        })
+                        .collect()
+                }
+                expr => vec![self.bind_grouping_set(vec![expr], &mut group_exprs)?],
+            };
+            grouping_sets = grouping_sets
+                .iter()
+                .cartesian_product(item_sets.iter())
+                .map(|(set, item_set)| set.iter().chain(item_set).copied().unique().collect())
+                .collect();
+        }
+        Ok((group_exprs, grouping_sets))
+    }
+
+    /// Binds exprs in a grouping set and returns their indices in `group_exprs`, adding the ones
+    /// not seen before.
+    fn bind_grouping_set(
+        &mut self,
+        set: Vec<Expr>,
+        group_exprs: &mut Vec<ExprImpl>,
+    ) -> Result<Vec<usize>> {
+        set.into_iter()
+            .map(|expr| -> Result<usize> {
+                let expr = self.bind_expr(expr)?;
+                Ok(match group_exprs.iter().position(|e| *e == expr) {
+                    Some(index) => index,
+                    None => {
+                        group_exprs.push(expr);
+                        group_exprs.len() - 1
+                    }
+                })
+            })
+            .try_collect()
+    }
+
     pub fn bind_select_list(
         &mut self,
         select_items: Vec<SelectItem>,
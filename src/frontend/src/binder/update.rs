@@ -21,6 +21,7 @@ use risingwave_common::ensure;
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::{Assignment, Expr, TableFactor, TableWithJoins};
 
+use super::bind_context::Clause;
 use super::{Binder, BoundTableSource, Relation};
 use crate::expr::{Expr as _, ExprImpl};
 
@@ -65,7 +66,9 @@ impl Binder {
         let table = self.bind_vec_table_with_joins(vec![table])?.unwrap();
         assert_matches!(table, Relation::BaseTable(_));
 
+        self.context.clause = Some(Clause::Where);
         let selection = selection.map(|expr| self.bind_expr(expr)).transpose()?;
+        self.context.clause = None;
 
         let mut assignment_exprs = HashMap::new();
         for Assignment { id, value } in assignments {
@@ -99,6 +102,17 @@ impl Binder {
 
             for (id, value) in assignments {
                 let id_expr = self.bind_expr(Expr::Identifier(id.clone()))?;
+                // The hidden row id is the primary key generated by the table, which is not
+                // supposed to be changed by the user.
+                if let ExprImpl::InputRef(input_ref) = &id_expr {
+                    if self.context.columns[input_ref.index()].is_hidden {
+                        return Err(ErrorCode::BindError(format!(
+                            "cannot assign to hidden column \"{}\"",
+                            id.real_value()
+                        ))
+                        .into());
+                    }
+                }
                 let value_expr = self.bind_expr(value)?.cast_assign(id_expr.return_type())?;
 
                 match assignment_exprs.entry(id_expr) {
@@ -79,7 +79,15 @@ pub trait CatalogWriter: Send + Sync {
 
     async fn drop_materialized_source(&self, source_id: u32, table_id: TableId) -> Result<()>;
 
-    async fn drop_materialized_view(&self, table_id: TableId) -> Result<()>;
+    /// Drops the materialized view, and the relations depending on it if `cascade` is set.
+    async fn drop_materialized_view(&self, table_id: TableId, cascade: bool) -> Result<()>;
+
+    /// Returns the names of the relations depending on the materialized view.
+    async fn rename_materialized_view(
+        &self,
+        table_id: TableId,
+        new_name: &str,
+    ) -> Result<Vec<String>>;
 
     async fn drop_source(&self, source_id: u32) -> Result<()>;
 
@@ -172,11 +180,27 @@ impl CatalogWriter for CatalogWriterImpl {
         self.wait_version(version).await
     }
 
-    async fn drop_materialized_view(&self, table_id: TableId) -> Result<()> {
-        let version = self.meta_client.drop_materialized_view(table_id).await?;
+    async fn drop_materialized_view(&self, table_id: TableId, cascade: bool) -> Result<()> {
+        let version = self
+            .meta_client
+            .drop_materialized_view(table_id, cascade)
+            .await?;
         self.wait_version(version).await
     }
 
+    async fn rename_materialized_view(
+        &self,
+        table_id: TableId,
+        new_name: &str,
+    ) -> Result<Vec<String>> {
+        let (version, dependent_relation_names) = self
+            .meta_client
+            .rename_materialized_view(table_id, new_name)
+            .await?;
+        self.wait_version(version).await?;
+        Ok(dependent_relation_names)
+    }
+
     async fn drop_source(&self, source_id: u32) -> Result<()> {
         let version = self.meta_client.drop_source(source_id).await?;
         self.wait_version(version).await
@@ -18,6 +18,10 @@ pub mod pg_matviews_info;
 pub mod pg_namespace;
 pub mod pg_type;
 pub mod pg_user;
+pub mod rw_fragments;
+pub mod rw_hummock_levels;
+pub mod rw_materialized_views;
+pub mod rw_worker_nodes;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -28,6 +32,12 @@ use risingwave_common::array::Row;
 use risingwave_common::catalog::{ColumnDesc, SysCatalogReader, TableId, DEFAULT_SUPER_USER_ID};
 use risingwave_common::error::{ErrorCode, Result};
 use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_pb::common::worker_node::State as WorkerState;
+use risingwave_pb::common::WorkerType;
+use risingwave_pb::hummock::LevelType;
+use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
+use risingwave_pb::meta::table_fragments::ActorState;
+use risingwave_pb::stream_plan::FragmentType;
 use serde_json::json;
 
 use crate::catalog::catalog_service::CatalogReader;
@@ -38,6 +48,10 @@ use crate::catalog::pg_catalog::pg_matviews_info::*;
 use crate::catalog::pg_catalog::pg_namespace::*;
 use crate::catalog::pg_catalog::pg_type::*;
 use crate::catalog::pg_catalog::pg_user::*;
+use crate::catalog::pg_catalog::rw_fragments::*;
+use crate::catalog::pg_catalog::rw_hummock_levels::*;
+use crate::catalog::pg_catalog::rw_materialized_views::*;
+use crate::catalog::pg_catalog::rw_worker_nodes::*;
 use crate::catalog::system_catalog::SystemCatalog;
 use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::worker_node_manager::WorkerNodeManagerRef;
@@ -85,6 +99,10 @@ impl SysCatalogReader for SysCatalogReaderImpl {
             PG_MATVIEWS_INFO_TABLE_NAME => self.read_mviews_info().await,
             PG_USER_TABLE_NAME => self.read_user_info(),
             PG_CLASS_TABLE_NAME => self.read_class_info(),
+            RW_MATERIALIZED_VIEWS_TABLE_NAME => self.read_materialized_views().await,
+            RW_FRAGMENTS_TABLE_NAME => self.read_fragments().await,
+            RW_WORKER_NODES_TABLE_NAME => self.read_worker_nodes().await,
+            RW_HUMMOCK_LEVELS_TABLE_NAME => self.read_hummock_levels().await,
             _ => {
                 Err(ErrorCode::ItemNotFound(format!("Invalid system table: {}", table_name)).into())
             }
@@ -247,6 +265,153 @@ impl SysCatalogReaderImpl {
 
         Ok(rows)
     }
+
+    /// Returns the id, name and schema name of the tables and materialized views, which are the
+    /// relations backed by streaming jobs.
+    fn list_streaming_relations(&self) -> Result<Vec<(u32, String, String)>> {
+        let reader = self.catalog_reader.read_guard();
+        let schemas = reader.get_all_schema_names(&self.auth_context.database)?;
+        let mut relations = vec![];
+        for schema in &schemas {
+            let schema_catalog = reader.get_schema_by_name(&self.auth_context.database, schema)?;
+            relations.extend(
+                schema_catalog
+                    .iter_table()
+                    .chain(schema_catalog.iter_mv())
+                    .map(|t| (t.id.table_id, t.name.clone(), schema.clone())),
+            );
+        }
+        Ok(relations)
+    }
+
+    async fn read_materialized_views(&self) -> Result<Vec<Row>> {
+        let relations = self.list_streaming_relations()?;
+        let table_ids = relations.iter().map(|(id, _, _)| *id).collect_vec();
+        let table_fragments = self.meta_client.list_table_fragments(&table_ids).await?;
+
+        Ok(relations
+            .into_iter()
+            .filter_map(|(id, name, schema)| {
+                let info = table_fragments.get(&id)?;
+                let fragments = &info.fragments;
+                let actors = fragments.iter().flat_map(|f| &f.actors).collect_vec();
+                let state = if actors
+                    .iter()
+                    .all(|actor| actor.state == ActorState::Running as i32)
+                {
+                    "running".to_string()
+                } else {
+                    format!("creating {}%", (info.create_progress * 100.0) as u32)
+                };
+                Some(Row::new(vec![
+                    Some(ScalarImpl::Int32(id as i32)),
+                    Some(ScalarImpl::Utf8(name)),
+                    Some(ScalarImpl::Utf8(schema)),
+                    Some(ScalarImpl::Int32(fragments.len() as i32)),
+                    Some(ScalarImpl::Int32(actors.len() as i32)),
+                    Some(ScalarImpl::Utf8(state)),
+                ]))
+            })
+            .collect_vec())
+    }
+
+    async fn read_fragments(&self) -> Result<Vec<Row>> {
+        let table_ids = self
+            .list_streaming_relations()?
+            .into_iter()
+            .map(|(id, _, _)| id)
+            .collect_vec();
+        let table_fragments = self.meta_client.list_table_fragments(&table_ids).await?;
+
+        Ok(table_fragments
+            .iter()
+            .flat_map(|(table_id, info)| {
+                info.fragments.iter().map(move |fragment| {
+                    let fragment_type = match FragmentType::from_i32(fragment.fragment_type) {
+                        Some(FragmentType::Source) => "source",
+                        Some(FragmentType::Sink) => "sink",
+                        Some(FragmentType::Others) => "others",
+                        _ => "unspecified",
+                    };
+                    let distribution_type =
+                        match FragmentDistributionType::from_i32(fragment.distribution_type) {
+                            Some(FragmentDistributionType::Single) => "single",
+                            Some(FragmentDistributionType::Hash) => "hash",
+                            _ => "unspecified",
+                        };
+                    let running_actor_count = fragment
+                        .actors
+                        .iter()
+                        .filter(|actor| actor.state == ActorState::Running as i32)
+                        .count();
+                    Row::new(vec![
+                        Some(ScalarImpl::Int32(fragment.id as i32)),
+                        Some(ScalarImpl::Int32(*table_id as i32)),
+                        Some(ScalarImpl::Utf8(fragment_type.to_string())),
+                        Some(ScalarImpl::Utf8(distribution_type.to_string())),
+                        Some(ScalarImpl::Int32(fragment.actors.len() as i32)),
+                        Some(ScalarImpl::Int32(running_actor_count as i32)),
+                    ])
+                })
+            })
+            .collect_vec())
+    }
+
+    async fn read_worker_nodes(&self) -> Result<Vec<Row>> {
+        let mut rows = vec![];
+        for (worker_type, type_name) in [
+            (WorkerType::Frontend, "frontend"),
+            (WorkerType::ComputeNode, "compute node"),
+            (WorkerType::Compactor, "compactor"),
+        ] {
+            let workers = self.meta_client.list_all_nodes(worker_type).await?;
+            rows.extend(workers.into_iter().map(|worker| {
+                let (host, port) = worker
+                    .host
+                    .map_or((String::new(), 0), |host| (host.host, host.port));
+                let state = match WorkerState::from_i32(worker.state) {
+                    Some(WorkerState::Starting) => "starting",
+                    Some(WorkerState::Running) => "running",
+                    Some(WorkerState::Draining) => "draining",
+                    _ => "unspecified",
+                };
+                Row::new(vec![
+                    Some(ScalarImpl::Int32(worker.id as i32)),
+                    Some(ScalarImpl::Utf8(host)),
+                    Some(ScalarImpl::Int32(port)),
+                    Some(ScalarImpl::Utf8(type_name.to_string())),
+                    Some(ScalarImpl::Utf8(state.to_string())),
+                    Some(ScalarImpl::Int32(worker.parallel_units.len() as i32)),
+                ])
+            }));
+        }
+        Ok(rows)
+    }
+
+    async fn read_hummock_levels(&self) -> Result<Vec<Row>> {
+        let version = self.meta_client.get_current_version().await?;
+        Ok(version
+            .levels
+            .iter()
+            .sorted_by_key(|(compaction_group_id, _)| **compaction_group_id)
+            .flat_map(|(compaction_group_id, levels)| {
+                levels.levels.iter().map(|level| {
+                    let level_type = match LevelType::from_i32(level.level_type) {
+                        Some(LevelType::Overlapping) => "overlapping",
+                        Some(LevelType::Nonoverlapping) => "nonoverlapping",
+                        _ => "unspecified",
+                    };
+                    Row::new(vec![
+                        Some(ScalarImpl::Int64(*compaction_group_id as i64)),
+                        Some(ScalarImpl::Int32(level.level_idx as i32)),
+                        Some(ScalarImpl::Utf8(level_type.to_string())),
+                        Some(ScalarImpl::Int32(level.table_infos.len() as i32)),
+                        Some(ScalarImpl::Int64(level.total_file_size as i64)),
+                    ])
+                })
+            })
+            .collect_vec())
+    }
 }
 
 // TODO: support struct column and type name when necessary.
@@ -288,7 +453,11 @@ lazy_static::lazy_static! {
             (PG_CAST_TABLE_NAME.to_string(), def_sys_catalog!(3, PG_CAST_TABLE_NAME, PG_CAST_COLUMNS)),
             (PG_MATVIEWS_INFO_TABLE_NAME.to_string(), def_sys_catalog!(4, PG_MATVIEWS_INFO_TABLE_NAME, PG_MATVIEWS_INFO_COLUMNS)),
             (PG_USER_TABLE_NAME.to_string(), def_sys_catalog!(5, PG_USER_TABLE_NAME, PG_USER_COLUMNS)),
-            (PG_CLASS_TABLE_NAME.to_string(), def_sys_catalog!(6, PG_CLASS_TABLE_NAME, PG_CLASS_COLUMNS))
+            (PG_CLASS_TABLE_NAME.to_string(), def_sys_catalog!(6, PG_CLASS_TABLE_NAME, PG_CLASS_COLUMNS)),
+            (RW_MATERIALIZED_VIEWS_TABLE_NAME.to_string(), def_sys_catalog!(7, RW_MATERIALIZED_VIEWS_TABLE_NAME, RW_MATERIALIZED_VIEWS_COLUMNS)),
+            (RW_FRAGMENTS_TABLE_NAME.to_string(), def_sys_catalog!(8, RW_FRAGMENTS_TABLE_NAME, RW_FRAGMENTS_COLUMNS)),
+            (RW_WORKER_NODES_TABLE_NAME.to_string(), def_sys_catalog!(9, RW_WORKER_NODES_TABLE_NAME, RW_WORKER_NODES_COLUMNS)),
+            (RW_HUMMOCK_LEVELS_TABLE_NAME.to_string(), def_sys_catalog!(10, RW_HUMMOCK_LEVELS_TABLE_NAME, RW_HUMMOCK_LEVELS_COLUMNS))
         ].into();
 }
 
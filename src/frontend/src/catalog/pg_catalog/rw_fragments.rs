@@ -0,0 +1,29 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The catalog `rw_fragments` lists the fragments of the streaming jobs of tables and
+/// materialized views.
+pub const RW_FRAGMENTS_TABLE_NAME: &str = "rw_fragments";
+pub const RW_FRAGMENTS_COLUMNS: &[PgCatalogColumnsDef] = &[
+    (DataType::Int32, "fragmentid"),
+    (DataType::Int32, "tableid"),
+    (DataType::Varchar, "fragmenttype"),
+    (DataType::Varchar, "distributiontype"),
+    (DataType::Int32, "actorcount"),
+    (DataType::Int32, "runningactorcount"),
+];
@@ -0,0 +1,28 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The catalog `rw_hummock_levels` describes the LSM tree of the current hummock version, with a
+/// row for each level of each compaction group.
+pub const RW_HUMMOCK_LEVELS_TABLE_NAME: &str = "rw_hummock_levels";
+pub const RW_HUMMOCK_LEVELS_COLUMNS: &[PgCatalogColumnsDef] = &[
+    (DataType::Int64, "compactiongroupid"),
+    (DataType::Int32, "levelidx"),
+    (DataType::Varchar, "leveltype"), // "overlapping" or "nonoverlapping".
+    (DataType::Int32, "filecount"),
+    (DataType::Int64, "totalsize"),
+];
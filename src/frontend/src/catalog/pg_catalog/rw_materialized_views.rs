@@ -0,0 +1,32 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The catalog `rw_materialized_views` lists the tables and materialized views with their
+/// streaming jobs, including the number of fragments and actors and whether all actors are
+/// running.
+pub const RW_MATERIALIZED_VIEWS_TABLE_NAME: &str = "rw_materialized_views";
+pub const RW_MATERIALIZED_VIEWS_COLUMNS: &[PgCatalogColumnsDef] = &[
+    (DataType::Int32, "id"),
+    (DataType::Varchar, "name"),
+    (DataType::Varchar, "schemaname"),
+    (DataType::Int32, "fragmentcount"),
+    (DataType::Int32, "actorcount"),
+    // "running" if all actors are running, otherwise "creating" with the estimated progress, e.g.
+    // "creating 42%".
+    (DataType::Varchar, "state"),
+];
@@ -0,0 +1,29 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::DataType;
+
+use crate::catalog::pg_catalog::PgCatalogColumnsDef;
+
+/// The catalog `rw_worker_nodes` lists the worker nodes in the cluster, including the ones still
+/// starting.
+pub const RW_WORKER_NODES_TABLE_NAME: &str = "rw_worker_nodes";
+pub const RW_WORKER_NODES_COLUMNS: &[PgCatalogColumnsDef] = &[
+    (DataType::Int32, "id"),
+    (DataType::Varchar, "host"),
+    (DataType::Int32, "port"),
+    (DataType::Varchar, "type"),
+    (DataType::Varchar, "state"),
+    (DataType::Int32, "parallelunitcount"),
+];
@@ -68,7 +68,10 @@ impl SchemaCatalog {
         let table: TableCatalog = prost.into();
 
         self.table_by_name.insert(name.clone(), table);
-        self.table_name_by_id.insert(id, name);
+        // The table may be renamed.
+        if let Some(old_name) = self.table_name_by_id.insert(id, name.clone()) && old_name != name {
+            self.table_by_name.remove(&old_name);
+        }
     }
 
     pub fn drop_table(&mut self, id: TableId) {
@@ -165,6 +168,10 @@ impl SchemaCatalog {
         self.table_by_name.get(table_name)
     }
 
+    pub fn get_table_by_id(&self, table_id: &TableId) -> Option<&TableCatalog> {
+        self.table_by_name.get(self.table_name_by_id.get(table_id)?)
+    }
+
     pub fn get_source_by_name(&self, source_name: &str) -> Option<&SourceCatalog> {
         self.source_by_name.get(source_name)
     }
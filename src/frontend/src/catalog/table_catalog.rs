@@ -89,6 +89,9 @@ pub struct TableCatalog {
     pub properties: HashMap<String, String>,
 
     pub read_pattern_prefix_column: u32,
+
+    /// The index of the first pk column covered by the bloom filter key.
+    pub read_pattern_start_column: u32,
 }
 
 impl TableCatalog {
@@ -164,6 +167,7 @@ impl TableCatalog {
             mapping: None,
             properties: HashMap::default(),
             read_pattern_prefix_column: self.read_pattern_prefix_column,
+            read_pattern_start_column: self.read_pattern_start_column,
         }
     }
 }
@@ -220,6 +224,7 @@ impl From<ProstTable> for TableCatalog {
             vnode_mapping: Some(vnode_mapping),
             properties: tb.properties,
             read_pattern_prefix_column: tb.read_pattern_prefix_column,
+            read_pattern_start_column: tb.read_pattern_start_column,
         }
     }
 }
@@ -290,6 +295,7 @@ mod tests {
             order_key: vec![FieldOrder {
                 index: 0,
                 direct: Direction::Asc,
+                nulls_first: false,
             }
             .to_protobuf()],
             pk: vec![0],
@@ -306,6 +312,7 @@ mod tests {
             }),
             properties: HashMap::from([(String::from("ttl"), String::from("300"))]),
             read_pattern_prefix_column: 0,
+            read_pattern_start_column: 0,
         }
         .into();
 
@@ -350,6 +357,7 @@ mod tests {
                 order_key: vec![FieldOrder {
                     index: 0,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 }],
                 distribution_key: vec![],
                 appendonly: false,
@@ -357,6 +365,7 @@ mod tests {
                 vnode_mapping: Some(mapping),
                 properties: HashMap::from([(String::from("ttl"), String::from("300"))]),
                 read_pattern_prefix_column: 0,
+                read_pattern_start_column: 0,
             }
         );
     }
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use super::{
-    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery, TableFunction,
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
+    TableFunction, WindowFunction,
 };
 
 /// with the same visit logic of `ExprVisitor`, but mutable.
@@ -27,6 +28,7 @@ pub trait ExprMutator {
             ExprImpl::Subquery(inner) => self.visit_subquery(inner),
             ExprImpl::CorrelatedInputRef(inner) => self.visit_correlated_input_ref(inner),
             ExprImpl::TableFunction(inner) => self.visit_table_function(inner),
+            ExprImpl::WindowFunction(inner) => self.visit_window_function(inner),
         }
     }
     fn visit_function_call(&mut self, func_call: &mut FunctionCall) {
@@ -51,4 +53,17 @@ pub trait ExprMutator {
             .iter_mut()
             .for_each(|expr| self.visit_expr(expr))
     }
+    fn visit_window_function(&mut self, func_call: &mut WindowFunction) {
+        func_call
+            .partition_by
+            .iter_mut()
+            .chain(
+                func_call
+                    .order_by
+                    .sort_exprs
+                    .iter_mut()
+                    .map(|e| &mut e.expr),
+            )
+            .for_each(|expr| self.visit_expr(expr))
+    }
 }
@@ -13,7 +13,8 @@
 // limitations under the License.
 
 use super::{
-    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery, TableFunction,
+    AggCall, CorrelatedInputRef, ExprImpl, FunctionCall, InputRef, Literal, Subquery,
+    TableFunction, WindowFunction,
 };
 
 /// Traverse an expression tree.
@@ -34,6 +35,7 @@ pub trait ExprVisitor {
             ExprImpl::Subquery(inner) => self.visit_subquery(inner),
             ExprImpl::CorrelatedInputRef(inner) => self.visit_correlated_input_ref(inner),
             ExprImpl::TableFunction(inner) => self.visit_table_function(inner),
+            ExprImpl::WindowFunction(inner) => self.visit_window_function(inner),
         }
     }
     fn visit_function_call(&mut self, func_call: &FunctionCall) {
@@ -55,4 +57,11 @@ pub trait ExprVisitor {
     fn visit_table_function(&mut self, func_call: &TableFunction) {
         func_call.args.iter().for_each(|expr| self.visit_expr(expr))
     }
+    fn visit_window_function(&mut self, func_call: &WindowFunction) {
+        func_call
+            .partition_by
+            .iter()
+            .chain(func_call.order_by.sort_exprs.iter().map(|e| &e.expr))
+            .for_each(|expr| self.visit_expr(expr))
+    }
 }
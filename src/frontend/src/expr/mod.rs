@@ -17,7 +17,7 @@ use fixedbitset::FixedBitSet;
 use paste::paste;
 use risingwave_common::array::Row;
 use risingwave_common::error::Result;
-use risingwave_common::types::{DataType, Datum, Scalar};
+use risingwave_common::types::{DataType, Datum, Scalar, ScalarImpl};
 use risingwave_expr::expr::{build_from_prost, AggKind};
 use risingwave_pb::expr::{ExprNode, ProjectSetSelectItem};
 
@@ -28,6 +28,7 @@ mod input_ref;
 mod literal;
 mod subquery;
 mod table_function;
+mod window_function;
 
 mod expr_mutator;
 mod expr_rewriter;
@@ -42,6 +43,7 @@ pub use input_ref::{as_alias_display, input_ref_to_column_indices, InputRef, Inp
 pub use literal::Literal;
 pub use subquery::{Subquery, SubqueryKind};
 pub use table_function::{TableFunction, TableFunctionType};
+pub use window_function::{WindowFunction, WindowFunctionType};
 
 pub type ExprType = risingwave_pb::expr::expr_node::Type;
 
@@ -72,6 +74,7 @@ pub enum ExprImpl {
     AggCall(Box<AggCall>),
     Subquery(Box<Subquery>),
     TableFunction(Box<TableFunction>),
+    WindowFunction(Box<WindowFunction>),
 }
 
 impl ExprImpl {
@@ -209,7 +212,7 @@ macro_rules! impl_has_variant {
     };
 }
 
-impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction}
+impl_has_variant! {InputRef, Literal, FunctionCall, AggCall, Subquery, TableFunction, WindowFunction}
 
 impl ExprImpl {
     /// Used to check whether the expression has [`CorrelatedInputRef`].
@@ -475,6 +478,35 @@ impl ExprImpl {
         }
     }
 
+    /// Returns the input ref and the fixed prefix of `input_ref LIKE 'prefix%...'`, i.e. the
+    /// characters before the first wildcard of a constant pattern. All matched rows are in the
+    /// range of strings starting with the prefix, but the LIKE must still be applied to them.
+    pub fn as_like_prefix(&self) -> Option<(InputRef, String)> {
+        if let ExprImpl::FunctionCall(function_call) = self &&
+        function_call.get_expr_type() == ExprType::Like {
+            let (_, op1, op2) = function_call.clone().decompose_as_binary();
+            let (ExprImpl::InputRef(input_ref), ExprImpl::Literal(pattern)) = (op1, op2) else {
+                return None;
+            };
+            let Some(ScalarImpl::Utf8(pattern)) = pattern.get_data() else {
+                return None;
+            };
+            let mut prefix = String::new();
+            let mut chars = pattern.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '%' | '_' => return (!prefix.is_empty()).then(|| (*input_ref, prefix)),
+                    '\\' => prefix.push(chars.next()?),
+                    c => prefix.push(c),
+                }
+            }
+            // A pattern without wildcards is bound as an equality instead.
+            None
+        } else {
+            None
+        }
+    }
+
     pub fn to_project_set_select_item_proto(&self) -> ProjectSetSelectItem {
         use risingwave_pb::expr::project_set_select_item::SelectItem::*;
 
@@ -497,6 +529,7 @@ impl Expr for ExprImpl {
             ExprImpl::Subquery(expr) => expr.return_type(),
             ExprImpl::CorrelatedInputRef(expr) => expr.return_type(),
             ExprImpl::TableFunction(expr) => expr.return_type(),
+            ExprImpl::WindowFunction(expr) => expr.return_type(),
         }
     }
 
@@ -511,6 +544,7 @@ impl Expr for ExprImpl {
             ExprImpl::TableFunction(_e) => {
                 unreachable!("Table function should not be converted to ExprNode")
             }
+            ExprImpl::WindowFunction(e) => e.to_expr_proto(),
         }
     }
 }
@@ -557,6 +591,12 @@ impl From<TableFunction> for ExprImpl {
     }
 }
 
+impl From<WindowFunction> for ExprImpl {
+    fn from(wf: WindowFunction) -> Self {
+        ExprImpl::WindowFunction(Box::new(wf))
+    }
+}
+
 impl From<Condition> for ExprImpl {
     fn from(c: Condition) -> Self {
         merge_expr_by_binary(
@@ -583,6 +623,7 @@ impl std::fmt::Debug for ExprImpl {
                     f.debug_tuple("CorrelatedInputRef").field(arg0).finish()
                 }
                 Self::TableFunction(arg0) => f.debug_tuple("TableFunction").field(arg0).finish(),
+                Self::WindowFunction(arg0) => f.debug_tuple("WindowFunction").field(arg0).finish(),
             };
         }
         match self {
@@ -593,6 +634,7 @@ impl std::fmt::Debug for ExprImpl {
             Self::Subquery(x) => write!(f, "{:?}", x),
             Self::CorrelatedInputRef(x) => write!(f, "{:?}", x),
             Self::TableFunction(x) => write!(f, "{:?}", x),
+            Self::WindowFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
@@ -630,6 +672,7 @@ impl std::fmt::Debug for ExprDisplay<'_> {
                 // TODO: TableFunctionCallVerboseDisplay
                 write!(f, "{:?}", x)
             }
+            ExprImpl::WindowFunction(x) => write!(f, "{:?}", x),
         }
     }
 }
@@ -663,4 +706,31 @@ mod tests {
         let s = format!("{:#?}", e);
         assert!(s.contains("return_type: Boolean"))
     }
+
+    #[test]
+    fn test_as_like_prefix() {
+        fn like(pattern: &str) -> ExprImpl {
+            FunctionCall::new(
+                ExprType::Like,
+                vec![
+                    InputRef::new(0, DataType::Varchar).into(),
+                    Literal::new(
+                        Some(ScalarImpl::Utf8(pattern.to_string())),
+                        DataType::Varchar,
+                    )
+                    .into(),
+                ],
+            )
+            .unwrap()
+            .into()
+        }
+
+        let (input_ref, prefix) = like("abc%").as_like_prefix().unwrap();
+        assert_eq!(input_ref.index(), 0);
+        assert_eq!(prefix, "abc");
+        assert_eq!(like("ab_d%").as_like_prefix().unwrap().1, "ab");
+        assert_eq!(like("a\\%b%").as_like_prefix().unwrap().1, "a%b");
+        assert!(like("%abc").as_like_prefix().is_none());
+        assert!(like("abc").as_like_prefix().is_none());
+    }
 }
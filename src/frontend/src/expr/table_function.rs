@@ -16,7 +16,7 @@ use std::str::FromStr;
 
 use itertools::Itertools;
 use risingwave_common::error::ErrorCode;
-use risingwave_common::types::{unnested_list_type, DataType};
+use risingwave_common::types::{unnested_list_type, DataType, IntervalUnit, ScalarImpl};
 use risingwave_pb::expr::table_function::Type;
 use risingwave_pb::expr::TableFunction as TableFunctionProst;
 
@@ -112,6 +112,7 @@ impl TableFunction {
                 }
 
                 let data_type = type_check(&args)?;
+                check_generate_series_step(&args)?;
 
                 Ok(TableFunction {
                     args,
@@ -173,6 +174,44 @@ impl TableFunction {
     }
 }
 
+/// Rejects a constant step of `generate_series` that isn't positive, or that can't reach the
+/// constant stop from the constant start, instead of generating a series that never ends.
+fn check_generate_series_step(args: &[ExprImpl]) -> Result<()> {
+    fn const_value(expr: &ExprImpl) -> Option<&ScalarImpl> {
+        match expr {
+            ExprImpl::Literal(literal) => literal.get_data().as_ref(),
+            _ => None,
+        }
+    }
+
+    let Some(step) = const_value(&args[2]) else {
+        return Ok(());
+    };
+    let is_positive = match step {
+        ScalarImpl::Int32(step) => *step > 0,
+        ScalarImpl::Interval(step) => *step > IntervalUnit::from_millis(0),
+        _ => return Ok(()),
+    };
+    if !is_positive {
+        return Err(ErrorCode::BindError(format!(
+            "Invalid arguments for Generate series function: step {} must be positive",
+            step
+        ))
+        .into());
+    }
+
+    if let (Some(start), Some(stop)) = (const_value(&args[0]), const_value(&args[1])) {
+        if start > stop {
+            return Err(ErrorCode::BindError(format!(
+                "Invalid arguments for Generate series function: start {} is greater than stop {}",
+                start, stop
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
 impl std::fmt::Debug for TableFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if f.alternate() {
@@ -554,6 +554,7 @@ fn build_type_derive_map() -> FuncSigMap {
     for t in [T::Timestamp, T::Date] {
         map.insert(E::TumbleStart, vec![t, T::Interval], T::Timestamp);
     }
+    map.insert(E::DateTrunc, vec![T::Varchar, T::Timestamp], T::Timestamp);
 
     // string expressions
     for e in [E::Trim, E::Ltrim, E::Rtrim, E::Lower, E::Upper, E::Md5] {
@@ -590,6 +591,8 @@ fn build_type_derive_map() -> FuncSigMap {
     }
     map.insert(E::Position, vec![T::Varchar, T::Varchar], T::Int32);
     map.insert(E::Like, vec![T::Varchar, T::Varchar], T::Boolean);
+    map.insert(E::ILike, vec![T::Varchar, T::Varchar], T::Boolean);
+    map.insert(E::LikeEscape, vec![T::Varchar, T::Varchar], T::Varchar);
     map.insert(
         E::SplitPart,
         vec![T::Varchar, T::Varchar, T::Int32],
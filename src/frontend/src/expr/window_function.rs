@@ -0,0 +1,106 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::str::FromStr;
+
+use risingwave_common::types::DataType;
+
+use super::{AggOrderBy, Expr, ExprImpl};
+
+/// A window function, e.g. `row_number() OVER (PARTITION BY a ORDER BY b)`. It computes a value
+/// for each row from the rows of the same partition.
+///
+/// It's only allowed in the select list, and is planned as a
+/// [`LogicalOverWindow`](crate::optimizer::plan_node::LogicalOverWindow).
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct WindowFunction {
+    pub function_type: WindowFunctionType,
+    pub partition_by: Vec<ExprImpl>,
+    pub order_by: AggOrderBy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowFunctionType {
+    RowNumber,
+}
+
+impl WindowFunctionType {
+    pub fn name(&self) -> &str {
+        match self {
+            WindowFunctionType::RowNumber => "row_number",
+        }
+    }
+}
+
+impl FromStr for WindowFunctionType {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("row_number") {
+            Ok(WindowFunctionType::RowNumber)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl WindowFunction {
+    pub fn new(
+        function_type: WindowFunctionType,
+        partition_by: Vec<ExprImpl>,
+        order_by: AggOrderBy,
+    ) -> Self {
+        Self {
+            function_type,
+            partition_by,
+            order_by,
+        }
+    }
+}
+
+impl std::fmt::Debug for WindowFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.debug_struct("WindowFunction")
+                .field("function_type", &self.function_type)
+                .field("partition_by", &self.partition_by)
+                .field("order_by", &self.order_by)
+                .finish()
+        } else {
+            write!(
+                f,
+                "{}() over (partition by {:?} order by {:?})",
+                self.function_type.name(),
+                self.partition_by,
+                self.order_by
+                    .sort_exprs
+                    .iter()
+                    .map(|e| format!("{:?} {}", e.expr, e.direction))
+                    .collect::<Vec<_>>()
+            )
+        }
+    }
+}
+
+impl Expr for WindowFunction {
+    fn return_type(&self) -> DataType {
+        match self.function_type {
+            WindowFunctionType::RowNumber => DataType::Int64,
+        }
+    }
+
+    fn to_expr_proto(&self) -> risingwave_pb::expr::ExprNode {
+        unreachable!("Window function should not be converted to ExprNode")
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use pgwire::pg_response::{PgResponse, StatementType};
+use risingwave_common::error::ErrorCode::PermissionDenied;
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_sqlparser::ast::{Ident, ObjectName};
+
+use super::privilege::check_super_user;
+use crate::binder::Binder;
+use crate::session::OptimizerContext;
+
+/// Renames the materialized view. As the streaming job and the state tables refer to it by id,
+/// only the catalog is changed.
+pub async fn handle_rename_mv(
+    context: OptimizerContext,
+    table_name: ObjectName,
+    new_name: Ident,
+) -> Result<PgResponse> {
+    let session = context.session_ctx;
+    let (schema_name, table_name) = Binder::resolve_table_name(table_name)?;
+    let new_name = Binder::resolve_table_name(ObjectName(vec![new_name]))?.1;
+
+    let catalog_reader = session.env().catalog_reader();
+    let table_id = {
+        let reader = catalog_reader.read_guard();
+        let table = reader.get_table_by_name(session.database(), &schema_name, &table_name)?;
+
+        let schema_owner = reader
+            .get_schema_by_name(session.database(), &schema_name)
+            .unwrap()
+            .owner();
+        if session.user_id() != table.owner
+            && session.user_id() != schema_owner
+            && !check_super_user(&session)
+        {
+            return Err(PermissionDenied("Do not have the privilege".to_string()).into());
+        }
+
+        if table.associated_source_id().is_some() || table.is_index_on.is_some() {
+            return Err(RwError::from(ErrorCode::InvalidInputSyntax(format!(
+                "\"{}\" is not a materialized view",
+                table_name
+            ))));
+        }
+
+        reader.check_relation_name_duplicated(session.database(), &schema_name, &new_name)?;
+        table.id()
+    };
+
+    let catalog_writer = session.env().catalog_writer();
+    let dependent_relation_names = catalog_writer
+        .rename_materialized_view(table_id, &new_name)
+        .await?;
+
+    // The dependent relations are not affected, but their definitions still mention the old name.
+    if dependent_relation_names.is_empty() {
+        Ok(PgResponse::empty_result(
+            StatementType::ALTER_MATERIALIZED_VIEW,
+        ))
+    } else {
+        Ok(PgResponse::empty_result_with_notice(
+            StatementType::ALTER_MATERIALIZED_VIEW,
+            format!(
+                "relations depending on {} still refer to it as {} in their definitions: {}",
+                new_name,
+                table_name,
+                dependent_relation_names.join(", ")
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::catalog::{DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME};
+
+    use crate::test_utils::LocalFrontend;
+
+    #[tokio::test]
+    async fn test_rename_mv_handler() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int);").await.unwrap();
+        frontend
+            .run_sql("create materialized view mv as select v1 from t;")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create materialized view mv2 as select v1 from t;")
+            .await
+            .unwrap();
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let table_id = catalog_reader
+            .read_guard()
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv")
+            .unwrap()
+            .id();
+
+        // The new name must be unique, and only materialized views can be renamed.
+        assert!(frontend
+            .run_sql("alter materialized view mv rename to mv2;")
+            .await
+            .is_err());
+        assert!(frontend
+            .run_sql("alter materialized view t rename to t2;")
+            .await
+            .is_err());
+
+        frontend
+            .run_sql("alter materialized view mv rename to mv3;")
+            .await
+            .unwrap();
+        let reader = catalog_reader.read_guard();
+        assert!(reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv")
+            .is_err());
+        let table = reader
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv3")
+            .unwrap();
+        assert_eq!(table.id(), table_id);
+    }
+}
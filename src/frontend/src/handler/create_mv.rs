@@ -31,6 +31,10 @@ use crate::planner::Planner;
 use crate::session::{OptimizerContext, OptimizerContextRef, SessionImpl};
 use crate::stream_fragmenter::StreamFragmenter;
 
+/// The `WITH` option to set the retention of the states of the streaming joins in the materialized
+/// view, overriding `RW_STREAMING_JOIN_STATE_RETENTION`.
+pub const STREAMING_JOIN_STATE_RETENTION_OPTION: &str = "streaming_join_state_retention";
+
 /// Generate create MV plan, return plan and mv table info.
 pub fn gen_create_mv_plan(
     session: &SessionImpl,
@@ -84,6 +88,16 @@ pub fn gen_create_mv_plan(
         }
     }
 
+    if let Some(retention) = properties.get(STREAMING_JOIN_STATE_RETENTION_OPTION) {
+        let retention = retention.parse().map_err(|_| {
+            ErrorCode::InvalidInputSyntax(format!(
+                "invalid {}: {}",
+                STREAMING_JOIN_STATE_RETENTION_OPTION, retention
+            ))
+        })?;
+        context.set_streaming_join_state_retention(retention);
+    }
+
     let mut plan_root = Planner::new(context).plan_query(bound)?;
     plan_root.set_required_dist(RequiredDist::Any);
     let materialize = plan_root.gen_create_mv_plan(table_name)?;
@@ -105,11 +119,36 @@ pub fn gen_create_mv_plan(
 pub async fn handle_create_mv(
     context: OptimizerContext,
     name: ObjectName,
+    if_not_exists: bool,
     query: Box<Query>,
     with_options: WithProperties,
 ) -> Result<PgResponse> {
     let session = context.session_ctx.clone();
 
+    // An `ORDER BY .. LIMIT ..` is maintained as a TopN, but a global order of all the rows can't
+    // be maintained incrementally.
+    if !query.order_by.is_empty() && query.limit.is_none() && query.offset.is_none() {
+        return Err(ErrorCode::InvalidInputSyntax(
+            "ORDER BY without LIMIT or OFFSET is not supported in a materialized view".to_string(),
+        )
+        .into());
+    }
+
+    // Check before planning, so that nothing is created in meta for a no-op.
+    if if_not_exists {
+        let (schema_name, table_name) = Binder::resolve_table_name(name.clone())?;
+        let catalog_reader = session.env().catalog_reader().read_guard();
+        let schema = catalog_reader.get_schema_by_name(session.database(), &schema_name)?;
+        if schema.get_table_by_name(&table_name).is_some()
+            || schema.get_source_by_name(&table_name).is_some()
+        {
+            return Ok(PgResponse::empty_result_with_notice(
+                StatementType::CREATE_MATERIALIZED_VIEW,
+                format!("relation {} exists, skipping", table_name),
+            ));
+        }
+    }
+
     let (table, graph) = {
         let (plan, table) = gen_create_mv_plan(
             &session,
@@ -207,6 +246,67 @@ pub mod tests {
         assert_eq!(columns, expected_columns);
     }
 
+    #[tokio::test]
+    async fn test_create_mv_if_not_exists() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int)").await.unwrap();
+
+        let sql = "create materialized view if not exists mv as select v1 from t";
+        frontend.run_sql(sql).await.unwrap();
+        // Creating again is a no-op, and the existing one is kept.
+        let sql = "create materialized view if not exists mv as select v1 as v2 from t";
+        frontend.run_sql(sql).await.unwrap();
+        let sql = "create materialized view mv as select v1 from t";
+        assert!(frontend.run_sql(sql).await.is_err());
+
+        let session = frontend.session_ref();
+        let catalog_reader = session.env().catalog_reader();
+        let table = catalog_reader
+            .read_guard()
+            .get_table_by_name(DEFAULT_DATABASE_NAME, DEFAULT_SCHEMA_NAME, "mv")
+            .unwrap()
+            .clone();
+        assert!(table.columns.iter().any(|c| c.name() == "v1"));
+        assert!(!table.columns.iter().any(|c| c.name() == "v2"));
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_with_join_state_retention() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t1 (k int, v int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql("create table t2 (k int, v int)")
+            .await
+            .unwrap();
+
+        let sql = "create materialized view mv1 with (streaming_join_state_retention = 60) as \
+                   select t1.v as v1, t2.v as v2 from t1 join t2 on t1.k = t2.k";
+        frontend.run_sql(sql).await.unwrap();
+        let sql = "create materialized view mv2 with (streaming_join_state_retention = 'forever') \
+                   as select t1.v as v1, t2.v as v2 from t1 join t2 on t1.k = t2.k";
+        assert!(frontend.run_sql(sql).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_mv_order_by() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend.run_sql("create table t (v1 int)").await.unwrap();
+
+        let sql = "create materialized view mv1 as select v1 from t order by v1";
+        let err = frontend.run_sql(sql).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input syntax: ORDER BY without LIMIT or OFFSET is not supported in a \
+             materialized view"
+        );
+
+        let sql = "create materialized view mv2 as select v1 from t order by v1 limit 3";
+        frontend.run_sql(sql).await.unwrap();
+    }
+
     /// When creating MV, The only thing to allow without explicit alias is `InputRef`.
     #[tokio::test]
     async fn test_no_alias() {
@@ -108,6 +108,22 @@ pub async fn handle_create_source(
             columns: bind_sql_columns(stmt.columns)?,
             pk_column_ids: vec![0],
         },
+        SourceSchema::Avro(avro_schema) => StreamSourceInfo {
+            properties: with_properties.clone(),
+            row_format: RowFormatType::Avro as i32,
+            row_schema_location: avro_schema.row_schema_location.0.clone(),
+            row_id_index: 0,
+            columns: bind_sql_columns(stmt.columns)?,
+            pk_column_ids: vec![0],
+        },
+        SourceSchema::Csv => StreamSourceInfo {
+            properties: with_properties.clone(),
+            row_format: RowFormatType::Csv as i32,
+            row_schema_location: "".to_string(),
+            row_id_index: 0,
+            columns: bind_sql_columns(stmt.columns)?,
+            pk_column_ids: vec![0],
+        },
     };
 
     let session = context.session_ctx.clone();
@@ -57,7 +57,9 @@ pub async fn handle_drop_index(
     };
 
     let catalog_writer = session.env().catalog_writer();
-    catalog_writer.drop_materialized_view(table_id).await?;
+    catalog_writer
+        .drop_materialized_view(table_id, false)
+        .await?;
 
     Ok(PgResponse::empty_result(StatementType::DROP_INDEX))
 }
@@ -15,7 +15,7 @@
 use pgwire::pg_response::{PgResponse, StatementType};
 use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::error::{ErrorCode, Result, RwError};
-use risingwave_sqlparser::ast::ObjectName;
+use risingwave_sqlparser::ast::{DropMode, ObjectName};
 
 use super::privilege::check_super_user;
 use crate::binder::Binder;
@@ -25,6 +25,8 @@ use crate::session::OptimizerContext;
 pub async fn handle_drop_mv(
     context: OptimizerContext,
     table_name: ObjectName,
+    if_exists: bool,
+    mode: Option<DropMode>,
 ) -> Result<PgResponse> {
     let session = context.session_ctx;
     let (schema_name, table_name) = Binder::resolve_table_name(table_name)?;
@@ -35,7 +37,19 @@ pub async fn handle_drop_mv(
 
     let table_id = {
         let reader = catalog_reader.read_guard();
-        let table = reader.get_table_by_name(session.database(), &schema_name, &table_name)?;
+        let table = match reader.get_table_by_name(session.database(), &schema_name, &table_name) {
+            Ok(table) => table,
+            Err(err) => {
+                return if if_exists {
+                    Ok(PgResponse::empty_result_with_notice(
+                        StatementType::DROP_MATERIALIZED_VIEW,
+                        format!("materialized view {} does not exist, skipping", table_name),
+                    ))
+                } else {
+                    Err(err)
+                };
+            }
+        };
 
         let schema_owner = reader
             .get_schema_by_name(session.database(), &schema_name)
@@ -65,7 +79,9 @@ pub async fn handle_drop_mv(
     };
 
     let catalog_writer = session.env().catalog_writer();
-    catalog_writer.drop_materialized_view(table_id).await?;
+    catalog_writer
+        .drop_materialized_view(table_id, matches!(mode, Some(DropMode::Cascade)))
+        .await?;
 
     Ok(PgResponse::empty_result(
         StatementType::DROP_MATERIALIZED_VIEW,
@@ -97,5 +113,12 @@ mod tests {
             .ok()
             .cloned();
         assert!(table.is_none());
+
+        // Dropping a dropped one is fine with `IF EXISTS`.
+        assert!(frontend.run_sql(sql_drop_mv).await.is_err());
+        frontend
+            .run_sql("drop materialized view if exists mv;")
+            .await
+            .unwrap();
     }
 }
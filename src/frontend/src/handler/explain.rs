@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
+use futures_async_stream::for_await;
+use itertools::Itertools;
 use pgwire::pg_field_descriptor::{PgFieldDescriptor, TypeOid};
 use pgwire::pg_response::{PgResponse, StatementType};
 use pgwire::types::Row;
-use risingwave_common::error::Result;
-use risingwave_common::session_config::QueryMode;
+use risingwave_batch::executor::ExecutorActualsCollector;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_sqlparser::ast::Statement;
 
 use super::create_index::gen_create_index_plan;
@@ -26,8 +29,11 @@ use super::create_mv::gen_create_mv_plan;
 use super::create_table::gen_create_table_plan;
 use super::util::handle_with_properties;
 use crate::binder::Binder;
-use crate::handler::util::force_local_mode;
+use crate::handler::util::{force_local_mode, gen_batch_plan_by_mode};
+use crate::optimizer::plan_node::PlanNodeId;
+use crate::optimizer::{estimate_row_count, PlanRef};
 use crate::planner::Planner;
+use crate::scheduler::{BatchPlanFragmenter, LocalQueryExecution};
 use crate::session::OptimizerContext;
 
 pub(super) fn handle_explain(
@@ -41,6 +47,8 @@ pub(super) fn handle_explain(
     context.explain_trace.store(trace, Ordering::Release);
     // bind, plan, optimize, and serialize here
     let mut planner = Planner::new(context.into());
+    // The mode a batch query would be executed in, and why.
+    let mut query_mode = None;
     let plan = match stmt {
         Statement::CreateView {
             or_replace: false,
@@ -92,23 +100,18 @@ pub(super) fn handle_explain(
                 binder.bind(stmt)?
             };
 
-            let query_mode = if force_local_mode(&bound) {
-                QueryMode::Local
-            } else {
-                session.config().get_query_mode()
-            };
+            let force_local = force_local_mode(&bound);
             let logical = planner.plan(bound)?;
-            match query_mode {
-                QueryMode::Local => logical.gen_batch_local_plan()?,
-                QueryMode::Distributed => logical.gen_batch_query_plan()?,
-            }
+            let (plan, mode, reason) = gen_batch_plan_by_mode(&session, &logical, force_local)?;
+            query_mode = Some((mode, reason));
+            plan
         }
     };
 
     let ctx = plan.plan_base().ctx.clone();
     let explain_trace = ctx.is_explain_trace();
 
-    let rows = if explain_trace {
+    let mut rows = if explain_trace {
         let trace = ctx.take_trace();
         trace
             .iter()
@@ -122,6 +125,11 @@ pub(super) fn handle_explain(
             .map(|s| Row::new(vec![Some(s.to_string().into())]))
             .collect::<Vec<_>>()
     };
+    if let Some((mode, reason)) = query_mode {
+        rows.push(Row::new(vec![Some(
+            format!("Query Mode: {} ({})", mode, reason).into(),
+        )]));
+    }
 
     Ok(PgResponse::new(
         StatementType::EXPLAIN,
@@ -134,3 +142,121 @@ pub(super) fn handle_explain(
         true,
     ))
 }
+
+/// Executes the query in local mode, and annotates each plan node with its estimated rows, the rows
+/// it produced and the time spent in it and its inputs. The plan nodes pushed down to the compute
+/// nodes through exchanges report their actuals back with the results. The time is omitted if
+/// `RW_EXPLAIN_ANALYZE_TIMING` is off, to get a stable output.
+pub(super) async fn handle_explain_analyze(
+    context: OptimizerContext,
+    stmt: Statement,
+    verbose: bool,
+) -> Result<PgResponse> {
+    if !matches!(stmt, Statement::Query(_)) {
+        return Err(ErrorCode::NotImplemented(
+            "EXPLAIN ANALYZE for statements other than queries".into(),
+            None.into(),
+        )
+        .into());
+    }
+    let session = context.session_ctx.clone();
+    context.explain_verbose.store(verbose, Ordering::Release);
+
+    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
+    let (plan_lines, query) = {
+        let bound = {
+            let mut binder = Binder::new(
+                session.env().catalog_reader().read_guard(),
+                session.database().to_string(),
+            );
+            binder.bind(stmt)?
+        };
+        let root = Planner::new(context.into()).plan(bound)?;
+        let plan = root.gen_batch_local_plan()?;
+
+        let mut plan_lines = vec![];
+        explain_with_estimates(&plan, 0, &mut plan_lines);
+        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
+        (plan_lines, plan_fragmenter.split(plan)?)
+    };
+
+    let collector = ExecutorActualsCollector::default();
+    let execution =
+        LocalQueryExecution::new(query, session.env().clone(), "", session.auth_context())
+            .with_actuals_collector(collector.clone());
+    let start_time = Instant::now();
+    #[for_await]
+    for chunk in execution.run() {
+        chunk?;
+    }
+    let execution_time = start_time.elapsed();
+    let timing = session.config().get_explain_analyze_timing();
+
+    let mut rows = plan_lines
+        .into_iter()
+        .map(|(line, plan_node_id, estimated_rows)| {
+            let estimated_rows = match estimated_rows {
+                Some(rows) => rows.to_string(),
+                None => "unknown".to_string(),
+            };
+            let line = match collector.get(plan_node_id.0) {
+                Some(actuals) if timing => format!(
+                    "{} (estimated rows={}, actual rows={}, time={:.3}ms)",
+                    line,
+                    estimated_rows,
+                    actuals.rows,
+                    actuals.elapsed.as_secs_f64() * 1000.0
+                ),
+                Some(actuals) => format!(
+                    "{} (estimated rows={}, actual rows={})",
+                    line, estimated_rows, actuals.rows
+                ),
+                None => format!("{} (estimated rows={})", line, estimated_rows),
+            };
+            Row::new(vec![Some(line.into())])
+        })
+        .collect_vec();
+    if timing {
+        rows.push(Row::new(vec![Some(
+            format!(
+                "Execution Time: {:.3}ms",
+                execution_time.as_secs_f64() * 1000.0
+            )
+            .into(),
+        )]));
+    }
+
+    Ok(PgResponse::new(
+        StatementType::EXPLAIN,
+        rows.len() as i32,
+        rows,
+        vec![PgFieldDescriptor::new(
+            "QUERY PLAN".to_owned(),
+            TypeOid::Varchar,
+        )],
+        true,
+    ))
+}
+
+/// Explains the plan like [`PlanRef::explain`], with the id and the estimated rows of the plan
+/// node on each line. Returns the estimated rows of the plan.
+fn explain_with_estimates(
+    plan: &PlanRef,
+    level: usize,
+    lines: &mut Vec<(String, PlanNodeId, Option<usize>)>,
+) -> Option<usize> {
+    let line_idx = lines.len();
+    lines.push((
+        format!("{}{}", " ".repeat(level * 2), plan),
+        plan.id(),
+        None,
+    ));
+    let input_row_counts = plan
+        .inputs()
+        .iter()
+        .map(|input| explain_with_estimates(input, level + 1, lines))
+        .collect_vec();
+    let row_count = estimate_row_count(plan, &input_row_counts);
+    lines[line_idx].2 = row_count;
+    row_count
+}
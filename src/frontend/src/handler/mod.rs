@@ -17,10 +17,13 @@ use std::sync::Arc;
 use pgwire::pg_response::PgResponse;
 use pgwire::pg_response::StatementType::{ABORT, START_TRANSACTION};
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_sqlparser::ast::{DropStatement, ObjectType, Statement, WithProperties};
+use risingwave_sqlparser::ast::{
+    AlterViewOperation, DropStatement, ObjectType, Statement, WithProperties,
+};
 
 use crate::session::{OptimizerContext, SessionImpl};
 
+mod alter_mv;
 pub mod alter_user;
 mod create_database;
 pub mod create_index;
@@ -57,6 +60,12 @@ pub async fn handle(
 ) -> Result<PgResponse> {
     let context = OptimizerContext::new(session.clone(), Arc::from(sql));
     match stmt {
+        Statement::Explain {
+            statement,
+            analyze: true,
+            verbose,
+            ..
+        } => explain::handle_explain_analyze(context, *statement, verbose).await,
         Statement::Explain {
             statement,
             verbose,
@@ -97,7 +106,9 @@ pub async fn handle(
             drop_mode,
         }) => match object_type {
             ObjectType::Table => drop_table::handle_drop_table(context, object_name).await,
-            ObjectType::MaterializedView => drop_mv::handle_drop_mv(context, object_name).await,
+            ObjectType::MaterializedView => {
+                drop_mv::handle_drop_mv(context, object_name, if_exists, drop_mode.into()).await
+            }
             ObjectType::Index => drop_index::handle_drop_index(context, object_name).await,
             ObjectType::Source => drop_source::handle_drop_source(context, object_name).await,
             ObjectType::Sink => drop_sink::handle_drop_sink(context, object_name).await,
@@ -129,11 +140,26 @@ pub async fn handle(
         Statement::CreateView {
             materialized: true,
             or_replace: false,
+            if_not_exists,
             name,
             query,
             with_options,
             ..
-        } => create_mv::handle_create_mv(context, name, query, WithProperties(with_options)).await,
+        } => {
+            create_mv::handle_create_mv(
+                context,
+                name,
+                if_not_exists,
+                query,
+                WithProperties(with_options),
+            )
+            .await
+        }
+        Statement::AlterView {
+            materialized: true,
+            name,
+            operation: AlterViewOperation::RenameView { view_name },
+        } => alter_mv::handle_rename_mv(context, name, view_name).await,
         Statement::Flush => flush::handle_flush(context).await,
         Statement::SetVariable {
             local: _,
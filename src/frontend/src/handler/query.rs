@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use futures_async_stream::for_await;
 use pgwire::pg_field_descriptor::PgFieldDescriptor;
 use pgwire::pg_response::{PgResponse, StatementType};
@@ -21,13 +23,13 @@ use risingwave_common::session_config::QueryMode;
 use risingwave_sqlparser::ast::Statement;
 use tracing::debug;
 
-use crate::binder::{Binder, BoundStatement};
-use crate::handler::util::{force_local_mode, to_pg_field, to_pg_rows};
+use crate::binder::Binder;
+use crate::handler::util::{force_local_mode, gen_batch_plan_by_mode, to_pg_field, to_pg_rows};
 use crate::planner::Planner;
 use crate::scheduler::{
-    BatchPlanFragmenter, ExecutionContext, ExecutionContextRef, LocalQueryExecution,
+    BatchPlanFragmenter, ExecutionContext, ExecutionContextRef, LocalQueryExecution, Query,
 };
-use crate::session::OptimizerContext;
+use crate::session::{OptimizerContext, SessionImpl};
 
 pub async fn handle_query(
     context: OptimizerContext,
@@ -37,25 +39,62 @@ pub async fn handle_query(
     let stmt_type = to_statement_type(&stmt);
     let session = context.session_ctx.clone();
 
-    let bound = {
+    let (bound, as_of_epochs) = {
         let mut binder = Binder::new(
             session.env().catalog_reader().read_guard(),
             session.database().to_string(),
         );
-        binder.bind(stmt)?
+        let bound = binder.bind(stmt)?;
+        (bound, binder.as_of_epochs().to_vec())
     };
 
-    let query_mode = if force_local_mode(&bound) {
+    // The historical snapshots must not have been garbage collected yet, and are pinned until the
+    // query completes.
+    let mut pinned_epochs = Vec::with_capacity(as_of_epochs.len());
+    for epoch in as_of_epochs {
+        pinned_epochs.push(
+            session
+                .env()
+                .hummock_snapshot_manager()
+                .pin_epoch(epoch)
+                .await?,
+        );
+    }
+
+    let force_local = force_local_mode(&bound);
+    if force_local {
         debug!("force query mode to local");
-        QueryMode::Local
-    } else {
-        session.config().get_query_mode()
+    }
+
+    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
+    let (query, query_mode, pg_descs) = {
+        let root = Planner::new(context.into()).plan(bound)?;
+
+        let pg_descs = root
+            .schema()
+            .fields()
+            .iter()
+            .map(to_pg_field)
+            .collect::<Vec<PgFieldDescriptor>>();
+
+        let (plan, query_mode, reason) = gen_batch_plan_by_mode(&session, &root, force_local)?;
+        debug!("query_mode:{:?}, because {}", query_mode, reason);
+        tracing::trace!(
+            "Generated {} plan: {:?}",
+            query_mode,
+            plan.explain_to_string()?
+        );
+
+        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
+        let query = plan_fragmenter.split(plan)?;
+        tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
+        (query, query_mode, pg_descs)
     };
-    debug!("query_mode:{:?}", query_mode);
 
-    let (data_stream, pg_descs) = match query_mode {
-        QueryMode::Local => local_execute(context, bound)?,
-        QueryMode::Distributed => distribute_execute(context, bound).await?,
+    let data_stream = match query_mode {
+        QueryMode::Local => local_execute(session, query),
+        QueryMode::Distributed => distribute_execute(session, query).await?,
+        QueryMode::Auto => unreachable!("query mode should be chosen when planning"),
     };
 
     let mut rows = vec![];
@@ -82,70 +121,20 @@ fn to_statement_type(stmt: &Statement) -> StatementType {
 }
 
 async fn distribute_execute(
-    context: OptimizerContext,
-    stmt: BoundStatement,
-) -> Result<(BoxedDataChunkStream, Vec<PgFieldDescriptor>)> {
-    let session = context.session_ctx.clone();
-    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-    let (query, pg_descs) = {
-        let root = Planner::new(context.into()).plan(stmt)?;
-
-        let pg_descs = root
-            .schema()
-            .fields()
-            .iter()
-            .map(to_pg_field)
-            .collect::<Vec<PgFieldDescriptor>>();
-
-        let plan = root.gen_batch_query_plan()?;
-
-        tracing::trace!(
-            "Generated distributed plan: {:?}",
-            plan.explain_to_string()?
-        );
-
-        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
-        let query = plan_fragmenter.split(plan)?;
-        tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
-        (query, pg_descs)
-    };
-
-    let execution_context: ExecutionContextRef = ExecutionContext::new(session.clone()).into();
+    session: Arc<SessionImpl>,
+    query: Query,
+) -> Result<BoxedDataChunkStream> {
+    let execution_context: ExecutionContextRef = ExecutionContext::new(session).into();
     let query_manager = execution_context.session().env().query_manager().clone();
-    Ok((
-        Box::pin(query_manager.schedule(execution_context, query).await?),
-        pg_descs,
+    Ok(Box::pin(
+        query_manager.schedule(execution_context, query).await?,
     ))
 }
 
-fn local_execute(
-    context: OptimizerContext,
-    stmt: BoundStatement,
-) -> Result<(BoxedDataChunkStream, Vec<PgFieldDescriptor>)> {
-    let session = context.session_ctx.clone();
-
-    // Subblock to make sure PlanRef (an Rc) is dropped before `await` below.
-    let (query, pg_descs) = {
-        let root = Planner::new(context.into()).plan(stmt)?;
-
-        let pg_descs = root
-            .schema()
-            .fields()
-            .iter()
-            .map(to_pg_field)
-            .collect::<Vec<PgFieldDescriptor>>();
-
-        let plan = root.gen_batch_local_plan()?;
-
-        let plan_fragmenter = BatchPlanFragmenter::new(session.env().worker_node_manager_ref());
-        let query = plan_fragmenter.split(plan)?;
-        tracing::trace!("Generated query after plan fragmenter: {:?}", &query);
-        (query, pg_descs)
-    };
-
+fn local_execute(session: Arc<SessionImpl>, query: Query) -> BoxedDataChunkStream {
     let front_env = session.env();
 
     // TODO: Passing sql here
     let execution = LocalQueryExecution::new(query, front_env.clone(), "", session.auth_context());
-    Ok((Box::pin(execution.run()), pg_descs))
+    Box::pin(execution.run())
 }
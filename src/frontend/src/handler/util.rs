@@ -23,10 +23,13 @@ use risingwave_common::array::DataChunk;
 use risingwave_common::catalog::{ColumnDesc, Field};
 use risingwave_common::error::ErrorCode::ProtocolError;
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::session_config::QueryMode;
 use risingwave_common::types::{DataType, ScalarRefImpl};
 use risingwave_sqlparser::ast::{SqlOption, Value};
 
 use crate::binder::{BoundSetExpr, BoundStatement};
+use crate::optimizer::{PlanRef, PlanRoot};
+use crate::session::SessionImpl;
 
 /// Format scalars according to postgres convention.
 fn pg_value_format(d: ScalarRefImpl, format: bool) -> Bytes {
@@ -162,6 +165,36 @@ pub fn force_local_mode(bound: &BoundStatement) -> bool {
     false
 }
 
+/// Generates the batch plan of a query for the mode it will be executed in. Returns the plan, the
+/// mode and the reason of the mode, which is shown by `EXPLAIN`.
+pub fn gen_batch_plan_by_mode(
+    session: &SessionImpl,
+    root: &PlanRoot,
+    force_local: bool,
+) -> Result<(PlanRef, QueryMode, &'static str)> {
+    if force_local {
+        return Ok((
+            root.gen_batch_local_plan()?,
+            QueryMode::Local,
+            "system tables are only scanned in local mode",
+        ));
+    }
+    let query_mode = session.config().get_query_mode();
+    match query_mode {
+        QueryMode::Auto => root.gen_batch_auto_plan(),
+        QueryMode::Local => Ok((
+            root.gen_batch_local_plan()?,
+            query_mode,
+            "set by session variable QUERY_MODE",
+        )),
+        QueryMode::Distributed => Ok((
+            root.gen_batch_query_plan()?,
+            query_mode,
+            "set by session variable QUERY_MODE",
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use risingwave_common::array::*;
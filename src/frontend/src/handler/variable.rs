@@ -25,13 +25,25 @@ pub(super) fn handle_set(
     name: Ident,
     value: Vec<SetVariableValue>,
 ) -> Result<PgResponse> {
-    let string_val = to_string(&value[0]);
-    // Currently store the config variable simply as String -> ConfigEntry(String).
-    // In future we can add converter/parser to make the API more robust.
     // We remark that the name of session parameter is always case-insensitive.
-    context
-        .session_ctx
-        .set_config(&name.value.to_lowercase(), &string_val)?;
+    let name = name.value.to_lowercase();
+    let [value] = value.as_slice() else {
+        return Err(ErrorCode::InvalidInputSyntax(format!(
+            "SET {} takes only one argument",
+            name
+        ))
+        .into());
+    };
+    match value {
+        SetVariableValue::Ident(ident) if ident.value.eq_ignore_ascii_case("DEFAULT") => {
+            context.session_ctx.reset_config(&name)?;
+        }
+        // Currently store the config variable simply as String -> ConfigEntry(String).
+        // In future we can add converter/parser to make the API more robust.
+        value => {
+            context.session_ctx.set_config(&name, &to_string(value))?;
+        }
+    }
 
     Ok(PgResponse::empty_result(StatementType::SET_OPTION))
 }
@@ -13,7 +13,10 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
+use risingwave_pb::common::{WorkerNode, WorkerType};
+use risingwave_pb::hummock::HummockVersion;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use risingwave_rpc_client::error::Result;
 use risingwave_rpc_client::{HummockMetaClient, MetaClient};
@@ -29,6 +32,9 @@ pub trait FrontendMetaClient: Send + Sync {
 
     async fn get_epoch(&self) -> Result<u64>;
 
+    /// Returns the safe epoch and the max committed epoch.
+    async fn get_readable_epoch_range(&self) -> Result<(u64, u64)>;
+
     async fn flush(&self) -> Result<()>;
 
     async fn list_table_fragments(
@@ -39,6 +45,16 @@ pub trait FrontendMetaClient: Send + Sync {
     async fn unpin_snapshot(&self) -> Result<()>;
 
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()>;
+
+    /// Pins the snapshot at `epoch` under `name` for `ttl`, or renews the pin.
+    async fn pin_named_snapshot(&self, name: &str, epoch: u64, ttl: Duration) -> Result<()>;
+
+    async fn unpin_named_snapshot(&self, name: &str) -> Result<()>;
+
+    /// Lists the nodes of `worker_type` in the cluster, including those still starting.
+    async fn list_all_nodes(&self, worker_type: WorkerType) -> Result<Vec<WorkerNode>>;
+
+    async fn get_current_version(&self) -> Result<HummockVersion>;
 }
 
 pub struct FrontendMetaClientImpl(pub MetaClient);
@@ -53,6 +69,10 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
         self.0.get_epoch().await
     }
 
+    async fn get_readable_epoch_range(&self) -> Result<(u64, u64)> {
+        self.0.get_readable_epoch_range().await
+    }
+
     async fn flush(&self) -> Result<()> {
         self.0.flush().await
     }
@@ -71,4 +91,21 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
     async fn unpin_snapshot_before(&self, epoch: u64) -> Result<()> {
         self.0.unpin_snapshot_before(epoch).await
     }
+
+    async fn pin_named_snapshot(&self, name: &str, epoch: u64, ttl: Duration) -> Result<()> {
+        self.0.pin_named_snapshot(name, epoch, ttl).await?;
+        Ok(())
+    }
+
+    async fn unpin_named_snapshot(&self, name: &str) -> Result<()> {
+        self.0.unpin_named_snapshot(name).await
+    }
+
+    async fn list_all_nodes(&self, worker_type: WorkerType) -> Result<Vec<WorkerNode>> {
+        self.0.list_all_nodes(worker_type, true).await
+    }
+
+    async fn get_current_version(&self) -> Result<HummockVersion> {
+        self.0.get_current_version().await
+    }
 }
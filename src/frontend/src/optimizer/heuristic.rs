@@ -107,6 +107,15 @@ impl Stats {
     pub fn has_applied_rule(&self) -> bool {
         !self.rule_counter.is_empty()
     }
+
+    /// Descriptions of the applied rules with their applied times, sorted by description.
+    pub fn applied_rules(&self) -> Vec<(&str, u32)> {
+        self.rule_counter
+            .iter()
+            .map(|(rule, count)| (rule.as_str(), *count))
+            .sorted()
+            .collect()
+    }
 }
 
 impl fmt::Display for Stats {
@@ -114,8 +123,8 @@ impl fmt::Display for Stats {
         write!(
             f,
             "{}",
-            self.rule_counter
-                .iter()
+            self.applied_rules()
+                .into_iter()
                 .map(|(rule, count)| format!("apply {} {} time(s)", rule, count))
                 .collect_vec()
                 .join("\n")
@@ -22,6 +22,8 @@ mod heuristic;
 mod plan_correlated_id_finder;
 mod plan_rewriter;
 mod plan_visitor;
+mod query_mode_selector;
+mod row_count_estimator;
 mod rule;
 
 use fixedbitset::FixedBitSet;
@@ -29,16 +31,23 @@ use itertools::Itertools as _;
 use property::Order;
 use risingwave_common::catalog::Schema;
 use risingwave_common::error::Result;
+use risingwave_common::session_config::QueryMode;
 
 use self::heuristic::{ApplyOrder, HeuristicOptimizer};
 use self::plan_node::{BatchProject, Convention, LogicalProject, StreamMaterialize};
 use self::property::RequiredDist;
+use self::query_mode_selector::QueryModeSelector;
+pub use self::row_count_estimator::estimate_row_count;
 use self::rule::*;
 use crate::catalog::TableId;
 use crate::optimizer::plan_node::BatchExchange;
 use crate::optimizer::property::Distribution;
 use crate::utils::Condition;
 
+/// The max times of applying a rule set in [`PlanRoot::optimize_by_rules_until_fix_point`], to
+/// avoid looping forever when some rules keep rewriting each other's output.
+const MAX_FIX_POINT_ITERATIONS: usize = 100;
+
 /// `PlanRoot` is used to describe a plan. planner will construct a `PlanRoot` with `LogicalNode`.
 /// and required distribution and order. And `PlanRoot` can generate corresponding streaming or
 /// batch plan with optimization. the required Order and Distribution columns might be more than the
@@ -138,7 +147,7 @@ impl PlanRoot {
     ) -> PlanRef {
         let mut output_plan = plan;
 
-        loop {
+        for _ in 0..MAX_FIX_POINT_ITERATIONS {
             let mut heuristic_optimizer = HeuristicOptimizer::new(&apply_order, &rules);
             output_plan = heuristic_optimizer.optimize(output_plan);
             let stats = heuristic_optimizer.get_stats();
@@ -155,6 +164,13 @@ impl PlanRoot {
                 return output_plan;
             }
         }
+
+        tracing::warn!(
+            "{} does not reach the fix point after {} iterations",
+            stage_name,
+            MAX_FIX_POINT_ITERATIONS
+        );
+        output_plan
     }
 
     /// Apply logical optimization to the plan.
@@ -235,6 +251,14 @@ impl PlanRoot {
             ctx.trace(plan.explain_to_string().unwrap());
         }
 
+        // Limit the rows numbered by a window function if only the first ones are used.
+        plan = self.optimize_by_rules(
+            plan,
+            "Over Window To TopN".to_string(),
+            vec![OverWindowToTopNRule::create()],
+            ApplyOrder::TopDown,
+        );
+
         // Convert distinct aggregates.
         plan = self.optimize_by_rules(
             plan,
@@ -274,7 +298,14 @@ impl PlanRoot {
     /// Optimize and generate a batch query plan for distributed execution.
     pub fn gen_batch_query_plan(&self) -> Result<PlanRef> {
         // Logical optimization
-        let mut plan = self.gen_optimized_logical_plan();
+        let plan = self.gen_optimized_logical_plan();
+
+        self.gen_batch_query_plan_from(plan)
+    }
+
+    /// Generate a batch query plan for distributed execution from the optimized logical plan.
+    fn gen_batch_query_plan_from(&self, mut plan: PlanRef) -> Result<PlanRef> {
+        plan.ctx().set_local_execution(false);
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order)?;
@@ -301,7 +332,14 @@ impl PlanRoot {
     /// Optimize and generate a batch query plan for local execution.
     pub fn gen_batch_local_plan(&self) -> Result<PlanRef> {
         // Logical optimization
-        let mut plan = self.gen_optimized_logical_plan();
+        let plan = self.gen_optimized_logical_plan();
+
+        self.gen_batch_local_plan_from(plan)
+    }
+
+    /// Generate a batch query plan for local execution from the optimized logical plan.
+    fn gen_batch_local_plan_from(&self, mut plan: PlanRef) -> Result<PlanRef> {
+        plan.ctx().set_local_execution(true);
 
         // Convert to physical plan node
         plan = plan.to_batch_with_order_required(&self.required_order)?;
@@ -332,6 +370,23 @@ impl PlanRoot {
         Ok(plan)
     }
 
+    /// Optimize and generate a batch query plan, for the mode chosen by [`QueryModeSelector`] from
+    /// the optimized logical plan. Returns the plan, the chosen mode and the reason.
+    ///
+    /// The mode is chosen before converting to the physical plan, since some physical operators,
+    /// e.g., lookup join, are only available in local mode.
+    pub fn gen_batch_auto_plan(&self) -> Result<(PlanRef, QueryMode, &'static str)> {
+        // Logical optimization
+        let plan = self.gen_optimized_logical_plan();
+
+        let (query_mode, reason) = QueryModeSelector::select(plan.clone());
+        let plan = match query_mode {
+            QueryMode::Local => self.gen_batch_local_plan_from(plan)?,
+            _ => self.gen_batch_query_plan_from(plan)?,
+        };
+        Ok((plan, query_mode, reason))
+    }
+
     /// Generate create index or create materialize view plan.
     fn gen_stream_plan(&mut self) -> Result<PlanRef> {
         let mut plan = match self.plan.convention() {
@@ -372,6 +427,11 @@ impl PlanRoot {
     }
 
     /// Optimize and generate a create materialize view plan.
+    ///
+    /// An `ORDER BY .. LIMIT ..` of the MV has already been planned as a `LogicalTopN`, which
+    /// becomes a `StreamTopN` maintaining the top rows incrementally. A bare `ORDER BY` is
+    /// rejected by `CREATE MATERIALIZED VIEW`; if there is one here, the required order is used as
+    /// the order the rows are stored in.
     pub fn gen_create_mv_plan(&mut self, mv_name: String) -> Result<StreamMaterialize> {
         let stream_plan = self.gen_stream_plan()?;
         StreamMaterialize::create(
@@ -77,16 +77,25 @@ impl ToDistributedBatch for BatchExchange {
 /// The serialization of Batch Exchange is default cuz it will be rewritten in scheduler.
 impl ToBatchProst for BatchExchange {
     fn to_batch_prost_body(&self) -> NodeBody {
+        let chunk_size = self
+            .base
+            .ctx
+            .inner()
+            .session_ctx
+            .config()
+            .get_batch_chunk_size() as u32;
         if self.base.order.is_any() {
             NodeBody::Exchange(ExchangeNode {
                 sources: vec![],
                 input_schema: self.base.schema.to_prost(),
+                chunk_size,
             })
         } else {
             NodeBody::MergeSortExchange(MergeSortExchangeNode {
                 exchange: Some(ExchangeNode {
                     sources: vec![],
                     input_schema: self.base.schema.to_prost(),
+                    chunk_size,
                 }),
                 column_orders: self.base.order.to_protobuf(&self.base.schema),
             })
@@ -14,6 +14,7 @@
 
 use std::fmt;
 
+use itertools::Itertools;
 use risingwave_common::error::Result;
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::HashAggNode;
@@ -71,11 +72,49 @@ impl PlanTreeNodeUnary for BatchHashAgg {
 impl_plan_tree_node_for_unary! { BatchHashAgg }
 impl ToDistributedBatch for BatchHashAgg {
     fn to_distributed(&self) -> Result<PlanRef> {
-        let new_input = self.input().to_distributed_with_required(
-            &Order::any(),
-            &RequiredDist::shard_by_key(self.input().schema().len(), self.group_key()),
-        )?;
-        Ok(self.clone_with_input(new_input).into())
+        let required_dist =
+            RequiredDist::shard_by_key(self.input().schema().len(), self.group_key());
+        let dist_input = self.input().to_distributed()?;
+
+        // Aggregate the rows partially before shuffling them if the input is not yet sharded by
+        // the group key, so that only a row per group key and input partition is shuffled.
+        if !dist_input.distribution().satisfies(&required_dist)
+            && dist_input.distribution().satisfies(&RequiredDist::AnyShard)
+            && self.logical.can_two_phase_hash_agg()
+        {
+            // partial agg
+            let partial_agg: PlanRef = self.clone_with_input(dist_input).into();
+
+            // insert exchange, on the partial agg's output of each distinct group key column
+            let group_key = (0..self.group_key().len()).collect_vec();
+            let dist_key = self
+                .group_key()
+                .iter()
+                .enumerate()
+                .unique_by(|(_, &idx)| idx)
+                .map(|(pos, _)| pos)
+                .collect_vec();
+            let exchange = RequiredDist::shard_by_key(partial_agg.schema().len(), &dist_key)
+                .enforce_if_not_satisfies(partial_agg, &Order::any())?;
+
+            // insert total agg
+            let total_agg_types = self
+                .logical
+                .agg_calls()
+                .iter()
+                .enumerate()
+                .map(|(partial_output_idx, agg_call)| {
+                    agg_call.partial_to_total_agg_call(group_key.len() + partial_output_idx)
+                })
+                .collect();
+            let total_agg_logical = LogicalAgg::new(total_agg_types, group_key, exchange);
+            Ok(BatchHashAgg::new(total_agg_logical).into())
+        } else {
+            let new_input = self
+                .input()
+                .to_distributed_with_required(&Order::any(), &required_dist)?;
+            Ok(self.clone_with_input(new_input).into())
+        }
     }
 }
 
@@ -40,6 +40,10 @@ impl BatchLimit {
         );
         BatchLimit { base, logical }
     }
+
+    pub fn logical(&self) -> &LogicalLimit {
+        &self.logical
+    }
 }
 
 impl fmt::Display for BatchLimit {
@@ -181,6 +181,13 @@ impl ToBatchProst for BatchLookupJoin {
                 .map(|&x| x as u32)
                 .collect(),
             worker_nodes: vec![], // To be filled in at local.rs
+            chunk_size: self
+                .base
+                .ctx
+                .inner()
+                .session_ctx
+                .config()
+                .get_batch_chunk_size() as u32,
         })
     }
 }
@@ -18,6 +18,7 @@ use std::ops::Bound;
 use itertools::Itertools;
 use risingwave_common::error::Result;
 use risingwave_common::types::ScalarImpl;
+use risingwave_common::util::epoch::INVALID_EPOCH;
 use risingwave_common::util::scan_range::{is_full_range, ScanRange};
 use risingwave_pb::batch_plan::plan_node::NodeBody;
 use risingwave_pb::batch_plan::{RowSeqScanNode, SysRowSeqScanNode};
@@ -170,6 +171,10 @@ impl fmt::Display for BatchSeqScan {
             write!(f, ", scan_ranges: [{}]", range_strs.join(" OR "))?;
         }
 
+        if let Some(as_of_epoch) = self.logical.as_of_epoch() {
+            write!(f, ", as_of_epoch: {}", as_of_epoch)?;
+        }
+
         if verbose {
             write!(
                 f,
@@ -217,6 +222,14 @@ impl ToBatchProst for BatchSeqScan {
                 scan_ranges: self.scan_ranges.iter().map(|r| r.to_protobuf()).collect(),
                 // To be filled by the scheduler.
                 vnode_bitmap: None,
+                as_of_epoch: self.logical.as_of_epoch().unwrap_or(INVALID_EPOCH),
+                chunk_size: self
+                    .base
+                    .ctx
+                    .inner()
+                    .session_ctx
+                    .config()
+                    .get_batch_chunk_size() as u32,
             })
         }
     }
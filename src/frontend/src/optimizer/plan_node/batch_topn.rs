@@ -41,6 +41,10 @@ impl BatchTopN {
         );
         BatchTopN { base, logical }
     }
+
+    pub fn logical(&self) -> &LogicalTopN {
+        &self.logical
+    }
 }
 
 impl fmt::Display for BatchTopN {
@@ -26,9 +26,9 @@ use risingwave_pb::expr::agg_call::OrderByField as ProstAggOrderByField;
 use risingwave_pb::expr::AggCall as ProstAggCall;
 
 use super::{
-    BatchHashAgg, BatchSimpleAgg, ColPrunable, LogicalProjectBuilder, PlanBase, PlanRef,
-    PlanTreeNodeUnary, PredicatePushdown, StreamGlobalSimpleAgg, StreamHashAgg,
-    StreamLocalSimpleAgg, ToBatch, ToStream,
+    BatchHashAgg, BatchHashDistinct, BatchSimpleAgg, ColPrunable, LogicalExpand,
+    LogicalProjectBuilder, PlanBase, PlanRef, PlanTreeNodeUnary, PredicatePushdown,
+    StreamGlobalSimpleAgg, StreamHashAgg, StreamLocalSimpleAgg, ToBatch, ToStream,
 };
 use crate::catalog::table_catalog::TableCatalog;
 use crate::expr::{
@@ -335,6 +335,8 @@ impl LogicalAgg {
                 internal_table_catalog_builder.add_column(&in_fields[include_key]);
                 column_mapping.push(include_key);
             }
+            // The sorted input of a group is read by the group key.
+            internal_table_catalog_builder.set_read_pattern(0, self.group_key.len());
             internal_table_catalog_builder.build_with_column_mapping(
                 in_dist_key.clone(),
                 in_append_only,
@@ -442,6 +444,86 @@ impl LogicalAgg {
         Ok(StreamGlobalSimpleAgg::new(total_agg_logical_plan).into())
     }
 
+    /// Whether the agg calls can be computed by a partial hash agg followed by a total hash agg on
+    /// the same group key. Only the agg calls that can be merged from their partial results in
+    /// any order, i.e. commutative and associative ones, qualify. Distinct agg calls don't, as a
+    /// value may be counted by several partial aggs.
+    pub(crate) fn can_two_phase_hash_agg(&self) -> bool {
+        self.agg_calls.iter().all(|c| {
+            !c.distinct
+                && c.order_by_fields.is_empty()
+                && matches!(
+                    c.agg_kind,
+                    AggKind::Count | AggKind::Sum | AggKind::Min | AggKind::Max
+                )
+        })
+    }
+
+    /// Two phase streaming hash agg for skewed group keys.
+    ///
+    /// The rows are salted with the vnode of their pk and partially aggregated on the group key
+    /// plus the salt, so that the rows of a hot group key are spread over several actors. A row
+    /// and its retraction share the pk, so they always go to the same partial group. The partial
+    /// results are then shuffled on the group key and merged by the total agg.
+    fn gen_salted_two_phase_streaming_agg_plan(&self) -> Result<PlanRef> {
+        let input_len = self.input.schema().len();
+        let salt = FunctionCall::new(
+            ExprType::Vnode,
+            self.input
+                .pk_indices()
+                .iter()
+                .map(|&idx| {
+                    ExprImpl::from(InputRef::new(
+                        idx,
+                        self.input.schema().fields()[idx].data_type(),
+                    ))
+                })
+                .collect(),
+        )?;
+        let exprs = self
+            .input
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| ExprImpl::from(InputRef::new(idx, field.data_type())))
+            .chain([salt.into()])
+            .collect();
+        let salted_input = LogicalProject::new(self.input.clone(), exprs);
+
+        // partial agg
+        let partial_group_key = self
+            .group_key
+            .iter()
+            .copied()
+            .chain([input_len])
+            .collect_vec();
+        let partial_input = salted_input.to_stream_with_dist_required(
+            &RequiredDist::shard_by_key(input_len + 1, &partial_group_key),
+        )?;
+        let partial_agg_plan: PlanRef = StreamHashAgg::new(LogicalAgg::new(
+            self.agg_calls.clone(),
+            partial_group_key,
+            partial_input,
+        ))
+        .into();
+
+        // The partial agg outputs the group key, the salt, and then the agg calls.
+        let group_key_len = self.group_key.len();
+        let total_group_key = (0..group_key_len).collect_vec();
+        let input = RequiredDist::shard_by_key(partial_agg_plan.schema().len(), &total_group_key)
+            .enforce_if_not_satisfies(partial_agg_plan, &Order::any())?;
+
+        // total agg
+        let total_agg_calls = self
+            .agg_calls
+            .iter()
+            .enumerate()
+            .map(|(idx, agg_call)| agg_call.partial_to_total_agg_call(group_key_len + 1 + idx))
+            .collect();
+        Ok(StreamHashAgg::new(LogicalAgg::new(total_agg_calls, total_group_key, input)).into())
+    }
+
     /// Check if the aggregation result will be affected by order by clause, if any.
     pub(crate) fn is_agg_result_affected_by_order(&self) -> bool {
         self.agg_calls.iter().any(|call| match call.agg_kind {
@@ -460,10 +542,14 @@ struct LogicalAggBuilder {
     input_proj_builder: LogicalProjectBuilder,
     /// the group key column indices in the project's output
     group_key: Vec<usize>,
+    /// the grouping sets as indices into `group_key`, empty if there is no grouping sets
+    grouping_sets: Vec<Vec<usize>>,
     /// the agg calls
     agg_calls: Vec<PlanAggCall>,
     /// the error during the expression rewriting
     error: Option<ErrorCode>,
+    /// the names of the input columns, used in the error messages
+    input_column_names: Vec<String>,
     /// If `is_in_filter_clause` is true, it means that
     /// we are processing filter clause.
     /// This field is needed because input refs in these clauses
@@ -473,7 +559,11 @@ struct LogicalAggBuilder {
 }
 
 impl LogicalAggBuilder {
-    fn new(group_exprs: Vec<ExprImpl>) -> Result<Self> {
+    fn new(
+        group_exprs: Vec<ExprImpl>,
+        grouping_sets: Vec<Vec<usize>>,
+        input_column_names: Vec<String>,
+    ) -> Result<Self> {
         let mut input_proj_builder = LogicalProjectBuilder::default();
 
         for expr in &group_exprs {
@@ -492,19 +582,103 @@ impl LogicalAggBuilder {
 
         Ok(LogicalAggBuilder {
             group_key,
+            grouping_sets,
             agg_calls: vec![],
             error: None,
+            input_column_names,
             input_proj_builder,
             is_in_filter_clause: false,
         })
     }
 
-    pub fn build(self, input: PlanRef) -> LogicalAgg {
+    pub fn build(self, input: PlanRef) -> PlanRef {
+        if !self.grouping_sets.is_empty() {
+            return self.build_with_grouping_sets(input);
+        }
+
         // This LogicalProject focuses on the exprs in aggregates and GROUP BY clause.
         let logical_project = self.input_proj_builder.build(input);
 
         // This LogicalAgg focuses on calculating the aggregates and grouping.
-        LogicalAgg::new(self.agg_calls, self.group_key, logical_project.into())
+        LogicalAgg::new(self.agg_calls, self.group_key, logical_project.into()).into()
+    }
+
+    /// Build the plan like `LogicalProject - LogicalAgg - LogicalExpand - LogicalProject` for
+    /// grouping sets.
+    ///
+    /// `LogicalExpand` replicates each row once per grouping set, filling the group columns not in
+    /// the set with NULL, and its flag column is appended to the group key to tell the sets apart.
+    /// The top `LogicalProject` removes the flag column so that the output still consists of the
+    /// group keys and the agg calls.
+    fn build_with_grouping_sets(self, input: PlanRef) -> PlanRef {
+        let mut exprs = self.input_proj_builder.into_exprs();
+        let input_len = exprs.len();
+
+        // Group columns referenced by agg calls are duplicated, so that the agg calls are not
+        // affected by the NULLs filled by `LogicalExpand`.
+        let mut agg_call_cols = FixedBitSet::with_capacity(input_len);
+        for agg_call in &self.agg_calls {
+            agg_call_cols.extend(agg_call.inputs.iter().map(|i| i.index()));
+            agg_call_cols.extend(agg_call.order_by_fields.iter().map(|f| f.input.index()));
+            for expr in &agg_call.filter.conjunctions {
+                agg_call_cols.union_with(&expr.collect_input_refs(input_len));
+            }
+        }
+        let mut map = (0..input_len).map(Some).collect_vec();
+        for &key in &self.group_key {
+            if agg_call_cols.contains(key) {
+                map[key] = Some(exprs.len());
+                exprs.push(exprs[key].clone());
+            }
+        }
+        let mut col_change = ColIndexMapping::with_target_size(map, exprs.len());
+        let agg_calls =
+            self.agg_calls
+                .into_iter()
+                .map(|mut agg_call| {
+                    agg_call.inputs.iter_mut().for_each(|i| {
+                        *i = InputRef::new(col_change.map(i.index()), i.return_type())
+                    });
+                    agg_call.order_by_fields.iter_mut().for_each(|field| {
+                        let i = &mut field.input;
+                        *i = InputRef::new(col_change.map(i.index()), i.return_type())
+                    });
+                    agg_call.filter = agg_call.filter.rewrite_expr(&mut col_change);
+                    agg_call
+                })
+                .collect_vec();
+        let logical_project: PlanRef = LogicalProject::new(input, exprs).into();
+
+        let group_key_cols: FixedBitSet = self.group_key.iter().copied().collect();
+        let other_cols = (0..logical_project.schema().len())
+            .filter(|i| !group_key_cols.contains(*i))
+            .collect_vec();
+        let column_subsets = self
+            .grouping_sets
+            .iter()
+            .map(|set| {
+                set.iter()
+                    .map(|&i| self.group_key[i])
+                    .chain(other_cols.iter().copied())
+                    .collect_vec()
+            })
+            .collect_vec();
+        let logical_expand = LogicalExpand::create(logical_project, column_subsets);
+
+        let flag = logical_expand.schema().len() - 1;
+        let mut group_key = self.group_key.clone();
+        group_key.push(flag);
+        let logical_agg = LogicalAgg::new(agg_calls, group_key, logical_expand);
+
+        let agg_len = logical_agg.schema().len();
+        let out_cols = (0..agg_len)
+            .filter(|&i| i != self.group_key.len())
+            .collect_vec();
+        LogicalProject::with_mapping(
+            logical_agg.into(),
+            ColIndexMapping::with_remaining_columns(&out_cols, agg_len),
+        )
+        .into()
     }
 
     fn rewrite_with_error(&mut self, expr: ExprImpl) -> Result<ExprImpl> {
@@ -694,16 +868,17 @@ impl ExprRewriter for LogicalAggBuilder {
 
     /// When there is an `InputRef` (outside of agg call), it must refers to a group column.
     fn rewrite_input_ref(&mut self, input_ref: InputRef) -> ExprImpl {
+        let index = input_ref.index();
         let expr = input_ref.into();
         if let Some(group_key) = self.try_as_group_expr(&expr) {
             InputRef::new(group_key, expr.return_type()).into()
         } else if self.is_in_filter_clause {
             InputRef::new(self.input_proj_builder.add_expr(&expr), expr.return_type()).into()
         } else {
-            self.error = Some(ErrorCode::InvalidInputSyntax(
-                "column must appear in the GROUP BY clause or be used in an aggregate function"
-                    .into(),
-            ));
+            self.error = Some(ErrorCode::InvalidInputSyntax(format!(
+                "column \"{}\" must appear in the GROUP BY clause or be used in an aggregate function",
+                self.input_column_names[index]
+            )));
             expr
         }
     }
@@ -782,6 +957,7 @@ impl LogicalAgg {
     pub fn create(
         select_exprs: Vec<ExprImpl>,
         group_exprs: Vec<ExprImpl>,
+        grouping_sets: Vec<Vec<usize>>,
         having: Option<ExprImpl>,
         input: PlanRef,
     ) -> Result<(PlanRef, Vec<ExprImpl>, Option<ExprImpl>)> {
@@ -797,7 +973,8 @@ impl LogicalAgg {
             .into());
         }
 
-        let mut agg_builder = LogicalAggBuilder::new(group_exprs)?;
+        let mut agg_builder =
+            LogicalAggBuilder::new(group_exprs, grouping_sets, input.schema().names())?;
 
         let rewritten_select_exprs = select_exprs
             .into_iter()
@@ -810,7 +987,7 @@ impl LogicalAgg {
         agg_builder.syntax_check()?;
 
         Ok((
-            agg_builder.build(input).into(),
+            agg_builder.build(input),
             rewritten_select_exprs,
             rewritten_having,
         ))
@@ -1035,6 +1212,9 @@ impl ToBatch for LogicalAgg {
         let new_logical = self.clone_with_input(new_input);
         if self.group_key().is_empty() {
             Ok(BatchSimpleAgg::new(new_logical).into())
+        } else if self.agg_calls().is_empty() {
+            // Without agg calls, e.g. `SELECT DISTINCT`, the rows can be deduplicated on the fly.
+            Ok(BatchHashDistinct::new(new_logical).into())
         } else {
             Ok(BatchHashAgg::new(new_logical).into())
         }
@@ -1046,14 +1226,21 @@ impl ToStream for LogicalAgg {
         let input = self.input();
         // simple-agg
         if self.group_key().is_empty() {
+            let input_stream = input.to_stream()?;
+            let input_distribution = input_stream.distribution();
+
             // TODO: Other agg calls will be supported by stateful local agg eventually.
             let agg_calls_can_use_two_phase = self.agg_calls.iter().all(|c| {
-                matches!(c.agg_kind, AggKind::Count | AggKind::Sum) && c.order_by_fields.is_empty()
+                c.order_by_fields.is_empty()
+                    && match c.agg_kind {
+                        AggKind::Count | AggKind::Sum => true,
+                        // The partial min/max can't be retracted, so they are only split when the
+                        // input is append-only.
+                        AggKind::Min | AggKind::Max => input_stream.append_only(),
+                        _ => false,
+                    }
             });
 
-            let input_stream = input.to_stream()?;
-            let input_distribution = input_stream.distribution();
-
             // simple 2-phase-agg
             if input_distribution.satisfies(&RequiredDist::AnyShard) && agg_calls_can_use_two_phase
             {
@@ -1066,6 +1253,19 @@ impl ToStream for LogicalAgg {
                 .into())
             }
 
+            // salted 2-phase hash-agg
+        } else if self
+            .base
+            .ctx
+            .inner()
+            .session_ctx
+            .config()
+            .get_streaming_enable_salted_agg()
+            && !self.input.pk_indices().is_empty()
+            && self.can_two_phase_hash_agg()
+        {
+            self.gen_salted_two_phase_streaming_agg_plan()
+
             // hash-agg
         } else {
             Ok(
@@ -1115,7 +1315,7 @@ mod tests {
     use super::*;
     use crate::expr::{
         assert_eq_input_ref, input_ref_to_column_indices, AggCall, AggOrderBy, ExprType,
-        FunctionCall,
+        FunctionCall, Literal,
     };
     use crate::optimizer::plan_node::LogicalValues;
     use crate::session::OptimizerContext;
@@ -1139,7 +1339,7 @@ mod tests {
                                   group_exprs|
          -> (Vec<ExprImpl>, Vec<PlanAggCall>, Vec<usize>) {
             let (plan, exprs, _) =
-                LogicalAgg::create(select_exprs, group_exprs, None, input.clone()).unwrap();
+                LogicalAgg::create(select_exprs, group_exprs, vec![], None, input.clone()).unwrap();
 
             let logical_agg = plan.as_logical_agg().unwrap();
             let agg_calls = logical_agg.agg_calls().to_vec();
@@ -1496,4 +1696,106 @@ mod tests {
         let values = values.as_logical_values().unwrap();
         assert_eq!(values.schema().fields(), &fields[1..]);
     }
+
+    #[tokio::test]
+    /// Pushing predicate `input_ref(0) > null AND input_ref(1) > null` down
+    /// ```text
+    /// Agg(min(input_ref(2))) group by (input_ref(1))
+    ///   TableScan(v1, v2, v3)
+    /// ```
+    /// will result in
+    /// ```text
+    /// Filter(input_ref(1) > null)
+    ///   Agg(min(input_ref(2))) group by (input_ref(1))
+    ///     Filter(input_ref(1) > null)
+    ///       TableScan(v1, v2, v3)
+    /// ```
+    /// and pruning it with required columns [1] (group key removed) will result in
+    /// ```text
+    /// Filter(input_ref(0) > null)
+    ///   Project(input_ref(1))
+    ///     Agg(min(input_ref(1))) group by (input_ref(0))
+    ///       Filter(input_ref(0) > null)
+    ///         TableScan(v2, v3)
+    /// ```
+    async fn test_predicate_pushdown() {
+        let ty = DataType::Int32;
+        let fields: Vec<Field> = vec![
+            Field::with_name(ty.clone(), "v1"),
+            Field::with_name(ty.clone(), "v2"),
+            Field::with_name(ty.clone(), "v3"),
+        ];
+        let agg = generate_agg_call(ty.clone(), fields.clone()).await;
+        let gen_greater_than = |index: usize| -> ExprImpl {
+            FunctionCall::new(
+                ExprType::GreaterThan,
+                vec![
+                    InputRef::new(index, ty.clone()).into(),
+                    Literal::new(None, ty.clone()).into(),
+                ],
+            )
+            .unwrap()
+            .into()
+        };
+        let predicate = Condition {
+            conjunctions: vec![gen_greater_than(0), gen_greater_than(1)],
+        };
+
+        // Perform the pushdown
+        let plan = agg.predicate_pushdown(predicate);
+
+        // Check the result: only the group key predicate is pushed below the agg
+        let filter = plan.as_logical_filter().unwrap();
+        assert_eq!(filter.predicate().conjunctions.len(), 1);
+        let call = filter.predicate().conjunctions[0]
+            .as_function_call()
+            .unwrap();
+        assert_eq_input_ref!(&call.inputs()[0], 1);
+
+        let agg_new = filter.input();
+        let agg_new = agg_new.as_logical_agg().unwrap();
+        assert_eq!(agg_new.group_key(), vec![1]);
+
+        let pushed_filter = agg_new.input();
+        let pushed_filter = pushed_filter.as_logical_filter().unwrap();
+        assert_eq!(pushed_filter.predicate().conjunctions.len(), 1);
+        let call = pushed_filter.predicate().conjunctions[0]
+            .as_function_call()
+            .unwrap();
+        assert_eq_input_ref!(&call.inputs()[0], 1);
+
+        // Perform the prune, the pushed predicate must still reference the kept group key
+        let required_cols = vec![1];
+        let plan = plan.prune_col(&required_cols);
+
+        let filter = plan.as_logical_filter().unwrap();
+        let call = filter.predicate().conjunctions[0]
+            .as_function_call()
+            .unwrap();
+        assert_eq_input_ref!(&call.inputs()[0], 0);
+
+        let project = filter.input();
+        let project = project.as_logical_project().unwrap();
+        assert_eq!(project.exprs().len(), 1);
+        assert_eq_input_ref!(&project.exprs()[0], 1);
+
+        let agg_new = project.input();
+        let agg_new = agg_new.as_logical_agg().unwrap();
+        assert_eq!(agg_new.group_key(), vec![0]);
+        assert_eq!(
+            input_ref_to_column_indices(&agg_new.agg_calls[0].inputs),
+            vec![1]
+        );
+
+        let pushed_filter = agg_new.input();
+        let pushed_filter = pushed_filter.as_logical_filter().unwrap();
+        let call = pushed_filter.predicate().conjunctions[0]
+            .as_function_call()
+            .unwrap();
+        assert_eq_input_ref!(&call.inputs()[0], 0);
+
+        let values = pushed_filter.input();
+        let values = values.as_logical_values().unwrap();
+        assert_eq!(values.schema().fields(), &fields[1..]);
+    }
 }
@@ -22,7 +22,7 @@ use risingwave_common::session_config::QueryMode;
 use risingwave_pb::plan_common::JoinType;
 
 use super::{
-    BatchProject, ColPrunable, CollectInputRef, LogicalProject, PlanBase, PlanNodeType, PlanRef,
+    BatchProject, ColPrunable, CollectInputRef, LogicalProject, LogicalScan, PlanBase, PlanRef,
     PlanTreeNodeBinary, PlanTreeNodeUnary, PredicatePushdown, StreamHashJoin, StreamProject,
     ToBatch, ToStream,
 };
@@ -481,21 +481,35 @@ impl LogicalJoin {
         }
     }
 
-    fn convert_to_lookup_join(
+    /// Returns whether the join can be done as a lookup join in local mode, where the right side
+    /// is looked up by the keys from the left side instead of being scanned.
+    pub fn can_be_lookup_join(&self) -> bool {
+        let predicate = EqJoinPredicate::create(
+            self.left.schema().len(),
+            self.right.schema().len(),
+            self.on.clone(),
+        );
+        predicate.has_eq() && self.lookup_scan(&predicate).is_ok()
+    }
+
+    /// Returns the scan on the right side to be looked up, or the reason why the join can't be
+    /// a lookup join.
+    fn lookup_scan(
         &self,
-        logical_join: LogicalJoin,
-        predicate: EqJoinPredicate,
-    ) -> Option<PlanRef> {
-        if self.right.as_ref().node_type() != PlanNodeType::LogicalScan {
-            log::warn!(
-                "Lookup Join only supports basic tables on the join's right side. A \
-            different join will be used instead."
+        predicate: &EqJoinPredicate,
+    ) -> std::result::Result<&LogicalScan, &'static str> {
+        let Some(logical_scan) = self.right.as_logical_scan() else {
+            return Err("Lookup Join only supports basic tables on the join's right side. A \
+            different join will be used instead.");
+        };
+        if logical_scan.as_of_epoch().is_some() {
+            // The lookups are done at the epoch of the query.
+            return Err(
+                "Lookup Join doesn't support AS OF on the join's right side. A \
+            different join will be used instead.",
             );
-            return None;
         }
-
-        let logical_scan = self.right.as_logical_scan().unwrap();
-        let table_desc = logical_scan.table_desc().clone();
+        let table_desc = logical_scan.table_desc();
         let output_column_ids = logical_scan.output_column_ids();
 
         // Verify that the right join key columns are the same as the primary key
@@ -505,17 +519,33 @@ impl LogicalJoin {
 
         let order_col_indices = table_desc.order_column_indices();
         if order_col_indices.len() != predicate.right_eq_indexes().len() {
-            log::warn!("{}", eq_col_warn_message);
-            return None;
+            return Err(eq_col_warn_message);
         }
 
         for (i, eq_idx) in predicate.right_eq_indexes().into_iter().enumerate() {
             if order_col_indices[i] != output_column_ids[eq_idx].get_id() as usize {
-                log::warn!("{}", eq_col_warn_message);
-                return None;
+                return Err(eq_col_warn_message);
             }
         }
 
+        Ok(logical_scan)
+    }
+
+    fn convert_to_lookup_join(
+        &self,
+        logical_join: LogicalJoin,
+        predicate: EqJoinPredicate,
+    ) -> Option<PlanRef> {
+        let logical_scan = match self.lookup_scan(&predicate) {
+            Ok(logical_scan) => logical_scan,
+            Err(message) => {
+                log::warn!("{}", message);
+                return None;
+            }
+        };
+        let table_desc = logical_scan.table_desc().clone();
+        let output_column_ids = logical_scan.output_column_ids();
+
         Some(BatchLookupJoin::new(logical_join, predicate, table_desc, output_column_ids).into())
     }
 }
@@ -745,13 +775,13 @@ impl ToBatch for LogicalJoin {
 
         if predicate.has_eq() {
             if config.get_batch_enable_lookup_join() {
-                if config.get_query_mode() == QueryMode::Local {
+                if self.base.ctx.is_local_execution() {
                     if let Some(lookup_join) =
                         self.convert_to_lookup_join(logical_join.clone(), predicate.clone())
                     {
                         return Ok(lookup_join);
                     }
-                } else {
+                } else if config.get_query_mode() == QueryMode::Distributed {
                     log::warn!(
                         "Lookup Join can only be done in local mode. A different join will \
                     be used instead."
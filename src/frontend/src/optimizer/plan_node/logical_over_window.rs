@@ -0,0 +1,299 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::DataType;
+
+use super::{
+    gen_filter_and_pushdown, ColPrunable, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
+    PredicatePushdown, StreamOverWindow, ToBatch, ToStream,
+};
+use crate::expr::{Expr, ExprImpl, ExprRewriter, InputRef, WindowFunction, WindowFunctionType};
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::property::{FieldOrder, Order, OrderDisplay, RequiredDist};
+use crate::utils::{ColIndexMapping, Condition};
+
+/// A window function in the plan, with its partition key and order referring to the input
+/// columns.
+#[derive(Debug, Clone)]
+pub struct PlanWindowFunction {
+    pub function_type: WindowFunctionType,
+    pub partition_by: Vec<usize>,
+    pub order_by: Order,
+}
+
+/// `LogicalOverWindow` computes a window function over the partitions of its input, and appends
+/// the result to each row.
+#[derive(Debug, Clone)]
+pub struct LogicalOverWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    pub(super) window_function: PlanWindowFunction,
+}
+
+impl LogicalOverWindow {
+    pub fn new(window_function: PlanWindowFunction, input: PlanRef) -> Self {
+        let schema: Schema = input
+            .schema()
+            .clone()
+            .into_fields()
+            .into_iter()
+            .chain([Field::with_name(
+                DataType::Int64,
+                window_function.function_type.name(),
+            )])
+            .collect();
+        // Each input row is emitted exactly once, so the pk of the input still works.
+        let pk_indices = input.pk_indices().to_vec();
+        let base = PlanBase::new_logical(input.ctx(), schema, pk_indices);
+        LogicalOverWindow {
+            base,
+            input,
+            window_function,
+        }
+    }
+
+    /// Plans the window functions in `select_exprs` with a `LogicalOverWindow` each, and replaces
+    /// them with references to the computed columns.
+    pub fn create(
+        input: PlanRef,
+        mut select_exprs: Vec<ExprImpl>,
+    ) -> Result<(PlanRef, Vec<ExprImpl>)> {
+        let mut extractor = WindowFunctionExtractor {
+            input_len: input.schema().len(),
+            window_functions: vec![],
+        };
+        select_exprs = select_exprs
+            .into_iter()
+            .map(|expr| extractor.rewrite_expr(expr))
+            .collect();
+
+        let mut root = input;
+        for window_function in extractor.window_functions {
+            let partition_by = window_function
+                .partition_by
+                .iter()
+                .map(Self::input_index)
+                .try_collect()?;
+            let field_order = window_function
+                .order_by
+                .sort_exprs
+                .iter()
+                .map(|e| {
+                    // The rows are kept in the order of the state table, where nulls always go
+                    // last in ascending order.
+                    if e.nulls_first != e.direction.default_nulls_first() {
+                        return Err(ErrorCode::NotImplemented(
+                            "NULLS FIRST/LAST in the ORDER BY of window functions".to_string(),
+                            None.into(),
+                        )
+                        .into());
+                    }
+                    Ok(FieldOrder {
+                        index: Self::input_index(&e.expr)?,
+                        direct: e.direction,
+                        nulls_first: e.nulls_first,
+                    })
+                })
+                .try_collect::<_, _, RwError>()?;
+            let window_function = PlanWindowFunction {
+                function_type: window_function.function_type,
+                partition_by,
+                order_by: Order::new(field_order),
+            };
+            root = Self::new(window_function, root).into();
+        }
+        Ok((root, select_exprs))
+    }
+
+    fn input_index(expr: &ExprImpl) -> Result<usize> {
+        match expr {
+            ExprImpl::InputRef(input_ref) => Ok(input_ref.index()),
+            _ => Err(ErrorCode::NotImplemented(
+                format!(
+                    "window function partitioned or ordered by expression: {:?}",
+                    expr
+                ),
+                3646.into(),
+            )
+            .into()),
+        }
+    }
+
+    pub fn window_function(&self) -> &PlanWindowFunction {
+        &self.window_function
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        let input_schema = self.input.schema();
+        write!(
+            f,
+            "{} {{ function: {}, partition_by: {}, order_by: {} }}",
+            name,
+            self.window_function.function_type.name(),
+            IndicesDisplay {
+                vec: &self.window_function.partition_by,
+                input_schema,
+            },
+            OrderDisplay {
+                order: &self.window_function.order_by,
+                input_schema,
+            },
+        )
+    }
+}
+
+/// Collects the window functions of the select list, and replaces each with a reference to the
+/// column it is computed into.
+struct WindowFunctionExtractor {
+    input_len: usize,
+    window_functions: Vec<WindowFunction>,
+}
+
+impl ExprRewriter for WindowFunctionExtractor {
+    fn rewrite_window_function(&mut self, window_func: WindowFunction) -> ExprImpl {
+        let index = self.input_len + self.window_functions.len();
+        let data_type = window_func.return_type();
+        self.window_functions.push(window_func);
+        InputRef::new(index, data_type).into()
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalOverWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.window_function.clone(), input)
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let partition_by = self
+            .window_function
+            .partition_by
+            .iter()
+            .map(|&idx| input_col_change.map(idx))
+            .collect_vec();
+        let order_by = input_col_change
+            .rewrite_required_order(&self.window_function.order_by)
+            .unwrap();
+        let window_function = PlanWindowFunction {
+            function_type: self.window_function.function_type,
+            partition_by,
+            order_by,
+        };
+
+        // The window function column follows the input columns.
+        let (mut map, new_input_col_num) = input_col_change.into_parts();
+        assert_eq!(new_input_col_num, input.schema().len());
+        map.push(Some(new_input_col_num));
+
+        (Self::new(window_function, input), ColIndexMapping::new(map))
+    }
+}
+
+impl_plan_tree_node_for_unary! {LogicalOverWindow}
+
+impl fmt::Display for LogicalOverWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalOverWindow")
+    }
+}
+
+impl ColPrunable for LogicalOverWindow {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_col_num = self.input.schema().len();
+        if !required_cols.contains(&input_col_num) {
+            // The window function is not used, so the node can be removed.
+            return self.input.prune_col(required_cols);
+        }
+
+        let input_required_cols = required_cols
+            .iter()
+            .copied()
+            .filter(|&idx| idx < input_col_num)
+            .chain(self.window_function.partition_by.iter().copied())
+            .chain(
+                self.window_function
+                    .order_by
+                    .field_order
+                    .iter()
+                    .map(|o| o.index),
+            )
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let new_input = self.input.prune_col(&input_required_cols);
+        let input_col_change =
+            ColIndexMapping::with_remaining_columns(&input_required_cols, input_col_num);
+        let (new_over_window, col_change) = self.rewrite_with_input(new_input, input_col_change);
+
+        let exprs = required_cols
+            .iter()
+            .map(|&idx| {
+                let mapped_idx = col_change.map(idx);
+                let data_type = new_over_window.schema().fields[mapped_idx].data_type();
+                InputRef::new(mapped_idx, data_type).into()
+            })
+            .collect_vec();
+        LogicalProject::create(new_over_window.into(), exprs)
+    }
+}
+
+impl PredicatePushdown for LogicalOverWindow {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // Filtering the input would change the rows of the partitions, so nothing can be pushed
+        // down.
+        gen_filter_and_pushdown(self, predicate, Condition::true_cond())
+    }
+}
+
+impl ToBatch for LogicalOverWindow {
+    fn to_batch(&self) -> Result<PlanRef> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "over window in batch query".to_string(),
+            3646.into(),
+        )))
+    }
+}
+
+impl ToStream for LogicalOverWindow {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let partition_by = &self.window_function.partition_by;
+        let required_dist = if partition_by.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input.schema().len(), partition_by)
+        };
+        let new_input = self.input.to_stream_with_dist_required(&required_dist)?;
+        let new_logical = self.clone_with_input(new_input);
+        Ok(StreamOverWindow::new(new_logical).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (over_window, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((over_window.into(), out_col_change))
+    }
+}
@@ -69,6 +69,11 @@ impl LogicalProjectBuilder {
     pub fn build(self, input: PlanRef) -> LogicalProject {
         LogicalProject::new(input, self.exprs)
     }
+
+    /// consume the `LogicalProjectBuilder` and return the deduplicated expressions
+    pub fn into_exprs(self) -> Vec<ExprImpl> {
+        self.exprs
+    }
 }
 /// `LogicalProject` computes a set of expressions from its input relation.
 #[derive(Debug, Clone)]
@@ -46,6 +46,8 @@ pub struct LogicalScan {
     indexes: Vec<(String, Rc<TableDesc>)>,
     /// The pushed down predicates. It refers to column indexes of the table.
     predicate: Condition,
+    /// The epoch to read the table at given by `AS OF`, or `None` for the epoch of the query.
+    as_of_epoch: Option<u64>,
 }
 
 impl LogicalScan {
@@ -109,9 +111,21 @@ impl LogicalScan {
             table_desc,
             indexes,
             predicate,
+            as_of_epoch: None,
         }
     }
 
+    /// Reads the table at `as_of_epoch` instead of the epoch of the query.
+    #[must_use]
+    pub fn with_as_of_epoch(mut self, as_of_epoch: Option<u64>) -> Self {
+        self.as_of_epoch = as_of_epoch;
+        self
+    }
+
+    pub fn as_of_epoch(&self) -> Option<u64> {
+        self.as_of_epoch
+    }
+
     /// Create a [`LogicalScan`] node. Used by planner.
     pub fn create(
         table_name: String, // explain-only
@@ -216,6 +230,21 @@ impl LogicalScan {
             .collect()
     }
 
+    /// Whether the scan only reads some ranges of the table, i.e., its predicate can be split to
+    /// scan ranges on the primary key, or it reads nothing at all.
+    pub fn is_range_scan(&self) -> bool {
+        if self.predicate.always_true() {
+            return false;
+        }
+        match self.predicate.clone().split_to_scan_ranges(
+            &self.table_desc.order_column_indices(),
+            self.table_desc.columns.len(),
+        ) {
+            Ok((scan_ranges, predicate)) => !scan_ranges.is_empty() || predicate.always_false(),
+            Err(_) => false,
+        }
+    }
+
     /// Get all indexes on this table
     pub fn indexes(&self) -> &[(String, Rc<TableDesc>)] {
         &self.indexes
@@ -265,6 +294,7 @@ impl LogicalScan {
             self.ctx(),
             self.predicate.clone(),
         )
+        .with_as_of_epoch(self.as_of_epoch)
     }
 
     /// a vec of `InputRef` corresponding to `output_col_idx`, which can represent a pulled project.
@@ -300,7 +330,8 @@ impl LogicalScan {
             self.indexes.clone(),
             self.ctx(),
             Condition::true_cond(),
-        );
+        )
+        .with_as_of_epoch(self.as_of_epoch);
         let project_expr = if self.required_col_idx != self.output_col_idx {
             Some(self.output_idx_to_input_ref())
         } else {
@@ -319,6 +350,7 @@ impl LogicalScan {
             self.base.ctx.clone(),
             predicate,
         )
+        .with_as_of_epoch(self.as_of_epoch)
     }
 
     pub fn clone_with_output_indices(&self, output_col_idx: Vec<usize>) -> Self {
@@ -331,6 +363,7 @@ impl LogicalScan {
             self.base.ctx.clone(),
             self.predicate.clone(),
         )
+        .with_as_of_epoch(self.as_of_epoch)
     }
 }
 
@@ -443,6 +476,12 @@ impl ToStream for LogicalScan {
                 None.into(),
             )));
         }
+        if self.as_of_epoch.is_some() {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "streaming on a historical snapshot with `AS OF` is not allowed".to_string(),
+                None.into(),
+            )));
+        }
         if self.predicate.always_true() {
             Ok(StreamTableScan::new(self.clone()).into())
         } else {
@@ -497,3 +536,65 @@ impl ToStream for LogicalScan {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::binder::Binder;
+    use crate::optimizer::plan_node::PlanTreeNode;
+    use crate::planner::Planner;
+    use crate::session::{OptimizerContext, SessionImpl};
+    use crate::test_utils::LocalFrontend;
+
+    /// Returns the scan of the optimized logical plan of `sql`, which scans one table.
+    fn plan_scan(session: Arc<SessionImpl>, sql: &str) -> LogicalScan {
+        let statement = Parser::parse_sql(sql).unwrap().remove(0);
+        let bound = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        )
+        .bind(statement)
+        .unwrap();
+        let mut plan = Planner::new(OptimizerContext::new(session, Arc::from(sql)).into())
+            .plan(bound)
+            .unwrap()
+            .gen_optimized_logical_plan();
+        loop {
+            if let Some(scan) = plan.as_logical_scan() {
+                return scan.clone();
+            }
+            plan = plan.inputs()[0].clone();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_is_range_scan() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        frontend
+            .run_sql("create table t (v1 int, v2 int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql(
+                "create materialized view mv as select v1, count(v2) as cnt from t group by v1",
+            )
+            .await
+            .unwrap();
+        let session = frontend.session_ref();
+
+        // The predicates on the primary key are split to scan ranges.
+        assert!(plan_scan(session.clone(), "select * from mv where v1 = 1").is_range_scan());
+        assert!(plan_scan(session.clone(), "select * from mv where v1 < 10").is_range_scan());
+        assert!(
+            plan_scan(session.clone(), "select * from mv where v1 = 1 and cnt > 2").is_range_scan()
+        );
+        // The predicates on other columns, or no predicate at all, scan the full table.
+        assert!(!plan_scan(session.clone(), "select * from mv where cnt = 1").is_range_scan());
+        assert!(!plan_scan(session.clone(), "select * from mv").is_range_scan());
+        assert!(!plan_scan(session, "select * from t where v1 = 1").is_range_scan());
+    }
+}
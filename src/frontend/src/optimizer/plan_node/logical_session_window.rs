@@ -0,0 +1,216 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::error::{ErrorCode, Result, RwError};
+use risingwave_common::types::{DataType, IntervalUnit};
+
+use super::{
+    gen_filter_and_pushdown, ColPrunable, LogicalProject, PlanBase, PlanRef, PlanTreeNodeUnary,
+    PredicatePushdown, StreamSessionWindow, ToBatch, ToStream,
+};
+use crate::expr::{InputRef, InputRefDisplay};
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::property::RequiredDist;
+use crate::utils::{ColIndexMapping, Condition};
+
+/// `LogicalSessionWindow` implements Session Table Function. It groups the rows of each partition
+/// into sessions separated by `gap`, and appends the bounds of the session to each row.
+#[derive(Debug, Clone)]
+pub struct LogicalSessionWindow {
+    pub base: PlanBase,
+    input: PlanRef,
+    pub(super) time_col: InputRef,
+    pub(super) gap: IntervalUnit,
+    pub(super) partition_key: Vec<usize>,
+}
+
+impl LogicalSessionWindow {
+    pub fn new(
+        input: PlanRef,
+        time_col: InputRef,
+        gap: IntervalUnit,
+        partition_key: Vec<usize>,
+    ) -> Self {
+        let schema: Schema = input
+            .schema()
+            .clone()
+            .into_fields()
+            .into_iter()
+            .chain([
+                Field::with_name(DataType::Timestamp, "window_start"),
+                Field::with_name(DataType::Timestamp, "window_end"),
+            ])
+            .collect();
+        // Each input row is emitted exactly once, so the pk of the input still works.
+        let pk_indices = input.pk_indices().to_vec();
+        let base = PlanBase::new_logical(input.ctx(), schema, pk_indices);
+        LogicalSessionWindow {
+            base,
+            input,
+            time_col,
+            gap,
+            partition_key,
+        }
+    }
+
+    pub fn create(
+        input: PlanRef,
+        time_col: InputRef,
+        gap: IntervalUnit,
+        partition_key: Vec<usize>,
+    ) -> PlanRef {
+        Self::new(input, time_col, gap, partition_key).into()
+    }
+
+    pub fn fmt_with_name(&self, f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+        write!(
+            f,
+            "{} {{ time_col: {}, gap: {}, partition_key: {} }}",
+            name,
+            InputRefDisplay {
+                input_ref: &self.time_col,
+                input_schema: self.input.schema()
+            },
+            self.gap,
+            IndicesDisplay {
+                vec: &self.partition_key,
+                input_schema: self.input.schema(),
+            },
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for LogicalSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(
+            input,
+            self.time_col.clone(),
+            self.gap,
+            self.partition_key.clone(),
+        )
+    }
+
+    #[must_use]
+    fn rewrite_with_input(
+        &self,
+        input: PlanRef,
+        input_col_change: ColIndexMapping,
+    ) -> (Self, ColIndexMapping) {
+        let mut time_col = self.time_col.clone();
+        time_col.index = input_col_change.map(time_col.index);
+        let partition_key = self
+            .partition_key
+            .iter()
+            .map(|&idx| input_col_change.map(idx))
+            .collect_vec();
+
+        // The window columns follow the input columns.
+        let (mut map, new_input_col_num) = input_col_change.into_parts();
+        assert_eq!(new_input_col_num, input.schema().len());
+        map.push(Some(new_input_col_num));
+        map.push(Some(new_input_col_num + 1));
+
+        (
+            Self::new(input, time_col, self.gap, partition_key),
+            ColIndexMapping::new(map),
+        )
+    }
+}
+
+impl_plan_tree_node_for_unary! {LogicalSessionWindow}
+
+impl fmt::Display for LogicalSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_name(f, "LogicalSessionWindow")
+    }
+}
+
+impl ColPrunable for LogicalSessionWindow {
+    fn prune_col(&self, required_cols: &[usize]) -> PlanRef {
+        let input_col_num = self.input.schema().len();
+        // The time column and the partition key are always required to assign the sessions.
+        let input_required_cols = required_cols
+            .iter()
+            .copied()
+            .filter(|&idx| idx < input_col_num)
+            .chain([self.time_col.index()])
+            .chain(self.partition_key.iter().copied())
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let new_input = self.input.prune_col(&input_required_cols);
+        let input_col_change =
+            ColIndexMapping::with_remaining_columns(&input_required_cols, input_col_num);
+        let (new_session_window, col_change) = self.rewrite_with_input(new_input, input_col_change);
+
+        let exprs = required_cols
+            .iter()
+            .map(|&idx| {
+                let mapped_idx = col_change.map(idx);
+                let data_type = new_session_window.schema().fields[mapped_idx].data_type();
+                InputRef::new(mapped_idx, data_type).into()
+            })
+            .collect_vec();
+        LogicalProject::create(new_session_window.into(), exprs)
+    }
+}
+
+impl PredicatePushdown for LogicalSessionWindow {
+    fn predicate_pushdown(&self, predicate: Condition) -> PlanRef {
+        // Filtering the input would change the sessions, so nothing can be pushed down.
+        gen_filter_and_pushdown(self, predicate, Condition::true_cond())
+    }
+}
+
+impl ToBatch for LogicalSessionWindow {
+    fn to_batch(&self) -> Result<PlanRef> {
+        Err(RwError::from(ErrorCode::NotImplemented(
+            "session window in batch query".to_string(),
+            None.into(),
+        )))
+    }
+}
+
+impl ToStream for LogicalSessionWindow {
+    fn to_stream(&self) -> Result<PlanRef> {
+        let required_dist = if self.partition_key.is_empty() {
+            RequiredDist::single()
+        } else {
+            RequiredDist::shard_by_key(self.input.schema().len(), &self.partition_key)
+        };
+        let new_input = self.input.to_stream_with_dist_required(&required_dist)?;
+        if !new_input.append_only() {
+            return Err(RwError::from(ErrorCode::NotImplemented(
+                "session window on non-append-only input".to_string(),
+                None.into(),
+            )));
+        }
+        let new_logical = self.clone_with_input(new_input);
+        Ok(StreamSessionWindow::new(new_logical).into())
+    }
+
+    fn logical_rewrite_for_stream(&self) -> Result<(PlanRef, ColIndexMapping)> {
+        let (input, input_col_change) = self.input.logical_rewrite_for_stream()?;
+        let (session_window, out_col_change) = self.rewrite_with_input(input, input_col_change);
+        Ok((session_window.into(), out_col_change))
+    }
+}
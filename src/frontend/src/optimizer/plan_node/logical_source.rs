@@ -102,9 +102,9 @@ impl PredicatePushdown for LogicalSource {
 
 impl ToBatch for LogicalSource {
     fn to_batch(&self) -> Result<PlanRef> {
-        Err(RwError::from(ErrorCode::NotImplemented(
-            "there is no batch source operator".to_string(),
-            None.into(),
+        Err(RwError::from(ErrorCode::InvalidInputSyntax(
+            "cannot SELECT from a non-materialized source; create a materialized source or MV first"
+                .to_string(),
         )))
     }
 }
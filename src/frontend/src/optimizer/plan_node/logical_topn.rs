@@ -16,19 +16,23 @@ use std::fmt;
 
 use fixedbitset::FixedBitSet;
 use itertools::Itertools;
-use risingwave_common::error::ErrorCode::InternalError;
+use risingwave_common::error::ErrorCode::{InternalError, NotImplemented};
 use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::DataType;
 
 use super::{
     gen_filter_and_pushdown, ColPrunable, PlanBase, PlanRef, PlanTreeNodeUnary, PredicatePushdown,
     ToBatch, ToStream,
 };
-use crate::optimizer::plan_node::{BatchTopN, LogicalProject, StreamTopN};
+use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef};
+use crate::optimizer::plan_node::utils::IndicesDisplay;
+use crate::optimizer::plan_node::{BatchTopN, LogicalProject, StreamGroupTopN, StreamTopN};
 use crate::optimizer::property::{FieldOrder, Order, OrderDisplay, RequiredDist};
 use crate::planner::LIMIT_ALL_COUNT;
 use crate::utils::{ColIndexMapping, Condition};
 
-/// `LogicalTopN` sorts the input data and fetches up to `limit` rows from `offset`
+/// `LogicalTopN` sorts the input data and fetches up to `limit` rows from `offset`. With a
+/// non-empty `group_key`, the rows are fetched from each group separately.
 #[derive(Debug, Clone)]
 pub struct LogicalTopN {
     pub base: PlanBase,
@@ -36,10 +40,21 @@ pub struct LogicalTopN {
     limit: usize,
     offset: usize,
     order: Order,
+    group_key: Vec<usize>,
 }
 
 impl LogicalTopN {
     pub fn new(input: PlanRef, limit: usize, offset: usize, order: Order) -> Self {
+        Self::with_group(input, limit, offset, order, vec![])
+    }
+
+    pub fn with_group(
+        input: PlanRef,
+        limit: usize,
+        offset: usize,
+        order: Order,
+        group_key: Vec<usize>,
+    ) -> Self {
         let ctx = input.ctx();
         let schema = input.schema().clone();
         let pk_indices = input.pk_indices().to_vec();
@@ -50,6 +65,7 @@ impl LogicalTopN {
             limit,
             offset,
             order,
+            group_key,
         }
     }
 
@@ -66,6 +82,10 @@ impl LogicalTopN {
         self.offset
     }
 
+    pub fn group_key(&self) -> &[usize] {
+        &self.group_key
+    }
+
     /// `topn_order` returns the order of the Top-N operator. This naming is because `order()`
     /// already exists and it was designed to return the operator's physical property order.
     ///
@@ -92,8 +112,20 @@ impl LogicalTopN {
         );
         builder
             .field("limit", &format_args!("{}", self.limit()))
-            .field("offset", &format_args!("{}", self.offset()))
-            .finish()
+            .field("offset", &format_args!("{}", self.offset()));
+        if !self.group_key.is_empty() {
+            builder.field(
+                "group_key",
+                &format_args!(
+                    "{}",
+                    IndicesDisplay {
+                        vec: &self.group_key,
+                        input_schema
+                    }
+                ),
+            );
+        }
+        builder.finish()
     }
 }
 
@@ -103,7 +135,13 @@ impl PlanTreeNodeUnary for LogicalTopN {
     }
 
     fn clone_with_input(&self, input: PlanRef) -> Self {
-        Self::new(input, self.limit, self.offset, self.order.clone())
+        Self::with_group(
+            input,
+            self.limit,
+            self.offset,
+            self.order.clone(),
+            self.group_key.clone(),
+        )
     }
 
     #[must_use]
@@ -113,13 +151,17 @@ impl PlanTreeNodeUnary for LogicalTopN {
         input_col_change: ColIndexMapping,
     ) -> (Self, ColIndexMapping) {
         (
-            Self::new(
+            Self::with_group(
                 input,
                 self.limit,
                 self.offset,
                 input_col_change
                     .rewrite_required_order(&self.order)
                     .unwrap(),
+                self.group_key
+                    .iter()
+                    .map(|&idx| input_col_change.map(idx))
+                    .collect(),
             ),
             input_col_change,
         )
@@ -141,6 +183,9 @@ impl ColPrunable for LogicalTopN {
                 .field_order
                 .iter()
                 .for_each(|fo| order_required_cols.insert(fo.index));
+            self.group_key
+                .iter()
+                .for_each(|&idx| order_required_cols.insert(idx));
             order_required_cols
         };
 
@@ -161,11 +206,14 @@ impl ColPrunable for LogicalTopN {
                 .map(|fo| FieldOrder {
                     index: mapping.map(fo.index),
                     direct: fo.direct,
+                    nulls_first: fo.nulls_first,
                 })
                 .collect(),
         };
+        let new_group_key = self.group_key.iter().map(|&idx| mapping.map(idx)).collect();
         let new_input = self.input.prune_col(&input_required_cols);
-        let top_n = Self::new(new_input, self.limit, self.offset, new_order).into();
+        let top_n =
+            Self::with_group(new_input, self.limit, self.offset, new_order, new_group_key).into();
 
         if input_required_cols == required_cols {
             top_n
@@ -196,6 +244,12 @@ impl ToBatch for LogicalTopN {
     }
 
     fn to_batch_with_order_required(&self, required_order: &Order) -> Result<PlanRef> {
+        if !self.group_key.is_empty() {
+            return Err(RwError::from(NotImplemented(
+                "group top n in batch query".to_string(),
+                None.into(),
+            )));
+        }
         let new_input = self.input().to_batch()?;
         let new_logical = self.clone_with_input(new_input);
         let ret = BatchTopN::new(new_logical).into();
@@ -208,18 +262,89 @@ impl ToBatch for LogicalTopN {
     }
 }
 
+impl LogicalTopN {
+    /// The state of the streaming top-n is kept in the memcomparable order of the order columns,
+    /// where nulls always go last in ascending order. So a non-default null ordering of a column
+    /// is rewritten to an extra `IS NULL` column ordered right before it, which is projected away
+    /// after the top-n. Returns `None` if all the columns use the default null ordering.
+    fn rewrite_nulls_order_for_stream(&self) -> Option<PlanRef> {
+        if self
+            .order
+            .field_order
+            .iter()
+            .all(|fo| fo.nulls_first == fo.direct.default_nulls_first())
+        {
+            return None;
+        }
+
+        let input_len = self.input.schema().len();
+        let mut exprs: Vec<ExprImpl> = self
+            .input
+            .schema()
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| InputRef::new(i, field.data_type()).into())
+            .collect();
+        let mut field_order = vec![];
+        for fo in &self.order.field_order {
+            if fo.nulls_first != fo.direct.default_nulls_first() {
+                let is_null = FunctionCall::new_unchecked(
+                    ExprType::IsNull,
+                    vec![exprs[fo.index].clone()],
+                    DataType::Boolean,
+                );
+                // `true` goes after `false` in ascending order.
+                field_order.push(if fo.nulls_first {
+                    FieldOrder::descending(exprs.len())
+                } else {
+                    FieldOrder::ascending(exprs.len())
+                });
+                exprs.push(is_null.into());
+            }
+            field_order.push(fo.clone().with_nulls_first(fo.direct.default_nulls_first()));
+        }
+
+        let input = LogicalProject::create(self.input(), exprs);
+        let top_n = Self::with_group(
+            input,
+            self.limit,
+            self.offset,
+            Order::new(field_order),
+            self.group_key.clone(),
+        );
+        Some(LogicalProject::with_out_col_idx(top_n.into(), 0..input_len).into())
+    }
+}
+
 impl ToStream for LogicalTopN {
     fn to_stream(&self) -> Result<PlanRef> {
-        // Unlike `BatchTopN`, `StreamTopN` cannot guarantee the output order
-        let input = self
-            .input()
-            .to_stream_with_dist_required(&RequiredDist::single())?;
-
         if self.offset() != 0 && self.limit == LIMIT_ALL_COUNT {
             return Err(RwError::from(InternalError(
                 "Doesn't support OFFSET without LIMIT".to_string(),
             )));
         }
+
+        if let Some(plan) = self.rewrite_nulls_order_for_stream() {
+            return plan.to_stream();
+        }
+
+        if !self.group_key.is_empty() {
+            // Each group is handled on its own, so the input only needs to be sharded by the group
+            // key.
+            let input = self
+                .input()
+                .to_stream_with_dist_required(&RequiredDist::shard_by_key(
+                    self.input().schema().len(),
+                    &self.group_key,
+                ))?;
+            return Ok(StreamGroupTopN::new(self.clone_with_input(input)).into());
+        }
+
+        // Unlike `BatchTopN`, `StreamTopN` cannot guarantee the output order
+        let input = self
+            .input()
+            .to_stream_with_dist_required(&RequiredDist::single())?;
         Ok(StreamTopN::new(self.clone_with_input(input)).into())
     }
 
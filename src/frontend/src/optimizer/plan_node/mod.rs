@@ -128,8 +128,8 @@ impl dyn PlanNode {
         self.to_batch_prost_identity(true)
     }
 
-    /// Serialize the plan node and its children to a batch plan proto without the identity field
-    /// (for testing).
+    /// Serialize the plan node and its children to a batch plan proto without the identity and plan
+    /// node id fields (for testing).
     pub fn to_batch_prost_identity(&self, identity: bool) -> BatchPlanProst {
         let node_body = Some(self.to_batch_prost_body());
         let children = self
@@ -144,6 +144,7 @@ impl dyn PlanNode {
             } else {
                 "".into()
             },
+            plan_node_id: if identity { self.id().0 } else { 0 },
             node_body,
         }
     }
@@ -172,7 +173,6 @@ impl dyn PlanNode {
             .into_iter()
             .map(|plan| plan.to_stream_prost_auto_fields(auto_fields))
             .collect();
-        // TODO: support pk_indices and operator_id
         StreamPlanProst {
             input,
             identity: if auto_fields {
@@ -210,6 +210,7 @@ mod batch_exchange;
 mod batch_expand;
 mod batch_filter;
 mod batch_hash_agg;
+mod batch_hash_distinct;
 mod batch_hash_join;
 mod batch_hop_window;
 mod batch_insert;
@@ -235,9 +236,11 @@ mod logical_insert;
 mod logical_join;
 mod logical_limit;
 mod logical_multi_join;
+mod logical_over_window;
 mod logical_project;
 mod logical_project_set;
 mod logical_scan;
+mod logical_session_window;
 mod logical_source;
 mod logical_table_function;
 mod logical_topn;
@@ -249,15 +252,19 @@ mod stream_exchange;
 mod stream_expand;
 mod stream_filter;
 mod stream_global_simple_agg;
+mod stream_group_topn;
 mod stream_hash_agg;
 mod stream_hash_join;
 mod stream_hop_window;
 mod stream_index_scan;
 mod stream_local_simple_agg;
 mod stream_materialize;
+mod stream_over_window;
 mod stream_project;
 mod stream_project_set;
+mod stream_session_window;
 mod stream_sink;
+mod stream_sort;
 mod stream_source;
 mod stream_table_scan;
 mod stream_topn;
@@ -269,6 +276,7 @@ pub use batch_exchange::BatchExchange;
 pub use batch_expand::BatchExpand;
 pub use batch_filter::BatchFilter;
 pub use batch_hash_agg::BatchHashAgg;
+pub use batch_hash_distinct::BatchHashDistinct;
 pub use batch_hash_join::BatchHashJoin;
 pub use batch_hop_window::BatchHopWindow;
 pub use batch_insert::BatchInsert;
@@ -294,9 +302,11 @@ pub use logical_insert::LogicalInsert;
 pub use logical_join::LogicalJoin;
 pub use logical_limit::LogicalLimit;
 pub use logical_multi_join::{LogicalMultiJoin, LogicalMultiJoinBuilder};
+pub use logical_over_window::{LogicalOverWindow, PlanWindowFunction};
 pub use logical_project::{LogicalProject, LogicalProjectBuilder};
 pub use logical_project_set::LogicalProjectSet;
 pub use logical_scan::LogicalScan;
+pub use logical_session_window::LogicalSessionWindow;
 pub use logical_source::LogicalSource;
 pub use logical_table_function::LogicalTableFunction;
 pub use logical_topn::LogicalTopN;
@@ -308,15 +318,19 @@ pub use stream_exchange::StreamExchange;
 pub use stream_expand::StreamExpand;
 pub use stream_filter::StreamFilter;
 pub use stream_global_simple_agg::StreamGlobalSimpleAgg;
+pub use stream_group_topn::StreamGroupTopN;
 pub use stream_hash_agg::StreamHashAgg;
 pub use stream_hash_join::StreamHashJoin;
 pub use stream_hop_window::StreamHopWindow;
 pub use stream_index_scan::StreamIndexScan;
 pub use stream_local_simple_agg::StreamLocalSimpleAgg;
 pub use stream_materialize::StreamMaterialize;
+pub use stream_over_window::StreamOverWindow;
 pub use stream_project::StreamProject;
 pub use stream_project_set::StreamProjectSet;
+pub use stream_session_window::StreamSessionWindow;
 pub use stream_sink::StreamSink;
+pub use stream_sort::StreamSort;
 pub use stream_source::StreamSource;
 pub use stream_table_scan::StreamTableScan;
 pub use stream_topn::StreamTopN;
@@ -358,9 +372,12 @@ macro_rules! for_all_plan_nodes {
             , { Logical, MultiJoin }
             , { Logical, Expand }
             , { Logical, ProjectSet }
+            , { Logical, SessionWindow }
+            , { Logical, OverWindow }
             // , { Logical, Sort } we don't need a LogicalSort, just require the Order
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
+            , { Batch, HashDistinct }
             , { Batch, Project }
             , { Batch, Filter }
             , { Batch, Insert }
@@ -397,6 +414,10 @@ macro_rules! for_all_plan_nodes {
             , { Stream, Expand }
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
+            , { Stream, Sort }
+            , { Stream, SessionWindow }
+            , { Stream, OverWindow }
+            , { Stream, GroupTopN }
         }
     };
 }
@@ -425,8 +446,11 @@ macro_rules! for_logical_plan_nodes {
             , { Logical, MultiJoin }
             , { Logical, Expand }
             , { Logical, ProjectSet }
-            // , { Logical, Sort} not sure if we will support Order by clause in subquery/view/MV
-            // if we dont support that, we don't need LogicalSort, just require the Order at the top of query
+            , { Logical, SessionWindow }
+            , { Logical, OverWindow }
+            // There is no `LogicalSort`: an `ORDER BY` with `LIMIT`/`OFFSET` is planned as a
+            // `LogicalTopN`, and a bare `ORDER BY` at the top of a query becomes the required order
+            // of the `PlanRoot`, which is rejected in an MV definition.
         }
     };
 }
@@ -439,6 +463,7 @@ macro_rules! for_batch_plan_nodes {
             [$($x),*]
             , { Batch, SimpleAgg }
             , { Batch, HashAgg }
+            , { Batch, HashDistinct }
             , { Batch, Project }
             , { Batch, Filter }
             , { Batch, SeqScan }
@@ -485,6 +510,10 @@ macro_rules! for_stream_plan_nodes {
             , { Stream, Expand }
             , { Stream, DynamicFilter }
             , { Stream, ProjectSet }
+            , { Stream, Sort }
+            , { Stream, SessionWindow }
+            , { Stream, OverWindow }
+            , { Stream, GroupTopN }
         }
     };
 }
@@ -16,7 +16,7 @@ use std::fmt;
 
 use itertools::Itertools;
 use risingwave_common::catalog::{DatabaseId, Field, Schema, SchemaId};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_pb::plan_common::JoinType;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
@@ -25,7 +25,7 @@ use risingwave_pb::stream_plan::HashJoinNode;
 use super::utils::TableCatalogBuilder;
 use super::{LogicalJoin, PlanBase, PlanRef, PlanTreeNodeBinary, StreamDeltaJoin, ToStreamProst};
 use crate::catalog::table_catalog::TableCatalog;
-use crate::expr::Expr;
+use crate::expr::{Expr, ExprImpl, ExprType, FunctionCall, InputRef, Literal};
 use crate::optimizer::plan_node::utils::IndicesDisplay;
 use crate::optimizer::plan_node::{EqJoinPredicate, EqJoinPredicateDisplay};
 use crate::optimizer::property::Distribution;
@@ -51,6 +51,10 @@ pub struct StreamHashJoin {
     /// Whether can optimize for append-only stream.
     /// It is true if input of both side is append-only
     is_append_only: bool,
+
+    /// Computes from the watermark of the first join key the bound below which the states are
+    /// cleaned, if the retention of the states is set for this join.
+    state_clean_expr: Option<ExprImpl>,
 }
 
 impl StreamHashJoin {
@@ -71,6 +75,11 @@ impl StreamHashJoin {
         );
 
         let force_delta = ctx.inner().session_ctx.config().get_delta_join();
+        let state_clean_expr = Self::derive_state_clean_expr(
+            &logical,
+            &eq_join_predicate,
+            ctx.streaming_join_state_retention(),
+        );
 
         // TODO: derive from input
         let base = PlanBase::new_stream(
@@ -87,7 +96,42 @@ impl StreamHashJoin {
             eq_join_predicate,
             is_delta: force_delta,
             is_append_only: append_only,
+            state_clean_expr,
+        }
+    }
+
+    /// Builds the expression subtracting `retention` from the watermark of the first join key,
+    /// in seconds for a time key, or in its own unit for an integer key. Returns `None` if the
+    /// retention is negative or the key is of other types, so the states are never cleaned.
+    fn derive_state_clean_expr(
+        logical: &LogicalJoin,
+        eq_join_predicate: &EqJoinPredicate,
+        retention: i32,
+    ) -> Option<ExprImpl> {
+        if retention < 0 {
+            return None;
         }
+        let key_type = logical.left().schema().fields()
+            [*eq_join_predicate.left_eq_indexes().first()?]
+        .data_type();
+        let retention = match key_type {
+            DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+                Literal::new(Some(ScalarImpl::Int32(retention)), DataType::Int32)
+            }
+            DataType::Date | DataType::Timestamp | DataType::Timestampz => Literal::new(
+                Some(ScalarImpl::Interval(IntervalUnit::from_millis(
+                    retention as i64 * 1000,
+                ))),
+                DataType::Interval,
+            ),
+            _ => return None,
+        };
+        let bound = FunctionCall::new(
+            ExprType::Subtract,
+            vec![InputRef::new(0, key_type.clone()).into(), retention.into()],
+        )
+        .ok()?;
+        ExprImpl::from(bound).cast_explicit(key_type).ok()
     }
 
     /// Get join type
@@ -234,6 +278,12 @@ impl ToStreamProst for StreamHashJoin {
                 .map(|&x| x as u32)
                 .collect(),
             is_append_only: self.is_append_only,
+            is_left_append_only: self.left().append_only(),
+            is_right_append_only: self.right().append_only(),
+            state_clean_expr: self
+                .state_clean_expr
+                .as_ref()
+                .map(|expr| expr.to_expr_proto()),
         })
     }
 }
@@ -246,6 +296,7 @@ fn infer_internal_table_catalog(input: PlanRef, join_key_indices: Vec<usize>) ->
     let dist_keys = base.dist.dist_column_indices().to_vec();
 
     // The pk of hash join internal table should be join_key + input_pk.
+    let join_key_len = join_key_indices.len();
     let mut pk_indices = join_key_indices;
     // TODO(yuhao): dedup the dist key and pk.
     pk_indices.extend(&base.pk_indices);
@@ -265,6 +316,8 @@ fn infer_internal_table_catalog(input: PlanRef, join_key_indices: Vec<usize>) ->
     pk_indices.iter().for_each(|idx| {
         internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
     });
+    // The rows are read by the join key.
+    internal_table_catalog_builder.set_read_pattern(0, join_key_len);
 
     internal_table_catalog_builder.build(dist_keys, append_only)
 }
@@ -36,10 +36,10 @@ impl StreamLocalSimpleAgg {
         let input_dist = input.distribution();
         debug_assert!(input_dist.satisfies(&RequiredDist::AnyShard));
 
-        // Although output are only inserts,
-        // this stream cannot be materialized,
-        // so its `append_only` property is false.
-        let append_only = false;
+        // The output are only inserts. But unless the input is append-only, the partial results may
+        // come from retractions, so its `append_only` property follows the input. This lets the
+        // global agg keep min/max of append-only input in memory.
+        let append_only = input.append_only();
         let base = PlanBase::new_stream(
             ctx,
             logical.schema().clone(),
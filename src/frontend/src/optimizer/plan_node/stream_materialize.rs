@@ -27,7 +27,7 @@ use crate::catalog::column_catalog::ColumnCatalog;
 use crate::catalog::table_catalog::TableCatalog;
 use crate::catalog::ColumnId;
 use crate::optimizer::plan_node::{PlanBase, PlanNode};
-use crate::optimizer::property::{Direction, Distribution, FieldOrder, Order, RequiredDist};
+use crate::optimizer::property::{Distribution, FieldOrder, Order, RequiredDist};
 
 /// Materializes a stream.
 #[derive(Debug, Clone)]
@@ -133,7 +133,12 @@ impl StreamMaterialize {
 
         for field in &user_order_by.field_order {
             let idx = field.index;
-            order_keys.push(field.clone());
+            // The storage keeps nulls last in ascending order, whatever the query asks for.
+            order_keys.push(
+                field
+                    .clone()
+                    .with_nulls_first(field.direct.default_nulls_first()),
+            );
             in_order.insert(idx);
         }
 
@@ -141,10 +146,7 @@ impl StreamMaterialize {
             if in_order.contains(idx) {
                 continue;
             }
-            order_keys.push(FieldOrder {
-                index: idx,
-                direct: Direction::Asc,
-            });
+            order_keys.push(FieldOrder::ascending(idx));
             in_order.insert(idx);
         }
 
@@ -162,6 +164,7 @@ impl StreamMaterialize {
             vnode_mapping: None,
             properties: HashMap::default(),
             read_pattern_prefix_column: 0,
+            read_pattern_start_column: 0,
         };
 
         Ok(Self { base, input, table })
@@ -0,0 +1,120 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{DatabaseId, SchemaId};
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::OverWindowNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{LogicalOverWindow, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::catalog::TableCatalog;
+use crate::optimizer::property::FieldOrder;
+
+/// [`StreamOverWindow`] computes a window function over the partitions of its input. When a
+/// partition changes, the rows whose window function value changed are retracted and emitted
+/// again.
+#[derive(Debug, Clone)]
+pub struct StreamOverWindow {
+    pub base: PlanBase,
+    logical: LogicalOverWindow,
+}
+
+impl StreamOverWindow {
+    pub fn new(logical: LogicalOverWindow) -> Self {
+        let input = logical.input();
+        // The input columns keep their positions, so does the distribution.
+        let base = PlanBase::new_stream(
+            logical.base.ctx.clone(),
+            logical.schema().clone(),
+            logical.base.pk_indices.to_vec(),
+            input.distribution().clone(),
+            false,
+        );
+        Self { base, logical }
+    }
+
+    /// The order of the rows in the state table: the partition key, the window order and the
+    /// input pk, so that the rows of a partition are contiguous and sorted.
+    fn column_orders(&self) -> Vec<FieldOrder> {
+        let window_function = self.logical.window_function();
+        let input = self.logical.input();
+        window_function
+            .partition_by
+            .iter()
+            .map(|&idx| FieldOrder::ascending(idx))
+            .chain(window_function.order_by.field_order.iter().cloned())
+            .chain(
+                input
+                    .pk_indices()
+                    .iter()
+                    .map(|&idx| FieldOrder::ascending(idx)),
+            )
+            .unique_by(|o| o.index)
+            .collect_vec()
+    }
+
+    fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let base = self.logical.input().plan_base();
+        let mut internal_table_catalog_builder = TableCatalogBuilder::new();
+
+        base.schema.fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        self.column_orders().iter().for_each(|o| {
+            internal_table_catalog_builder.add_order_column(o.index, o.direct.to_order())
+        });
+
+        internal_table_catalog_builder.build(base.dist.dist_column_indices().to_vec(), true)
+    }
+}
+
+impl fmt::Display for StreamOverWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamOverWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamOverWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! {StreamOverWindow}
+
+impl ToStreamProst for StreamOverWindow {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::OverWindow(OverWindowNode {
+            partition_key_indices: self
+                .logical
+                .window_function()
+                .partition_by
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            state_table: Some(self.infer_internal_table_catalog().to_prost(
+                SchemaId::placeholder() as u32,
+                DatabaseId::placeholder() as u32,
+            )),
+        })
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use itertools::Itertools;
+use risingwave_common::catalog::{DatabaseId, SchemaId};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::SessionWindowNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{LogicalSessionWindow, PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::catalog::TableCatalog;
+
+/// [`StreamSessionWindow`] represents a session window table function. The rows of a session are
+/// emitted once the watermark of the time column passes the end of the session.
+#[derive(Debug, Clone)]
+pub struct StreamSessionWindow {
+    pub base: PlanBase,
+    logical: LogicalSessionWindow,
+}
+
+impl StreamSessionWindow {
+    pub fn new(logical: LogicalSessionWindow) -> Self {
+        let input = logical.input();
+        assert!(
+            input.append_only(),
+            "StreamSessionWindow only supports append-only input"
+        );
+        // The input columns keep their positions, so does the distribution.
+        let base = PlanBase::new_stream(
+            logical.base.ctx.clone(),
+            logical.schema().clone(),
+            logical.base.pk_indices.to_vec(),
+            input.distribution().clone(),
+            true,
+        );
+        Self { base, logical }
+    }
+
+    fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let base = self.logical.input().plan_base();
+
+        // The pk of the internal table is the partition key, the time column and the input pk.
+        let pk_indices = self
+            .logical
+            .partition_key
+            .iter()
+            .copied()
+            .chain([self.logical.time_col.index()])
+            .chain(base.pk_indices.iter().copied())
+            .unique()
+            .collect_vec();
+
+        let mut internal_table_catalog_builder = TableCatalogBuilder::new();
+
+        base.schema.fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        pk_indices.iter().for_each(|idx| {
+            internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
+        });
+
+        internal_table_catalog_builder.build(base.dist.dist_column_indices().to_vec(), true)
+    }
+}
+
+impl fmt::Display for StreamSessionWindow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.logical.fmt_with_name(f, "StreamSessionWindow")
+    }
+}
+
+impl PlanTreeNodeUnary for StreamSessionWindow {
+    fn input(&self) -> PlanRef {
+        self.logical.input()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(self.logical.clone_with_input(input))
+    }
+}
+
+impl_plan_tree_node_for_unary! {StreamSessionWindow}
+
+impl ToStreamProst for StreamSessionWindow {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::SessionWindow(SessionWindowNode {
+            time_col: Some(self.logical.time_col.to_proto()),
+            gap: Some(self.logical.gap.into()),
+            partition_key_indices: self
+                .logical
+                .partition_key
+                .iter()
+                .map(|&x| x as u32)
+                .collect(),
+            state_table: Some(self.infer_internal_table_catalog().to_prost(
+                SchemaId::placeholder() as u32,
+                DatabaseId::placeholder() as u32,
+            )),
+        })
+    }
+}
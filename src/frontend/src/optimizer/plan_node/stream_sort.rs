@@ -0,0 +1,112 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use risingwave_common::catalog::{DatabaseId, SchemaId};
+use risingwave_common::util::sort_util::OrderType;
+use risingwave_pb::stream_plan::stream_node::NodeBody as ProstStreamNode;
+use risingwave_pb::stream_plan::SortNode;
+
+use super::utils::TableCatalogBuilder;
+use super::{PlanBase, PlanRef, PlanTreeNodeUnary, ToStreamProst};
+use crate::catalog::TableCatalog;
+
+/// [`StreamSort`] reorders an append-only input by the sort column. Rows are emitted once the
+/// watermark of the sort column passes them, and rows arriving behind the watermark are dropped.
+#[derive(Debug, Clone)]
+pub struct StreamSort {
+    pub base: PlanBase,
+    input: PlanRef,
+    sort_column_index: usize,
+}
+
+impl StreamSort {
+    pub fn new(input: PlanRef, sort_column_index: usize) -> Self {
+        assert!(
+            input.append_only(),
+            "StreamSort only supports append-only input"
+        );
+        let base = PlanBase::new_stream(
+            input.ctx(),
+            input.schema().clone(),
+            input.pk_indices().to_vec(),
+            input.distribution().clone(),
+            true,
+        );
+        Self {
+            base,
+            input,
+            sort_column_index,
+        }
+    }
+
+    pub fn sort_column_index(&self) -> usize {
+        self.sort_column_index
+    }
+
+    fn infer_internal_table_catalog(&self) -> TableCatalog {
+        let base = self.input.plan_base();
+
+        // The pk of the internal table is the sort column followed by the input pk.
+        let mut pk_indices = vec![self.sort_column_index];
+        pk_indices.extend(&base.pk_indices);
+
+        let mut internal_table_catalog_builder = TableCatalogBuilder::new();
+
+        base.schema.fields().iter().for_each(|field| {
+            internal_table_catalog_builder.add_column(field);
+        });
+
+        pk_indices.iter().for_each(|idx| {
+            internal_table_catalog_builder.add_order_column(*idx, OrderType::Ascending)
+        });
+
+        internal_table_catalog_builder.build(base.dist.dist_column_indices().to_vec(), true)
+    }
+}
+
+impl fmt::Display for StreamSort {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "StreamSort {{ sort_column: {} }}",
+            self.input.schema().fields()[self.sort_column_index].name
+        )
+    }
+}
+
+impl PlanTreeNodeUnary for StreamSort {
+    fn input(&self) -> PlanRef {
+        self.input.clone()
+    }
+
+    fn clone_with_input(&self, input: PlanRef) -> Self {
+        Self::new(input, self.sort_column_index)
+    }
+}
+
+impl_plan_tree_node_for_unary! { StreamSort }
+
+impl ToStreamProst for StreamSort {
+    fn to_stream_prost_body(&self) -> ProstStreamNode {
+        ProstStreamNode::Sort(SortNode {
+            sort_column_index: self.sort_column_index as u32,
+            state_table: Some(self.infer_internal_table_catalog().to_prost(
+                SchemaId::placeholder() as u32,
+                DatabaseId::placeholder() as u32,
+            )),
+        })
+    }
+}
@@ -140,6 +140,14 @@ impl StreamTableScan {
 
         let pk_indices = self.base.pk_indices.iter().map(|x| *x as u32).collect_vec();
 
+        let enable_rearranged_chain = self
+            .base
+            .ctx
+            .inner()
+            .session_ctx
+            .config()
+            .get_streaming_enable_rearranged_chain();
+
         ProstStreamPlan {
             fields: self.schema().to_prost(),
             input: vec![
@@ -165,7 +173,7 @@ impl StreamTableScan {
             node_body: Some(ProstStreamNode::Chain(ChainNode {
                 table_id: self.logical.table_desc().table_id.table_id,
                 same_worker_node: false,
-                disable_rearrange: false,
+                disable_rearrange: !enable_rearranged_chain,
                 // The fields from upstream
                 upstream_fields: self
                     .logical
@@ -29,6 +29,8 @@ pub struct TableCatalogBuilder {
     column_names: HashMap<String, i32>,
     order_key: Vec<FieldOrder>,
     pk_indices: Vec<usize>,
+    read_pattern_start_column: u32,
+    read_pattern_prefix_column: u32,
 }
 
 /// For DRY, mainly used for construct internal table catalog in stateful streaming executors.
@@ -67,9 +69,18 @@ impl TableCatalogBuilder {
                 OrderType::Ascending => Direction::Asc,
                 OrderType::Descending => Direction::Desc,
             },
+            nulls_first: order_type.default_nulls_first(),
         });
     }
 
+    /// Set the read pattern of the table: the table is mostly read by the `prefix_column` pk
+    /// columns starting from the `start_column`-th one, which become the keys of its bloom filter.
+    pub fn set_read_pattern(&mut self, start_column: usize, prefix_column: usize) {
+        assert!(start_column + prefix_column <= self.pk_indices.len());
+        self.read_pattern_start_column = start_column as u32;
+        self.read_pattern_prefix_column = prefix_column as u32;
+    }
+
     /// Check the column name whether exist before. if true, record occurrence and change the name
     /// to avoid duplicate.
     fn avoid_duplicate_col_name(&mut self, column_desc: &mut ColumnDesc) {
@@ -97,7 +108,8 @@ impl TableCatalogBuilder {
             owner: risingwave_common::catalog::DEFAULT_SUPER_USER_ID,
             vnode_mapping: None,
             properties: HashMap::default(),
-            read_pattern_prefix_column: 0,
+            read_pattern_prefix_column: self.read_pattern_prefix_column,
+            read_pattern_start_column: self.read_pattern_start_column,
         }
     }
 
@@ -94,11 +94,14 @@ impl fmt::Debug for OrderDisplay<'_> {
 pub struct FieldOrder {
     pub index: usize,
     pub direct: Direction,
+    /// Whether nulls go before all the other values. Ignored if the direction is `Any`.
+    pub nulls_first: bool,
 }
 
 impl std::fmt::Debug for FieldOrder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "${} {}", self.index, self.direct)
+        write!(f, "${} {}", self.index, self.direct)?;
+        self.fmt_nulls(f)
     }
 }
 
@@ -115,7 +118,8 @@ impl FieldOrderDisplay<'_> {
             "{} {}",
             FieldDisplay(self.input_schema.fields.get(that.index).unwrap()),
             that.direct
-        )
+        )?;
+        that.fmt_nulls(f)
     }
 }
 
@@ -126,24 +130,51 @@ impl fmt::Debug for FieldOrderDisplay<'_> {
 }
 
 impl FieldOrder {
+    /// `ASC NULLS LAST`
     pub fn ascending(index: usize) -> Self {
         Self {
             index,
             direct: Direction::Asc,
+            nulls_first: false,
         }
     }
 
+    /// `DESC NULLS FIRST`
     pub fn descending(index: usize) -> Self {
         Self {
             index,
             direct: Direction::Desc,
+            nulls_first: true,
         }
     }
 
+    pub fn with_nulls_first(mut self, nulls_first: bool) -> Self {
+        self.nulls_first = nulls_first;
+        self
+    }
+
+    /// Only the null ordering different from the default of the direction is shown.
+    fn fmt_nulls(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.direct == Direction::Any || self.nulls_first == self.direct.default_nulls_first() {
+            Ok(())
+        } else if self.nulls_first {
+            f.write_str(" NULLS FIRST")
+        } else {
+            f.write_str(" NULLS LAST")
+        }
+    }
+
+    pub fn satisfies(&self, other: &FieldOrder) -> bool {
+        self.index == other.index
+            && self.direct.satisfies(&other.direct)
+            && (other.direct == Direction::Any || self.nulls_first == other.nulls_first)
+    }
+
     pub fn to_protobuf(&self) -> ColumnOrder {
         ColumnOrder {
             order_type: self.direct.to_protobuf() as i32,
             index: self.index as u32,
+            nulls_first: self.nulls_first,
         }
     }
 
@@ -152,6 +183,7 @@ impl FieldOrder {
         Self {
             direct: Direction::from_protobuf(&order_type),
             index: column_order.index as usize,
+            nulls_first: column_order.nulls_first,
         }
     }
 
@@ -160,13 +192,15 @@ impl FieldOrder {
         OrderPair {
             column_idx: self.index,
             order_type: self.direct.to_order(),
+            nulls_first: self.nulls_first,
         }
     }
 }
 
 impl fmt::Display for FieldOrder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "${} {}", self.index, self.direct)
+        write!(f, "${} {}", self.index, self.direct)?;
+        self.fmt_nulls(f)
     }
 }
 
@@ -223,6 +257,11 @@ impl Direction {
             _ => unreachable!(),
         }
     }
+
+    /// `ASC NULLS LAST` and `DESC NULLS FIRST` as in the SQL standard.
+    pub fn default_nulls_first(&self) -> bool {
+        *self == Direction::Desc
+    }
 }
 
 impl Direction {
@@ -258,7 +297,7 @@ impl Order {
         }
         #[expect(clippy::disallowed_methods)]
         for (order, other_order) in self.field_order.iter().zip(other.field_order.iter()) {
-            if order.index != other_order.index || !order.direct.satisfies(&other_order.direct) {
+            if !order.satisfies(other_order) {
                 return false;
             }
         }
@@ -287,14 +326,17 @@ mod tests {
                 FieldOrder {
                     index: 0,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
                 FieldOrder {
                     index: 1,
                     direct: Direction::Desc,
+                    nulls_first: true,
                 },
                 FieldOrder {
                     index: 2,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
             ],
         };
@@ -303,10 +345,12 @@ mod tests {
                 FieldOrder {
                     index: 0,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
                 FieldOrder {
                     index: 1,
                     direct: Direction::Desc,
+                    nulls_first: true,
                 },
             ],
         };
@@ -315,10 +359,12 @@ mod tests {
                 FieldOrder {
                     index: 0,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
                 FieldOrder {
                     index: 1,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
             ],
         };
@@ -327,10 +373,12 @@ mod tests {
                 FieldOrder {
                     index: 0,
                     direct: Direction::Asc,
+                    nulls_first: false,
                 },
                 FieldOrder {
                     index: 1,
                     direct: Direction::Any,
+                    nulls_first: false,
                 },
             ],
         };
@@ -347,4 +395,34 @@ mod tests {
         assert!(!o4.satisfies(&o2));
         assert!(!o4.satisfies(&o3));
     }
+
+    #[test]
+    fn test_order_satisfy_with_nulls() {
+        // `$0 ASC NULLS FIRST, $1 DESC NULLS LAST`
+        let o1 = Order::new(vec![
+            FieldOrder::ascending(0).with_nulls_first(true),
+            FieldOrder::descending(1).with_nulls_first(false),
+        ]);
+        // `$0 ASC, $1 DESC`, with the default null ordering.
+        let o2 = Order::new(vec![FieldOrder::ascending(0), FieldOrder::descending(1)]);
+        // `$0 ASC NULLS FIRST, $1 ANY`
+        let o3 = Order::new(vec![
+            FieldOrder::ascending(0).with_nulls_first(true),
+            FieldOrder {
+                index: 1,
+                direct: Direction::Any,
+                nulls_first: false,
+            },
+        ]);
+
+        assert!(o1.satisfies(&o1));
+        assert!(!o1.satisfies(&o2));
+        assert!(!o2.satisfies(&o1));
+
+        assert!(o1.satisfies(&o3));
+        assert!(!o2.satisfies(&o3));
+
+        assert_eq!(o1.to_string(), "[$0 ASC NULLS FIRST, $1 DESC NULLS LAST]");
+        assert_eq!(o2.to_string(), "[$0 ASC, $1 DESC]");
+    }
 }
@@ -0,0 +1,198 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::session_config::QueryMode;
+
+use crate::optimizer::plan_node::{
+    LogicalAgg, LogicalJoin, LogicalScan, LogicalTopN, PlanTreeNodeBinary, PlanTreeNodeUnary,
+};
+use crate::optimizer::plan_visitor::PlanVisitor;
+use crate::optimizer::PlanRef;
+
+/// Chooses the mode of a batch query whose `QUERY_MODE` is [`QueryMode::Auto`], from its
+/// optimized logical plan.
+///
+/// Local mode runs the whole plan in one task scheduled by the frontend, which saves the cost of
+/// scheduling stages and exchanging data between them. That cost dominates small queries, so local
+/// mode is chosen when no operator of the plan requires an exchange in distributed mode, and no
+/// table is fully scanned. There are no table statistics yet, so the scan ranges are the only
+/// estimation of the amount of data scanned.
+///
+/// A join that can be a lookup join requires no exchange, and its right side is looked up instead
+/// of scanned.
+#[derive(Default)]
+pub struct QueryModeSelector {
+    enable_lookup_join: bool,
+    has_exchange: bool,
+    has_full_scan: bool,
+}
+
+impl QueryModeSelector {
+    /// Returns the chosen mode, and the reason shown by `EXPLAIN`.
+    pub fn select(plan: PlanRef) -> (QueryMode, &'static str) {
+        let mut selector = Self {
+            enable_lookup_join: plan
+                .ctx()
+                .inner()
+                .session_ctx
+                .config()
+                .get_batch_enable_lookup_join(),
+            ..Default::default()
+        };
+        selector.visit(plan);
+        if selector.has_exchange {
+            (
+                QueryMode::Distributed,
+                "the plan exchanges data between stages",
+            )
+        } else if selector.has_full_scan {
+            (QueryMode::Distributed, "the plan scans a full table")
+        } else {
+            (
+                QueryMode::Local,
+                "the plan is a small scan without exchange",
+            )
+        }
+    }
+}
+
+impl PlanVisitor<()> for QueryModeSelector {
+    fn visit_logical_agg(&mut self, plan: &LogicalAgg) {
+        self.has_exchange = true;
+        self.visit(plan.input());
+    }
+
+    fn visit_logical_top_n(&mut self, plan: &LogicalTopN) {
+        self.has_exchange = true;
+        self.visit(plan.input());
+    }
+
+    fn visit_logical_join(&mut self, plan: &LogicalJoin) {
+        if self.enable_lookup_join && plan.can_be_lookup_join() {
+            self.visit(plan.left());
+        } else {
+            self.has_exchange = true;
+            self.visit(plan.left());
+            self.visit(plan.right());
+        }
+    }
+
+    fn visit_logical_scan(&mut self, plan: &LogicalScan) {
+        if !plan.is_range_scan() {
+            self.has_full_scan = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use risingwave_sqlparser::parser::Parser;
+
+    use super::*;
+    use crate::binder::Binder;
+    use crate::planner::Planner;
+    use crate::session::{OptimizerContext, SessionImpl};
+    use crate::test_utils::LocalFrontend;
+
+    async fn create_table_and_mv(frontend: &LocalFrontend) {
+        frontend
+            .run_sql("create table t (v1 int, v2 int)")
+            .await
+            .unwrap();
+        frontend
+            .run_sql(
+                "create materialized view mv as select v1, count(v2) as cnt from t group by v1",
+            )
+            .await
+            .unwrap();
+    }
+
+    fn select(session: Arc<SessionImpl>, sql: &str) -> QueryMode {
+        let statement = Parser::parse_sql(sql).unwrap().remove(0);
+        let bound = Binder::new(
+            session.env().catalog_reader().read_guard(),
+            session.database().to_string(),
+        )
+        .bind(statement)
+        .unwrap();
+        let plan = Planner::new(OptimizerContext::new(session, Arc::from(sql)).into())
+            .plan(bound)
+            .unwrap()
+            .gen_optimized_logical_plan();
+        QueryModeSelector::select(plan).0
+    }
+
+    #[tokio::test]
+    async fn test_select_query_mode() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        create_table_and_mv(&frontend).await;
+        let session = frontend.session_ref();
+
+        assert_eq!(
+            select(session.clone(), "select * from mv where v1 = 1"),
+            QueryMode::Local
+        );
+        assert_eq!(
+            select(session.clone(), "select * from mv where v1 < 10"),
+            QueryMode::Local
+        );
+        assert_eq!(
+            select(session.clone(), "select * from t"),
+            QueryMode::Distributed
+        );
+        assert_eq!(
+            select(session.clone(), "select * from mv where cnt = 1"),
+            QueryMode::Distributed
+        );
+        assert_eq!(
+            select(session.clone(), "select count(*) from mv where v1 = 1"),
+            QueryMode::Distributed
+        );
+        assert_eq!(
+            select(
+                session,
+                "select * from mv where v1 < 10 order by cnt limit 1"
+            ),
+            QueryMode::Distributed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_query_mode_with_lookup_join() {
+        let frontend = LocalFrontend::new(Default::default()).await;
+        create_table_and_mv(&frontend).await;
+        let sql = "select * from mv as a join mv as b on a.v1 = b.v1 where a.v1 = 1";
+
+        let session = frontend.session_ref();
+        assert_eq!(select(session, sql), QueryMode::Distributed);
+
+        let session = frontend.session_ref();
+        session
+            .set_config("RW_BATCH_ENABLE_LOOKUP_JOIN", "true")
+            .unwrap();
+        assert_eq!(select(session, sql), QueryMode::Local);
+
+        // The left side of the lookup join is still a full scan.
+        let session = frontend.session_ref();
+        session
+            .set_config("RW_BATCH_ENABLE_LOOKUP_JOIN", "true")
+            .unwrap();
+        assert_eq!(
+            select(session, "select * from t join mv on t.v1 = mv.v1"),
+            QueryMode::Distributed
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::util::scan_range::{is_full_range, ScanRange};
+
+use crate::optimizer::plan_node::{BatchLimit, BatchSeqScan, BatchTopN, BatchValues, PlanNodeType};
+use crate::optimizer::PlanRef;
+
+/// Estimates the rows produced by a batch plan node from the estimated rows of its inputs, for
+/// `EXPLAIN ANALYZE`. Returns `None` if the rows can't be estimated.
+///
+/// There are no table statistics yet, so only the rows of values, point lookups on the primary key
+/// and nodes with a known upper bound are estimated. The estimations of the nodes that don't
+/// change the number of rows, or only filter them, are passed through from their inputs as an
+/// upper bound.
+pub fn estimate_row_count(plan: &PlanRef, input_row_counts: &[Option<usize>]) -> Option<usize> {
+    match plan.node_type() {
+        PlanNodeType::BatchValues => {
+            let values = plan.downcast_ref::<BatchValues>().unwrap();
+            Some(values.logical().rows().len())
+        }
+        PlanNodeType::BatchSeqScan => {
+            let scan = plan.downcast_ref::<BatchSeqScan>().unwrap();
+            let pk_len = scan.logical().table_desc().pk.len();
+            let is_point_lookup = |scan_range: &ScanRange| {
+                pk_len > 0
+                    && scan_range.eq_conds.len() == pk_len
+                    && is_full_range(&scan_range.range)
+            };
+            if !scan.scan_ranges().is_empty() && scan.scan_ranges().iter().all(is_point_lookup) {
+                Some(scan.scan_ranges().len())
+            } else {
+                None
+            }
+        }
+        PlanNodeType::BatchLimit => {
+            let limit = plan.downcast_ref::<BatchLimit>().unwrap().logical();
+            Some(bounded_by_limit(
+                input_row_counts[0],
+                limit.limit(),
+                limit.offset(),
+            ))
+        }
+        PlanNodeType::BatchTopN => {
+            let top_n = plan.downcast_ref::<BatchTopN>().unwrap().logical();
+            Some(bounded_by_limit(
+                input_row_counts[0],
+                top_n.limit(),
+                top_n.offset(),
+            ))
+        }
+        PlanNodeType::BatchSimpleAgg => Some(1),
+        PlanNodeType::BatchFilter
+        | PlanNodeType::BatchProject
+        | PlanNodeType::BatchExchange
+        | PlanNodeType::BatchSort
+        | PlanNodeType::BatchHashAgg
+        | PlanNodeType::BatchHashDistinct => input_row_counts[0],
+        _ => None,
+    }
+}
+
+fn bounded_by_limit(input: Option<usize>, limit: usize, offset: usize) -> usize {
+    match input {
+        Some(input) => input.saturating_sub(offset).min(limit),
+        None => limit,
+    }
+}
@@ -57,6 +57,8 @@ mod apply_join;
 mod distinct_agg;
 pub use apply_join::*;
 pub use distinct_agg::*;
+mod over_window_to_topn;
+pub use over_window_to_topn::*;
 
 #[macro_export]
 macro_rules! for_all_rules {
@@ -71,6 +73,7 @@ macro_rules! for_all_rules {
             ,{DistinctAggRule}
             ,{IndexDeltaJoinRule}
             ,{MergeMultiJoinRule}
+            ,{OverWindowToTopNRule}
             ,{ProjectEliminateRule}
             ,{ProjectJoinRule}
             ,{ProjectMergeRule}
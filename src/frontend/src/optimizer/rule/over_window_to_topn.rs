@@ -0,0 +1,104 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::types::ScalarImpl;
+
+use super::{BoxedRule, Rule};
+use crate::expr::ExprType;
+use crate::optimizer::plan_node::{
+    LogicalFilter, LogicalOverWindow, LogicalTopN, PlanTreeNodeUnary,
+};
+use crate::optimizer::PlanRef;
+
+/// Transform a filter on the row number of each partition into a grouped `LogicalTopN` below the
+/// `LogicalOverWindow`, so that only the rows that may pass the filter are numbered.
+///
+/// ```text
+/// LogicalFilter(rn <= k)
+///   LogicalOverWindow(row_number() over (partition by p order by o) as rn)
+///     input
+/// ```
+///
+/// becomes
+///
+/// ```text
+/// LogicalFilter(rn <= k)
+///   LogicalOverWindow(row_number() over (partition by p order by o) as rn)
+///     LogicalTopN(order: o, limit: k, group_key: p)
+///       input
+/// ```
+pub struct OverWindowToTopNRule {}
+impl Rule for OverWindowToTopNRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let filter: &LogicalFilter = plan.as_logical_filter()?;
+        let over_window_plan = filter.input();
+        let over_window: &LogicalOverWindow = over_window_plan.as_logical_over_window()?;
+        let input = over_window.input();
+        let window_function = over_window.window_function();
+        if let Some(topn) = input.as_logical_top_n() && topn.group_key() == window_function.partition_by {
+            // Already applied.
+            return None;
+        }
+
+        let row_number_idx = input.schema().len();
+        let limit = filter
+            .predicate()
+            .conjunctions
+            .iter()
+            .filter_map(|expr| {
+                let (input_ref, cmp, value) = match expr.as_comparison_const() {
+                    Some(comparison) => comparison,
+                    None => {
+                        let (input_ref, value) = expr.as_eq_const()?;
+                        (input_ref, ExprType::Equal, value)
+                    }
+                };
+                if input_ref.index() != row_number_idx {
+                    return None;
+                }
+                let value = match value.eval_row_const().ok()?? {
+                    ScalarImpl::Int16(v) => v as i64,
+                    ScalarImpl::Int32(v) => v as i64,
+                    ScalarImpl::Int64(v) => v,
+                    _ => return None,
+                };
+                match cmp {
+                    ExprType::LessThanOrEqual | ExprType::Equal => Some(value),
+                    ExprType::LessThan => Some(value - 1),
+                    _ => None,
+                }
+            })
+            .min()?;
+        // A filter that no row passes is left as is.
+        if limit < 1 {
+            return None;
+        }
+
+        let topn = LogicalTopN::with_group(
+            input,
+            limit as usize,
+            0,
+            window_function.order_by.clone(),
+            window_function.partition_by.clone(),
+        );
+        let over_window = over_window.clone_with_input(topn.into());
+        Some(filter.clone_with_input(over_window.into()).into())
+    }
+}
+
+impl OverWindowToTopNRule {
+    pub fn create() -> BoxedRule {
+        Box::new(OverWindowToTopNRule {})
+    }
+}
@@ -17,7 +17,7 @@ use risingwave_common::error::Result;
 
 use super::Planner;
 use crate::binder::BoundDelete;
-use crate::optimizer::plan_node::{LogicalDelete, LogicalFilter};
+use crate::optimizer::plan_node::{LogicalDelete, LogicalProject};
 use crate::optimizer::property::{Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 
@@ -26,8 +26,15 @@ impl Planner {
         let name = delete.table_source.name.clone();
         let source_id = delete.table_source.source_id;
         let scan = self.plan_base_table(delete.table)?;
+        let scan_len = scan.schema().len();
         let input = if let Some(expr) = delete.selection {
-            LogicalFilter::create_with_expr(scan, expr)
+            let input = self.plan_where(scan, expr)?;
+            // Scalar subqueries in the predicate append their columns, which are not deleted.
+            if input.schema().len() > scan_len {
+                LogicalProject::with_out_col_idx(input, 0..scan_len).into()
+            } else {
+                input
+            }
         } else {
             scan
         };
@@ -27,7 +27,6 @@ impl Planner {
         if !insert.cast_exprs.is_empty() {
             input = LogicalProject::create(input, insert.cast_exprs);
         }
-        // `columns` not used by backend yet.
         let plan: PlanRef = LogicalInsert::create(
             input,
             insert.table_source.name,
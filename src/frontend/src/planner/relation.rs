@@ -16,16 +16,16 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use risingwave_common::error::{ErrorCode, Result};
-use risingwave_common::types::ScalarImpl;
+use risingwave_common::types::{DataType, IntervalUnit, ScalarImpl};
 
 use crate::binder::{
     BoundBaseTable, BoundJoin, BoundSource, BoundSystemTable, BoundWindowTableFunction, Relation,
     WindowTableFunctionKind,
 };
-use crate::expr::{ExprImpl, ExprType, FunctionCall, InputRef, TableFunction};
+use crate::expr::{Expr, ExprImpl, ExprType, FunctionCall, InputRef, TableFunction};
 use crate::optimizer::plan_node::{
-    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSource,
-    LogicalTableFunction, PlanRef,
+    LogicalHopWindow, LogicalJoin, LogicalProject, LogicalScan, LogicalSessionWindow,
+    LogicalSource, LogicalTableFunction, PlanRef,
 };
 use crate::planner::Planner;
 
@@ -66,6 +66,7 @@ impl Planner {
                 .collect(),
             self.ctx(),
         )
+        .with_as_of_epoch(base_table.as_of_epoch)
         .into())
     }
 
@@ -97,6 +98,11 @@ impl Planner {
                 table_function.time_col,
                 table_function.args,
             ),
+            Session => self.plan_session_window(
+                table_function.input,
+                table_function.time_col,
+                table_function.args,
+            ),
         }
     }
 
@@ -120,6 +126,16 @@ impl Planner {
 
         match (args.next(), args.next()) {
             (Some(window_size @ ExprImpl::Literal(_)), None) => {
+                if let ExprImpl::Literal(literal) = &window_size
+                    && let Some(ScalarImpl::Interval(size)) = *literal.get_data()
+                    && size <= IntervalUnit::from_millis(0)
+                {
+                    return Err(ErrorCode::BindError(format!(
+                        "Invalid arguments for TUMBLE window function: window_size {} must be positive",
+                        size
+                    ))
+                    .into());
+                }
                 let mut exprs = Vec::with_capacity(cols.len() + 2);
                 for (idx, col) in cols.iter().enumerate() {
                     exprs.push(InputRef::new(idx, col.data_type().clone()).into());
@@ -165,6 +181,23 @@ impl Planner {
         let Some(ScalarImpl::Interval(window_size)) = *window_size.get_data() else {
             return Err(ErrorCode::BindError("Invalid arguments for HOP window function".to_string()).into());
         };
+        if window_slide <= IntervalUnit::from_millis(0) {
+            return Err(ErrorCode::BindError(format!(
+                "Invalid arguments for HOP window function: window_slide {} must be positive",
+                window_slide
+            ))
+            .into());
+        }
+        if window_size <= IntervalUnit::from_millis(0) {
+            return Err(ErrorCode::BindError(format!(
+                "Invalid arguments for HOP window function: window_size {} must be positive",
+                window_size
+            ))
+            .into());
+        }
+        if window_slide > window_size {
+            return Err(ErrorCode::BindError(format!("Invalid arguments for HOP window function: window_slide {} cannot be larger than window_size {}", window_slide, window_size)).into());
+        }
         if window_size.exact_div(&window_slide).is_none() {
             return Err(ErrorCode::BindError(format!("Invalid arguments for HOP window function: window_size {} cannot be divided by window_slide {}",window_size, window_slide)).into());
         }
@@ -175,4 +208,48 @@ impl Planner {
             window_size,
         ))
     }
+
+    /// Plans `SESSION(table, time_col, gap [, partition_col]...)`.
+    fn plan_session_window(
+        &mut self,
+        input: Relation,
+        time_col: InputRef,
+        args: Vec<ExprImpl>,
+    ) -> Result<PlanRef> {
+        let input = self.plan_relation(input)?;
+        let mut args = args.into_iter();
+        let Some(ExprImpl::Literal(gap)) = args.next() else {
+            return Err(ErrorCode::BindError("Invalid arguments for SESSION window function".to_string()).into());
+        };
+        let Some(ScalarImpl::Interval(gap)) = *gap.get_data() else {
+            return Err(ErrorCode::BindError("Invalid arguments for SESSION window function".to_string()).into());
+        };
+        if gap <= IntervalUnit::from_millis(0) {
+            return Err(ErrorCode::BindError(format!(
+                "Invalid arguments for SESSION window function: gap {} must be positive",
+                gap
+            ))
+            .into());
+        }
+        if time_col.return_type() != DataType::Timestamp {
+            return Err(ErrorCode::BindError(format!("Invalid arguments for SESSION window function: time_col must be of type timestamp, got {:?}", time_col.return_type())).into());
+        }
+        let partition_key = args
+            .map(|arg| match arg {
+                ExprImpl::InputRef(input_ref) if input_ref.index() < input.schema().len() => {
+                    Ok(input_ref.index())
+                }
+                _ => Err(ErrorCode::BindError(
+                    "Invalid arguments for SESSION window function: partition key should be input columns".to_string(),
+                )
+                .into()),
+            })
+            .collect::<Result<_>>()?;
+        Ok(LogicalSessionWindow::create(
+            input,
+            time_col,
+            gap,
+            partition_key,
+        ))
+    }
 }
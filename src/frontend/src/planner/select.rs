@@ -25,8 +25,8 @@ use crate::expr::{
 };
 pub use crate::optimizer::plan_node::LogicalFilter;
 use crate::optimizer::plan_node::{
-    LogicalAgg, LogicalApply, LogicalJoin, LogicalProject, LogicalProjectSet, LogicalValues,
-    PlanAggCall, PlanRef,
+    LogicalAgg, LogicalApply, LogicalJoin, LogicalOverWindow, LogicalProject, LogicalProjectSet,
+    LogicalValues, PlanAggCall, PlanRef,
 };
 use crate::planner::Planner;
 use crate::utils::Condition;
@@ -38,6 +38,7 @@ impl Planner {
             where_clause,
             mut select_items,
             group_by,
+            grouping_sets,
             mut having,
             distinct,
             ..
@@ -65,9 +66,9 @@ impl Planner {
         // Plan the SELECT clause.
         // TODO: select-agg, group-by, having can also contain subquery exprs.
         let has_agg_call = select_items.iter().any(|expr| expr.has_agg_call());
-        if !group_by.is_empty() || having.is_some() || has_agg_call {
+        if !group_by.is_empty() || !grouping_sets.is_empty() || having.is_some() || has_agg_call {
             (root, select_items, having) =
-                LogicalAgg::create(select_items, group_by, having, root)?;
+                LogicalAgg::create(select_items, group_by, grouping_sets, having, root)?;
         }
 
         if let Some(having) = having {
@@ -77,6 +78,9 @@ impl Planner {
         if select_items.iter().any(|e| e.has_subquery()) {
             (root, select_items) = self.substitute_subqueries(root, select_items)?;
         }
+        if select_items.iter().any(|e| e.has_window_function()) {
+            (root, select_items) = LogicalOverWindow::create(root, select_items)?;
+        }
         if select_items.iter().any(|e| e.has_table_function()) {
             root = LogicalProjectSet::create(root, select_items)
         } else {
@@ -117,7 +121,11 @@ impl Planner {
     ///
     /// For other subqueries, we plan it as `LeftOuter` [`LogicalApply`] (correlated) or
     /// [`LogicalJoin`] using [`Self::substitute_subqueries`].
-    fn plan_where(&mut self, mut input: PlanRef, where_clause: ExprImpl) -> Result<PlanRef> {
+    pub(super) fn plan_where(
+        &mut self,
+        mut input: PlanRef,
+        where_clause: ExprImpl,
+    ) -> Result<PlanRef> {
         if !where_clause.has_subquery() {
             return Ok(LogicalFilter::create_with_expr(input, where_clause));
         }
@@ -15,10 +15,9 @@
 use fixedbitset::FixedBitSet;
 use risingwave_common::error::Result;
 
-use super::select::LogicalFilter;
 use super::Planner;
 use crate::binder::BoundUpdate;
-use crate::optimizer::plan_node::LogicalUpdate;
+use crate::optimizer::plan_node::{LogicalProject, LogicalUpdate};
 use crate::optimizer::property::{Order, RequiredDist};
 use crate::optimizer::{PlanRef, PlanRoot};
 
@@ -27,8 +26,15 @@ impl Planner {
         let name = update.table_source.name.clone();
         let source_id = update.table_source.source_id;
         let scan = self.plan_relation(update.table)?;
+        let scan_len = scan.schema().len();
         let input = if let Some(expr) = update.selection {
-            LogicalFilter::create_with_expr(scan, expr)
+            let input = self.plan_where(scan, expr)?;
+            // Scalar subqueries in the predicate append their columns, which are not updated.
+            if input.schema().len() > scan_len {
+                LogicalProject::with_out_col_idx(input, 0..scan_len).into()
+            } else {
+                input
+            }
         } else {
             scan
         };
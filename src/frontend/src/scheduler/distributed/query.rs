@@ -495,6 +495,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(0, 0),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let worker2 = WorkerNode {
             id: 1,
@@ -505,6 +507,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(8, 1),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let worker3 = WorkerNode {
             id: 2,
@@ -515,6 +519,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(16, 2),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let workers = vec![worker1, worker2, worker3];
         let worker_node_manager = Arc::new(WorkerNodeManager::mock(workers));
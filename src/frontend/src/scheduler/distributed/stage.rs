@@ -401,14 +401,16 @@ impl StageRunner {
                 let exchange_sources = child_stage.all_exchange_sources_for(task_id);
 
                 match &execution_plan_node.node {
-                    NodeBody::Exchange(_exchange_node) => {
+                    NodeBody::Exchange(exchange_node) => {
                         PlanNodeProst {
                             children: vec![],
                             // TODO: Generate meaningful identify
                             identity: Uuid::new_v4().to_string(),
+                            plan_node_id: execution_plan_node.plan_node_id.0,
                             node_body: Some(NodeBody::Exchange(ExchangeNode {
                                 sources: exchange_sources,
                                 input_schema: execution_plan_node.schema.clone(),
+                                chunk_size: exchange_node.chunk_size,
                             })),
                         }
                     }
@@ -417,10 +419,14 @@ impl StageRunner {
                             children: vec![],
                             // TODO: Generate meaningful identify
                             identity: Uuid::new_v4().to_string(),
+                            plan_node_id: execution_plan_node.plan_node_id.0,
                             node_body: Some(NodeBody::MergeSortExchange(MergeSortExchangeNode {
                                 exchange: Some(ExchangeNode {
                                     sources: exchange_sources,
                                     input_schema: execution_plan_node.schema.clone(),
+                                    chunk_size: sort_merge_exchange_node
+                                        .get_exchange()
+                                        .map_or(0, |exchange_node| exchange_node.chunk_size),
                                 }),
                                 column_orders: sort_merge_exchange_node.column_orders.clone(),
                             })),
@@ -441,6 +447,7 @@ impl StageRunner {
                     children: vec![],
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(NodeBody::RowSeqScan(scan_node)),
                 }
             }
@@ -455,6 +462,7 @@ impl StageRunner {
                     children,
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(execution_plan_node.node.clone()),
                 }
             }
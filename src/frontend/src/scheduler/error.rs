@@ -26,6 +26,12 @@ pub enum SchedulerError {
     #[error("Rpc error: {0}")]
     RpcError(#[from] RpcError),
 
+    #[error("Snapshot of epoch {0} is no longer available, the earliest readable epoch is {1}")]
+    SnapshotExpired(u64, u64),
+
+    #[error("Snapshot of epoch {0} is not committed yet, the latest readable epoch is {1}")]
+    SnapshotNotCommitted(u64, u64),
+
     #[error("Feature is not yet implemented: {0}, {1}")]
     NotImplemented(String, TrackingIssue),
 
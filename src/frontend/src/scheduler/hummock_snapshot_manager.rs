@@ -21,6 +21,8 @@ use anyhow::anyhow;
 use log::error;
 use tokio::sync::mpsc::{channel, Sender};
 use tokio::sync::oneshot::{channel as once_channel, Sender as Callback};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
 
 use crate::meta_client::FrontendMetaClient;
 use crate::scheduler::plan_fragmenter::QueryId;
@@ -28,10 +30,13 @@ use crate::scheduler::{SchedulerError, SchedulerResult};
 
 const MAX_WAIT_EPOCH_REQUEST_NUM: usize = 4096;
 const UNPIN_INTERVAL_SECS: u64 = 10;
+/// The TTL of the pins of historical snapshots, which are renewed at half of it while being read.
+const HISTORICAL_SNAPSHOT_TTL: Duration = Duration::from_secs(60);
 
 /// Cache of hummock snapshot in meta.
 pub struct HummockSnapshotManager {
     sender: Sender<EpochOperation>,
+    meta_client: Arc<dyn FrontendMetaClient>,
 }
 pub type HummockSnapshotManagerRef = Arc<HummockSnapshotManager>;
 
@@ -52,8 +57,9 @@ impl HummockSnapshotManager {
         // do not use unbounded_channel because it may cause OOM when the RPC `get_epoch` blocks a
         // long time.
         let (sender, mut receiver) = channel(MAX_WAIT_EPOCH_REQUEST_NUM);
+        let core_meta_client = meta_client.clone();
         tokio::spawn(async move {
-            let mut manager = HummockSnapshotManagerCore::new(meta_client);
+            let mut manager = HummockSnapshotManagerCore::new(core_meta_client);
             let mut unpin_batches = vec![];
             let mut pin_batches = vec![];
             let mut unpin_interval =
@@ -101,7 +107,64 @@ impl HummockSnapshotManager {
                 }
             }
         });
-        Self { sender }
+        Self {
+            sender,
+            meta_client,
+        }
+    }
+
+    /// Checks that the snapshot of `epoch` can be read, i.e. it has been committed and not yet
+    /// garbage collected. Used by the queries reading a historical snapshot with `AS OF`.
+    pub async fn check_epoch_readable(&self, epoch: u64) -> SchedulerResult<()> {
+        let (safe_epoch, max_committed_epoch) = self.meta_client.get_readable_epoch_range().await?;
+        if epoch < safe_epoch {
+            return Err(SchedulerError::SnapshotExpired(epoch, safe_epoch));
+        }
+        if epoch > max_committed_epoch {
+            return Err(SchedulerError::SnapshotNotCommitted(
+                epoch,
+                max_committed_epoch,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Pins the historical snapshot of `epoch` for a query reading it with `AS OF`, so that it's
+    /// not garbage collected while being read. The snapshot stays pinned until the returned
+    /// [`PinnedEpoch`] is dropped.
+    pub async fn pin_epoch(&self, epoch: u64) -> SchedulerResult<PinnedEpoch> {
+        self.check_epoch_readable(epoch).await?;
+        // The snapshot may be garbage collected after the check, in which case meta refuses to
+        // pin it.
+        let name = format!("as-of-{}", Uuid::new_v4());
+        self.meta_client
+            .pin_named_snapshot(&name, epoch, HISTORICAL_SNAPSHOT_TTL)
+            .await?;
+
+        let meta_client = self.meta_client.clone();
+        let renew_name = name.clone();
+        let renew = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HISTORICAL_SNAPSHOT_TTL / 2);
+            // The first tick completes immediately.
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = meta_client
+                    .pin_named_snapshot(&renew_name, epoch, HISTORICAL_SNAPSHOT_TTL)
+                    .await
+                {
+                    error!(
+                        "Request meta to renew snapshot {} failed {:?}!",
+                        renew_name, e
+                    );
+                }
+            }
+        });
+        Ok(PinnedEpoch {
+            name,
+            renew,
+            meta_client: self.meta_client.clone(),
+        })
     }
 
     pub async fn get_epoch(&self, query_id: QueryId) -> SchedulerResult<u64> {
@@ -138,6 +201,28 @@ impl HummockSnapshotManager {
     }
 }
 
+/// A historical snapshot pinned by [`HummockSnapshotManager::pin_epoch`]. The pin is renewed in
+/// the background until this is dropped, which unpins the snapshot. If the frontend crashes, the
+/// pin expires by its TTL.
+pub struct PinnedEpoch {
+    name: String,
+    renew: JoinHandle<()>,
+    meta_client: Arc<dyn FrontendMetaClient>,
+}
+
+impl Drop for PinnedEpoch {
+    fn drop(&mut self) {
+        self.renew.abort();
+        let name = std::mem::take(&mut self.name);
+        let meta_client = self.meta_client.clone();
+        tokio::spawn(async move {
+            if let Err(e) = meta_client.unpin_named_snapshot(&name).await {
+                error!("Request meta to unpin snapshot {} failed {:?}!", name, e);
+            }
+        });
+    }
+}
+
 struct HummockSnapshotManagerCore {
     /// Record the query ids that pin each snapshot.
     /// Send an `unpin_snapshot` RPC when a snapshot is not pinned any more.
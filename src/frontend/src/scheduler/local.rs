@@ -18,7 +18,7 @@ use std::sync::Arc;
 
 use futures_async_stream::try_stream;
 use itertools::Itertools;
-use risingwave_batch::executor::ExecutorBuilder;
+use risingwave_batch::executor::{ExecutorActualsCollector, ExecutorBuilder};
 use risingwave_batch::task::TaskId;
 use risingwave_common::array::DataChunk;
 use risingwave_common::bail;
@@ -47,6 +47,8 @@ pub struct LocalQueryExecution {
     epoch: Option<u64>,
 
     auth_context: Arc<AuthContext>,
+    /// Collects the actuals of the executors run in the frontend, for `EXPLAIN ANALYZE`.
+    actuals_collector: Option<ExecutorActualsCollector>,
 }
 
 impl LocalQueryExecution {
@@ -62,9 +64,16 @@ impl LocalQueryExecution {
             front_env,
             epoch: None,
             auth_context,
+            actuals_collector: None,
         }
     }
 
+    #[must_use]
+    pub fn with_actuals_collector(mut self, collector: ExecutorActualsCollector) -> Self {
+        self.actuals_collector = Some(collector);
+        self
+    }
+
     #[try_stream(ok = DataChunk, error = RwError)]
     pub async fn run(mut self) {
         debug!(
@@ -91,7 +100,10 @@ impl LocalQueryExecution {
         self.epoch = Some(epoch);
         let plan_fragment = self.create_plan_fragment()?;
         let plan_node = plan_fragment.root.unwrap();
-        let executor = ExecutorBuilder::new(&plan_node, &task_id, context, epoch);
+        let mut executor = ExecutorBuilder::new(&plan_node, &task_id, context, epoch);
+        if let Some(collector) = self.actuals_collector.take() {
+            executor = executor.with_actuals_collector(collector);
+        }
         let executor = executor.build().await?;
 
         #[for_await]
@@ -213,6 +225,7 @@ impl LocalQueryExecution {
                             epoch: self.epoch.expect(
                                 "Local execution mode has not acquired the epoch when generating the plan.",
                             ),
+                            collect_actuals: self.actuals_collector.is_some(),
                             };
                         let exchange_source = ExchangeSource {
                             task_output_id: Some(TaskOutputId {
@@ -244,12 +257,13 @@ impl LocalQueryExecution {
                     epoch: self.epoch.expect(
                         "Local execution mode has not acquired the epoch when generating the plan.",
                     ),
+                    collect_actuals: self.actuals_collector.is_some(),
                     };
 
                     let workers = if second_stage.parallelism == 1 {
                         vec![self.front_env.worker_node_manager().next_random()?]
                     } else {
-                        self.front_env.worker_node_manager().list_worker_nodes()
+                        self.front_env.worker_node_manager().list_serving_worker_nodes()
                     };
                     *sources = workers
                         .iter()
@@ -277,6 +291,7 @@ impl LocalQueryExecution {
                     /// there is no children any more.
                     children: vec![],
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(node_body),
                 })
             }
@@ -297,6 +312,7 @@ impl LocalQueryExecution {
                     children: vec![],
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(node_body),
                 })
             }
@@ -317,6 +333,7 @@ impl LocalQueryExecution {
                 Ok(PlanNodeProst {
                     children: vec![left_child],
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(node_body),
                 })
             }
@@ -331,6 +348,7 @@ impl LocalQueryExecution {
                     children,
                     // TODO: Generate meaningful identify
                     identity: Uuid::new_v4().to_string(),
+                    plan_node_id: execution_plan_node.plan_node_id.0,
                     node_body: Some(execution_plan_node.node.clone()),
                 })
             }
@@ -27,7 +27,7 @@ pub use distributed::QueryManager;
 mod hummock_snapshot_manager;
 pub use hummock_snapshot_manager::*;
 mod plan_fragmenter;
-pub use plan_fragmenter::BatchPlanFragmenter;
+pub use plan_fragmenter::{BatchPlanFragmenter, Query};
 mod local;
 pub use local::*;
 mod error;
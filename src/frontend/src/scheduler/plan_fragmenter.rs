@@ -373,7 +373,7 @@ impl BatchPlanFragmenter {
                 1
             }
             _ => match &table_scan_info {
-                None => self.worker_node_manager.worker_node_count(),
+                None => self.worker_node_manager.serving_worker_node_count(),
                 Some(info) => info.partitions.as_ref().map(|m| m.len()).unwrap_or(1),
             },
         };
@@ -694,6 +694,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(0, 0),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let worker2 = WorkerNode {
             id: 1,
@@ -704,6 +706,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(8, 1),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let worker3 = WorkerNode {
             id: 2,
@@ -714,6 +718,8 @@ mod tests {
             }),
             state: risingwave_pb::common::worker_node::State::Running as i32,
             parallel_units: generate_parallel_units(16, 2),
+            labels: Default::default(),
+            role: Default::default(),
         };
         let workers = vec![worker1, worker2, worker3];
         let worker_node_manager = Arc::new(WorkerNodeManager::mock(workers));
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use risingwave_batch::executor::BatchMetrics;
 use risingwave_batch::task::{BatchTaskContext, TaskOutput, TaskOutputId};
 use risingwave_common::catalog::SysCatalogReaderRef;
+use risingwave_common::config::BatchConfig;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
 use risingwave_source::SourceManagerRef;
@@ -65,6 +66,11 @@ impl BatchTaskContext for FrontendBatchTaskContext {
     }
 
     fn stats(&self) -> Arc<BatchMetrics> {
-        todo!()
+        // The executors run in frontend are not monitored yet.
+        Arc::new(BatchMetrics::unused())
+    }
+
+    fn config(&self) -> BatchConfig {
+        BatchConfig::default()
     }
 }
@@ -14,11 +14,12 @@
 
 use std::sync::{Arc, RwLock};
 
+use itertools::Itertools;
 use rand::distributions::{Distribution as RandDistribution, Uniform};
 use risingwave_common::bail;
 use risingwave_common::types::ParallelUnitId;
 use risingwave_common::util::worker_util::get_pu_to_worker_mapping;
-use risingwave_pb::common::WorkerNode;
+use risingwave_pb::common::{WorkerNode, WorkerRole};
 
 use crate::scheduler::SchedulerResult;
 
@@ -64,9 +65,15 @@ impl WorkerNodeManager {
         *write_guard = nodes;
     }
 
-    /// Get a random worker node.
+    /// List the worker nodes to schedule batch tasks onto, i.e. the serving-only compute nodes, or
+    /// all worker nodes if there's none.
+    pub fn list_serving_worker_nodes(&self) -> Vec<WorkerNode> {
+        serving_worker_nodes(&self.worker_nodes.read().unwrap())
+    }
+
+    /// Get a random worker node, preferring the serving-only compute nodes.
     pub fn next_random(&self) -> SchedulerResult<WorkerNode> {
-        let current_nodes = self.worker_nodes.read().unwrap();
+        let current_nodes = self.list_serving_worker_nodes();
         let mut rng = rand::thread_rng();
         if current_nodes.is_empty() {
             tracing::error!("No worker node available.");
@@ -81,11 +88,29 @@ impl WorkerNodeManager {
         self.worker_nodes.read().unwrap().len()
     }
 
+    pub fn serving_worker_node_count(&self) -> usize {
+        self.list_serving_worker_nodes().len()
+    }
+
+    /// Get the worker nodes to scan the partitions owned by `parallel_unit_ids`. As the data can
+    /// be read from the shared storage, the partitions are spread over the serving-only compute
+    /// nodes if there are any. Otherwise each partition is scanned on the worker owning it.
     pub fn get_workers_by_parallel_unit_ids(
         &self,
         parallel_unit_ids: &[ParallelUnitId],
     ) -> SchedulerResult<Vec<WorkerNode>> {
-        let pu_to_worker = get_pu_to_worker_mapping(&self.worker_nodes.read().unwrap());
+        let current_nodes = self.worker_nodes.read().unwrap();
+        let serving_nodes = current_nodes
+            .iter()
+            .filter(|w| w.role() == WorkerRole::Serving)
+            .collect_vec();
+        if !serving_nodes.is_empty() {
+            return Ok((0..parallel_unit_ids.len())
+                .map(|i| serving_nodes[i % serving_nodes.len()].clone())
+                .collect());
+        }
+
+        let pu_to_worker = get_pu_to_worker_mapping(&current_nodes);
 
         let mut workers = Vec::with_capacity(parallel_unit_ids.len());
         for parallel_unit_id in parallel_unit_ids {
@@ -101,11 +126,25 @@ impl WorkerNodeManager {
     }
 }
 
+/// The serving-only compute nodes in `nodes`, or all of them if there's none.
+fn serving_worker_nodes(nodes: &[WorkerNode]) -> Vec<WorkerNode> {
+    let serving_nodes = nodes
+        .iter()
+        .filter(|w| w.role() == WorkerRole::Serving)
+        .cloned()
+        .collect_vec();
+    if serving_nodes.is_empty() {
+        nodes.to_vec()
+    } else {
+        serving_nodes
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use risingwave_common::util::addr::HostAddr;
-    use risingwave_pb::common::{worker_node, WorkerType};
+    use risingwave_pb::common::{worker_node, ParallelUnit, WorkerType};
 
     #[test]
     fn test_worker_node_manager() {
@@ -122,6 +161,8 @@ mod tests {
                 host: Some(HostAddr::try_from("127.0.0.1:1234").unwrap().to_protobuf()),
                 state: worker_node::State::Running as i32,
                 parallel_units: vec![],
+                labels: Default::default(),
+                role: WorkerRole::Both as i32,
             },
             WorkerNode {
                 id: 2,
@@ -129,6 +170,8 @@ mod tests {
                 host: Some(HostAddr::try_from("127.0.0.1:1235").unwrap().to_protobuf()),
                 state: worker_node::State::Running as i32,
                 parallel_units: vec![],
+                labels: Default::default(),
+                role: WorkerRole::Both as i32,
             },
         ];
         worker_nodes
@@ -144,4 +187,56 @@ mod tests {
             worker_nodes.as_slice()[1..].to_vec()
         );
     }
+
+    #[test]
+    fn test_serving_worker_nodes() {
+        use super::*;
+
+        let worker_node = |id, role: WorkerRole| WorkerNode {
+            id,
+            r#type: WorkerType::ComputeNode as i32,
+            host: Some(
+                HostAddr::try_from(format!("127.0.0.1:{}", 1234 + id).as_str())
+                    .unwrap()
+                    .to_protobuf(),
+            ),
+            state: worker_node::State::Running as i32,
+            parallel_units: vec![ParallelUnit {
+                id,
+                worker_node_id: id,
+            }],
+            labels: Default::default(),
+            role: role as i32,
+        };
+
+        // Fall back to all nodes if there's no serving node.
+        let manager = WorkerNodeManager::mock(vec![
+            worker_node(1, WorkerRole::Streaming),
+            worker_node(2, WorkerRole::Both),
+        ]);
+        assert_eq!(manager.serving_worker_node_count(), 2);
+        assert_eq!(
+            manager
+                .get_workers_by_parallel_unit_ids(&[2, 1])
+                .unwrap()
+                .iter()
+                .map(|w| w.id)
+                .collect_vec(),
+            vec![2, 1]
+        );
+
+        // Batch tasks only go to the serving nodes once there's one.
+        manager.add_worker_node(worker_node(3, WorkerRole::Serving));
+        assert_eq!(manager.serving_worker_node_count(), 1);
+        assert_eq!(manager.next_random().unwrap().id, 3);
+        assert_eq!(
+            manager
+                .get_workers_by_parallel_unit_ids(&[1, 2])
+                .unwrap()
+                .iter()
+                .map(|w| w.id)
+                .collect_vec(),
+            vec![3, 3]
+        );
+    }
 }
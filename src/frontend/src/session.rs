@@ -34,7 +34,7 @@ use risingwave_common::error::Result;
 use risingwave_common::session_config::ConfigMap;
 use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::observer_manager::ObserverManager;
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerRole, WorkerType};
 use risingwave_pb::user::auth_info::EncryptionType;
 use risingwave_rpc_client::{ComputeClientPool, MetaClient};
 use risingwave_sqlparser::ast::Statement;
@@ -78,6 +78,11 @@ pub struct OptimizerContext {
     pub optimizer_trace: Arc<Mutex<Vec<String>>>,
     /// Store correlated id
     pub next_correlated_id: AtomicU32,
+    /// Whether the batch plan is generated for local execution
+    pub local_execution: AtomicBool,
+    /// The retention of the streaming join states given in the `WITH` options of the statement,
+    /// which overrides the session config
+    pub streaming_join_state_retention: Mutex<Option<i32>>,
 }
 
 #[derive(Clone, Debug)]
@@ -121,6 +126,35 @@ impl OptimizerContextRef {
         self.inner.explain_trace.load(Ordering::Acquire)
     }
 
+    pub fn is_local_execution(&self) -> bool {
+        self.inner.local_execution.load(Ordering::Acquire)
+    }
+
+    pub fn set_local_execution(&self, local_execution: bool) {
+        self.inner
+            .local_execution
+            .store(local_execution, Ordering::Release);
+    }
+
+    /// Returns the retention of the streaming join states, from the `WITH` options of the statement
+    /// if given, or from the session config otherwise.
+    pub fn streaming_join_state_retention(&self) -> i32 {
+        self.inner
+            .streaming_join_state_retention
+            .lock()
+            .unwrap()
+            .unwrap_or_else(|| {
+                self.inner
+                    .session_ctx
+                    .config()
+                    .get_streaming_join_state_retention()
+            })
+    }
+
+    pub fn set_streaming_join_state_retention(&self, retention: i32) {
+        *self.inner.streaming_join_state_retention.lock().unwrap() = Some(retention);
+    }
+
     pub fn trace(&self, str: String) {
         let mut guard = self.inner.optimizer_trace.lock().unwrap();
         guard.push(str);
@@ -143,6 +177,8 @@ impl OptimizerContext {
             explain_trace: AtomicBool::new(false),
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
+            local_execution: AtomicBool::new(false),
+            streaming_join_state_retention: Mutex::new(None),
         }
     }
 
@@ -158,6 +194,8 @@ impl OptimizerContext {
             explain_trace: AtomicBool::new(false),
             optimizer_trace: Arc::new(Mutex::new(vec![])),
             next_correlated_id: AtomicU32::new(1),
+            local_execution: AtomicBool::new(false),
+            streaming_join_state_retention: Mutex::new(None),
         }
         .into()
     }
@@ -253,7 +291,13 @@ impl FrontendEnv {
             .unwrap();
         // Register in meta by calling `AddWorkerNode` RPC.
         meta_client
-            .register(WorkerType::Frontend, &frontend_address, 0)
+            .register(
+                WorkerType::Frontend,
+                &frontend_address,
+                0,
+                Default::default(),
+                WorkerRole::Unspecified,
+            )
             .await?;
 
         let (heartbeat_join_handle, heartbeat_shutdown_sender) = MetaClient::start_heartbeat_loop(
@@ -459,6 +503,10 @@ impl SessionImpl {
     pub fn set_config(&self, key: &str, value: &str) -> Result<()> {
         self.config_map.write().set(key, value)
     }
+
+    pub fn reset_config(&self, key: &str) -> Result<()> {
+        self.config_map.write().reset(key)
+    }
 }
 
 pub struct SessionManagerImpl {
@@ -363,6 +363,10 @@ impl StreamFragmenter {
                 append_only_top_n_node.table_id_h = state.gen_table_id();
             }
 
+            NodeBody::GroupTopN(group_top_n_node) => {
+                group_top_n_node.table_id = state.gen_table_id();
+            }
+
             NodeBody::DynamicFilter(dynamic_filter_node) => {
                 if let Some(left_table) = &mut dynamic_filter_node.left_table {
                     left_table.id = state.gen_table_id();
@@ -372,6 +376,24 @@ impl StreamFragmenter {
                 }
             }
 
+            NodeBody::Sort(sort_node) => {
+                if let Some(state_table) = &mut sort_node.state_table {
+                    state_table.id = state.gen_table_id();
+                }
+            }
+
+            NodeBody::SessionWindow(session_window_node) => {
+                if let Some(state_table) = &mut session_window_node.state_table {
+                    state_table.id = state.gen_table_id();
+                }
+            }
+
+            NodeBody::OverWindow(over_window_node) => {
+                if let Some(state_table) = &mut over_window_node.state_table {
+                    state_table.id = state.gen_table_id();
+                }
+            }
+
             _ => {}
         }
     }
@@ -435,6 +457,7 @@ mod tests {
             order_key: vec![ColumnOrder {
                 index: 0,
                 order_type: 2,
+                nulls_first: true,
             }],
             pk: vec![2],
             ..Default::default()
@@ -18,7 +18,7 @@ use risingwave_common::error::Result;
 use risingwave_common::try_match_expand;
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
 use risingwave_pb::catalog::Table;
-use risingwave_pb::plan_common::{ColumnOrder, Field as ProstField, OrderType as ProstOrderType};
+use risingwave_pb::plan_common::{Field as ProstField, OrderType as ProstOrderType};
 use risingwave_pb::stream_plan::lookup_node::ArrangementTableId;
 use risingwave_pb::stream_plan::stream_node::NodeBody;
 use risingwave_pb::stream_plan::{
@@ -128,10 +128,7 @@ impl StreamFragmenter {
                     .iter()
                     .map(|x| OrderPair::new(*x as usize, OrderType::Ascending)),
             )
-            .map(|x| ColumnOrder {
-                order_type: x.order_type.to_prost() as i32,
-                index: x.column_idx as u32,
-            })
+            .map(|x| x.to_protobuf())
             .collect();
 
         // Simply generate column id 0..schema_len
@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use parking_lot::RwLock;
 use pgwire::pg_response::PgResponse;
@@ -30,7 +31,8 @@ use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
     Table as ProstTable,
 };
-use risingwave_pb::common::ParallelUnitMapping;
+use risingwave_pb::common::{ParallelUnitMapping, WorkerNode, WorkerType};
+use risingwave_pb::hummock::HummockVersion;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_pb::user::update_user_request::UpdateField;
@@ -247,7 +249,7 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
-    async fn drop_materialized_view(&self, table_id: TableId) -> Result<()> {
+    async fn drop_materialized_view(&self, table_id: TableId, _cascade: bool) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_source_id(table_id.table_id);
         self.catalog
             .write()
@@ -255,6 +257,29 @@ impl CatalogWriter for MockCatalogWriter {
         Ok(())
     }
 
+    async fn rename_materialized_view(
+        &self,
+        table_id: TableId,
+        new_name: &str,
+    ) -> Result<Vec<String>> {
+        let schema_id = self.table_id_to_schema_id.read()[&table_id.table_id];
+        let database_id = self.get_database_id_by_schema(schema_id);
+        let mut table = {
+            let catalog = self.catalog.read();
+            catalog
+                .get_all_database_names()
+                .iter()
+                .flat_map(|db_name| catalog.iter_schemas(db_name).unwrap())
+                .find(|schema| schema.id() == schema_id)
+                .and_then(|schema| schema.get_table_by_id(&table_id))
+                .unwrap()
+                .to_prost(schema_id, database_id)
+        };
+        table.name = new_name.to_string();
+        self.catalog.write().update_table(&table);
+        Ok(vec![])
+    }
+
     async fn drop_source(&self, source_id: u32) -> Result<()> {
         let (database_id, schema_id) = self.drop_table_or_source_id(source_id);
         self.catalog
@@ -530,6 +555,10 @@ impl FrontendMetaClient for MockFrontendMetaClient {
         Ok(0)
     }
 
+    async fn get_readable_epoch_range(&self) -> RpcResult<(u64, u64)> {
+        Ok((0, u64::MAX))
+    }
+
     async fn flush(&self) -> RpcResult<()> {
         Ok(())
     }
@@ -548,6 +577,22 @@ impl FrontendMetaClient for MockFrontendMetaClient {
     async fn unpin_snapshot_before(&self, _epoch: u64) -> RpcResult<()> {
         Ok(())
     }
+
+    async fn pin_named_snapshot(&self, _name: &str, _epoch: u64, _ttl: Duration) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn unpin_named_snapshot(&self, _name: &str) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn list_all_nodes(&self, _worker_type: WorkerType) -> RpcResult<Vec<WorkerNode>> {
+        Ok(vec![])
+    }
+
+    async fn get_current_version(&self) -> RpcResult<HummockVersion> {
+        Ok(HummockVersion::default())
+    }
 }
 pub static PROTO_FILE_DATA: &str = r#"
     syntax = "proto3";
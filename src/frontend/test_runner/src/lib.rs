@@ -23,6 +23,7 @@ use std::sync::Arc;
 use anyhow::{anyhow, Result};
 pub use resolve_id::*;
 use risingwave_frontend::binder::Binder;
+use risingwave_frontend::handler::util::{force_local_mode, gen_batch_plan_by_mode};
 use risingwave_frontend::handler::{
     create_index, create_mv, create_source, create_table, drop_table,
 };
@@ -67,6 +68,9 @@ pub struct TestCase {
     /// Batch plan for local execution `.gen_batch_local_plan()`
     pub batch_local_plan: Option<String>,
 
+    /// The mode the query is executed in and the reason, as shown by `EXPLAIN`
+    pub query_mode: Option<String>,
+
     /// Create MV plan `.gen_create_mv_plan()`
     pub stream_plan: Option<String>,
 
@@ -124,6 +128,9 @@ pub struct TestCaseResult {
     /// Batch plan for local execution `.gen_batch_local_plan()`
     pub batch_local_plan: Option<String>,
 
+    /// The mode the query is executed in and the reason, as shown by `EXPLAIN`
+    pub query_mode: Option<String>,
+
     /// Create MV plan `.gen_create_mv_plan()`
     pub stream_plan: Option<String>,
 
@@ -168,6 +175,7 @@ impl TestCaseResult {
             optimized_logical_plan: self.optimized_logical_plan,
             batch_plan: self.batch_plan,
             batch_local_plan: self.batch_local_plan,
+            query_mode: self.query_mode,
             stream_plan: self.stream_plan,
             stream_plan_proto: self.stream_plan_proto,
             batch_plan_proto: self.batch_plan_proto,
@@ -302,13 +310,20 @@ impl TestCase {
                 Statement::CreateView {
                     materialized: true,
                     or_replace: false,
+                    if_not_exists,
                     name,
                     query,
                     with_options,
                     ..
                 } => {
-                    create_mv::handle_create_mv(context, name, query, WithProperties(with_options))
-                        .await?;
+                    create_mv::handle_create_mv(
+                        context,
+                        name,
+                        if_not_exists,
+                        query,
+                        WithProperties(with_options),
+                    )
+                    .await?;
                 }
                 Statement::Drop(drop_statement) => {
                     drop_table::handle_drop_table(context, drop_statement.object_name).await?;
@@ -341,6 +356,7 @@ impl TestCase {
             }
         };
 
+        let force_local = force_local_mode(&bound);
         let mut planner = Planner::new(context.clone());
 
         let logical_plan = match planner.plan(bound) {
@@ -402,6 +418,18 @@ impl TestCase {
             }
         }
 
+        if self.query_mode.is_some() {
+            let (_, mode, reason) =
+                match gen_batch_plan_by_mode(&session, &logical_plan, force_local) {
+                    Ok(ret) => ret,
+                    Err(err) => {
+                        ret.batch_error = Some(err.to_string());
+                        return Ok(ret);
+                    }
+                };
+            ret.query_mode = Some(format!("Query Mode: {} ({})", mode, reason));
+        }
+
         if self.stream_plan.is_some() || self.stream_plan_proto.is_some() {
             let q = if let Statement::Query(q) = stmt {
                 q.as_ref().clone()
@@ -465,6 +493,7 @@ fn check_result(expected: &TestCase, actual: &TestCaseResult) -> Result<()> {
         &expected.batch_local_plan,
         &actual.batch_local_plan,
     )?;
+    check_option_plan_eq("query_mode", &expected.query_mode, &actual.query_mode)?;
     check_option_plan_eq("stream_plan", &expected.stream_plan, &actual.stream_plan)?;
     check_option_plan_eq(
         "stream_plan_proto",
@@ -161,6 +161,10 @@ pub struct CommandContext<S: MetaStore> {
     pub curr_epoch: Epoch,
 
     pub command: Command,
+
+    /// Whether the data written in `prev_epoch` and the previous non-checkpoint epochs should be
+    /// synced and committed after this barrier is collected.
+    pub checkpoint: bool,
 }
 
 impl<S: MetaStore> CommandContext<S> {
@@ -171,6 +175,7 @@ impl<S: MetaStore> CommandContext<S> {
         prev_epoch: Epoch,
         curr_epoch: Epoch,
         command: Command,
+        checkpoint: bool,
     ) -> Self {
         Self {
             fragment_manager,
@@ -179,6 +184,7 @@ impl<S: MetaStore> CommandContext<S> {
             prev_epoch,
             curr_epoch,
             command,
+            checkpoint,
         }
     }
 }
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::future::Future;
 use std::iter::once;
 use std::mem::take;
 use std::sync::Arc;
@@ -26,32 +27,34 @@ use prometheus::HistogramTimer;
 use risingwave_common::catalog::TableId;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::epoch::{Epoch, INVALID_EPOCH};
-use risingwave_hummock_sdk::LocalSstableInfo;
-use risingwave_pb::common::worker_node::State::Running;
-use risingwave_pb::common::WorkerType;
+use risingwave_hummock_sdk::{HummockEpoch, LocalSstableInfo};
+use risingwave_pb::common::worker_node::State;
+use risingwave_pb::common::{HostAddress, WorkerNode, WorkerType};
 use risingwave_pb::meta::table_fragments::ActorState;
 use risingwave_pb::stream_plan::Barrier;
 use risingwave_pb::stream_service::{
     BarrierCompleteRequest, BarrierCompleteResponse, InjectBarrierRequest,
 };
+use risingwave_rpc_client::StreamClient;
 use smallvec::SmallVec;
-use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot::{Receiver, Sender};
-use tokio::sync::{oneshot, watch, RwLock};
+use tokio::sync::{mpsc, oneshot, watch, RwLock};
 use tokio::task::JoinHandle;
+use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tonic::{Code, Status};
 use uuid::Uuid;
 
 use self::command::CommandContext;
 pub use self::command::{Command, Reschedule};
 use self::info::BarrierActorInfo;
 use self::notifier::Notifier;
-use crate::barrier::progress::CreateMviewProgressTracker;
+use crate::barrier::progress::{CreateMviewProgressTracker, CreatingMview};
 use crate::barrier::BarrierEpochState::{Completed, InFlight};
 use crate::cluster::{ClusterManagerRef, META_NODE_ID};
 use crate::hummock::HummockManagerRef;
 use crate::manager::{CatalogManagerRef, MetaSrvEnv};
 use crate::model::{ActorId, BarrierManagerState};
-use crate::rpc::metrics::MetaMetrics;
+use crate::rpc::metrics::{histogram_quantile, MetaMetrics};
 use crate::storage::MetaStore;
 use crate::stream::FragmentManagerRef;
 
@@ -63,6 +66,9 @@ mod recovery;
 
 type Scheduled = (Command, SmallVec<[Notifier; 1]>);
 
+/// The result of collecting the barrier with the `prev_epoch` from all compute nodes.
+type BarrierCompletion = (u64, Result<Vec<BarrierCompleteResponse>>);
+
 /// A buffer or queue for scheduling barriers.
 ///
 /// We manually implement one here instead of using channels since we may need to update the front
@@ -186,12 +192,24 @@ pub struct GlobalBarrierManager<S: MetaStore> {
     /// Enable migrate expired actors to newly joined node
     enable_migrate: bool,
 
+    /// The notifiers of the pending drains of workers. The actors on the draining workers are
+    /// migrated as soon as there's no barrier in flight, and then the notifiers are sent the
+    /// result. Barriers are still injected meanwhile.
+    drain_requests: parking_lot::Mutex<Vec<Sender<Result<()>>>>,
+
+    /// The epoch to recover from, if the last recovery failed. No barrier is injected until a
+    /// retry of the recovery succeeds.
+    pending_recovery: parking_lot::Mutex<Option<Epoch>>,
+
     /// The queue of scheduled barriers.
     scheduled_barriers: ScheduledBarriers,
 
     /// The max barrier nums in flight
     in_flight_barrier_nums: usize,
 
+    /// The number of barriers between two checkpoints.
+    checkpoint_frequency: usize,
+
     cluster_manager: ClusterManagerRef<S>,
 
     catalog_manager: CatalogManagerRef<S>,
@@ -203,6 +221,10 @@ pub struct GlobalBarrierManager<S: MetaStore> {
     metrics: Arc<MetaMetrics>,
 
     env: MetaSrvEnv<S>,
+
+    /// The progress of the creating mviews, from 0 to 1, published by the barrier loop after
+    /// updating the [`CreateMviewProgressTracker`].
+    create_mview_progress: parking_lot::Mutex<HashMap<TableId, f64>>,
 }
 
 /// Controls the concurrent execution of commands.
@@ -370,6 +392,16 @@ where
         complete_nodes
     }
 
+    /// Whether there's some in-flight barrier creating mviews.
+    fn is_creating_mview(&self) -> bool {
+        !self.creating_tables.is_empty()
+    }
+
+    /// Whether there's no barrier in the queue.
+    fn is_empty(&self) -> bool {
+        self.command_ctx_queue.is_empty()
+    }
+
     /// Remove all nodes from queue and return them.
     fn fail(&mut self) -> Vec<EpochNode<S>> {
         let complete_nodes = self.command_ctx_queue.drain(..).collect_vec();
@@ -438,6 +470,21 @@ pub struct EpochNode<S: MetaStore> {
     notifiers: SmallVec<[Notifier; 1]>,
 }
 
+/// A snapshot of the barrier statistics, see [`GlobalBarrierManager::barrier_stats`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarrierStats {
+    /// The number of barriers that are injected but not collected yet.
+    pub in_flight_barrier_nums: usize,
+    /// The number of barriers that are in-flight or collected but waiting to be committed.
+    pub all_barrier_nums: usize,
+    /// The max committed epoch in hummock.
+    pub max_committed_epoch: u64,
+    /// Percentiles of the barrier latency, in seconds.
+    pub latency_p50: f64,
+    pub latency_p90: f64,
+    pub latency_p99: f64,
+}
+
 /// The state of barrier.
 #[derive(PartialEq)]
 enum BarrierEpochState {
@@ -448,10 +495,105 @@ enum BarrierEpochState {
     Completed(Result<Vec<BarrierCompleteResponse>>),
 }
 
+/// Base of the exponential backoff in milliseconds when retrying the barrier RPCs.
+const BARRIER_RPC_RETRY_BASE_INTERVAL: u64 = 10;
+
+/// Calls the RPC `f` with the stream client of `node`, and retries with backoff on the
+/// transport-level errors as long as the total backoff is within `max_retry_time`, so that a
+/// momentary network failure doesn't fail the barrier. The logical errors are not retried.
+///
+/// A retried request may have already been handled by the worker, so `f` must be idempotent. For
+/// example, the compute node ignores a barrier injected again with an epoch it has seen.
+async fn call_with_retry<S, F, Fut, T>(
+    env: &MetaSrvEnv<S>,
+    node: &WorkerNode,
+    max_retry_time: Duration,
+    f: F,
+) -> Result<T>
+where
+    S: MetaStore,
+    F: Fn(StreamClient) -> Fut,
+    Fut: Future<Output = std::result::Result<tonic::Response<T>, Status>>,
+{
+    let mut total_backoff = Duration::ZERO;
+    let retry_strategy = ExponentialBackoff::from_millis(BARRIER_RPC_RETRY_BASE_INTERVAL)
+        .map(jitter)
+        .take_while(move |backoff| {
+            total_backoff += *backoff;
+            total_backoff < max_retry_time
+        });
+    // The error is paired with whether it's retryable.
+    tokio_retry::RetryIf::spawn(
+        retry_strategy,
+        || async {
+            let client = env
+                .stream_client_pool()
+                .get(node)
+                .await
+                .map_err(|err| (true, RwError::from(err)))?;
+            let pool = env.stream_client_pool();
+            match f(client).await {
+                Ok(response) => {
+                    pool.report_success(node.id);
+                    Ok(response.into_inner())
+                }
+                Err(status) => {
+                    let retryable = status.code() == Code::Unavailable;
+                    if retryable {
+                        pool.report_failure(node.id).await;
+                    }
+                    Err((retryable, RwError::from(status)))
+                }
+            }
+        },
+        |(retryable, err): &(bool, RwError)| {
+            if *retryable {
+                tracing::warn!(
+                    "barrier RPC to worker {} failed, retrying: {}",
+                    node.id,
+                    err
+                );
+            }
+            *retryable
+        },
+    )
+    .await
+    .map_err(|(_, err)| err)
+}
+
+/// Groups the SSTs synced by a checkpoint barrier by the epoch they are written in, since a
+/// checkpoint barrier also syncs the data of the non-checkpoint epochs before it.
+///
+/// SSTs reported more than once are deduplicated by `(compaction_group_id, sst_id)`. Within an
+/// epoch, the SSTs of the same compaction group are put together, and the sort is stable so that
+/// they keep the order they are generated.
+fn group_synced_ssts_by_epoch(
+    resps: &[BarrierCompleteResponse],
+) -> BTreeMap<HummockEpoch, Vec<LocalSstableInfo>> {
+    let mut reported_ssts = HashSet::new();
+    let mut ssts_by_epoch: BTreeMap<HummockEpoch, Vec<LocalSstableInfo>> = BTreeMap::new();
+    for grouped in resps.iter().flat_map(|resp| resp.synced_sstables.iter()) {
+        let sst = grouped.sst.clone().expect("field not None");
+        if reported_ssts.insert((grouped.compaction_group_id, sst.id)) {
+            ssts_by_epoch
+                .entry(grouped.epoch)
+                .or_default()
+                .push((grouped.compaction_group_id, sst));
+        }
+    }
+    for ssts in ssts_by_epoch.values_mut() {
+        ssts.sort_by_key(|(compaction_group_id, _)| *compaction_group_id);
+    }
+    ssts_by_epoch
+}
+
 impl<S> GlobalBarrierManager<S>
 where
     S: MetaStore,
 {
+    /// The interval to retry a failed recovery.
+    const RECOVERY_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
     /// Create a new [`crate::barrier::GlobalBarrierManager`].
     pub fn new(
         env: MetaSrvEnv<S>,
@@ -465,17 +607,21 @@ where
         let enable_migrate = env.opts.enable_migrate;
         let interval = env.opts.checkpoint_interval;
         let in_flight_barrier_nums = env.opts.in_flight_barrier_nums;
+        let checkpoint_frequency = env.opts.checkpoint_frequency;
         tracing::info!(
-            "Starting barrier manager with: interval={:?}, enable_recovery={} , in_flight_barrier_nums={}",
+            "Starting barrier manager with: interval={:?}, enable_recovery={} , in_flight_barrier_nums={}, checkpoint_frequency={}",
             interval,
             enable_recovery,
             in_flight_barrier_nums,
+            checkpoint_frequency,
         );
 
         Self {
             interval,
             enable_recovery,
             enable_migrate,
+            drain_requests: Default::default(),
+            pending_recovery: Default::default(),
             cluster_manager,
             catalog_manager,
             fragment_manager,
@@ -484,6 +630,8 @@ where
             metrics,
             env,
             in_flight_barrier_nums,
+            checkpoint_frequency,
+            create_mview_progress: Default::default(),
         }
     }
 
@@ -513,30 +661,35 @@ where
     async fn run(&self, mut shutdown_rx: Receiver<()>) {
         let mut tracker = CreateMviewProgressTracker::default();
         let mut state = BarrierManagerState::create(self.env.meta_store()).await;
-        if self.enable_recovery {
-            // handle init, here we simply trigger a recovery process to achieve the consistency. We
-            // may need to avoid this when we have more state persisted in meta store.
+        if self.enable_recovery && self.can_resume(&state).await {
+            // The first barrier sent from the persisted epoch checks that the compute nodes are
+            // still consistent with the meta store. If it fails, a recovery is triggered as usual.
+            tracing::info!(
+                "resume barriers from epoch {} without recovery",
+                state.in_flight_prev_epoch.0
+            );
+        } else if self.enable_recovery {
+            // handle init, here we simply trigger a recovery process to achieve the consistency.
             let new_epoch = state.in_flight_prev_epoch.next();
             assert!(new_epoch > state.in_flight_prev_epoch);
             state.in_flight_prev_epoch = new_epoch;
 
-            let (new_epoch, actors_to_track, create_mview_progress) =
-                self.recovery(state.in_flight_prev_epoch).await;
-            tracker.add(new_epoch, actors_to_track, vec![]);
-            for progress in &create_mview_progress {
-                tracker.update(progress);
-            }
-            state.in_flight_prev_epoch = new_epoch;
-            state
-                .update_inflight_prev_epoch(self.env.meta_store())
-                .await
-                .unwrap();
+            let prev_epoch = state.in_flight_prev_epoch;
+            self.recovery_and_track(prev_epoch, &mut state, &mut tracker)
+                .await;
+            self.publish_create_mview_progress(&tracker);
         }
         let mut min_interval = tokio::time::interval(self.interval);
         min_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
         let mut barrier_timer: Option<HistogramTimer> = None;
-        let (barrier_complete_tx, mut barrier_complete_rx) = tokio::sync::mpsc::unbounded_channel();
+        // There's one completion for each in-flight barrier, whose number is limited by
+        // `in_flight_barrier_nums`. Completions are always sent from spawned tasks, so that a full
+        // channel never blocks this loop.
+        let (barrier_complete_tx, mut barrier_complete_rx) =
+            mpsc::channel(self.in_flight_barrier_nums.max(1));
         let mut checkpoint_control = CheckpointControl::new();
+        // The number of barriers injected since the last checkpoint.
+        let mut uncheckpointed_barrier_nums = 0;
         loop {
             tokio::select! {
                 biased;
@@ -546,11 +699,7 @@ where
                     return;
                 }
                 result = barrier_complete_rx.recv() => {
-                    let (in_flight_nums, all_nums) = checkpoint_control.get_barrier_len();
-                    self.metrics
-                        .in_flight_barrier_nums
-                        .set(in_flight_nums as i64);
-                    self.metrics.all_barrier_nums.set(all_nums as i64);
+                    self.update_barrier_nums_metrics(&checkpoint_control);
 
                     let (prev_epoch, result) = result.unwrap();
                     self.barrier_complete_and_commit(
@@ -563,10 +712,33 @@ where
                     .await;
                     continue;
                 }
+                // Migrate the actors on draining workers as soon as all in-flight barriers are
+                // done. The sources have been paused by the drain, and are resumed by the next
+                // barrier whether the migration succeeds or not.
+                _ = futures::future::ready(()), if self.is_drain_requested() && checkpoint_control.is_empty() => {
+                    let notifiers = take(&mut *self.drain_requests.lock());
+                    let result = self.migrate_draining_actors(&mut state, &mut tracker).await;
+                    self.scheduled_barriers
+                        .push(once((Command::resume(), Default::default())))
+                        .await;
+                    for notifier in notifiers {
+                        // The drain may have timed out and stopped waiting.
+                        let _ = notifier.send(result.clone());
+                    }
+                    continue;
+                }
+                // Retry the failed recovery after a while.
+                _ = tokio::time::sleep(Self::RECOVERY_RETRY_INTERVAL), if self.pending_recovery.lock().is_some() => {
+                    let prev_epoch = self.pending_recovery.lock().unwrap();
+                    self.recovery_and_track(prev_epoch, &mut state, &mut tracker)
+                        .await;
+                    self.publish_create_mview_progress(&tracker);
+                    continue;
+                }
                 // there's barrier scheduled.
-                _ = self.scheduled_barriers.wait_one(), if checkpoint_control.can_inject_barrier(self.in_flight_barrier_nums) => {}
+                _ = self.scheduled_barriers.wait_one(), if self.can_inject_barrier(&checkpoint_control) => {}
                 // Wait for the minimal interval,
-                _ = min_interval.tick(), if checkpoint_control.can_inject_barrier(self.in_flight_barrier_nums) => {}
+                _ = min_interval.tick(), if self.can_inject_barrier(&checkpoint_control) => {}
             }
 
             if let Some(barrier_timer) = barrier_timer {
@@ -575,7 +747,7 @@ where
             barrier_timer = Some(self.metrics.barrier_send_latency.start_timer());
             let (command, notifiers) = self.scheduled_barriers.pop_or_default().await;
             let info = self
-                .resolve_actor_info(&mut checkpoint_control, &command)
+                .resolve_actor_info(&mut checkpoint_control, &command, true)
                 .await;
             // When there's no actors exist in the cluster, we don't need to send the barrier. This
             // is an advance optimization. Besides if another barrier comes immediately,
@@ -588,13 +760,33 @@ where
             }
             let prev_epoch = state.in_flight_prev_epoch;
             let new_epoch = prev_epoch.next();
-            state.in_flight_prev_epoch = new_epoch;
             assert!(
                 new_epoch > prev_epoch,
                 "new{:?},prev{:?}",
                 new_epoch,
                 prev_epoch
             );
+
+            // Besides every `checkpoint_frequency` barriers, a barrier must be a checkpoint if it
+            // carries a command, someone is waiting for it (e.g., flush), or some mview is being
+            // created, so that the effects are committed once the barrier is collected.
+            let checkpoint = uncheckpointed_barrier_nums + 1 >= self.checkpoint_frequency
+                || !matches!(command, Command::Plain(_))
+                || !notifiers.is_empty()
+                || checkpoint_control.is_creating_mview()
+                || !tracker.is_empty();
+            if checkpoint {
+                uncheckpointed_barrier_nums = 0;
+            } else {
+                uncheckpointed_barrier_nums += 1;
+            }
+
+            state.in_flight_prev_epoch = new_epoch;
+            state.checkpoint_prev_epoch = if checkpoint {
+                prev_epoch
+            } else {
+                INVALID_EPOCH.into()
+            };
             state
                 .update_inflight_prev_epoch(self.env.meta_store())
                 .await
@@ -607,11 +799,14 @@ where
                 prev_epoch,
                 new_epoch,
                 command,
+                checkpoint,
             ));
             let mut notifiers = notifiers;
             notifiers.iter_mut().for_each(Notifier::notify_to_send);
             let timer = self.metrics.barrier_latency.start_timer();
             checkpoint_control.inject(command_ctx.clone(), notifiers, timer);
+            self.update_barrier_nums_metrics(&checkpoint_control);
+            self.update_stream_client_pool_metrics();
 
             self.inject_and_send_err(command_ctx, barrier_complete_tx.clone())
                 .await;
@@ -622,15 +817,19 @@ where
     async fn inject_and_send_err(
         &self,
         command_context: Arc<CommandContext<S>>,
-        barrier_complete_tx: UnboundedSender<(u64, Result<Vec<BarrierCompleteResponse>>)>,
+        barrier_complete_tx: mpsc::Sender<BarrierCompletion>,
     ) {
         let result = self
             .inject_barrier(command_context.clone(), barrier_complete_tx.clone())
             .await;
         if let Err(e) = result {
-            barrier_complete_tx
-                .send((command_context.prev_epoch.0, Err(e)))
-                .unwrap();
+            let prev_epoch = command_context.prev_epoch.0;
+            tokio::spawn(async move {
+                barrier_complete_tx
+                    .send((prev_epoch, Err(e)))
+                    .await
+                    .unwrap();
+            });
         }
     }
 
@@ -639,7 +838,7 @@ where
     async fn inject_barrier(
         &self,
         command_context: Arc<CommandContext<S>>,
-        barrier_complete_tx: UnboundedSender<(u64, Result<Vec<BarrierCompleteResponse>>)>,
+        barrier_complete_tx: mpsc::Sender<BarrierCompletion>,
     ) -> Result<()> {
         fail_point!("inject_barrier_err", |_| Err(RwError::from(
             ErrorCode::InternalError("inject_barrier_err".to_string(),)
@@ -668,34 +867,32 @@ where
                     // TODO(chi): add distributed tracing
                     span: vec![],
                 };
-                async move {
-                    let mut client = self.env.stream_client_pool().get(node).await?;
-
-                    let request = InjectBarrierRequest {
-                        request_id,
-                        barrier: Some(barrier),
-                        actor_ids_to_send,
-                        actor_ids_to_collect,
-                    };
-                    tracing::trace!(
-                        target: "events::meta::barrier::inject_barrier",
-                        "inject barrier request: {:?}", request
-                    );
+                let request = InjectBarrierRequest {
+                    request_id,
+                    barrier: Some(barrier),
+                    actor_ids_to_send,
+                    actor_ids_to_collect,
+                };
+                tracing::trace!(
+                    target: "events::meta::barrier::inject_barrier",
+                    "inject barrier request: {:?}", request
+                );
 
-                    // This RPC returns only if this worker node has injected this barrier.
-                    client
-                        .inject_barrier(request)
-                        .await
-                        .map(tonic::Response::<_>::into_inner)
-                        .map_err(RwError::from)
-                }
+                // This RPC returns only if this worker node has injected this barrier. Retrying it
+                // is safe since the worker ignores a barrier injected again.
+                call_with_retry(&self.env, node, self.interval, move |mut client| {
+                    let request = request.clone();
+                    async move { client.inject_barrier(request).await }
+                })
                 .into()
             }
         });
         try_join_all(inject_futures).await?;
         let env = self.env.clone();
+        let max_retry_time = self.interval;
         tokio::spawn(async move {
             let prev_epoch = command_context.prev_epoch.0;
+            let checkpoint = command_context.checkpoint;
             let collect_futures = info.node_map.iter().filter_map(|(node_id, node)| {
                 if !*node_need_collect.get(node_id).unwrap() {
                     // No need to send or collect barrier for this node.
@@ -703,30 +900,33 @@ where
                 } else {
                     let request_id = Uuid::new_v4().to_string();
                     let env = env.clone();
-                    async move {
-                        let mut client = env.stream_client_pool().get(node).await?;
-                        let request = BarrierCompleteRequest {
-                            request_id,
-                            prev_epoch,
-                        };
-                        tracing::trace!(
-                            target: "events::meta::barrier::barrier_complete",
-                            "barrier complete request: {:?}", request
-                        );
+                    let request = BarrierCompleteRequest {
+                        request_id,
+                        prev_epoch,
+                        checkpoint,
+                    };
+                    tracing::trace!(
+                        target: "events::meta::barrier::barrier_complete",
+                        "barrier complete request: {:?}", request
+                    );
 
+                    async move {
                         // This RPC returns only if this worker node has collected this barrier.
-                        client
-                            .barrier_complete(request)
-                            .await
-                            .map(tonic::Response::<_>::into_inner)
-                            .map_err(RwError::from)
+                        call_with_retry(&env, node, max_retry_time, move |mut client| {
+                            let request = request.clone();
+                            async move { client.barrier_complete(request).await }
+                        })
+                        .await
                     }
                     .into()
                 }
             });
 
             let result = try_join_all(collect_futures).await;
-            barrier_complete_tx.send((prev_epoch, result)).unwrap();
+            barrier_complete_tx
+                .send((prev_epoch, result))
+                .await
+                .unwrap();
         });
         Ok(())
     }
@@ -771,22 +971,79 @@ where
             }
             if self.enable_recovery {
                 // If failed, enter recovery mode.
-                let (new_epoch, actors_to_track, create_mview_progress) =
-                    self.recovery(new_epoch).await;
-                *tracker = CreateMviewProgressTracker::default();
-                tracker.add(new_epoch, actors_to_track, vec![]);
-                for progress in &create_mview_progress {
-                    tracker.update(progress);
-                }
-                state.in_flight_prev_epoch = new_epoch;
-                state
-                    .update_inflight_prev_epoch(self.env.meta_store())
-                    .await
-                    .unwrap();
+                self.recovery_and_track(new_epoch, state, tracker).await;
             } else {
                 panic!("failed to execute barrier: {:?}", err);
             }
         }
+        self.publish_create_mview_progress(tracker);
+    }
+
+    fn publish_create_mview_progress(&self, tracker: &CreateMviewProgressTracker) {
+        *self.create_mview_progress.lock() = tracker.mview_progress();
+    }
+
+    /// Recover the cluster from `prev_epoch`, then reset the `tracker` with the progress of the
+    /// creating mviews after recovery. The creating mviews tracked before are carried forward.
+    ///
+    /// If the recovery fails, the barrier manager is degraded: no barrier is injected, and the
+    /// recovery is retried every `RECOVERY_RETRY_INTERVAL` until it succeeds.
+    async fn recovery_and_track(
+        &self,
+        prev_epoch: Epoch,
+        state: &mut BarrierManagerState,
+        tracker: &mut CreateMviewProgressTracker,
+    ) {
+        let (new_epoch, actors_to_track, create_mview_progress) =
+            match self.recovery(prev_epoch).await {
+                Ok(result) => result,
+                Err(err) => {
+                    tracing::error!(
+                        "{}, no barrier will be injected until a retry in {:?} succeeds",
+                        err,
+                        Self::RECOVERY_RETRY_INTERVAL
+                    );
+                    *self.pending_recovery.lock() = Some(prev_epoch);
+                    return;
+                }
+            };
+        *self.pending_recovery.lock() = None;
+        tracker.recover(new_epoch, actors_to_track);
+        for progress in &create_mview_progress {
+            tracker.update(progress);
+        }
+        state.in_flight_prev_epoch = new_epoch;
+        state.checkpoint_prev_epoch = INVALID_EPOCH.into();
+        state
+            .update_inflight_prev_epoch(self.env.meta_store())
+            .await
+            .unwrap();
+    }
+
+    fn update_barrier_nums_metrics(&self, checkpoint_control: &CheckpointControl<S>) {
+        let (in_flight_nums, all_nums) = checkpoint_control.get_barrier_len();
+        self.metrics
+            .in_flight_barrier_nums
+            .set(in_flight_nums as i64);
+        self.metrics.all_barrier_nums.set(all_nums as i64);
+    }
+
+    fn update_stream_client_pool_metrics(&self) {
+        let pool = self.env.stream_client_pool();
+        self.metrics.stream_client_pool_size.set(pool.len() as i64);
+        self.metrics
+            .stream_client_pool_eviction_count
+            .set(pool.eviction_count() as i64);
+    }
+
+    fn is_drain_requested(&self) -> bool {
+        !self.drain_requests.lock().is_empty()
+    }
+
+    /// New barriers are not injected while the last recovery failed.
+    fn can_inject_barrier(&self, checkpoint_control: &CheckpointControl<S>) -> bool {
+        self.pending_recovery.lock().is_none()
+            && checkpoint_control.can_inject_barrier(self.in_flight_barrier_nums)
     }
 
     /// Try to commit this node. If err, returns
@@ -803,26 +1060,34 @@ where
                 // because the storage engine will query from new to old in the order in which
                 // the L0 layer files are generated.
                 // See https://github.com/singularity-data/risingwave/issues/1251
-                let synced_ssts: Vec<LocalSstableInfo> = resps
-                    .iter()
-                    .flat_map(|resp| resp.synced_sstables.clone())
-                    .map(|grouped| {
-                        (
-                            grouped.compaction_group_id,
-                            grouped.sst.expect("field not None"),
-                        )
-                    })
-                    .collect_vec();
+                // The SSTs synced by a checkpoint barrier are committed epoch by epoch, in the
+                // same ascending order.
+                let mut ssts_by_epoch = group_synced_ssts_by_epoch(resps);
 
                 if prev_epoch == INVALID_EPOCH {
                     assert!(
-                        synced_ssts.is_empty(),
+                        ssts_by_epoch.is_empty(),
                         "no sstables should be produced in the first epoch"
                     );
+                } else if !node.command_ctx.checkpoint {
+                    assert!(
+                        ssts_by_epoch.is_empty(),
+                        "no sstables should be synced in non-checkpoint epoch"
+                    );
                 } else {
-                    self.hummock_manager
-                        .commit_epoch(prev_epoch, synced_ssts)
-                        .await?;
+                    if let Some(epoch) = ssts_by_epoch.keys().find(|epoch| **epoch > prev_epoch) {
+                        return Err(RwError::from(ErrorCode::InternalError(format!(
+                            "sstables of epoch {} are synced by the barrier of epoch {}",
+                            epoch, prev_epoch
+                        ))));
+                    }
+                    // The checkpoint epoch is committed even if it has no SSTs.
+                    ssts_by_epoch.entry(prev_epoch).or_default();
+                    for (epoch, synced_ssts) in ssts_by_epoch {
+                        self.hummock_manager
+                            .commit_epoch(epoch, synced_ssts)
+                            .await?;
+                    }
                 }
 
                 node.timer.take().unwrap().observe_duration();
@@ -834,7 +1099,31 @@ where
 
                 // Then try to finish the barrier for Create MVs.
                 let actors_to_finish = node.command_ctx.actors_to_track();
-                tracker.add(node.command_ctx.curr_epoch, actors_to_finish, notifiers);
+                let mview = match &node.command_ctx.command {
+                    Command::CreateMaterializedView {
+                        table_fragments, ..
+                    } => {
+                        let upstream_table_ids = table_fragments
+                            .dependent_table_ids()
+                            .into_iter()
+                            .map(|table_id| table_id.table_id)
+                            .collect();
+                        Some(CreatingMview {
+                            table_id: table_fragments.table_id(),
+                            upstream_total_rows: self
+                                .hummock_manager
+                                .estimate_table_rows(&upstream_table_ids)
+                                .await,
+                        })
+                    }
+                    _ => None,
+                };
+                tracker.add(
+                    node.command_ctx.curr_epoch,
+                    mview,
+                    actors_to_finish,
+                    notifiers,
+                );
                 for progress in resps.iter().flat_map(|r| &r.create_mview_progress) {
                     tracker.update(progress);
                 }
@@ -854,20 +1143,31 @@ where
     /// Resolve actor information from cluster, fragment manager and `ChangedTableId`.
     /// We use `changed_table_id` to modify the actors to be sent or collected. Because these actor
     /// will create or drop before this barrier flow through them.
+    ///
+    /// The actors on draining workers are still sent or collected until they're migrated, unless
+    /// `include_draining` is false.
     async fn resolve_actor_info(
         &self,
         checkpoint_control: &mut CheckpointControl<S>,
         command: &Command,
+        include_draining: bool,
     ) -> BarrierActorInfo {
         checkpoint_control.pre_resolve(command);
 
         let check_state = |s: ActorState, table_id: TableId, actor_id: ActorId| {
             checkpoint_control.can_actor_send_or_collect(s, table_id, actor_id)
         };
-        let all_nodes = self
+        let mut all_nodes = self
             .cluster_manager
-            .list_worker_node(WorkerType::ComputeNode, Some(Running))
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
             .await;
+        if include_draining {
+            all_nodes.extend(
+                self.cluster_manager
+                    .list_worker_node(WorkerType::ComputeNode, Some(State::Draining))
+                    .await,
+            );
+        }
         let all_actor_infos = self.fragment_manager.load_all_actors(check_state).await;
 
         let info = BarrierActorInfo::resolve(all_nodes, all_actor_infos);
@@ -940,6 +1240,81 @@ where
         self.run_multiple_commands(vec![command]).await
     }
 
+    /// Get a snapshot of the barrier statistics, which is read from the metrics reported by the
+    /// barrier loop.
+    pub fn barrier_stats(&self) -> Result<BarrierStats> {
+        let latency = &self.metrics.barrier_latency;
+        Ok(BarrierStats {
+            in_flight_barrier_nums: self.metrics.in_flight_barrier_nums.get() as usize,
+            all_barrier_nums: self.metrics.all_barrier_nums.get() as usize,
+            max_committed_epoch: self.hummock_manager.get_last_epoch()?.epoch,
+            latency_p50: histogram_quantile(latency, 0.5),
+            latency_p90: histogram_quantile(latency, 0.9),
+            latency_p99: histogram_quantile(latency, 0.99),
+        })
+    }
+
+    /// Get the progress of the creating mviews, from 0 to 1. The progress is estimated by the
+    /// number of rows consumed from the upstream tables, and is only known for the mviews
+    /// created since the meta node started.
+    pub fn create_mview_progress(&self) -> HashMap<TableId, f64> {
+        self.create_mview_progress.lock().clone()
+    }
+
+    /// Drain the compute node at `host_address`. The node is marked as `Draining` first so that no
+    /// more actors will be scheduled onto it. Then the sources are paused by a checkpoint barrier,
+    /// and its actors are migrated to the free parallel units of the running compute nodes by the
+    /// barrier loop, after which the node is deleted from the cluster.
+    ///
+    /// Returns an error and leaves the node `Draining` if the actors can't be migrated within
+    /// `drain_timeout`, e.g., the running compute nodes don't have enough free parallel units.
+    pub async fn drain_worker_node(&self, host_address: HostAddress) -> Result<()> {
+        if !self.enable_recovery || !self.enable_migrate {
+            return Err(RwError::from(ErrorCode::InternalError(
+                "draining a worker requires both recovery and migration to be enabled".to_string(),
+            )));
+        }
+        let worker_node = self
+            .cluster_manager
+            .drain_worker_node(host_address.clone())
+            .await?;
+
+        let has_actors = |node_actors: &HashMap<_, Vec<_>>| {
+            node_actors
+                .get(&worker_node.id)
+                .map_or(false, |actors| !actors.is_empty())
+        };
+        if has_actors(&self.fragment_manager.all_node_actors(true).await) {
+            // Stop the data flowing and persist the states before the actors are moved.
+            self.run_command(Command::pause()).await?;
+            let (tx, rx) = oneshot::channel();
+            self.drain_requests.lock().push(tx);
+            // Wait until the actors are migrated by the barrier loop.
+            let drain_timeout = self.env.opts.drain_timeout;
+            tokio::time::timeout(drain_timeout, rx)
+                .await
+                .map_err(|_| {
+                    RwError::from(ErrorCode::InternalError(format!(
+                        "actors on worker {} are not migrated within {:?}",
+                        worker_node.id, drain_timeout
+                    )))
+                })?
+                .map_err(|_| {
+                    RwError::from(ErrorCode::InternalError(
+                        "barrier manager is stopped".to_string(),
+                    ))
+                })??;
+            if has_actors(&self.fragment_manager.all_node_actors(true).await) {
+                return Err(RwError::from(ErrorCode::InternalError(format!(
+                    "actors on worker {} are not migrated",
+                    worker_node.id
+                ))));
+            }
+        }
+
+        self.cluster_manager.delete_worker_node(host_address).await
+    }
+
     /// Wait for the next barrier to collect. Note that the barrier flowing in our stream graph is
     /// ignored, if exists.
     pub async fn wait_for_next_barrier_to_collect(&self) -> Result<()> {
@@ -956,3 +1331,68 @@ where
 }
 
 pub type BarrierManagerRef<S> = Arc<GlobalBarrierManager<S>>;
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::hummock::SstableInfo;
+    use risingwave_pb::stream_service::barrier_complete_response::GroupedSstableInfo;
+
+    use super::*;
+
+    fn grouped_sst(epoch: HummockEpoch, compaction_group_id: u64, id: u64) -> GroupedSstableInfo {
+        GroupedSstableInfo {
+            compaction_group_id,
+            sst: Some(SstableInfo {
+                id,
+                ..Default::default()
+            }),
+            epoch,
+        }
+    }
+
+    #[test]
+    fn test_group_synced_ssts_by_epoch() {
+        // A checkpoint barrier of epoch 3 syncs the SSTs of the non-checkpoint epochs 1 and 2 as
+        // well. The SST 3 is reported twice.
+        let resps = vec![
+            BarrierCompleteResponse {
+                synced_sstables: vec![
+                    grouped_sst(2, 2, 4),
+                    grouped_sst(1, 3, 1),
+                    grouped_sst(3, 2, 5),
+                    grouped_sst(2, 2, 3),
+                ],
+                ..Default::default()
+            },
+            BarrierCompleteResponse {
+                synced_sstables: vec![
+                    grouped_sst(1, 2, 2),
+                    grouped_sst(2, 2, 3),
+                    grouped_sst(2, 1, 6),
+                ],
+                ..Default::default()
+            },
+        ];
+        let ssts_by_epoch = group_synced_ssts_by_epoch(&resps);
+
+        let ssts_by_epoch = ssts_by_epoch
+            .into_iter()
+            .map(|(epoch, ssts)| {
+                (
+                    epoch,
+                    ssts.into_iter()
+                        .map(|(compaction_group_id, sst)| (compaction_group_id, sst.id))
+                        .collect_vec(),
+                )
+            })
+            .collect_vec();
+        assert_eq!(
+            ssts_by_epoch,
+            vec![
+                (1, vec![(2, 2), (3, 1)]),
+                (2, vec![(1, 6), (2, 4), (2, 3)]),
+                (3, vec![(2, 5)]),
+            ]
+        );
+    }
+}
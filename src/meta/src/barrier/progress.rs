@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::mem::take;
+use std::time::{Duration, Instant};
 
 use itertools::Itertools;
+use risingwave_common::catalog::TableId;
 use risingwave_common::util::epoch::Epoch;
 use risingwave_pb::stream_service::barrier_complete_response::CreateMviewProgress;
 
@@ -31,16 +34,33 @@ enum ChainState {
     Done,
 }
 
+/// The mview being created, with the estimated number of rows in its upstream tables when the
+/// creation starts.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct CreatingMview {
+    pub table_id: TableId,
+    pub upstream_total_rows: u64,
+}
+
 /// Progress of all actors containing chain nodes while creating mview.
 struct Progress {
+    /// The mview being created, unknown for the mviews found creating on recovery.
+    mview: Option<CreatingMview>,
+
     states: HashMap<ActorId, ChainState>,
 
     done_count: usize,
+
+    /// The number of rows consumed from the snapshot by each actor.
+    consumed_rows: HashMap<ActorId, u64>,
+
+    /// When the tracking started, used to estimate the backfill rate.
+    start_time: Instant,
 }
 
 impl Progress {
     /// Create a [`Progress`] for some creating mview, with all `actors` containing the chain nodes.
-    fn new(actors: impl IntoIterator<Item = ActorId>) -> Self {
+    fn new(mview: Option<CreatingMview>, actors: impl IntoIterator<Item = ActorId>) -> Self {
         let states = actors
             .into_iter()
             .map(|a| (a, ChainState::ConsumingSnapshot))
@@ -48,21 +68,73 @@ impl Progress {
         assert!(!states.is_empty());
 
         Self {
+            mview,
             states,
             done_count: 0,
+            consumed_rows: HashMap::new(),
+            start_time: Instant::now(),
         }
     }
 
-    /// Update the progress of `actor`.
-    fn update(&mut self, actor: ActorId, new_state: ChainState) {
+    /// Update the progress of `actor`, which has consumed `consumed_rows` rows from the snapshot.
+    /// The reports from a done actor are ignored, as it may report again after recovery.
+    fn update(&mut self, actor: ActorId, new_state: ChainState, consumed_rows: u64) {
         match self.states.get_mut(&actor).unwrap() {
             state @ (ChainState::ConsumingSnapshot | ChainState::ConsumingUpstream(_)) => {
                 if matches!(new_state, ChainState::Done) {
                     self.done_count += 1;
                 }
                 *state = new_state;
+                self.consumed_rows.insert(actor, consumed_rows);
             }
-            ChainState::Done => panic!("should not report done multiple times"),
+            ChainState::Done => {}
+        }
+    }
+
+    /// Reset the progress of the actors not done yet, since they'll consume the snapshot again
+    /// after recovery. The done actors and the consumed epochs reported before are kept.
+    fn reset(&mut self) {
+        let states = &self.states;
+        self.consumed_rows
+            .retain(|actor, _| matches!(states.get(actor), Some(ChainState::Done)));
+        self.start_time = Instant::now();
+    }
+
+    /// Returns the number of rows consumed from the snapshot by all actors.
+    fn consumed_rows(&self) -> u64 {
+        self.consumed_rows.values().sum()
+    }
+
+    /// Returns the number of rows consumed from the snapshot per second since the tracking
+    /// started.
+    fn rows_per_sec(&self) -> f64 {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.consumed_rows() as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the estimated time to consume the rest of the upstream rows at the current rate,
+    /// or `None` if nothing is consumed yet.
+    fn eta(&self, upstream_total_rows: u64) -> Option<Duration> {
+        estimate_remaining_time(
+            self.consumed_rows(),
+            upstream_total_rows,
+            self.start_time.elapsed(),
+        )
+    }
+
+    /// Returns the fraction of the upstream rows consumed, from 0 to 1. As the number of upstream
+    /// rows is only an estimate, it's capped below 1 until all chains are done.
+    fn ratio(&self, upstream_total_rows: u64) -> f64 {
+        if self.is_done() {
+            1.0
+        } else if upstream_total_rows == 0 {
+            0.0
+        } else {
+            (self.consumed_rows() as f64 / upstream_total_rows as f64).min(0.99)
         }
     }
 
@@ -78,6 +150,20 @@ impl Progress {
     }
 }
 
+/// Estimates the time to consume `total_rows` rows, if `consumed_rows` rows have been consumed in
+/// `elapsed`.
+fn estimate_remaining_time(
+    consumed_rows: u64,
+    total_rows: u64,
+    elapsed: Duration,
+) -> Option<Duration> {
+    if consumed_rows == 0 {
+        return None;
+    }
+    let remaining_rows = total_rows.saturating_sub(consumed_rows);
+    Some(elapsed.mul_f64(remaining_rows as f64 / consumed_rows as f64))
+}
+
 /// Track the progress of all creating mviews. When creation is done, `notify_finished` will be
 /// called on registered notifiers.
 #[derive(Default)]
@@ -97,6 +183,7 @@ impl CreateMviewProgressTracker {
     pub fn add(
         &mut self,
         ddl_epoch: Epoch,
+        mview: Option<CreatingMview>,
         actors: impl IntoIterator<Item = ActorId>,
         notifiers: impl IntoIterator<Item = Notifier>,
     ) {
@@ -111,12 +198,60 @@ impl CreateMviewProgressTracker {
             self.actor_map.insert(actor, ddl_epoch);
         }
 
-        let progress = Progress::new(actors);
+        let progress = Progress::new(mview, actors);
         let notifiers = notifiers.into_iter().collect();
         let old = self.progress_map.insert(ddl_epoch, (progress, notifiers));
         assert!(old.is_none());
     }
 
+    /// Reset the tracker after recovery, where all `actors` containing the chain nodes will report
+    /// their progress again.
+    ///
+    /// The creating mviews tracked before are carried forward with their notifiers, done actors and
+    /// reported progress if all of their actors still exist, so that the DDLs can still be
+    /// finished. Other actors are tracked with the `epoch` of recovery.
+    pub fn recover(&mut self, epoch: Epoch, actors: impl IntoIterator<Item = ActorId>) {
+        let actors: HashSet<ActorId> = actors.into_iter().collect();
+
+        let old = take(self);
+        for (ddl_epoch, (mut progress, notifiers)) in old.progress_map {
+            if !progress.actors().all(|actor| actors.contains(&actor)) {
+                tracing::warn!(
+                    "actors of creating mview with epoch {} are gone after recovery",
+                    ddl_epoch
+                );
+                continue;
+            }
+            progress.reset();
+            for actor in progress.actors() {
+                self.actor_map.insert(actor, ddl_epoch);
+            }
+            self.progress_map.insert(ddl_epoch, (progress, notifiers));
+        }
+
+        let other_actors = actors
+            .into_iter()
+            .filter(|actor| !self.actor_map.contains_key(actor))
+            .collect_vec();
+        self.add(epoch, None, other_actors, vec![]);
+    }
+
+    /// Returns the progress of the creating mviews whose upstream tables are known, from 0 to 1.
+    pub fn mview_progress(&self) -> HashMap<TableId, f64> {
+        self.progress_map
+            .values()
+            .filter_map(|(progress, _)| {
+                let mview = progress.mview?;
+                Some((mview.table_id, progress.ratio(mview.upstream_total_rows)))
+            })
+            .collect()
+    }
+
+    /// Whether there's no creating mview to track.
+    pub fn is_empty(&self) -> bool {
+        self.progress_map.is_empty()
+    }
+
     /// Update the progress of `actor` according to the Prost struct. If all actors in this MV have
     /// finished, `notify_finished` will be called on registered notifiers.
     pub fn update(&mut self, progress: &CreateMviewProgress) {
@@ -125,8 +260,12 @@ impl CreateMviewProgressTracker {
             panic!("no tracked progress for actor {}, is it already finished?", actor);
         };
 
+        let progress_consumed_rows = progress.consumed_rows;
         let new_state = if progress.done {
             ChainState::Done
+        } else if progress.consumed_epoch == 0 {
+            // The actor is still on the snapshot and only reports the consumed rows.
+            ChainState::ConsumingSnapshot
         } else {
             ChainState::ConsumingUpstream(progress.consumed_epoch.into())
         };
@@ -134,7 +273,28 @@ impl CreateMviewProgressTracker {
         match self.progress_map.entry(epoch) {
             Entry::Occupied(mut o) => {
                 let progress = &mut o.get_mut().0;
-                progress.update(actor, new_state);
+                progress.update(actor, new_state, progress_consumed_rows);
+                tracing::debug!(
+                    "creating mview with epoch {}: {}/{} actors done, {} rows at {:.0} rows/s",
+                    epoch,
+                    progress.done_count,
+                    progress.states.len(),
+                    progress.consumed_rows(),
+                    progress.rows_per_sec(),
+                );
+                if let Some((mview, eta)) = progress.mview.and_then(|mview| {
+                    progress
+                        .eta(mview.upstream_total_rows)
+                        .map(|eta| (mview, eta))
+                }) {
+                    tracing::debug!(
+                        "creating mview {} with epoch {}: about {:.0}s left for {} upstream rows",
+                        mview.table_id,
+                        epoch,
+                        eta.as_secs_f64(),
+                        mview.upstream_total_rows,
+                    );
+                }
 
                 if progress.is_done() {
                     tracing::debug!("all actors done for creating mview with epoch {}!", epoch);
@@ -152,3 +312,105 @@ impl CreateMviewProgressTracker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot;
+
+    use super::*;
+
+    fn progress(actor: ActorId, done: bool) -> CreateMviewProgress {
+        CreateMviewProgress {
+            chain_actor_id: actor,
+            done,
+            consumed_epoch: 0,
+            consumed_rows: 0,
+        }
+    }
+
+    #[test]
+    fn test_recover() {
+        let mut tracker = CreateMviewProgressTracker::default();
+        let (finished_tx, mut finished_rx) = oneshot::channel();
+        let notifier = Notifier {
+            finished: Some(finished_tx),
+            ..Default::default()
+        };
+        tracker.add(Epoch(1), None, [1, 2], [notifier]);
+        tracker.update(&progress(1, true));
+
+        // Actors 3 and 4 belong to the created mviews.
+        tracker.recover(Epoch(2), [1, 2, 3, 4]);
+        tracker.update(&progress(3, true));
+        tracker.update(&progress(4, true));
+        tracker.update(&progress(1, true));
+        assert!(finished_rx.try_recv().is_err());
+
+        tracker.update(&progress(2, true));
+        assert!(finished_rx.try_recv().is_ok());
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_recover_keeps_done_actors() {
+        let mut tracker = CreateMviewProgressTracker::default();
+        let (finished_tx, mut finished_rx) = oneshot::channel();
+        let notifier = Notifier {
+            finished: Some(finished_tx),
+            ..Default::default()
+        };
+        tracker.add(Epoch(1), None, [1, 2], [notifier]);
+        tracker.update(&progress(1, true));
+
+        // Actor 1 is not waited for again after recovery.
+        tracker.recover(Epoch(2), [1, 2]);
+        tracker.update(&progress(2, true));
+        assert!(finished_rx.try_recv().is_ok());
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn test_consumed_rows() {
+        let mut tracker = CreateMviewProgressTracker::default();
+        let mview = CreatingMview {
+            table_id: TableId::new(10),
+            upstream_total_rows: 100,
+        };
+        tracker.add(Epoch(1), Some(mview), [1, 2], []);
+        let update = |tracker: &mut CreateMviewProgressTracker, actor, consumed_rows| {
+            tracker.update(&CreateMviewProgress {
+                chain_actor_id: actor,
+                done: false,
+                consumed_epoch: 0,
+                consumed_rows,
+            })
+        };
+        update(&mut tracker, 1, 10);
+        update(&mut tracker, 2, 20);
+        // The latest report of each actor overrides the previous one.
+        update(&mut tracker, 1, 30);
+
+        let progress = &tracker.progress_map[&Epoch(1)].0;
+        assert_eq!(progress.consumed_rows(), 50);
+        assert_eq!(tracker.mview_progress()[&TableId::new(10)], 0.5);
+
+        // The estimate of the upstream rows may be smaller than the actual.
+        update(&mut tracker, 2, 90);
+        assert_eq!(tracker.mview_progress()[&TableId::new(10)], 0.99);
+    }
+
+    #[test]
+    fn test_estimate_remaining_time() {
+        let elapsed = Duration::from_secs(10);
+        assert_eq!(estimate_remaining_time(0, 100, elapsed), None);
+        assert_eq!(
+            estimate_remaining_time(25, 100, elapsed),
+            Some(Duration::from_secs(30))
+        );
+        // The estimate of the upstream rows may be smaller than the actual.
+        assert_eq!(
+            estimate_remaining_time(200, 100, elapsed),
+            Some(Duration::ZERO)
+        );
+    }
+}
@@ -12,34 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter::Map;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+use fail::fail_point;
 use futures::future::try_join_all;
 use itertools::Itertools;
-use log::{debug, error};
-use risingwave_common::error::{ErrorCode, Result, RwError};
+use log::{debug, error, info, warn};
+use risingwave_common::catalog::TableId;
+use risingwave_common::error::{internal_error, ErrorCode, Result, RwError};
 use risingwave_common::types::VIRTUAL_NODE_COUNT;
 use risingwave_common::util::compress::decompress_data;
-use risingwave_common::util::epoch::Epoch;
+use risingwave_common::util::epoch::{Epoch, INVALID_EPOCH};
 use risingwave_pb::common::worker_node::State;
 use risingwave_pb::common::{ActorInfo, WorkerNode, WorkerType};
 use risingwave_pb::data::Epoch as ProstEpoch;
+use risingwave_pb::meta::table_fragments::ActorState;
 use risingwave_pb::stream_service::barrier_complete_response::CreateMviewProgress;
 use risingwave_pb::stream_service::{
-    BroadcastActorInfoTableRequest, BuildActorsRequest, ForceStopActorsRequest, SyncSourcesRequest,
-    UpdateActorsRequest,
+    BroadcastActorInfoTableRequest, BuildActorsRequest, DropActorsRequest, ForceStopActorsRequest,
+    SyncSourcesRequest, UpdateActorsRequest,
 };
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use uuid::Uuid;
 
 use crate::barrier::command::CommandContext;
 use crate::barrier::info::BarrierActorInfo;
+use crate::barrier::progress::CreateMviewProgressTracker;
 use crate::barrier::{CheckpointControl, Command, GlobalBarrierManager};
-use crate::cluster::WorkerId;
-use crate::model::ActorId;
+use crate::cluster::{is_streaming_worker, WorkerId};
+use crate::model::{ActorId, BarrierManagerState, TableFragments};
 use crate::storage::MetaStore;
 
 pub type RecoveryResult = (Epoch, HashSet<ActorId>, Vec<CreateMviewProgress>);
@@ -62,18 +68,118 @@ where
     }
 
     async fn resolve_actor_info_for_recovery(&self) -> BarrierActorInfo {
-        self.resolve_actor_info(&mut CheckpointControl::new(), &Command::checkpoint())
+        // Actors on draining workers are treated as expired, so that they'll be migrated.
+        self.resolve_actor_info(&mut CheckpointControl::new(), &Command::checkpoint(), false)
             .await
     }
 
+    /// Whether the barriers can be resumed from the epoch persisted in `state` when the meta node
+    /// starts, without a recovery. This requires that:
+    ///
+    /// - the last barrier sent is a checkpoint, and its `prev_epoch` is committed to Hummock, so
+    ///   that all the barriers sent before are collected and committed;
+    /// - no mview is being created, as the progress of the creating mviews is not persisted;
+    /// - all the actors are on running workers.
+    ///
+    /// The compute nodes are not asked here: the first barrier sent from the persisted epoch fails
+    /// if any of them is down or has lost its actors, which triggers a recovery.
+    pub(crate) async fn can_resume(&self, state: &BarrierManagerState) -> bool {
+        let checkpoint_prev_epoch = state.checkpoint_prev_epoch.0;
+        if checkpoint_prev_epoch == INVALID_EPOCH {
+            info!("cannot resume: the last barrier is not a checkpoint");
+            return false;
+        }
+        let max_committed_epoch = self
+            .hummock_manager
+            .get_current_version()
+            .await
+            .max_committed_epoch;
+        if max_committed_epoch != checkpoint_prev_epoch {
+            info!(
+                "cannot resume: epoch {} of the last checkpoint is not committed, max committed epoch {}",
+                checkpoint_prev_epoch, max_committed_epoch
+            );
+            return false;
+        }
+
+        let creating = match self.fragment_manager.list_table_fragments().await {
+            Ok(table_fragments) => table_fragments.iter().any(|table_fragments| {
+                table_fragments
+                    .worker_actor_states()
+                    .values()
+                    .flatten()
+                    .any(|(_, state)| *state == ActorState::Inactive)
+            }),
+            Err(err) => {
+                warn!("cannot resume: failed to list table fragments: {}", err);
+                return false;
+            }
+        };
+        if creating {
+            info!("cannot resume: some mview is being created");
+            return false;
+        }
+
+        let info = self.resolve_actor_info_for_recovery().await;
+        if let Some(worker) = info
+            .actor_map
+            .iter()
+            .find(|(worker, actors)| !actors.is_empty() && !info.node_map.contains_key(worker))
+            .map(|(worker, _)| worker)
+        {
+            info!("cannot resume: worker {} is not running", worker);
+            return false;
+        }
+        true
+    }
+
     /// Recovery the whole cluster from the latest epoch.
-    pub(crate) async fn recovery(&self, prev_epoch: Epoch) -> RecoveryResult {
+    ///
+    /// The recovery is retried until it succeeds, or until `max_recovery_attempts` or
+    /// `recovery_timeout` is reached, where an error is returned instead of hanging. It's refused
+    /// right away if it would replay more than `max_recovery_replay` since the last committed
+    /// epoch.
+    pub(crate) async fn recovery(&self, prev_epoch: Epoch) -> Result<RecoveryResult> {
         // Abort buffered schedules, they might be dirty already.
         self.scheduled_barriers.abort().await;
 
-        debug!("recovery start!");
-        let retry_strategy = Self::get_retry_strategy();
-        let (new_epoch, responses) = tokio_retry::Retry::spawn(retry_strategy, || async {
+        if let Some(max_replay) = self.env.opts.max_recovery_replay {
+            let committed_epoch = Epoch::from(
+                self.hummock_manager
+                    .get_current_version()
+                    .await
+                    .max_committed_epoch,
+            );
+            let replay = Duration::from_millis(
+                prev_epoch
+                    .physical_time()
+                    .saturating_sub(committed_epoch.physical_time()),
+            );
+            if replay > max_replay {
+                return Err(internal_error(format!(
+                    "recovery from epoch {} would replay {:?} since the committed epoch {}, over the limit {:?}",
+                    prev_epoch.0, replay, committed_epoch.0, max_replay
+                )));
+            }
+        }
+
+        info!("recovery start from epoch {}", prev_epoch);
+        let timer = self.metrics.recovery_duration.start_timer();
+        let start = Instant::now();
+        let attempts = AtomicUsize::new(0);
+        let max_attempts = self.env.opts.max_recovery_attempts;
+        // The first attempt is not a retry.
+        let max_retries = max_attempts.checked_sub(1).unwrap_or(usize::MAX);
+        let retry_strategy = Self::get_retry_strategy().take(max_retries);
+        let recover = tokio_retry::Retry::spawn(retry_strategy, || async {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            self.metrics.recovery_attempts.inc();
+            info!(
+                "recovery attempt {} started, {:?} elapsed",
+                attempt,
+                start.elapsed()
+            );
+
             let mut info = self.resolve_actor_info_for_recovery().await;
             let mut new_epoch = prev_epoch.next();
 
@@ -81,16 +187,30 @@ where
                 // Migrate expired actors to newly joined node by changing actor_map
                 self.migrate_actors(&info).await?;
                 info = self.resolve_actor_info_for_recovery().await;
+                info!("recovery attempt {}: actors migrated", attempt);
             }
 
-            // Reset all compute nodes, stop and drop existing actors.
+            // Reset all compute nodes including the draining ones, stop and drop existing actors.
+            let draining_nodes = self
+                .cluster_manager
+                .list_worker_node(WorkerType::ComputeNode, Some(State::Draining))
+                .await;
             if let Err(err) = self
-                .reset_compute_nodes(&info, &prev_epoch, &new_epoch)
+                .reset_compute_nodes(
+                    info.node_map.values().chain(draining_nodes.iter()),
+                    &prev_epoch,
+                    &new_epoch,
+                )
                 .await
             {
                 error!("reset compute nodes failed: {}", err);
                 return Err(err);
             }
+            info!(
+                "recovery attempt {}: {} compute nodes reset",
+                attempt,
+                info.node_map.len() + draining_nodes.len()
+            );
 
             // Refresh sources in local source manger of compute node.
             if let Err(err) = self.sync_sources(&info).await {
@@ -107,6 +227,14 @@ where
                 error!("build_actors failed: {}", err);
                 return Err(err);
             }
+            info!(
+                "recovery attempt {}: {} actors built",
+                attempt,
+                info.actor_map
+                    .values()
+                    .map(|actors| actors.len())
+                    .sum::<usize>()
+            );
 
             let prev_epoch = new_epoch;
             new_epoch = prev_epoch.next();
@@ -118,10 +246,11 @@ where
                 prev_epoch,
                 new_epoch,
                 Command::checkpoint(),
+                true,
             ));
 
             let command_ctx_clone = command_ctx.clone();
-            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let (tx, mut rx) = tokio::sync::mpsc::channel(1);
             if let Err(err) = self.inject_barrier(command_ctx_clone, tx).await {
                 error!("inject_barrier failed: {}", err);
                 return Err(err);
@@ -139,23 +268,46 @@ where
                     Err(err)
                 }
             }
-        })
-        .await
-        .expect("Retry until recovery success.");
-        debug!("recovery success");
-
-        (
+        });
+        let result = match self.env.opts.recovery_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, recover)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(
+                        ErrorCode::InternalError(format!("recovery timed out after {:?}", timeout))
+                            .into(),
+                    )
+                }),
+            None => recover.await,
+        };
+        let (new_epoch, responses) = result.map_err(|err| {
+            internal_error(format!(
+                "recovery failed after {} attempt(s): {}",
+                attempts.load(Ordering::Relaxed),
+                err
+            ))
+        })?;
+        timer.observe_duration();
+        info!(
+            "recovery success in {:?} after {} attempt(s), new epoch {}",
+            start.elapsed(),
+            attempts.load(Ordering::Relaxed),
+            new_epoch
+        );
+
+        Ok((
             new_epoch,
             self.fragment_manager.all_chain_actor_ids().await,
             responses
                 .into_iter()
                 .flat_map(|r| r.create_mview_progress)
                 .collect(),
-        )
+        ))
     }
 
     /// map expired CNs to newly joined CNs, so we can migrate actors later
-    /// wait until get a sufficient amount of new CNs
+    /// wait until get a sufficient amount of new CNs, where a CN without any actors is also
+    /// considered as a new one
     /// return "map of `ActorId` in expired CN to new CN id" and "map of `WorkerId` to
     /// `WorkerNode` struct in new CNs"
     async fn get_migrate_map_plan(
@@ -175,7 +327,12 @@ where
             let new_nodes = current_nodes
                 .iter()
                 .filter(|&node| {
-                    !info.node_map.contains_key(&node.id) && !node_map.contains_key(&node.id)
+                    is_streaming_worker(node)
+                        && info
+                            .actor_map
+                            .get(&node.id)
+                            .map_or(true, |actors| actors.is_empty())
+                        && !node_map.contains_key(&node.id)
                 })
                 .collect_vec();
             for new_node in new_nodes {
@@ -197,6 +354,298 @@ where
         (migrate_map, node_map)
     }
 
+    /// Migrates the actors on the draining workers to the running workers that already exist,
+    /// without waiting for new compute nodes. See [`Self::plan_drain_migration`].
+    ///
+    /// It must be called with no barrier in flight, after a `Pause` checkpoint barrier has been
+    /// committed, so that no data is flowing and all the states are persisted. Then only the
+    /// streaming jobs connected to the migrated actors are rebuilt: their actors are dropped from
+    /// the old workers and built on the new ones, and they're initialized by the next barrier. The
+    /// other actors keep running. If the rebuild fails halfway, the cluster is recovered as usual.
+    pub(crate) async fn migrate_draining_actors(
+        &self,
+        state: &mut BarrierManagerState,
+        tracker: &mut CreateMviewProgressTracker,
+    ) -> Result<()> {
+        let draining_workers: HashSet<_> = self
+            .cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Draining))
+            .await
+            .into_iter()
+            .map(|worker| worker.id)
+            .collect();
+        let table_fragments = self.fragment_manager.list_table_fragments().await?;
+        let (migrate_map, node_map) = self
+            .plan_drain_migration(&table_fragments, &draining_workers)
+            .await?;
+        if migrate_map.is_empty() {
+            return Ok(());
+        }
+        info!("migrate actors on draining workers {:?}", draining_workers);
+
+        let rebuilt_table_fragments =
+            Self::connected_table_fragments(&table_fragments, migrate_map.keys());
+        if let Some(table_fragments) = rebuilt_table_fragments.iter().find(|table_fragments| {
+            table_fragments
+                .actor_status
+                .values()
+                .any(|status| status.state() == ActorState::Inactive)
+        }) {
+            return Err(internal_error(format!(
+                "mview {} connected to the actors to migrate is being created",
+                table_fragments.table_id()
+            )));
+        }
+        // The actors are dropped from where they are before the migration.
+        let mut old_node_actors: BTreeMap<WorkerId, Vec<ActorId>> = BTreeMap::new();
+        for table_fragments in &rebuilt_table_fragments {
+            for (node_id, actors) in table_fragments.worker_actor_ids() {
+                old_node_actors.entry(node_id).or_default().extend(actors);
+            }
+        }
+        let rebuilt_actors: HashSet<_> = old_node_actors.values().flatten().copied().collect();
+
+        // The metadata is restored if the migration fails, and nothing is changed on the workers.
+        self.migrate_actors_to(migrate_map, node_map).await?;
+
+        if let Err(err) = self.rebuild_actors(&old_node_actors, &rebuilt_actors).await {
+            error!("rebuild migrated actors failed: {}", err);
+            let prev_epoch = state.in_flight_prev_epoch;
+            self.recovery_and_track(prev_epoch, state, tracker).await;
+            return Err(err);
+        }
+        info!(
+            "{} actors of {} streaming jobs rebuilt for the migration",
+            rebuilt_actors.len(),
+            rebuilt_table_fragments.len()
+        );
+        Ok(())
+    }
+
+    /// Plans the migration of the actors on `draining_workers`. All the actors on a parallel unit
+    /// are moved to a free parallel unit, i.e., one without any actor, of a running worker.
+    ///
+    /// Returns an error right away if the running workers don't have enough free parallel units.
+    async fn plan_drain_migration(
+        &self,
+        table_fragments: &[TableFragments],
+        draining_workers: &HashSet<WorkerId>,
+    ) -> Result<(HashMap<ActorId, WorkerId>, HashMap<WorkerId, WorkerNode>)> {
+        let actor_parallel_units = table_fragments
+            .iter()
+            .flat_map(|table_fragments| table_fragments.actor_status.iter())
+            .filter_map(|(&actor_id, status)| {
+                status
+                    .parallel_unit
+                    .as_ref()
+                    .map(|parallel_unit| (actor_id, parallel_unit))
+            })
+            .collect_vec();
+        let used_parallel_units: HashSet<_> = actor_parallel_units
+            .iter()
+            .map(|(_, parallel_unit)| parallel_unit.id)
+            .collect();
+
+        // Only the free parallel units are left in the workers, which are taken by the migration.
+        let mut node_map: HashMap<WorkerId, WorkerNode> = self
+            .cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
+            .await
+            .into_iter()
+            .filter(is_streaming_worker)
+            .map(|mut node| {
+                node.parallel_units
+                    .retain(|parallel_unit| !used_parallel_units.contains(&parallel_unit.id));
+                (node.id, node)
+            })
+            .collect();
+        let mut free_parallel_units = node_map
+            .values()
+            .flat_map(|node| node.parallel_units.iter().map(|_| node.id))
+            .collect_vec();
+
+        let mut migrate_map = HashMap::new();
+        let mut parallel_unit_targets = HashMap::new();
+        for (actor_id, parallel_unit) in actor_parallel_units {
+            if !draining_workers.contains(&parallel_unit.worker_node_id) {
+                continue;
+            }
+            let target = match parallel_unit_targets.entry(parallel_unit.id) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    *entry.insert(free_parallel_units.pop().ok_or_else(|| {
+                        internal_error(format!(
+                            "no free parallel unit on the running workers to take actor {}",
+                            actor_id
+                        ))
+                    })?)
+                }
+            };
+            migrate_map.insert(actor_id, target);
+        }
+        let targets: HashSet<_> = migrate_map.values().copied().collect();
+        node_map.retain(|worker_id, _| targets.contains(worker_id));
+        Ok((migrate_map, node_map))
+    }
+
+    /// Returns the streaming jobs containing `actors`, along with the jobs connected to them
+    /// through the mviews they depend on, transitively. The actors of a job are connected by the
+    /// exchange channels, which are bound when the actors are built, so they must be rebuilt
+    /// together.
+    fn connected_table_fragments<'a>(
+        table_fragments: &[TableFragments],
+        actors: impl Iterator<Item = &'a ActorId>,
+    ) -> Vec<TableFragments> {
+        let mut edges: HashMap<TableId, HashSet<TableId>> = HashMap::new();
+        for table_fragments in table_fragments {
+            let table_id = table_fragments.table_id();
+            for dependent_table_id in table_fragments.dependent_table_ids() {
+                edges
+                    .entry(table_id)
+                    .or_default()
+                    .insert(dependent_table_id);
+                edges
+                    .entry(dependent_table_id)
+                    .or_default()
+                    .insert(table_id);
+            }
+        }
+
+        let actors: HashSet<_> = actors.copied().collect();
+        let mut to_visit = table_fragments
+            .iter()
+            .filter(|table_fragments| {
+                table_fragments
+                    .actor_status
+                    .keys()
+                    .any(|actor_id| actors.contains(actor_id))
+            })
+            .map(|table_fragments| table_fragments.table_id())
+            .collect_vec();
+        let mut visited = HashSet::new();
+        while let Some(table_id) = to_visit.pop() {
+            if visited.insert(table_id) {
+                to_visit.extend(edges.get(&table_id).into_iter().flatten().copied());
+            }
+        }
+
+        table_fragments
+            .iter()
+            .filter(|table_fragments| visited.contains(&table_fragments.table_id()))
+            .cloned()
+            .collect()
+    }
+
+    /// Drops `actors` from the workers in `old_node_actors`, and builds them on the workers they're
+    /// located on now.
+    async fn rebuild_actors(
+        &self,
+        old_node_actors: &BTreeMap<WorkerId, Vec<ActorId>>,
+        actors: &HashSet<ActorId>,
+    ) -> Result<()> {
+        let mut all_nodes = self
+            .cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
+            .await;
+        all_nodes.extend(
+            self.cluster_manager
+                .list_worker_node(WorkerType::ComputeNode, Some(State::Draining))
+                .await,
+        );
+        let node_map: HashMap<_, _> = all_nodes.into_iter().map(|node| (node.id, node)).collect();
+        let get_node = |node_id: &WorkerId| {
+            node_map.get(node_id).ok_or_else(|| {
+                internal_error(format!("worker {} not found to rebuild actors", node_id))
+            })
+        };
+
+        let futures = old_node_actors
+            .iter()
+            .map(|(node_id, actor_ids)| async move {
+                let node = get_node(node_id)?;
+                let client = self.env.stream_client_pool().get(node).await?;
+                debug!("drop actors on worker {}: {:?}", node_id, actor_ids);
+                client
+                    .to_owned()
+                    .drop_actors(DropActorsRequest {
+                        request_id: Uuid::new_v4().to_string(),
+                        actor_ids: actor_ids.clone(),
+                    })
+                    .await?;
+                Ok::<_, RwError>(())
+            });
+        try_join_all(futures).await?;
+
+        let node_actors: HashMap<_, _> = self
+            .fragment_manager
+            .all_node_actors(false)
+            .await
+            .into_iter()
+            .map(|(node_id, node_actors)| {
+                let node_actors = node_actors
+                    .into_iter()
+                    .filter(|actor| actors.contains(&actor.actor_id))
+                    .collect_vec();
+                (node_id, node_actors)
+            })
+            .filter(|(_, node_actors)| !node_actors.is_empty())
+            .collect();
+        let mut actor_infos = vec![];
+        for (node_id, node_actors) in &node_actors {
+            let host = get_node(node_id)?.host.clone();
+            actor_infos.extend(node_actors.iter().map(|actor| ActorInfo {
+                actor_id: actor.actor_id,
+                host: host.clone(),
+            }));
+        }
+
+        // All the actors are updated before any of them is built, as in a recovery.
+        for (node_id, node_actors) in &node_actors {
+            let client = self
+                .env
+                .stream_client_pool()
+                .get(get_node(node_id)?)
+                .await?;
+            client
+                .to_owned()
+                .broadcast_actor_info_table(BroadcastActorInfoTableRequest {
+                    info: actor_infos.clone(),
+                })
+                .await?;
+            client
+                .to_owned()
+                .update_actors(UpdateActorsRequest {
+                    request_id: Uuid::new_v4().to_string(),
+                    actors: node_actors.clone(),
+                    ..Default::default()
+                })
+                .await?;
+        }
+        for (node_id, node_actors) in &node_actors {
+            let client = self
+                .env
+                .stream_client_pool()
+                .get(get_node(node_id)?)
+                .await?;
+            client
+                .to_owned()
+                .build_actors(BuildActorsRequest {
+                    request_id: Uuid::new_v4().to_string(),
+                    actor_id: node_actors.iter().map(|actor| actor.actor_id).collect(),
+                })
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Migrates the actors on the expired workers to the newly joined ones, by updating the
+    /// fragments and the mappings of the tables.
+    ///
+    /// The fragments are restored if the table mappings fail to update, so that a failed
+    /// migration leaves the metadata as it was, and the next attempt of the recovery migrates the
+    /// actors again. The actors are not touched here: every attempt resets the compute nodes and
+    /// builds all the actors from the metadata anyway.
     async fn migrate_actors(&self, info: &BarrierActorInfo) -> Result<()> {
         debug!("start migrate actors.");
         // get expired workers
@@ -212,18 +661,73 @@ where
         }
         debug!("got expired workers {:#?}", expired_workers);
         let (migrate_map, node_map) = self.get_migrate_map_plan(info, &expired_workers).await;
-        // migrate actors in fragments, return updated fragments and pu to pu migrate plan
-        let (new_fragments, migrate_map) = self
+        self.migrate_actors_to(migrate_map, node_map).await
+    }
+
+    /// Migrates the actors in `migrate_map` to the workers in `node_map`. See
+    /// [`Self::migrate_actors`].
+    async fn migrate_actors_to(
+        &self,
+        migrate_map: HashMap<ActorId, WorkerId>,
+        node_map: HashMap<WorkerId, WorkerNode>,
+    ) -> Result<()> {
+        let plan = self
             .fragment_manager
-            .migrate_actors(&migrate_map, &node_map)
+            .plan_migration(&migrate_map, &node_map)
             .await?;
-        debug!("got parallel unit migrate plan {:#?}", migrate_map);
+        info!(
+            "migration plan: {}",
+            serde_json::to_string(&plan).map_err(|e| internal_error(e.to_string()))?
+        );
+        let old_fragments = self.fragment_manager.list_table_fragments().await?;
+        // migrate actors in fragments, return updated fragments
+        let new_fragments = self.fragment_manager.apply_migration(&plan).await?;
+        let migrate_map = plan.parallel_unit_migrate_map();
         // update mapping in table and notify frontends
-        let res = self
-            .catalog_manager
-            .update_table_mapping(&new_fragments, &migrate_map)
-            .await
-            .map_err(RwError::from);
+        let res = async {
+            fail_point!("migrate_actors_update_fragments_err", |_| Err(
+                RwError::from(ErrorCode::InternalError(
+                    "migrate_actors_update_fragments_err".to_string()
+                ))
+            ));
+            let old_mappings = self
+                .catalog_manager
+                .update_table_mapping(&new_fragments, &migrate_map)
+                .await
+                .map_err(RwError::from)?;
+            let res = async {
+                fail_point!("migrate_actors_update_table_mapping_err", |_| Err(
+                    RwError::from(ErrorCode::InternalError(
+                        "migrate_actors_update_table_mapping_err".to_string()
+                    ))
+                ));
+                Ok::<_, RwError>(())
+            }
+            .await;
+            if let Err(err) = res {
+                self.catalog_manager
+                    .restore_table_mapping(old_mappings)
+                    .await
+                    .map_err(RwError::from)?;
+                warn!("migrate actors failed, table mappings restored: {}", err);
+                return Err(err);
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(err) = res {
+            let updated_table_ids: HashSet<_> =
+                new_fragments.iter().map(|f| f.table_id()).collect();
+            let old_fragments = old_fragments
+                .into_iter()
+                .filter(|f| updated_table_ids.contains(&f.table_id()))
+                .collect_vec();
+            self.fragment_manager
+                .batch_update_table_fragments(&old_fragments)
+                .await?;
+            warn!("migrate actors failed, fragments restored: {}", err);
+            return Err(err);
+        }
         // update hash mapping
         for fragments in new_fragments {
             for (fragment_id, fragment) in fragments.fragments {
@@ -236,7 +740,7 @@ where
             }
         }
         debug!("migrate actors succeed.");
-        res
+        Ok(())
     }
 
     /// Sync all sources in compute nodes, the local source manager in compute nodes may be dirty
@@ -332,11 +836,11 @@ where
     /// Reset all compute nodes by calling `force_stop_actors`.
     async fn reset_compute_nodes(
         &self,
-        info: &BarrierActorInfo,
+        worker_nodes: impl Iterator<Item = &WorkerNode>,
         prev_epoch: &Epoch,
         new_epoch: &Epoch,
     ) -> Result<()> {
-        let futures = info.node_map.iter().map(|(_, worker_node)| async move {
+        let futures = worker_nodes.map(|worker_node| async move {
             let client = self.env.stream_client_pool().get(worker_node).await?;
             debug!("force stop actors: {}", worker_node.id);
             client
@@ -358,3 +862,156 @@ where
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "failpoints"))]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use risingwave_common::catalog::TableId;
+    use risingwave_common::util::compress::compress_data;
+    use risingwave_pb::catalog::Table;
+    use risingwave_pb::common::{HostAddress, ParallelUnitMapping};
+    use risingwave_pb::meta::table_fragments::{ActorState, ActorStatus, Fragment};
+    use risingwave_pb::stream_plan::StreamActor;
+
+    use super::*;
+    use crate::hummock::test_utils::setup_compute_env;
+    use crate::manager::CatalogManager;
+    use crate::model::{MetadataModel, TableFragments};
+    use crate::rpc::metrics::MetaMetrics;
+    use crate::stream::FragmentManager;
+
+    #[tokio::test]
+    async fn test_failpoints_migrate_actors_rollback() {
+        let (env, hummock_manager, cluster_manager, expired_worker) =
+            setup_compute_env(12340).await;
+        let new_host = HostAddress {
+            host: "127.0.0.1".to_string(),
+            port: 12341,
+        };
+        let new_worker = cluster_manager
+            .add_worker_node(
+                WorkerType::ComputeNode,
+                new_host.clone(),
+                4,
+                Default::default(),
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        cluster_manager
+            .activate_worker_node(new_host)
+            .await
+            .unwrap();
+        let new_worker = cluster_manager
+            .get_worker_by_id(new_worker.id)
+            .await
+            .unwrap()
+            .worker_node;
+
+        // One actor on the expired worker.
+        let parallel_unit = expired_worker.parallel_units[0].clone();
+        let (original_indices, data) = compress_data(&[parallel_unit.id; VIRTUAL_NODE_COUNT]);
+        let mapping = ParallelUnitMapping {
+            original_indices,
+            data,
+            ..Default::default()
+        };
+        let table = Table {
+            id: 0,
+            mapping: Some(mapping.clone()),
+            ..Default::default()
+        };
+        table.insert(env.meta_store()).await.unwrap();
+
+        let catalog_manager = Arc::new(CatalogManager::new(env.clone()).await.unwrap());
+        let fragment_manager = Arc::new(FragmentManager::new(env.clone()).await.unwrap());
+        let meta_store = env.meta_store_ref();
+        let barrier_manager = GlobalBarrierManager::new(
+            env,
+            cluster_manager,
+            catalog_manager,
+            fragment_manager.clone(),
+            hummock_manager,
+            Arc::new(MetaMetrics::new()),
+        );
+
+        let fragment = Fragment {
+            fragment_id: 0,
+            actors: vec![StreamActor {
+                actor_id: 1,
+                ..Default::default()
+            }],
+            vnode_mapping: Some(mapping),
+            ..Default::default()
+        };
+        let mut table_fragments = TableFragments::new(
+            TableId::new(0),
+            BTreeMap::from([(0, fragment)]),
+            Default::default(),
+        );
+        table_fragments.set_actor_status(BTreeMap::from([(
+            1,
+            ActorStatus {
+                parallel_unit: Some(parallel_unit),
+                state: ActorState::Running as i32,
+            },
+        )]));
+        fragment_manager
+            .start_create_table_fragments(table_fragments.clone())
+            .await
+            .unwrap();
+
+        let info = BarrierActorInfo {
+            node_map: HashMap::from([(new_worker.id, new_worker.clone())]),
+            actor_map: HashMap::from([(expired_worker.id, vec![1])]),
+            actor_map_to_send: HashMap::new(),
+        };
+        let list_table_fragments = || async {
+            fragment_manager
+                .list_table_fragments()
+                .await
+                .unwrap()
+                .iter()
+                .map(MetadataModel::to_protobuf)
+                .collect_vec()
+        };
+
+        let select_table = || async {
+            Table::select(meta_store.as_ref(), &0)
+                .await
+                .unwrap()
+                .unwrap()
+        };
+
+        // A failure at any stage leaves the fragments and the table mapping as they were.
+        for fail_point in [
+            "migrate_actors_update_fragments_err",
+            "migrate_actors_update_table_mapping_err",
+        ] {
+            fail::cfg(fail_point, "return").unwrap();
+            assert!(barrier_manager.migrate_actors(&info).await.is_err());
+            assert_eq!(
+                list_table_fragments().await,
+                vec![table_fragments.to_protobuf()]
+            );
+            assert_eq!(select_table().await, table);
+            fail::remove(fail_point);
+        }
+
+        barrier_manager.migrate_actors(&info).await.unwrap();
+        let table_fragments = fragment_manager
+            .select_table_fragments_by_table_id(&TableId::new(0))
+            .await
+            .unwrap();
+        assert_eq!(
+            table_fragments.actor_status[&1]
+                .get_parallel_unit()
+                .unwrap()
+                .worker_node_id,
+            new_worker.id
+        );
+        let mapping = select_table().await.mapping.unwrap();
+        assert!(!mapping.data.contains(&expired_worker.parallel_units[0].id));
+    }
+}
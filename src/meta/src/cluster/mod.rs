@@ -14,7 +14,7 @@
 
 use std::cmp;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::ops::Add;
 use std::sync::Arc;
@@ -24,7 +24,7 @@ use itertools::Itertools;
 use risingwave_common::error::{internal_error, ErrorCode, Result};
 use risingwave_common::types::ParallelUnitId;
 use risingwave_pb::common::worker_node::State;
-use risingwave_pb::common::{HostAddress, ParallelUnit, WorkerNode, WorkerType};
+use risingwave_pb::common::{HostAddress, ParallelUnit, WorkerNode, WorkerRole, WorkerType};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use tokio::sync::oneshot::Sender;
 use tokio::sync::{RwLock, RwLockReadGuard};
@@ -58,6 +58,12 @@ impl Hash for WorkerKey {
 /// The id preserved for the meta node. Note that there's no such entry in cluster manager.
 pub const META_NODE_ID: u32 = 0;
 
+/// Whether streaming actors can be scheduled onto the worker, i.e. it's not a serving-only compute
+/// node.
+pub fn is_streaming_worker(worker: &WorkerNode) -> bool {
+    worker.role() != WorkerRole::Serving
+}
+
 /// [`ClusterManager`] manager cluster/worker meta data in [`MetaStore`].
 pub struct ClusterManager<S: MetaStore> {
     env: MetaSrvEnv<S>,
@@ -97,7 +103,15 @@ where
         r#type: WorkerType,
         host_address: HostAddress,
         worker_node_parallelism: usize,
+        labels: HashMap<String, String>,
+        role: WorkerRole,
     ) -> Result<WorkerNode> {
+        if r#type == WorkerType::ComputeNode && worker_node_parallelism == 0 {
+            return Err(ErrorCode::InvalidParameterValue(
+                "compute node should have at least 1 parallel unit".to_string(),
+            )
+            .into());
+        }
         let mut core = self.core.write().await;
         match core.get_worker_by_host(host_address.clone()) {
             // TODO(zehua): update parallelism when the worker exists.
@@ -122,6 +136,8 @@ where
                     host: Some(host_address.clone()),
                     state: State::Starting as i32,
                     parallel_units,
+                    labels,
+                    role: role as i32,
                 };
 
                 let worker = Worker::from_protobuf(worker_node.clone());
@@ -140,7 +156,8 @@ where
     pub async fn activate_worker_node(&self, host_address: HostAddress) -> Result<()> {
         let mut core = self.core.write().await;
         let mut worker = core.get_worker_by_host_checked(host_address.clone())?;
-        if worker.worker_node.state == State::Running as i32 {
+        // A draining worker should never be activated again.
+        if worker.worker_node.state != State::Starting as i32 {
             return Ok(());
         }
         worker.worker_node.state = State::Running as i32;
@@ -159,11 +176,43 @@ where
         Ok(())
     }
 
+    /// Mark the worker as `Draining`, so that it will be excluded from the running workers and no
+    /// more actors will be scheduled onto its parallel units. The actors on it are expected to be
+    /// migrated by the barrier manager before it is deleted.
+    pub async fn drain_worker_node(&self, host_address: HostAddress) -> Result<WorkerNode> {
+        let mut core = self.core.write().await;
+        let mut worker = core.get_worker_by_host_checked(host_address)?;
+        if worker.worker_type() != WorkerType::ComputeNode {
+            return Err(ErrorCode::InvalidParameterValue(format!(
+                "only compute node can be drained, got {:?}",
+                worker.worker_type()
+            ))
+            .into());
+        }
+        if worker.worker_node.state == State::Draining as i32 {
+            return Ok(worker.to_protobuf());
+        }
+        worker.worker_node.state = State::Draining as i32;
+        worker.insert(self.env.meta_store()).await?;
+
+        core.drain_worker_node(worker.clone());
+
+        // Notify frontends not to schedule batch tasks onto the draining node.
+        self.env
+            .notification_manager()
+            .notify_frontend(Operation::Delete, Info::Node(worker.worker_node.clone()))
+            .await;
+
+        Ok(worker.to_protobuf())
+    }
+
     pub async fn delete_worker_node(&self, host_address: HostAddress) -> Result<()> {
         let mut core = self.core.write().await;
         let worker = core.get_worker_by_host_checked(host_address.clone())?;
         let worker_type = worker.worker_type();
         let worker_node = worker.to_protobuf();
+        // Frontends have been notified to delete the node when it started draining.
+        let notified = worker_node.state == State::Draining as i32;
 
         // Persist deletion.
         Worker::delete(self.env.meta_store(), &host_address).await?;
@@ -171,8 +220,14 @@ where
         // Update core.
         core.delete_worker_node(worker);
 
+        // Drop the connection to the deleted node at once.
+        self.env
+            .stream_client_pool()
+            .invalidate(worker_node.id)
+            .await;
+
         // Notify frontends to delete compute node.
-        if worker_type == WorkerType::ComputeNode {
+        if worker_type == WorkerType::ComputeNode && !notified {
             self.env
                 .notification_manager()
                 .notify_frontend(Operation::Delete, Info::Node(worker_node.clone()))
@@ -290,14 +345,31 @@ where
         core.list_worker_node(worker_type, worker_state)
     }
 
+    /// Get live nodes with the specified type and all labels in `label_selector`.
+    pub async fn list_worker_node_by_label(
+        &self,
+        worker_type: WorkerType,
+        label_selector: &HashMap<String, String>,
+    ) -> Vec<WorkerNode> {
+        let core = self.core.read().await;
+        core.list_worker_node_by_label(worker_type, label_selector)
+    }
+
     pub async fn list_parallel_units(&self) -> Vec<ParallelUnit> {
         let core = self.core.read().await;
         core.list_parallel_units()
     }
 
-    pub async fn get_parallel_unit_count(&self) -> usize {
+    /// List the parallel units that streaming actors can be scheduled onto, i.e. those not on the
+    /// serving-only compute nodes.
+    pub async fn list_streaming_parallel_units(&self) -> Vec<ParallelUnit> {
+        let core = self.core.read().await;
+        core.list_streaming_parallel_units()
+    }
+
+    pub async fn get_streaming_parallel_unit_count(&self) -> usize {
         let core = self.core.read().await;
-        core.get_parallel_unit_count()
+        core.list_streaming_parallel_units().len()
     }
 
     /// Generate `parallel_degree` parallel units.
@@ -344,7 +416,9 @@ impl ClusterManagerCore {
 
         workers.into_iter().for_each(|w| {
             worker_map.insert(WorkerKey(w.key().unwrap()), w.clone());
-            parallel_units.extend(w.worker_node.parallel_units);
+            if w.worker_node.state != State::Draining as i32 {
+                parallel_units.extend(w.worker_node.parallel_units);
+            }
         });
 
         Ok(Self {
@@ -382,7 +456,13 @@ impl ClusterManagerCore {
             .insert(WorkerKey(worker.key().unwrap()), worker);
     }
 
-    fn delete_worker_node(&mut self, worker: Worker) {
+    /// Update the draining worker and remove its parallel units from the schedulable ones.
+    fn drain_worker_node(&mut self, worker: Worker) {
+        self.remove_parallel_units(&worker);
+        self.update_worker_node(worker);
+    }
+
+    fn remove_parallel_units(&mut self, worker: &Worker) {
         worker
             .worker_node
             .parallel_units
@@ -390,6 +470,10 @@ impl ClusterManagerCore {
             .for_each(|parallel_unit| {
                 self.parallel_units.retain(|p| p.id != parallel_unit.id);
             });
+    }
+
+    fn delete_worker_node(&mut self, worker: Worker) {
+        self.remove_parallel_units(&worker);
         self.workers.remove(&WorkerKey(worker.key().unwrap()));
     }
 
@@ -409,12 +493,37 @@ impl ClusterManagerCore {
             .collect_vec()
     }
 
+    fn list_worker_node_by_label(
+        &self,
+        worker_type: WorkerType,
+        label_selector: &HashMap<String, String>,
+    ) -> Vec<WorkerNode> {
+        self.list_worker_node(worker_type, None)
+            .into_iter()
+            .filter(|w| {
+                label_selector
+                    .iter()
+                    .all(|(key, value)| w.labels.get(key) == Some(value))
+            })
+            .collect_vec()
+    }
+
     fn list_parallel_units(&self) -> Vec<ParallelUnit> {
         self.parallel_units.clone()
     }
 
-    fn get_parallel_unit_count(&self) -> usize {
-        self.parallel_units.len()
+    fn list_streaming_parallel_units(&self) -> Vec<ParallelUnit> {
+        let serving_workers: HashSet<WorkerId> = self
+            .workers
+            .values()
+            .filter(|w| !is_streaming_worker(&w.worker_node))
+            .map(|w| w.worker_id())
+            .collect();
+        self.parallel_units
+            .iter()
+            .filter(|p| !serving_workers.contains(&p.worker_node_id))
+            .cloned()
+            .collect_vec()
     }
 
     fn update_worker_ttl(&mut self, host_address: HostAddress, ttl: Duration) {
@@ -460,7 +569,13 @@ mod tests {
                 port: 5000 + i as i32,
             };
             let worker_node = cluster_manager
-                .add_worker_node(WorkerType::ComputeNode, fake_host_address, fake_parallelism)
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    fake_host_address,
+                    fake_parallelism,
+                    HashMap::new(),
+                    WorkerRole::Both,
+                )
                 .await
                 .unwrap();
             worker_nodes.push(worker_node);
@@ -482,6 +597,76 @@ mod tests {
         }
         assert_cluster_manager(&cluster_manager, fake_parallelism).await;
 
+        // Compute node without any parallel unit is rejected.
+        let fake_host_address = HostAddress {
+            host: "localhost".to_string(),
+            port: 5000 + worker_count as i32,
+        };
+        assert!(cluster_manager
+            .add_worker_node(
+                WorkerType::ComputeNode,
+                fake_host_address,
+                0,
+                HashMap::new(),
+                WorkerRole::Both
+            )
+            .await
+            .is_err());
+        assert_cluster_manager(&cluster_manager, fake_parallelism).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drain_worker_node() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager = ClusterManager::new(env, Duration::new(0, 0)).await?;
+
+        let fake_parallelism = 4;
+        for i in 0..2 {
+            let fake_host_address = HostAddress {
+                host: "localhost".to_string(),
+                port: 5000 + i,
+            };
+            cluster_manager
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    fake_host_address.clone(),
+                    fake_parallelism,
+                    HashMap::new(),
+                    WorkerRole::Both,
+                )
+                .await?;
+            cluster_manager
+                .activate_worker_node(fake_host_address)
+                .await?;
+        }
+
+        let draining_host_address = HostAddress {
+            host: "localhost".to_string(),
+            port: 5000,
+        };
+        let worker_node = cluster_manager
+            .drain_worker_node(draining_host_address.clone())
+            .await?;
+        assert_eq!(worker_node.state, State::Draining as i32);
+        assert_cluster_manager(&cluster_manager, fake_parallelism).await;
+
+        // The draining node is excluded from running nodes and will not be activated again.
+        cluster_manager
+            .activate_worker_node(draining_host_address.clone())
+            .await?;
+        let running_nodes = cluster_manager
+            .list_worker_node(WorkerType::ComputeNode, Some(State::Running))
+            .await;
+        assert_eq!(running_nodes.len(), 1);
+        assert_ne!(running_nodes[0].id, worker_node.id);
+
+        cluster_manager
+            .delete_worker_node(draining_host_address)
+            .await?;
+        assert_cluster_manager(&cluster_manager, fake_parallelism).await;
+
         Ok(())
     }
 
@@ -509,6 +694,22 @@ mod tests {
                 WorkerType::ComputeNode,
                 fake_host_address_2,
                 fake_parallelism,
+                HashMap::new(),
+                WorkerRole::Both,
+            )
+            .await
+            .unwrap();
+        let fake_host_address_3 = HostAddress {
+            host: "127.0.0.1".to_string(),
+            port: 3,
+        };
+        let _frontend_node = cluster_manager
+            .add_worker_node(
+                WorkerType::Frontend,
+                fake_host_address_3,
+                0,
+                HashMap::new(),
+                WorkerRole::Unspecified,
             )
             .await
             .unwrap();
@@ -520,6 +721,14 @@ mod tests {
                 .len(),
             2
         );
+        // One live frontend
+        assert_eq!(
+            cluster_manager
+                .list_worker_node(WorkerType::Frontend, None)
+                .await
+                .len(),
+            1
+        );
 
         let ttl = cluster_manager.max_heartbeat_interval;
         let check_interval = std::cmp::min(Duration::from_millis(100), ttl / 4);
@@ -557,6 +766,11 @@ mod tests {
                 .len(),
             1
         );
+        // Frontends are expired in the same way as compute nodes.
+        assert!(cluster_manager
+            .list_worker_node(WorkerType::Frontend, None)
+            .await
+            .is_empty());
 
         shutdown_sender.send(()).unwrap();
         join_handle.await.unwrap();
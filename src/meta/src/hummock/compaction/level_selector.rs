@@ -350,6 +350,7 @@ impl LevelSelector for DynamicLevelSelector {
 
 #[cfg(test)]
 pub mod tests {
+    use std::collections::HashMap;
     use std::ops::Range;
 
     use itertools::Itertools;
@@ -378,6 +379,8 @@ pub mod tests {
             }),
             file_size: (right - left + 1) as u64,
             table_ids: vec![],
+            table_key_counts: HashMap::new(),
+            range_tombstone_count: 0,
         }
     }
 
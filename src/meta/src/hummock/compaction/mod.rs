@@ -134,6 +134,18 @@ impl CompactStatus {
         let select_level_id = ret.input.input_levels[0].level_idx;
         let target_level_id = ret.input.target_level;
 
+        // Tasks of a group may run concurrently, so a task is only issued if its key range does
+        // not overlap any other pending task writing to the same level.
+        if self.overlaps_pending_tasks(levels, task_id, &ret.input) {
+            for level_handler in &mut self.level_handlers {
+                level_handler.remove_task(task_id);
+            }
+            return None;
+        }
+        if !self.level_handlers[target_level_id].has_pending_task(task_id) {
+            self.level_handlers[target_level_id].add_pending_task(task_id, &[]);
+        }
+
         let splits = if ret.splits.is_empty() {
             vec![KeyRange::inf()]
         } else {
@@ -216,11 +228,45 @@ impl CompactStatus {
         )
     }
 
+    /// Whether the input of the task overlaps that of any other pending task with the same target
+    /// level. Each task is registered in the level handler of its target level, even if it
+    /// compacts no SST of that level.
+    fn overlaps_pending_tasks(
+        &self,
+        levels: &[Level],
+        task_id: HummockCompactionTaskId,
+        input: &CompactionInput,
+    ) -> bool {
+        let overlap_strategy = create_overlap_strategy(self.compaction_config.compaction_mode());
+        let pending_task_ids = self.level_handlers[input.target_level].pending_tasks_ids();
+        pending_task_ids
+            .into_iter()
+            .filter(|pending_task_id| *pending_task_id != task_id)
+            .any(|pending_task_id| {
+                let mut overlap_info = overlap_strategy.create_overlap_info();
+                for (level_idx, level) in levels.iter().enumerate() {
+                    for table in &level.table_infos {
+                        if self.level_handlers[level_idx].pending_task_id(&table.id)
+                            == Some(pending_task_id)
+                        {
+                            overlap_info.update(table);
+                        }
+                    }
+                }
+                input
+                    .input_levels
+                    .iter()
+                    .flat_map(|level| level.table_infos.iter())
+                    .any(|table| overlap_info.check_overlap(table))
+            })
+    }
+
     /// Declares a task is either finished or canceled.
     pub fn report_compact_task(&mut self, compact_task: &CompactTask) {
         for level in &compact_task.input_ssts {
             self.level_handlers[level.level_idx as usize].remove_task(compact_task.task_id);
         }
+        self.level_handlers[compact_task.target_level as usize].remove_task(compact_task.task_id);
     }
 
     pub fn cancel_compaction_tasks_if<F: Fn(u64) -> bool>(&mut self, should_cancel: F) -> u32 {
@@ -304,3 +350,70 @@ pub trait CompactionPicker {
         level_handlers: &mut [LevelHandler],
     ) -> Option<CompactionInput>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hummock::compaction::compaction_config::CompactionConfigBuilder;
+    use crate::hummock::compaction::level_selector::tests::{generate_level, generate_table};
+
+    #[test]
+    fn test_overlaps_pending_tasks() {
+        let config = CompactionConfigBuilder::new().max_level(4).build();
+        let mut status = CompactStatus::new(1, Arc::new(config));
+        let levels = vec![
+            generate_level(0, vec![]),
+            generate_level(
+                1,
+                vec![
+                    generate_table(1, 1, 0, 100, 1),
+                    generate_table(2, 1, 200, 300, 1),
+                ],
+            ),
+        ];
+
+        // Task 1 compacts the first SST of level 1 into level 1.
+        status.level_handlers[1].add_pending_task(1, &levels[1].table_infos[..1]);
+
+        let input = |target_level, table| CompactionInput {
+            input_levels: vec![InputLevel {
+                level_idx: 0,
+                level_type: LevelType::Overlapping as i32,
+                table_infos: vec![table],
+            }],
+            target_level,
+            target_sub_level_id: 0,
+        };
+
+        // Overlaps the SST of task 1.
+        assert!(status.overlaps_pending_tasks(
+            &levels,
+            2,
+            &input(1, generate_table(3, 1, 50, 150, 2))
+        ));
+        // The task itself is not a conflict.
+        assert!(!status.overlaps_pending_tasks(
+            &levels,
+            1,
+            &input(1, generate_table(3, 1, 50, 150, 2))
+        ));
+        // Overlaps only the SST of level 1 not compacted by any task.
+        assert!(!status.overlaps_pending_tasks(
+            &levels,
+            2,
+            &input(1, generate_table(4, 1, 250, 350, 2))
+        ));
+        // Doesn't overlap any SST.
+        assert!(!status.overlaps_pending_tasks(
+            &levels,
+            2,
+            &input(1, generate_table(5, 1, 400, 500, 2))
+        ));
+        // Task 1 writes to another level.
+        assert!(!status.overlaps_pending_tasks(
+            &levels,
+            2,
+            &input(2, generate_table(3, 1, 50, 150, 2))
+        ));
+    }
+}
@@ -21,6 +21,7 @@ use risingwave_hummock_sdk::compact::compact_task_to_string;
 use risingwave_hummock_sdk::CompactionGroupId;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::oneshot::Receiver;
+use tokio::sync::Semaphore;
 
 use crate::hummock::compaction::CompactStatus;
 use crate::hummock::error::Error;
@@ -64,12 +65,18 @@ impl CompactionRequestChannel {
 }
 
 /// Schedules compaction task picking and assignment.
+///
+/// Up to `max_concurrent_tasks` requests are processed concurrently, so tasks of different
+/// compaction groups, or of different key ranges in the same group, can be assigned to different
+/// compactors in parallel. `CompactStatus` guarantees that concurrent tasks of a compaction group
+/// never write to overlapping key ranges of the same level.
 pub struct CompactionScheduler<S>
 where
     S: MetaStore,
 {
     hummock_manager: HummockManagerRef<S>,
     compactor_manager: CompactorManagerRef,
+    max_concurrent_tasks: usize,
 }
 
 impl<S> CompactionScheduler<S>
@@ -79,21 +86,31 @@ where
     pub fn new(
         hummock_manager: HummockManagerRef<S>,
         compactor_manager: CompactorManagerRef,
+        max_concurrent_tasks: usize,
     ) -> Self {
         Self {
             hummock_manager,
             compactor_manager,
+            max_concurrent_tasks,
         }
     }
 
-    pub async fn start(&self, mut shutdown_rx: Receiver<()>) {
+    pub async fn start(self: &Arc<Self>, mut shutdown_rx: Receiver<()>) {
         let (request_tx, mut request_rx) =
             tokio::sync::mpsc::unbounded_channel::<CompactionGroupId>();
         let request_channel = Arc::new(CompactionRequestChannel::new(request_tx));
         self.hummock_manager
             .set_compaction_scheduler(request_channel.clone());
+        let task_permits = Arc::new(Semaphore::new(self.max_concurrent_tasks));
         tracing::info!("Start compaction scheduler.");
         'compaction_trigger: loop {
+            let permit = tokio::select! {
+                permit = task_permits.clone().acquire_owned() => permit.unwrap(),
+                // Shutdown compactor
+                _ = &mut shutdown_rx => {
+                    break 'compaction_trigger;
+                }
+            };
             let compaction_group: CompactionGroupId = tokio::select! {
                 compaction_group = request_rx.recv() => {
                     match compaction_group {
@@ -108,8 +125,14 @@ where
                     break 'compaction_trigger;
                 }
             };
-            self.pick_and_assign(compaction_group, request_channel.clone())
-                .await;
+            let scheduler = self.clone();
+            let request_channel = request_channel.clone();
+            tokio::spawn(async move {
+                scheduler
+                    .pick_and_assign(compaction_group, request_channel)
+                    .await;
+                drop(permit);
+            });
         }
         tracing::info!("Compaction scheduler is stopped");
     }
@@ -163,7 +186,6 @@ where
                 }
                 Some(compactor) => compactor,
             };
-            // TODO: skip busy compactor
 
             // 2.2 Send the compaction task to the compactor.
             let send_task = async {
@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use rand::Rng;
 use risingwave_common::error::{ErrorCode, Result, ToErrorStr};
 use risingwave_hummock_sdk::HummockContextId;
-use risingwave_pb::hummock::{CompactTask, SubscribeCompactTasksResponse, VacuumTask};
+use risingwave_pb::hummock::{
+    CancelCompactTask, CompactTask, SubscribeCompactTasksResponse, VacuumTask,
+};
 use tokio::sync::mpsc::{Receiver, Sender};
 
 const STREAM_BUFFER_SIZE: usize = 4;
@@ -27,6 +30,8 @@ pub type CompactorManagerRef = Arc<CompactorManager>;
 pub struct Compactor {
     context_id: HummockContextId,
     sender: Sender<Result<SubscribeCompactTasksResponse>>,
+    /// The number of compact tasks assigned to the compactor and not yet reported.
+    assigned_task_num: AtomicUsize,
 }
 
 impl Compactor {
@@ -40,6 +45,19 @@ impl Compactor {
             .send(Ok(SubscribeCompactTasksResponse {
                 compact_task,
                 vacuum_task,
+                cancel_compact_task: None,
+            }))
+            .await
+            .map_err(|e| ErrorCode::InternalError(e.to_error_str()).into())
+    }
+
+    /// Instructs the compactor to abort the compact task.
+    pub async fn cancel_task(&self, task_id: u64) -> Result<()> {
+        self.sender
+            .send(Ok(SubscribeCompactTasksResponse {
+                compact_task: None,
+                vacuum_task: None,
+                cancel_compact_task: Some(CancelCompactTask { task_id }),
             }))
             .await
             .map_err(|e| ErrorCode::InternalError(e.to_error_str()).into())
@@ -48,14 +66,19 @@ impl Compactor {
     pub fn context_id(&self) -> HummockContextId {
         self.context_id
     }
+
+    pub fn assigned_task_num(&self) -> usize {
+        self.assigned_task_num.load(Ordering::Relaxed)
+    }
 }
 
 struct CompactorManagerInner {
     /// Senders of stream to available compactors
     compactors: Vec<Arc<Compactor>>,
 
-    /// We use round-robin approach to assign tasks to compactors.
-    /// This field indexes the compactor which the next task should be assigned to.
+    /// Tasks are assigned to the compactor with the fewest assigned tasks, and compactors with the
+    /// same number of tasks are chosen in a round-robin approach.
+    /// This field indexes the compactor which the search for the next compactor starts from.
     next_compactor: usize,
 }
 
@@ -94,18 +117,50 @@ impl CompactorManager {
         }
     }
 
-    /// Gets next compactor to assign task.
+    /// Gets next compactor to assign task, preferring idle ones.
     pub fn next_compactor(&self) -> Option<Arc<Compactor>> {
         let mut guard = self.inner.write();
         if guard.compactors.is_empty() {
             return None;
         }
-        let compactor_index = guard.next_compactor % guard.compactors.len();
+        let compactor_num = guard.compactors.len();
+        let compactor_index = (0..compactor_num)
+            .map(|offset| (guard.next_compactor + offset) % compactor_num)
+            .min_by_key(|index| guard.compactors[*index].assigned_task_num())
+            .unwrap();
         let compactor = guard.compactors[compactor_index].clone();
-        guard.next_compactor += 1;
+        guard.next_compactor = compactor_index + 1;
         Some(compactor)
     }
 
+    /// Records a compact task assigned to the compactor.
+    pub fn assign_task(&self, context_id: HummockContextId) {
+        if let Some(compactor) = self.get_compactor(context_id) {
+            compactor.assigned_task_num.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records a compact task of the compactor is reported, either finished or cancelled.
+    pub fn finish_task(&self, context_id: HummockContextId) {
+        if let Some(compactor) = self.get_compactor(context_id) {
+            // The compactor may have been added again since the task is assigned.
+            let _ = compactor.assigned_task_num.fetch_update(
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+                |num| num.checked_sub(1),
+            );
+        }
+    }
+
+    pub fn get_compactor(&self, context_id: HummockContextId) -> Option<Arc<Compactor>> {
+        self.inner
+            .read()
+            .compactors
+            .iter()
+            .find(|c| c.context_id == context_id)
+            .cloned()
+    }
+
     pub fn random_compactor(&self) -> Option<Arc<Compactor>> {
         let guard = self.inner.read();
         if guard.compactors.is_empty() {
@@ -127,6 +182,7 @@ impl CompactorManager {
         guard.compactors.push(Arc::new(Compactor {
             context_id,
             sender: tx,
+            assigned_task_num: AtomicUsize::new(0),
         }));
         tracing::info!("Added compactor {}", context_id);
         rx
@@ -291,5 +347,16 @@ mod tests {
             let compactor = compactor_manager.next_compactor().unwrap();
             assert_eq!(compactor.context_id as usize, i % receivers.len());
         }
+
+        // Idle compactors are preferred.
+        for context_id in 0..3 {
+            compactor_manager.assign_task(context_id);
+        }
+        for i in 0..4 {
+            let compactor = compactor_manager.next_compactor().unwrap();
+            assert_eq!(compactor.context_id as usize, 3 + i % 2);
+        }
+        compactor_manager.finish_task(1);
+        assert_eq!(compactor_manager.next_compactor().unwrap().context_id, 1);
     }
 }
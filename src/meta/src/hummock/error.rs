@@ -14,7 +14,7 @@
 
 use risingwave_common::error::{ErrorCode, ToErrorStr};
 use risingwave_hummock_sdk::compaction_group::StateTableId;
-use risingwave_hummock_sdk::{CompactionGroupId, HummockContextId};
+use risingwave_hummock_sdk::{CompactionGroupId, HummockContextId, HummockEpoch};
 use thiserror::Error;
 
 use crate::model::MetadataModelError;
@@ -38,6 +38,8 @@ pub enum Error {
     InvalidCompactionGroup(CompactionGroupId),
     #[error("compaction group member {0} not found")]
     InvalidCompactionGroupMember(StateTableId),
+    #[error("snapshot at epoch {0} is not available, the safe epoch is {1} and the max committed epoch is {2}")]
+    SnapshotUnavailable(HummockEpoch, HummockEpoch, HummockEpoch),
     #[error("internal error: {0}")]
     InternalError(String),
 }
@@ -90,6 +92,12 @@ impl From<Error> for ErrorCode {
             Error::InvalidCompactionGroupMember(prefix) => {
                 ErrorCode::InternalError(format!("invalid compaction group member {}", prefix))
             }
+            Error::SnapshotUnavailable(epoch, safe_epoch, max_committed_epoch) => {
+                ErrorCode::InternalError(format!(
+                    "snapshot at epoch {} is not available, the safe epoch is {} and the max committed epoch is {}",
+                    epoch, safe_epoch, max_committed_epoch
+                ))
+            }
         }
     }
 }
@@ -14,7 +14,7 @@
 
 use std::borrow::{Borrow, BorrowMut};
 use std::cmp;
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::future::Future;
 use std::ops::Bound::{Excluded, Included};
 use std::ops::{DerefMut, RangeBounds};
@@ -35,8 +35,9 @@ use risingwave_hummock_sdk::{
 };
 use risingwave_pb::hummock::hummock_version::Levels;
 use risingwave_pb::hummock::{
-    CompactTask, CompactTaskAssignment, HummockPinnedSnapshot, HummockPinnedVersion,
-    HummockSnapshot, HummockVersion, HummockVersionDelta, Level, LevelDelta, LevelType,
+    CompactTask, CompactTaskAssignment, CompactTaskInfo, CompactTaskProgress,
+    HummockPinnedSnapshot, HummockPinnedVersion, HummockSnapshot, HummockVersion,
+    HummockVersionDelta, Level, LevelDelta, LevelType,
 };
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use risingwave_pb::meta::MetaLeaderInfo;
@@ -47,7 +48,9 @@ use crate::hummock::compaction::{CompactStatus, ManualCompactionOption};
 use crate::hummock::compaction_group::manager::CompactionGroupManagerRef;
 use crate::hummock::compaction_scheduler::CompactionRequestChannelRef;
 use crate::hummock::error::{Error, Result};
-use crate::hummock::metrics_utils::{trigger_commit_stat, trigger_sst_stat};
+use crate::hummock::metrics_utils::{
+    trigger_commit_stat, trigger_compact_task_stat, trigger_sst_stat,
+};
 use crate::hummock::CompactorManagerRef;
 use crate::manager::{IdCategory, MetaSrvEnv};
 use crate::model::{BTreeMapTransaction, MetadataModel, ValTransaction, VarTransaction};
@@ -88,6 +91,8 @@ struct Compaction {
     compaction_statuses: BTreeMap<CompactionGroupId, CompactStatus>,
     /// Available compaction task ids for use
     next_task_ids: VecDeque<HummockCompactionTaskId>,
+    /// Progress of the assigned compaction tasks, reported by the compactors. It's not persisted.
+    compact_task_progress: HashMap<HummockCompactionTaskId, CompactTaskProgress>,
 }
 
 impl Compaction {
@@ -188,6 +193,9 @@ struct Versioning {
     // - AND It either contains no SST to delete, or all these SSTs has been deleted. See
     //   `extend_ssts_to_delete_from_deltas`.
     deltas_to_delete: Vec<HummockVersionId>,
+    // Snapshots pinned by name for external readers, see `pin_named_snapshot`. They are not
+    // persisted, so the readers have to pin them again after meta restarts.
+    named_snapshots: BTreeMap<String, NamedSnapshot>,
 
     // Persistent states below
 
@@ -199,6 +207,25 @@ struct Versioning {
     checkpoint_version: HummockVersion,
 }
 
+struct NamedSnapshot {
+    epoch: HummockEpoch,
+    /// `None` if the TTL is too long to be represented, in which case it never expires.
+    expire_at: Option<Instant>,
+}
+
+impl NamedSnapshot {
+    fn new(epoch: HummockEpoch, now: Instant, ttl: Duration) -> Self {
+        Self {
+            epoch,
+            expire_at: now.checked_add(ttl),
+        }
+    }
+
+    fn is_alive(&self, now: Instant) -> bool {
+        self.expire_at.map_or(true, |expire_at| expire_at > now)
+    }
+}
+
 impl Versioning {
     pub fn min_pinned_version_id(&self) -> HummockVersionId {
         let mut min_pinned_version_id = HummockVersionId::MAX;
@@ -572,6 +599,71 @@ where
         })
     }
 
+    /// Pins the current snapshot under `name` for an external reader, until it's unpinned by
+    /// `unpin_named_snapshot` or `ttl` passes, so that a crashed reader can't hold back compaction
+    /// forever. Pinning an existing name again renews its TTL and returns the same snapshot.
+    ///
+    /// Like `pin_snapshot`, this only prevents compaction from dropping the versions of keys
+    /// visible at the snapshot. An SST is vacuumed once no pinned version refers to it, so the
+    /// reader still relies on the version pinned by the worker serving the read.
+    #[named]
+    pub async fn pin_named_snapshot(&self, name: String, ttl: Duration) -> Result<HummockSnapshot> {
+        let max_committed_epoch = self.max_committed_epoch.load(Ordering::Relaxed);
+        let mut versioning_guard = write_lock!(self, versioning).await;
+        let _timer = start_measure_real_process_timer!(self);
+        let now = Instant::now();
+        versioning_guard
+            .named_snapshots
+            .retain(|_, snapshot| snapshot.is_alive(now));
+        let epoch = versioning_guard
+            .named_snapshots
+            .get(&name)
+            .map_or(max_committed_epoch, |snapshot| snapshot.epoch);
+        versioning_guard
+            .named_snapshots
+            .insert(name, NamedSnapshot::new(epoch, now, ttl));
+        Ok(HummockSnapshot { epoch })
+    }
+
+    /// Pins the snapshot at `epoch` under `name`, like `pin_named_snapshot`. Fails if `epoch` is
+    /// below the safe epoch, where the snapshot may have been compacted away, or is not committed
+    /// yet.
+    #[named]
+    pub async fn pin_named_snapshot_at(
+        &self,
+        name: String,
+        epoch: HummockEpoch,
+        ttl: Duration,
+    ) -> Result<HummockSnapshot> {
+        let mut versioning_guard = write_lock!(self, versioning).await;
+        let _timer = start_measure_real_process_timer!(self);
+        let safe_epoch = versioning_guard.current_version.safe_epoch;
+        let max_committed_epoch = versioning_guard.current_version.max_committed_epoch;
+        if epoch < safe_epoch || epoch > max_committed_epoch {
+            return Err(Error::SnapshotUnavailable(
+                epoch,
+                safe_epoch,
+                max_committed_epoch,
+            ));
+        }
+        let now = Instant::now();
+        versioning_guard
+            .named_snapshots
+            .retain(|_, snapshot| snapshot.is_alive(now));
+        versioning_guard
+            .named_snapshots
+            .insert(name, NamedSnapshot::new(epoch, now, ttl));
+        Ok(HummockSnapshot { epoch })
+    }
+
+    #[named]
+    pub async fn unpin_named_snapshot(&self, name: &str) -> Result<()> {
+        let mut versioning_guard = write_lock!(self, versioning).await;
+        let _timer = start_measure_real_process_timer!(self);
+        versioning_guard.named_snapshots.remove(name);
+        Ok(())
+    }
+
     pub fn get_last_epoch(&self) -> Result<HummockSnapshot> {
         let max_committed_epoch = self.max_committed_epoch.load(Ordering::Relaxed);
         Ok(HummockSnapshot {
@@ -694,10 +786,18 @@ where
                 compact_task.watermark = {
                     let versioning_guard = read_lock!(self, versioning).await;
                     let max_committed_epoch = versioning_guard.current_version.max_committed_epoch;
+                    let now = Instant::now();
                     versioning_guard
                         .pinned_snapshots
                         .values()
                         .map(|v| v.minimal_pinned_snapshot)
+                        .chain(
+                            versioning_guard
+                                .named_snapshots
+                                .values()
+                                .filter(|snapshot| snapshot.is_alive(now))
+                                .map(|snapshot| snapshot.epoch),
+                        )
                         .fold(max_committed_epoch, std::cmp::min)
                 };
 
@@ -809,6 +909,7 @@ where
             },
         );
         commit_multi_var!(self, Some(assignee_context_id), compact_task_assignment)?;
+        self.compactor_manager.assign_task(assignee_context_id);
 
         #[cfg(test)]
         {
@@ -852,6 +953,9 @@ where
         if assignee_context_id.is_none() && !trivial_move {
             return Ok(false);
         }
+        compaction
+            .compact_task_progress
+            .remove(&compact_task.task_id);
         compact_status.report_compact_task(compact_task);
         if compact_task.task_status {
             // The compaction task is finished.
@@ -921,12 +1025,20 @@ where
             )?;
         }
 
+        if let Some(context_id) = assignee_context_id {
+            self.compactor_manager.finish_task(context_id);
+        }
+
         tracing::trace!(
             "Reported compaction task. {}. cost time: {:?}",
             compact_task_to_string(compact_task),
             start_time.elapsed(),
         );
 
+        if !trivial_move {
+            trigger_compact_task_stat(&self.metrics, compact_task);
+        }
+
         trigger_sst_stat(
             &self.metrics,
             compaction
@@ -950,6 +1062,91 @@ where
         Ok(true)
     }
 
+    /// Updates the progress of the compaction tasks assigned to the compactor.
+    #[named]
+    pub async fn update_compact_task_progress(
+        &self,
+        context_id: HummockContextId,
+        progress: Vec<CompactTaskProgress>,
+    ) {
+        let mut compaction_guard = write_lock!(self, compaction).await;
+        let compaction = compaction_guard.deref_mut();
+        for task_progress in progress {
+            // The progress of a task that is not assigned to the compactor any more is ignored.
+            match compaction
+                .compact_task_assignment
+                .get(&task_progress.task_id)
+            {
+                Some(assignment) if assignment.context_id == context_id => {
+                    compaction
+                        .compact_task_progress
+                        .insert(task_progress.task_id, task_progress);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Lists the assigned compaction tasks along with their latest reported progress.
+    #[named]
+    pub async fn list_compaction_tasks(&self) -> Vec<CompactTaskInfo> {
+        let compaction_guard = read_lock!(self, compaction).await;
+        compaction_guard
+            .compact_task_assignment
+            .iter()
+            .map(|(task_id, assignment)| CompactTaskInfo {
+                compact_task: assignment.compact_task.clone(),
+                context_id: assignment.context_id,
+                progress: compaction_guard.compact_task_progress.get(task_id).cloned(),
+            })
+            .collect()
+    }
+
+    /// Cancels an assigned compaction task. The task is reported as cancelled first, so that its
+    /// input SSTs can be picked again and its output won't be committed. Then the compactor is
+    /// instructed to abort it. Returns `Ok(false)` if the task is not assigned.
+    #[named]
+    pub async fn cancel_compact_task(&self, task_id: HummockCompactionTaskId) -> Result<bool> {
+        let assignment = read_lock!(self, compaction)
+            .await
+            .compact_task_assignment
+            .get(&task_id)
+            .cloned();
+        let Some(assignment) = assignment else {
+            return Ok(false);
+        };
+        let mut compact_task = assignment.compact_task.ok_or_else(|| {
+            Error::InternalError(format!(
+                "compaction task {} is assigned without the task",
+                task_id
+            ))
+        })?;
+        compact_task.task_status = false;
+        compact_task.sorted_output_ssts.clear();
+        if !self.report_compact_task(&compact_task).await? {
+            // The task has been reported by the compactor in the meantime.
+            return Ok(false);
+        }
+        tracing::info!(
+            "Cancelled compaction task {} assigned to compactor {}",
+            task_id,
+            assignment.context_id
+        );
+        if let Some(compactor) = self.compactor_manager.get_compactor(assignment.context_id) {
+            // The task is already cancelled in meta, so the compactor failing to receive the
+            // instruction only wastes its resources.
+            if let Err(e) = compactor.cancel_task(task_id).await {
+                tracing::warn!(
+                    "Failed to instruct compactor {} to cancel compaction task {}: {}",
+                    assignment.context_id,
+                    task_id,
+                    e
+                );
+            }
+        }
+        Ok(true)
+    }
+
     /// Caller should ensure `epoch` > `max_committed_epoch`
     #[named]
     pub async fn commit_epoch(
@@ -1138,6 +1335,7 @@ where
                 .collect_vec();
             for task_id in task_ids_to_remove {
                 compact_task_assignment.remove(task_id);
+                compaction.compact_task_progress.remove(&task_id);
             }
             pinned_versions.remove(*context_id);
             pinned_snapshots.remove(*context_id);
@@ -1292,6 +1490,31 @@ where
         read_lock!(self, versioning).await.current_version.clone()
     }
 
+    /// Snapshots below the safe epoch of the current version may have been compacted away.
+    #[named]
+    pub async fn get_safe_epoch(&self) -> HummockEpoch {
+        read_lock!(self, versioning)
+            .await
+            .current_version
+            .safe_epoch
+    }
+
+    /// Estimates the number of rows in the tables by the put key counts of the tables in the SSTs,
+    /// which exclude the deletes but still count the old versions not compacted yet.
+    #[named]
+    pub async fn estimate_table_rows(&self, table_ids: &HashSet<u32>) -> u64 {
+        read_lock!(self, versioning)
+            .await
+            .current_version
+            .get_combined_levels()
+            .into_iter()
+            .flat_map(|level| &level.table_infos)
+            .flat_map(|sst| &sst.table_key_counts)
+            .filter(|(table_id, _)| table_ids.contains(table_id))
+            .map(|(_, key_count)| key_count)
+            .sum()
+    }
+
     pub fn set_compaction_scheduler(&self, sender: CompactionRequestChannelRef) {
         *self.compaction_scheduler.write() = Some(sender);
     }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::cmp::Ordering;
+use std::time::Duration;
 
 use itertools::Itertools;
 use risingwave_common::util::epoch::INVALID_EPOCH;
@@ -25,7 +26,7 @@ use risingwave_hummock_sdk::{
 };
 use risingwave_pb::common::{HostAddress, WorkerType};
 use risingwave_pb::hummock::{
-    HummockPinnedSnapshot, HummockPinnedVersion, HummockSnapshot, KeyRange,
+    CompactTaskProgress, HummockPinnedSnapshot, HummockPinnedVersion, HummockSnapshot, KeyRange,
 };
 
 use crate::hummock::compaction::ManualCompactionOption;
@@ -277,6 +278,36 @@ async fn test_hummock_table() {
     );
 }
 
+#[tokio::test]
+async fn test_estimate_table_rows() {
+    let (_env, hummock_manager, _cluster_manager, _worker_node) = setup_compute_env(80).await;
+
+    let epoch: u64 = 1;
+    // The SSTs contain tables {1, 2} and {2, 3}, with one key of each table.
+    let mut original_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+    // Only the key count of table 3 is counted for it, though the SST also contains table 2.
+    original_tables[1].table_key_counts.insert(3, 10);
+    register_sstable_infos_to_compaction_group(
+        hummock_manager.compaction_group_manager_ref_for_test(),
+        &original_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    hummock_manager
+        .commit_epoch(epoch, to_local_sstable_info(&original_tables))
+        .await
+        .unwrap();
+
+    assert_eq!(hummock_manager.estimate_table_rows(&[1].into()).await, 1);
+    assert_eq!(hummock_manager.estimate_table_rows(&[2].into()).await, 2);
+    assert_eq!(hummock_manager.estimate_table_rows(&[3].into()).await, 10);
+    assert_eq!(
+        hummock_manager.estimate_table_rows(&[1, 3].into()).await,
+        11
+    );
+    assert_eq!(hummock_manager.estimate_table_rows(&[4].into()).await, 0);
+}
+
 #[tokio::test]
 async fn test_hummock_transaction() {
     let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
@@ -398,6 +429,8 @@ async fn test_release_context_resource() {
             WorkerType::ComputeNode,
             fake_host_address_2,
             fake_parallelism,
+            Default::default(),
+            Default::default(),
         )
         .await
         .unwrap();
@@ -498,6 +531,8 @@ async fn test_hummock_manager_basic() {
             WorkerType::ComputeNode,
             fake_host_address_2,
             fake_parallelism,
+            Default::default(),
+            Default::default(),
         )
         .await
         .unwrap();
@@ -928,3 +963,196 @@ async fn test_trigger_manual_compaction() {
         assert!(result.is_err());
     }
 }
+
+#[tokio::test]
+async fn test_pin_named_snapshot() {
+    let (env, hummock_manager, cluster_manager, _worker_node) = setup_compute_env(80).await;
+    let ttl = Duration::from_secs(60);
+
+    // Construct vnode mappings for generating compaction tasks.
+    let parallel_units = cluster_manager.list_parallel_units().await;
+    env.hash_mapping_manager()
+        .build_fragment_hash_mapping(1, &parallel_units);
+
+    let mut epoch: u64 = 1;
+    for _ in 0..2 {
+        let test_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+        for table in &test_tables {
+            for table_id in &table.table_ids {
+                env.hash_mapping_manager()
+                    .set_fragment_state_table(1, *table_id);
+            }
+        }
+        register_sstable_infos_to_compaction_group(
+            hummock_manager.compaction_group_manager_ref_for_test(),
+            &test_tables,
+            StaticCompactionGroupId::StateDefault.into(),
+        )
+        .await;
+        hummock_manager
+            .commit_epoch(epoch, to_local_sstable_info(&test_tables))
+            .await
+            .unwrap();
+
+        if epoch == 1 {
+            for name in ["reader", "crashed_reader"] {
+                let ttl = if name == "reader" {
+                    ttl
+                } else {
+                    Duration::ZERO
+                };
+                let snapshot = hummock_manager
+                    .pin_named_snapshot(name.to_string(), ttl)
+                    .await
+                    .unwrap();
+                assert_eq!(snapshot.epoch, 1);
+            }
+        }
+        epoch += 1;
+    }
+
+    // Pinning again renews the TTL of the snapshot, while an expired one is pinned anew.
+    let snapshot = hummock_manager
+        .pin_named_snapshot("reader".to_string(), ttl)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 1);
+    let snapshot = hummock_manager
+        .pin_named_snapshot("crashed_reader".to_string(), Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 2);
+
+    // The snapshot pinned by name holds back the watermark of compaction.
+    let compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(compact_task.watermark, 1);
+
+    hummock_manager
+        .unpin_named_snapshot("reader")
+        .await
+        .unwrap();
+    let snapshot = hummock_manager
+        .pin_named_snapshot("reader".to_string(), ttl)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 2);
+
+    // A committed epoch can be pinned explicitly, while an uncommitted one can't.
+    let snapshot = hummock_manager
+        .pin_named_snapshot_at("backfill".to_string(), 1, ttl)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 1);
+    assert!(matches!(
+        hummock_manager
+            .pin_named_snapshot_at("backfill".to_string(), 3, ttl)
+            .await,
+        Err(Error::SnapshotUnavailable(3, _, 2))
+    ));
+
+    // A TTL too long to be represented never expires, instead of overflowing.
+    let snapshot = hummock_manager
+        .pin_named_snapshot("forever".to_string(), Duration::MAX)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 2);
+    let snapshot = hummock_manager
+        .pin_named_snapshot_at("backfill".to_string(), 1, Duration::MAX)
+        .await
+        .unwrap();
+    assert_eq!(snapshot.epoch, 1);
+}
+
+#[tokio::test]
+async fn test_cancel_compaction_task() {
+    let (env, hummock_manager, cluster_manager, worker_node) = setup_compute_env(80).await;
+    let context_id = worker_node.id;
+
+    // Construct vnode mappings for generating compaction tasks.
+    let parallel_units = cluster_manager.list_parallel_units().await;
+    env.hash_mapping_manager()
+        .build_fragment_hash_mapping(1, &parallel_units);
+    let epoch: u64 = 1;
+    let original_tables = generate_test_tables(epoch, get_sst_ids(&hummock_manager, 2).await);
+    for table in &original_tables {
+        for table_id in &table.table_ids {
+            env.hash_mapping_manager()
+                .set_fragment_state_table(1, *table_id);
+        }
+    }
+    register_sstable_infos_to_compaction_group(
+        hummock_manager.compaction_group_manager_ref_for_test(),
+        &original_tables,
+        StaticCompactionGroupId::StateDefault.into(),
+    )
+    .await;
+    hummock_manager
+        .commit_epoch(epoch, to_local_sstable_info(&original_tables))
+        .await
+        .unwrap();
+
+    let mut compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    hummock_manager
+        .assign_compaction_task(&compact_task, context_id, async { true })
+        .await
+        .unwrap();
+    let progress = CompactTaskProgress {
+        task_id: compact_task.task_id,
+        read_bytes: 1,
+        written_bytes: 0,
+        total_input_bytes: 2,
+    };
+    hummock_manager
+        .update_compact_task_progress(context_id, vec![progress.clone()])
+        .await;
+    // The progress reported by another compactor is ignored.
+    hummock_manager
+        .update_compact_task_progress(
+            context_id + 1,
+            vec![CompactTaskProgress {
+                read_bytes: 2,
+                ..progress.clone()
+            }],
+        )
+        .await;
+    let tasks = hummock_manager.list_compaction_tasks().await;
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].context_id, context_id);
+    assert_eq!(tasks[0].compact_task.as_ref(), Some(&compact_task));
+    assert_eq!(tasks[0].progress, Some(progress));
+
+    let version = hummock_manager.get_current_version().await;
+    assert!(hummock_manager
+        .cancel_compact_task(compact_task.task_id)
+        .await
+        .unwrap());
+    assert!(hummock_manager.list_compaction_tasks().await.is_empty());
+    // Cancelling again is a no-op.
+    assert!(!hummock_manager
+        .cancel_compact_task(compact_task.task_id)
+        .await
+        .unwrap());
+
+    // The output of the cancelled task is never committed, and its input can be compacted again.
+    compact_task.task_status = true;
+    compact_task.sorted_output_ssts = compact_task.input_ssts[0].table_infos.clone();
+    assert!(!hummock_manager
+        .report_compact_task(&compact_task)
+        .await
+        .unwrap());
+    assert_eq!(hummock_manager.get_current_version().await, version);
+    let new_compact_task = hummock_manager
+        .get_compact_task(StaticCompactionGroupId::StateDefault.into())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_compact_task.input_ssts, compact_task.input_ssts);
+}
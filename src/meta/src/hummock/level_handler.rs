@@ -55,6 +55,15 @@ impl LevelHandler {
         self.compacting_files.contains_key(sst_id)
     }
 
+    /// Returns the id of the pending task which is compacting the SST, if any.
+    pub fn pending_task_id(&self, sst_id: &HummockSstableId) -> Option<u64> {
+        self.compacting_files.get(sst_id).copied()
+    }
+
+    pub fn has_pending_task(&self, task_id: u64) -> bool {
+        self.pending_tasks.iter().any(|(id, _, _)| *id == task_id)
+    }
+
     pub fn add_pending_task(&mut self, task_id: u64, ssts: &[SstableInfo]) {
         let mut table_ids = vec![];
         let mut total_file_size = 0;
@@ -15,13 +15,13 @@
 use std::sync::atomic::Ordering;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use itertools::enumerate;
+use itertools::{enumerate, Itertools};
 use num_traits::FromPrimitive;
 use prost::Message;
 use risingwave_hummock_sdk::compaction_group::hummock_version_ext::HummockVersionExt;
 use risingwave_hummock_sdk::compaction_group::StaticCompactionGroupId;
 use risingwave_hummock_sdk::CompactionGroupId;
-use risingwave_pb::hummock::HummockVersion;
+use risingwave_pb::hummock::{CompactTask, HummockVersion};
 
 use crate::hummock::compaction::CompactStatus;
 use crate::rpc::metrics::MetaMetrics;
@@ -35,6 +35,41 @@ pub fn trigger_commit_stat(metrics: &MetaMetrics, current_version: &HummockVersi
         .set(current_version.encoded_len() as i64);
 }
 
+pub fn trigger_compact_task_stat(metrics: &MetaMetrics, compact_task: &CompactTask) {
+    let group_label = compact_task.compaction_group_id.to_string();
+    let result_label = if compact_task.task_status {
+        "finished"
+    } else {
+        "cancelled"
+    };
+    metrics
+        .compact_task_count
+        .with_label_values(&[&group_label, result_label])
+        .inc();
+    if !compact_task.task_status {
+        return;
+    }
+    let read_size: u64 = compact_task
+        .input_ssts
+        .iter()
+        .flat_map(|level| level.table_infos.iter())
+        .map(|table| table.file_size)
+        .sum();
+    let write_size: u64 = compact_task
+        .sorted_output_ssts
+        .iter()
+        .map(|table| table.file_size)
+        .sum();
+    metrics
+        .compact_read_size
+        .with_label_values(&[&group_label])
+        .inc_by(read_size / 1024);
+    metrics
+        .compact_write_size
+        .with_label_values(&[&group_label])
+        .inc_by(write_size / 1024);
+}
+
 pub fn trigger_sst_stat(
     metrics: &MetaMetrics,
     compact_status: &CompactStatus,
@@ -66,6 +101,16 @@ pub fn trigger_sst_stat(
             .with_label_values(&[&level_label])
             .set(level_sst_size(idx) as i64);
     }
+    let pending_task_num = compact_status
+        .level_handlers
+        .iter()
+        .flat_map(|level_handler| level_handler.pending_tasks_ids())
+        .unique()
+        .count();
+    metrics
+        .compact_pending_task_num
+        .with_label_values(&[&compaction_group_id.to_string()])
+        .set(pending_task_num as i64);
 
     use std::sync::atomic::AtomicU64;
 
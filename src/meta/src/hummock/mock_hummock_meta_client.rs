@@ -21,8 +21,8 @@ use risingwave_hummock_sdk::{
     HummockContextId, HummockEpoch, HummockSstableId, HummockVersionId, LocalSstableInfo,
 };
 use risingwave_pb::hummock::{
-    CompactTask, CompactionGroup, HummockSnapshot, HummockVersion, HummockVersionDelta,
-    SubscribeCompactTasksResponse, VacuumTask,
+    CompactTask, CompactTaskProgress, CompactionGroup, HummockSnapshot, HummockVersion,
+    HummockVersionDelta, SubscribeCompactTasksResponse, VacuumTask,
 };
 use risingwave_rpc_client::error::{Result, RpcError};
 use risingwave_rpc_client::HummockMetaClient;
@@ -134,6 +134,16 @@ impl HummockMetaClient for MockHummockMetaClient {
             .map_err(mock_err)
     }
 
+    async fn report_compaction_task_progress(
+        &self,
+        progress: Vec<CompactTaskProgress>,
+    ) -> Result<()> {
+        self.hummock_manager
+            .update_compact_task_progress(self.context_id, progress)
+            .await;
+        Ok(())
+    }
+
     async fn commit_epoch(
         &self,
         epoch: HummockEpoch,
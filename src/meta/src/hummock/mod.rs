@@ -55,13 +55,14 @@ pub async fn start_hummock_workers<S>(
     vacuum_trigger: Arc<VacuumTrigger<S>>,
     notification_manager: NotificationManagerRef,
     compaction_scheduler: CompactionSchedulerRef<S>,
+    vacuum_interval: Duration,
 ) -> Vec<(JoinHandle<()>, Sender<()>)>
 where
     S: MetaStore,
 {
     vec![
         start_compaction_scheduler(compaction_scheduler),
-        start_vacuum_scheduler(vacuum_trigger),
+        start_vacuum_scheduler(vacuum_trigger, vacuum_interval),
         subscribe_cluster_membership_change(
             hummock_manager,
             compactor_manager,
@@ -139,16 +140,17 @@ where
     (join_handle, shutdown_tx)
 }
 
-/// Vacuum is triggered at this rate.
-const VACUUM_TRIGGER_INTERVAL: Duration = Duration::from_secs(30);
-/// Starts a task to periodically vacuum hummock.
-pub fn start_vacuum_scheduler<S>(vacuum: Arc<VacuumTrigger<S>>) -> (JoinHandle<()>, Sender<()>)
+/// Starts a task to periodically vacuum hummock, triggered at the rate of `interval`.
+pub fn start_vacuum_scheduler<S>(
+    vacuum: Arc<VacuumTrigger<S>>,
+    interval: Duration,
+) -> (JoinHandle<()>, Sender<()>)
 where
     S: MetaStore,
 {
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
     let join_handle = tokio::spawn(async move {
-        let mut min_trigger_interval = tokio::time::interval(VACUUM_TRIGGER_INTERVAL);
+        let mut min_trigger_interval = tokio::time::interval(interval);
         loop {
             tokio::select! {
                 // Wait for interval
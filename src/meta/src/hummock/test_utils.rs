@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -130,6 +131,8 @@ pub fn generate_test_tables(epoch: u64, sst_ids: Vec<HummockSstableId>) -> Vec<S
             }),
             file_size: 1,
             table_ids: vec![(i + 1) as u32, (i + 2) as u32],
+            table_key_counts: HashMap::from([((i + 1) as u32, 1), ((i + 2) as u32, 1)]),
+            range_tombstone_count: 0,
         });
     }
     sst_info
@@ -259,7 +262,13 @@ pub async fn setup_compute_env(
     };
     let fake_parallelism = 4;
     let worker_node = cluster_manager
-        .add_worker_node(WorkerType::ComputeNode, fake_host_address, fake_parallelism)
+        .add_worker_node(
+            WorkerType::ComputeNode,
+            fake_host_address,
+            fake_parallelism,
+            Default::default(),
+            Default::default(),
+        )
         .await
         .unwrap();
     (env, hummock_manager, cluster_manager, worker_node)
@@ -21,6 +21,7 @@ use risingwave_hummock_sdk::HummockSstableId;
 use risingwave_pb::hummock::VacuumTask;
 
 use crate::hummock::{CompactorManager, HummockManagerRef};
+use crate::rpc::metrics::MetaMetrics;
 use crate::storage::MetaStore;
 
 // TODO #4037: GC orphan SSTs in object store
@@ -30,6 +31,9 @@ pub struct VacuumTrigger<S: MetaStore> {
     compactor_manager: Arc<CompactorManager>,
     /// SST ids which have been dispatched to vacuum nodes but are not replied yet.
     pending_sst_ids: parking_lot::RwLock<HashSet<HummockSstableId>>,
+    metrics: Arc<MetaMetrics>,
+    /// Only logs the SSTs to delete, without dispatching any vacuum task.
+    dry_run: bool,
 }
 
 impl<S> VacuumTrigger<S>
@@ -39,11 +43,15 @@ where
     pub fn new(
         hummock_manager: HummockManagerRef<S>,
         compactor_manager: Arc<CompactorManager>,
+        metrics: Arc<MetaMetrics>,
+        dry_run: bool,
     ) -> Self {
         Self {
             hummock_manager,
             compactor_manager,
             pending_sst_ids: Default::default(),
+            metrics,
+            dry_run,
         }
     }
 
@@ -69,8 +77,20 @@ where
 
     /// Schedules deletion of SSTs from object store
     ///
-    /// Returns SSTs scheduled in worker node.
+    /// Returns SSTs scheduled in worker node. In dry run mode, returns the SSTs that would be
+    /// scheduled, without scheduling them.
     pub async fn vacuum_sst_data(&self) -> Result<Vec<HummockSstableId>> {
+        if self.dry_run {
+            let ssts_to_delete = self.hummock_manager.get_ssts_to_delete().await;
+            self.metrics
+                .vacuum_scanned_sst_count
+                .inc_by(ssts_to_delete.len() as u64);
+            if !ssts_to_delete.is_empty() {
+                tracing::info!("Dry run: SSTs to vacuum {:?}", ssts_to_delete);
+            }
+            return Ok(ssts_to_delete);
+        }
+
         // Select SSTs to delete.
         let ssts_to_delete = {
             // 1. Retry the pending SSTs first.
@@ -86,6 +106,9 @@ where
                 if ssts_to_delete.is_empty() {
                     return Ok(vec![]);
                 }
+                self.metrics
+                    .vacuum_scanned_sst_count
+                    .inc_by(ssts_to_delete.len() as u64);
                 // Track these SST ids, so that we can remove them from metadata later.
                 self.pending_sst_ids.write().extend(ssts_to_delete.clone());
                 ssts_to_delete
@@ -163,6 +186,9 @@ where
             self.pending_sst_ids
                 .write()
                 .retain(|p| !deleted_sst_ids.contains(p));
+            self.metrics
+                .vacuum_deleted_sst_count
+                .inc_by(deleted_sst_ids.len() as u64);
         }
         tracing::info!("Finish vacuuming SSTs {:?}", vacuum_task.sstable_ids);
         Ok(())
@@ -172,19 +198,27 @@ where
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
+    use std::time::Duration;
 
     use itertools::Itertools;
     use risingwave_pb::hummock::VacuumTask;
 
     use crate::hummock::test_utils::{add_test_tables, setup_compute_env};
     use crate::hummock::{start_vacuum_scheduler, CompactorManager, VacuumTrigger};
+    use crate::rpc::metrics::MetaMetrics;
 
     #[tokio::test]
     async fn test_shutdown_vacuum() {
         let (_env, hummock_manager, _cluster_manager, _worker_node) = setup_compute_env(80).await;
         let compactor_manager = Arc::new(CompactorManager::new());
-        let vacuum = Arc::new(VacuumTrigger::new(hummock_manager, compactor_manager));
-        let (join_handle, shutdown_sender) = start_vacuum_scheduler(vacuum);
+        let vacuum = Arc::new(VacuumTrigger::new(
+            hummock_manager,
+            compactor_manager,
+            Arc::new(MetaMetrics::new()),
+            false,
+        ));
+        let (join_handle, shutdown_sender) =
+            start_vacuum_scheduler(vacuum, Duration::from_secs(30));
         shutdown_sender.send(()).unwrap();
         join_handle.await.unwrap();
     }
@@ -194,9 +228,12 @@ mod tests {
         let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
         let context_id = worker_node.id;
         let compactor_manager = Arc::new(CompactorManager::default());
+        let metrics = Arc::new(MetaMetrics::new());
         let vacuum = Arc::new(VacuumTrigger::new(
             hummock_manager.clone(),
             compactor_manager.clone(),
+            metrics.clone(),
+            false,
         ));
         let _receiver = compactor_manager.add_compactor(0);
 
@@ -258,6 +295,9 @@ mod tests {
             .await
             .unwrap();
 
+        assert_eq!(metrics.vacuum_scanned_sst_count.get(), 3);
+        assert_eq!(metrics.vacuum_deleted_sst_count.get(), 3);
+
         // The delta can be deleted now.
         assert_eq!(
             VacuumTrigger::vacuum_version_metadata(&vacuum)
@@ -273,5 +313,37 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_vacuum_dry_run() {
+        let (_env, hummock_manager, _cluster_manager, worker_node) = setup_compute_env(80).await;
+        let context_id = worker_node.id;
+        let compactor_manager = Arc::new(CompactorManager::default());
+        let metrics = Arc::new(MetaMetrics::new());
+        let vacuum = Arc::new(VacuumTrigger::new(
+            hummock_manager.clone(),
+            compactor_manager.clone(),
+            metrics.clone(),
+            true,
+        ));
+        let mut receiver = compactor_manager.add_compactor(0);
+
+        add_test_tables(hummock_manager.as_ref(), context_id).await;
+        VacuumTrigger::vacuum_version_metadata(&vacuum)
+            .await
+            .unwrap();
+
+        // The SSTs to delete are returned every time, but no vacuum task is dispatched.
+        for _ in 0..2 {
+            assert_eq!(
+                VacuumTrigger::vacuum_sst_data(&vacuum).await.unwrap().len(),
+                3
+            );
+        }
+        assert!(receiver.try_recv().is_err());
+        assert_eq!(hummock_manager.get_ssts_to_delete().await.len(), 3);
+        assert_eq!(metrics.vacuum_scanned_sst_count.get(), 6);
+        assert_eq!(metrics.vacuum_deleted_sst_count.get(), 0);
+    }
+
     // TODO #4081: re-enable after orphan SST GC via listing object store is implemented
 }
@@ -129,6 +129,38 @@ pub struct MetaNodeOpts {
     /// It is mainly useful for playgrounds.
     #[clap(long)]
     dangerous_max_idle_secs: Option<u64>,
+
+    /// Interval in seconds of vacuuming the hummock metadata and the SSTs to delete.
+    #[clap(long, default_value = "30")]
+    vacuum_interval_sec: u64,
+
+    /// Only log the SSTs to vacuum instead of deleting them. Useful to validate the vacuum
+    /// candidates before enabling the deletion.
+    #[clap(long)]
+    vacuum_dry_run: bool,
+
+    /// The maximum number of compaction tasks that are picked and assigned concurrently.
+    #[clap(long, default_value = "8")]
+    max_concurrent_compaction_tasks: usize,
+
+    /// The maximum number of attempts of a recovery before it's given up and retried later. 0 for
+    /// unlimited.
+    #[clap(long, default_value = "0")]
+    max_recovery_attempts: usize,
+
+    /// The time limit in seconds of a recovery before it's given up and retried later. 0 for
+    /// unlimited.
+    #[clap(long, default_value = "0")]
+    recovery_timeout_sec: u64,
+
+    /// The maximum time in seconds a recovery may replay since the last committed epoch, beyond
+    /// which the recovery is refused. 0 for unlimited.
+    #[clap(long, default_value = "0")]
+    max_recovery_replay_sec: u64,
+
+    /// The time limit in seconds of migrating the actors on a draining compute node.
+    #[clap(long, default_value = "600")]
+    drain_timeout_sec: u64,
 }
 
 fn load_config(opts: &MetaNodeOpts) -> ComputeNodeConfig {
@@ -168,6 +200,24 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
             Duration::from_millis(compute_config.streaming.checkpoint_interval_ms as u64);
         let max_idle_ms = opts.dangerous_max_idle_secs.unwrap_or(0) * 1000;
         let in_flight_barrier_nums = compute_config.streaming.in_flight_barrier_nums as usize;
+        let checkpoint_frequency = compute_config.streaming.checkpoint_frequency;
+        if checkpoint_frequency < 1 {
+            panic!(
+                "checkpoint_frequency should be at least 1, got {}",
+                checkpoint_frequency
+            );
+        }
+        if opts.vacuum_interval_sec == 0 {
+            panic!("vacuum_interval_sec should be positive");
+        }
+        let vacuum_interval = Duration::from_secs(opts.vacuum_interval_sec);
+        if opts.max_concurrent_compaction_tasks == 0 {
+            panic!("max_concurrent_compaction_tasks should be positive");
+        }
+        let recovery_timeout =
+            (opts.recovery_timeout_sec > 0).then(|| Duration::from_secs(opts.recovery_timeout_sec));
+        let max_recovery_replay = (opts.max_recovery_replay_sec > 0)
+            .then(|| Duration::from_secs(opts.max_recovery_replay_sec));
 
         tracing::info!("Meta server listening at {}", listen_addr);
         let add_info = AddressInfo {
@@ -188,6 +238,14 @@ pub fn start(opts: MetaNodeOpts) -> Pin<Box<dyn Future<Output = ()> + Send>> {
                 checkpoint_interval,
                 max_idle_ms,
                 in_flight_barrier_nums,
+                checkpoint_frequency,
+                vacuum_interval,
+                vacuum_dry_run: opts.vacuum_dry_run,
+                max_concurrent_compaction_tasks: opts.max_concurrent_compaction_tasks,
+                max_recovery_attempts: opts.max_recovery_attempts,
+                recovery_timeout,
+                max_recovery_replay,
+                drain_timeout: Duration::from_secs(opts.drain_timeout_sec),
             },
         )
         .await
@@ -27,7 +27,7 @@ use risingwave_common::error::ErrorCode::PermissionDenied;
 use risingwave_common::types::ParallelUnitId;
 use risingwave_pb::catalog::table::OptionalAssociatedSourceId;
 use risingwave_pb::catalog::{Database, Schema, Sink, Source, Table};
-use risingwave_pb::common::ParallelUnit;
+use risingwave_pb::common::{ParallelUnit, ParallelUnitMapping};
 use risingwave_pb::meta::subscribe_response::{Info, Operation};
 use tokio::sync::{Mutex, MutexGuard};
 
@@ -265,14 +265,16 @@ where
     pub async fn start_create_table_procedure(&self, table: &Table) -> Result<()> {
         let mut core = self.core.lock().await;
         let key = (table.database_id, table.schema_id, table.name.clone());
-        if !core.has_table(table) && !core.has_in_progress_creation(&key) {
+        if core.has_table(table) {
+            bail!("table {} already exists", table.name)
+        } else if core.has_in_progress_creation(&key) {
+            bail!("table {} is being created by another procedure", table.name)
+        } else {
             core.mark_creating(&key);
             for &dependent_relation_id in &table.dependent_relations {
                 core.increase_ref_count(dependent_relation_id);
             }
             Ok(())
-        } else {
-            bail!("table already exists or in creating procedure".to_string(),)
         }
     }
 
@@ -328,11 +330,14 @@ where
         let table = Table::select(self.env.meta_store(), &table_id).await?;
         if let Some(table) = table {
             match core.get_ref_count(table_id) {
-                Some(ref_count) => Err(PermissionDenied(format!(
-                    "Fail to delete table `{}` because {} other relation(s) depend on it",
-                    table.name, ref_count
-                ))
-                .into()),
+                Some(ref_count) => Err(core
+                    .dependents_error(
+                        Relation::Table(table.clone()).kind(),
+                        &table.name,
+                        table_id,
+                        ref_count,
+                    )
+                    .await?),
                 None => {
                     Table::delete(self.env.meta_store(), &table_id).await?;
                     core.drop_table(&table);
@@ -352,6 +357,72 @@ where
         }
     }
 
+    /// Lists the relations depending on `relation_id` directly or indirectly, in the order to drop
+    /// them, i.e. each relation comes before those it depends on.
+    pub async fn list_dependent_relations(&self, relation_id: RelationId) -> Result<Vec<Relation>> {
+        let core = self.core.lock().await;
+        let mut relations = HashMap::new();
+        let mut visited = HashSet::new();
+        let mut ordered = vec![];
+        // Post-order DFS, where `true` means all the dependents of the relation have been pushed.
+        let mut stack = vec![(relation_id, false)];
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                if id != relation_id {
+                    ordered.push(relations.remove(&id).unwrap());
+                }
+                continue;
+            }
+            if !visited.insert(id) {
+                continue;
+            }
+            stack.push((id, true));
+            for dependent in core.list_dependents(id).await? {
+                stack.push((dependent.id(), false));
+                relations.entry(dependent.id()).or_insert(dependent);
+            }
+        }
+        Ok(ordered)
+    }
+
+    /// Renames the materialized view. Only the catalog is updated, while the table fragments and
+    /// the state tables are untouched as they refer to the materialized view by id. Returns the
+    /// names of the relations depending on it.
+    pub async fn rename_table(
+        &self,
+        table_id: TableId,
+        new_name: &str,
+    ) -> Result<(NotificationVersion, Vec<String>)> {
+        let mut core = self.core.lock().await;
+        let Some(mut table) = Table::select(self.env.meta_store(), &table_id).await? else {
+            bail!("table doesn't exist")
+        };
+        if table.optional_associated_source_id.is_some() || table.is_index {
+            bail!("{} is not a materialized view", table.name)
+        }
+        let key = (table.database_id, table.schema_id, new_name.to_string());
+        if core.has_relation(&key) || core.has_in_progress_creation(&key) {
+            bail!("relation {} already exists", new_name)
+        }
+
+        core.drop_table(&table);
+        table.name = new_name.to_string();
+        table.insert(self.env.meta_store()).await?;
+        core.add_table(&table);
+
+        let dependent_relation_names = Table::list(self.env.meta_store())
+            .await?
+            .into_iter()
+            .filter(|t| t.dependent_relations.contains(&table_id))
+            .map(|t| t.name)
+            .collect();
+        let version = self
+            .broadcast_info_op(Operation::Update, Info::Table(table))
+            .await;
+
+        Ok((version, dependent_relation_names))
+    }
+
     pub async fn start_create_source_procedure(&self, source: &Source) -> Result<()> {
         let mut core = self.core.lock().await;
         let key = (source.database_id, source.schema_id, source.name.clone());
@@ -395,14 +466,17 @@ where
         }
     }
 
+    /// Replaces the migrated parallel units in the mappings of the tables in `fragments`, and
+    /// returns the replaced mappings, which can be restored by [`Self::restore_table_mapping`].
     pub async fn update_table_mapping(
         &self,
         fragments: &Vec<TableFragments>,
         migrate_map: &HashMap<ParallelUnitId, ParallelUnit>,
-    ) -> Result<()> {
+    ) -> Result<HashMap<TableId, ParallelUnitMapping>> {
         let mut core = self.core.lock().await;
         let mut transaction = Transaction::default();
         let mut tables = Vec::new();
+        let mut old_mappings = HashMap::new();
         for fragment in fragments {
             let table_id = fragment.table_id().table_id();
             let internal_tables = fragment.internal_table_ids();
@@ -412,6 +486,7 @@ where
                 let table = Table::select(self.env.meta_store(), &table_id).await?;
                 if let Some(mut table) = table {
                     if let Some(ref mut mapping) = table.mapping {
+                        let old_mapping = mapping.clone();
                         let mut migrated = false;
                         mapping.data.iter_mut().for_each(|id| {
                             if migrate_map.contains_key(id) {
@@ -420,6 +495,7 @@ where
                             }
                         });
                         if migrated {
+                            old_mappings.insert(table_id, old_mapping);
                             table.upsert_in_transaction(&mut transaction)?;
                             tables.push(table);
                         }
@@ -433,6 +509,29 @@ where
                 .await;
             core.add_table(table);
         }
+        Ok(old_mappings)
+    }
+
+    /// Restores the table mappings returned by [`Self::update_table_mapping`].
+    pub async fn restore_table_mapping(
+        &self,
+        mappings: HashMap<TableId, ParallelUnitMapping>,
+    ) -> Result<()> {
+        let _core = self.core.lock().await;
+        let mut transaction = Transaction::default();
+        let mut tables = Vec::new();
+        for (table_id, mapping) in mappings {
+            if let Some(mut table) = Table::select(self.env.meta_store(), &table_id).await? {
+                table.mapping = Some(mapping);
+                table.upsert_in_transaction(&mut transaction)?;
+                tables.push(table);
+            }
+        }
+        self.env.meta_store().txn(transaction).await?;
+        for table in tables {
+            self.broadcast_info_op(Operation::Update, Info::Table(table))
+                .await;
+        }
         Ok(())
     }
 
@@ -441,11 +540,9 @@ where
         let source = Source::select(self.env.meta_store(), &source_id).await?;
         if let Some(source) = source {
             match core.get_ref_count(source_id) {
-                Some(ref_count) => Err(PermissionDenied(format!(
-                    "Fail to delete source `{}` because {} other relation(s) depend on it",
-                    source.name, ref_count
-                ))
-                .into()),
+                Some(ref_count) => Err(core
+                    .dependents_error("source", &source.name, source_id, ref_count)
+                    .await?),
                 None => {
                     Source::delete(self.env.meta_store(), &source_id).await?;
                     core.drop_source(&source);
@@ -573,18 +670,14 @@ where
                 }
                 // check ref count
                 if let Some(ref_count) = core.get_ref_count(mview_id) {
-                    return Err(PermissionDenied(format!(
-                        "Fail to delete table `{}` because {} other relation(s) depend on it",
-                        mview.name, ref_count
-                    ))
-                    .into());
+                    return Err(core
+                        .dependents_error("table", &mview.name, mview_id, ref_count)
+                        .await?);
                 }
                 if let Some(ref_count) = core.get_ref_count(source_id) {
-                    return Err(PermissionDenied(format!(
-                        "Fail to delete source `{}` because {} other relation(s) depend on it",
-                        source.name, ref_count
-                    ))
-                    .into());
+                    return Err(core
+                        .dependents_error("source", &source.name, source_id, ref_count)
+                        .await?);
                 }
 
                 // now is safe to delete both mview and source
@@ -860,6 +953,11 @@ where
             .remove(&(table.database_id, table.schema_id, table.name.clone()))
     }
 
+    /// Whether there's a table, source or sink with the name.
+    fn has_relation(&self, key: &RelationKey) -> bool {
+        self.tables.contains(key) || self.sources.contains(key) || self.sinks.contains(key)
+    }
+
     fn has_source(&self, source: &Source) -> bool {
         self.sources
             .contains(&(source.database_id, source.schema_id, source.name.clone()))
@@ -902,6 +1000,49 @@ where
             .map_err(Into::into)
     }
 
+    /// Lists the tables and sinks depending on `relation_id` directly.
+    async fn list_dependents(&self, relation_id: RelationId) -> Result<Vec<Relation>> {
+        let tables = Table::list(self.env.meta_store())
+            .await?
+            .into_iter()
+            .filter(|t| t.dependent_relations.contains(&relation_id))
+            .map(Relation::Table);
+        let sinks = Sink::list(self.env.meta_store())
+            .await?
+            .into_iter()
+            .filter(|s| s.dependent_relations.contains(&relation_id))
+            .map(Relation::Sink);
+        Ok(tables.chain(sinks).collect())
+    }
+
+    /// The error for dropping a relation that others still depend on.
+    async fn dependents_error(
+        &self,
+        kind: &str,
+        name: &str,
+        relation_id: RelationId,
+        ref_count: usize,
+    ) -> Result<anyhow::Error> {
+        let dependents = self.list_dependents(relation_id).await?;
+        // The dependents still in creation are not in the catalog yet.
+        let dependents = match dependents.as_slice() {
+            [] => format!("{} other relation(s) depend", ref_count),
+            [dependent] => format!("{} `{}` depends", dependent.kind(), dependent.name()),
+            _ => format!(
+                "{} depend",
+                dependents
+                    .iter()
+                    .map(|d| format!("{} `{}`", d.kind(), d.name()))
+                    .join(", ")
+            ),
+        };
+        Ok(PermissionDenied(format!(
+            "cannot drop {} `{}` because {} on it",
+            kind, name, dependents
+        ))
+        .into())
+    }
+
     fn get_ref_count(&self, relation_id: RelationId) -> Option<usize> {
         self.relation_ref_count.get(&relation_id).cloned()
     }
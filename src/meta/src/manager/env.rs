@@ -75,6 +75,27 @@ pub struct MetaOpts {
     /// 0 for infinite, process will never be exited due to long idle time.
     pub max_idle_ms: u64,
     pub in_flight_barrier_nums: usize,
+
+    /// The number of barriers between two checkpoints, should be at least 1.
+    pub checkpoint_frequency: usize,
+
+    /// Interval of vacuuming the hummock metadata and the SSTs to delete.
+    pub vacuum_interval: Duration,
+    /// Only log the SSTs to vacuum instead of deleting them.
+    pub vacuum_dry_run: bool,
+    /// The maximum number of compaction tasks that are picked and assigned concurrently.
+    pub max_concurrent_compaction_tasks: usize,
+
+    /// The maximum number of attempts of a recovery, 0 for unlimited.
+    pub max_recovery_attempts: usize,
+    /// The time limit of a recovery, `None` for unlimited. A recovery that can't complete within
+    /// the limit or the attempts is retried later, with no barrier injected in between.
+    pub recovery_timeout: Option<Duration>,
+    /// The maximum time a recovery may replay since the last committed epoch, `None` for
+    /// unlimited.
+    pub max_recovery_replay: Option<Duration>,
+    /// The time limit of migrating the actors on a draining worker.
+    pub drain_timeout: Duration,
 }
 
 impl Default for MetaOpts {
@@ -85,6 +106,14 @@ impl Default for MetaOpts {
             checkpoint_interval: Duration::from_millis(250),
             max_idle_ms: 0,
             in_flight_barrier_nums: 40,
+            checkpoint_frequency: 1,
+            vacuum_interval: Duration::from_secs(30),
+            vacuum_dry_run: false,
+            max_concurrent_compaction_tasks: 8,
+            max_recovery_attempts: 0,
+            recovery_timeout: None,
+            max_recovery_replay: None,
+            drain_timeout: Duration::from_secs(600),
         }
     }
 }
@@ -99,6 +128,14 @@ impl MetaOpts {
             checkpoint_interval: Duration::from_millis(250),
             max_idle_ms: 0,
             in_flight_barrier_nums: 40,
+            checkpoint_frequency: 1,
+            vacuum_interval: Duration::from_secs(30),
+            vacuum_dry_run: false,
+            max_concurrent_compaction_tasks: 8,
+            max_recovery_attempts: 0,
+            recovery_timeout: None,
+            max_recovery_replay: None,
+            drain_timeout: Duration::from_secs(600),
         }
     }
 }
@@ -24,6 +24,23 @@ pub enum Relation {
 }
 
 impl Relation {
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Table(table) => table.id,
+            Self::Sink(sink) => sink.id,
+        }
+    }
+
+    /// The kind of the relation shown to users.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Table(table) if table.optional_associated_source_id.is_some() => "table",
+            Self::Table(table) if table.is_index => "index",
+            Self::Table(_) => "materialized view",
+            Self::Sink(_) => "sink",
+        }
+    }
+
     pub fn set_id(&mut self, id: u32) {
         match self {
             Self::Table(table) => table.id = id,
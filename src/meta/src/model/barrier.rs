@@ -14,13 +14,23 @@
 
 use risingwave_common::util::epoch::{Epoch, INVALID_EPOCH};
 
-use crate::storage::{MetaStore, MetaStoreError, MetaStoreResult, DEFAULT_COLUMN_FAMILY};
+use crate::storage::{
+    MetaStore, MetaStoreError, MetaStoreResult, Transaction, DEFAULT_COLUMN_FAMILY,
+};
+
+const IN_FLIGHT_PREV_EPOCH_KEY: &[u8] = b"barrier_manager_state_epoch_inflight";
+const CHECKPOINT_PREV_EPOCH_KEY: &[u8] = b"barrier_manager_state_epoch_checkpoint_prev";
 
 /// `BarrierManagerState` defines the necessary state of `GlobalBarrierManager`, this will be stored
 /// persistently to meta store. Add more states when needed.
 pub struct BarrierManagerState {
     /// The last sent `prev_epoch`
     pub in_flight_prev_epoch: Epoch,
+
+    /// The `prev_epoch` of the last sent barrier if it's a checkpoint, or `INVALID_EPOCH`
+    /// otherwise. Once Hummock has committed this epoch, all the barriers sent before are
+    /// collected and committed.
+    pub checkpoint_prev_epoch: Epoch,
 }
 
 impl BarrierManagerState {
@@ -28,19 +38,20 @@ impl BarrierManagerState {
     where
         S: MetaStore,
     {
-        let in_flight_prev_epoch = match store
-            .get_cf(
-                DEFAULT_COLUMN_FAMILY,
-                b"barrier_manager_state_epoch_inflight",
-            )
-            .await
-        {
+        Self {
+            in_flight_prev_epoch: Self::get_epoch(store, IN_FLIGHT_PREV_EPOCH_KEY).await,
+            checkpoint_prev_epoch: Self::get_epoch(store, CHECKPOINT_PREV_EPOCH_KEY).await,
+        }
+    }
+
+    async fn get_epoch<S>(store: &S, key: &[u8]) -> Epoch
+    where
+        S: MetaStore,
+    {
+        match store.get_cf(DEFAULT_COLUMN_FAMILY, key).await {
             Ok(byte_vec) => u64::from_be_bytes(byte_vec.as_slice().try_into().unwrap()).into(),
             Err(MetaStoreError::ItemNotFound(_)) => INVALID_EPOCH.into(),
             Err(e) => panic!("{:?}", e),
-        };
-        Self {
-            in_flight_prev_epoch,
         }
     }
 
@@ -48,13 +59,17 @@ impl BarrierManagerState {
     where
         S: MetaStore,
     {
-        store
-            .put_cf(
-                DEFAULT_COLUMN_FAMILY,
-                b"barrier_manager_state_epoch_inflight".to_vec(),
-                self.in_flight_prev_epoch.0.to_be_bytes().to_vec(),
-            )
-            .await
-            .map_err(Into::into)
+        let mut trx = Transaction::default();
+        trx.put(
+            DEFAULT_COLUMN_FAMILY.to_string(),
+            IN_FLIGHT_PREV_EPOCH_KEY.to_vec(),
+            self.in_flight_prev_epoch.0.to_be_bytes().to_vec(),
+        );
+        trx.put(
+            DEFAULT_COLUMN_FAMILY.to_string(),
+            CHECKPOINT_PREV_EPOCH_KEY.to_vec(),
+            self.checkpoint_prev_epoch.0.to_be_bytes().to_vec(),
+        );
+        store.txn(trx).await
     }
 }
@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use prometheus::core::Metric;
 use prometheus::{
     exponential_buckets, histogram_opts, register_histogram_vec_with_registry,
-    register_histogram_with_registry, register_int_gauge_vec_with_registry,
-    register_int_gauge_with_registry, Histogram, HistogramVec, IntGauge, IntGaugeVec, Registry,
+    register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+    register_int_gauge_with_registry, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Registry,
 };
 
 pub struct MetaMetrics {
@@ -33,6 +36,14 @@ pub struct MetaMetrics {
     pub all_barrier_nums: IntGauge,
     /// the nums of in-flight barrier
     pub in_flight_barrier_nums: IntGauge,
+    /// duration of each successful recovery
+    pub recovery_duration: Histogram,
+    /// num of recovery attempts, including the failed ones
+    pub recovery_attempts: IntCounter,
+    /// num of cached stream clients to compute nodes
+    pub stream_client_pool_size: IntGauge,
+    /// num of stream clients evicted, due to failures or deleted workers
+    pub stream_client_pool_eviction_count: IntGauge,
 
     /// max committed epoch
     pub max_committed_epoch: IntGauge,
@@ -47,11 +58,25 @@ pub struct MetaMetrics {
     /// hummock version size
     pub version_size: IntGauge,
 
+    /// num of pending compact tasks in each compaction group
+    pub compact_pending_task_num: IntGaugeVec,
+    /// num of reported compact tasks in each compaction group, by result
+    pub compact_task_count: IntCounterVec,
+    /// KBs read by finished compact tasks in each compaction group
+    pub compact_read_size: IntCounterVec,
+    /// KBs written by finished compact tasks in each compaction group
+    pub compact_write_size: IntCounterVec,
+
     /// Latency for hummock manager to acquire lock
     pub hummock_manager_lock_time: HistogramVec,
 
     /// Latency for hummock manager to really process a request after acquire the lock
     pub hummock_manager_real_process_time: HistogramVec,
+
+    /// num of SSTs found to be vacuumed
+    pub vacuum_scanned_sst_count: IntCounter,
+    /// num of SSTs acknowledged to be deleted by vacuum
+    pub vacuum_deleted_sst_count: IntCounter,
 }
 
 impl MetaMetrics {
@@ -92,6 +117,32 @@ impl MetaMetrics {
         )
         .unwrap();
 
+        let opts = histogram_opts!(
+            "meta_recovery_duration_seconds",
+            "recovery latency",
+            exponential_buckets(0.1, 1.5, 20).unwrap() // max 221s
+        );
+        let recovery_duration = register_histogram_with_registry!(opts, registry).unwrap();
+        let recovery_attempts = register_int_counter_with_registry!(
+            "meta_recovery_attempts",
+            "num of recovery attempts",
+            registry
+        )
+        .unwrap();
+
+        let stream_client_pool_size = register_int_gauge_with_registry!(
+            "meta_stream_client_pool_size",
+            "num of cached stream clients",
+            registry
+        )
+        .unwrap();
+        let stream_client_pool_eviction_count = register_int_gauge_with_registry!(
+            "meta_stream_client_pool_eviction_count",
+            "num of evicted stream clients",
+            registry
+        )
+        .unwrap();
+
         let max_committed_epoch = register_int_gauge_with_registry!(
             "storage_max_committed_epoch",
             "max committed epoch",
@@ -132,6 +183,38 @@ impl MetaMetrics {
         )
         .unwrap();
 
+        let compact_pending_task_num = register_int_gauge_vec_with_registry!(
+            "storage_compact_pending_task_num",
+            "num of pending compact tasks in each compaction group",
+            &["group"],
+            registry
+        )
+        .unwrap();
+
+        let compact_task_count = register_int_counter_vec_with_registry!(
+            "storage_compact_task_count",
+            "num of reported compact tasks in each compaction group",
+            &["group", "result"],
+            registry
+        )
+        .unwrap();
+
+        let compact_read_size = register_int_counter_vec_with_registry!(
+            "storage_compact_read_size",
+            "KBs read by finished compact tasks in each compaction group",
+            &["group"],
+            registry
+        )
+        .unwrap();
+
+        let compact_write_size = register_int_counter_vec_with_registry!(
+            "storage_compact_write_size",
+            "KBs written by finished compact tasks in each compaction group",
+            &["group"],
+            registry
+        )
+        .unwrap();
+
         let hummock_manager_lock_time = register_histogram_vec_with_registry!(
             "hummock_manager_lock_time",
             "latency for hummock manager to acquire the rwlock",
@@ -148,6 +231,20 @@ impl MetaMetrics {
         )
         .unwrap();
 
+        let vacuum_scanned_sst_count = register_int_counter_with_registry!(
+            "storage_vacuum_scanned_sst_count",
+            "num of SSTs found to be vacuumed",
+            registry
+        )
+        .unwrap();
+
+        let vacuum_deleted_sst_count = register_int_counter_with_registry!(
+            "storage_vacuum_deleted_sst_count",
+            "num of SSTs acknowledged to be deleted by vacuum",
+            registry
+        )
+        .unwrap();
+
         Self {
             registry,
 
@@ -156,6 +253,10 @@ impl MetaMetrics {
             barrier_send_latency,
             all_barrier_nums,
             in_flight_barrier_nums,
+            recovery_duration,
+            recovery_attempts,
+            stream_client_pool_size,
+            stream_client_pool_eviction_count,
 
             max_committed_epoch,
             uncommitted_sst_num,
@@ -163,8 +264,14 @@ impl MetaMetrics {
             level_compact_cnt,
             level_file_size,
             version_size,
+            compact_pending_task_num,
+            compact_task_count,
+            compact_read_size,
+            compact_write_size,
             hummock_manager_lock_time,
             hummock_manager_real_process_time,
+            vacuum_scanned_sst_count,
+            vacuum_deleted_sst_count,
         }
     }
 
@@ -172,8 +279,61 @@ impl MetaMetrics {
         &self.registry
     }
 }
+
+/// Estimate the `q`-quantile of the observations in `histogram` by linear interpolation inside the
+/// bucket where the quantile falls, in the same way as `histogram_quantile` of Prometheus. Returns
+/// 0 if there's no observation.
+pub fn histogram_quantile(histogram: &Histogram, q: f64) -> f64 {
+    let metric = histogram.metric();
+    let histogram = metric.get_histogram();
+    let sample_count = histogram.get_sample_count();
+    if sample_count == 0 {
+        return 0.0;
+    }
+
+    let rank = q * sample_count as f64;
+    let (mut prev_count, mut prev_bound) = (0, 0.0);
+    for bucket in histogram.get_bucket() {
+        let count = bucket.get_cumulative_count();
+        let bound = bucket.get_upper_bound();
+        if count as f64 >= rank {
+            if count == prev_count {
+                return bound;
+            }
+            return prev_bound
+                + (bound - prev_bound) * (rank - prev_count as f64) / (count - prev_count) as f64;
+        }
+        (prev_count, prev_bound) = (count, bound);
+    }
+    // The quantile falls into the `+Inf` bucket.
+    prev_bound
+}
+
 impl Default for MetaMetrics {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_quantile() {
+        let histogram =
+            Histogram::with_opts(histogram_opts!("test", "test", vec![1.0, 2.0, 4.0])).unwrap();
+        assert_eq!(histogram_quantile(&histogram, 0.5), 0.0);
+
+        for v in [0.5, 1.5, 1.5, 3.0] {
+            histogram.observe(v);
+        }
+        assert_eq!(histogram_quantile(&histogram, 0.25), 1.0);
+        assert_eq!(histogram_quantile(&histogram, 0.5), 1.5);
+        assert_eq!(histogram_quantile(&histogram, 1.0), 4.0);
+
+        // Observations out of the largest bucket.
+        histogram.observe(10.0);
+        assert_eq!(histogram_quantile(&histogram, 1.0), 4.0);
+    }
+}
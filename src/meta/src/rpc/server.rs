@@ -409,10 +409,13 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
     let compaction_scheduler = Arc::new(CompactionScheduler::new(
         hummock_manager.clone(),
         compactor_manager.clone(),
+        env.opts.max_concurrent_compaction_tasks,
     ));
     let vacuum_trigger = Arc::new(hummock::VacuumTrigger::new(
         hummock_manager.clone(),
         compactor_manager.clone(),
+        meta_metrics.clone(),
+        env.opts.vacuum_dry_run,
     ));
     let ddl_lock = Arc::new(RwLock::new(()));
 
@@ -421,7 +424,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         env.clone(),
         catalog_manager.clone(),
         stream_manager,
-        source_manager,
+        source_manager.clone(),
         cluster_manager.clone(),
         fragment_manager.clone(),
         ddl_lock.clone(),
@@ -433,9 +436,11 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         barrier_manager.clone(),
         fragment_manager.clone(),
         cluster_manager.clone(),
+        source_manager,
         ddl_lock,
     );
-    let cluster_srv = ClusterServiceImpl::<S>::new(cluster_manager.clone());
+    let cluster_srv =
+        ClusterServiceImpl::<S>::new(cluster_manager.clone(), barrier_manager.clone());
     let stream_srv = StreamServiceImpl::<S>::new(
         env.clone(),
         barrier_manager.clone(),
@@ -469,6 +474,7 @@ pub async fn rpc_serve_with_store<S: MetaStore>(
         vacuum_trigger,
         notification_manager,
         compaction_scheduler,
+        env.opts.vacuum_interval,
     )
     .await;
     sub_tasks.push((lease_handle, lease_shutdown));
@@ -16,25 +16,33 @@ use risingwave_common::error::tonic_err;
 use risingwave_pb::meta::cluster_service_server::ClusterService;
 use risingwave_pb::meta::{
     ActivateWorkerNodeRequest, ActivateWorkerNodeResponse, AddWorkerNodeRequest,
-    AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse, ListAllNodesRequest,
-    ListAllNodesResponse,
+    AddWorkerNodeResponse, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse,
+    DrainWorkerNodeRequest, DrainWorkerNodeResponse, ListAllNodesRequest, ListAllNodesResponse,
 };
 use tonic::{Request, Response, Status};
 
+use crate::barrier::BarrierManagerRef;
 use crate::cluster::ClusterManagerRef;
 use crate::storage::MetaStore;
 
 #[derive(Clone)]
 pub struct ClusterServiceImpl<S: MetaStore> {
     cluster_manager: ClusterManagerRef<S>,
+    barrier_manager: BarrierManagerRef<S>,
 }
 
 impl<S> ClusterServiceImpl<S>
 where
     S: MetaStore,
 {
-    pub fn new(cluster_manager: ClusterManagerRef<S>) -> Self {
-        ClusterServiceImpl { cluster_manager }
+    pub fn new(
+        cluster_manager: ClusterManagerRef<S>,
+        barrier_manager: BarrierManagerRef<S>,
+    ) -> Self {
+        ClusterServiceImpl {
+            cluster_manager,
+            barrier_manager,
+        }
     }
 }
 
@@ -51,9 +59,10 @@ where
         let worker_type = req.get_worker_type().map_err(tonic_err)?;
         let host = req.get_host().map_err(tonic_err)?.clone();
         let worker_node_parallelism = req.worker_node_parallelism as usize;
+        let role = req.role();
         let worker_node = self
             .cluster_manager
-            .add_worker_node(worker_type, host, worker_node_parallelism)
+            .add_worker_node(worker_type, host, worker_node_parallelism, req.labels, role)
             .await?;
         Ok(Response::new(AddWorkerNodeResponse {
             status: None,
@@ -81,6 +90,16 @@ where
         Ok(Response::new(DeleteWorkerNodeResponse { status: None }))
     }
 
+    async fn drain_worker_node(
+        &self,
+        request: Request<DrainWorkerNodeRequest>,
+    ) -> Result<Response<DrainWorkerNodeResponse>, Status> {
+        let req = request.into_inner();
+        let host = req.get_host().map_err(tonic_err)?.clone();
+        self.barrier_manager.drain_worker_node(host).await?;
+        Ok(Response::new(DrainWorkerNodeResponse { status: None }))
+    }
+
     async fn list_all_nodes(
         &self,
         request: Request<ListAllNodesRequest>,
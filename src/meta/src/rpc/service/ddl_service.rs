@@ -308,7 +308,37 @@ where
 
         self.env.idle_manager().record_activity();
 
-        let table_id = request.into_inner().table_id;
+        let req = request.into_inner();
+        let table_id = req.table_id;
+        // 0. Drop the dependent relations first if cascading, from the downstream ones.
+        if req.cascade {
+            let dependents = self
+                .catalog_manager
+                .list_dependent_relations(table_id)
+                .await
+                .map_err(tonic_err)?;
+            for dependent in dependents {
+                match dependent {
+                    Relation::Table(table) => {
+                        self.catalog_manager
+                            .drop_table(table.id)
+                            .await
+                            .map_err(tonic_err)?;
+                        self.stream_manager
+                            .drop_materialized_view(&TableId::new(table.id))
+                            .await
+                            .map_err(tonic_err)?;
+                    }
+                    Relation::Sink(sink) => {
+                        self.catalog_manager
+                            .drop_sink(sink.id)
+                            .await
+                            .map_err(tonic_err)?;
+                    }
+                }
+            }
+        }
+
         // 1. Drop table in catalog. Ref count will be checked.
         let version = self
             .catalog_manager
@@ -328,6 +358,28 @@ where
         }))
     }
 
+    async fn rename_materialized_view(
+        &self,
+        request: Request<RenameMaterializedViewRequest>,
+    ) -> Result<Response<RenameMaterializedViewResponse>, Status> {
+        self.ddl_lock.read().await;
+        self.env.idle_manager().record_activity();
+
+        let req = request.into_inner();
+        // Only the catalog is updated, as the stream manager refers to the mview by id.
+        let (version, dependent_relation_names) = self
+            .catalog_manager
+            .rename_table(req.table_id, &req.new_name)
+            .await
+            .map_err(tonic_err)?;
+
+        Ok(Response::new(RenameMaterializedViewResponse {
+            status: None,
+            version,
+            dependent_relation_names,
+        }))
+    }
+
     async fn create_materialized_source(
         &self,
         request: Request<CreateMaterializedSourceRequest>,
@@ -529,7 +581,10 @@ where
         };
 
         // Resolve fragments.
-        let parallel_degree = self.cluster_manager.get_parallel_unit_count().await;
+        let parallel_degree = self
+            .cluster_manager
+            .get_streaming_parallel_unit_count()
+            .await;
 
         let mut actor_graph_builder = ActorGraphBuilder::new(
             self.env.id_gen_manager_ref(),
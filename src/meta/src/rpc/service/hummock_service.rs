@@ -14,6 +14,7 @@
 
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
 
 use risingwave_common::catalog::TableId;
 use risingwave_common::error::{tonic_err, ErrorCode};
@@ -181,6 +182,43 @@ where
         Ok(Response::new(UnpinSnapshotBeforeResponse { status: None }))
     }
 
+    async fn pin_named_snapshot(
+        &self,
+        request: Request<PinNamedSnapshotRequest>,
+    ) -> Result<Response<PinNamedSnapshotResponse>, Status> {
+        let req = request.into_inner();
+        // A snapshot pinned with no TTL would be unpinned right away.
+        if req.ttl_sec == 0 {
+            return Err(Status::invalid_argument("ttl_sec must be positive"));
+        }
+        let ttl = Duration::from_secs(req.ttl_sec);
+        let result = if req.epoch == 0 {
+            self.hummock_manager.pin_named_snapshot(req.name, ttl).await
+        } else {
+            self.hummock_manager
+                .pin_named_snapshot_at(req.name, req.epoch, ttl)
+                .await
+        };
+        match result {
+            Ok(hummock_snapshot) => Ok(Response::new(PinNamedSnapshotResponse {
+                status: None,
+                snapshot: Some(hummock_snapshot),
+            })),
+            Err(e) => Err(tonic_err(e)),
+        }
+    }
+
+    async fn unpin_named_snapshot(
+        &self,
+        request: Request<UnpinNamedSnapshotRequest>,
+    ) -> Result<Response<UnpinNamedSnapshotResponse>, Status> {
+        let req = request.into_inner();
+        if let Err(e) = self.hummock_manager.unpin_named_snapshot(&req.name).await {
+            return Err(tonic_err(e));
+        }
+        Ok(Response::new(UnpinNamedSnapshotResponse { status: None }))
+    }
+
     async fn get_new_table_id(
         &self,
         _request: Request<GetNewTableIdRequest>,
@@ -311,8 +349,57 @@ where
             Ok(hummock_snapshot) => Ok(Response::new(GetEpochResponse {
                 status: None,
                 snapshot: Some(hummock_snapshot),
+                safe_epoch: self.hummock_manager.get_safe_epoch().await,
             })),
             Err(e) => Err(tonic_err(e)),
         }
     }
+
+    async fn report_compaction_task_progress(
+        &self,
+        request: Request<ReportCompactionTaskProgressRequest>,
+    ) -> Result<Response<ReportCompactionTaskProgressResponse>, Status> {
+        let req = request.into_inner();
+        self.hummock_manager
+            .update_compact_task_progress(req.context_id, req.progress)
+            .await;
+        Ok(Response::new(ReportCompactionTaskProgressResponse {
+            status: None,
+        }))
+    }
+
+    async fn list_compaction_tasks(
+        &self,
+        _request: Request<ListCompactionTasksRequest>,
+    ) -> Result<Response<ListCompactionTasksResponse>, Status> {
+        Ok(Response::new(ListCompactionTasksResponse {
+            status: None,
+            tasks: self.hummock_manager.list_compaction_tasks().await,
+        }))
+    }
+
+    async fn cancel_compaction_task(
+        &self,
+        request: Request<CancelCompactionTaskRequest>,
+    ) -> Result<Response<CancelCompactionTaskResponse>, Status> {
+        let req = request.into_inner();
+        match self.hummock_manager.cancel_compact_task(req.task_id).await {
+            Ok(cancelled) => Ok(Response::new(CancelCompactionTaskResponse {
+                status: None,
+                cancelled,
+            })),
+            Err(e) => Err(tonic_err(e)),
+        }
+    }
+
+    async fn get_current_version(
+        &self,
+        _request: Request<GetCurrentVersionRequest>,
+    ) -> Result<Response<GetCurrentVersionResponse>, Status> {
+        let current_version = self.hummock_manager.get_current_version().await;
+        Ok(Response::new(GetCurrentVersionResponse {
+            status: None,
+            current_version: Some(current_version),
+        }))
+    }
 }
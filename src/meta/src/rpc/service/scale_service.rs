@@ -17,8 +17,8 @@ use std::sync::Arc;
 use risingwave_pb::common::WorkerType;
 use risingwave_pb::meta::scale_service_server::ScaleService;
 use risingwave_pb::meta::{
-    GetClusterInfoRequest, GetClusterInfoResponse, PauseRequest, PauseResponse, ResumeRequest,
-    ResumeResponse,
+    GetClusterInfoRequest, GetClusterInfoResponse, PauseRequest, PauseResponse,
+    RebalanceSplitsRequest, RebalanceSplitsResponse, ResumeRequest, ResumeResponse,
 };
 use tokio::sync::RwLock;
 use tonic::{Request, Response, Status};
@@ -27,12 +27,13 @@ use crate::barrier::{BarrierManagerRef, Command};
 use crate::cluster::ClusterManagerRef;
 use crate::model::MetadataModel;
 use crate::storage::MetaStore;
-use crate::stream::FragmentManagerRef;
+use crate::stream::{FragmentManagerRef, SourceManagerRef};
 
 pub struct ScaleServiceImpl<S: MetaStore> {
     barrier_manager: BarrierManagerRef<S>,
     fragment_manager: FragmentManagerRef<S>,
     cluster_manager: ClusterManagerRef<S>,
+    source_manager: SourceManagerRef<S>,
     ddl_lock: Arc<RwLock<()>>,
 }
 
@@ -44,12 +45,14 @@ where
         barrier_manager: BarrierManagerRef<S>,
         fragment_manager: FragmentManagerRef<S>,
         cluster_manager: ClusterManagerRef<S>,
+        source_manager: SourceManagerRef<S>,
         ddl_lock: Arc<RwLock<()>>,
     ) -> Self {
         Self {
             barrier_manager,
             fragment_manager,
             cluster_manager,
+            source_manager,
             ddl_lock,
         }
     }
@@ -97,4 +100,16 @@ where
             table_fragments,
         }))
     }
+
+    #[cfg_attr(coverage, no_coverage)]
+    async fn rebalance_splits(
+        &self,
+        request: Request<RebalanceSplitsRequest>,
+    ) -> Result<Response<RebalanceSplitsResponse>, Status> {
+        // Not to race with the DDLs changing the actors of the fragment.
+        let _ddl_guard = self.ddl_lock.read().await;
+        let fragment_id = request.into_inner().fragment_id;
+        self.source_manager.rebalance_splits(fragment_id).await?;
+        Ok(Response::new(RebalanceSplitsResponse {}))
+    }
 }
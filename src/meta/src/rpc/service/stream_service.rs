@@ -19,6 +19,7 @@ use risingwave_pb::meta::list_table_fragments_response::{
     ActorInfo, FragmentInfo, TableFragmentInfo,
 };
 use risingwave_pb::meta::stream_manager_service_server::StreamManagerService;
+use risingwave_pb::meta::table_fragments::ActorState;
 use risingwave_pb::meta::*;
 use tonic::{Request, Response, Status};
 
@@ -70,6 +71,24 @@ where
         Ok(Response::new(FlushResponse { status: None }))
     }
 
+    #[cfg_attr(coverage, no_coverage)]
+    async fn get_barrier_stats(
+        &self,
+        request: Request<GetBarrierStatsRequest>,
+    ) -> TonicResponse<GetBarrierStatsResponse> {
+        let _req = request.into_inner();
+
+        let stats = self.barrier_manager.barrier_stats()?;
+        Ok(Response::new(GetBarrierStatsResponse {
+            in_flight_barrier_nums: stats.in_flight_barrier_nums as u32,
+            all_barrier_nums: stats.all_barrier_nums as u32,
+            max_committed_epoch: stats.max_committed_epoch,
+            latency_p50: stats.latency_p50,
+            latency_p90: stats.latency_p90,
+            latency_p99: stats.latency_p99,
+        }))
+    }
+
     #[cfg_attr(coverage, no_coverage)]
     async fn list_table_fragments(
         &self,
@@ -78,6 +97,7 @@ where
         let req = request.into_inner();
         let table_ids = HashSet::<u32>::from_iter(req.table_ids);
         let table_fragments = self.fragment_manager.list_table_fragments().await?;
+        let create_progress = self.barrier_manager.create_mview_progress();
         let info = table_fragments
             .into_iter()
             .filter(|tf| table_ids.contains(&tf.table_id().table_id))
@@ -85,15 +105,27 @@ where
                 (
                     tf.table_id().table_id,
                     TableFragmentInfo {
+                        create_progress: create_progress
+                            .get(&tf.table_id())
+                            .copied()
+                            .unwrap_or_default(),
                         fragments: tf
                             .fragments
                             .into_iter()
                             .map(|(id, fragment)| FragmentInfo {
                                 id,
+                                fragment_type: fragment.fragment_type,
+                                distribution_type: fragment.distribution_type,
                                 actors: fragment
                                     .actors
                                     .into_iter()
                                     .map(|actor| ActorInfo {
+                                        state: tf
+                                            .actor_status
+                                            .get(&actor.actor_id)
+                                            .map_or(ActorState::Unspecified as i32, |status| {
+                                                status.state
+                                            }),
                                         id: actor.actor_id,
                                         node: actor.nodes,
                                         dispatcher: actor.dispatcher,
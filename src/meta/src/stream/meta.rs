@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::collections::hash_map::Entry;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{btree_map, BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 use itertools::Itertools;
@@ -26,6 +26,7 @@ use risingwave_common::{bail, try_match_expand};
 use risingwave_pb::common::{ParallelUnit, WorkerNode};
 use risingwave_pb::meta::table_fragments::ActorState;
 use risingwave_pb::stream_plan::{Dispatcher, FragmentType, StreamActor};
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
 use crate::barrier::Reschedule;
@@ -35,6 +36,26 @@ use crate::model::{ActorId, FragmentId, MetadataModel, TableFragments, Transacti
 use crate::storage::{MetaStore, Transaction};
 use crate::stream::record_table_vnode_mappings;
 
+/// The plan of migrating actors to other workers, made by [`FragmentManager::plan_migration`],
+/// which can be logged or reviewed before [`FragmentManager::apply_migration`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    /// The workers the actors are migrated to.
+    pub actor_migrations: BTreeMap<ActorId, WorkerId>,
+    /// The parallel units of the migrated actors, and the parallel units of the target workers
+    /// replacing them.
+    pub parallel_unit_migrations: BTreeMap<ParallelUnitId, ParallelUnit>,
+}
+
+impl MigrationPlan {
+    pub fn parallel_unit_migrate_map(&self) -> HashMap<ParallelUnitId, ParallelUnit> {
+        self.parallel_unit_migrations
+            .iter()
+            .map(|(id, parallel_unit)| (*id, parallel_unit.clone()))
+            .collect()
+    }
+}
+
 struct FragmentManagerCore {
     table_fragments: HashMap<TableId, TableFragments>,
 }
@@ -321,59 +342,100 @@ where
         }
     }
 
-    /// Used in [`crate::barrier::GlobalBarrierManager`]
-    /// migrate actors and update fragments, generate migrate info
-    pub async fn migrate_actors(
+    /// Plans the migration of the actors in `migrate_map` to their target workers in `node_map`,
+    /// without updating anything. All the actors on a parallel unit are moved to the same
+    /// parallel unit of the target worker.
+    ///
+    /// An unknown actor or target worker, or a target worker without enough parallel units, fails
+    /// the planning.
+    pub async fn plan_migration(
         &self,
         migrate_map: &HashMap<ActorId, WorkerId>,
         node_map: &HashMap<WorkerId, WorkerNode>,
-    ) -> Result<(Vec<TableFragments>, HashMap<ParallelUnitId, ParallelUnit>)> {
-        let mut parallel_unit_migrate_map = HashMap::new();
+    ) -> Result<MigrationPlan> {
+        let table_fragments = self.list_table_fragments().await?;
+
+        let all_actor_ids: HashSet<_> = table_fragments
+            .iter()
+            .flat_map(|fragment| fragment.actor_status.keys().copied())
+            .collect();
+        for (actor_id, worker_id) in migrate_map {
+            if !all_actor_ids.contains(actor_id) {
+                bail!("actor {} not found", actor_id);
+            }
+            if !node_map.contains_key(worker_id) {
+                bail!("worker {} not found", worker_id);
+            }
+        }
+
+        let mut plan = MigrationPlan::default();
         let mut pu_map: HashMap<WorkerId, Vec<&ParallelUnit>> = HashMap::new();
         // split parallel units of node into types, map them with WorkerId
         for (node_id, node) in node_map {
             let pu = node.parallel_units.iter().collect_vec();
             pu_map.insert(*node_id, pu);
         }
-        // update actor status and generate pu to pu migrate info
+        for fragment in &table_fragments {
+            for (actor_id, status) in &fragment.actor_status {
+                let new_node_id = match migrate_map.get(actor_id) {
+                    Some(new_node_id) => new_node_id,
+                    None => continue,
+                };
+                plan.actor_migrations.insert(*actor_id, *new_node_id);
+                if let Some(ref old_parallel_unit) = status.parallel_unit {
+                    if let btree_map::Entry::Vacant(e) =
+                        plan.parallel_unit_migrations.entry(old_parallel_unit.id)
+                    {
+                        let new_parallel_unit = match pu_map.get_mut(new_node_id).unwrap().pop() {
+                            Some(parallel_unit) => parallel_unit,
+                            None => bail!(
+                                "worker {} has no parallel unit left to migrate actor {} to",
+                                new_node_id,
+                                actor_id
+                            ),
+                        };
+                        e.insert(new_parallel_unit.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Applies the migration `plan` to the fragments, and returns the updated fragments.
+    pub async fn apply_migration(&self, plan: &MigrationPlan) -> Result<Vec<TableFragments>> {
+        let parallel_unit_migrate_map = plan.parallel_unit_migrate_map();
         let mut table_fragments = self.list_table_fragments().await?;
         let mut new_fragments = Vec::new();
-        table_fragments.iter_mut().for_each(|fragment| {
+        for fragment in &mut table_fragments {
             let mut flag = false;
-            fragment
-                .actor_status
-                .iter_mut()
-                .for_each(|(actor_id, status)| {
-                    if let Some(new_node_id) = migrate_map.get(actor_id) {
-                        if let Some(ref old_parallel_unit) = status.parallel_unit {
-                            if let Entry::Vacant(e) =
-                                parallel_unit_migrate_map.entry(old_parallel_unit.id)
-                            {
-                                let new_parallel_unit =
-                                    pu_map.get_mut(new_node_id).unwrap().pop().unwrap();
-                                e.insert(new_parallel_unit.clone());
-                                status.parallel_unit = Some(new_parallel_unit.clone());
-                                flag = true;
-                            } else {
-                                status.parallel_unit = Some(
-                                    parallel_unit_migrate_map
-                                        .get(&old_parallel_unit.id)
-                                        .unwrap()
-                                        .clone(),
-                                );
-                            }
-                        }
-                    };
-                });
+            for (actor_id, status) in &mut fragment.actor_status {
+                if !plan.actor_migrations.contains_key(actor_id) {
+                    continue;
+                }
+                if let Some(ref old_parallel_unit) = status.parallel_unit {
+                    let new_parallel_unit = parallel_unit_migrate_map
+                        .get(&old_parallel_unit.id)
+                        .ok_or_else(|| {
+                            RwError::from(InternalError(format!(
+                                "parallel unit {} of actor {} is not in the migration plan",
+                                old_parallel_unit.id, actor_id
+                            )))
+                        })?;
+                    status.parallel_unit = Some(new_parallel_unit.clone());
+                    flag = true;
+                }
+            }
             if flag {
                 // update vnode mapping of updated fragments
                 fragment.update_vnode_mapping(&parallel_unit_migrate_map);
                 new_fragments.push(fragment.clone());
             }
-        });
+        }
         // update fragments
         self.batch_update_table_fragments(&new_fragments).await?;
-        Ok((new_fragments, parallel_unit_migrate_map))
+        Ok(new_fragments)
     }
 
     pub async fn all_node_actors(
@@ -610,3 +672,114 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_pb::meta::table_fragments::{ActorStatus, Fragment};
+
+    use super::*;
+    use crate::manager::MetaSrvEnv;
+
+    fn parallel_unit(id: ParallelUnitId, worker_node_id: WorkerId) -> ParallelUnit {
+        ParallelUnit { id, worker_node_id }
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_apply_migration() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let fragment_manager = FragmentManager::new(env).await?;
+
+        // Actors 1 and 2 share a parallel unit on worker 1, where actor 3 is on another one.
+        let actor_parallel_units = [
+            (1, parallel_unit(0, 1)),
+            (2, parallel_unit(0, 1)),
+            (3, parallel_unit(1, 1)),
+            (4, parallel_unit(2, 2)),
+        ];
+        let fragment = Fragment {
+            fragment_id: 0,
+            actors: actor_parallel_units
+                .iter()
+                .map(|(actor_id, _)| StreamActor {
+                    actor_id: *actor_id,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let mut table_fragments = TableFragments::new(
+            TableId::new(0),
+            BTreeMap::from([(0, fragment)]),
+            Default::default(),
+        );
+        table_fragments.set_actor_status(
+            actor_parallel_units
+                .iter()
+                .map(|(actor_id, parallel_unit)| {
+                    (
+                        *actor_id,
+                        ActorStatus {
+                            parallel_unit: Some(parallel_unit.clone()),
+                            state: ActorState::Running as i32,
+                        },
+                    )
+                })
+                .collect(),
+        );
+        fragment_manager
+            .start_create_table_fragments(table_fragments)
+            .await?;
+
+        let node_map = HashMap::from([(
+            3,
+            WorkerNode {
+                id: 3,
+                parallel_units: vec![parallel_unit(10, 3), parallel_unit(11, 3)],
+                ..Default::default()
+            },
+        )]);
+        let migrate_map = HashMap::from([(1, 3), (2, 3), (3, 3)]);
+        assert!(fragment_manager
+            .plan_migration(&HashMap::from([(1, 4)]), &node_map)
+            .await
+            .is_err());
+        assert!(fragment_manager
+            .plan_migration(&HashMap::from([(5, 3)]), &node_map)
+            .await
+            .is_err());
+
+        let plan = fragment_manager
+            .plan_migration(&migrate_map, &node_map)
+            .await?;
+        assert_eq!(
+            plan,
+            MigrationPlan {
+                actor_migrations: migrate_map.clone().into_iter().collect(),
+                parallel_unit_migrations: BTreeMap::from([
+                    (0, parallel_unit(11, 3)),
+                    (1, parallel_unit(10, 3)),
+                ]),
+            }
+        );
+        let json = serde_json::to_string(&plan).unwrap();
+        assert_eq!(serde_json::from_str::<MigrationPlan>(&json).unwrap(), plan);
+
+        // Nothing is updated until the plan is applied.
+        let table_fragments = fragment_manager
+            .select_table_fragments_by_table_id(&TableId::new(0))
+            .await?;
+        assert_eq!(table_fragments.worker_actor_ids()[&1], vec![1, 2, 3]);
+
+        let new_fragments = fragment_manager.apply_migration(&plan).await?;
+        assert_eq!(new_fragments.len(), 1);
+        let table_fragments = fragment_manager
+            .select_table_fragments_by_table_id(&TableId::new(0))
+            .await?;
+        let worker_actor_ids = table_fragments.worker_actor_ids();
+        assert_eq!(worker_actor_ids[&3], vec![1, 2, 3]);
+        assert_eq!(worker_actor_ids[&2], vec![4]);
+        assert!(!worker_actor_ids.contains_key(&1));
+
+        Ok(())
+    }
+}
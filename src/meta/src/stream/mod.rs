@@ -76,6 +76,18 @@ pub fn record_table_vnode_mappings(
             hash_mapping_manager
                 .set_fragment_state_table(fragment_id, node.right_table.as_ref().unwrap().id);
         }
+        NodeBody::Sort(node) => {
+            hash_mapping_manager
+                .set_fragment_state_table(fragment_id, node.state_table.as_ref().unwrap().id);
+        }
+        NodeBody::SessionWindow(node) => {
+            hash_mapping_manager
+                .set_fragment_state_table(fragment_id, node.state_table.as_ref().unwrap().id);
+        }
+        NodeBody::OverWindow(node) => {
+            hash_mapping_manager
+                .set_fragment_state_table(fragment_id, node.state_table.as_ref().unwrap().id);
+        }
         _ => {}
     }
     let input_nodes = stream_node.get_input();
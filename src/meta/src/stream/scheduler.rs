@@ -12,17 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::iter::empty;
 
 use anyhow::anyhow;
+use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use risingwave_common::bail;
 use risingwave_common::buffer::BitmapBuilder;
 use risingwave_common::error::Result;
 use risingwave_common::types::VIRTUAL_NODE_COUNT;
 use risingwave_common::util::compress::compress_data;
-use risingwave_pb::common::{ActorInfo, ParallelUnit, ParallelUnitMapping, WorkerNode};
+use risingwave_pb::common::{ActorInfo, ParallelUnit, ParallelUnitMapping, WorkerNode, WorkerType};
 use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
 use risingwave_pb::meta::table_fragments::Fragment;
 
@@ -32,6 +33,29 @@ use crate::manager::HashMappingManagerRef;
 use crate::model::ActorId;
 use crate::storage::MetaStore;
 
+/// Prefix of the materialized view properties that select workers by labels. For example,
+/// `WITH (worker.label.disk = 'nvme')` only schedules actors onto workers labeled `disk=nvme`.
+pub const WORKER_LABEL_PROPERTY_PREFIX: &str = "worker.label.";
+
+/// Extract the worker label selector from properties of a materialized view, or `None` if no
+/// label is specified.
+pub fn label_selector_from_properties(
+    properties: &HashMap<String, String>,
+) -> Option<HashMap<String, String>> {
+    let label_selector: HashMap<_, _> = properties
+        .iter()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(WORKER_LABEL_PROPERTY_PREFIX)
+                .map(|label| (label.to_string(), value.clone()))
+        })
+        .collect();
+    if label_selector.is_empty() {
+        None
+    } else {
+        Some(label_selector)
+    }
+}
+
 /// [`Scheduler`] defines schedule logic for mv actors.
 pub struct Scheduler<S: MetaStore> {
     cluster_manager: ClusterManagerRef<S>,
@@ -148,10 +172,16 @@ where
     /// The schedule procedure is two-fold:
     /// (1) For singleton fragments, we schedule each to one parallel unit randomly.
     /// (2) For normal fragments, we schedule them to all the parallel units in the cluster.
+    ///
+    /// The parallel units on serving-only compute nodes are never candidates.
+    ///
+    /// If `label_selector` is given, only parallel units on the workers with all these labels are
+    /// candidates, except for actors that must be colocated with their upstream.
     pub async fn schedule(
         &self,
         fragment: &mut Fragment,
         locations: &mut ScheduledLocations,
+        label_selector: Option<&HashMap<String, String>>,
     ) -> Result<()> {
         if fragment.actors.is_empty() {
             bail!("fragment has no actor");
@@ -169,7 +199,7 @@ where
                     locations.schedule_colocate_with(&actor.upstream_actor_id)?
                 } else {
                     // Randomly choose one parallel unit to schedule from all parallel units.
-                    let parallel_units = self.cluster_manager.list_parallel_units().await;
+                    let parallel_units = self.list_parallel_units(label_selector).await?;
                     parallel_units
                         .choose(&mut rand::thread_rng())
                         .unwrap()
@@ -187,7 +217,7 @@ where
             // Normal fragment
 
             // Find out all the hash parallel units in the cluster.
-            let mut parallel_units = self.cluster_manager.list_parallel_units().await;
+            let mut parallel_units = self.list_parallel_units(label_selector).await?;
             // FIXME(Kexiang): select appropriate parallel_units, currently only support
             // `parallel_degree < parallel_units.size()`
             parallel_units.truncate(fragment.actors.len());
@@ -225,8 +255,16 @@ where
                         parallel_units[idx % parallel_units.len()].clone()
                     };
 
-                actor.vnode_bitmap =
-                    Some(vnode_bitmaps.get(&parallel_unit.id).unwrap().to_protobuf());
+                let vnode_bitmap = vnode_bitmaps.get(&parallel_unit.id).ok_or_else(|| {
+                    anyhow!(
+                        "actor {} is colocated with parallel unit {} which is not selected by \
+                         the worker labels {:?}",
+                        actor.actor_id,
+                        parallel_unit.id,
+                        label_selector
+                    )
+                })?;
+                actor.vnode_bitmap = Some(vnode_bitmap.to_protobuf());
                 locations
                     .actor_locations
                     .insert(actor.actor_id, parallel_unit);
@@ -236,6 +274,37 @@ where
         Ok(())
     }
 
+    /// List all parallel units for streaming in the cluster, or only those on the compute nodes
+    /// matching `label_selector` if given. Returns an error if no compute node matches.
+    async fn list_parallel_units(
+        &self,
+        label_selector: Option<&HashMap<String, String>>,
+    ) -> Result<Vec<ParallelUnit>> {
+        let parallel_units = self.cluster_manager.list_streaming_parallel_units().await;
+        let Some(label_selector) = label_selector else {
+            return Ok(parallel_units);
+        };
+
+        let workers: HashSet<WorkerId> = self
+            .cluster_manager
+            .list_worker_node_by_label(WorkerType::ComputeNode, label_selector)
+            .await
+            .into_iter()
+            .map(|w| w.id)
+            .collect();
+        let parallel_units = parallel_units
+            .into_iter()
+            .filter(|p| workers.contains(&p.worker_node_id))
+            .collect_vec();
+        if parallel_units.is_empty() {
+            bail!(
+                "no compute node matches the worker labels {:?}",
+                label_selector
+            );
+        }
+        Ok(parallel_units)
+    }
+
     /// `set_fragment_vnode_mapping` works by following steps:
     /// 1. Build a vnode mapping according to parallel units where the fragment is scheduled.
     /// 2. Set the vnode mapping into the fragment.
@@ -275,7 +344,7 @@ mod test {
     use itertools::Itertools;
     use risingwave_common::buffer::Bitmap;
     use risingwave_common::types::VIRTUAL_NODE_COUNT;
-    use risingwave_pb::common::{HostAddress, WorkerType};
+    use risingwave_pb::common::{HostAddress, WorkerRole, WorkerType};
     use risingwave_pb::meta::table_fragments::fragment::FragmentDistributionType;
     use risingwave_pb::stream_plan::stream_node::NodeBody;
     use risingwave_pb::stream_plan::{MaterializeNode, StreamActor, StreamNode, TopNNode};
@@ -298,7 +367,13 @@ mod test {
                 port: i as i32,
             };
             cluster_manager
-                .add_worker_node(WorkerType::ComputeNode, host.clone(), fake_parallelism)
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    host.clone(),
+                    fake_parallelism,
+                    Default::default(),
+                    Default::default(),
+                )
                 .await?;
             cluster_manager.activate_worker_node(host).await?;
         }
@@ -367,7 +442,10 @@ mod test {
 
         // Test round robin schedule for singleton fragments
         for fragment in &mut single_fragments {
-            scheduler.schedule(fragment, &mut locations).await.unwrap();
+            scheduler
+                .schedule(fragment, &mut locations, None)
+                .await
+                .unwrap();
         }
         for fragment in single_fragments {
             assert_ne!(
@@ -388,7 +466,10 @@ mod test {
 
         // Test normal schedule for other fragments
         for fragment in &mut normal_fragments {
-            scheduler.schedule(fragment, &mut locations).await.unwrap();
+            scheduler
+                .schedule(fragment, &mut locations, None)
+                .await
+                .unwrap();
         }
         assert_eq!(
             locations
@@ -425,4 +506,163 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_schedule_with_label_selector() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager =
+            Arc::new(ClusterManager::new(env.clone(), Duration::from_secs(3600)).await?);
+
+        let fake_parallelism = 4;
+        let mut labeled_worker_id = 0;
+        for i in 0..2 {
+            let host = HostAddress {
+                host: "127.0.0.1".to_string(),
+                port: i as i32,
+            };
+            let labels = if i == 0 {
+                HashMap::from([("disk".to_string(), "nvme".to_string())])
+            } else {
+                HashMap::new()
+            };
+            let worker_node = cluster_manager
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    host.clone(),
+                    fake_parallelism,
+                    labels,
+                    Default::default(),
+                )
+                .await?;
+            if i == 0 {
+                labeled_worker_id = worker_node.id;
+            }
+            cluster_manager.activate_worker_node(host).await?;
+        }
+
+        let scheduler = Scheduler::new(cluster_manager, env.hash_mapping_manager_ref());
+        let mut locations = ScheduledLocations::new();
+
+        let mut fragment = Fragment {
+            fragment_id: 1,
+            fragment_type: 0,
+            distribution_type: FragmentDistributionType::Hash as i32,
+            actors: (1..=fake_parallelism as u32)
+                .map(|actor_id| StreamActor {
+                    actor_id,
+                    fragment_id: 1,
+                    nodes: Some(StreamNode {
+                        node_body: Some(NodeBody::Materialize(MaterializeNode {
+                            table_id: 1,
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }),
+                    dispatcher: vec![],
+                    upstream_actor_id: vec![],
+                    same_worker_node_as_upstream: false,
+                    vnode_bitmap: None,
+                })
+                .collect_vec(),
+            vnode_mapping: None,
+        };
+
+        // All actors are scheduled to the labeled worker.
+        let label_selector = label_selector_from_properties(&HashMap::from([(
+            format!("{}disk", WORKER_LABEL_PROPERTY_PREFIX),
+            "nvme".to_string(),
+        )]));
+        scheduler
+            .schedule(&mut fragment, &mut locations, label_selector.as_ref())
+            .await?;
+        assert_eq!(locations.actor_locations.len(), fake_parallelism);
+        assert!(locations
+            .actor_locations
+            .values()
+            .all(|p| p.worker_node_id == labeled_worker_id));
+
+        // Fail if no worker matches.
+        let label_selector = HashMap::from([("disk".to_string(), "hdd".to_string())]);
+        assert!(scheduler
+            .schedule(&mut fragment, &mut locations, Some(&label_selector))
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_schedule_without_serving_workers() -> Result<()> {
+        let env = MetaSrvEnv::for_test().await;
+        let cluster_manager =
+            Arc::new(ClusterManager::new(env.clone(), Duration::from_secs(3600)).await?);
+
+        let fake_parallelism = 4;
+        let mut streaming_worker_id = 0;
+        for (i, role) in [WorkerRole::Serving, WorkerRole::Streaming]
+            .into_iter()
+            .enumerate()
+        {
+            let host = HostAddress {
+                host: "127.0.0.1".to_string(),
+                port: i as i32,
+            };
+            let worker_node = cluster_manager
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    host.clone(),
+                    fake_parallelism,
+                    HashMap::new(),
+                    role,
+                )
+                .await?;
+            if role == WorkerRole::Streaming {
+                streaming_worker_id = worker_node.id;
+            }
+            cluster_manager.activate_worker_node(host).await?;
+        }
+        assert_eq!(
+            cluster_manager.get_streaming_parallel_unit_count().await,
+            fake_parallelism
+        );
+
+        let scheduler = Scheduler::new(cluster_manager, env.hash_mapping_manager_ref());
+        let mut locations = ScheduledLocations::new();
+
+        let mut fragment = Fragment {
+            fragment_id: 1,
+            fragment_type: 0,
+            distribution_type: FragmentDistributionType::Hash as i32,
+            actors: (1..=fake_parallelism as u32 * 2)
+                .map(|actor_id| StreamActor {
+                    actor_id,
+                    fragment_id: 1,
+                    nodes: Some(StreamNode {
+                        node_body: Some(NodeBody::Materialize(MaterializeNode {
+                            table_id: 1,
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }),
+                    dispatcher: vec![],
+                    upstream_actor_id: vec![],
+                    same_worker_node_as_upstream: false,
+                    vnode_bitmap: None,
+                })
+                .collect_vec(),
+            vnode_mapping: None,
+        };
+
+        // No actor is scheduled to the serving worker.
+        scheduler
+            .schedule(&mut fragment, &mut locations, None)
+            .await?;
+        assert_eq!(locations.actor_locations.len(), fake_parallelism * 2);
+        assert!(locations
+            .actor_locations
+            .values()
+            .all(|p| p.worker_node_id == streaming_worker_id));
+
+        Ok(())
+    }
 }
@@ -70,6 +70,9 @@ pub struct SourceManager<S: MetaStore> {
     barrier_manager: BarrierManagerRef<S>,
     compaction_group_manager: CompactionGroupManagerRef<S>,
     core: Arc<Mutex<SourceManagerCore<S>>>,
+    /// Serializes the split changes of the split discovery and the rebalance, which are pushed
+    /// down by barriers without holding the `core` lock.
+    split_change_lock: Mutex<()>,
 }
 
 pub struct SharedSplitMap {
@@ -334,6 +337,76 @@ where
     }
 }
 
+/// The split changes of a rebalance, applied by two barriers in order.
+#[derive(Debug, Default, PartialEq)]
+struct SplitRebalance {
+    /// The splits left to the actors that lose some splits.
+    revoked: HashMap<ActorId, Vec<SplitImpl>>,
+    /// The splits of the actors that gain some splits.
+    assigned: HashMap<ActorId, Vec<SplitImpl>>,
+}
+
+/// Computes an assignment of the splits where every actor has either `n` or `n + 1` splits,
+/// moving as few splits as possible. The discovered splits owned by none of the actors, e.g.,
+/// those of the actors removed by scaling, are assigned as well.
+///
+/// Returns `None` if the assignment is already balanced.
+fn rebalance_splits(
+    mut actor_splits: HashMap<ActorId, Vec<SplitImpl>>,
+    discovered_splits: &BTreeMap<String, SplitImpl>,
+) -> Option<SplitRebalance> {
+    if actor_splits.is_empty() {
+        return None;
+    }
+
+    let owned_split_ids: HashSet<_> = actor_splits
+        .values()
+        .flat_map(|splits| splits.iter().map(SplitImpl::id))
+        .collect();
+    let mut pool = discovered_splits
+        .iter()
+        .filter(|(split_id, _)| !owned_split_ids.contains(*split_id))
+        .map(|(_, split)| split.clone())
+        .collect_vec();
+
+    // The actors with the most splits keep the extra ones.
+    let mut actors = actor_splits
+        .iter()
+        .map(|(&actor_id, splits)| (actor_id, splits.len()))
+        .collect_vec();
+    actors.sort_by(|(id1, n1), (id2, n2)| n2.cmp(n1).then(id1.cmp(id2)));
+    let total = owned_split_ids.len() + pool.len();
+    let quotas = actors
+        .iter()
+        .enumerate()
+        .map(|(i, &(actor_id, _))| {
+            let quota = total / actors.len() + usize::from(i < total % actors.len());
+            (actor_id, quota)
+        })
+        .collect_vec();
+
+    let mut rebalance = SplitRebalance::default();
+    for &(actor_id, quota) in &quotas {
+        let splits = actor_splits.get_mut(&actor_id).unwrap();
+        if splits.len() > quota {
+            splits.sort_by_key(SplitImpl::id);
+            pool.extend(splits.drain(quota..));
+            rebalance.revoked.insert(actor_id, splits.clone());
+        }
+    }
+    for &(actor_id, quota) in &quotas {
+        let splits = actor_splits.get_mut(&actor_id).unwrap();
+        if splits.len() < quota {
+            let lacking = quota - splits.len();
+            splits.extend(pool.drain(..lacking));
+            rebalance.assigned.insert(actor_id, splits.clone());
+        }
+    }
+    assert!(pool.is_empty());
+
+    (!rebalance.revoked.is_empty() || !rebalance.assigned.is_empty()).then_some(rebalance)
+}
+
 pub(crate) fn fetch_source_fragments(
     source_fragments: &mut HashMap<SourceId, BTreeSet<FragmentId>>,
     table_fragments: &TableFragments,
@@ -371,12 +444,12 @@ fn diff_splits(
         return None;
     }
 
-    let mut new_discovered_splits = HashSet::new();
-    for (split_id, split) in discovered_splits {
-        if !prev_split_ids.contains(split_id) {
-            new_discovered_splits.insert(split.id());
-        }
-    }
+    // The actors of the splits, so that a new split is assigned to the actor of its parent, which
+    // reads it after the parent is drained.
+    let mut split_actors: HashMap<String, ActorId> = prev_actor_splits
+        .iter()
+        .flat_map(|(actor_id, splits)| splits.iter().map(|split| (split.id(), *actor_id)))
+        .collect();
 
     let mut result = HashMap::new();
 
@@ -387,9 +460,18 @@ fn diff_splits(
 
     let actor_len = actors.len();
 
-    for (index, split_id) in new_discovered_splits.into_iter().enumerate() {
-        let target_actor_id = actors[index % actor_len];
-        let split = discovered_splits.get(&split_id).unwrap().clone();
+    // The splits are discovered in the order of their ids, where the parents come first.
+    let new_discovered_splits = discovered_splits
+        .iter()
+        .filter(|(split_id, _)| !prev_split_ids.contains(*split_id));
+    for (index, (split_id, split)) in new_discovered_splits.enumerate() {
+        let target_actor_id = split
+            .parent_ids()
+            .iter()
+            .find_map(|parent_id| split_actors.get(parent_id).copied())
+            .unwrap_or(actors[index % actor_len]);
+        split_actors.insert(split_id.clone(), target_actor_id);
+        let split = split.clone();
 
         result
             .entry(target_actor_id)
@@ -405,6 +487,7 @@ impl<S> SourceManager<S>
 where
     S: MetaStore,
 {
+    const SOURCE_MAX_RETRIES: usize = 6;
     const SOURCE_RETRY_INTERVAL: Duration = Duration::from_secs(10);
     const SOURCE_TICK_INTERVAL: Duration = Duration::from_secs(10);
 
@@ -453,6 +536,7 @@ where
             barrier_manager,
             compaction_group_manager,
             core,
+            split_change_lock: Mutex::new(()),
         })
     }
 
@@ -488,13 +572,25 @@ where
         &self,
         source_fragments: Option<HashMap<SourceId, BTreeSet<FragmentId>>>,
         actor_splits: Option<HashMap<ActorId, Vec<SplitImpl>>>,
+    ) -> Result<()> {
+        let mut core = self.core.lock().await;
+        self.persist_actor_splits(&mut core, actor_splits.unwrap_or_default())
+            .await?;
+        core.patch_diff(source_fragments, None);
+
+        Ok(())
+    }
+
+    /// Persists the new splits of the actors, and updates them in `core`.
+    async fn persist_actor_splits(
+        &self,
+        core: &mut SourceManagerCore<S>,
+        actor_splits: HashMap<ActorId, Vec<SplitImpl>>,
     ) -> Result<()> {
         let mut trx = Transaction::default();
-        if let Some(actor_splits) = actor_splits.clone() {
-            for (actor_id, splits) in actor_splits {
-                let source_actor_info = SourceActorInfo { actor_id, splits };
-                source_actor_info.upsert_in_transaction(&mut trx)?;
-            }
+        for (actor_id, splits) in actor_splits.clone() {
+            let source_actor_info = SourceActorInfo { actor_id, splits };
+            source_actor_info.upsert_in_transaction(&mut trx)?;
         }
 
         self.env
@@ -503,8 +599,92 @@ where
             .await
             .map_err(|e| internal_error(e.to_string()))?;
 
-        let mut core = self.core.lock().await;
-        core.patch_diff(source_fragments, actor_splits);
+        core.patch_diff(None, Some(actor_splits));
+
+        Ok(())
+    }
+
+    fn split_change_command(actor_splits: &HashMap<ActorId, Vec<SplitImpl>>) -> Command {
+        Command::Plain(Some(Mutation::Splits(SourceChangeSplitMutation {
+            actor_splits: actor_splits
+                .iter()
+                .map(|(&actor_id, splits)| {
+                    (
+                        actor_id,
+                        ConnectorSplits {
+                            splits: splits.iter().map(ConnectorSplit::from).collect(),
+                        },
+                    )
+                })
+                .collect(),
+        })))
+    }
+
+    /// Reassigns the splits of the source fragment across its current actors, e.g., after the
+    /// fragment is scaled, so that every actor has either `n` or `n + 1` splits.
+    ///
+    /// The moved splits are first revoked from their actors by a barrier, and then assigned to
+    /// their new actors by another barrier, which is injected after the first one is committed.
+    /// The offsets of the splits are kept in the state store shared by the actors of the source,
+    /// so the new actors continue from the offsets committed by the previous ones, and no
+    /// message is lost or consumed twice.
+    pub async fn rebalance_splits(&self, fragment_id: FragmentId) -> Result<()> {
+        // Hold the lock all along, so that the split discovery doesn't assign splits in between.
+        // The `core` lock is released before injecting the barriers, as in `tick`.
+        let _split_change_guard = self.split_change_lock.lock().await;
+        let core = self.core.lock().await;
+
+        let source_id = core
+            .source_fragments
+            .iter()
+            .find(|(_, fragment_ids)| fragment_ids.contains(&fragment_id))
+            .map(|(&source_id, _)| source_id)
+            .ok_or_else(|| internal_error(format!("fragment {} reads no source", fragment_id)))?;
+        let discovered_splits = match core.managed_sources.get(&source_id) {
+            Some(handle) => handle
+                .splits
+                .lock()
+                .await
+                .splits
+                .clone()
+                .unwrap_or_default(),
+            None => BTreeMap::new(),
+        };
+        let actor_splits = core
+            .fragment_manager
+            .get_running_actors_of_fragment(fragment_id)
+            .await?
+            .into_iter()
+            .map(|actor_id| {
+                let splits = core.actor_splits.get(&actor_id).cloned();
+                (actor_id, splits.unwrap_or_default())
+            })
+            .collect();
+
+        let SplitRebalance { revoked, assigned } =
+            match rebalance_splits(actor_splits, &discovered_splits) {
+                Some(rebalance) => rebalance,
+                None => return Ok(()),
+            };
+        drop(core);
+        tracing::info!(
+            "rebalance splits of fragment {}, revoked: {:?}, assigned: {:?}",
+            fragment_id,
+            revoked,
+            assigned
+        );
+
+        for actor_splits in [revoked, assigned] {
+            if actor_splits.is_empty() {
+                continue;
+            }
+            // `run_command` returns after the barrier is committed.
+            self.barrier_manager
+                .run_command(Self::split_change_command(&actor_splits))
+                .await?;
+            let mut core = self.core.lock().await;
+            self.persist_actor_splits(&mut core, actor_splits).await?;
+        }
 
         Ok(())
     }
@@ -706,37 +886,26 @@ where
     }
 
     async fn tick(&self) -> Result<()> {
-        let diff = {
-            let mut core_guard = self.core.lock().await;
-            core_guard.diff().await?
-        };
+        // Not to race with the rebalance, which also changes the splits of the actors.
+        let _split_change_guard = self.split_change_lock.lock().await;
+        // The `core` lock is released before injecting the barrier, since the DDLs running the
+        // barriers may wait for it.
+        let diff = self.core.lock().await.diff().await?;
 
         if !diff.is_empty() {
-            let command = Command::Plain(Some(Mutation::Splits(SourceChangeSplitMutation {
-                actor_splits: diff
-                    .iter()
-                    .map(|(&actor_id, splits)| {
-                        (
-                            actor_id,
-                            ConnectorSplits {
-                                splits: splits.iter().map(ConnectorSplit::from).collect(),
-                            },
-                        )
-                    })
-                    .collect(),
-            })));
+            let command = Self::split_change_command(&diff);
             log::debug!("pushing down mutation {:#?}", command);
 
-            tokio_retry::Retry::spawn(FixedInterval::new(Self::SOURCE_RETRY_INTERVAL), || async {
+            let retry_strategy =
+                FixedInterval::new(Self::SOURCE_RETRY_INTERVAL).take(Self::SOURCE_MAX_RETRIES);
+            tokio_retry::Retry::spawn(retry_strategy, || async {
                 let command = command.clone();
                 self.barrier_manager.run_command(command).await
             })
-            .await
-            .expect("source manager barrier push down failed");
+            .await?;
 
-            self.patch_update(None, Some(diff))
-                .await
-                .expect("patch update failed");
+            let mut core = self.core.lock().await;
+            self.persist_actor_splits(&mut core, diff).await?;
         }
 
         Ok(())
@@ -766,3 +935,77 @@ where
             .collect_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_connector::source::kafka::KafkaSplit;
+    use risingwave_connector::source::kinesis::split::KinesisSplit;
+
+    use super::*;
+
+    fn split(partition: i32) -> SplitImpl {
+        SplitImpl::Kafka(KafkaSplit::new(partition, Some(0), None, "t".to_string()))
+    }
+
+    fn split_ids(splits: &[SplitImpl]) -> Vec<String> {
+        splits.iter().map(SplitImpl::id).sorted().collect()
+    }
+
+    #[test]
+    fn test_rebalance_splits() {
+        let discovered_splits = (0..5).map(|i| (i.to_string(), split(i))).collect();
+
+        // Actor 3 is added by scaling, and partition 4 was owned by a removed actor.
+        let actor_splits = HashMap::from([
+            (1, vec![split(0), split(1), split(2)]),
+            (2, vec![split(3)]),
+            (3, vec![]),
+        ]);
+        let rebalance = rebalance_splits(actor_splits, &discovered_splits).unwrap();
+        assert_eq!(rebalance.revoked.len(), 1);
+        assert_eq!(split_ids(&rebalance.revoked[&1]), vec!["0", "1"]);
+        assert_eq!(rebalance.assigned.len(), 2);
+        assert_eq!(split_ids(&rebalance.assigned[&2]), vec!["3", "4"]);
+        assert_eq!(split_ids(&rebalance.assigned[&3]), vec!["2"]);
+
+        let actor_splits = HashMap::from([
+            (1, vec![split(0), split(1)]),
+            (2, vec![split(3), split(4)]),
+            (3, vec![split(2)]),
+        ]);
+        assert_eq!(rebalance_splits(actor_splits, &discovered_splits), None);
+    }
+
+    #[test]
+    fn test_diff_splits_with_parents() {
+        let shard = |id: &str, parents: &[&str]| {
+            let split = KinesisSplit::restore_from_bytes(
+                serde_json::json!({
+                    "shard_id": id,
+                    "start_position": "Earliest",
+                    "end_position": "None",
+                    "parent_shard_ids": parents,
+                })
+                .to_string()
+                .as_bytes(),
+            )
+            .unwrap();
+            (id.to_string(), SplitImpl::Kinesis(split))
+        };
+        let discovered_splits = BTreeMap::from([
+            shard("0", &[]),
+            shard("1", &[]),
+            shard("2", &["1"]),
+            shard("3", &["1"]),
+        ]);
+
+        // The children of shard 1 are assigned to the actor reading shard 1.
+        let actor_splits = HashMap::from([
+            (1, vec![discovered_splits["0"].clone()]),
+            (2, vec![discovered_splits["1"].clone()]),
+        ]);
+        let diff = diff_splits(actor_splits, &discovered_splits).unwrap();
+        assert_eq!(diff.len(), 1);
+        assert_eq!(split_ids(&diff[&2]), vec!["1", "2", "3"]);
+    }
+}
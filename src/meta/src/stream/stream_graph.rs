@@ -626,6 +626,13 @@ impl StreamGraphBuilder {
                         check_and_fill_internal_table(node.table_id_h, None);
                     }
 
+                    NodeBody::GroupTopN(node) => {
+                        node.table_id += table_id_offset;
+
+                        // TODO add catalog::Table to GroupTopN
+                        check_and_fill_internal_table(node.table_id, None);
+                    }
+
                     NodeBody::GlobalSimpleAgg(node) | NodeBody::LocalSimpleAgg(node) => {
                         assert_eq!(node.internal_tables.len(), node.agg_calls.len());
                         // In-place update the table id. Convert from local to global.
@@ -666,6 +673,48 @@ impl StreamGraphBuilder {
                             check_and_fill_internal_table(table.id, Some(table.clone()));
                         }
                     }
+
+                    NodeBody::Sort(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            table.id += table_id_offset;
+                            table.schema_id = ctx.schema_id;
+                            table.database_id = ctx.database_id;
+                            table.name = generate_intertable_name_with_type(
+                                &ctx.mview_name,
+                                table.id,
+                                "Sort",
+                            );
+                            check_and_fill_internal_table(table.id, Some(table.clone()));
+                        }
+                    }
+
+                    NodeBody::SessionWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            table.id += table_id_offset;
+                            table.schema_id = ctx.schema_id;
+                            table.database_id = ctx.database_id;
+                            table.name = generate_intertable_name_with_type(
+                                &ctx.mview_name,
+                                table.id,
+                                "SessionWindow",
+                            );
+                            check_and_fill_internal_table(table.id, Some(table.clone()));
+                        }
+                    }
+
+                    NodeBody::OverWindow(node) => {
+                        if let Some(table) = &mut node.state_table {
+                            table.id += table_id_offset;
+                            table.schema_id = ctx.schema_id;
+                            table.database_id = ctx.database_id;
+                            table.name = generate_intertable_name_with_type(
+                                &ctx.mview_name,
+                                table.id,
+                                "OverWindow",
+                            );
+                            check_and_fill_internal_table(table.id, Some(table.clone()));
+                        }
+                    }
                     _ => {}
                 }
 
@@ -32,7 +32,7 @@ use risingwave_pb::stream_service::{
 use risingwave_rpc_client::StreamClientPoolRef;
 use uuid::Uuid;
 
-use super::ScheduledLocations;
+use super::{label_selector_from_properties, ScheduledLocations};
 use crate::barrier::{BarrierManagerRef, Command};
 use crate::cluster::{ClusterManagerRef, WorkerId};
 use crate::hummock::compaction_group::manager::CompactionGroupManagerRef;
@@ -345,10 +345,13 @@ where
 
             // Schedule each fragment(actors) to nodes, recorded in `locations`.
             // Vnode mapping in fragment will be filled in as well.
+            let label_selector = label_selector_from_properties(table_properties);
             let topological_order = table_fragments.generate_topological_order();
             for fragment_id in topological_order {
                 let fragment = table_fragments.fragments.get_mut(&fragment_id).unwrap();
-                self.scheduler.schedule(fragment, &mut locations).await?;
+                self.scheduler
+                    .schedule(fragment, &mut locations, label_selector.as_ref())
+                    .await?;
             }
 
             locations
@@ -889,7 +892,13 @@ mod tests {
             };
             let fake_parallelism = 4;
             cluster_manager
-                .add_worker_node(WorkerType::ComputeNode, host.clone(), fake_parallelism)
+                .add_worker_node(
+                    WorkerType::ComputeNode,
+                    host.clone(),
+                    fake_parallelism,
+                    Default::default(),
+                    Default::default(),
+                )
                 .await?;
             cluster_manager.activate_worker_node(host).await?;
 
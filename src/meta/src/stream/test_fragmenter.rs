@@ -84,6 +84,7 @@ fn make_column_order(index: u32) -> ColumnOrder {
     ColumnOrder {
         order_type: OrderType::Ascending as i32,
         index,
+        nulls_first: false,
     }
 }
 
@@ -115,6 +116,7 @@ fn make_internal_table(id: u32, is_agg_value: bool) -> ProstTable {
         order_key: vec![ColumnOrder {
             index: 0,
             order_type: 2,
+            nulls_first: true,
         }],
         pk: vec![2],
         ..Default::default()
@@ -13,6 +13,10 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+#[cfg(any(test, feature = "test"))]
+use std::sync::Arc;
+#[cfg(any(test, feature = "test"))]
+use std::time::Duration;
 
 use bytes::Bytes;
 use fail::fail_point;
@@ -27,6 +31,8 @@ use crate::object::{BlockLocation, ObjectMetadata, ObjectStore};
 #[derive(Default)]
 pub struct InMemObjectStore {
     objects: Mutex<HashMap<String, Bytes>>,
+    #[cfg(any(test, feature = "test"))]
+    fault_injector: Option<Arc<FaultInjector>>,
 }
 
 #[async_trait::async_trait]
@@ -35,6 +41,8 @@ impl ObjectStore for InMemObjectStore {
         fail_point!("mem_upload_err", |_| Err(ObjectError::internal(
             "mem upload error"
         )));
+        #[cfg(any(test, feature = "test"))]
+        self.inject_upload_fault().await?;
         if obj.is_empty() {
             Err(ObjectError::internal("upload empty object"))
         } else {
@@ -47,11 +55,19 @@ impl ObjectStore for InMemObjectStore {
         fail_point!("mem_read_err", |_| Err(ObjectError::internal(
             "mem read error"
         )));
-        if let Some(loc) = block {
+        #[cfg(any(test, feature = "test"))]
+        let truncate_to = self.inject_read_fault().await?;
+        let obj = if let Some(loc) = block {
             self.get_object(path, |obj| find_block(obj, loc)).await?
         } else {
             self.get_object(path, |obj| Ok(obj.clone())).await?
-        }
+        }?;
+        #[cfg(any(test, feature = "test"))]
+        let obj = match truncate_to {
+            Some(len) => obj.slice(..len.min(obj.len())),
+            None => obj,
+        };
+        Ok(obj)
     }
 
     async fn readv(&self, path: &str, block_locs: &[BlockLocation]) -> ObjectResult<Vec<Bytes>> {
@@ -78,9 +94,62 @@ impl ObjectStore for InMemObjectStore {
 
 impl InMemObjectStore {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store whose uploads and reads go through `fault_injector`.
+    #[cfg(any(test, feature = "test"))]
+    pub fn with_fault_injector(fault_injector: Arc<FaultInjector>) -> Self {
         Self {
-            objects: Mutex::new(HashMap::new()),
+            fault_injector: Some(fault_injector),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(any(test, feature = "test"))]
+    async fn inject_upload_fault(&self) -> ObjectResult<()> {
+        let Some(injector) = &self.fault_injector else {
+            return Ok(());
+        };
+        let (latency, fail) = {
+            let mut inner = injector.inner.lock().unwrap();
+            inner.upload_count += 1;
+            (
+                inner.latency,
+                inner.fail_upload_at == Some(inner.upload_count),
+            )
+        };
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        if fail {
+            return Err(ObjectError::internal("injected upload error"));
         }
+        Ok(())
+    }
+
+    /// Returns the length the read should be truncated to, if any.
+    #[cfg(any(test, feature = "test"))]
+    async fn inject_read_fault(&self) -> ObjectResult<Option<usize>> {
+        let Some(injector) = &self.fault_injector else {
+            return Ok(None);
+        };
+        let (latency, fail, truncate_to) = {
+            let mut inner = injector.inner.lock().unwrap();
+            inner.read_count += 1;
+            (
+                inner.latency,
+                inner.fail_read_at == Some(inner.read_count),
+                inner.truncate_read_to,
+            )
+        };
+        if let Some(latency) = latency {
+            tokio::time::sleep(latency).await;
+        }
+        if fail {
+            return Err(ObjectError::internal("injected read error"));
+        }
+        Ok(truncate_to)
     }
 
     async fn get_object<R, F>(&self, path: &str, f: F) -> ObjectResult<R>
@@ -104,6 +173,59 @@ fn find_block(obj: &Bytes, block: BlockLocation) -> ObjectResult<Bytes> {
     }
 }
 
+/// A hook to inject faults into an [`InMemObjectStore`], so that the error paths of its callers
+/// can be tested deterministically without a real object store.
+#[cfg(any(test, feature = "test"))]
+#[derive(Default)]
+pub struct FaultInjector {
+    inner: std::sync::Mutex<FaultInjectorInner>,
+}
+
+#[cfg(any(test, feature = "test"))]
+#[derive(Default)]
+struct FaultInjectorInner {
+    upload_count: usize,
+    read_count: usize,
+    fail_upload_at: Option<usize>,
+    fail_read_at: Option<usize>,
+    latency: Option<Duration>,
+    truncate_read_to: Option<usize>,
+}
+
+#[cfg(any(test, feature = "test"))]
+impl FaultInjector {
+    /// Fails the `n`-th upload from now on, counting from 1.
+    pub fn fail_nth_upload(&self, n: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.fail_upload_at = Some(inner.upload_count + n);
+    }
+
+    /// Fails the `n`-th read from now on, counting from 1. Each block of a `readv` is a read.
+    pub fn fail_nth_read(&self, n: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.fail_read_at = Some(inner.read_count + n);
+    }
+
+    /// Delays every upload and read by `latency`.
+    pub fn set_latency(&self, latency: Duration) {
+        self.inner.lock().unwrap().latency = Some(latency);
+    }
+
+    /// Truncates the bytes returned by every read to at most `len`.
+    pub fn truncate_reads_to(&self, len: usize) {
+        self.inner.lock().unwrap().truncate_read_to = Some(len);
+    }
+
+    /// Removes all the injected faults.
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.fail_upload_at = None;
+        inner.fail_read_at = None;
+        inner.latency = None;
+        inner.truncate_read_to = None;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -151,4 +273,28 @@ mod tests {
         let metadata = obj_store.metadata("/abc").await.unwrap();
         assert_eq!(metadata.total_size, 6);
     }
+
+    #[tokio::test]
+    async fn test_fault_injector() {
+        let injector = Arc::new(FaultInjector::default());
+        let obj_store = InMemObjectStore::with_fault_injector(injector.clone());
+
+        injector.fail_nth_upload(2);
+        obj_store.upload("/a", Bytes::from("123456")).await.unwrap();
+        obj_store
+            .upload("/b", Bytes::from("123456"))
+            .await
+            .unwrap_err();
+        obj_store.upload("/b", Bytes::from("123456")).await.unwrap();
+
+        injector.fail_nth_read(1);
+        obj_store.read("/a", None).await.unwrap_err();
+        assert_eq!(obj_store.read("/a", None).await.unwrap(), "123456");
+
+        injector.truncate_reads_to(2);
+        assert_eq!(obj_store.read("/a", None).await.unwrap(), "12");
+
+        injector.clear();
+        assert_eq!(obj_store.read("/a", None).await.unwrap(), "123456");
+    }
 }
@@ -15,7 +15,7 @@
 use async_trait::async_trait;
 use risingwave_hummock_sdk::{HummockEpoch, HummockSstableId, HummockVersionId, LocalSstableInfo};
 use risingwave_pb::hummock::{
-    CompactTask, CompactionGroup, HummockVersion, HummockVersionDelta,
+    CompactTask, CompactTaskProgress, CompactionGroup, HummockVersion, HummockVersionDelta,
     SubscribeCompactTasksResponse, VacuumTask,
 };
 use tonic::Streaming;
@@ -36,6 +36,10 @@ pub trait HummockMetaClient: Send + Sync + 'static {
     async fn get_epoch(&self) -> Result<HummockEpoch>;
     async fn get_new_table_id(&self) -> Result<HummockSstableId>;
     async fn report_compaction_task(&self, compact_task: CompactTask) -> Result<()>;
+    async fn report_compaction_task_progress(
+        &self,
+        progress: Vec<CompactTaskProgress>,
+    ) -> Result<()>;
     // We keep `commit_epoch` only for test/benchmark like ssbench.
     async fn commit_epoch(
         &self,
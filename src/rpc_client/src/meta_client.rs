@@ -25,7 +25,7 @@ use risingwave_pb::catalog::{
     Database as ProstDatabase, Schema as ProstSchema, Sink as ProstSink, Source as ProstSource,
     Table as ProstTable,
 };
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerNode, WorkerRole, WorkerType};
 use risingwave_pb::ddl_service::ddl_service_client::DdlServiceClient;
 use risingwave_pb::ddl_service::*;
 use risingwave_pb::hummock::hummock_manager_service_client::HummockManagerServiceClient;
@@ -96,11 +96,15 @@ impl MetaClient {
         worker_type: WorkerType,
         addr: &HostAddr,
         worker_node_parallelism: usize,
+        labels: HashMap<String, String>,
+        role: WorkerRole,
     ) -> Result<u32> {
         let request = AddWorkerNodeRequest {
             worker_type: worker_type as i32,
             host: Some(addr.to_protobuf()),
             worker_node_parallelism: worker_node_parallelism as u64,
+            labels,
+            role: role as i32,
         };
         let resp = self.inner.add_worker_node(request).await?;
         let worker_node = resp.node.expect("AddWorkerNodeResponse::node is empty");
@@ -127,6 +131,20 @@ impl MetaClient {
         Ok(())
     }
 
+    /// List the nodes of `worker_type` in the cluster, optionally including those still starting.
+    pub async fn list_all_nodes(
+        &self,
+        worker_type: WorkerType,
+        include_starting_nodes: bool,
+    ) -> Result<Vec<WorkerNode>> {
+        let request = ListAllNodesRequest {
+            worker_type: worker_type as i32,
+            include_starting_nodes,
+        };
+        let resp = self.inner.list_all_nodes(request).await?;
+        Ok(resp.nodes)
+    }
+
     pub async fn create_database(&self, db: ProstDatabase) -> Result<(DatabaseId, CatalogVersion)> {
         let request = CreateDatabaseRequest { db: Some(db) };
         let resp = self.inner.create_database(request).await?;
@@ -157,15 +175,36 @@ impl MetaClient {
         Ok((resp.table_id.into(), resp.version))
     }
 
-    pub async fn drop_materialized_view(&self, table_id: TableId) -> Result<CatalogVersion> {
+    pub async fn drop_materialized_view(
+        &self,
+        table_id: TableId,
+        cascade: bool,
+    ) -> Result<CatalogVersion> {
         let request = DropMaterializedViewRequest {
             table_id: table_id.table_id(),
+            cascade,
         };
 
         let resp = self.inner.drop_materialized_view(request).await?;
         Ok(resp.version)
     }
 
+    /// Returns the catalog version and the names of the relations depending on the materialized
+    /// view.
+    pub async fn rename_materialized_view(
+        &self,
+        table_id: TableId,
+        new_name: &str,
+    ) -> Result<(CatalogVersion, Vec<String>)> {
+        let request = RenameMaterializedViewRequest {
+            table_id: table_id.table_id(),
+            new_name: new_name.to_string(),
+        };
+
+        let resp = self.inner.rename_materialized_view(request).await?;
+        Ok((resp.version, resp.dependent_relation_names))
+    }
+
     pub async fn create_source(&self, source: ProstSource) -> Result<(u32, CatalogVersion)> {
         let request = CreateSourceRequest {
             source: Some(source),
@@ -309,6 +348,15 @@ impl MetaClient {
         Ok(())
     }
 
+    /// Drain the node from the cluster, its actors will be migrated before it's deleted.
+    pub async fn drain(&self, addr: HostAddr) -> Result<()> {
+        let request = DrainWorkerNodeRequest {
+            host: Some(addr.to_protobuf()),
+        };
+        self.inner.drain_worker_node(request).await?;
+        Ok(())
+    }
+
     pub fn start_heartbeat_loop(
         meta_client: MetaClient,
         min_interval: Duration,
@@ -362,6 +410,44 @@ impl MetaClient {
         Ok(())
     }
 
+    pub async fn get_barrier_stats(&self) -> Result<GetBarrierStatsResponse> {
+        let request = GetBarrierStatsRequest::default();
+        let resp = self.inner.get_barrier_stats(request).await?;
+        Ok(resp)
+    }
+
+    /// Returns the range of epochs that can be read: from the safe epoch to the max committed
+    /// epoch, both inclusive.
+    pub async fn get_readable_epoch_range(&self) -> Result<(HummockEpoch, HummockEpoch)> {
+        let resp = self.inner.get_epoch(GetEpochRequest {}).await?;
+        Ok((resp.safe_epoch, resp.snapshot.unwrap().epoch))
+    }
+
+    /// Pins the snapshot at `epoch` under `name` for `ttl`. Pinning the same name again renews
+    /// the TTL.
+    pub async fn pin_named_snapshot(
+        &self,
+        name: &str,
+        epoch: HummockEpoch,
+        ttl: Duration,
+    ) -> Result<HummockEpoch> {
+        let request = PinNamedSnapshotRequest {
+            name: name.to_string(),
+            ttl_sec: ttl.as_secs(),
+            epoch,
+        };
+        let resp = self.inner.pin_named_snapshot(request).await?;
+        Ok(resp.snapshot.unwrap().epoch)
+    }
+
+    pub async fn unpin_named_snapshot(&self, name: &str) -> Result<()> {
+        let request = UnpinNamedSnapshotRequest {
+            name: name.to_string(),
+        };
+        self.inner.unpin_named_snapshot(request).await?;
+        Ok(())
+    }
+
     pub async fn list_table_fragments(
         &self,
         table_ids: &[u32],
@@ -390,6 +476,32 @@ impl MetaClient {
         let resp = self.inner.get_cluster_info(request).await?;
         Ok(resp)
     }
+
+    pub async fn rebalance_splits(&self, fragment_id: u32) -> Result<()> {
+        let request = RebalanceSplitsRequest { fragment_id };
+        let _resp = self.inner.rebalance_splits(request).await?;
+        Ok(())
+    }
+
+    pub async fn list_compaction_tasks(&self) -> Result<Vec<CompactTaskInfo>> {
+        let request = ListCompactionTasksRequest {};
+        let resp = self.inner.list_compaction_tasks(request).await?;
+        Ok(resp.tasks)
+    }
+
+    /// Returns false if the task is not assigned, e.g. it has already been finished or cancelled.
+    pub async fn cancel_compaction_task(&self, task_id: u64) -> Result<bool> {
+        let request = CancelCompactionTaskRequest { task_id };
+        let resp = self.inner.cancel_compaction_task(request).await?;
+        Ok(resp.cancelled)
+    }
+
+    /// Returns the current hummock version without pinning it.
+    pub async fn get_current_version(&self) -> Result<HummockVersion> {
+        let request = GetCurrentVersionRequest {};
+        let resp = self.inner.get_current_version(request).await?;
+        Ok(resp.current_version.unwrap())
+    }
 }
 
 #[async_trait]
@@ -474,6 +586,18 @@ impl HummockMetaClient for MetaClient {
         Ok(())
     }
 
+    async fn report_compaction_task_progress(
+        &self,
+        progress: Vec<CompactTaskProgress>,
+    ) -> Result<()> {
+        let req = ReportCompactionTaskProgressRequest {
+            context_id: self.worker_id(),
+            progress,
+        };
+        self.inner.report_compaction_task_progress(req).await?;
+        Ok(())
+    }
+
     async fn commit_epoch(
         &self,
         _epoch: HummockEpoch,
@@ -609,9 +733,11 @@ macro_rules! for_all_meta_rpc {
             ,{ cluster_client, add_worker_node, AddWorkerNodeRequest, AddWorkerNodeResponse }
             ,{ cluster_client, activate_worker_node, ActivateWorkerNodeRequest, ActivateWorkerNodeResponse }
             ,{ cluster_client, delete_worker_node, DeleteWorkerNodeRequest, DeleteWorkerNodeResponse }
+            ,{ cluster_client, drain_worker_node, DrainWorkerNodeRequest, DrainWorkerNodeResponse }
             ,{ cluster_client, list_all_nodes, ListAllNodesRequest, ListAllNodesResponse }
             ,{ heartbeat_client, heartbeat, HeartbeatRequest, HeartbeatResponse }
             ,{ stream_client, flush, FlushRequest, FlushResponse }
+            ,{ stream_client, get_barrier_stats, GetBarrierStatsRequest, GetBarrierStatsResponse }
             ,{ stream_client, list_table_fragments, ListTableFragmentsRequest, ListTableFragmentsResponse }
             ,{ ddl_client, create_materialized_source, CreateMaterializedSourceRequest, CreateMaterializedSourceResponse }
             ,{ ddl_client, create_materialized_view, CreateMaterializedViewRequest, CreateMaterializedViewResponse }
@@ -621,6 +747,7 @@ macro_rules! for_all_meta_rpc {
             ,{ ddl_client, create_database, CreateDatabaseRequest, CreateDatabaseResponse }
             ,{ ddl_client, drop_materialized_source, DropMaterializedSourceRequest, DropMaterializedSourceResponse }
             ,{ ddl_client, drop_materialized_view, DropMaterializedViewRequest, DropMaterializedViewResponse }
+            ,{ ddl_client, rename_materialized_view, RenameMaterializedViewRequest, RenameMaterializedViewResponse }
             ,{ ddl_client, drop_source, DropSourceRequest, DropSourceResponse }
             ,{ ddl_client, drop_sink, DropSinkRequest, DropSinkResponse }
             ,{ ddl_client, drop_database, DropDatabaseRequest, DropDatabaseResponse }
@@ -633,12 +760,18 @@ macro_rules! for_all_meta_rpc {
             ,{ hummock_client, get_epoch, GetEpochRequest, GetEpochResponse }
             ,{ hummock_client, unpin_snapshot, UnpinSnapshotRequest, UnpinSnapshotResponse }
             ,{ hummock_client, unpin_snapshot_before, UnpinSnapshotBeforeRequest, UnpinSnapshotBeforeResponse }
+            ,{ hummock_client, pin_named_snapshot, PinNamedSnapshotRequest, PinNamedSnapshotResponse }
+            ,{ hummock_client, unpin_named_snapshot, UnpinNamedSnapshotRequest, UnpinNamedSnapshotResponse }
             ,{ hummock_client, report_compaction_tasks, ReportCompactionTasksRequest, ReportCompactionTasksResponse }
             ,{ hummock_client, get_new_table_id, GetNewTableIdRequest, GetNewTableIdResponse }
             ,{ hummock_client, subscribe_compact_tasks, SubscribeCompactTasksRequest, Streaming<SubscribeCompactTasksResponse> }
             ,{ hummock_client, report_vacuum_task, ReportVacuumTaskRequest, ReportVacuumTaskResponse }
             ,{ hummock_client, get_compaction_groups, GetCompactionGroupsRequest, GetCompactionGroupsResponse }
             ,{ hummock_client, trigger_manual_compaction, TriggerManualCompactionRequest, TriggerManualCompactionResponse }
+            ,{ hummock_client, report_compaction_task_progress, ReportCompactionTaskProgressRequest, ReportCompactionTaskProgressResponse }
+            ,{ hummock_client, list_compaction_tasks, ListCompactionTasksRequest, ListCompactionTasksResponse }
+            ,{ hummock_client, cancel_compaction_task, CancelCompactionTaskRequest, CancelCompactionTaskResponse }
+            ,{ hummock_client, get_current_version, GetCurrentVersionRequest, GetCurrentVersionResponse }
             ,{ user_client, create_user, CreateUserRequest, CreateUserResponse }
             ,{ user_client, update_user, UpdateUserRequest, UpdateUserResponse }
             ,{ user_client, drop_user, DropUserRequest, DropUserResponse }
@@ -647,6 +780,7 @@ macro_rules! for_all_meta_rpc {
             ,{ scale_client, pause, PauseRequest, PauseResponse }
             ,{ scale_client, resume, ResumeRequest, ResumeResponse }
             ,{ scale_client, get_cluster_info, GetClusterInfoRequest, GetClusterInfoResponse }
+            ,{ scale_client, rebalance_splits, RebalanceSplitsRequest, RebalanceSplitsResponse }
         }
     };
 }
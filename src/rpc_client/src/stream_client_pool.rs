@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -28,10 +30,17 @@ pub type StreamClient = StreamServiceClient<Channel>;
 
 pub type WorkerId = u32;
 
+/// The number of consecutive failed RPCs after which a client is evicted.
+pub const MAX_CONSECUTIVE_FAILURES: usize = 3;
+
 /// [`StreamClientPool`] maintains stream service clients to known compute nodes.
 pub struct StreamClientPool {
     /// Stores the [`StreamClient`] mapping: `node_id` => client.
     clients: Cache<WorkerId, StreamClient>,
+    /// The number of consecutive failed RPCs of each client.
+    failures: Mutex<HashMap<WorkerId, usize>>,
+    /// The number of clients evicted so far.
+    eviction_count: AtomicU64,
 }
 
 impl Default for StreamClientPool {
@@ -44,6 +53,8 @@ impl StreamClientPool {
     pub fn new() -> Self {
         Self {
             clients: Cache::new(u64::MAX),
+            failures: Default::default(),
+            eviction_count: AtomicU64::new(0),
         }
     }
 
@@ -65,6 +76,54 @@ impl StreamClientPool {
             .await
             .map_err(|e| anyhow!("failed to create compute client: {:?}", e).into())
     }
+
+    /// Reports a successful RPC with the client of `worker_id`.
+    pub fn report_success(&self, worker_id: WorkerId) {
+        self.failures.lock().unwrap().remove(&worker_id);
+    }
+
+    /// Reports a failed RPC with the client of `worker_id`. The client is evicted after
+    /// [`MAX_CONSECUTIVE_FAILURES`] consecutive failures, so that a new connection is established
+    /// on the next [`StreamClientPool::get`].
+    pub async fn report_failure(&self, worker_id: WorkerId) {
+        let evict = {
+            let mut failures = self.failures.lock().unwrap();
+            let count = failures.entry(worker_id).or_default();
+            *count += 1;
+            *count >= MAX_CONSECUTIVE_FAILURES
+        };
+        if evict {
+            tracing::warn!(
+                "evict the stream client of worker {} after {} consecutive failures",
+                worker_id,
+                MAX_CONSECUTIVE_FAILURES
+            );
+            self.invalidate(worker_id).await;
+        }
+    }
+
+    /// Drops the client of `worker_id`, e.g., when the worker is deleted.
+    pub async fn invalidate(&self, worker_id: WorkerId) {
+        self.failures.lock().unwrap().remove(&worker_id);
+        if self.clients.get(&worker_id).is_some() {
+            self.clients.invalidate(&worker_id).await;
+            self.eviction_count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The number of cached clients.
+    pub fn len(&self) -> usize {
+        self.clients.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of clients evicted so far.
+    pub fn eviction_count(&self) -> u64 {
+        self.eviction_count.load(Ordering::Relaxed)
+    }
 }
 
 pub type StreamClientPoolRef = Arc<StreamClientPool>;
@@ -26,7 +26,7 @@ use risingwave_connector::source::{
     Column, ConnectorProperties, ConnectorState, SourceMessage, SplitMetaData, SplitReaderImpl,
 };
 use tokio::sync::mpsc::{Receiver, Sender};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, watch};
 use tokio::task::JoinHandle;
 
 use crate::common::SourceChunkBuilder;
@@ -60,6 +60,8 @@ struct InnerConnectorSourceReader {
 struct InnerConnectorSourceReaderHandle {
     stop_tx: oneshot::Sender<()>,
     join_handle: JoinHandle<()>,
+    /// Closed once the reader is done, after which the children of the split can be read.
+    drained: watch::Receiver<()>,
 }
 
 const CONNECTOR_MESSAGE_BUFFER_SIZE: usize = 512;
@@ -124,11 +126,24 @@ impl InnerConnectorSourceReader {
         })
     }
 
+    /// Reads the split after the readers of its `parents` are done, so that the data of a key is
+    /// read in order across a resharding. `_drained` is dropped once the reader is done.
     async fn run(
         &mut self,
         mut stop: oneshot::Receiver<()>,
         output: mpsc::Sender<Either<Vec<SourceMessage>, RwError>>,
+        parents: Vec<watch::Receiver<()>>,
+        _drained: watch::Sender<()>,
     ) {
+        for mut parent in parents {
+            tokio::select! {
+                biased;
+                _ = stop.borrow_mut() => return,
+                // Nothing is sent, so this only returns when the parent reader is done.
+                _ = async { while parent.changed().await.is_ok() {} } => {}
+            }
+        }
+
         let actor_id = self.context.actor_id.to_string();
         let source_id = self.context.source_id.to_string();
         loop {
@@ -192,7 +207,10 @@ impl StreamSourceReader for ConnectorSourceReader {
                 *split_offset_mapping
                     .entry(msg.split_id.clone())
                     .or_insert_with(|| "".to_string()) = msg.offset.to_string();
-                events.push(self.parser.parse(content.as_ref(), &self.columns)?);
+                events.push(
+                    self.parser
+                        .parse(&msg.split_id, content.as_ref(), &self.columns)?,
+                );
             }
         }
         let mut ops = Vec::with_capacity(events.iter().map(|e| e.ops.len()).sum());
@@ -227,9 +245,7 @@ impl ConnectorSourceReader {
     pub async fn add_split(&mut self, split: ConnectorState) -> Result<()> {
         if let Some(append_splits) = split {
             for split in append_splits {
-                let split_id = split.id();
-
-                let mut reader = InnerConnectorSourceReader::new(
+                let reader = InnerConnectorSourceReader::new(
                     self.config.clone(),
                     Some(vec![split]),
                     self.columns.clone(),
@@ -237,18 +253,8 @@ impl ConnectorSourceReader {
                     self.context.clone(),
                 )
                 .await?;
-                let (stop_tx, stop_rx) = oneshot::channel();
-                let sender = self.message_tx.clone();
-                let join_handle = tokio::spawn(async move { reader.run(stop_rx, sender).await });
-
                 if let Some(handles) = self.handles.as_mut() {
-                    handles.insert(
-                        split_id,
-                        InnerConnectorSourceReaderHandle {
-                            stop_tx,
-                            join_handle,
-                        },
-                    );
+                    spawn_inner_reader(handles, reader, self.message_tx.clone());
                 }
             }
         }
@@ -271,6 +277,36 @@ impl ConnectorSourceReader {
     }
 }
 
+/// Spawns `reader`, which starts reading after the readers of its parent splits in `handles` are
+/// done.
+fn spawn_inner_reader(
+    handles: &mut HashMap<String, InnerConnectorSourceReaderHandle>,
+    mut reader: InnerConnectorSourceReader,
+    output: Sender<Either<Vec<SourceMessage>, RwError>>,
+) {
+    let (split_id, parent_ids) = match &reader.split {
+        Some(s) => (s[0].id(), s[0].parent_ids()),
+        None => ("None".to_string(), vec![]),
+    };
+    let parents = parent_ids
+        .iter()
+        .filter_map(|parent_id| handles.get(parent_id))
+        .map(|handle| handle.drained.clone())
+        .collect();
+    let (stop_tx, stop_rx) = oneshot::channel();
+    let (drained_tx, drained) = watch::channel(());
+    let join_handle =
+        tokio::spawn(async move { reader.run(stop_rx, output, parents, drained_tx).await });
+    handles.insert(
+        split_id,
+        InnerConnectorSourceReaderHandle {
+            stop_tx,
+            join_handle,
+            drained,
+        },
+    );
+}
+
 #[derive(Clone, Debug)]
 pub struct ConnectorSource {
     pub config: ConnectorProperties,
@@ -334,22 +370,12 @@ impl ConnectorSource {
             }))
             .await?;
 
-        for mut reader in readers {
-            let split_id = match &reader.split {
-                Some(s) => s[0].id(),
-                None => "None".to_string(),
-            };
-            let (stop_tx, stop_rx) = oneshot::channel();
-            let sender = tx.clone();
-            let join_handle = tokio::spawn(async move { reader.run(stop_rx, sender).await });
-
-            handles.insert(
-                split_id,
-                InnerConnectorSourceReaderHandle {
-                    stop_tx,
-                    join_handle,
-                },
-            );
+        // The parent splits are spawned first, which have smaller ids.
+        for reader in readers
+            .into_iter()
+            .sorted_by_key(|reader| reader.split.as_ref().map(|s| s[0].id()))
+        {
+            spawn_inner_reader(&mut handles, reader, tx.clone());
         }
 
         Ok(ConnectorSourceReader {
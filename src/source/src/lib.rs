@@ -64,6 +64,7 @@ pub enum SourceFormat {
     Protobuf,
     DebeziumJson,
     Avro,
+    Csv,
 }
 
 #[derive(Debug, EnumAsInner)]
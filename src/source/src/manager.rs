@@ -128,6 +128,7 @@ impl SourceManager for MemSourceManager {
             RowFormatType::Protobuf => SourceFormat::Protobuf,
             RowFormatType::DebeziumJson => SourceFormat::DebeziumJson,
             RowFormatType::Avro => SourceFormat::Avro,
+            RowFormatType::Csv => SourceFormat::Csv,
             RowFormatType::RowUnspecified => unreachable!(),
         };
 
@@ -18,7 +18,7 @@ use std::future::Future;
 use std::path::Path;
 
 use apache_avro::types::Value;
-use apache_avro::{Reader, Schema};
+use apache_avro::{from_avro_datum, Reader, Schema};
 use chrono::{Datelike, NaiveDate};
 use num_traits::FromPrimitive;
 use risingwave_common::array::Op;
@@ -30,17 +30,23 @@ use risingwave_common::types::{
 use risingwave_connector::aws_utils::{default_conn_config, s3_client, AwsConfigV2};
 use url::Url;
 
+use crate::parser::schema_registry::{extract_schema_id, SchemaRegistryClient};
 use crate::{Event, SourceColumnDesc, SourceParser};
 
 const AVRO_SCHEMA_LOCATION_S3_REGION: &str = "region";
+const KAFKA_TOPIC_KEY: &str = "kafka.topic";
 
 pub fn unix_epoch_days() -> i32 {
     NaiveDate::from_ymd(1970, 1, 1).num_days_from_ce()
 }
 
+/// Parses Avro messages. If the schema location is a http or https url, it's treated as a Confluent
+/// Schema Registry, and the messages are expected to be in the Confluent wire format. Otherwise,
+/// the messages are expected to be Avro object container files of the schema at the location.
 #[derive(Debug)]
 pub struct AvroParser {
     schema: Schema,
+    schema_registry: Option<SchemaRegistryClient>,
 }
 
 impl AvroParser {
@@ -49,6 +55,9 @@ impl AvroParser {
             .map_err(|e| InternalError(format!("failed to parse url ({}): {}", schema_location, e)))
             .unwrap();
         let url_schema = url.scheme();
+        if url_schema == "http" || url_schema == "https" {
+            return Self::new_with_schema_registry(schema_location, &props).await;
+        }
         let schema_path = url.path();
         let arvo_schema =
             match url_schema {
@@ -72,11 +81,57 @@ impl AvroParser {
                 )))),
             };
         if let Ok(schema) = arvo_schema {
-            Ok(Self { schema })
+            Ok(Self {
+                schema,
+                schema_registry: None,
+            })
         } else {
             Err(arvo_schema.err().unwrap())
         }
     }
+
+    /// Creates a parser that resolves the schema of each message from the registry at `url`.
+    /// All the value schemas of the topic are fetched here, as parsing is synchronous.
+    async fn new_with_schema_registry(url: &str, props: &HashMap<String, String>) -> Result<Self> {
+        let topic = props.get(KAFKA_TOPIC_KEY).ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "Must specify '{}' in WITH clause",
+                KAFKA_TOPIC_KEY
+            )))
+        })?;
+        let mut schema_registry = SchemaRegistryClient::new(url);
+        let schema = schema_registry
+            .fetch_subject(&format!("{}-value", topic))
+            .await?;
+        Ok(Self {
+            schema: schema.as_ref().clone(),
+            schema_registry: Some(schema_registry),
+        })
+    }
+
+    /// Parses a single message in the Confluent wire format, which is decoded with the schema it
+    /// was written with.
+    fn parse_with_schema_registry(
+        &self,
+        schema_registry: &SchemaRegistryClient,
+        payload: &[u8],
+        columns: &[SourceColumnDesc],
+    ) -> Result<Event> {
+        let (schema_id, mut datum) = extract_schema_id(payload)?;
+        let writer_schema = schema_registry.get_schema(schema_id)?;
+        let value = from_avro_datum(writer_schema, &mut datum, None)
+            .map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+        if let Value::Record(fields) = value {
+            Ok(Event {
+                ops: vec![Op::Insert],
+                rows: vec![avro_record_to_row(&fields, columns)?],
+            })
+        } else {
+            Err(RwError::from(ProtocolError(
+                "avro parse error.expect a record".to_string(),
+            )))
+        }
+    }
 }
 
 macro_rules! from_avro_datetime {
@@ -178,26 +233,46 @@ pub(crate) fn from_avro_value(column: &SourceColumnDesc, field_value: Value) ->
     }
 }
 
+/// Maps the fields of an Avro record to a row by column name. Columns missing in the record, e.g.
+/// a nullable field added by a newer schema version, are filled with null.
+fn avro_record_to_row(
+    fields: &[(String, Value)],
+    columns: &[SourceColumnDesc],
+) -> Result<Vec<Datum>> {
+    columns
+        .iter()
+        .map(|column| {
+            if column.skip_parse {
+                return Ok(None);
+            }
+            let value = match fields.iter().find(|(name, _)| column.name.eq(name)) {
+                Some((_, value)) => value.clone(),
+                None => return Ok(None),
+            };
+            // Nullable fields are encoded as unions with null.
+            let value = match value {
+                Value::Union(_, value) => *value,
+                value => value,
+            };
+            match value {
+                Value::Null => Ok(None),
+                value => from_avro_value(column, value).map(Some),
+            }
+        })
+        .collect()
+}
+
 impl SourceParser for AvroParser {
     fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
+        if let Some(schema_registry) = &self.schema_registry {
+            return self.parse_with_schema_registry(schema_registry, payload, columns);
+        }
         let reader_rs = Reader::with_schema(&self.schema, payload);
         if let Ok(reader) = reader_rs {
             let mut rows = Vec::new();
             for record in reader {
                 if let Ok(Value::Record(fields)) = record {
-                    let vals = columns
-                        .iter()
-                        .map(|column| {
-                            if column.skip_parse {
-                                None
-                            } else {
-                                let tuple =
-                                    fields.iter().find(|val| column.name.eq(&val.0)).unwrap();
-                                from_avro_value(column, tuple.clone().1).ok()
-                            }
-                        })
-                        .collect::<Vec<Datum>>();
-                    rows.push(vals);
+                    rows.push(avro_record_to_row(&fields, columns)?);
                 } else {
                     return Err(RwError::from(ProtocolError(
                         record.err().unwrap().to_string(),
@@ -343,9 +418,10 @@ mod test {
     use std::collections::HashMap;
     use std::env;
     use std::ops::Sub;
+    use std::sync::Arc;
 
     use apache_avro::types::{Record, Value};
-    use apache_avro::{Codec, Schema, Writer};
+    use apache_avro::{to_avro_datum, Codec, Schema, Writer};
     use chrono::NaiveDate;
     use risingwave_common::catalog::ColumnId;
     use risingwave_common::error;
@@ -356,6 +432,7 @@ mod test {
     use crate::parser::avro_parser::{
         load_schema_async, read_schema_from_local, read_schema_from_s3, unix_epoch_days, AvroParser,
     };
+    use crate::parser::schema_registry::SchemaRegistryClient;
     use crate::{SourceColumnDesc, SourceParser};
 
     fn test_data_path(file_name: &str) -> String {
@@ -472,6 +549,47 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_avro_parser_with_schema_registry() {
+        let schema = new_avro_parser_from_local("simple-schema.avsc")
+            .await
+            .unwrap()
+            .schema;
+        let record = build_avro_data(&schema);
+        let mut payload = vec![0, 0, 0, 0, 1];
+        payload.extend(to_avro_datum(&schema, record).unwrap());
+
+        let schema_registry = SchemaRegistryClient::with_schemas(
+            "http://localhost:8081",
+            HashMap::from([(1, Arc::new(schema.clone()))]),
+        );
+        let avro_parser = AvroParser {
+            schema,
+            schema_registry: Some(schema_registry),
+        };
+        let mut columns = build_rw_columns();
+        // A column that the writer schema doesn't have yet is parsed as null.
+        columns.push(SourceColumnDesc {
+            name: "nickname".to_string(),
+            data_type: DataType::Varchar,
+            column_id: ColumnId::from(8),
+            skip_parse: false,
+            fields: vec![],
+        });
+        let event = avro_parser.parse(&payload, &columns).unwrap();
+        let row = event.rows.first().unwrap();
+        assert_eq!(row.len(), columns.len());
+        assert_eq!(row[0], Some(ScalarImpl::Int32(32)));
+        assert_eq!(row[2], Some(ScalarImpl::Utf8("str_value".to_string())));
+        assert_eq!(row[8], None);
+
+        // Messages without the Confluent framing are rejected.
+        assert!(avro_parser.parse(&payload[5..], &columns).is_err());
+        // Messages written with unknown schemas are rejected.
+        payload[4] = 2;
+        assert!(avro_parser.parse(&payload, &columns).is_err());
+    }
+
     fn build_rw_columns() -> Vec<SourceColumnDesc> {
         vec![
             SourceColumnDesc {
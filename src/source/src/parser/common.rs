@@ -56,9 +56,15 @@ fn do_parse_json_value(column: &ColumnDesc, v: &Value) -> Result<ScalarImpl> {
         DataType::Int64 => ensure_int!(v, i64).into(),
         DataType::Float32 => ScalarImpl::Float32((ensure_float!(v, f32) as f32).into()),
         DataType::Float64 => ScalarImpl::Float64((ensure_float!(v, f64) as f64).into()),
-        DataType::Decimal => Decimal::from_f64(ensure_float!(v, Decimal))
-            .ok_or_else(|| anyhow!("expect decimal"))?
-            .into(),
+        // Decimals may be encoded as strings to keep the precision.
+        DataType::Decimal => match v {
+            Value::String(s) => s
+                .parse::<Decimal>()
+                .map_err(|e| anyhow!("expect decimal: {}", e))?,
+            v => Decimal::from_f64(ensure_float!(v, Decimal))
+                .ok_or_else(|| anyhow!("expect decimal"))?,
+        }
+        .into(),
         DataType::Varchar => ensure_str!(v, "varchar").to_string().into(),
         DataType::Date => str_to_date(ensure_str!(v, "date"))?.into(),
         DataType::Time => str_to_time(ensure_str!(v, "time"))?.into(),
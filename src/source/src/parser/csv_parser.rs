@@ -0,0 +1,268 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use parking_lot::RwLock;
+use risingwave_common::array::Op;
+use risingwave_common::error::ErrorCode::{InvalidConfigValue, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use risingwave_common::types::{DataType, Datum, Decimal, ScalarImpl};
+use risingwave_expr::vector_op::cast::{
+    str_parse, str_to_bool, str_to_date, str_to_time, str_to_timestamp,
+};
+
+use crate::{Event, SourceColumnDesc, SourceParser};
+
+const CSV_DELIMITER_KEY: &str = "csv.delimiter";
+const CSV_QUOTE_KEY: &str = "csv.quote";
+const CSV_HAS_HEADER_KEY: &str = "csv.has_header";
+
+/// Parser for CSV format.
+///
+/// Fields are mapped to the columns by position, or by name if the data of each split starts
+/// with a header row. An empty field is parsed as null.
+#[derive(Debug)]
+pub struct CsvParser {
+    delimiter: u8,
+    quote: u8,
+    has_header: bool,
+    /// The column names in the header row of each split, set once the header row of the split is
+    /// read.
+    headers: RwLock<HashMap<String, Vec<String>>>,
+}
+
+impl CsvParser {
+    pub fn new(properties: &HashMap<String, String>) -> Result<Self> {
+        let delimiter = parse_char_property(properties, CSV_DELIMITER_KEY, b',')?;
+        let quote = parse_char_property(properties, CSV_QUOTE_KEY, b'"')?;
+        let has_header = match properties.get(CSV_HAS_HEADER_KEY) {
+            None => false,
+            Some(value) => value.parse().map_err(|_| {
+                RwError::from(InvalidConfigValue {
+                    config_entry: CSV_HAS_HEADER_KEY.to_string(),
+                    config_value: value.to_string(),
+                })
+            })?,
+        };
+        Ok(Self {
+            delimiter,
+            quote,
+            has_header,
+            headers: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Parses the `payload` read from the split `split_id`. The header row is expected at the
+    /// beginning of each split.
+    pub fn parse_split(
+        &self,
+        split_id: &str,
+        payload: &[u8],
+        columns: &[SourceColumnDesc],
+    ) -> Result<Event> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(payload);
+
+        let mut rows = vec![];
+        for record in reader.records() {
+            let record = record.map_err(|e| RwError::from(ProtocolError(e.to_string())))?;
+            if !self.has_header {
+                rows.push(parse_record(&record, None, columns)?);
+                continue;
+            }
+            let headers = self.headers.read();
+            match headers.get(split_id) {
+                Some(headers) => {
+                    rows.push(parse_record(&record, Some(headers.as_slice()), columns)?)
+                }
+                None => {
+                    drop(headers);
+                    self.headers.write().insert(
+                        split_id.to_string(),
+                        record.iter().map(str::to_string).collect(),
+                    );
+                }
+            }
+        }
+        Ok(Event {
+            ops: vec![Op::Insert; rows.len()],
+            rows,
+        })
+    }
+}
+
+impl SourceParser for CsvParser {
+    fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
+        self.parse_split("", payload, columns)
+    }
+}
+
+fn parse_record(
+    record: &csv::StringRecord,
+    headers: Option<&[String]>,
+    columns: &[SourceColumnDesc],
+) -> Result<Vec<Datum>> {
+    let mut position = 0;
+    columns
+        .iter()
+        .map(|column| {
+            if column.skip_parse {
+                return Ok(None);
+            }
+            let field = match headers {
+                Some(headers) => headers
+                    .iter()
+                    .position(|name| name == &column.name)
+                    .and_then(|idx| record.get(idx)),
+                None => record.get(position),
+            };
+            position += 1;
+            match field {
+                None | Some("") => Ok(None),
+                Some(field) => parse_field(&column.data_type, field)
+                    .map(Some)
+                    .map_err(|e| {
+                        RwError::from(ProtocolError(format!(
+                            "failed to parse csv field {:?} of column {}: {}",
+                            field, column.name, e
+                        )))
+                    }),
+            }
+        })
+        .collect()
+}
+
+fn parse_char_property(properties: &HashMap<String, String>, key: &str, default: u8) -> Result<u8> {
+    match properties.get(key) {
+        None => Ok(default),
+        Some(value) if value.len() == 1 => Ok(value.as_bytes()[0]),
+        Some(value) => Err(RwError::from(InvalidConfigValue {
+            config_entry: key.to_string(),
+            config_value: value.to_string(),
+        })),
+    }
+}
+
+fn parse_field(data_type: &DataType, field: &str) -> anyhow::Result<ScalarImpl> {
+    let v = match data_type {
+        DataType::Boolean => str_to_bool(field)?.into(),
+        DataType::Int16 => ScalarImpl::Int16(str_parse(field)?),
+        DataType::Int32 => ScalarImpl::Int32(str_parse(field)?),
+        DataType::Int64 => ScalarImpl::Int64(str_parse(field)?),
+        DataType::Float32 => ScalarImpl::Float32(str_parse(field)?),
+        DataType::Float64 => ScalarImpl::Float64(str_parse(field)?),
+        DataType::Decimal => ScalarImpl::Decimal(str_parse::<Decimal>(field)?),
+        DataType::Varchar => field.to_string().into(),
+        DataType::Date => str_to_date(field)?.into(),
+        DataType::Time => str_to_time(field)?.into(),
+        DataType::Timestamp => str_to_timestamp(field)?.into(),
+        _ => return Err(anyhow!("unsupported type {:?} for csv parser", data_type)),
+    };
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use maplit::hashmap;
+    use risingwave_common::catalog::ColumnId;
+
+    use super::*;
+
+    fn column(name: &str, data_type: DataType, id: i32) -> SourceColumnDesc {
+        SourceColumnDesc {
+            name: name.to_string(),
+            data_type,
+            column_id: ColumnId::from(id),
+            skip_parse: false,
+            fields: vec![],
+        }
+    }
+
+    fn columns() -> Vec<SourceColumnDesc> {
+        vec![
+            column("id", DataType::Int32, 0),
+            column("name", DataType::Varchar, 1),
+            column("score", DataType::Float64, 2),
+        ]
+    }
+
+    #[test]
+    fn test_csv_parser() {
+        let parser = CsvParser::new(&HashMap::new()).unwrap();
+        let columns = columns();
+
+        let event = parser.parse(b"1,\"a,b\",1.5\n2,,", &columns).unwrap();
+        assert_eq!(event.ops, vec![Op::Insert, Op::Insert]);
+        assert_eq!(
+            event.rows,
+            vec![
+                vec![
+                    Some(ScalarImpl::Int32(1)),
+                    Some(ScalarImpl::Utf8("a,b".to_string())),
+                    Some(ScalarImpl::Float64(1.5.into())),
+                ],
+                vec![Some(ScalarImpl::Int32(2)), None, None],
+            ]
+        );
+
+        assert!(parser.parse(b"x,a,1.5", &columns).is_err());
+    }
+
+    #[test]
+    fn test_csv_parser_with_header() {
+        let parser = CsvParser::new(&hashmap! {
+            CSV_DELIMITER_KEY.to_string() => "|".to_string(),
+            CSV_QUOTE_KEY.to_string() => "'".to_string(),
+            CSV_HAS_HEADER_KEY.to_string() => "true".to_string(),
+        })
+        .unwrap();
+        let columns = columns();
+
+        let event = parser.parse_split("0", b"score|id|name", &columns).unwrap();
+        assert!(event.rows.is_empty());
+        let event = parser.parse_split("0", b"2.5|1|'a|b'", &columns).unwrap();
+        assert_eq!(
+            event.rows,
+            vec![vec![
+                Some(ScalarImpl::Int32(1)),
+                Some(ScalarImpl::Utf8("a|b".to_string())),
+                Some(ScalarImpl::Float64(2.5.into())),
+            ]]
+        );
+
+        // Each split starts with its own header row.
+        let event = parser
+            .parse_split("1", b"name|id\n'c'|2", &columns)
+            .unwrap();
+        assert_eq!(
+            event.rows,
+            vec![vec![
+                Some(ScalarImpl::Int32(2)),
+                Some(ScalarImpl::Utf8("c".to_string())),
+                None,
+            ]]
+        );
+
+        assert!(CsvParser::new(&hashmap! {
+            CSV_DELIMITER_KEY.to_string() => "||".to_string(),
+        })
+        .is_err());
+    }
+}
@@ -16,11 +16,12 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+pub use csv_parser::*;
 pub use debezium::*;
 pub use json_parser::*;
 pub use protobuf_parser::*;
 use risingwave_common::array::Op;
-use risingwave_common::error::ErrorCode::ProtocolError;
+use risingwave_common::error::ErrorCode::{InvalidConfigValue, ProtocolError};
 use risingwave_common::error::{Result, RwError};
 use risingwave_common::types::Datum;
 
@@ -29,9 +30,11 @@ use crate::{SourceColumnDesc, SourceFormat};
 
 mod avro_parser;
 mod common;
+mod csv_parser;
 mod debezium;
 mod json_parser;
 mod protobuf_parser;
+mod schema_registry;
 
 #[derive(Debug, Default)]
 pub struct Event {
@@ -48,21 +51,50 @@ pub trait SourceParser: Send + Sync + Debug + 'static {
     fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event>;
 }
 
+/// The formats to try in order after the row format of the source fails to parse a message,
+/// separated by commas, e.g. `'avro,json'`. The formats share the other options of the source,
+/// including the schema location.
+const ROW_FORMAT_FALLBACK_KEY: &str = "row.format.fallback";
+
 #[derive(Debug)]
 pub enum SourceParserImpl {
     Json(JSONParser),
     Protobuf(ProtobufParser),
     DebeziumJson(DebeziumJsonParser),
     Avro(AvroParser),
+    Csv(CsvParser),
+    /// Tries the parsers in order until one succeeds. Only used when fallback formats are
+    /// declared, so that a source with a single format doesn't pay for it.
+    Fallback(Vec<(SourceFormat, SourceParserImpl)>),
 }
 
 impl SourceParserImpl {
-    pub fn parse(&self, payload: &[u8], columns: &[SourceColumnDesc]) -> Result<Event> {
+    /// Parses the `payload` read from the split `split_id`.
+    pub fn parse(
+        &self,
+        split_id: &str,
+        payload: &[u8],
+        columns: &[SourceColumnDesc],
+    ) -> Result<Event> {
         match self {
             Self::Json(parser) => parser.parse(payload, columns),
             Self::Protobuf(parser) => parser.parse(payload, columns),
             Self::DebeziumJson(parser) => parser.parse(payload, columns),
             Self::Avro(avro_parser) => avro_parser.parse(payload, columns),
+            Self::Csv(parser) => parser.parse_split(split_id, payload, columns),
+            Self::Fallback(parsers) => {
+                let mut errors = Vec::with_capacity(parsers.len());
+                for (format, parser) in parsers {
+                    match parser.parse(split_id, payload, columns) {
+                        Ok(event) => return Ok(event),
+                        Err(e) => errors.push(format!("{:?}: {}", format, e)),
+                    }
+                }
+                Err(RwError::from(ProtocolError(format!(
+                    "failed to parse with any of the formats: {}",
+                    errors.join("; ")
+                ))))
+            }
         }
     }
 
@@ -71,6 +103,26 @@ impl SourceParserImpl {
         properties: &HashMap<String, String>,
         schema_location: &str,
     ) -> Result<Arc<Self>> {
+        let parser = Self::create_for_format(format, properties, schema_location).await?;
+        let fallback_formats = match properties.get(ROW_FORMAT_FALLBACK_KEY) {
+            Some(formats) => parse_formats(formats)?,
+            None => return Ok(Arc::new(parser)),
+        };
+
+        let mut parsers = Vec::with_capacity(fallback_formats.len() + 1);
+        parsers.push((format.clone(), parser));
+        for format in fallback_formats {
+            let parser = Self::create_for_format(&format, properties, schema_location).await?;
+            parsers.push((format, parser));
+        }
+        Ok(Arc::new(SourceParserImpl::Fallback(parsers)))
+    }
+
+    async fn create_for_format(
+        format: &SourceFormat,
+        properties: &HashMap<String, String>,
+        schema_location: &str,
+    ) -> Result<Self> {
         const PROTOBUF_MESSAGE_KEY: &str = "proto.message";
         let parser = match format {
             SourceFormat::Json => SourceParserImpl::Json(JSONParser {}),
@@ -87,12 +139,93 @@ impl SourceParserImpl {
             SourceFormat::Avro => {
                 SourceParserImpl::Avro(AvroParser::new(schema_location, properties.clone()).await?)
             }
+            SourceFormat::Csv => SourceParserImpl::Csv(CsvParser::new(properties)?),
             _ => {
                 return Err(RwError::from(ProtocolError(
                     "format not support".to_string(),
                 )));
             }
         };
-        Ok(Arc::new(parser))
+        Ok(parser)
+    }
+}
+
+/// Parses the comma-separated formats of [`ROW_FORMAT_FALLBACK_KEY`].
+fn parse_formats(formats: &str) -> Result<Vec<SourceFormat>> {
+    formats
+        .split(',')
+        .map(|format| match format.trim().to_lowercase().as_str() {
+            "json" => Ok(SourceFormat::Json),
+            "protobuf" => Ok(SourceFormat::Protobuf),
+            "debezium_json" => Ok(SourceFormat::DebeziumJson),
+            "avro" => Ok(SourceFormat::Avro),
+            "csv" => Ok(SourceFormat::Csv),
+            _ => Err(RwError::from(InvalidConfigValue {
+                config_entry: ROW_FORMAT_FALLBACK_KEY.to_string(),
+                config_value: formats.to_string(),
+            })),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use risingwave_common::catalog::ColumnId;
+    use risingwave_common::types::{DataType, ScalarImpl};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fallback_parser() {
+        let columns = vec![
+            SourceColumnDesc {
+                name: "id".to_string(),
+                data_type: DataType::Int32,
+                column_id: ColumnId::from(0),
+                skip_parse: false,
+                fields: vec![],
+            },
+            SourceColumnDesc {
+                name: "name".to_string(),
+                data_type: DataType::Varchar,
+                column_id: ColumnId::from(1),
+                skip_parse: false,
+                fields: vec![],
+            },
+        ];
+        let expected = vec![vec![
+            Some(ScalarImpl::Int32(1)),
+            Some(ScalarImpl::Utf8("a".to_string())),
+        ]];
+
+        let properties = HashMap::from([(ROW_FORMAT_FALLBACK_KEY.to_string(), "csv".to_string())]);
+        let parser = SourceParserImpl::create(&SourceFormat::Json, &properties, "")
+            .await
+            .unwrap();
+        assert_matches!(parser.as_ref(), SourceParserImpl::Fallback(_));
+
+        let event = parser
+            .parse("0", br#"{"id": 1, "name": "a"}"#, &columns)
+            .unwrap();
+        assert_eq!(event.rows, expected);
+        let event = parser.parse("0", b"1,a", &columns).unwrap();
+        assert_eq!(event.rows, expected);
+        assert!(parser.parse("0", b"{x", &columns).is_err());
+
+        // Without fallback formats, the parser of the row format is used directly.
+        let parser = SourceParserImpl::create(&SourceFormat::Json, &HashMap::new(), "")
+            .await
+            .unwrap();
+        assert_matches!(parser.as_ref(), SourceParserImpl::Json(_));
+        assert!(parser.parse("0", b"1,a", &columns).is_err());
+
+        let properties =
+            HashMap::from([(ROW_FORMAT_FALLBACK_KEY.to_string(), "csv,xml".to_string())]);
+        assert!(
+            SourceParserImpl::create(&SourceFormat::Json, &properties, "")
+                .await
+                .is_err()
+        );
     }
 }
@@ -0,0 +1,178 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apache_avro::Schema;
+use hyper::body::Buf;
+use hyper::{Client, Uri};
+use hyper_tls::HttpsConnector;
+use risingwave_common::error::ErrorCode::{InternalError, ProtocolError};
+use risingwave_common::error::{Result, RwError};
+use serde_derive::Deserialize;
+
+/// The first byte of every message in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// Splits a message in the Confluent wire format into the schema id and the Avro binary datum.
+/// The datum is prefixed with a magic byte `0` and the 4-byte big-endian schema id.
+pub(crate) fn extract_schema_id(payload: &[u8]) -> Result<(i32, &[u8])> {
+    if payload.len() < 5 || payload[0] != MAGIC_BYTE {
+        return Err(RwError::from(ProtocolError(
+            "message is not in the Confluent Avro wire format".to_string(),
+        )));
+    }
+    let schema_id = i32::from_be_bytes(payload[1..5].try_into().unwrap());
+    Ok((schema_id, &payload[5..]))
+}
+
+#[derive(Deserialize)]
+struct GetSubjectVersionResponse {
+    id: i32,
+    schema: String,
+}
+
+/// A client of the Confluent Schema Registry REST API over http or https.
+///
+/// All the schemas registered under a subject are fetched when the source is created, so that
+/// parsing never waits on the registry.
+#[derive(Debug)]
+pub(crate) struct SchemaRegistryClient {
+    url: String,
+    /// Schemas fetched so far, keyed by schema id. A registered schema never changes, so the
+    /// fetched schemas are never invalidated.
+    schemas: HashMap<i32, Arc<Schema>>,
+}
+
+impl SchemaRegistryClient {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            schemas: HashMap::new(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_schemas(url: &str, schemas: HashMap<i32, Arc<Schema>>) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            schemas,
+        }
+    }
+
+    /// Fetches all the schema versions registered under `subject`, and returns the latest one.
+    pub async fn fetch_subject(&mut self, subject: &str) -> Result<Arc<Schema>> {
+        let versions: Vec<i32> = self
+            .http_get(&format!("subjects/{}/versions", subject))
+            .await?;
+        let latest_version = versions.iter().max().copied().ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "no schema is registered under subject {}",
+                subject
+            )))
+        })?;
+
+        let mut latest = None;
+        for version in versions {
+            let resp: GetSubjectVersionResponse = self
+                .http_get(&format!("subjects/{}/versions/{}", subject, version))
+                .await?;
+            let schema = Arc::new(parse_schema(&resp.schema)?);
+            self.schemas.insert(resp.id, schema.clone());
+            if version == latest_version {
+                latest = Some(schema);
+            }
+        }
+        Ok(latest.unwrap())
+    }
+
+    /// Returns the schema with the given id. Schemas registered after the source is created are
+    /// unknown until the source is rebuilt, e.g. by recovery.
+    pub fn get_schema(&self, schema_id: i32) -> Result<&Arc<Schema>> {
+        self.schemas.get(&schema_id).ok_or_else(|| {
+            RwError::from(ProtocolError(format!(
+                "schema {} was not registered in {} when the source was created",
+                schema_id, self.url
+            )))
+        })
+    }
+
+    async fn http_get<T>(&self, path: &str) -> Result<T>
+    where
+        T: for<'a> serde::Deserialize<'a>,
+    {
+        let url = format!("{}/{}", self.url, path);
+        let uri: Uri = url.parse().map_err(|e| {
+            RwError::from(InternalError(format!(
+                "invalid schema registry url {}: {}",
+                url, e
+            )))
+        })?;
+        let res = Client::builder()
+            .build::<_, hyper::Body>(HttpsConnector::new())
+            .get(uri)
+            .await
+            .map_err(|e| {
+                RwError::from(InternalError(format!(
+                    "failed to request schema registry {}: {}",
+                    url, e
+                )))
+            })?;
+        if !res.status().is_success() {
+            return Err(RwError::from(InternalError(format!(
+                "schema registry {} responded with {}",
+                url,
+                res.status()
+            ))));
+        }
+        let body = hyper::body::aggregate(res).await.map_err(|e| {
+            RwError::from(InternalError(format!(
+                "failed to read schema registry response: {}",
+                e
+            )))
+        })?;
+        serde_json::from_reader(body.reader()).map_err(|e| {
+            RwError::from(InternalError(format!(
+                "invalid schema registry response: {}",
+                e
+            )))
+        })
+    }
+}
+
+fn parse_schema(content: &str) -> Result<Schema> {
+    Schema::parse_str(content).map_err(|e| {
+        RwError::from(InternalError(format!(
+            "Avro schema parse error {}",
+            anyhow::Error::from(e)
+        )))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_schema_id() {
+        let payload = [0, 0, 0, 1, 2, 42];
+        let (schema_id, datum) = extract_schema_id(&payload).unwrap();
+        assert_eq!(schema_id, 258);
+        assert_eq!(datum, &[42]);
+
+        assert!(extract_schema_id(&[1, 0, 0, 0, 1]).is_err());
+        assert!(extract_schema_id(&[0, 0, 0]).is_err());
+    }
+}
@@ -133,6 +133,22 @@ impl fmt::Display for AlterTableOperation {
     }
 }
 
+/// An `ALTER VIEW` (`Statement::AlterView`) operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AlterViewOperation {
+    /// `RENAME TO <view_name>`
+    RenameView { view_name: Ident },
+}
+
+impl fmt::Display for AlterViewOperation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlterViewOperation::RenameView { view_name } => write!(f, "RENAME TO {}", view_name),
+        }
+    }
+}
+
 /// An `ALTER COLUMN` (`Statement::AlterTable`) operation
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
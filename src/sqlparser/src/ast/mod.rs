@@ -32,14 +32,14 @@ use serde::{Deserialize, Serialize};
 
 pub use self::data_type::{DataType, StructField};
 pub use self::ddl::{
-    AlterColumnOperation, AlterTableOperation, ColumnDef, ColumnOption, ColumnOptionDef,
-    ReferentialAction, TableConstraint,
+    AlterColumnOperation, AlterTableOperation, AlterViewOperation, ColumnDef, ColumnOption,
+    ColumnOptionDef, ReferentialAction, TableConstraint,
 };
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, Offset, OffsetRows, OrderByExpr,
-    Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, Top,
-    Values, With,
+    AsOf, Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, Offset, OffsetRows,
+    OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor,
+    TableWithJoins, Top, Values, With,
 };
 pub use self::statement::*;
 pub use self::value::{DateTimeField, TrimWhereField, Value};
@@ -791,6 +791,7 @@ pub enum Statement {
     CreateView {
         or_replace: bool,
         materialized: bool,
+        if_not_exists: bool,
         /// View name
         name: ObjectName,
         columns: Vec<Ident>,
@@ -834,6 +835,13 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// ALTER [MATERIALIZED] VIEW
+    AlterView {
+        materialized: bool,
+        /// View name
+        name: ObjectName,
+        operation: AlterViewOperation,
+    },
     /// DESCRIBE TABLE OR SOURCE
     Describe {
         /// Table or Source name
@@ -1083,13 +1091,15 @@ impl fmt::Display for Statement {
                 columns,
                 query,
                 materialized,
+                if_not_exists,
                 with_options,
             } => {
                 write!(
                     f,
-                    "CREATE {or_replace}{materialized}VIEW {name}",
+                    "CREATE {or_replace}{materialized}VIEW {if_not_exists}{name}",
                     or_replace = if *or_replace { "OR REPLACE " } else { "" },
                     materialized = if *materialized { "MATERIALIZED " } else { "" },
+                    if_not_exists = if *if_not_exists { "IF NOT EXISTS " } else { "" },
                     name = name
                 )?;
                 if !with_options.is_empty() {
@@ -1180,6 +1190,17 @@ impl fmt::Display for Statement {
             Statement::AlterTable { name, operation } => {
                 write!(f, "ALTER TABLE {} {}", name, operation)
             }
+            Statement::AlterView {
+                materialized,
+                name,
+                operation,
+            } => write!(
+                f,
+                "ALTER {materialized}VIEW {name} {operation}",
+                materialized = if *materialized { "MATERIALIZED " } else { "" },
+                name = name,
+                operation = operation
+            ),
             Statement::Drop(stmt) => write!(f, "DROP {}", stmt),
             Statement::SetVariable {
                 local,
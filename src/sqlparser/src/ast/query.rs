@@ -307,6 +307,23 @@ impl fmt::Display for TableWithJoins {
     }
 }
 
+/// The point in history to read a table at.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AsOf {
+    Epoch(u64),
+    Timestamp(String),
+}
+
+impl fmt::Display for AsOf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsOf::Epoch(epoch) => write!(f, "AS OF {}", epoch),
+            AsOf::Timestamp(ts) => write!(f, "AS OF '{}'", value::escape_single_quote_string(ts)),
+        }
+    }
+}
+
 /// A table name or a parenthesized subquery with an optional alias
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -318,6 +335,8 @@ pub enum TableFactor {
         /// and MSSQL. Note that deprecated MSSQL `FROM foo (NOLOCK)` syntax
         /// will also be parsed as `args`.
         args: Vec<FunctionArg>,
+        /// `AS OF <epoch | timestamp>` to read a historical snapshot of the table.
+        as_of: Option<AsOf>,
     },
     Derived {
         lateral: bool,
@@ -341,11 +360,19 @@ pub enum TableFactor {
 impl fmt::Display for TableFactor {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            TableFactor::Table { name, alias, args } => {
+            TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            } => {
                 write!(f, "{}", name)?;
                 if !args.is_empty() {
                     write!(f, "({})", display_comma_separated(args))?;
                 }
+                if let Some(as_of) = as_of {
+                    write!(f, " {}", as_of)?;
+                }
                 if let Some(alias) = alias {
                     write!(f, " AS {}", alias)?;
                 }
@@ -85,7 +85,9 @@ pub struct CreateSourceStatement {
 pub enum SourceSchema {
     Protobuf(ProtobufSchema),
     // Keyword::PROTOBUF ProtobufSchema
-    Json, // Keyword::JSON
+    Json,             // Keyword::JSON
+    Avro(AvroSchema), // Keyword::AVRO AvroSchema
+    Csv,              // Keyword::CSV
 }
 
 impl ParseTo for SourceSchema {
@@ -95,9 +97,14 @@ impl ParseTo for SourceSchema {
         } else if p.parse_keywords(&[Keyword::PROTOBUF]) {
             impl_parse_to!(protobuf_schema: ProtobufSchema, p);
             SourceSchema::Protobuf(protobuf_schema)
+        } else if p.parse_keywords(&[Keyword::AVRO]) {
+            impl_parse_to!(avro_schema: AvroSchema, p);
+            SourceSchema::Avro(avro_schema)
+        } else if p.parse_keywords(&[Keyword::CSV]) {
+            SourceSchema::Csv
         } else {
             return Err(ParserError::ParserError(
-                "expected JSON | PROTOBUF after ROW FORMAT".to_string(),
+                "expected JSON | PROTOBUF | AVRO | CSV after ROW FORMAT".to_string(),
             ));
         };
         Ok(schema)
@@ -109,6 +116,8 @@ impl fmt::Display for SourceSchema {
         match self {
             SourceSchema::Protobuf(protobuf_schema) => write!(f, "PROTOBUF {}", protobuf_schema),
             SourceSchema::Json => write!(f, "JSON"),
+            SourceSchema::Avro(avro_schema) => write!(f, "AVRO {}", avro_schema),
+            SourceSchema::Csv => write!(f, "CSV"),
         }
     }
 }
@@ -150,6 +159,35 @@ impl fmt::Display for ProtobufSchema {
     }
 }
 
+// sql_grammar!(AvroSchema {
+//     [Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION],
+//     row_schema_location: AstString,
+// });
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AvroSchema {
+    pub row_schema_location: AstString,
+}
+
+impl ParseTo for AvroSchema {
+    fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
+        impl_parse_to!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], p);
+        impl_parse_to!(row_schema_location: AstString, p);
+        Ok(Self {
+            row_schema_location,
+        })
+    }
+}
+
+impl fmt::Display for AvroSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v: Vec<String> = vec![];
+        impl_fmt_display!([Keyword::ROW, Keyword::SCHEMA, Keyword::LOCATION], v);
+        impl_fmt_display!(row_schema_location, v, self);
+        v.iter().join(" ").fmt(f)
+    }
+}
+
 impl ParseTo for CreateSourceStatement {
     fn parse_to(p: &mut Parser) -> Result<Self, ParserError> {
         impl_parse_to!(if_not_exists => [Keyword::IF, Keyword::NOT, Keyword::EXISTS], p);
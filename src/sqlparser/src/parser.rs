@@ -1029,10 +1029,34 @@ impl Parser {
         };
 
         if let Some(op) = regular_binary_operator {
+            let mut right = self.parse_subexpr(precedence)?;
+            // Same as PostgreSQL, `x LIKE pattern ESCAPE escape` is parsed as
+            // `x LIKE like_escape(pattern, escape)`.
+            if matches!(
+                op,
+                BinaryOperator::Like
+                    | BinaryOperator::NotLike
+                    | BinaryOperator::ILike
+                    | BinaryOperator::NotILike
+            ) && self.parse_keyword(Keyword::ESCAPE)
+            {
+                let escape = self.parse_subexpr(precedence)?;
+                right = Expr::Function(Function {
+                    name: ObjectName(vec![Ident::new("like_escape")]),
+                    args: vec![
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(right)),
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(escape)),
+                    ],
+                    over: None,
+                    distinct: false,
+                    order_by: vec![],
+                    filter: None,
+                });
+            }
             Ok(Expr::BinaryOp {
                 left: Box::new(expr),
                 op,
-                right: Box::new(self.parse_subexpr(precedence)?),
+                right: Box::new(right),
             })
         } else if let Token::Word(w) = &tok {
             match w.keyword {
@@ -1465,6 +1489,7 @@ impl Parser {
     ) -> Result<Statement, ParserError> {
         // Many dialects support `OR ALTER` right after `CREATE`, but we don't (yet).
         // ANSI SQL and Postgres support RECURSIVE here, but we don't support it either.
+        let if_not_exists = self.parse_keywords(&[Keyword::IF, Keyword::NOT, Keyword::EXISTS]);
         let name = self.parse_object_name()?;
         let columns = self.parse_parenthesized_column_list(Optional)?;
         let with_options = self.parse_options(Keyword::WITH)?;
@@ -1477,6 +1502,7 @@ impl Parser {
             query,
             materialized,
             or_replace,
+            if_not_exists,
             with_options,
         })
     }
@@ -1800,13 +1826,31 @@ impl Parser {
     pub fn parse_alter(&mut self) -> Result<Statement, ParserError> {
         if self.parse_keyword(Keyword::TABLE) {
             self.parse_alter_table()
+        } else if self.parse_keyword(Keyword::VIEW) {
+            self.parse_alter_view(false)
+        } else if self.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+            self.parse_alter_view(true)
         } else if self.parse_keyword(Keyword::USER) {
             self.parse_alter_user()
         } else {
-            self.expected("TABLE or USER after ALTER", self.peek_token())
+            self.expected(
+                "TABLE, VIEW, MATERIALIZED VIEW or USER after ALTER",
+                self.peek_token(),
+            )
         }
     }
 
+    pub fn parse_alter_view(&mut self, materialized: bool) -> Result<Statement, ParserError> {
+        let name = self.parse_object_name()?;
+        self.expect_keywords(&[Keyword::RENAME, Keyword::TO])?;
+        let view_name = self.parse_identifier()?;
+        Ok(Statement::AlterView {
+            materialized,
+            name,
+            operation: AlterViewOperation::RenameView { view_name },
+        })
+    }
+
     pub fn parse_alter_user(&mut self) -> Result<Statement, ParserError> {
         Ok(Statement::AlterUser(AlterUserStatement::parse_to(self)?))
     }
@@ -2841,8 +2885,26 @@ impl Parser {
                 return parser_err!("Table-valued functions do not support ORDER BY clauses");
             }
 
+            let as_of = self.parse_as_of()?;
             let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
-            Ok(TableFactor::Table { name, alias, args })
+            Ok(TableFactor::Table {
+                name,
+                alias,
+                args,
+                as_of,
+            })
+        }
+    }
+
+    /// Parse an optional `AS OF <epoch | 'timestamp'>`.
+    pub fn parse_as_of(&mut self) -> Result<Option<AsOf>, ParserError> {
+        if !self.parse_keywords(&[Keyword::AS, Keyword::OF]) {
+            return Ok(None);
+        }
+        match self.peek_token() {
+            Token::Number(..) => Ok(Some(AsOf::Epoch(self.parse_literal_uint()?))),
+            Token::SingleQuotedString(_) => Ok(Some(AsOf::Timestamp(self.parse_literal_string()?))),
+            unexpected => self.expected("epoch or timestamp after AS OF", unexpected),
         }
     }
 
@@ -128,6 +128,7 @@ pub fn table(name: impl Into<String>) -> TableFactor {
         name: ObjectName(vec![Ident::new(name.into())]),
         alias: None,
         args: vec![],
+        as_of: None,
     }
 }
 
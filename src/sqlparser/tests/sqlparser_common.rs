@@ -155,6 +155,7 @@ fn parse_update_with_table_alias() {
                             columns: vec![]
                         }),
                         args: vec![],
+                        as_of: None,
                     },
                     joins: vec![]
                 },
@@ -716,6 +717,43 @@ fn parse_like() {
     chk(true);
 }
 
+#[test]
+fn parse_like_escape() {
+    let sql = "SELECT * FROM customers WHERE name LIKE '%a!%' ESCAPE '!'";
+    let select = match one_statement_parses_to(
+        sql,
+        "SELECT * FROM customers WHERE name LIKE like_escape('%a!%', '!')",
+    ) {
+        Statement::Query(query) => match query.body {
+            SetExpr::Select(select) => select,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("name"))),
+            op: BinaryOperator::Like,
+            right: Box::new(Expr::Function(Function {
+                name: ObjectName(vec![Ident::new("like_escape")]),
+                args: vec![
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                        Value::SingleQuotedString("%a!%".to_string())
+                    ))),
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                        Value::SingleQuotedString("!".to_string())
+                    ))),
+                ],
+                over: None,
+                distinct: false,
+                order_by: vec![],
+                filter: None,
+            })),
+        },
+        select.selection.unwrap()
+    );
+}
+
 #[test]
 fn parse_ilike() {
     fn chk(negated: bool) {
@@ -1503,6 +1541,23 @@ fn parse_create_table_trailing_comma() {
     one_statement_parses_to(sql, "CREATE TABLE foo (bar INT)");
 }
 
+#[test]
+fn parse_alter_materialized_view() {
+    let sql = "ALTER MATERIALIZED VIEW myschema.mv RENAME TO new_mv";
+    match verified_stmt(sql) {
+        Statement::AlterView {
+            materialized,
+            name,
+            operation: AlterViewOperation::RenameView { view_name },
+        } => {
+            assert!(materialized);
+            assert_eq!("myschema.mv", name.to_string());
+            assert_eq!("new_mv", view_name.to_string());
+        }
+        _ => unreachable!(),
+    };
+}
+
 #[test]
 fn parse_alter_table() {
     let add_column = "ALTER TABLE tab ADD COLUMN foo TEXT;";
@@ -2133,7 +2188,9 @@ fn parse_delimited_identifiers() {
     );
     // check FROM
     match only(select.from).relation {
-        TableFactor::Table { name, alias, args } => {
+        TableFactor::Table {
+            name, alias, args, ..
+        } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
             assert!(args.is_empty());
@@ -2261,6 +2318,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![],
             },
@@ -2269,6 +2327,7 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![],
             }
@@ -2285,12 +2344,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t1a".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t1b".into()]),
                         alias: None,
                         args: vec![],
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2300,12 +2361,14 @@ fn parse_implicit_join() {
                     name: ObjectName(vec!["t2a".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
                         name: ObjectName(vec!["t2b".into()]),
                         alias: None,
                         args: vec![],
+                        as_of: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2325,6 +2388,7 @@ fn parse_cross_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                as_of: None,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2332,6 +2396,34 @@ fn parse_cross_join() {
     );
 }
 
+#[test]
+fn parse_select_as_of() {
+    let sql = "SELECT * FROM t1 AS OF 3735928559 AS t JOIN t2 AS OF '2022-08-01 12:00:00' ON true";
+    let select = verified_only_select(sql);
+    let from = only(select.from);
+    assert_eq!(
+        TableFactor::Table {
+            name: ObjectName(vec![Ident::new("t1")]),
+            alias: table_alias("t"),
+            args: vec![],
+            as_of: Some(AsOf::Epoch(3735928559)),
+        },
+        from.relation,
+    );
+    assert_eq!(
+        TableFactor::Table {
+            name: ObjectName(vec![Ident::new("t2")]),
+            alias: None,
+            args: vec![],
+            as_of: Some(AsOf::Timestamp("2022-08-01 12:00:00".to_string())),
+        },
+        only(from.joins).relation,
+    );
+
+    let res = parse_sql_statements("SELECT * FROM t1 AS OF now()");
+    assert!(res.is_err());
+}
+
 #[test]
 fn parse_joins_on() {
     fn join_with_constraint(
@@ -2344,6 +2436,7 @@ fn parse_joins_on() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2396,6 +2489,7 @@ fn parse_joins_using() {
                 name: ObjectName(vec![Ident::new(relation.into())]),
                 alias,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2440,6 +2534,7 @@ fn parse_natural_join() {
                 name: ObjectName(vec![Ident::new("t2")]),
                 alias: None,
                 args: vec![],
+                as_of: None,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2678,6 +2773,7 @@ fn parse_derived_tables() {
                     name: ObjectName(vec!["t2".into()]),
                     alias: None,
                     args: vec![],
+                    as_of: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
@@ -2889,6 +2985,7 @@ fn parse_create_view() {
             query,
             or_replace,
             materialized,
+            if_not_exists,
             with_options,
         } => {
             assert_eq!("myschema.myview", name.to_string());
@@ -2896,6 +2993,7 @@ fn parse_create_view() {
             assert_eq!("SELECT foo FROM bar", query.to_string());
             assert!(!materialized);
             assert!(!or_replace);
+            assert!(!if_not_exists);
             assert_eq!(with_options, vec![]);
         }
         _ => unreachable!(),
@@ -2936,12 +3034,14 @@ fn parse_create_view_with_columns() {
             with_options,
             query,
             materialized,
+            if_not_exists,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![Ident::new("has"), Ident::new("cols")]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1, 2", query.to_string());
             assert!(!materialized);
+            assert!(!if_not_exists);
             assert!(!or_replace)
         }
         _ => unreachable!(),
@@ -2958,12 +3058,14 @@ fn parse_create_or_replace_view() {
             with_options,
             query,
             materialized,
+            if_not_exists,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
             assert!(!materialized);
+            assert!(!if_not_exists);
             assert!(or_replace)
         }
         _ => unreachable!(),
@@ -2985,12 +3087,14 @@ fn parse_create_or_replace_materialized_view() {
             with_options,
             query,
             materialized,
+            if_not_exists,
         } => {
             assert_eq!("v", name.to_string());
             assert_eq!(columns, vec![]);
             assert_eq!(with_options, vec![]);
             assert_eq!("SELECT 1", query.to_string());
             assert!(materialized);
+            assert!(!if_not_exists);
             assert!(or_replace)
         }
         _ => unreachable!(),
@@ -3007,12 +3111,14 @@ fn parse_create_materialized_view() {
             columns,
             query,
             materialized,
+            if_not_exists,
             with_options,
         } => {
             assert_eq!("myschema.myview", name.to_string());
             assert_eq!(Vec::<Ident>::new(), columns);
             assert_eq!("SELECT foo FROM bar", query.to_string());
             assert!(materialized);
+            assert!(!if_not_exists);
             assert_eq!(with_options, vec![]);
             assert!(!or_replace);
         }
@@ -3020,6 +3126,24 @@ fn parse_create_materialized_view() {
     }
 }
 
+#[test]
+fn parse_create_materialized_view_if_not_exists() {
+    let sql = "CREATE MATERIALIZED VIEW IF NOT EXISTS myschema.myview AS SELECT foo FROM bar";
+    match verified_stmt(sql) {
+        Statement::CreateView {
+            name,
+            materialized,
+            if_not_exists,
+            ..
+        } => {
+            assert_eq!("myschema.myview", name.to_string());
+            assert!(materialized);
+            assert!(if_not_exists);
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn parse_drop_table() {
     let sql = "DROP TABLE foo";
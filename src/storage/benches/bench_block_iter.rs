@@ -55,8 +55,9 @@ fn bench_block_iter(c: &mut Criterion) {
         &data,
         |b, data| {
             b.iter(|| {
-                let block =
-                    BlockHolder::from_owned_block(Box::new(Block::decode(data.clone()).unwrap()));
+                let block = BlockHolder::from_owned_block(Box::new(
+                    Block::decode(data.clone(), true).unwrap(),
+                ));
                 block_iter_next(block)
             });
         },
@@ -73,14 +74,15 @@ fn bench_block_iter(c: &mut Criterion) {
         &data,
         |b, data| {
             b.iter(|| {
-                let block =
-                    BlockHolder::from_owned_block(Box::new(Block::decode(data.clone()).unwrap()));
+                let block = BlockHolder::from_owned_block(Box::new(
+                    Block::decode(data.clone(), true).unwrap(),
+                ));
                 block_iter_prev(block)
             });
         },
     );
 
-    let block = BlockHolder::from_owned_block(Box::new(Block::decode(data.clone()).unwrap()));
+    let block = BlockHolder::from_owned_block(Box::new(Block::decode(data.clone(), true).unwrap()));
     let mut iter = BlockIterator::new(block);
     iter.seek_to_first();
     for t in 1..=TABLES_PER_SSTABLE {
@@ -24,7 +24,7 @@ use risingwave_common::util::addr::HostAddr;
 use risingwave_common_service::metrics_manager::MetricsManager;
 use risingwave_common_service::observer_manager::ObserverManager;
 use risingwave_object_store::object::parse_remote_object_store;
-use risingwave_pb::common::WorkerType;
+use risingwave_pb::common::{WorkerRole, WorkerType};
 use risingwave_pb::hummock::compactor_service_server::CompactorServiceServer;
 use risingwave_rpc_client::MetaClient;
 use risingwave_storage::hummock::compaction_executor::CompactionExecutor;
@@ -59,7 +59,13 @@ pub async fn compactor_serve(
     // Register to the cluster.
     let mut meta_client = MetaClient::new(&opts.meta_address).await.unwrap();
     let worker_id = meta_client
-        .register(WorkerType::Compactor, &client_addr, 0)
+        .register(
+            WorkerType::Compactor,
+            &client_addr,
+            0,
+            Default::default(),
+            WorkerRole::Unspecified,
+        )
         .await
         .unwrap();
     tracing::info!("Assigned compactor id {}", worker_id);
@@ -96,6 +102,8 @@ pub async fn compactor_serve(
         storage_config.data_directory.to_string(),
         storage_config.block_cache_capacity_mb * (1 << 20),
         storage_config.meta_cache_capacity_mb * (1 << 20),
+        storage_config.verify_block_checksum,
+        state_store_stats.clone(),
     ));
 
     let table_id_to_slice_transform = Arc::new(RwLock::new(HashMap::new()));
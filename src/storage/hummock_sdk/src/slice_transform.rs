@@ -80,7 +80,8 @@ impl SliceTransform for DummySliceTransform {
 }
 
 /// [`SchemaSliceTransform`] build from table_catalog and transform a `full_key` to prefix for
-/// prefix_bloom_filter
+/// prefix_bloom_filter, or to some columns in the middle of the pk if the table is not read by a
+/// prefix of the pk.
 #[derive(Clone)]
 pub struct SchemaSliceTransform {
     /// Each stateful operator has its own read pattern, partly using prefix scan.
@@ -88,6 +89,9 @@ pub struct SchemaSliceTransform {
     /// `TableCatalog`. `read_pattern_prefix_column` means the count of column to decode prefix
     /// from storage key.
     read_pattern_prefix_column: u32,
+    /// The index of the first pk column to extract. If it's not 0, only the bytes of the
+    /// extracted columns are returned, without the table prefix and the vnode.
+    read_pattern_start_column: u32,
     deserializer: OrderedRowDeserializer,
     // TODO:need some bench test for same prefix case like join (if we need a prefix_cache for same
     // prefix_key)
@@ -95,27 +99,66 @@ pub struct SchemaSliceTransform {
 
 impl SliceTransform for SchemaSliceTransform {
     fn transform<'a>(&mut self, full_key: &'a [u8]) -> &'a [u8] {
+        self.extract(full_key)
+    }
+}
+
+impl SchemaSliceTransform {
+    /// Same as `transform`, without the need of a mutable transform, so that the transforms
+    /// shared by the reads of a table are not cloned.
+    pub fn extract<'a>(&self, full_key: &'a [u8]) -> &'a [u8] {
         debug_assert!(full_key.len() >= TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE);
 
+        // if the key with table_id deserializer fail from schema, that shoud panic here for early
+        // detection
+        let (pk_start, pk_end) = self.extracted_range(full_key).unwrap();
+
+        let pk_offset = TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE;
+        if self.read_pattern_start_column == 0 {
+            &full_key[0..pk_offset + pk_end]
+        } else {
+            &full_key[pk_offset + pk_start..pk_offset + pk_end]
+        }
+    }
+
+    /// Extracts the bloom filter key shared by all the keys starting with `key_prefix`, which is
+    /// only possible if the extracted columns are a prefix of the pk, and are all in `key_prefix`.
+    /// `key_prefix` must consist of whole columns, as the encoded key prefixes of the pk.
+    pub fn extract_prefix<'a>(&self, key_prefix: &'a [u8]) -> Option<&'a [u8]> {
+        if self.read_pattern_start_column != 0
+            || key_prefix.len() < TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE
+        {
+            return None;
+        }
+        let (_, pk_end) = self.extracted_range(key_prefix)?;
+        if TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE + pk_end > key_prefix.len() {
+            return None;
+        }
+        Some(&key_prefix[0..TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE + pk_end])
+    }
+
+    /// Returns the range of the extracted columns in the pk of `full_key`.
+    fn extracted_range(&self, full_key: &[u8]) -> Option<(usize, usize)> {
         let (_table_prefix, key) = full_key.split_at(TABLE_PREFIX_LEN);
         let (_vnode_prefix, pk) = key.split_at(VIRTUAL_NODE_SIZE);
 
-        // if the key with table_id deserializer fail from schema, that shoud panic here for early
-        // detection
-        let pk_prefix_len = self
+        let start_column = self.read_pattern_start_column as usize;
+        let end_column = start_column + self.read_pattern_prefix_column as usize;
+        let pk_start = self
             .deserializer
-            .deserialize_prefix_len_with_column_indices(
-                pk,
-                0..self.read_pattern_prefix_column as usize,
-            )
-            .unwrap();
-
-        let prefix_len = TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE + pk_prefix_len;
-        &full_key[0..prefix_len]
+            .deserialize_prefix_len_with_column_indices(pk, 0..start_column)
+            .ok()?;
+        let pk_end = pk_start
+            + self
+                .deserializer
+                .deserialize_prefix_len_with_column_indices(
+                    &pk[pk_start..],
+                    start_column..end_column,
+                )
+                .ok()?;
+        Some((pk_start, pk_end))
     }
-}
 
-impl SchemaSliceTransform {
     pub fn new(table_catalog: &Table) -> Self {
         assert_ne!(0, table_catalog.read_pattern_prefix_column);
 
@@ -144,6 +187,7 @@ impl SchemaSliceTransform {
 
         Self {
             read_pattern_prefix_column: table_catalog.read_pattern_prefix_column,
+            read_pattern_start_column: table_catalog.read_pattern_start_column,
             deserializer: OrderedRowDeserializer::new(data_types, order_types),
         }
     }
@@ -314,10 +358,12 @@ mod tests {
                 ColumnOrder {
                     order_type: 1, // Ascending
                     index: 1,
+                    nulls_first: false,
                 },
                 ColumnOrder {
                     order_type: 1, // Ascending
                     index: 3,
+                    nulls_first: false,
                 },
             ],
             pk: vec![0],
@@ -329,9 +375,54 @@ mod tests {
             mapping: None,
             properties: HashMap::from([(String::from("ttl"), String::from("300"))]),
             read_pattern_prefix_column: column_count, // 1 column
+            read_pattern_start_column: 0,
         }
     }
 
+    #[test]
+    fn test_schema_slice_transform_non_prefix() {
+        // Extract the second pk column only.
+        let mut prost_table = build_table_with_prefix_column_num(1);
+        prost_table.read_pattern_start_column = 1;
+        let mut schema_slice_transform = SchemaSliceTransform::new(&prost_table);
+
+        let order_types: Vec<OrderType> = vec![OrderType::Ascending, OrderType::Ascending];
+        let serializer = OrderedRowSerializer::new(order_types);
+        let serialize = |v0: i64, v1: &str| {
+            let row = Row(vec![
+                Some(ScalarImpl::Int64(v0)),
+                Some(ScalarImpl::Utf8(v1.to_string())),
+            ]);
+            let mut row_bytes = vec![];
+            serializer.serialize(&row, &mut row_bytes);
+            row_bytes
+        };
+
+        let table_prefix = {
+            let mut buf = BytesMut::with_capacity(TABLE_PREFIX_LEN);
+            buf.put_u8(b't');
+            buf.put_u32(1);
+            buf.to_vec()
+        };
+        let vnode_prefix = "v".as_bytes();
+
+        let row_bytes = serialize(100, "abc");
+        let full_key = [&table_prefix, vnode_prefix, &row_bytes].concat();
+        let output_key = schema_slice_transform.transform(&full_key).to_vec();
+        assert_eq!(
+            &row_bytes[1 + mem::size_of::<i64>()..],
+            output_key.as_slice()
+        );
+
+        // Keys only differing in the first column are extracted to the same bloom filter key.
+        let row_bytes = serialize(200, "abc");
+        let full_key = [&table_prefix, vnode_prefix, &row_bytes].concat();
+        assert_eq!(
+            output_key.as_slice(),
+            schema_slice_transform.transform(&full_key)
+        );
+    }
+
     #[test]
     fn test_schema_slice_transform() {
         let prost_table = build_table_with_prefix_column_num(1);
@@ -363,6 +454,16 @@ mod tests {
             TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE + 1 + mem::size_of::<i64>(),
             output_key.len()
         );
+
+        // A key prefix containing the extracted column shares the bloom filter key.
+        let key_prefix = &full_key[..output_key.len() + 1];
+        assert_eq!(
+            Some(output_key),
+            schema_slice_transform.extract_prefix(key_prefix)
+        );
+        // A key prefix not containing the extracted column doesn't.
+        let key_prefix = &full_key[..TABLE_PREFIX_LEN + VIRTUAL_NODE_SIZE];
+        assert_eq!(None, schema_slice_transform.extract_prefix(key_prefix));
     }
 
     #[test]
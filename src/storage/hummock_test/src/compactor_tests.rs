@@ -177,7 +177,13 @@ mod tests {
         );
 
         // 3. compact
-        Compactor::compact(Arc::new(compact_ctx), compact_task.clone()).await;
+        Compactor::compact(
+            Arc::new(compact_ctx),
+            compact_task.clone(),
+            Arc::default(),
+            tokio::sync::oneshot::channel().1,
+        )
+        .await;
 
         // 4. get the latest version and check
         let version = hummock_manager_ref.get_current_version().await;
@@ -275,7 +281,13 @@ mod tests {
         );
 
         // 3. compact
-        Compactor::compact(Arc::new(compact_ctx), compact_task.clone()).await;
+        Compactor::compact(
+            Arc::new(compact_ctx),
+            compact_task.clone(),
+            Arc::default(),
+            tokio::sync::oneshot::channel().1,
+        )
+        .await;
 
         // 4. get the latest version and check
         let version = hummock_manager_ref.get_current_version().await;
@@ -412,7 +424,13 @@ mod tests {
         );
 
         // 3. compact
-        Compactor::compact(Arc::new(compact_ctx), compact_task.clone()).await;
+        Compactor::compact(
+            Arc::new(compact_ctx),
+            compact_task.clone(),
+            Arc::default(),
+            tokio::sync::oneshot::channel().1,
+        )
+        .await;
 
         // 4. get the latest version and check
         let version = hummock_manager_ref.get_current_version().await;
@@ -529,7 +547,13 @@ mod tests {
         );
 
         // 3. compact
-        Compactor::compact(Arc::new(compact_ctx), compact_task.clone()).await;
+        Compactor::compact(
+            Arc::new(compact_ctx),
+            compact_task.clone(),
+            Arc::default(),
+            tokio::sync::oneshot::channel().1,
+        )
+        .await;
 
         // 4. get the latest version and check
         let version: HummockVersion = hummock_manager_ref.get_current_version().await;
@@ -688,7 +712,13 @@ mod tests {
         );
 
         // 3. compact
-        Compactor::compact(Arc::new(compact_ctx), compact_task.clone()).await;
+        Compactor::compact(
+            Arc::new(compact_ctx),
+            compact_task.clone(),
+            Arc::default(),
+            tokio::sync::oneshot::channel().1,
+        )
+        .await;
 
         // 4. get the latest version and check
         let version: HummockVersion = hummock_manager_ref.get_current_version().await;
@@ -950,3 +950,119 @@ async fn test_delete_get() {
         .unwrap()
         .is_none());
 }
+
+#[tokio::test]
+async fn test_delete_range() {
+    let sstable_store = mock_sstable_store();
+    let hummock_options = Arc::new(default_config_for_test());
+    let (_env, hummock_manager_ref, _cluster_manager_ref, worker_node) =
+        setup_compute_env(8080).await;
+    let hummock_meta_client = Arc::new(MockHummockMetaClient::new(
+        hummock_manager_ref.clone(),
+        worker_node.id,
+    ));
+
+    let hummock_storage = HummockStorage::with_default_stats(
+        hummock_options,
+        sstable_store.clone(),
+        hummock_meta_client.clone(),
+        Arc::new(StateStoreMetrics::unused()),
+        Arc::new(DummyCompactionGroupClient::new(
+            StaticCompactionGroupId::StateDefault.into(),
+        )),
+    )
+    .await
+    .unwrap();
+
+    let initial_epoch = hummock_storage
+        .local_version_manager()
+        .get_pinned_version()
+        .max_committed_epoch();
+    let epoch1 = initial_epoch + 1;
+    let batch1 = vec![
+        (Bytes::from("aa"), StorageValue::new_default_put("111")),
+        (Bytes::from("bb"), StorageValue::new_default_put("222")),
+        (Bytes::from("cc"), StorageValue::new_default_put("333")),
+    ];
+    hummock_storage
+        .ingest_batch(
+            batch1,
+            WriteOptions {
+                epoch: epoch1,
+                table_id: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+    hummock_storage.sync(Some(epoch1)).await.unwrap();
+    let ssts = hummock_storage.get_uncommitted_ssts(epoch1);
+    hummock_meta_client
+        .commit_epoch(epoch1, ssts)
+        .await
+        .unwrap();
+
+    let epoch2 = initial_epoch + 2;
+    hummock_storage
+        .delete_range(
+            b"aa",
+            b"cc",
+            WriteOptions {
+                epoch: epoch2,
+                table_id: Default::default(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let read_options = |epoch| ReadOptions {
+        epoch,
+        table_id: Default::default(),
+        ttl: None,
+    };
+    let check = |hummock_storage: HummockStorage| async move {
+        for key in ["aa", "bb"] {
+            assert!(hummock_storage
+                .get(key.as_bytes(), read_options(epoch2))
+                .await
+                .unwrap()
+                .is_none());
+            // The keys can still be read at the previous epoch.
+            assert!(hummock_storage
+                .get(key.as_bytes(), read_options(epoch1))
+                .await
+                .unwrap()
+                .is_some());
+        }
+        assert_eq!(
+            hummock_storage
+                .get(b"cc", read_options(epoch2))
+                .await
+                .unwrap(),
+            Some(Bytes::from("333"))
+        );
+        let kvs = hummock_storage
+            .scan::<_, Vec<u8>>(.., None, read_options(epoch2))
+            .await
+            .unwrap();
+        assert_eq!(kvs, vec![(Bytes::from("cc"), Bytes::from("333"))]);
+        let kvs = hummock_storage
+            .backward_scan::<_, Vec<u8>>(.., None, read_options(epoch2))
+            .await
+            .unwrap();
+        assert_eq!(kvs, vec![(Bytes::from("cc"), Bytes::from("333"))]);
+    };
+
+    // The range tombstone is in the shared buffer.
+    check(hummock_storage.clone()).await;
+
+    hummock_storage.sync(Some(epoch2)).await.unwrap();
+    let ssts = hummock_storage.get_uncommitted_ssts(epoch2);
+    hummock_meta_client
+        .commit_epoch(epoch2, ssts)
+        .await
+        .unwrap();
+    hummock_storage.wait_epoch(epoch2).await.unwrap();
+
+    // The range tombstone is in an SST.
+    check(hummock_storage).await;
+}
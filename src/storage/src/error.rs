@@ -43,6 +43,11 @@ pub enum StorageError {
 
     #[error("Array error: {0}")]
     ArrayError(#[from] ArrayError),
+
+    #[error(
+        "Memory state store capacity exceeded: usage {usage} bytes, capacity {capacity} bytes"
+    )]
+    MemoryCapacityExceeded { usage: usize, capacity: usize },
 }
 
 pub type StorageResult<T> = std::result::Result<T, StorageError>;
@@ -15,10 +15,11 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use futures::Future;
-use risingwave_common::cache::{CachableEntry, LruCache};
+use risingwave_common::cache::{CachableEntry, LruCache, LruCacheEventListener};
 use risingwave_hummock_sdk::HummockSstableId;
 
 use super::{Block, HummockResult};
@@ -74,9 +75,26 @@ impl Deref for BlockHolder {
 unsafe impl Send for BlockHolder {}
 unsafe impl Sync for BlockHolder {}
 
+/// Counts the blocks evicted from the block cache, so that we can tell whether the cache is large
+/// enough for the working set.
+#[derive(Default)]
+struct BlockCacheEvictionCounter {
+    count: AtomicU64,
+}
+
+impl LruCacheEventListener for BlockCacheEvictionCounter {
+    type K = (HummockSstableId, u64);
+    type T = Box<Block>;
+
+    fn on_evict(&self, _key: &Self::K, _value: &Self::T) {
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
 #[derive(Clone)]
 pub struct BlockCache {
     inner: Arc<LruCache<(HummockSstableId, u64), Box<Block>>>,
+    eviction_counter: Arc<BlockCacheEvictionCounter>,
 }
 
 impl BlockCache {
@@ -87,9 +105,13 @@ impl BlockCache {
         while (capacity >> max_shard_bits) < MIN_BUFFER_SIZE_PER_SHARD && max_shard_bits > 0 {
             max_shard_bits -= 1;
         }
-        let cache = LruCache::new(max_shard_bits, capacity);
+        let eviction_counter = Arc::new(BlockCacheEvictionCounter::default());
+        let listener: Arc<dyn LruCacheEventListener<K = (HummockSstableId, u64), T = Box<Block>>> =
+            eviction_counter.clone();
+        let cache = LruCache::with_event_listeners(max_shard_bits, capacity, vec![listener]);
         Self {
             inner: Arc::new(cache),
+            eviction_counter,
         }
     }
 
@@ -156,6 +178,11 @@ impl BlockCache {
         self.inner.get_memory_usage()
     }
 
+    /// Returns the number of blocks evicted from the cache since it was created.
+    pub fn eviction_count(&self) -> u64 {
+        self.eviction_counter.count.load(Ordering::Relaxed)
+    }
+
     #[cfg(any(test, feature = "test"))]
     pub fn clear(&self) {
         // This is only a method for test. Therefore it should be safe to call the unsafe method.
@@ -20,32 +20,33 @@ use std::time::{Duration, Instant};
 
 use bytes::{Bytes, BytesMut};
 use dyn_clone::DynClone;
-use futures::future::{try_join_all, BoxFuture};
+use futures::future::{try_join_all, AbortHandle, Abortable, BoxFuture};
 use futures::{stream, FutureExt, StreamExt, TryFutureExt};
 use itertools::Itertools;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use risingwave_common::config::constant::hummock::{CompactionFilterFlag, TABLE_OPTION_DUMMY_TTL};
 use risingwave_common::config::StorageConfig;
 use risingwave_hummock_sdk::compact::compact_task_to_string;
 use risingwave_hummock_sdk::compaction_group::StaticCompactionGroupId;
 use risingwave_hummock_sdk::key::{
-    extract_table_id_and_epoch, get_epoch, get_table_id, Epoch, FullKey,
+    extract_table_id_and_epoch, get_epoch, get_table_id, user_key, Epoch, FullKey,
 };
 use risingwave_hummock_sdk::key_range::KeyRange;
 use risingwave_hummock_sdk::slice_transform::SliceTransformImpl;
 use risingwave_hummock_sdk::{CompactionGroupId, HummockSstableId, VersionedComparator};
 use risingwave_pb::hummock::{
-    CompactTask, LevelType, SstableInfo, SubscribeCompactTasksResponse, VacuumTask,
+    CancelCompactTask, CompactTask, CompactTaskProgress, LevelType, SstableInfo,
+    SubscribeCompactTasksResponse, VacuumTask,
 };
 use risingwave_rpc_client::HummockMetaClient;
-use tokio::sync::oneshot::Sender;
+use tokio::sync::oneshot::{Receiver, Sender};
 use tokio::task::JoinHandle;
 
 use super::iterator::ConcatIterator;
 use super::multi_builder::CapacitySplitTableBuilder;
 use super::{
-    CompressionAlgorithm, HummockResult, Sstable, SstableBuilder, SstableBuilderOptions,
-    SstableIterator, SstableIteratorType,
+    CompressionAlgorithm, HummockResult, RangeTombstone, RangeTombstones, Sstable, SstableBuilder,
+    SstableBuilderOptions, SstableIterator, SstableIteratorType,
 };
 use crate::hummock::compaction_executor::CompactionExecutor;
 use crate::hummock::iterator::{
@@ -70,6 +71,7 @@ pub struct RemoteBuilderFactory {
     limiter: Arc<MemoryLimiter>,
     options: SstableBuilderOptions,
     remote_rpc_cost: Arc<AtomicU64>,
+    slice_transforms: HashMap<u32, SliceTransformImpl>,
 }
 
 #[async_trait::async_trait]
@@ -88,7 +90,8 @@ impl TableBuilderFactory for RemoteBuilderFactory {
             .require_memory(self.options.capacity as u64 + self.options.block_capacity as u64)
             .await
             .unwrap();
-        let builder = SstableBuilder::new(table_id, self.options.clone());
+        let builder = SstableBuilder::new(table_id, self.options.clone())
+            .with_slice_transforms(self.slice_transforms.clone());
         Ok((tracker, builder))
     }
 }
@@ -230,6 +233,37 @@ impl MultiCompactionFilter {
     }
 }
 
+/// The progress of a compact task, which is reported to the hummock manager periodically.
+#[derive(Default)]
+pub struct TaskProgress {
+    read_bytes: AtomicU64,
+    written_bytes: AtomicU64,
+    total_input_bytes: AtomicU64,
+}
+
+impl TaskProgress {
+    fn inc_read_bytes(&self, bytes: u64) {
+        self.read_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn inc_written_bytes(&self, bytes: u64) {
+        self.written_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn to_prost(&self, task_id: u64) -> CompactTaskProgress {
+        CompactTaskProgress {
+            task_id,
+            read_bytes: self.read_bytes.load(Ordering::Relaxed),
+            written_bytes: self.written_bytes.load(Ordering::Relaxed),
+            total_input_bytes: self.total_input_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The compact tasks running in a compactor node, with their progress and the senders to cancel
+/// them.
+type RunningCompactTasks = Arc<Mutex<HashMap<u64, (Arc<TaskProgress>, Sender<()>)>>>;
+
 #[derive(Clone)]
 /// Implementation of Hummock compaction.
 pub struct Compactor {
@@ -240,6 +274,8 @@ pub struct Compactor {
     /// When it's local compaction from memory, it uses a locally
     /// constructed compaction task.
     compact_task: CompactTask,
+
+    task_progress: Arc<TaskProgress>,
 }
 
 pub type CompactOutput = (usize, Vec<(Sstable, Vec<u32>)>);
@@ -250,6 +286,7 @@ impl Compactor {
         Self {
             context,
             compact_task,
+            task_progress: Arc::default(),
         }
     }
 
@@ -371,6 +408,22 @@ impl Compactor {
 
         let sstable_store = context.sstable_store.clone();
         let stats = context.stats.clone();
+        let mut local_stats = StoreLocalStatistic::default();
+
+        let mut range_tombstones = vec![];
+        for data in payload.iter().flatten() {
+            match data {
+                UncommittedData::Batch(batch) => {
+                    range_tombstones.extend(batch.range_tombstone().cloned());
+                }
+                UncommittedData::Sst((_, table_info)) => {
+                    let table = sstable_store
+                        .sstable(table_info.id, &mut local_stats)
+                        .await?;
+                    range_tombstones.extend(table.value().meta.range_tombstones.iter().cloned());
+                }
+            }
+        }
 
         let parallelism = compact_task.splits.len();
         let mut compact_success = true;
@@ -378,7 +431,6 @@ impl Compactor {
         let mut compaction_futures = vec![];
         let compactor = Compactor::new(context, compact_task.clone());
 
-        let mut local_stats = StoreLocalStatistic::default();
         for (split_index, _) in compact_task.splits.iter().enumerate() {
             let compactor = compactor.clone();
             let iter = build_ordered_merge_iter::<ForwardIter>(
@@ -391,7 +443,12 @@ impl Compactor {
             .await?;
             let compaction_executor = compactor.context.compaction_executor.as_ref().cloned();
 
-            let split_task = async move { compactor.compact_key_range(split_index, iter).await };
+            let range_tombstones = range_tombstones.clone();
+            let split_task = async move {
+                compactor
+                    .compact_key_range(split_index, iter, range_tombstones)
+                    .await
+            };
             let rx = Compactor::request_execution(compaction_executor, split_task)?;
             compaction_futures.push(rx);
         }
@@ -460,7 +517,14 @@ impl Compactor {
 
     /// Handle a compaction task and report its status to hummock manager.
     /// Always return `Ok` and let hummock manager handle errors.
-    pub async fn compact(context: Arc<CompactorContext>, compact_task: CompactTask) -> bool {
+    ///
+    /// The task is aborted once `shutdown_rx` receives a message, and is then reported as failed.
+    pub async fn compact(
+        context: Arc<CompactorContext>,
+        compact_task: CompactTask,
+        task_progress: Arc<TaskProgress>,
+        mut shutdown_rx: Receiver<()>,
+    ) -> bool {
         use risingwave_common::catalog::TableOption;
         let group_label = compact_task.compaction_group_id.to_string();
         let cur_level_label = compact_task.input_ssts[0].level_idx.to_string();
@@ -524,7 +588,18 @@ impl Compactor {
         let mut compact_success = true;
         let mut output_ssts = Vec::with_capacity(parallelism);
         let mut compaction_futures = vec![];
+        let mut abort_handles = Vec::with_capacity(parallelism);
+        task_progress.total_input_bytes.store(
+            compact_task
+                .input_ssts
+                .iter()
+                .flat_map(|level| level.table_infos.iter())
+                .map(|table| table.file_size)
+                .sum(),
+            Ordering::Relaxed,
+        );
         let mut compactor = Compactor::new(context, compact_task.clone());
+        compactor.task_progress = task_progress;
 
         let mut multi_filter = MultiCompactionFilter::default();
         let compaction_filter_flag =
@@ -561,10 +636,21 @@ impl Compactor {
             let filter = multi_filter.clone();
             let split_task = async move {
                 let merge_iter = compactor.build_sst_iter().await?;
+                let range_tombstones = compactor.collect_range_tombstones().await?;
                 compactor
-                    .compact_key_range_with_filter(split_index, merge_iter, filter)
+                    .compact_key_range_with_filter(
+                        split_index,
+                        merge_iter,
+                        filter,
+                        range_tombstones,
+                    )
                     .await
             };
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            abort_handles.push(abort_handle);
+            let split_task = Abortable::new(split_task, abort_registration).map(|result| {
+                result.unwrap_or_else(|_| Err(HummockError::other("compaction task is cancelled")))
+            });
             let rx = match Compactor::request_execution(compaction_executor, split_task) {
                 Ok(rx) => rx,
                 Err(err) => {
@@ -576,7 +662,24 @@ impl Compactor {
         }
 
         let mut buffered = stream::iter(compaction_futures).buffer_unordered(parallelism);
-        while let Some(future_result) = buffered.next().await {
+        let mut shutdown_rx_done = false;
+        loop {
+            let future_result = tokio::select! {
+                result = &mut shutdown_rx, if !shutdown_rx_done => {
+                    shutdown_rx_done = true;
+                    // The sender may be dropped without cancelling the task.
+                    if result.is_ok() {
+                        tracing::info!("Cancelling compaction task {}", compact_task.task_id);
+                        compact_success = false;
+                        abort_handles.iter().for_each(AbortHandle::abort);
+                    }
+                    continue;
+                }
+                future_result = buffered.next() => match future_result {
+                    Some(future_result) => future_result,
+                    None => break,
+                },
+            };
             match future_result.unwrap() {
                 Ok((split_index, ssts)) => {
                     output_ssts.push((split_index, ssts));
@@ -629,6 +732,8 @@ impl Compactor {
                     }),
                     file_size: sst.meta.estimated_size as u64,
                     table_ids,
+                    table_key_counts: sst.meta.table_key_counts_map(),
+                    range_tombstone_count: sst.meta.range_tombstones.len() as u32,
                 };
                 compaction_write_bytes += sst_info.file_size;
                 self.compact_task.sorted_output_ssts.push(sst_info);
@@ -669,6 +774,7 @@ impl Compactor {
         split_index: usize,
         iter: impl HummockIterator<Direction = Forward>,
         compaction_filter: impl CompactionFilter,
+        range_tombstones: Vec<RangeTombstone>,
     ) -> HummockResult<CompactOutput> {
         let split = self.compact_task.splits[split_index].clone();
         let kr = KeyRange {
@@ -676,6 +782,8 @@ impl Compactor {
             right: Bytes::copy_from_slice(split.get_right()),
             inf: split.get_inf(),
         };
+        let split_start_user_key = (!kr.left.is_empty()).then(|| user_key(&kr.left).to_vec());
+        let split_end_user_key = (!kr.right.is_empty()).then(|| user_key(&kr.right).to_vec());
 
         let get_id_time = Arc::new(AtomicU64::new(0));
         let max_target_file_size = self.context.options.sstable_size_mb as usize * (1 << 20);
@@ -699,6 +807,7 @@ impl Compactor {
             limiter: self.context.memory_limiter.clone(),
             options,
             remote_rpc_cost: get_id_time.clone(),
+            slice_transforms: self.context.table_id_to_slice_transform.read().clone(),
         };
 
         // NOTICE: should be user_key overlap, NOT full_key overlap!
@@ -707,6 +816,25 @@ impl Compactor {
             cache_policy,
             self.context.sstable_store.clone(),
         );
+        // Once the keys they cover are dropped, range tombstones below the watermark are useless
+        // in the bottom level.
+        let watermark = self.compact_task.watermark;
+        builder.add_range_tombstones(
+            range_tombstones
+                .iter()
+                .filter(|range_tombstone| {
+                    !(self.compact_task.gc_delete_keys && range_tombstone.epoch <= watermark)
+                })
+                .filter_map(|range_tombstone| {
+                    range_tombstone.clip(
+                        split_start_user_key.as_deref(),
+                        split_end_user_key.as_deref(),
+                    )
+                }),
+        );
+        // The keys covered by the range tombstones below the watermark can be dropped.
+        let mut dropping_range_tombstones = RangeTombstones::new(watermark);
+        dropping_range_tombstones.extend(&range_tombstones);
 
         // Monitor time cost building shared buffer to SSTs.
         let compact_timer = if self.context.is_share_buffer_compact {
@@ -721,11 +849,13 @@ impl Compactor {
             iter,
             self.compact_task.gc_delete_keys,
             self.compact_task.watermark,
+            &dropping_range_tombstones,
             compaction_filter,
+            &self.task_progress,
         )
         .await?;
         let builder_len = builder.len();
-        let sealed_builders = builder.finish();
+        let sealed_builders = builder.finish().await?;
         compact_timer.observe_duration();
 
         let mut ssts = Vec::with_capacity(builder_len);
@@ -764,6 +894,11 @@ impl Compactor {
             })
         }))
         .await?;
+        self.task_progress.inc_written_bytes(
+            ssts.iter()
+                .map(|(sst, _)| sst.meta.estimated_size as u64)
+                .sum(),
+        );
 
         self.context
             .stats
@@ -776,9 +911,10 @@ impl Compactor {
         &self,
         split_index: usize,
         iter: impl HummockIterator<Direction = Forward>,
+        range_tombstones: Vec<RangeTombstone>,
     ) -> HummockResult<CompactOutput> {
         let dummy_compaction_filter = DummyCompactionFilter {};
-        self.compact_key_range_impl(split_index, iter, dummy_compaction_filter)
+        self.compact_key_range_impl(split_index, iter, dummy_compaction_filter, range_tombstones)
             .await
     }
 
@@ -787,11 +923,33 @@ impl Compactor {
         split_index: usize,
         iter: impl HummockIterator<Direction = Forward>,
         compaction_filter: impl CompactionFilter,
+        range_tombstones: Vec<RangeTombstone>,
     ) -> HummockResult<CompactOutput> {
-        self.compact_key_range_impl(split_index, iter, compaction_filter)
+        self.compact_key_range_impl(split_index, iter, compaction_filter, range_tombstones)
             .await
     }
 
+    /// Collects the range tombstones of the input ssts.
+    async fn collect_range_tombstones(&self) -> HummockResult<Vec<RangeTombstone>> {
+        let mut range_tombstones = vec![];
+        let mut stats = StoreLocalStatistic::default();
+        for table_info in self
+            .compact_task
+            .input_ssts
+            .iter()
+            .flat_map(|level| level.table_infos.iter())
+        {
+            let table = self
+                .context
+                .sstable_store
+                .sstable(table_info.id, &mut stats)
+                .await?;
+            range_tombstones.extend(table.value().meta.range_tombstones.iter().cloned());
+        }
+        stats.report(self.context.stats.as_ref());
+        Ok(range_tombstones)
+    }
+
     /// Build the merge iterator based on the given input ssts.
     async fn build_sst_iter(&self) -> HummockResult<MultiSstIterator> {
         let mut table_iters = Vec::new();
@@ -892,19 +1050,36 @@ impl Compactor {
         });
         let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel();
         let stream_retry_interval = Duration::from_secs(60);
+        let progress_report_interval = Duration::from_secs(10);
+        let running_tasks = RunningCompactTasks::default();
         let join_handle = tokio::spawn(async move {
-            let process_task = |compact_task,
+            let process_task = |compact_task: Option<CompactTask>,
                                 vacuum_task,
                                 compactor_context,
                                 sstable_store,
-                                hummock_meta_client| async {
+                                hummock_meta_client,
+                                running_tasks: RunningCompactTasks| async move {
                 if let Some(compact_task) = compact_task {
-                    Compactor::compact(compactor_context, compact_task).await;
+                    let task_id = compact_task.task_id;
+                    let task_progress = Arc::new(TaskProgress::default());
+                    let (task_shutdown_tx, task_shutdown_rx) = tokio::sync::oneshot::channel();
+                    running_tasks
+                        .lock()
+                        .insert(task_id, (task_progress.clone(), task_shutdown_tx));
+                    Compactor::compact(
+                        compactor_context,
+                        compact_task,
+                        task_progress,
+                        task_shutdown_rx,
+                    )
+                    .await;
+                    running_tasks.lock().remove(&task_id);
                 }
 
                 Compactor::try_vacuum(vacuum_task, sstable_store, hummock_meta_client).await;
             };
             let mut min_interval = tokio::time::interval(stream_retry_interval);
+            let mut progress_interval = tokio::time::interval(progress_report_interval);
             // This outer loop is to recreate stream.
             'start_stream: loop {
                 tokio::select! {
@@ -941,6 +1116,23 @@ impl Compactor {
                         message = stream.message() => {
                             message
                         },
+                        // Report the progress of the running tasks.
+                        _ = progress_interval.tick() => {
+                            let progress = running_tasks
+                                .lock()
+                                .iter()
+                                .map(|(task_id, (task_progress, _))| task_progress.to_prost(*task_id))
+                                .collect_vec();
+                            if !progress.is_empty() {
+                                if let Err(e) = hummock_meta_client
+                                    .report_compaction_task_progress(progress)
+                                    .await
+                                {
+                                    tracing::warn!("Failed to report compaction task progress. {}", e);
+                                }
+                            }
+                            continue;
+                        }
                         // Shutdown compactor
                         _ = &mut shutdown_rx => {
                             tracing::info!("Compactor is shutting down");
@@ -952,13 +1144,22 @@ impl Compactor {
                         Ok(Some(SubscribeCompactTasksResponse {
                             compact_task,
                             vacuum_task,
+                            cancel_compact_task,
                         })) => {
+                            if let Some(CancelCompactTask { task_id }) = cancel_compact_task {
+                                if let Some((_, task_shutdown_tx)) =
+                                    running_tasks.lock().remove(&task_id)
+                                {
+                                    let _ = task_shutdown_tx.send(());
+                                }
+                            }
                             tokio::spawn(process_task(
                                 compact_task,
                                 vacuum_task,
                                 compactor_context.clone(),
                                 sstable_store.clone(),
                                 hummock_meta_client.clone(),
+                                running_tasks.clone(),
                             ));
                         }
                         Err(e) => {
@@ -983,8 +1184,13 @@ impl Compactor {
         mut iter: impl HummockIterator<Direction = Forward>,
         gc_delete_keys: bool,
         watermark: Epoch,
+        range_tombstones: &RangeTombstones,
         mut compaction_filter: impl CompactionFilter,
+        task_progress: &TaskProgress,
     ) -> HummockResult<()> {
+        /// The read bytes are accumulated locally and added to the progress in batches.
+        const PROGRESS_BATCH_BYTES: u64 = 1 << 20;
+
         if !kr.left.is_empty() {
             iter.seek(&kr.left).await?;
         } else {
@@ -993,9 +1199,15 @@ impl Compactor {
 
         let mut last_key = BytesMut::new();
         let mut watermark_can_see_last_key = false;
+        let mut read_bytes = 0;
 
         while iter.is_valid() {
             let iter_key = iter.key();
+            read_bytes += (iter_key.len() + iter.value().encoded_len()) as u64;
+            if read_bytes >= PROGRESS_BATCH_BYTES {
+                task_progress.inc_read_bytes(read_bytes);
+                read_bytes = 0;
+            }
 
             let is_new_user_key =
                 last_key.is_empty() || !VersionedComparator::same_user_key(iter_key, &last_key);
@@ -1027,6 +1239,10 @@ impl Compactor {
                 drop = true;
             }
 
+            if !drop && range_tombstones.is_deleted(user_key(iter_key), epoch) {
+                drop = true;
+            }
+
             if !drop && compaction_filter.should_delete(iter_key) {
                 drop = true;
             }
@@ -1047,6 +1263,7 @@ impl Compactor {
 
             iter.next().await?;
         }
+        task_progress.inc_read_bytes(read_bytes);
         Ok(())
     }
 }
@@ -89,6 +89,10 @@ impl HummockError {
         HummockErrorInner::ChecksumMismatch { expected, found }.into()
     }
 
+    pub fn is_checksum_mismatch(&self) -> bool {
+        matches!(self.inner, HummockErrorInner::ChecksumMismatch { .. })
+    }
+
     pub fn meta_error(error: impl ToString) -> HummockError {
         HummockErrorInner::MetaError(error.to_string()).into()
     }
@@ -24,7 +24,7 @@ use crate::hummock::iterator::{
 };
 use crate::hummock::local_version::PinnedVersion;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{BackwardSstableIterator, HummockResult};
+use crate::hummock::{BackwardSstableIterator, HummockResult, RangeTombstones};
 use crate::monitor::StateStoreMetrics;
 
 /// [`BackwardUserIterator`] can be used by user directly.
@@ -57,6 +57,9 @@ pub struct BackwardUserIterator {
     /// Only reads values if `ts > self.min_epoch`. use for ttl
     min_epoch: Epoch,
 
+    /// Values deleted by the range tombstones are skipped.
+    range_tombstones: RangeTombstones,
+
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<Arc<PinnedVersion>>,
 }
@@ -70,7 +73,14 @@ impl BackwardUserIterator {
         >,
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     ) -> Self {
-        Self::with_epoch(iterator, key_range, Epoch::MAX, 0, None)
+        Self::with_epoch(
+            iterator,
+            key_range,
+            Epoch::MAX,
+            0,
+            RangeTombstones::new(Epoch::MAX),
+            None,
+        )
     }
 
     /// Creates [`BackwardUserIterator`] with given `read_epoch`.
@@ -81,6 +91,7 @@ impl BackwardUserIterator {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         read_epoch: u64,
         min_epoch: u64,
+        range_tombstones: RangeTombstones,
         version: Option<Arc<PinnedVersion>>,
     ) -> Self {
         Self {
@@ -93,6 +104,7 @@ impl BackwardUserIterator {
             last_delete: true,
             read_epoch,
             min_epoch,
+            range_tombstones,
             _version: version,
         }
     }
@@ -184,6 +196,10 @@ impl BackwardUserIterator {
 
                 // 1 and 2(a)
                 match self.iterator.value() {
+                    // The key is deleted by a range tombstone.
+                    HummockValue::Put(_) if self.range_tombstones.is_deleted(key, epoch) => {
+                        self.last_delete = true;
+                    }
                     HummockValue::Put(val) => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
@@ -283,11 +299,17 @@ impl DirectedUserIteratorBuilder for BackwardUserIterator {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         read_epoch: u64,
         min_epoch: u64,
+        range_tombstones: RangeTombstones,
         version: Option<Arc<PinnedVersion>>,
     ) -> DirectedUserIterator {
         let iterator = UnorderedMergeIteratorInner::new(iterator_iter, stats);
         DirectedUserIterator::Backward(BackwardUserIterator::with_epoch(
-            iterator, key_range, read_epoch, min_epoch, version,
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            range_tombstones,
+            version,
         ))
     }
 }
@@ -1153,8 +1175,14 @@ mod tests {
         let min_epoch = (TEST_KEYS_COUNT / 5) as u64;
         let mi =
             UnorderedMergeIteratorInner::new(backward_iters, Arc::new(StateStoreMetrics::unused()));
-        let mut ui =
-            BackwardUserIterator::with_epoch(mi, (Unbounded, Unbounded), u64::MAX, min_epoch, None);
+        let mut ui = BackwardUserIterator::with_epoch(
+            mi,
+            (Unbounded, Unbounded),
+            u64::MAX,
+            min_epoch,
+            RangeTombstones::new(u64::MAX),
+            None,
+        );
         ui.rewind().await.unwrap();
 
         let mut i = 0;
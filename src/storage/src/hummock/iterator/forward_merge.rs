@@ -50,9 +50,36 @@ mod test {
             gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
             Arc::new(StateStoreMetrics::unused()),
         ));
+        let mut unordered_loser_tree_iter: HummockIteratorUnion<
+            Forward,
+            UnorderedMergeIteratorInner<SstableIterator>,
+            OrderedMergeIteratorInner<SstableIterator>,
+        > = HummockIteratorUnion::First(
+            UnorderedMergeIteratorInner::new(
+                gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+                Arc::new(StateStoreMetrics::unused()),
+            )
+            .with_loser_tree_threshold(0),
+        );
+        let mut ordered_loser_tree_iter: HummockIteratorUnion<
+            Forward,
+            UnorderedMergeIteratorInner<SstableIterator>,
+            OrderedMergeIteratorInner<SstableIterator>,
+        > = HummockIteratorUnion::Second(
+            OrderedMergeIteratorInner::new(
+                gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+                Arc::new(StateStoreMetrics::unused()),
+            )
+            .with_loser_tree_threshold(0),
+        );
 
-        // Test both ordered and unordered iterators
-        let test_iters = vec![&mut unordered_iter, &mut ordered_iter];
+        // Test both ordered and unordered iterators, merging with a heap or a loser tree
+        let test_iters = vec![
+            &mut unordered_iter,
+            &mut ordered_iter,
+            &mut unordered_loser_tree_iter,
+            &mut ordered_loser_tree_iter,
+        ];
         for iter in test_iters {
             let mut i = 0;
             iter.rewind().await.unwrap();
@@ -93,9 +120,36 @@ mod test {
             gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
             Arc::new(StateStoreMetrics::unused()),
         ));
+        let mut unordered_loser_tree_iter: HummockIteratorUnion<
+            Forward,
+            UnorderedMergeIteratorInner<SstableIterator>,
+            OrderedMergeIteratorInner<SstableIterator>,
+        > = HummockIteratorUnion::First(
+            UnorderedMergeIteratorInner::new(
+                gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+                Arc::new(StateStoreMetrics::unused()),
+            )
+            .with_loser_tree_threshold(0),
+        );
+        let mut ordered_loser_tree_iter: HummockIteratorUnion<
+            Forward,
+            UnorderedMergeIteratorInner<SstableIterator>,
+            OrderedMergeIteratorInner<SstableIterator>,
+        > = HummockIteratorUnion::Second(
+            OrderedMergeIteratorInner::new(
+                gen_merge_iterator_interleave_test_sstable_iters(TEST_KEYS_COUNT, 3).await,
+                Arc::new(StateStoreMetrics::unused()),
+            )
+            .with_loser_tree_threshold(0),
+        );
 
-        // Test both ordered and unordered iterators
-        let test_iters = vec![&mut unordered_iter, &mut ordered_iter];
+        // Test both ordered and unordered iterators, merging with a heap or a loser tree
+        let test_iters = vec![
+            &mut unordered_iter,
+            &mut ordered_iter,
+            &mut unordered_loser_tree_iter,
+            &mut ordered_loser_tree_iter,
+        ];
 
         for iter in test_iters {
             // right edge case
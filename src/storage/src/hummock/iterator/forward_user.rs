@@ -26,7 +26,7 @@ use crate::hummock::local_version::PinnedVersion;
 use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterator;
 use crate::hummock::shared_buffer::SharedBufferIteratorType;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{HummockResult, SstableIterator, SstableIteratorType};
+use crate::hummock::{HummockResult, RangeTombstones, SstableIterator, SstableIteratorType};
 use crate::monitor::StateStoreMetrics;
 
 pub enum DirectedUserIterator {
@@ -59,6 +59,7 @@ pub trait DirectedUserIteratorBuilder {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         read_epoch: u64,
         min_epoch: u64,
+        range_tombstones: RangeTombstones,
         version: Option<Arc<PinnedVersion>>,
     ) -> DirectedUserIterator;
 }
@@ -136,6 +137,9 @@ pub struct UserIterator {
     /// Only reads values if `ts > self.min_epoch`. use for ttl
     min_epoch: Epoch,
 
+    /// Values deleted by the range tombstones are skipped.
+    range_tombstones: RangeTombstones,
+
     /// Ensures the SSTs needed by `iterator` won't be vacuumed.
     _version: Option<Arc<PinnedVersion>>,
 }
@@ -148,7 +152,14 @@ impl UserIterator {
         iterator: UnorderedMergeIteratorInner<UserIteratorPayloadType<Forward, SstableIterator>>,
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
     ) -> Self {
-        Self::new(iterator, key_range, Epoch::MAX, 0, None)
+        Self::new(
+            iterator,
+            key_range,
+            Epoch::MAX,
+            0,
+            RangeTombstones::new(Epoch::MAX),
+            None,
+        )
     }
 
     #[cfg(test)]
@@ -158,7 +169,14 @@ impl UserIterator {
         read_epoch: u64,
         min_epoch: u64,
     ) -> Self {
-        Self::new(iterator, key_range, read_epoch, min_epoch, None)
+        Self::new(
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            RangeTombstones::new(read_epoch),
+            None,
+        )
     }
 
     /// Create [`UserIterator`] with given `read_epoch`.
@@ -167,6 +185,7 @@ impl UserIterator {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         read_epoch: u64,
         min_epoch: u64,
+        range_tombstones: RangeTombstones,
         version: Option<Arc<PinnedVersion>>,
     ) -> Self {
         Self {
@@ -177,6 +196,7 @@ impl UserIterator {
             last_val: Vec::new(),
             read_epoch,
             min_epoch,
+            range_tombstones,
             _version: version,
         }
     }
@@ -202,6 +222,8 @@ impl UserIterator {
 
                 // handle delete operation
                 match self.iterator.value() {
+                    // The key is deleted by a range tombstone.
+                    HummockValue::Put(_) if self.range_tombstones.is_deleted(key, epoch) => {}
                     HummockValue::Put(val) => {
                         self.last_val.clear();
                         self.last_val.extend_from_slice(val);
@@ -309,11 +331,17 @@ impl DirectedUserIteratorBuilder for UserIterator {
         key_range: (Bound<Vec<u8>>, Bound<Vec<u8>>),
         read_epoch: u64,
         min_epoch: u64,
+        range_tombstones: RangeTombstones,
         version: Option<Arc<PinnedVersion>>,
     ) -> DirectedUserIterator {
         let iterator = UnorderedMergeIteratorInner::new(iterator_iter, stats);
         DirectedUserIterator::Forward(Self::new(
-            iterator, key_range, read_epoch, min_epoch, version,
+            iterator,
+            key_range,
+            read_epoch,
+            min_epoch,
+            range_tombstones,
+            version,
         ))
     }
 }
@@ -337,6 +365,7 @@ mod tests {
     };
     use crate::hummock::test_utils::create_small_table_cache;
     use crate::hummock::value::HummockValue;
+    use crate::hummock::RangeTombstone;
     use crate::monitor::StateStoreMetrics;
 
     #[tokio::test]
@@ -552,6 +581,56 @@ mod tests {
         assert!(!ui.is_valid());
     }
 
+    #[tokio::test]
+    async fn test_range_tombstone() {
+        let sstable_store = mock_sstable_store();
+
+        // key=[idx, epoch], value
+        let kv_pairs = vec![
+            (1, 100, HummockValue::put(iterator_test_value_of(1))),
+            (2, 300, HummockValue::put(iterator_test_value_of(2))),
+            (2, 100, HummockValue::put(iterator_test_value_of(2))),
+            (3, 100, HummockValue::put(iterator_test_value_of(3))),
+        ];
+        let table0 =
+            gen_iterator_test_sstable_from_kv_pair(0, kv_pairs, sstable_store.clone()).await;
+
+        let read_options = Arc::new(SstableIteratorReadOptions::default());
+        let cache = create_small_table_cache();
+        let iters = vec![HummockIteratorUnion::Fourth(SstableIterator::create(
+            cache.insert(table0.id, table0.id, 1, Box::new(table0)),
+            sstable_store.clone(),
+            read_options,
+        ))];
+
+        // Deletes `[key 1, key 3)` at epoch 200.
+        let mut range_tombstones = RangeTombstones::new(Epoch::MAX);
+        range_tombstones.extend(&[RangeTombstone::new(
+            user_key(&iterator_test_key_of(1)).to_vec(),
+            user_key(&iterator_test_key_of(3)).to_vec(),
+            200,
+        )]);
+        let mi = UnorderedMergeIteratorInner::new(iters, Arc::new(StateStoreMetrics::unused()));
+        let mut ui = UserIterator::new(
+            mi,
+            (Unbounded, Unbounded),
+            Epoch::MAX,
+            0,
+            range_tombstones,
+            None,
+        );
+        ui.rewind().await.unwrap();
+
+        // Key 1 is deleted, and key 2 is written again after the tombstone.
+        for idx in [2, 3] {
+            assert!(ui.is_valid());
+            assert_eq!(ui.key(), user_key(iterator_test_key_of(idx).as_slice()));
+            assert_eq!(ui.value(), iterator_test_value_of(idx));
+            ui.next().await.unwrap();
+        }
+        assert!(!ui.is_valid());
+    }
+
     // left..=end
     #[tokio::test]
     async fn test_range_inclusive() {
@@ -0,0 +1,179 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// A loser tree, a.k.a. tournament tree, over a fixed number of slots, always popping the greatest
+/// node like [`std::collections::BinaryHeap`].
+///
+/// Replacing the top node takes one comparison at each level from its leaf to the root, while a
+/// binary heap takes two at each level when sifting it down, so it's cheaper for a wide merge.
+pub struct LoserTree<N: Ord> {
+    /// Nodes at the leaves, with `None` for a vacant slot, which loses to any node.
+    leaves: Vec<Option<N>>,
+
+    /// `losers[0]` is the leaf of the winner, and `losers[i]` is the leaf that lost at the
+    /// internal node `i`, whose children are `2i` and `2i + 1`. Leaf `j` is at `j + leaves.len()`.
+    losers: Vec<usize>,
+
+    /// Vacant leaves, for pushing nodes back.
+    vacant: Vec<usize>,
+}
+
+impl<N: Ord> LoserTree<N> {
+    pub fn new(nodes: impl IntoIterator<Item = N>) -> Self {
+        let leaves: Vec<Option<N>> = nodes.into_iter().map(Some).collect();
+        let size = leaves.len();
+        let mut losers = vec![0; size.max(1)];
+        if size > 1 {
+            let mut winners = vec![0; 2 * size];
+            for (i, winner) in winners[size..].iter_mut().enumerate() {
+                *winner = i;
+            }
+            for i in (1..size).rev() {
+                let (left, right) = (winners[2 * i], winners[2 * i + 1]);
+                if Self::beats(&leaves, left, right) {
+                    winners[i] = left;
+                    losers[i] = right;
+                } else {
+                    winners[i] = right;
+                    losers[i] = left;
+                }
+            }
+            losers[0] = winners[1];
+        }
+        Self {
+            leaves,
+            losers,
+            vacant: vec![],
+        }
+    }
+
+    /// Whether the node at leaf `a` wins over the one at leaf `b`.
+    fn beats(leaves: &[Option<N>], a: usize, b: usize) -> bool {
+        match (&leaves[a], &leaves[b]) {
+            (Some(a), Some(b)) => a >= b,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Replays the matches from `leaf` to the root after its node changes.
+    fn replay(&mut self, leaf: usize) {
+        let mut winner = leaf;
+        let mut i = (leaf + self.leaves.len()) / 2;
+        while i > 0 {
+            if Self::beats(&self.leaves, self.losers[i], winner) {
+                std::mem::swap(&mut self.losers[i], &mut winner);
+            }
+            i /= 2;
+        }
+        self.losers[0] = winner;
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len() - self.vacant.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn peek(&self) -> Option<&N> {
+        self.leaves.get(self.losers[0])?.as_ref()
+    }
+
+    /// Returns the greatest node to modify. [`LoserTree::update_top`] must be called afterwards.
+    pub fn peek_mut(&mut self) -> Option<&mut N> {
+        self.leaves.get_mut(self.losers[0])?.as_mut()
+    }
+
+    /// Restores the order after the greatest node is modified.
+    pub fn update_top(&mut self) {
+        if !self.leaves.is_empty() {
+            self.replay(self.losers[0]);
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<N> {
+        let top = self.losers[0];
+        let node = self.leaves.get_mut(top)?.take()?;
+        self.vacant.push(top);
+        self.replay(top);
+        Some(node)
+    }
+
+    /// Pushes a node to a vacant leaf. Only as many nodes as popped can be pushed.
+    pub fn push(&mut self, node: N) {
+        let leaf = self.vacant.pop().expect("no vacant leaf in the loser tree");
+        self.leaves[leaf] = Some(node);
+        self.replay(leaf);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &N> {
+        self.leaves.iter().flatten()
+    }
+
+    pub fn into_vec(self) -> Vec<N> {
+        self.leaves.into_iter().flatten().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BinaryHeap;
+
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_loser_tree_pop() {
+        for size in 0..20 {
+            let nodes = (0..size).map(|i| i * 7 % 13).collect::<Vec<_>>();
+            let mut tree = LoserTree::new(nodes.clone());
+            let mut heap = BinaryHeap::from(nodes);
+            assert_eq!(tree.len(), heap.len());
+            while let Some(node) = heap.pop() {
+                assert_eq!(tree.peek(), Some(&node));
+                assert_eq!(tree.pop(), Some(node));
+            }
+            assert!(tree.is_empty());
+            assert_eq!(tree.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_loser_tree_update() {
+        let mut rng = rand::thread_rng();
+        let nodes = (0..33).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>();
+        let mut tree = LoserTree::new(nodes.clone());
+        let mut heap = BinaryHeap::from(nodes);
+        for _ in 0..1000 {
+            assert_eq!(tree.peek(), heap.peek());
+            let delta = rng.gen_range(0..10);
+            *tree.peek_mut().unwrap() -= delta;
+            tree.update_top();
+            *heap.peek_mut().unwrap() -= delta;
+
+            if rng.gen_bool(0.1) {
+                let node = tree.pop().unwrap();
+                assert_eq!(Some(node), heap.pop());
+                tree.push(node + 1);
+                heap.push(node + 1);
+            }
+        }
+        let mut tree_nodes = tree.into_vec();
+        tree_nodes.sort_unstable();
+        assert_eq!(tree_nodes, heap.into_sorted_vec());
+    }
+}
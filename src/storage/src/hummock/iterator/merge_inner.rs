@@ -18,8 +18,10 @@ use std::collections::{BinaryHeap, LinkedList};
 use std::future::Future;
 use std::sync::Arc;
 
+use itertools::Either;
 use risingwave_hummock_sdk::VersionedComparator;
 
+use super::loser_tree::LoserTree;
 use crate::hummock::iterator::{DirectionEnum, HummockIterator, HummockIteratorDirection};
 use crate::hummock::value::HummockValue;
 use crate::hummock::HummockResult;
@@ -95,13 +97,88 @@ impl<I: HummockIterator> PartialEq for Node<I, OrderedNodeExtra> {
     }
 }
 
+/// The default number of valid iterators above which a loser tree is used for merge sort.
+const DEFAULT_LOSER_TREE_THRESHOLD: usize = 16;
+
+/// The queue of iterators for merge sort, always popping the one with the smallest key.
+enum NodeQueue<N: Ord> {
+    Heap(BinaryHeap<N>),
+    LoserTree(LoserTree<N>),
+}
+
+impl<N: Ord> Default for NodeQueue<N> {
+    fn default() -> Self {
+        Self::Heap(BinaryHeap::new())
+    }
+}
+
+impl<N: Ord> NodeQueue<N> {
+    fn new(nodes: Vec<N>, loser_tree_threshold: usize) -> Self {
+        if nodes.len() > loser_tree_threshold {
+            Self::LoserTree(LoserTree::new(nodes))
+        } else {
+            Self::Heap(nodes.into())
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Heap(heap) => heap.is_empty(),
+            Self::LoserTree(tree) => tree.is_empty(),
+        }
+    }
+
+    fn peek(&self) -> Option<&N> {
+        match self {
+            Self::Heap(heap) => heap.peek(),
+            Self::LoserTree(tree) => tree.peek(),
+        }
+    }
+
+    fn pop(&mut self) -> Option<N> {
+        match self {
+            Self::Heap(heap) => heap.pop(),
+            Self::LoserTree(tree) => tree.pop(),
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn push(&mut self, node: N) {
+        match self {
+            Self::Heap(heap) => heap.push(node),
+            Self::LoserTree(tree) => tree.push(node),
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &N> {
+        match self {
+            Self::Heap(heap) => Either::Left(heap.iter()),
+            Self::LoserTree(tree) => Either::Right(tree.iter()),
+        }
+    }
+
+    fn into_vec(self) -> Vec<N> {
+        match self {
+            Self::Heap(heap) => heap.into_vec(),
+            Self::LoserTree(tree) => tree.into_vec(),
+        }
+    }
+}
+
 /// Iterates on multiple iterators, a.k.a. `MergeIterator`.
 pub struct MergeIteratorInner<I: HummockIterator, NE: NodeExtraOrderInfo> {
     /// Invalid or non-initialized iterators.
     unused_iters: LinkedList<Node<I, NE>>,
 
-    /// The heap for merge sort.
-    heap: BinaryHeap<Node<I, NE>>,
+    /// The queue for merge sort.
+    heap: NodeQueue<Node<I, NE>>,
+
+    /// A loser tree instead of a binary heap is used for merge sort if there are more valid
+    /// iterators than this.
+    loser_tree_threshold: usize,
 
     /// Statistics.
     stats: Arc<StateStoreMetrics>,
@@ -122,15 +199,23 @@ impl<I: HummockIterator> OrderedMergeIteratorInner<I> {
                     extra_order_info: i,
                 })
                 .collect(),
-            heap: BinaryHeap::new(),
+            heap: NodeQueue::default(),
+            loser_tree_threshold: DEFAULT_LOSER_TREE_THRESHOLD,
             stats,
         }
     }
 }
 
 impl<I: HummockIterator, NE: NodeExtraOrderInfo> MergeIteratorInner<I, NE> {
+    /// Sets the number of valid iterators above which a loser tree is used for merge sort, which
+    /// takes effect on the next `rewind` or `seek`.
+    pub fn with_loser_tree_threshold(mut self, loser_tree_threshold: usize) -> Self {
+        self.loser_tree_threshold = loser_tree_threshold;
+        self
+    }
+
     fn collect_local_statistic_impl(&self, stats: &mut StoreLocalStatistic) {
-        for node in &self.heap {
+        for node in self.heap.iter() {
             node.iter.collect_local_statistic(stats);
         }
         for node in &self.unused_iters {
@@ -153,7 +238,8 @@ impl<I: HummockIterator> UnorderedMergeIteratorInner<I> {
                     extra_order_info: (),
                 })
                 .collect(),
-            heap: BinaryHeap::new(),
+            heap: NodeQueue::default(),
+            loser_tree_threshold: DEFAULT_LOSER_TREE_THRESHOLD,
             stats,
         }
     }
@@ -165,7 +251,8 @@ where
 {
     /// Moves all iterators from the `heap` to the linked list.
     fn reset_heap(&mut self) {
-        self.unused_iters.extend(self.heap.drain());
+        self.unused_iters
+            .extend(std::mem::take(&mut self.heap).into_vec());
     }
 
     /// After some iterators in `unused_iterators` are sought or rewound, calls this function
@@ -173,10 +260,12 @@ where
     fn build_heap(&mut self) {
         assert!(self.heap.is_empty());
 
-        self.heap = self
-            .unused_iters
-            .drain_filter(|i| i.iter.is_valid())
-            .collect();
+        self.heap = NodeQueue::new(
+            self.unused_iters
+                .drain_filter(|i| i.iter.is_valid())
+                .collect(),
+            self.loser_tree_threshold,
+        );
     }
 }
 
@@ -198,10 +287,10 @@ impl<I: HummockIterator> MergeIteratorNext for OrderedMergeIteratorInner<I> {
             let mut popped_nodes = vec![];
 
             // Take all nodes with the same current key as the top_node out of the heap.
-            while let Some(next_node) = self.heap.peek_mut() {
+            while let Some(next_node) = self.heap.peek() {
                 match VersionedComparator::compare_key(top_node.iter.key(), next_node.iter.key()) {
                     Ordering::Equal => {
-                        popped_nodes.push(PeekMut::pop(next_node));
+                        popped_nodes.push(self.heap.pop().unwrap());
                     }
                     _ => break,
                 }
@@ -209,9 +298,8 @@ impl<I: HummockIterator> MergeIteratorNext for OrderedMergeIteratorInner<I> {
 
             popped_nodes.push(top_node);
 
-            // WARNING: within scope of BinaryHeap::PeekMut, we must carefully handle all places of
-            // return. Once the iterator enters an invalid state, we should remove it from heap
-            // before returning.
+            // WARNING: we must carefully handle all places of return. Once the iterator enters an
+            // invalid state, we should remove it from heap before returning.
 
             // Put the popped nodes back to the heap if valid or unused_iters if invalid.
             for mut node in popped_nodes {
@@ -242,7 +330,27 @@ impl<I: HummockIterator> MergeIteratorNext for UnorderedMergeIteratorInner<I> {
 
     fn next_inner(&mut self) -> Self::HummockResultFuture<'_> {
         async {
-            let mut node = self.heap.peek_mut().expect("no inner iter");
+            let heap = match &mut self.heap {
+                NodeQueue::Heap(heap) => heap,
+                NodeQueue::LoserTree(tree) => {
+                    let node = tree.peek_mut().expect("no inner iter");
+                    if let Err(e) = node.iter.next().await {
+                        // If the iterator returns error, we should clear the tree, so that this
+                        // iterator becomes invalid.
+                        self.heap.clear();
+                        return Err(e);
+                    }
+                    if !node.iter.is_valid() {
+                        // Put back to `unused_iters`
+                        let node = tree.pop().unwrap();
+                        self.unused_iters.push_back(node);
+                    } else {
+                        tree.update_top();
+                    }
+                    return Ok(());
+                }
+            };
+            let mut node = heap.peek_mut().expect("no inner iter");
 
             // WARNING: within scope of BinaryHeap::PeekMut, we must carefully handle all places of
             // return. Once the iterator enters an invalid state, we should remove it from heap
@@ -254,7 +362,7 @@ impl<I: HummockIterator> MergeIteratorNext for UnorderedMergeIteratorInner<I> {
                     // If the iterator returns error, we should clear the heap, so that this
                     // iterator becomes invalid.
                     PeekMut::pop(node);
-                    self.heap.clear();
+                    heap.clear();
                     return Err(e);
                 }
             }
@@ -31,6 +31,7 @@ pub use backward_user::*;
 mod forward_merge;
 pub use forward_merge::*;
 pub mod forward_user;
+mod loser_tree;
 mod merge_inner;
 pub use forward_user::*;
 pub use merge_inner::{OrderedMergeIteratorInner, UnorderedMergeIteratorInner};
@@ -29,7 +29,7 @@ use crate::hummock::{
     HummockValue, Sstable, SstableBuilderOptions, SstableIterator, SstableIteratorType,
     SstableStoreRef,
 };
-use crate::monitor::ObjectStoreMetrics;
+use crate::monitor::{ObjectStoreMetrics, StateStoreMetrics};
 
 /// `assert_eq` two `Vec<u8>` with human-readable format.
 #[macro_export]
@@ -58,7 +58,14 @@ pub fn mock_sstable_store() -> SstableStoreRef {
 
 pub fn mock_sstable_store_with_object_store(store: ObjectStoreRef) -> SstableStoreRef {
     let path = "test".to_string();
-    Arc::new(SstableStore::new(store, path, 64 << 20, 64 << 20))
+    Arc::new(SstableStore::new(
+        store,
+        path,
+        64 << 20,
+        64 << 20,
+        true,
+        Arc::new(StateStoreMetrics::unused()),
+    ))
 }
 
 /// Generates keys like `key_test_00002` with epoch 233.
@@ -47,7 +47,7 @@ use crate::hummock::shared_buffer::UploadTaskType::{FlushWriteBatch, SyncEpoch};
 use crate::hummock::shared_buffer::{OrderIndex, SharedBufferEvent, WriteRequest};
 use crate::hummock::utils::validate_table_key_range;
 use crate::hummock::{
-    HummockEpoch, HummockError, HummockResult, HummockVersionId, INVALID_VERSION_ID,
+    HummockEpoch, HummockError, HummockResult, HummockVersionId, RangeTombstone, INVALID_VERSION_ID,
 };
 use crate::monitor::StateStoreMetrics;
 use crate::storage_value::StorageValue;
@@ -369,6 +369,33 @@ impl LocalVersionManager {
             compaction_group_id,
             table_id,
         );
+        self.write_shared_buffer_batch(epoch, batch, is_remote_batch)
+            .await
+    }
+
+    /// Writes a range tombstone to the shared buffer, at the epoch of the tombstone.
+    pub async fn write_range_tombstone(
+        &self,
+        compaction_group_id: CompactionGroupId,
+        range_tombstone: RangeTombstone,
+        table_id: u32,
+    ) -> HummockResult<usize> {
+        let epoch = range_tombstone.epoch;
+        let batch = SharedBufferBatch::new_range_tombstone(
+            range_tombstone,
+            self.buffer_tracker.buffer_event_sender.clone(),
+            compaction_group_id,
+            table_id,
+        );
+        self.write_shared_buffer_batch(epoch, batch, false).await
+    }
+
+    async fn write_shared_buffer_batch(
+        &self,
+        epoch: HummockEpoch,
+        batch: SharedBufferBatch,
+        is_remote_batch: bool,
+    ) -> HummockResult<usize> {
         let batch_size = batch.size();
         if self.buffer_tracker.try_write(batch_size) {
             self.write_shared_buffer_inner(epoch, batch, is_remote_batch);
@@ -60,7 +60,7 @@ use risingwave_hummock_sdk::slice_transform::SliceTransformImpl;
 use value::*;
 
 use self::iterator::HummockIterator;
-use self::key::user_key;
+use self::key::{get_epoch, user_key};
 pub use self::sstable_store::*;
 pub use self::state_store::HummockStateStoreIter;
 use super::monitor::StateStoreMetrics;
@@ -87,6 +87,9 @@ pub struct HummockStorage {
     stats: Arc<StateStoreMetrics>,
 
     compaction_group_client: Arc<dyn CompactionGroupClient>,
+
+    /// Slice transforms of the tables, to extract the bloom filter keys when reading SSTs.
+    table_id_to_slice_transform: Arc<RwLock<HashMap<u32, SliceTransformImpl>>>,
 }
 
 impl HummockStorage {
@@ -129,7 +132,7 @@ impl HummockStorage {
             stats.clone(),
             hummock_meta_client.clone(),
             write_conflict_detector,
-            table_id_to_slice_transform,
+            table_id_to_slice_transform.clone(),
         )
         .await;
 
@@ -140,6 +143,7 @@ impl HummockStorage {
             sstable_store,
             stats,
             compaction_group_client,
+            table_id_to_slice_transform,
         };
         Ok(instance)
     }
@@ -149,11 +153,13 @@ impl HummockStorage {
         sstable: TableHolder,
         internal_key: &[u8],
         key: &[u8],
+        range_tombstones: &RangeTombstones,
+        sliced_key: Option<&[u8]>,
         _read_options: &ReadOptions,
         stats: &mut StoreLocalStatistic,
     ) -> HummockResult<Option<Option<Bytes>>> {
         // TODO: via read_options to determine whether to check bloom_filter next PR
-        if sstable.value().surely_not_have_user_key(key) {
+        if sstable.value().surely_not_have_user_key(key, sliced_key) {
             stats.bloom_filter_true_negative_count += 1;
             return Ok(None);
         }
@@ -175,6 +181,7 @@ impl HummockStorage {
         // Iterator gets us the key, we tell if it's the key we want
         // or key next to it.
         let value = match user_key(iter.key()) == key {
+            true if range_tombstones.is_deleted(key, get_epoch(iter.key())) => Some(None),
             true => Some(iter.value().into_user_value().map(Bytes::copy_from_slice)),
             false => None,
         };
@@ -29,12 +29,16 @@ use crate::hummock::iterator::{
 use crate::hummock::shared_buffer::SharedBufferEvent;
 use crate::hummock::shared_buffer::SharedBufferEvent::BufferRelease;
 use crate::hummock::value::HummockValue;
-use crate::hummock::{key, HummockEpoch, HummockResult};
+use crate::hummock::{key, HummockEpoch, HummockResult, RangeTombstone};
 
 pub(crate) type SharedBufferItem = (Bytes, HummockValue<Bytes>);
 
 pub(crate) struct SharedBufferBatchInner {
     payload: Vec<SharedBufferItem>,
+    /// A batch has either key-value pairs in the payload or a range tombstone.
+    range_tombstone: Option<RangeTombstone>,
+    /// The full key range of the range tombstone.
+    range_tombstone_key_range: (Vec<u8>, Vec<u8>),
     size: usize,
     buffer_release_notifier: mpsc::UnboundedSender<SharedBufferEvent>,
 }
@@ -62,15 +66,15 @@ impl Debug for SharedBufferBatchInner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "SharedBufferBatchInner {{ payload: {:?}, size: {} }}",
-            self.payload, self.size
+            "SharedBufferBatchInner {{ payload: {:?}, range_tombstone: {:?}, size: {} }}",
+            self.payload, self.range_tombstone, self.size
         )
     }
 }
 
 impl PartialEq for SharedBufferBatchInner {
     fn eq(&self, other: &Self) -> bool {
-        self.payload == other.payload
+        self.payload == other.payload && self.range_tombstone == other.range_tombstone
     }
 }
 
@@ -101,6 +105,8 @@ impl SharedBufferBatch {
         Self {
             inner: Arc::new(SharedBufferBatchInner {
                 payload: sorted_items,
+                range_tombstone: None,
+                range_tombstone_key_range: Default::default(),
                 size,
                 buffer_release_notifier,
             }),
@@ -110,6 +116,33 @@ impl SharedBufferBatch {
         }
     }
 
+    /// Creates a batch with only a range tombstone, which is written at the epoch of the
+    /// tombstone.
+    pub fn new_range_tombstone(
+        range_tombstone: RangeTombstone,
+        buffer_release_notifier: mpsc::UnboundedSender<SharedBufferEvent>,
+        compaction_group_id: CompactionGroupId,
+        table_id: u32,
+    ) -> Self {
+        let size = range_tombstone.start_user_key.len() + range_tombstone.end_user_key.len();
+        let range_tombstone_key_range = (
+            range_tombstone.smallest_key(),
+            range_tombstone.largest_key(),
+        );
+        Self {
+            epoch: range_tombstone.epoch,
+            inner: Arc::new(SharedBufferBatchInner {
+                payload: vec![],
+                range_tombstone: Some(range_tombstone),
+                range_tombstone_key_range,
+                size,
+                buffer_release_notifier,
+            }),
+            compaction_group_id,
+            table_id,
+        }
+    }
+
     pub fn measure_batch_size(batches: &[SharedBufferItem]) -> usize {
         // size = Sum(length of full key + length of user value)
         batches
@@ -153,20 +186,30 @@ impl SharedBufferBatch {
         &self.inner
     }
 
+    pub fn range_tombstone(&self) -> Option<&RangeTombstone> {
+        self.inner.range_tombstone.as_ref()
+    }
+
     pub fn start_key(&self) -> &[u8] {
-        &self.inner.first().unwrap().0
+        match self.inner.first() {
+            Some((key, _)) => key,
+            None => &self.inner.range_tombstone_key_range.0,
+        }
     }
 
     pub fn end_key(&self) -> &[u8] {
-        &self.inner.last().unwrap().0
+        match self.inner.last() {
+            Some((key, _)) => key,
+            None => &self.inner.range_tombstone_key_range.1,
+        }
     }
 
     pub fn start_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.first().unwrap().0)
+        key::user_key(self.start_key())
     }
 
     pub fn end_user_key(&self) -> &[u8] {
-        key::user_key(&self.inner.last().unwrap().0)
+        key::user_key(self.end_key())
     }
 
     pub fn epoch(&self) -> u64 {
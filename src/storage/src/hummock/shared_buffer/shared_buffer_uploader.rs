@@ -131,6 +131,8 @@ impl SharedBufferUploader {
                         }),
                         file_size: sst.meta.estimated_size as u64,
                         table_ids,
+                        table_key_counts: sst.meta.table_key_counts_map(),
+                        range_tombstone_count: sst.meta.range_tombstones.len() as u32,
                     },
                 )
             })
@@ -36,10 +36,12 @@ pub struct Block {
 }
 
 impl Block {
-    pub fn decode(buf: Bytes) -> HummockResult<Self> {
-        // Verify checksum.
-        let xxhash64_checksum = (&buf[buf.len() - 8..]).get_u64_le();
-        xxhash64_verify(&buf[..buf.len() - 8], xxhash64_checksum)?;
+    /// Decodes a block, verifying its checksum first if `verify_checksum` is set.
+    pub fn decode(buf: Bytes, verify_checksum: bool) -> HummockResult<Self> {
+        if verify_checksum {
+            let xxhash64_checksum = (&buf[buf.len() - 8..]).get_u64_le();
+            xxhash64_verify(&buf[..buf.len() - 8], xxhash64_checksum)?;
+        }
 
         // Decompress.
         let compression = CompressionAlgorithm::decode(&mut &buf[buf.len() - 9..buf.len() - 8])?;
@@ -344,7 +346,7 @@ mod tests {
         builder.add(&full_key(b"k3", 3), b"v03");
         builder.add(&full_key(b"k4", 4), b"v04");
         let buf = builder.build();
-        let block = Box::new(Block::decode(buf).unwrap());
+        let block = Box::new(Block::decode(buf, true).unwrap());
         let mut bi = BlockIterator::new(BlockHolder::from_owned_block(block));
 
         bi.seek_to_first();
@@ -388,7 +390,7 @@ mod tests {
         builder.add(&full_key(b"k3", 3), b"v03");
         builder.add(&full_key(b"k4", 4), b"v04");
         let buf = builder.build();
-        let block = Box::new(Block::decode(buf).unwrap());
+        let block = Box::new(Block::decode(buf, true).unwrap());
         let mut bi = BlockIterator::new(BlockHolder::from_owned_block(block));
 
         bi.seek_to_first();
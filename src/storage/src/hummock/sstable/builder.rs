@@ -12,16 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::BTreeSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use risingwave_common::config::StorageConfig;
 use risingwave_hummock_sdk::key::{get_table_id, user_key};
+use risingwave_hummock_sdk::slice_transform::SliceTransformImpl;
+use risingwave_hummock_sdk::VersionedComparator;
 
 use super::bloom::Bloom;
 use super::utils::CompressionAlgorithm;
 use super::{
-    BlockBuilder, BlockBuilderOptions, BlockMeta, SstableMeta, DEFAULT_BLOCK_SIZE,
+    BlockBuilder, BlockBuilderOptions, BlockMeta, RangeTombstone, SstableMeta, DEFAULT_BLOCK_SIZE,
     DEFAULT_ENTRY_SIZE, DEFAULT_RESTART_INTERVAL, VERSION,
 };
 use crate::hummock::value::HummockValue;
@@ -78,10 +81,19 @@ pub struct SstableBuilder {
     block_metas: Vec<BlockMeta>,
     /// `table_id` of added keys.
     table_ids: BTreeSet<u32>,
-    /// Hashes of user keys.
+    /// Hashes of user keys, or of the keys extracted by the slice transforms.
     user_key_hashes: Vec<u32>,
+    /// Slice transforms extracting the bloom filter keys, by table id. The user keys of the other
+    /// tables are hashed as is.
+    slice_transforms: HashMap<u32, SliceTransformImpl>,
+    /// `table_id` of the keys extracted by the slice transforms.
+    sliced_table_ids: BTreeSet<u32>,
+    /// The number of put keys of each table.
+    table_key_counts: BTreeMap<u32, u32>,
     /// Last added full key.
     last_full_key: Bytes,
+    /// Range tombstones, which are stored in the meta.
+    range_tombstones: Vec<RangeTombstone>,
     key_count: usize,
     sstable_id: u64,
 }
@@ -95,12 +107,26 @@ impl SstableBuilder {
             block_metas: Vec::with_capacity(options.capacity / options.block_capacity + 1),
             table_ids: BTreeSet::new(),
             user_key_hashes: Vec::with_capacity(options.capacity / DEFAULT_ENTRY_SIZE + 1),
+            slice_transforms: HashMap::new(),
+            sliced_table_ids: BTreeSet::new(),
+            table_key_counts: BTreeMap::new(),
             last_full_key: Bytes::default(),
+            range_tombstones: vec![],
             key_count: 0,
             sstable_id,
         }
     }
 
+    /// Builds the bloom filter over the keys extracted by `slice_transforms` for the tables they
+    /// are registered for.
+    pub fn with_slice_transforms(
+        mut self,
+        slice_transforms: HashMap<u32, SliceTransformImpl>,
+    ) -> Self {
+        self.slice_transforms = slice_transforms;
+        self
+    }
+
     /// Add kv pair to sstable.
     pub fn add(&mut self, full_key: &[u8], value: HummockValue<&[u8]>) {
         // Rotate block builder if the previous one has been built.
@@ -123,15 +149,27 @@ impl SstableBuilder {
         // TODO: refine me
         let mut raw_value = BytesMut::default();
         value.encode(&mut raw_value);
-        if let Some(table_id) = get_table_id(full_key) {
+        let table_id = get_table_id(full_key);
+        if let Some(table_id) = table_id {
             self.table_ids.insert(table_id);
+            if !value.is_delete() {
+                *self.table_key_counts.entry(table_id).or_default() += 1;
+            }
         }
         let raw_value = raw_value.freeze();
 
         block_builder.add(full_key, &raw_value);
 
         let user_key = user_key(full_key);
-        self.user_key_hashes.push(farmhash::fingerprint32(user_key));
+        let bloom_key = match table_id.and_then(|id| self.slice_transforms.get_mut(&id)) {
+            Some(SliceTransformImpl::FullKey(_)) | None => user_key,
+            Some(slice_transform) => {
+                self.sliced_table_ids.insert(table_id.unwrap());
+                slice_transform.transform(user_key)
+            }
+        };
+        self.user_key_hashes
+            .push(farmhash::fingerprint32(bloom_key));
 
         if self.last_full_key.is_empty() {
             self.block_metas.last_mut().unwrap().smallest_key = full_key.to_vec();
@@ -144,6 +182,14 @@ impl SstableBuilder {
         self.key_count += 1;
     }
 
+    /// Add a range tombstone to sstable. The key range of the sstable is extended to cover it.
+    pub fn add_range_tombstone(&mut self, range_tombstone: RangeTombstone) {
+        if let Some(table_id) = get_table_id(&range_tombstone.start_user_key) {
+            self.table_ids.insert(table_id);
+        }
+        self.range_tombstones.push(range_tombstone);
+    }
+
     /// Finish building sst.
     ///
     /// Unlike most LSM-Tree implementations, sstable meta and data are encoded separately.
@@ -156,15 +202,41 @@ impl SstableBuilder {
     /// ```plain
     /// | Block 0 | ... | Block N-1 | N (4B) |
     /// ```
+    ///
+    /// An sstable may have no blocks at all if only range tombstones are added.
     pub fn finish(mut self) -> (u64, Bytes, SstableMeta, Vec<u32>) {
-        let smallest_key = self.block_metas[0].smallest_key.clone();
-        let largest_key = self.last_full_key.to_vec();
+        assert!(
+            !self.block_metas.is_empty() || !self.range_tombstones.is_empty(),
+            "empty sstable"
+        );
+        let mut smallest_key = self
+            .block_metas
+            .first()
+            .map(|block_meta| block_meta.smallest_key.clone())
+            .unwrap_or_default();
+        let mut largest_key = self.last_full_key.to_vec();
+        for range_tombstone in &self.range_tombstones {
+            let start_key = range_tombstone.smallest_key();
+            if smallest_key.is_empty()
+                || VersionedComparator::compare_key(&start_key, &smallest_key) == Ordering::Less
+            {
+                smallest_key = start_key;
+            }
+            let end_key = range_tombstone.largest_key();
+            if largest_key.is_empty()
+                || VersionedComparator::compare_key(&end_key, &largest_key) == Ordering::Greater
+            {
+                largest_key = end_key;
+            }
+        }
         self.build_block();
         self.buf.put_u32_le(self.block_metas.len() as u32);
 
         let meta = SstableMeta {
             block_metas: self.block_metas,
-            bloom_filter: if self.options.bloom_false_positive > 0.0 {
+            bloom_filter: if self.options.bloom_false_positive > 0.0
+                && !self.user_key_hashes.is_empty()
+            {
                 let bits_per_key = Bloom::bloom_bits_per_key(
                     self.user_key_hashes.len(),
                     self.options.bloom_false_positive,
@@ -177,6 +249,9 @@ impl SstableBuilder {
             key_count: self.key_count as u32,
             smallest_key,
             largest_key,
+            range_tombstones: self.range_tombstones,
+            sliced_table_ids: self.sliced_table_ids.into_iter().collect(),
+            table_key_counts: self.table_key_counts.into_iter().collect(),
             version: VERSION,
         };
 
@@ -219,8 +294,12 @@ impl SstableBuilder {
 
 #[cfg(test)]
 pub(super) mod tests {
+    use risingwave_hummock_sdk::key::key_with_epoch;
+    use risingwave_hummock_sdk::slice_transform::{DummySliceTransform, SliceTransform};
+
     use super::*;
     use crate::hummock::iterator::test_utils::mock_sstable_store;
+    use crate::hummock::sstable::Sstable;
     use crate::hummock::test_utils::{
         default_builder_opt_for_test, gen_default_test_sstable, test_key_of, test_value_of,
         TEST_KEYS_COUNT,
@@ -256,6 +335,30 @@ pub(super) mod tests {
         assert_eq!(test_key_of(TEST_KEYS_COUNT - 1), meta.largest_key);
     }
 
+    #[test]
+    fn test_range_tombstone_key_range() {
+        let mut b = SstableBuilder::new(0, default_builder_opt_for_test());
+        let range_tombstone = RangeTombstone::new(b"a".to_vec(), b"z".to_vec(), 1);
+        b.add_range_tombstone(range_tombstone.clone());
+        let (_, _, meta, _) = b.finish();
+        assert_eq!(range_tombstone.smallest_key(), meta.smallest_key);
+        assert_eq!(range_tombstone.largest_key(), meta.largest_key);
+        assert_eq!(vec![range_tombstone], meta.range_tombstones);
+        assert_eq!(0, meta.key_count);
+
+        let mut b = SstableBuilder::new(0, default_builder_opt_for_test());
+        for i in 0..TEST_KEYS_COUNT {
+            b.add(&test_key_of(i), HummockValue::put(&test_value_of(i)));
+        }
+        b.add_range_tombstone(RangeTombstone::new(b"a".to_vec(), b"b".to_vec(), 1));
+        let (_, _, meta, _) = b.finish();
+        assert_eq!(test_key_of(TEST_KEYS_COUNT - 1), meta.largest_key);
+        assert_eq!(
+            RangeTombstone::new(b"a".to_vec(), b"b".to_vec(), 1).smallest_key(),
+            meta.smallest_key
+        );
+    }
+
     async fn test_with_bloom_filter(with_blooms: bool) {
         let key_count = 1000;
 
@@ -274,7 +377,7 @@ pub(super) mod tests {
         assert_eq!(table.has_bloom_filter(), with_blooms);
         for i in 0..key_count {
             let full_key = test_key_of(i);
-            assert!(!table.surely_not_have_user_key(user_key(full_key.as_slice())));
+            assert!(!table.surely_not_have_user_key(user_key(full_key.as_slice()), None));
         }
     }
 
@@ -283,4 +386,38 @@ pub(super) mod tests {
         test_with_bloom_filter(false).await;
         test_with_bloom_filter(true).await;
     }
+
+    #[test]
+    fn test_bloom_filter_with_slice_transform() {
+        let table_key = |table_id: u32, i: u8| {
+            let mut key = b"t".to_vec();
+            key.put_u32(table_id);
+            key.extend_from_slice(&[i; 8]);
+            key
+        };
+
+        // Only the keys of table 1 are extracted, and they all map to the same empty key.
+        let mut b = SstableBuilder::new(0, default_builder_opt_for_test()).with_slice_transforms(
+            HashMap::from([(1, SliceTransformImpl::Dummy(DummySliceTransform))]),
+        );
+        for (table_id, i) in [(1, 1), (1, 2), (2, 1)] {
+            b.add(
+                &key_with_epoch(table_key(table_id, i), 1),
+                HummockValue::put(b"v"),
+            );
+        }
+        let (_, _, meta, _) = b.finish();
+        assert_eq!(vec![1], meta.sliced_table_ids);
+
+        let table = Sstable::new(0, meta);
+        let mut slice_transform = DummySliceTransform;
+        let sliced_key = slice_transform.transform(&table_key(1, 3));
+        assert!(!table.surely_not_have_user_key(&table_key(1, 3), Some(sliced_key)));
+        assert!(!table.surely_not_have_prefix(1, sliced_key));
+        // The bloom filter can't be used without the sliced key.
+        assert!(!table.surely_not_have_user_key(&table_key(1, 3), None));
+        assert!(!table.surely_not_have_user_key(&table_key(2, 1), None));
+        // The keys of table 2 are not sliced.
+        assert!(!table.surely_not_have_prefix(2, &[]));
+    }
 }
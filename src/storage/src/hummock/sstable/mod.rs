@@ -17,6 +17,7 @@
 // Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
 mod block;
 
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
@@ -34,6 +35,9 @@ use fail::fail_point;
 pub use forward_sstable_iterator::*;
 mod backward_sstable_iterator;
 pub use backward_sstable_iterator::*;
+mod range_tombstone;
+pub use range_tombstone::*;
+use risingwave_hummock_sdk::key::get_table_id;
 use risingwave_hummock_sdk::HummockSstableId;
 #[cfg(test)]
 use risingwave_pb::hummock::{KeyRange, SstableInfo};
@@ -48,7 +52,7 @@ use super::{HummockError, HummockResult};
 
 const DEFAULT_META_BUFFER_CAPACITY: usize = 4096;
 const MAGIC: u32 = 0x5785ab73;
-const VERSION: u32 = 1;
+const VERSION: u32 = 4;
 
 /// [`Sstable`] is a handle for accessing SST.
 pub struct Sstable {
@@ -79,11 +83,15 @@ impl Sstable {
         id: HummockSstableId,
         meta: SstableMeta,
         data: Bytes,
+        verify_checksum: bool,
     ) -> HummockResult<Self> {
         let mut blocks = vec![];
         for block_meta in &meta.block_metas {
             let end_offset = (block_meta.offset + block_meta.len) as usize;
-            let block = Block::decode(data.slice(block_meta.offset as usize..end_offset))?;
+            let block = Block::decode(
+                data.slice(block_meta.offset as usize..end_offset),
+                verify_checksum,
+            )?;
             blocks.push(Arc::new(block));
         }
         Ok(Self { id, meta, blocks })
@@ -93,13 +101,33 @@ impl Sstable {
         !self.meta.bloom_filter.is_empty()
     }
 
-    pub fn surely_not_have_user_key(&self, user_key: &[u8]) -> bool {
+    /// Checks the bloom filter for `user_key`. If the keys of its table were extracted by a slice
+    /// transform when building the SST, `sliced_key` must be extracted from `user_key` by the same
+    /// one, otherwise the bloom filter is skipped.
+    pub fn surely_not_have_user_key(&self, user_key: &[u8], sliced_key: Option<&[u8]>) -> bool {
+        match get_table_id(user_key) {
+            Some(table_id) if self.meta.sliced_table_ids.contains(&table_id) => match sliced_key {
+                Some(sliced_key) => self.surely_not_have_bloom_key(sliced_key),
+                None => false,
+            },
+            _ => self.surely_not_have_bloom_key(user_key),
+        }
+    }
+
+    /// Checks the bloom filter for the keys of `table_id` starting with `prefix`, which must be
+    /// extracted by the slice transform of the table when building the SST. Returns false if the
+    /// keys of the table were not extracted by a slice transform.
+    pub fn surely_not_have_prefix(&self, table_id: u32, prefix: &[u8]) -> bool {
+        self.meta.sliced_table_ids.contains(&table_id) && self.surely_not_have_bloom_key(prefix)
+    }
+
+    fn surely_not_have_bloom_key(&self, bloom_key: &[u8]) -> bool {
         let enable_bloom_filter: fn() -> bool = || {
             fail_point!("disable_bloom_filter", |_| false);
             true
         };
         if enable_bloom_filter() && self.has_bloom_filter() {
-            let hash = farmhash::fingerprint32(user_key);
+            let hash = farmhash::fingerprint32(bloom_key);
             let bloom = Bloom::new(&self.meta.bloom_filter);
             bloom.surely_not_have_hash(hash)
         } else {
@@ -127,6 +155,8 @@ impl Sstable {
             }),
             file_size: self.meta.estimated_size as u64,
             table_ids: vec![],
+            table_key_counts: self.meta.table_key_counts_map(),
+            range_tombstone_count: self.meta.range_tombstones.len() as u32,
         }
     }
 }
@@ -175,6 +205,14 @@ pub struct SstableMeta {
     pub key_count: u32,
     pub smallest_key: Vec<u8>,
     pub largest_key: Vec<u8>,
+    /// Range tombstones of the SST, added since version 2.
+    pub range_tombstones: Vec<RangeTombstone>,
+    /// `table_id` of the keys whose bloom filter keys are extracted by a slice transform, added
+    /// since version 3.
+    pub sliced_table_ids: Vec<u32>,
+    /// The number of put keys of each table sorted by `table_id`, excluding the deletes, added
+    /// since version 4.
+    pub table_key_counts: Vec<(u32, u32)>,
     /// Format version, for further compatibility.
     pub version: u32,
 }
@@ -189,6 +227,9 @@ impl SstableMeta {
     /// | estimated size (4B) | key count (4B) |
     /// | smallest key len (4B) | smallest key |
     /// | largest key len (4B) | largest key |
+    /// | M (4B) | range tombstone 0 | ... | range tombstone M-1 |
+    /// | K (4B) | sliced table id 0 (4B) | ... | sliced table id K-1 (4B) |
+    /// | T (4B) | table id 0 (4B) | key count 0 (4B) | ... | table id T-1 (4B) | key count T-1 (4B) |
     /// | checksum (8B) | version (4B) | magic (4B) |
     /// ```
     pub fn encode_to_bytes(&self) -> Vec<u8> {
@@ -202,6 +243,19 @@ impl SstableMeta {
         buf.put_u32_le(self.key_count as u32);
         put_length_prefixed_slice(&mut buf, &self.smallest_key);
         put_length_prefixed_slice(&mut buf, &self.largest_key);
+        buf.put_u32_le(self.range_tombstones.len() as u32);
+        for range_tombstone in &self.range_tombstones {
+            range_tombstone.encode(&mut buf);
+        }
+        buf.put_u32_le(self.sliced_table_ids.len() as u32);
+        for table_id in &self.sliced_table_ids {
+            buf.put_u32_le(*table_id);
+        }
+        buf.put_u32_le(self.table_key_counts.len() as u32);
+        for (table_id, key_count) in &self.table_key_counts {
+            buf.put_u32_le(*table_id);
+            buf.put_u32_le(*key_count);
+        }
         let checksum = xxhash64_checksum(&buf);
         buf.put_u64_le(checksum);
         buf.put_u32_le(VERSION);
@@ -220,7 +274,9 @@ impl SstableMeta {
 
         cursor -= 4;
         let version = (&buf[cursor..cursor + 4]).get_u32_le();
-        if version != VERSION {
+        // Version 1 differs only in lacking the range tombstones, version 2 in lacking the sliced
+        // table ids, and version 3 in lacking the key counts of the tables.
+        if version == 0 || version > VERSION {
             return Err(HummockError::invalid_format_version(version));
         }
 
@@ -239,6 +295,28 @@ impl SstableMeta {
         let key_count = buf.get_u32_le();
         let smallest_key = get_length_prefixed_slice(buf);
         let largest_key = get_length_prefixed_slice(buf);
+        let range_tombstones = if version >= 2 {
+            let range_tombstone_count = buf.get_u32_le() as usize;
+            (0..range_tombstone_count)
+                .map(|_| RangeTombstone::decode(buf))
+                .collect()
+        } else {
+            vec![]
+        };
+        let sliced_table_ids = if version >= 3 {
+            let sliced_table_count = buf.get_u32_le() as usize;
+            (0..sliced_table_count).map(|_| buf.get_u32_le()).collect()
+        } else {
+            vec![]
+        };
+        let table_key_counts = if version >= 4 {
+            let table_count = buf.get_u32_le() as usize;
+            (0..table_count)
+                .map(|_| (buf.get_u32_le(), buf.get_u32_le()))
+                .collect()
+        } else {
+            vec![]
+        };
 
         Ok(Self {
             block_metas,
@@ -247,10 +325,21 @@ impl SstableMeta {
             key_count,
             smallest_key,
             largest_key,
+            range_tombstones,
+            sliced_table_ids,
+            table_key_counts,
             version,
         })
     }
 
+    /// The key counts of the tables as in `SstableInfo`.
+    pub fn table_key_counts_map(&self) -> HashMap<u32, u64> {
+        self.table_key_counts
+            .iter()
+            .map(|&(table_id, key_count)| (table_id, key_count as u64))
+            .collect()
+    }
+
     #[inline]
     pub fn encoded_size(&self) -> usize {
         4 // block meta count
@@ -267,6 +356,16 @@ impl SstableMeta {
             + self.smallest_key.len()
             + 4 // key len
             + self.largest_key.len()
+            + 4 // range tombstone count
+            + self
+            .range_tombstones
+            .iter()
+            .map(|range_tombstone| range_tombstone.encoded_size())
+            .sum::<usize>()
+            + 4 // sliced table count
+            + 4 * self.sliced_table_ids.len()
+            + 4 // table count
+            + 8 * self.table_key_counts.len()
             + 8 // checksum
             + 4 // version
             + 4 // magic
@@ -302,6 +401,13 @@ mod tests {
             key_count: 123,
             smallest_key: b"0-smallest-key".to_vec(),
             largest_key: b"9-largest-key".to_vec(),
+            range_tombstones: vec![RangeTombstone::new(
+                b"1-start-key".to_vec(),
+                b"3-end-key".to_vec(),
+                100,
+            )],
+            sliced_table_ids: vec![1, 3],
+            table_key_counts: vec![(1, 100), (3, 23)],
             version: VERSION,
         };
         let buf = meta.encode_to_bytes();
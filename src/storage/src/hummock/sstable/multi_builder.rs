@@ -12,11 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use risingwave_hummock_sdk::key::{Epoch, FullKey};
+use risingwave_hummock_sdk::key::{user_key, Epoch, FullKey};
 use risingwave_hummock_sdk::HummockSstableId;
 use tokio::task::JoinHandle;
 
-use super::SstableMeta;
+use super::{RangeTombstone, SstableMeta};
 use crate::hummock::sstable_store::SstableStoreRef;
 use crate::hummock::utils::MemoryTracker;
 use crate::hummock::value::HummockValue;
@@ -39,6 +39,10 @@ pub struct SealedSstableBuilder {
 /// based on their target capacity set in options.
 ///
 /// When building is finished, one may call `finish` to get the results of zero, one or more tables.
+///
+/// Range tombstones are split into the tables so that their key ranges don't overlap: each table
+/// takes the part of the tombstones from its first user key to the first user key of the next
+/// table, while the first and the last tables take the rest on their sides.
 pub struct CapacitySplitTableBuilder<F: TableBuilderFactory> {
     /// When creating a new [`SstableBuilder`], caller use this closure to specify the id and
     /// options.
@@ -48,6 +52,15 @@ pub struct CapacitySplitTableBuilder<F: TableBuilderFactory> {
 
     current_builder: Option<SstableBuilder>,
 
+    /// The builder sealed but not finished yet, as its range tombstones depend on the first key
+    /// of the next table. Only used when there are range tombstones.
+    pending_builder: Option<(SstableBuilder, Option<MemoryTracker>)>,
+
+    range_tombstones: Vec<RangeTombstone>,
+
+    /// The user key from which the range tombstones go to the next finished table.
+    range_tombstone_start: Option<Vec<u8>>,
+
     policy: CachePolicy,
     sstable_store: SstableStoreRef,
     tracker: Option<MemoryTracker>,
@@ -60,6 +73,9 @@ impl<F: TableBuilderFactory> CapacitySplitTableBuilder<F> {
             builder_factory,
             sealed_builders: Vec::new(),
             current_builder: None,
+            pending_builder: None,
+            range_tombstones: vec![],
+            range_tombstone_start: None,
             policy,
             sstable_store,
             tracker: None,
@@ -68,12 +84,24 @@ impl<F: TableBuilderFactory> CapacitySplitTableBuilder<F> {
 
     /// Returns the number of [`SstableBuilder`]s.
     pub fn len(&self) -> usize {
-        self.sealed_builders.len() + if self.current_builder.is_some() { 1 } else { 0 }
+        self.sealed_builders.len()
+            + self.pending_builder.iter().count()
+            + self.current_builder.iter().count()
     }
 
     /// Returns true if no builder is created.
     pub fn is_empty(&self) -> bool {
-        self.sealed_builders.is_empty() && self.current_builder.is_none()
+        self.len() == 0
+    }
+
+    /// Adds range tombstones to be split into the tables. It should be called before any key is
+    /// added.
+    pub fn add_range_tombstones(
+        &mut self,
+        range_tombstones: impl IntoIterator<Item = RangeTombstone>,
+    ) {
+        assert!(self.is_empty());
+        self.range_tombstones.extend(range_tombstones);
     }
 
     /// Adds a user key-value pair to the underlying builders, with given `epoch`.
@@ -112,6 +140,7 @@ impl<F: TableBuilderFactory> CapacitySplitTableBuilder<F> {
         }
 
         if self.current_builder.is_none() {
+            self.finish_pending(Some(user_key(full_key.inner())));
             let (tracker, builder) = self.builder_factory.open_builder().await?;
             self.current_builder = Some(builder);
             self.tracker = Some(tracker);
@@ -128,42 +157,73 @@ impl<F: TableBuilderFactory> CapacitySplitTableBuilder<F> {
     /// will be no-op.
     pub fn seal_current(&mut self) {
         if let Some(builder) = self.current_builder.take() {
-            let (table_id, data, meta, table_ids) = builder.finish();
-            let len = data.len();
-            let sstable_store = self.sstable_store.clone();
-            let meta_clone = meta.clone();
-            let policy = self.policy;
             let tracker = self.tracker.take();
-            let upload_join_handle = tokio::spawn(async move {
-                let ret = if policy == CachePolicy::Fill {
-                    let sst = Sstable::new_with_data(table_id, meta_clone, data.clone())?;
-                    sstable_store.put(sst, data, CachePolicy::Fill).await
-                } else {
-                    sstable_store
-                        .put(
-                            Sstable::new(table_id, meta_clone),
-                            data,
-                            CachePolicy::NotFill,
-                        )
-                        .await
-                };
-                drop(tracker);
-                ret
-            });
-            self.sealed_builders.push(SealedSstableBuilder {
-                id: table_id,
-                meta,
-                table_ids,
-                upload_join_handle,
-                data_len: len,
-            })
+            if self.range_tombstones.is_empty() {
+                self.finish_builder(builder, tracker);
+            } else {
+                self.pending_builder = Some((builder, tracker));
+            }
+        }
+    }
+
+    /// Finishes the pending builder, with the range tombstones before `end_user_key`.
+    fn finish_pending(&mut self, end_user_key: Option<&[u8]>) {
+        if let Some((mut builder, tracker)) = self.pending_builder.take() {
+            for range_tombstone in &self.range_tombstones {
+                if let Some(range_tombstone) =
+                    range_tombstone.clip(self.range_tombstone_start.as_deref(), end_user_key)
+                {
+                    builder.add_range_tombstone(range_tombstone);
+                }
+            }
+            self.range_tombstone_start = end_user_key.map(|key| key.to_vec());
+            self.finish_builder(builder, tracker);
         }
     }
 
+    fn finish_builder(&mut self, builder: SstableBuilder, tracker: Option<MemoryTracker>) {
+        let (table_id, data, meta, table_ids) = builder.finish();
+        let len = data.len();
+        let sstable_store = self.sstable_store.clone();
+        let meta_clone = meta.clone();
+        let policy = self.policy;
+        let upload_join_handle = tokio::spawn(async move {
+            let ret = if policy == CachePolicy::Fill {
+                let sst = Sstable::new_with_data(table_id, meta_clone, data.clone(), true)?;
+                sstable_store.put(sst, data, CachePolicy::Fill).await
+            } else {
+                sstable_store
+                    .put(
+                        Sstable::new(table_id, meta_clone),
+                        data,
+                        CachePolicy::NotFill,
+                    )
+                    .await
+            };
+            drop(tracker);
+            ret
+        });
+        self.sealed_builders.push(SealedSstableBuilder {
+            id: table_id,
+            meta,
+            table_ids,
+            upload_join_handle,
+            data_len: len,
+        })
+    }
+
     /// Finalizes all the tables to be ids, blocks and metadata.
-    pub fn finish(mut self) -> Vec<SealedSstableBuilder> {
+    ///
+    /// If there are range tombstones but no keys, a table with only the range tombstones is built.
+    pub async fn finish(mut self) -> HummockResult<Vec<SealedSstableBuilder>> {
+        if self.is_empty() && !self.range_tombstones.is_empty() {
+            let (tracker, builder) = self.builder_factory.open_builder().await?;
+            self.current_builder = Some(builder);
+            self.tracker = Some(tracker);
+        }
         self.seal_current();
-        self.sealed_builders
+        self.finish_pending(None);
+        Ok(self.sealed_builders)
     }
 }
 
@@ -225,7 +285,7 @@ mod tests {
             CachePolicy::NotFill,
             mock_sstable_store(),
         );
-        let results = builder.finish();
+        let results = builder.finish().await.unwrap();
         assert!(results.is_empty());
     }
 
@@ -260,7 +320,7 @@ mod tests {
                 .unwrap();
         }
 
-        let results = builder.finish();
+        let results = builder.finish().await.unwrap();
         assert!(results.len() > 1);
         assert_eq!(results.iter().map(|p| p.id).duplicates().count(), 0);
     }
@@ -300,10 +360,52 @@ mod tests {
         builder.seal_current();
         assert_eq!(builder.len(), 2);
 
-        let results = builder.finish();
+        let results = builder.finish().await.unwrap();
         assert_eq!(results.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_range_tombstones() {
+        let mut builder = CapacitySplitTableBuilder::new(
+            LocalTableBuilderFactory::new(1001, default_builder_opt_for_test()),
+            CachePolicy::NotFill,
+            mock_sstable_store(),
+        );
+        builder.add_range_tombstones([RangeTombstone::new(b"a".to_vec(), b"z".to_vec(), 100)]);
+        for key in [b"c", b"m"] {
+            builder
+                .add_user_key(key.to_vec(), HummockValue::put(b"v"), 233)
+                .await
+                .unwrap();
+            builder.seal_current();
+        }
+
+        let results = builder.finish().await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].meta.range_tombstones,
+            vec![RangeTombstone::new(b"a".to_vec(), b"m".to_vec(), 100)]
+        );
+        assert_eq!(
+            results[1].meta.range_tombstones,
+            vec![RangeTombstone::new(b"m".to_vec(), b"z".to_vec(), 100)]
+        );
+        assert_eq!(user_key(&results[0].meta.smallest_key), b"a");
+        assert_eq!(user_key(&results[1].meta.smallest_key), b"m");
+        assert_eq!(user_key(&results[1].meta.largest_key), b"z");
+
+        // Only range tombstones.
+        let mut builder = CapacitySplitTableBuilder::new(
+            LocalTableBuilderFactory::new(1001, default_builder_opt_for_test()),
+            CachePolicy::NotFill,
+            mock_sstable_store(),
+        );
+        builder.add_range_tombstones([RangeTombstone::new(b"a".to_vec(), b"z".to_vec(), 100)]);
+        let results = builder.finish().await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].meta.key_count, 0);
+    }
+
     #[tokio::test]
     async fn test_initial_not_allowed_split() {
         let mut builder = CapacitySplitTableBuilder::new(
@@ -0,0 +1,192 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::{Buf, BufMut};
+use risingwave_hummock_sdk::key::{key_with_epoch, Epoch};
+
+use super::utils::{get_length_prefixed_slice, put_length_prefixed_slice};
+use crate::hummock::HummockEpoch;
+
+/// A range tombstone deletes all the versions of the user keys in
+/// `[start_user_key, end_user_key)` whose epoch is not larger than `epoch`.
+///
+/// Unlike the point deletes, which are stored as [`HummockValue::Delete`] among the key-value
+/// pairs, range tombstones are kept aside in the [`SharedBufferBatch`] and in the
+/// [`SstableMeta`], and are applied by the readers and the compactor.
+///
+/// [`HummockValue::Delete`]: crate::hummock::value::HummockValue::Delete
+/// [`SharedBufferBatch`]: crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatch
+/// [`SstableMeta`]: super::SstableMeta
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RangeTombstone {
+    pub start_user_key: Vec<u8>,
+    pub end_user_key: Vec<u8>,
+    pub epoch: Epoch,
+}
+
+impl RangeTombstone {
+    pub fn new(start_user_key: Vec<u8>, end_user_key: Vec<u8>, epoch: Epoch) -> Self {
+        Self {
+            start_user_key,
+            end_user_key,
+            epoch,
+        }
+    }
+
+    /// Whether `user_key` is in the range of the tombstone.
+    pub fn contains(&self, user_key: &[u8]) -> bool {
+        self.start_user_key.as_slice() <= user_key && user_key < self.end_user_key.as_slice()
+    }
+
+    /// Whether the version of `user_key` written at `epoch` is deleted by the tombstone.
+    pub fn covers(&self, user_key: &[u8], epoch: Epoch) -> bool {
+        epoch <= self.epoch && self.contains(user_key)
+    }
+
+    /// Returns the part of the tombstone inside `[start_user_key, end_user_key)`, where `None`
+    /// means unbounded, or `None` if they don't overlap.
+    pub fn clip(&self, start_user_key: Option<&[u8]>, end_user_key: Option<&[u8]>) -> Option<Self> {
+        let start = match start_user_key {
+            Some(start) if start > self.start_user_key.as_slice() => start,
+            _ => self.start_user_key.as_slice(),
+        };
+        let end = match end_user_key {
+            Some(end) if end < self.end_user_key.as_slice() => end,
+            _ => self.end_user_key.as_slice(),
+        };
+        if start >= end {
+            return None;
+        }
+        Some(Self::new(start.to_vec(), end.to_vec(), self.epoch))
+    }
+
+    /// The smallest full key that the tombstone may cover.
+    pub fn smallest_key(&self) -> Vec<u8> {
+        key_with_epoch(self.start_user_key.clone(), HummockEpoch::MAX)
+    }
+
+    /// A full key that is larger than all the full keys the tombstone may cover, but smaller than
+    /// any version of `end_user_key`.
+    pub fn largest_key(&self) -> Vec<u8> {
+        key_with_epoch(self.end_user_key.clone(), HummockEpoch::MAX)
+    }
+
+    /// Format:
+    ///
+    /// ```plain
+    /// | start user key len (4B) | start user key | end user key len (4B) | end user key |
+    /// | epoch (8B) |
+    /// ```
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        put_length_prefixed_slice(buf, &self.start_user_key);
+        put_length_prefixed_slice(buf, &self.end_user_key);
+        buf.put_u64_le(self.epoch);
+    }
+
+    pub fn decode(buf: &mut &[u8]) -> Self {
+        let start_user_key = get_length_prefixed_slice(buf);
+        let end_user_key = get_length_prefixed_slice(buf);
+        let epoch = buf.get_u64_le();
+        Self::new(start_user_key, end_user_key, epoch)
+    }
+
+    #[inline]
+    pub fn encoded_size(&self) -> usize {
+        8 /* key lens */ + self.start_user_key.len() + self.end_user_key.len() + 8 // epoch
+    }
+}
+
+/// The range tombstones visible to a read at `read_epoch`.
+///
+/// Tombstones are expected to be rare, so they are simply kept in a list and checked one by one.
+#[derive(Clone, Debug)]
+pub struct RangeTombstones {
+    read_epoch: Epoch,
+    tombstones: Vec<RangeTombstone>,
+}
+
+impl RangeTombstones {
+    pub fn new(read_epoch: Epoch) -> Self {
+        Self {
+            read_epoch,
+            tombstones: vec![],
+        }
+    }
+
+    /// Adds the tombstones that are visible to the read.
+    pub fn extend<'a>(&mut self, tombstones: impl IntoIterator<Item = &'a RangeTombstone>) {
+        self.tombstones.extend(
+            tombstones
+                .into_iter()
+                .filter(|tombstone| tombstone.epoch <= self.read_epoch)
+                .cloned(),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    /// The largest epoch of the tombstones covering `user_key`, if any.
+    pub fn max_covering_epoch(&self, user_key: &[u8]) -> Option<Epoch> {
+        self.tombstones
+            .iter()
+            .filter(|tombstone| tombstone.contains(user_key))
+            .map(|tombstone| tombstone.epoch)
+            .max()
+    }
+
+    /// Whether the version of `user_key` written at `epoch` is deleted by any of the tombstones.
+    pub fn is_deleted(&self, user_key: &[u8], epoch: Epoch) -> bool {
+        self.tombstones
+            .iter()
+            .any(|tombstone| tombstone.covers(user_key, epoch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_tombstone() {
+        let tombstone = RangeTombstone::new(b"b".to_vec(), b"d".to_vec(), 10);
+        assert!(!tombstone.covers(b"a", 5));
+        assert!(tombstone.covers(b"b", 5));
+        assert!(tombstone.covers(b"c", 10));
+        assert!(!tombstone.covers(b"c", 11));
+        assert!(!tombstone.covers(b"d", 5));
+
+        assert_eq!(
+            tombstone.clip(Some(&b"c"[..]), None),
+            Some(RangeTombstone::new(b"c".to_vec(), b"d".to_vec(), 10))
+        );
+        assert_eq!(tombstone.clip(None, Some(&b"b"[..])), None);
+
+        let mut buf = vec![];
+        tombstone.encode(&mut buf);
+        assert_eq!(buf.len(), tombstone.encoded_size());
+        assert_eq!(RangeTombstone::decode(&mut &buf[..]), tombstone);
+
+        let mut tombstones = RangeTombstones::new(10);
+        tombstones.extend(&[
+            tombstone,
+            RangeTombstone::new(b"a".to_vec(), b"z".to_vec(), 20),
+        ]);
+        assert!(tombstones.is_deleted(b"c", 8));
+        assert!(!tombstones.is_deleted(b"y", 8));
+        assert_eq!(tombstones.max_covering_epoch(b"c"), Some(10));
+        assert_eq!(tombstones.max_covering_epoch(b"e"), None);
+    }
+}
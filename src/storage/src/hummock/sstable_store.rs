@@ -23,7 +23,7 @@ use risingwave_object_store::object::{get_local_path, BlockLocation, ObjectStore
 
 use super::{Block, BlockCache, Sstable, SstableMeta};
 use crate::hummock::{BlockHolder, CachableEntry, HummockError, HummockResult, LruCache};
-use crate::monitor::StoreLocalStatistic;
+use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
 const MAX_META_CACHE_SHARD_BITS: usize = 2;
 const MAX_CACHE_SHARD_BITS: usize = 6; // It means that there will be 64 shards lru-cache to avoid lock conflict.
@@ -47,6 +47,9 @@ pub struct SstableStore {
     store: ObjectStoreRef,
     block_cache: BlockCache,
     meta_cache: Arc<LruCache<HummockSstableId, Box<Sstable>>>,
+    /// Whether to verify the checksum of the blocks read from the object store.
+    verify_checksum: bool,
+    metrics: Arc<StateStoreMetrics>,
 }
 
 impl SstableStore {
@@ -55,6 +58,8 @@ impl SstableStore {
         path: String,
         block_cache_capacity: usize,
         meta_cache_capacity: usize,
+        verify_checksum: bool,
+        metrics: Arc<StateStoreMetrics>,
     ) -> Self {
         let mut shard_bits = MAX_META_CACHE_SHARD_BITS;
         while (meta_cache_capacity >> shard_bits) < MIN_BUFFER_SIZE_PER_SHARD && shard_bits > 0 {
@@ -66,6 +71,8 @@ impl SstableStore {
             store,
             block_cache: BlockCache::new(block_cache_capacity, MAX_CACHE_SHARD_BITS),
             meta_cache,
+            verify_checksum,
+            metrics,
         }
     }
 
@@ -76,6 +83,8 @@ impl SstableStore {
         path: String,
         block_cache_capacity: usize,
         meta_cache_capacity: usize,
+        verify_checksum: bool,
+        metrics: Arc<StateStoreMetrics>,
     ) -> Self {
         let meta_cache = Arc::new(LruCache::new(0, meta_cache_capacity));
         Self {
@@ -83,6 +92,8 @@ impl SstableStore {
             store,
             block_cache: BlockCache::new(block_cache_capacity, 2),
             meta_cache,
+            verify_checksum,
+            metrics,
         }
     }
 
@@ -161,7 +172,10 @@ impl SstableStore {
         block_idx: u64,
         block_data: Bytes,
     ) -> HummockResult<()> {
-        let block = Box::new(Block::decode(block_data)?);
+        let block = Box::new(count_checksum_mismatch(
+            Block::decode(block_data, self.verify_checksum),
+            &self.metrics,
+        )?);
         self.block_cache.insert(sst_id, block_idx, block);
         Ok(())
     }
@@ -188,13 +202,16 @@ impl SstableStore {
             };
             let data_path = self.get_sst_data_path(sst.id);
             let store = self.store.clone();
+            let verify_checksum = self.verify_checksum;
+            let metrics = self.metrics.clone();
 
             async move {
                 let block_data = store
                     .read(&data_path, Some(block_loc))
                     .await
                     .map_err(HummockError::object_io_error)?;
-                let block = Block::decode(block_data)?;
+                let block =
+                    count_checksum_mismatch(Block::decode(block_data, verify_checksum), &metrics)?;
                 Ok(Box::new(block))
             }
         };
@@ -277,6 +294,8 @@ impl SstableStore {
                     let store = self.store.clone();
                     let meta_path = self.get_sst_meta_path(sst_id);
                     let data_path = self.get_sst_data_path(sst_id);
+                    let verify_checksum = self.verify_checksum;
+                    let metrics = self.metrics.clone();
                     stats.cache_meta_block_miss += 1;
 
                     async move {
@@ -297,7 +316,10 @@ impl SstableStore {
                                 .await
                                 .map_err(HummockError::object_io_error)?;
                             size += block_data.len();
-                            let sst = Sstable::new_with_data(sst_id, meta, block_data)?;
+                            let sst = count_checksum_mismatch(
+                                Sstable::new_with_data(sst_id, meta, block_data, verify_checksum),
+                                &metrics,
+                            )?;
                             size += sst
                                 .blocks
                                 .iter()
@@ -339,17 +361,38 @@ impl SstableStore {
 
 pub type SstableStoreRef = Arc<SstableStore>;
 
+/// Counts the checksum mismatches of the blocks read from the object store.
+fn count_checksum_mismatch<T>(
+    result: HummockResult<T>,
+    metrics: &StateStoreMetrics,
+) -> HummockResult<T> {
+    result.inspect_err(|e| {
+        if e.is_checksum_mismatch() {
+            metrics.sst_store_block_checksum_mismatch_counts.inc();
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::hummock::iterator::test_utils::{iterator_test_key_of, mock_sstable_store};
+    use bytes::{Bytes, BytesMut};
+    use risingwave_object_store::object::object_metrics::ObjectStoreMetrics;
+    use risingwave_object_store::object::{
+        FaultInjector, InMemObjectStore, ObjectStore, ObjectStoreImpl,
+    };
+
+    use super::SstableStore;
+    use crate::hummock::iterator::test_utils::{
+        iterator_test_key_of, mock_sstable_store, mock_sstable_store_with_object_store,
+    };
     use crate::hummock::iterator::HummockIterator;
     use crate::hummock::sstable::SstableIteratorReadOptions;
     use crate::hummock::test_utils::{default_builder_opt_for_test, gen_test_sstable_data};
     use crate::hummock::value::HummockValue;
     use crate::hummock::{CachePolicy, Sstable, SstableIterator};
-    use crate::monitor::StoreLocalStatistic;
+    use crate::monitor::{StateStoreMetrics, StoreLocalStatistic};
 
     #[tokio::test]
     async fn test_read_whole_data_object() {
@@ -391,4 +434,97 @@ mod tests {
             iter.next().await.unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_put_with_failed_meta_upload() {
+        let injector = Arc::new(FaultInjector::default());
+        let sstable_store = mock_sstable_store_with_object_store(Arc::new(ObjectStoreImpl::InMem(
+            InMemObjectStore::with_fault_injector(injector.clone())
+                .monitored(Arc::new(ObjectStoreMetrics::unused())),
+        )));
+        let (data, meta, _) = gen_test_sstable_data(
+            default_builder_opt_for_test(),
+            (0..100).map(|x| (iterator_test_key_of(x), HummockValue::put(b"v".to_vec()))),
+        );
+
+        // The data is uploaded before the meta. No part of the SST is left when the meta fails.
+        injector.fail_nth_upload(2);
+        sstable_store
+            .put(
+                Sstable::new(1, meta.clone()),
+                data.clone(),
+                CachePolicy::Fill,
+            )
+            .await
+            .unwrap_err();
+        let store = sstable_store.store();
+        store
+            .metadata(&sstable_store.get_sst_data_path(1))
+            .await
+            .unwrap_err();
+        store
+            .metadata(&sstable_store.get_sst_meta_path(1))
+            .await
+            .unwrap_err();
+        let mut stats = StoreLocalStatistic::default();
+        assert!(sstable_store.sstable(1, &mut stats).await.is_err());
+
+        sstable_store
+            .put(Sstable::new(1, meta.clone()), data, CachePolicy::Fill)
+            .await
+            .unwrap();
+        let holder = sstable_store.sstable(1, &mut stats).await.unwrap();
+        assert_eq!(holder.value().meta, meta);
+    }
+
+    #[tokio::test]
+    async fn test_block_checksum_mismatch() {
+        let metrics = Arc::new(StateStoreMetrics::unused());
+        let sstable_store = SstableStore::new(
+            Arc::new(ObjectStoreImpl::InMem(
+                InMemObjectStore::new().monitored(Arc::new(ObjectStoreMetrics::unused())),
+            )),
+            "test".to_string(),
+            64 << 20,
+            64 << 20,
+            true,
+            metrics.clone(),
+        );
+        let (data, meta, _) = gen_test_sstable_data(
+            default_builder_opt_for_test(),
+            (0..1000).map(|x| (iterator_test_key_of(x), HummockValue::put(b"v".to_vec()))),
+        );
+        sstable_store
+            .put(
+                Sstable::new(1, meta.clone()),
+                data.clone(),
+                CachePolicy::NotFill,
+            )
+            .await
+            .unwrap();
+
+        // Corrupt the first block in the object store.
+        let mut corrupted = BytesMut::from(&data[..]);
+        corrupted[0] ^= 0xff;
+        sstable_store
+            .store()
+            .upload(&sstable_store.get_sst_data_path(1), Bytes::from(corrupted))
+            .await
+            .unwrap();
+
+        let mut stats = StoreLocalStatistic::default();
+        assert!(meta.block_metas.len() > 1);
+        let sst = Sstable::new(1, meta);
+        let err = sstable_store
+            .get(&sst, 0, CachePolicy::Disable, &mut stats)
+            .await
+            .err()
+            .unwrap();
+        assert!(err.is_checksum_mismatch());
+        assert_eq!(metrics.sst_store_block_checksum_mismatch_counts.get(), 1);
+        sstable_store
+            .get(&sst, 1, CachePolicy::Disable, &mut stats)
+            .await
+            .unwrap();
+    }
 }
@@ -13,15 +13,17 @@
 // limitations under the License.
 
 use std::future::Future;
-use std::ops::Bound::{Excluded, Included};
+use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::RangeBounds;
 use std::sync::Arc;
 
 use bytes::Bytes;
+use futures::{stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
-use risingwave_hummock_sdk::key::key_with_epoch;
+use risingwave_hummock_sdk::key::{get_table_id, key_with_epoch, next_key};
+use risingwave_hummock_sdk::slice_transform::SliceTransformImpl;
 use risingwave_hummock_sdk::LocalSstableInfo;
-use risingwave_pb::hummock::LevelType;
+use risingwave_pb::hummock::{LevelType, SstableInfo};
 
 use super::iterator::{
     BackwardUserIterator, ConcatIteratorInner, DirectedUserIterator, UserIterator,
@@ -39,8 +41,9 @@ use crate::hummock::shared_buffer::{
     build_ordered_merge_iter, OrderSortedUncommittedData, UncommittedData,
 };
 use crate::hummock::sstable::SstableIteratorReadOptions;
+use crate::hummock::sstable_store::TableHolder;
 use crate::hummock::utils::prune_ssts;
-use crate::hummock::HummockResult;
+use crate::hummock::{HummockError, HummockResult, RangeTombstone, RangeTombstones};
 use crate::monitor::StoreLocalStatistic;
 use crate::storage_value::StorageValue;
 use crate::store::*;
@@ -75,6 +78,34 @@ impl HummockIteratorType for BackwardIter {
 }
 
 impl HummockStorage {
+    /// Opens the SSTs concurrently, at most `iter_open_sstable_concurrency` at a time, and returns
+    /// them in the given order.
+    async fn open_sstables<'a>(
+        &self,
+        table_infos: impl Iterator<Item = &'a SstableInfo>,
+        stats: &mut StoreLocalStatistic,
+    ) -> HummockResult<Vec<TableHolder>> {
+        let tables: Vec<_> = stream::iter(table_infos.map(|table_info| {
+            let sstable_store = self.sstable_store.clone();
+            let table_id = table_info.id;
+            async move {
+                let mut stats = StoreLocalStatistic::default();
+                let table = sstable_store.sstable(table_id, &mut stats).await?;
+                Ok::<_, HummockError>((table, stats))
+            }
+        }))
+        .buffered(self.options.iter_open_sstable_concurrency.max(1))
+        .try_collect()
+        .await?;
+        Ok(tables
+            .into_iter()
+            .map(|(table, table_stats)| {
+                stats.add(&table_stats);
+                table
+            })
+            .collect())
+    }
+
     async fn iter_inner<R, B, T>(
         &self,
         key_range: R,
@@ -97,11 +128,36 @@ impl HummockStorage {
         let (shared_buffer_data, pinned_version) = self.read_filter(&read_options, &key_range)?;
 
         let mut stats = StoreLocalStatistic::default();
+        let mut range_tombstones = RangeTombstones::new(epoch);
+        // The SSTs are skipped by the bloom filter key shared by all the keys in the range.
+        let range_bloom_key = self.range_bloom_key(&key_range);
+        let surely_not_in_range = |table: &TableHolder| {
+            range_bloom_key
+                .as_ref()
+                .map_or(false, |(table_id, bloom_key)| {
+                    table.value().surely_not_have_prefix(*table_id, bloom_key)
+                })
+        };
 
         for (replicated_batches, uncommitted_data) in shared_buffer_data {
             for batch in replicated_batches {
+                range_tombstones.extend(batch.range_tombstone());
                 overlapped_iters.push(HummockIteratorUnion::First(batch.into_directed_iter()));
             }
+            for data in uncommitted_data.iter().flatten() {
+                match data {
+                    UncommittedData::Batch(batch) => {
+                        range_tombstones.extend(batch.range_tombstone());
+                    }
+                    UncommittedData::Sst((_, table_info)) => {
+                        let table = self
+                            .sstable_store
+                            .sstable(table_info.id, &mut stats)
+                            .await?;
+                        range_tombstones.extend(&table.value().meta.range_tombstones);
+                    }
+                }
+            }
             overlapped_iters.push(HummockIteratorUnion::Second(
                 build_ordered_merge_iter::<T>(
                     &uncommitted_data,
@@ -131,6 +187,16 @@ impl HummockStorage {
         // would contain tables from different compaction_group, even for those in L0.
         //
         // When adopting dynamic compaction group in the future, be sure to revisit this assumption.
+        //
+        // The SSTs to read in all levels are selected first, so that the ones to open eagerly can
+        // be opened concurrently. The SSTs of overlapping levels are merged and must be
+        // opened. The SSTs of nonoverlapping levels are opened lazily by the concat
+        // iterators, except the ones whose range tombstones must be collected, or all of
+        // them if they can be skipped by the bloom filter, which needs the opened SSTs.
+        let open_eagerly = |nonoverlapping: bool, table_info: &SstableInfo| {
+            !nonoverlapping || range_bloom_key.is_some() || table_info.range_tombstone_count > 0
+        };
+        let mut level_table_infos = vec![];
         for level in pinned_version.levels(compaction_group_id) {
             let table_infos = prune_ssts(level.table_infos.iter(), &key_range);
             if table_infos.is_empty() {
@@ -147,19 +213,49 @@ impl HummockStorage {
                     _ => table_infos.len().saturating_sub(1),
                 };
                 assert!(start_table_idx < table_infos.len() && end_table_idx < table_infos.len());
-                let matched_table_infos = &table_infos[start_table_idx..=end_table_idx];
-
-                let tables = match T::Direction::direction() {
-                    DirectionEnum::Backward => matched_table_infos
-                        .iter()
-                        .rev()
-                        .map(|&info| info.clone())
-                        .collect_vec(),
-                    DirectionEnum::Forward => matched_table_infos
-                        .iter()
-                        .map(|&info| info.clone())
-                        .collect_vec(),
-                };
+                let matched_table_infos = table_infos[start_table_idx..=end_table_idx].to_vec();
+                level_table_infos.push((true, matched_table_infos));
+            } else {
+                level_table_infos.push((false, table_infos.into_iter().rev().collect_vec()));
+            }
+        }
+        let mut opened_tables = self
+            .open_sstables(
+                level_table_infos
+                    .iter()
+                    .flat_map(|(nonoverlapping, table_infos)| {
+                        table_infos
+                            .iter()
+                            .copied()
+                            .filter(|info| open_eagerly(*nonoverlapping, *info))
+                    }),
+                &mut stats,
+            )
+            .await?
+            .into_iter();
+
+        for (nonoverlapping, table_infos) in level_table_infos {
+            if nonoverlapping {
+                let mut tables = Vec::with_capacity(table_infos.len());
+                for info in table_infos {
+                    if open_eagerly(true, info) {
+                        let table = opened_tables.next().unwrap();
+                        // The range tombstones of the skipped SSTs may still delete the keys in
+                        // other SSTs.
+                        range_tombstones.extend(&table.value().meta.range_tombstones);
+                        if surely_not_in_range(&table) {
+                            stats.bloom_filter_true_negative_count += 1;
+                            continue;
+                        }
+                    }
+                    tables.push(info.clone());
+                }
+                if tables.is_empty() {
+                    continue;
+                }
+                if let DirectionEnum::Backward = T::Direction::direction() {
+                    tables.reverse();
+                }
 
                 overlapped_iters.push(HummockIteratorUnion::Third(ConcatIteratorInner::<
                     T::SstableIteratorType,
@@ -169,11 +265,12 @@ impl HummockStorage {
                     iter_read_options.clone(),
                 )));
             } else {
-                for table_info in table_infos.into_iter().rev() {
-                    let table = self
-                        .sstable_store
-                        .sstable(table_info.id, &mut stats)
-                        .await?;
+                for table in opened_tables.by_ref().take(table_infos.len()) {
+                    range_tombstones.extend(&table.value().meta.range_tombstones);
+                    if surely_not_in_range(&table) {
+                        stats.bloom_filter_true_negative_count += 1;
+                        continue;
+                    }
                     overlapped_iters.push(HummockIteratorUnion::Fourth(
                         T::SstableIteratorType::create(
                             table,
@@ -202,11 +299,15 @@ impl HummockStorage {
             key_range,
             epoch,
             min_epoch,
+            range_tombstones,
             Some(pinned_version),
         );
 
         user_iterator.rewind().await?;
         stats.report(self.stats.as_ref());
+        if let Some(table_id) = read_options.table_id {
+            stats.report_table(table_id, self.stats.as_ref());
+        }
         Ok(HummockStateStoreIter::new(user_iterator))
     }
 
@@ -230,21 +331,40 @@ impl HummockStorage {
         let mut stats = StoreLocalStatistic::default();
         let (shared_buffer_data, pinned_version) = self.read_filter(&read_options, &(key..=key))?;
 
+        // The range tombstones of the data visited so far. As newer data is visited first, they
+        // cover the key found in the current data if it's ever deleted by a range tombstone.
+        let mut range_tombstones = RangeTombstones::new(epoch);
+
         // Return `Some(None)` means the key is deleted.
-        let get_from_batch = |batch: &SharedBufferBatch| -> Option<Option<Bytes>> {
+        let get_from_batch = |batch: &SharedBufferBatch,
+                              range_tombstones: &mut RangeTombstones|
+         -> Option<Option<Bytes>> {
+            range_tombstones.extend(batch.range_tombstone());
             batch.get(key).map(|v| {
                 self.stats.get_shared_buffer_hit_counts.inc();
+                if range_tombstones.is_deleted(key, batch.epoch()) {
+                    return None;
+                }
                 v.into_user_value().map(|v| v.into())
             })
         };
 
         let mut table_counts = 0;
         let internal_key = key_with_epoch(key.to_vec(), epoch);
+        // The bloom filter keys of some tables are extracted by their slice transforms.
+        let sliced_key = get_table_id(key).and_then(|table_id| {
+            match self.table_id_to_slice_transform.read().get(&table_id) {
+                Some(SliceTransformImpl::Schema(slice_transform)) => {
+                    Some(slice_transform.extract(key))
+                }
+                _ => None,
+            }
+        });
 
         // Query shared buffer. Return the value without iterating SSTs if found
         for (replicated_batches, uncommitted_data) in shared_buffer_data {
             for batch in replicated_batches {
-                if let Some(v) = get_from_batch(&batch) {
+                if let Some(v) = get_from_batch(&batch, &mut range_tombstones) {
                     return Ok(v);
                 }
             }
@@ -253,7 +373,7 @@ impl HummockStorage {
                 for data in data_list {
                     match data {
                         UncommittedData::Batch(batch) => {
-                            if let Some(v) = get_from_batch(&batch) {
+                            if let Some(v) = get_from_batch(&batch, &mut range_tombstones) {
                                 return Ok(v);
                             }
                         }
@@ -263,11 +383,14 @@ impl HummockStorage {
                                 .sstable(table_info.id, &mut stats)
                                 .await?;
                             table_counts += 1;
+                            range_tombstones.extend(&table.value().meta.range_tombstones);
                             if let Some(v) = self
                                 .get_from_table(
                                     table,
                                     &internal_key,
                                     key,
+                                    &range_tombstones,
+                                    sliced_key,
                                     &read_options,
                                     &mut stats,
                                 )
@@ -288,15 +411,34 @@ impl HummockStorage {
                 continue;
             }
             {
-                let table_infos = prune_ssts(level.table_infos.iter(), &(key..=key));
+                // The SSTs of a nonoverlapping level are sorted and disjoint, so only the one found
+                // by binary search may contain the key.
+                let table_infos = if level.level_type == LevelType::Nonoverlapping as i32 {
+                    let table_idx = search_sst_idx(&level.table_infos, key);
+                    prune_ssts(
+                        level.table_infos[table_idx..=table_idx].iter(),
+                        &(key..=key),
+                    )
+                } else {
+                    prune_ssts(level.table_infos.iter(), &(key..=key))
+                };
                 for table_info in table_infos.into_iter().rev() {
                     let table = self
                         .sstable_store
                         .sstable(table_info.id, &mut stats)
                         .await?;
                     table_counts += 1;
+                    range_tombstones.extend(&table.value().meta.range_tombstones);
                     if let Some(v) = self
-                        .get_from_table(table, &internal_key, key, &read_options, &mut stats)
+                        .get_from_table(
+                            table,
+                            &internal_key,
+                            key,
+                            &range_tombstones,
+                            sliced_key,
+                            &read_options,
+                            &mut stats,
+                        )
                         .await?
                     {
                         return Ok(v);
@@ -306,6 +448,9 @@ impl HummockStorage {
         }
 
         stats.report(self.stats.as_ref());
+        if let Some(table_id) = read_options.table_id {
+            stats.report_table(table_id, self.stats.as_ref());
+        }
         self.stats
             .iter_merge_sstable_counts
             .with_label_values(&["sub-iter"])
@@ -313,6 +458,36 @@ impl HummockStorage {
         Ok(None)
     }
 
+    /// Deletes all the keys in `[start_key, end_key)` written at or before `write_options.epoch`,
+    /// by writing a range tombstone instead of a delete for each key.
+    ///
+    /// Note that keys written at the same epoch are deleted as well, even if they are written
+    /// after the tombstone.
+    pub async fn delete_range(
+        &self,
+        start_key: &[u8],
+        end_key: &[u8],
+        write_options: WriteOptions,
+    ) -> StorageResult<usize> {
+        if start_key >= end_key {
+            return Ok(0);
+        }
+        let compaction_group_id = self.get_compaction_group_id(write_options.table_id).await?;
+        let range_tombstone =
+            RangeTombstone::new(start_key.to_vec(), end_key.to_vec(), write_options.epoch);
+        // See comments in HummockStorage::iter_inner for details about using compaction_group_id
+        // in read/write path.
+        let size = self
+            .local_version_manager
+            .write_range_tombstone(
+                compaction_group_id,
+                range_tombstone,
+                write_options.table_id.into(),
+            )
+            .await?;
+        Ok(size)
+    }
+
     #[expect(clippy::type_complexity)]
     fn read_filter<R, B>(
         &self,
@@ -340,6 +515,36 @@ impl HummockStorage {
 
         Ok((shared_buffer_data, read_version.pinned_version))
     }
+
+    /// Returns the table and the bloom filter key shared by all the keys in `key_range`, if the
+    /// bloom filter keys of the table are extracted by a prefix of the pk, and the range is within
+    /// a single prefix.
+    fn range_bloom_key<R, B>(&self, key_range: &R) -> Option<(u32, Vec<u8>)>
+    where
+        R: RangeBounds<B>,
+        B: AsRef<[u8]>,
+    {
+        let start_key = match key_range.start_bound() {
+            Included(key) | Excluded(key) => key.as_ref(),
+            Unbounded => return None,
+        };
+        let table_id = get_table_id(start_key)?;
+        let bloom_key = match self.table_id_to_slice_transform.read().get(&table_id) {
+            Some(SliceTransformImpl::Schema(slice_transform)) => {
+                slice_transform.extract_prefix(start_key)?.to_vec()
+            }
+            _ => return None,
+        };
+        // All the keys between two keys with the same prefix have the prefix as well.
+        let within_prefix = match key_range.end_bound() {
+            Included(key) => key.as_ref().starts_with(&bloom_key),
+            Excluded(key) => {
+                key.as_ref().starts_with(&bloom_key) || key.as_ref() == next_key(&bloom_key)
+            }
+            Unbounded => false,
+        };
+        within_prefix.then(|| (table_id, bloom_key))
+    }
 }
 
 impl StateStore for HummockStorage {
@@ -420,6 +625,15 @@ impl StateStore for HummockStorage {
         }
     }
 
+    fn delete_range<'a>(
+        &'a self,
+        start_key: &'a [u8],
+        end_key: &'a [u8],
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move { self.delete_range(start_key, end_key, write_options).await }
+    }
+
     /// Replicates a batch to shared buffer, without uploading to the storage backend.
     fn replicate_batch(
         &self,
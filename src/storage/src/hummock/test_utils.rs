@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -49,6 +50,8 @@ pub fn default_config_for_test() -> StorageConfig {
         local_object_store: "memory".to_string(),
         share_buffer_upload_concurrency: 1,
         compactor_memory_limit_mb: 64,
+        verify_block_checksum: true,
+        iter_open_sstable_concurrency: 4,
     }
 }
 
@@ -79,6 +82,11 @@ pub fn gen_dummy_sst_info(id: HummockSstableId, batches: Vec<SharedBufferBatch>)
         }),
         file_size: batches.len() as u64,
         table_ids: vec![],
+        table_key_counts: HashMap::new(),
+        range_tombstone_count: batches
+            .iter()
+            .filter(|batch| batch.range_tombstone().is_some())
+            .count() as u32,
     }
 }
 
@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::RangeBounds;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 
-use risingwave_hummock_sdk::key::user_key;
+use risingwave_hummock_sdk::key::{get_epoch, user_key};
+use risingwave_hummock_sdk::HummockEpoch;
 use risingwave_pb::hummock::{Level, SstableInfo};
 use tokio::sync::Notify;
 
@@ -103,23 +105,29 @@ where
 pub fn can_concat(ssts: &[&SstableInfo]) -> bool {
     let len = ssts.len();
     for i in 0..len - 1 {
-        if user_key(&ssts[i].get_key_range().as_ref().unwrap().right).cmp(user_key(
+        let right = &ssts[i].get_key_range().as_ref().unwrap().right;
+        match user_key(right).cmp(user_key(
             &ssts[i + 1].get_key_range().as_ref().unwrap().left,
-        )) != Ordering::Less
-        {
-            return false;
+        )) {
+            Ordering::Less => {}
+            // A right key with the max epoch is the exclusive end of a range tombstone, which
+            // covers no version of its user key.
+            Ordering::Equal if get_epoch(right) == HummockEpoch::MAX => {}
+            _ => return false,
         }
     }
     true
 }
 
 /// Search the SST containing the specified key within a level, using binary search.
-pub(crate) fn search_sst_idx<B>(ssts: &[&SstableInfo], key: &B) -> usize
+pub(crate) fn search_sst_idx<S, B>(ssts: &[S], key: &B) -> usize
 where
+    S: Borrow<SstableInfo>,
     B: AsRef<[u8]> + Send + ?Sized,
 {
     ssts.partition_point(|table| {
-        let ord = user_key(&table.key_range.as_ref().unwrap().left).cmp(key.as_ref());
+        let left = &table.borrow().key_range.as_ref().unwrap().left;
+        let ord = user_key(left).cmp(key.as_ref());
         ord == Ordering::Less || ord == Ordering::Equal
     })
     .saturating_sub(1) // considering the boundary of 0
@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use std::cmp::Reverse;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::{Bound, RangeBounds};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -37,12 +38,21 @@ type KeyWithEpoch = (Bytes, Reverse<u64>);
 /// so the memory usage will be high. At the same time, every time we create a new iterator on
 /// `BTreeMap`, it will fully clone the map, so as to act as a snapshot. Therefore, in-memory state
 /// store should never be used in production.
+///
+/// A store created by [`MemoryStateStore::with_capacity`] rejects write batches once the
+/// approximate size of the stored entries would exceed the capacity, instead of growing without
+/// bound. Since old versions may still be read by any epoch, nothing is ever evicted.
 #[derive(Clone)]
 pub struct MemoryStateStore {
     /// Stores (key, epoch) -> user value. We currently don't consider value meta here.
     inner: Arc<RwLock<BTreeMap<KeyWithEpoch, Option<Bytes>>>>,
     /// current largest committed epoch,
     epoch: Option<u64>,
+    /// Approximate size of all entries in `inner`, in bytes. Only updated with the write lock of
+    /// `inner` held.
+    usage: Arc<AtomicUsize>,
+    /// Upper bound of `usage`. `None` means unbounded.
+    capacity: Option<usize>,
 }
 
 impl Default for MemoryStateStore {
@@ -69,14 +79,34 @@ where
     (start, end)
 }
 
+/// Returns the approximate memory usage of an entry in [`MemoryStateStore`].
+fn entry_size(key: &Bytes, value: &Option<Bytes>) -> usize {
+    key.len() + std::mem::size_of::<u64>() + value.as_ref().map_or(0, |v| v.len())
+}
+
 impl MemoryStateStore {
     pub fn new() -> Self {
         Self {
             inner: Arc::new(RwLock::new(BTreeMap::new())),
             epoch: None,
+            usage: Arc::new(AtomicUsize::new(0)),
+            capacity: None,
+        }
+    }
+
+    /// Creates a store that holds at most `capacity` bytes of kv pairs.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::new()
         }
     }
 
+    /// Returns the approximate size of all stored kv pairs, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.usage.load(Ordering::Relaxed)
+    }
+
     pub fn shared() -> Self {
         lazy_static! {
             static ref STORE: MemoryStateStore = MemoryStateStore::new();
@@ -181,15 +211,81 @@ impl StateStore for MemoryStateStore {
         async move {
             let epoch = write_options.epoch;
             let mut inner = self.inner.write();
+            let mut usage = self.usage.load(Ordering::Relaxed);
+            // Check before inserting anything, so that a rejected batch leaves no partial writes.
+            if let Some(capacity) = self.capacity {
+                // The entries overwritten by the batch are freed, including the ones written
+                // earlier in the same batch, so only the net growth of each key counts.
+                let mut new_usage = usage;
+                let mut batch_sizes: HashMap<&Bytes, usize> = HashMap::new();
+                for (key, value) in &kv_pairs {
+                    let new_size = entry_size(key, &value.user_value);
+                    let old_size = match batch_sizes.get(key) {
+                        Some(size) => *size,
+                        None => inner
+                            .get(&(key.clone(), Reverse(epoch)))
+                            .map_or(0, |old_value| entry_size(key, old_value)),
+                    };
+                    batch_sizes.insert(key, new_size);
+                    new_usage = new_usage + new_size - old_size;
+                }
+                if new_usage > capacity {
+                    return Err(StorageError::MemoryCapacityExceeded {
+                        usage: new_usage,
+                        capacity,
+                    });
+                }
+            }
             let mut size: usize = 0;
             for (key, value) in kv_pairs {
                 size += key.len() + value.size();
-                inner.insert((key, Reverse(epoch)), value.user_value);
+                usage += entry_size(&key, &value.user_value);
+                if let Some(old_value) =
+                    inner.insert((key.clone(), Reverse(epoch)), value.user_value)
+                {
+                    usage -= entry_size(&key, &old_value);
+                }
             }
+            self.usage.store(usage, Ordering::Relaxed);
             Ok(size)
         }
     }
 
+    fn delete_range<'a>(
+        &'a self,
+        start_key: &'a [u8],
+        end_key: &'a [u8],
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            if start_key >= end_key {
+                return Ok(0);
+            }
+            // There's no range tombstone in memory, so the visible keys are deleted one by one.
+            let kv_pairs = {
+                let epoch = write_options.epoch;
+                let inner = self.inner.read();
+                let mut kv_pairs = vec![];
+                let mut last_key = None;
+                for ((key, Reverse(key_epoch)), value) in
+                    inner.range(to_bytes_range(start_key..end_key))
+                {
+                    if *key_epoch > epoch {
+                        continue;
+                    }
+                    if Some(key) != last_key.as_ref() {
+                        if value.is_some() {
+                            kv_pairs.push((key.clone(), StorageValue::new_default_delete()));
+                        }
+                        last_key = Some(key.clone());
+                    }
+                }
+                kv_pairs
+            };
+            self.ingest_batch(kv_pairs, write_options).await
+        }
+    }
+
     fn replicate_batch(
         &self,
         _kv_pairs: Vec<(Bytes, StorageValue)>,
@@ -443,4 +539,69 @@ mod tests {
             None
         );
     }
+
+    #[tokio::test]
+    async fn test_capacity() {
+        let state_store = MemoryStateStore::with_capacity(64);
+        let write_options = WriteOptions {
+            epoch: 1,
+            table_id: Default::default(),
+        };
+        state_store
+            .ingest_batch(
+                vec![(
+                    b"a".to_vec().into(),
+                    StorageValue::new_default_put(vec![0; 32]),
+                )],
+                write_options.clone(),
+            )
+            .await
+            .unwrap();
+        let usage = state_store.memory_usage();
+        assert!(usage > 0);
+
+        // Overwriting the same version does not grow the usage.
+        state_store
+            .ingest_batch(
+                vec![(
+                    b"a".to_vec().into(),
+                    StorageValue::new_default_put(vec![1; 32]),
+                )],
+                write_options.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(state_store.memory_usage(), usage);
+
+        // A batch that exceeds the capacity is rejected as a whole.
+        let err = state_store
+            .ingest_batch(
+                vec![
+                    (b"b".to_vec().into(), StorageValue::new_default_delete()),
+                    (
+                        b"c".to_vec().into(),
+                        StorageValue::new_default_put(vec![0; 32]),
+                    ),
+                ],
+                write_options,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::MemoryCapacityExceeded { .. }));
+        assert_eq!(state_store.memory_usage(), usage);
+        assert_eq!(
+            state_store
+                .get(
+                    b"b",
+                    ReadOptions {
+                        epoch: 1,
+                        table_id: Default::default(),
+                        ttl: None,
+                    }
+                )
+                .await
+                .unwrap(),
+            None
+        );
+    }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use risingwave_common::catalog::TableId;
+
 use crate::monitor::StateStoreMetrics;
 
 #[derive(Default)]
@@ -89,4 +91,25 @@ impl StoreLocalStatistic {
             metrics.remote_read_time.observe(self.remote_io_time);
         }
     }
+
+    /// Report the data block cache hits and misses of reading the table with `table_id`.
+    pub fn report_table(&self, table_id: TableId, metrics: &StateStoreMetrics) {
+        if self.cache_data_block_total == 0 {
+            return;
+        }
+        let table_id = table_id.table_id.to_string();
+        let cache_data_block_hit = self.cache_data_block_total - self.cache_data_block_miss;
+        if cache_data_block_hit > 0 {
+            metrics
+                .table_block_cache_requests
+                .with_label_values(&[&table_id, "hit"])
+                .inc_by(cache_data_block_hit);
+        }
+        if self.cache_data_block_miss > 0 {
+            metrics
+                .table_block_cache_requests
+                .with_label_values(&[&table_id, "miss"])
+                .inc_by(self.cache_data_block_miss);
+        }
+    }
 }
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 use futures::Future;
+use risingwave_common::catalog::TableId;
 use risingwave_hummock_sdk::LocalSstableInfo;
 use tracing::error;
 
@@ -49,6 +50,7 @@ where
 {
     async fn monitored_iter<'a, I>(
         &self,
+        table_id: Option<TableId>,
         iter: I,
     ) -> StorageResult<<MonitoredStateStore<S> as StateStore>::Iter>
     where
@@ -68,7 +70,9 @@ where
         // create a monitored iterator to collect metrics
         let monitored = MonitoredStateStoreIter {
             inner: iter,
+            table_id,
             total_items: 0,
+            total_keys: 0,
             total_size: 0,
             start_time,
             scan_time: minstant::Instant::now(),
@@ -80,6 +84,22 @@ where
     pub fn stats(&self) -> Arc<StateStoreMetrics> {
         self.stats.clone()
     }
+
+    fn report_table_read_keys(&self, table_id: Option<TableId>, key_count: usize) {
+        report_table_read_keys(&self.stats, table_id, key_count);
+    }
+}
+
+fn report_table_read_keys(stats: &StateStoreMetrics, table_id: Option<TableId>, key_count: usize) {
+    if key_count == 0 {
+        return;
+    }
+    if let Some(table_id) = table_id {
+        stats
+            .table_read_keys
+            .with_label_values(&[&table_id.table_id.to_string()])
+            .inc_by(key_count as _);
+    }
 }
 
 impl<S> StateStore for MonitoredStateStore<S>
@@ -93,6 +113,7 @@ where
     fn get<'a>(&'a self, key: &'a [u8], read_options: ReadOptions) -> Self::GetFuture<'_> {
         async move {
             let timer = self.stats.get_duration.start_timer();
+            let table_id = read_options.table_id;
             let value = self
                 .inner
                 .get(key, read_options)
@@ -104,6 +125,7 @@ where
             if let Some(value) = value.as_ref() {
                 self.stats.get_value_size.observe(value.len() as _);
             }
+            self.report_table_read_keys(table_id, value.is_some() as usize);
 
             Ok(value)
         }
@@ -121,6 +143,7 @@ where
     {
         async move {
             let timer = self.stats.range_scan_duration.start_timer();
+            let table_id = read_options.table_id;
             let result = self
                 .inner
                 .scan(key_range, limit, read_options)
@@ -131,6 +154,7 @@ where
             self.stats
                 .range_scan_size
                 .observe(result.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>() as _);
+            self.report_table_read_keys(table_id, result.len());
 
             Ok(result)
         }
@@ -148,6 +172,7 @@ where
     {
         async move {
             let timer = self.stats.range_backward_scan_duration.start_timer();
+            let table_id = read_options.table_id;
             let result = self
                 .inner
                 .scan(key_range, limit, read_options)
@@ -158,6 +183,7 @@ where
             self.stats
                 .range_backward_scan_size
                 .observe(result.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>() as _);
+            self.report_table_read_keys(table_id, result.len());
 
             Ok(result)
         }
@@ -177,6 +203,7 @@ where
                 .write_batch_tuple_counts
                 .inc_by(kv_pairs.len() as _);
             let timer = self.stats.write_batch_duration.start_timer();
+            let table_id = write_options.table_id;
             let batch_size = self
                 .inner
                 .ingest_batch(kv_pairs, write_options)
@@ -185,17 +212,36 @@ where
             timer.observe_duration();
 
             self.stats.write_batch_size.observe(batch_size as _);
+            self.stats
+                .table_write_bytes
+                .with_label_values(&[&table_id.table_id.to_string()])
+                .inc_by(batch_size as _);
             Ok(batch_size)
         }
     }
 
+    fn delete_range<'a>(
+        &'a self,
+        start_key: &'a [u8],
+        end_key: &'a [u8],
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            self.inner
+                .delete_range(start_key, end_key, write_options)
+                .await
+                .inspect_err(|e| error!("Failed in delete_range: {:?}", e))
+        }
+    }
+
     fn iter<R, B>(&self, key_range: R, read_options: ReadOptions) -> Self::IterFuture<'_, R, B>
     where
         R: RangeBounds<B> + Send,
         B: AsRef<[u8]> + Send,
     {
         async move {
-            self.monitored_iter(self.inner.iter(key_range, read_options))
+            let table_id = read_options.table_id;
+            self.monitored_iter(table_id, self.inner.iter(key_range, read_options))
                 .await
         }
     }
@@ -210,7 +256,8 @@ where
         B: AsRef<[u8]> + Send,
     {
         async move {
-            self.monitored_iter(self.inner.backward_iter(key_range, read_options))
+            let table_id = read_options.table_id;
+            self.monitored_iter(table_id, self.inner.backward_iter(key_range, read_options))
                 .await
         }
     }
@@ -280,7 +327,9 @@ impl MonitoredStateStore<HummockStorage> {
 /// A state store iterator wrapper for monitoring metrics.
 pub struct MonitoredStateStoreIter<I> {
     inner: I,
+    table_id: Option<TableId>,
     total_items: usize,
+    total_keys: usize,
     total_size: usize,
     start_time: minstant::Instant,
     scan_time: minstant::Instant,
@@ -305,6 +354,7 @@ where
                 .inspect_err(|e| error!("Failed in next: {:?}", e))?;
 
             self.total_items += 1;
+            self.total_keys += pair.is_some() as usize;
             self.total_size += pair
                 .as_ref()
                 .map(|(k, v)| k.len() + v.len())
@@ -325,5 +375,6 @@ impl<I> Drop for MonitoredStateStoreIter<I> {
             .observe(self.scan_time.elapsed().as_secs_f64());
         self.stats.iter_item.observe(self.total_items as f64);
         self.stats.iter_size.observe(self.total_size as f64);
+        report_table_read_keys(&self.stats, self.table_id, self.total_keys);
     }
 }
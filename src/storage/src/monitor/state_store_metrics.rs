@@ -59,6 +59,7 @@ macro_rules! for_all_metrics {
             iter_merge_sstable_counts: HistogramVec,
 
             sst_store_block_request_counts: GenericCounterVec<AtomicU64>,
+            sst_store_block_checksum_mismatch_counts: GenericCounter<AtomicU64>,
 
             shared_buffer_to_l0_duration: Histogram,
             shared_buffer_to_sstable_size: Histogram,
@@ -77,6 +78,10 @@ macro_rules! for_all_metrics {
 
             get_table_id_total_time_duration: Histogram,
             remote_read_time: Histogram,
+
+            table_read_keys: GenericCounterVec<AtomicU64>,
+            table_write_bytes: GenericCounterVec<AtomicU64>,
+            table_block_cache_requests: GenericCounterVec<AtomicU64>,
         }
     };
 }
@@ -282,6 +287,13 @@ impl StateStoreMetrics {
         )
         .unwrap();
 
+        let sst_store_block_checksum_mismatch_counts = register_int_counter_with_registry!(
+            "state_store_sst_store_block_checksum_mismatch_counts",
+            "Total number of sst blocks read from the object store with mismatched checksums",
+            registry
+        )
+        .unwrap();
+
         // --
         let compaction_upload_sst_counts = register_int_counter_with_registry!(
             "state_store_compaction_upload_sst_counts",
@@ -380,6 +392,31 @@ impl StateStoreMetrics {
         )
         .unwrap();
 
+        // ----- per table -----
+        let table_read_keys = register_int_counter_vec_with_registry!(
+            "state_store_table_read_keys",
+            "Total number of keys read by get and scan from each table",
+            &["table_id"],
+            registry
+        )
+        .unwrap();
+
+        let table_write_bytes = register_int_counter_vec_with_registry!(
+            "state_store_table_write_bytes",
+            "Total bytes of batched write that have been issued to each table",
+            &["table_id"],
+            registry
+        )
+        .unwrap();
+
+        let table_block_cache_requests = register_int_counter_vec_with_registry!(
+            "state_store_table_block_cache_requests",
+            "Total number of data block cache hits and misses when reading each table",
+            &["table_id", "type"],
+            registry
+        )
+        .unwrap();
+
         Self {
             get_duration,
             get_key_size,
@@ -403,6 +440,7 @@ impl StateStoreMetrics {
             write_build_l0_bytes,
             iter_merge_sstable_counts,
             sst_store_block_request_counts,
+            sst_store_block_checksum_mismatch_counts,
             shared_buffer_to_l0_duration,
             shared_buffer_to_sstable_size,
 
@@ -420,6 +458,10 @@ impl StateStoreMetrics {
 
             get_table_id_total_time_duration,
             remote_read_time,
+
+            table_read_keys,
+            table_write_bytes,
+            table_block_cache_requests,
         }
     }
 
@@ -434,6 +476,7 @@ struct StateStoreCollector {
     meta_cache: Arc<LruCache<HummockSstableId, Box<Sstable>>>,
     descs: Vec<Desc>,
     block_cache_size: IntGauge,
+    block_cache_eviction_count: IntGauge,
     meta_cache_size: IntGauge,
     limit_memory_size: IntGauge,
     memory_limiter: Arc<MemoryLimiter>,
@@ -450,6 +493,13 @@ impl StateStoreCollector {
         .unwrap();
         descs.extend(block_cache_size.desc().into_iter().cloned());
 
+        let block_cache_eviction_count = IntGauge::with_opts(Opts::new(
+            "state_store_block_cache_eviction_count",
+            "the number of blocks evicted from data block cache",
+        ))
+        .unwrap();
+        descs.extend(block_cache_eviction_count.desc().into_iter().cloned());
+
         let meta_cache_size = IntGauge::with_opts(Opts::new(
             "state_store_meta_cache_size",
             "the size of cache for meta file cache",
@@ -468,6 +518,7 @@ impl StateStoreCollector {
             meta_cache: sstable_store.get_meta_cache(),
             descs,
             block_cache_size,
+            block_cache_eviction_count,
             meta_cache_size,
             memory_limiter,
             limit_memory_size,
@@ -482,14 +533,17 @@ impl Collector for StateStoreCollector {
 
     fn collect(&self) -> Vec<proto::MetricFamily> {
         self.block_cache_size.set(self.block_cache.size() as i64);
+        self.block_cache_eviction_count
+            .set(self.block_cache.eviction_count() as i64);
         self.meta_cache_size
             .set(self.meta_cache.get_memory_usage() as i64);
         self.limit_memory_size
             .set(self.memory_limiter.get_memory_usage() as i64);
 
         // collect MetricFamilies.
-        let mut mfs = Vec::with_capacity(2);
+        let mut mfs = Vec::with_capacity(4);
         mfs.extend(self.block_cache_size.collect());
+        mfs.extend(self.block_cache_eviction_count.collect());
         mfs.extend(self.meta_cache_size.collect());
         mfs.extend(self.limit_memory_size.collect());
         mfs
@@ -77,6 +77,17 @@ impl StateStore for PanicStateStore {
         }
     }
 
+    fn delete_range<'a>(
+        &'a self,
+        _start_key: &'a [u8],
+        _end_key: &'a [u8],
+        _write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_> {
+        async move {
+            panic!("should not write the state store!");
+        }
+    }
+
     fn replicate_batch(
         &self,
         _kv_pairs: Vec<(Bytes, StorageValue)>,
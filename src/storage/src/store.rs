@@ -44,6 +44,7 @@ macro_rules! define_state_store_associated_type {
                 B: 'static + Send + AsRef<[u8]>;
 
         type IngestBatchFuture<'a> = impl IngestBatchFutureTrait<'a>;
+        type DeleteRangeFuture<'a> = impl IngestBatchFutureTrait<'a>;
         type ReplicateBatchFuture<'a> = impl EmptyFutureTrait<'a>;
         type WaitEpochFuture<'a> = impl EmptyFutureTrait<'a>;
         type SyncFuture<'a> = impl EmptyFutureTrait<'a>;
@@ -76,6 +77,8 @@ pub trait StateStore: Send + Sync + 'static + Clone {
 
     type IngestBatchFuture<'a>: IngestBatchFutureTrait<'a>;
 
+    type DeleteRangeFuture<'a>: IngestBatchFutureTrait<'a>;
+
     type ReplicateBatchFuture<'a>: EmptyFutureTrait<'a>;
 
     type WaitEpochFuture<'a>: EmptyFutureTrait<'a>;
@@ -138,6 +141,16 @@ pub trait StateStore: Send + Sync + 'static + Clone {
         write_options: WriteOptions,
     ) -> Self::IngestBatchFuture<'_>;
 
+    /// Deletes all the keys in `[start_key, end_key)` visible at `write_options.epoch`. Keys
+    /// written at the same epoch may be deleted as well, so the caller should not write into
+    /// the range in that epoch.
+    fn delete_range<'a>(
+        &'a self,
+        start_key: &'a [u8],
+        end_key: &'a [u8],
+        write_options: WriteOptions,
+    ) -> Self::DeleteRangeFuture<'_>;
+
     /// Functions the same as `ingest_batch`, except that data won't be persisted.
     fn replicate_batch(
         &self,
@@ -120,6 +120,8 @@ impl StateStoreImpl {
                     config.data_directory.to_string(),
                     config.block_cache_capacity_mb * (1 << 20),
                     config.meta_cache_capacity_mb * (1 << 20),
+                    config.verify_block_checksum,
+                    state_store_stats.clone(),
                 ));
                 let compaction_group_client =
                     Arc::new(CompactionGroupClientImpl::new(hummock_meta_client.clone()));
@@ -136,6 +136,11 @@ impl MemTable {
         }
     }
 
+    /// Drops the operations on the pks below `end`.
+    pub fn remove_below(&mut self, end: &[u8]) {
+        self.buffer = self.buffer.split_off(end);
+    }
+
     pub fn into_parts(self) -> BTreeMap<Vec<u8>, RowOp> {
         self.buffer
     }
@@ -27,6 +27,7 @@ use risingwave_common::array::{DataChunk, Row};
 use risingwave_common::buffer::{Bitmap, BitmapBuilder};
 use risingwave_common::catalog::Schema;
 use risingwave_common::types::VIRTUAL_NODE_COUNT;
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
 
 use crate::error::StorageResult;
 use crate::table::storage_table::DEFAULT_VNODE;
@@ -76,15 +77,19 @@ impl Distribution {
 pub trait TableIter: Send {
     async fn next_row(&mut self) -> StorageResult<Option<Row>>;
 
+    /// Collects at most `chunk_size` rows into a chunk, or [`DEFAULT_CHUNK_BUFFER_SIZE`] rows if
+    /// not given. Returns `None` if there are no more rows.
     async fn collect_data_chunk(
         &mut self,
         schema: &Schema,
         chunk_size: Option<usize>,
     ) -> StorageResult<Option<DataChunk>> {
-        let mut builders = schema.create_array_builders(chunk_size.unwrap_or(0));
+        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_BUFFER_SIZE);
+        assert!(chunk_size > 0, "chunk size must be positive");
+        let mut builders = schema.create_array_builders(chunk_size);
 
         let mut row_count = 0;
-        for _ in 0..chunk_size.unwrap_or(usize::MAX) {
+        for _ in 0..chunk_size {
             match self.next_row().await? {
                 Some(row) => {
                     for (datum, builder) in row.0.into_iter().zip_eq(builders.iter_mut()) {
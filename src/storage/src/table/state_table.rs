@@ -23,6 +23,7 @@ use futures_async_stream::try_stream;
 use risingwave_common::array::Row;
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::{ColumnDesc, TableId};
+use risingwave_common::types::Datum;
 use risingwave_common::util::ordered::OrderedRowSerializer;
 use risingwave_common::util::sort_util::OrderType;
 use risingwave_hummock_sdk::key::range_of_prefix;
@@ -183,6 +184,18 @@ impl<S: StateStore, RS: RowSerde> StateTableBase<S, RS> {
         Ok(())
     }
 
+    /// Deletes the rows whose first pk column is below `bound`, including the uncommitted ones. The
+    /// rows written into the range afterwards in the same epoch may be deleted as well, so it
+    /// should be called right before `commit` with the same epoch.
+    pub async fn delete_range_below(&mut self, bound: &Datum, epoch: u64) -> StorageResult<()> {
+        let encoded_bound = serialize_pk(
+            &Row::new(vec![bound.clone()]),
+            &self.pk_serializer().prefix(1),
+        );
+        self.mem_table.remove_below(&encoded_bound);
+        self.storage_table.delete_range_below(bound, epoch).await
+    }
+
     pub async fn commit(&mut self, new_epoch: u64) -> StorageResult<()> {
         let mem_table = std::mem::take(&mut self.mem_table).into_parts();
         self.storage_table
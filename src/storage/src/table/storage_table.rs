@@ -624,6 +624,35 @@ impl<S: StateStore, RS: RowSerde> StorageTableBase<S, RS, READ_WRITE> {
         batch.ingest().await?;
         Ok(())
     }
+
+    /// Deletes the rows whose first pk column is below `bound`, with a range deletion in each vnode
+    /// of this table. The rows with a null first pk column are kept, as the column must be in
+    /// ascending order.
+    pub async fn delete_range_below(&mut self, bound: &Datum, epoch: u64) -> StorageResult<()> {
+        let encoded_bound = serialize_pk(
+            &Row::new(vec![bound.clone()]),
+            &self.pk_serializer.prefix(1),
+        );
+        let write_options = WriteOptions {
+            epoch,
+            table_id: self.keyspace.table_id(),
+        };
+        let store = self.keyspace.state_store();
+        for vnode in self
+            .vnodes
+            .iter()
+            .enumerate()
+            .filter(|&(_, set)| set)
+            .map(|(i, _)| i as VirtualNode)
+        {
+            let start_key = self.keyspace.prefixed_key(vnode.to_be_bytes());
+            let end_key = [start_key.as_slice(), encoded_bound.as_slice()].concat();
+            store
+                .delete_range(&start_key, &end_key, write_options.clone())
+                .await?;
+        }
+        Ok(())
+    }
 }
 
 pub trait PkAndRowStream = Stream<Item = StorageResult<(Vec<u8>, Row)>> + Send;
@@ -19,6 +19,7 @@ use itertools::Itertools;
 use risingwave_common::array::Row;
 use risingwave_common::catalog::{ColumnDesc, ColumnId, OrderedColumnDesc, TableId};
 use risingwave_common::types::DataType;
+use risingwave_common::util::chunk_coalesce::DEFAULT_CHUNK_BUFFER_SIZE;
 use risingwave_common::util::ordered::OrderedRowSerializer;
 use risingwave_common::util::sort_util::OrderType;
 
@@ -1381,6 +1382,36 @@ async fn test_cell_based_scan_empty_column_ids_cardinality() {
     assert_eq!(chunk.cardinality(), 2);
 }
 
+#[tokio::test]
+async fn test_collect_data_chunk_with_default_size() {
+    let state_store = MemoryStateStore::new();
+    let column_descs = vec![ColumnDesc::unnamed(ColumnId::from(0), DataType::Int32)];
+    let mut state = StateTable::new_without_distribution(
+        state_store,
+        TableId::from(0x42),
+        column_descs,
+        vec![OrderType::Ascending],
+        vec![0],
+    );
+    let table = state.storage_table().clone();
+    let epoch: u64 = 0;
+
+    let row_count = DEFAULT_CHUNK_BUFFER_SIZE + 1;
+    for i in 0..row_count {
+        state.insert(Row(vec![Some((i as i32).into())])).unwrap();
+    }
+    state.commit(epoch).await.unwrap();
+
+    // Without a chunk size, the rows are split into chunks of the default size.
+    let iter = table.batch_iter(u64::MAX).await.unwrap();
+    pin_mut!(iter);
+    let mut cardinalities = vec![];
+    while let Some(chunk) = iter.collect_data_chunk(table.schema(), None).await.unwrap() {
+        cardinalities.push(chunk.cardinality());
+    }
+    assert_eq!(cardinalities, vec![DEFAULT_CHUNK_BUFFER_SIZE, 1]);
+}
+
 #[tokio::test]
 async fn test_state_table_iter_with_prefix() {
     let state_store = MemoryStateStore::new();
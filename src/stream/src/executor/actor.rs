@@ -81,6 +81,7 @@ impl OperatorInfoStatus {
                         .push_back((epoch, Instant::now()))
                 }
             }
+            Message::Watermark(_) => {}
         }
     }
 }
@@ -153,7 +154,18 @@ where
         pin_mut!(stream);
 
         // Drive the streaming task with an infinite loop
-        while let Some(barrier) = stream.next().instrument(span).await.transpose()? {
+        loop {
+            let barrier = match stream.next().instrument(span).await {
+                Some(Ok(barrier)) => barrier,
+                Some(Err(err)) => {
+                    // Report the error while the outputs are still alive, so that the downstream
+                    // actors can tell why the channels are closed.
+                    self.context.report_actor_failure(self.id, &err);
+                    return Err(err);
+                }
+                None => break,
+            };
+
             {
                 // Calculate metrics
                 let prev_epoch = barrier.epoch.prev;
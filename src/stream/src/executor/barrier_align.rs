@@ -20,16 +20,20 @@ use futures_async_stream::try_stream;
 use madsim::time::Instant;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, BoxedMessageStream, Message, StreamChunk};
+use super::{Barrier, BoxedMessageStream, Message, StreamChunk, Watermark};
 use crate::executor::monitor::StreamingMetrics;
 
 #[derive(Debug, PartialEq)]
 pub enum AlignedMessage {
     Left(StreamChunk),
     Right(StreamChunk),
+    WatermarkLeft(Watermark),
+    WatermarkRight(Watermark),
     Barrier(Barrier),
 }
 
+/// Aligns the barriers of two streams. Watermarks are passed through with the side they come
+/// from.
 #[try_stream(ok = AlignedMessage, error = StreamExecutorError)]
 pub async fn barrier_align(
     mut left: BoxedMessageStream,
@@ -55,6 +59,9 @@ pub async fn barrier_align(
                 while let Some(msg) = right.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(_) => {
                             panic!("right barrier received while left stream end")
                         }
@@ -67,6 +74,9 @@ pub async fn barrier_align(
                 while let Some(msg) = left.next().await {
                     match msg? {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(_) => {
                             panic!("left barrier received while right stream end")
                         }
@@ -76,11 +86,15 @@ pub async fn barrier_align(
             }
             Either::Left((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkLeft(watermark),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
                     // received left barrier, waiting for right barrier
                     match right.next().await.unwrap()? {
                         Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkRight(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             metrics
@@ -94,11 +108,15 @@ pub async fn barrier_align(
             },
             Either::Right((Some(msg), _)) => match msg? {
                 Message::Chunk(chunk) => yield AlignedMessage::Right(chunk),
+                Message::Watermark(watermark) => yield AlignedMessage::WatermarkRight(watermark),
                 Message::Barrier(_) => loop {
                     let start_time = Instant::now();
                     // received right barrier, waiting for left barrier
                     match left.next().await.unwrap()? {
                         Message::Chunk(chunk) => yield AlignedMessage::Left(chunk),
+                        Message::Watermark(watermark) => {
+                            yield AlignedMessage::WatermarkLeft(watermark)
+                        }
                         Message::Barrier(barrier) => {
                             yield AlignedMessage::Barrier(barrier);
                             metrics
@@ -25,6 +25,12 @@ use crate::task::{ActorId, CreateMviewProgress};
 /// newly appended executors. Currently, [`ChainExecutor`] is mainly used to implement MV on MV
 /// feature. It pipes new data of existing MVs to newly created MV only all of the old data in the
 /// existing MVs are dispatched.
+///
+/// The barriers from the upstream are processed between the batches of the snapshot, reporting the
+/// rows consumed so far, while the other upstream messages are buffered and yielded after the
+/// snapshot. Once [`MAX_PENDING_UPSTREAM_ROWS`] rows are buffered, the upstream is not polled
+/// until the whole snapshot is consumed. Use [`super::RearrangedChainExecutor`] to keep processing
+/// the barriers without buffering.
 pub struct ChainExecutor {
     snapshot: BoxedExecutor,
 
@@ -39,6 +45,9 @@ pub struct ChainExecutor {
     info: ExecutorInfo,
 }
 
+/// The max number of rows from the upstream buffered while consuming the snapshot.
+const MAX_PENDING_UPSTREAM_ROWS: usize = 65536;
+
 fn mapping(upstream_indices: &[usize], chunk: StreamChunk) -> StreamChunk {
     let (ops, columns, visibility) = chunk.into_inner();
     let mapped_columns = upstream_indices
@@ -88,28 +97,73 @@ impl ChainExecutor {
 
         // 2. Consume the snapshot if needed. Note that the snapshot is already projected, so
         // there's no mapping required.
+        let mut consumed_rows = 0;
+        // The upstream messages other than barriers received meanwhile, with their rows.
+        let mut pending_upstream = vec![];
+        let mut pending_upstream_rows = 0;
+        let mut upstream_finished = false;
         if to_consume_snapshot {
             // Init the snapshot with reading epoch.
-            let snapshot = self.snapshot.execute_with_epoch(prev_epoch);
+            let mut snapshot = self.snapshot.execute_with_epoch(prev_epoch);
 
-            #[for_await]
-            for msg in snapshot {
-                yield msg?;
+            loop {
+                tokio::select! {
+                    msg = snapshot.next() => match msg {
+                        Some(msg) => {
+                            let msg = msg?;
+                            if let Message::Chunk(chunk) = &msg {
+                                consumed_rows += chunk.cardinality() as u64;
+                            }
+                            yield msg;
+                        }
+                        None => break,
+                    },
+                    // Process the upstream barriers between the batches of the snapshot.
+                    msg = upstream.next(),
+                        if !upstream_finished && pending_upstream_rows < MAX_PENDING_UPSTREAM_ROWS
+                        => match msg.transpose()? {
+                        Some(Message::Barrier(barrier)) => {
+                            self.progress.update_snapshot(barrier.epoch.curr, consumed_rows);
+                            yield Message::Barrier(barrier);
+                        }
+                        Some(msg) => {
+                            if let Message::Chunk(chunk) = &msg {
+                                pending_upstream_rows += chunk.cardinality();
+                            }
+                            pending_upstream.push(msg);
+                        }
+                        None => upstream_finished = true,
+                    },
+                }
             }
         }
 
-        // 3. Continuously consume the upstream. Report that we've finished the creation on the
-        // first barrier.
+        // 3. Continuously consume the upstream, starting from the messages buffered while
+        // consuming the snapshot. Report that we've finished the creation on the first barrier.
+        let upstream = if upstream_finished {
+            futures::stream::empty().boxed()
+        } else {
+            upstream
+        };
         #[for_await]
-        for msg in upstream {
+        for msg in futures::stream::iter(pending_upstream.into_iter().map(Ok)).chain(upstream) {
             match msg? {
                 Message::Chunk(chunk) => {
                     yield Message::Chunk(mapping(&self.upstream_indices, chunk));
                 }
                 Message::Barrier(barrier) => {
-                    self.progress.finish(barrier.epoch.curr);
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
                     yield Message::Barrier(barrier);
                 }
+                Message::Watermark(watermark) => {
+                    if let Some(idx) = self
+                        .upstream_indices
+                        .iter()
+                        .position(|&i| i == watermark.col_idx)
+                    {
+                        yield Message::Watermark(watermark.with_idx(idx));
+                    }
+                }
             }
         }
     }
@@ -193,10 +247,14 @@ mod test {
         let mut chain = Box::new(chain).execute();
         chain.next().await;
 
+        // The barriers of the upstream may be yielded between the chunks of the snapshot, while
+        // the chunks of the upstream are always yielded after the snapshot.
         let mut count = 0;
-        while let Some(Message::Chunk(ck)) = chain.next().await.transpose().unwrap() {
-            count += 1;
-            assert_eq!(ck, StreamChunk::from_pretty(&format!("I\n + {count}")));
+        while let Some(msg) = chain.next().await.transpose().unwrap() {
+            if let Message::Chunk(ck) = msg {
+                count += 1;
+                assert_eq!(ck, StreamChunk::from_pretty(&format!("I\n + {count}")));
+            }
         }
         assert_eq!(count, 4);
     }
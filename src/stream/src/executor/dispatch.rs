@@ -33,7 +33,7 @@ use tracing::event;
 
 use super::exchange::output::{new_output, BoxedOutput};
 use crate::executor::monitor::StreamingMetrics;
-use crate::executor::{Barrier, BoxedExecutor, Message, Mutation, StreamConsumer};
+use crate::executor::{Barrier, BoxedExecutor, Message, Mutation, StreamConsumer, Watermark};
 use crate::task::{ActorId, DispatcherId, SharedContext};
 
 /// [`DispatchExecutor`] consumes messages and send them into downstream actors. Usually,
@@ -82,6 +82,15 @@ impl DispatchExecutorInner {
                     .actor_output_buffer_blocking_duration_ns
                     .with_label_values(&[&self.actor_id_str])
                     .inc_by(start_time.elapsed().as_nanos() as u64);
+                self.metrics
+                    .actor_output_buffer_fullness
+                    .with_label_values(&[&self.actor_id_str])
+                    .set(
+                        self.dispatchers
+                            .iter()
+                            .map(|d| d.max_buffer_fullness())
+                            .fold(0.0, f64::max),
+                    );
             }
             Message::Barrier(barrier) => {
                 let start_time = minstant::Instant::now();
@@ -96,6 +105,11 @@ impl DispatchExecutorInner {
                     .with_label_values(&[&self.actor_id_str])
                     .inc_by(start_time.elapsed().as_nanos() as u64);
             }
+            Message::Watermark(watermark) => {
+                for dispatcher in &mut self.dispatchers {
+                    dispatcher.dispatch_watermark(watermark.clone()).await?;
+                }
+            }
         };
         Ok(())
     }
@@ -345,6 +359,12 @@ macro_rules! impl_dispatcher {
                 }
             }
 
+            pub async fn dispatch_watermark(&mut self, watermark: Watermark) -> Result<()> {
+                match self {
+                    $( Self::$variant_name(inner) => inner.dispatch_watermark(watermark).await, )*
+                }
+            }
+
             pub fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
                 match self {
                     $(Self::$variant_name(inner) => inner.add_outputs(outputs), )*
@@ -368,6 +388,12 @@ macro_rules! impl_dispatcher {
                     $(Self::$variant_name(inner) => inner.is_empty(), )*
                 }
             }
+
+            pub fn max_buffer_fullness(&self) -> f64 {
+                match self {
+                    $(Self::$variant_name(inner) => inner.max_buffer_fullness(), )*
+                }
+            }
         }
     }
 }
@@ -390,23 +416,36 @@ macro_rules! define_dispatcher_associated_types {
     () => {
         type DataFuture<'a> = impl DispatchFuture<'a>;
         type BarrierFuture<'a> = impl DispatchFuture<'a>;
+        type WatermarkFuture<'a> = impl DispatchFuture<'a>;
     };
 }
 
 pub trait DispatchFuture<'a> = Future<Output = Result<()>> + Send;
 
+fn max_buffer_fullness<'a>(outputs: impl Iterator<Item = &'a BoxedOutput>) -> f64 {
+    outputs
+        .map(|output| output.buffer_fullness())
+        .fold(0.0, f64::max)
+}
+
 pub trait Dispatcher: Debug + 'static {
     type DataFuture<'a>: DispatchFuture<'a>;
     type BarrierFuture<'a>: DispatchFuture<'a>;
+    type WatermarkFuture<'a>: DispatchFuture<'a>;
 
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_>;
     fn dispatch_barrier(&mut self, barrier: Barrier) -> Self::BarrierFuture<'_>;
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_>;
 
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>);
     fn remove_outputs(&mut self, actor_ids: &HashSet<ActorId>);
 
     fn dispatcher_id(&self) -> DispatcherId;
     fn is_empty(&self) -> bool;
+
+    /// The largest buffer fullness among the outputs, see
+    /// [`Output::buffer_fullness`](super::exchange::output::Output::buffer_fullness).
+    fn max_buffer_fullness(&self) -> f64;
 }
 
 #[derive(Debug)]
@@ -448,6 +487,16 @@ impl Dispatcher for RoundRobinDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            // always broadcast watermark
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs.extend(outputs.into_iter());
     }
@@ -466,6 +515,10 @@ impl Dispatcher for RoundRobinDataDispatcher {
     fn is_empty(&self) -> bool {
         self.outputs.is_empty()
     }
+
+    fn max_buffer_fullness(&self) -> f64 {
+        max_buffer_fullness(self.outputs.iter())
+    }
 }
 
 pub struct HashDataDispatcher {
@@ -520,6 +573,16 @@ impl Dispatcher for HashDataDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            // always broadcast watermark
+            for output in &mut self.outputs {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn dispatch_data(&mut self, chunk: StreamChunk) -> Self::DataFuture<'_> {
         async move {
             // A chunk can be shuffled into multiple output chunks that to be sent to downstreams.
@@ -642,6 +705,10 @@ impl Dispatcher for HashDataDispatcher {
     fn is_empty(&self) -> bool {
         self.outputs.is_empty()
     }
+
+    fn max_buffer_fullness(&self) -> f64 {
+        max_buffer_fullness(self.outputs.iter())
+    }
 }
 
 /// `BroadcastDispatcher` dispatches message to all outputs.
@@ -692,6 +759,15 @@ impl Dispatcher for BroadcastDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            for output in self.outputs.values_mut() {
+                output.send(Message::Watermark(watermark.clone())).await?;
+            }
+            Ok(())
+        }
+    }
+
     fn add_outputs(&mut self, outputs: impl IntoIterator<Item = BoxedOutput>) {
         self.outputs.extend(Self::into_pairs(outputs));
     }
@@ -709,6 +785,10 @@ impl Dispatcher for BroadcastDispatcher {
     fn is_empty(&self) -> bool {
         self.outputs.is_empty()
     }
+
+    fn max_buffer_fullness(&self) -> f64 {
+        max_buffer_fullness(self.outputs.values())
+    }
 }
 
 /// `SimpleDispatcher` dispatches message to a single output.
@@ -757,6 +837,13 @@ impl Dispatcher for SimpleDispatcher {
         }
     }
 
+    fn dispatch_watermark(&mut self, watermark: Watermark) -> Self::WatermarkFuture<'_> {
+        async move {
+            self.output().send(Message::Watermark(watermark)).await?;
+            Ok(())
+        }
+    }
+
     fn remove_outputs(&mut self, actor_ids: &HashSet<ActorId>) {
         if actor_ids.contains(&self.output().actor_id()) {
             self.output = None;
@@ -770,6 +857,10 @@ impl Dispatcher for SimpleDispatcher {
     fn is_empty(&self) -> bool {
         self.output.is_none()
     }
+
+    fn max_buffer_fullness(&self) -> f64 {
+        max_buffer_fullness(self.output.iter())
+    }
 }
 
 #[cfg(test)]
@@ -309,6 +309,8 @@ impl<S: StateStore> DynamicFilterExecutor<S> {
                         return Err(anyhow!("RHS updates should always end with inserts").into());
                     }
                 }
+                // No watermark is derived for the output for now.
+                AlignedMessage::WatermarkLeft(_) | AlignedMessage::WatermarkRight(_) => {}
                 AlignedMessage::Barrier(barrier) => {
                     // Flush the difference between the `prev_value` and `current_value`
                     let curr: Datum = current_epoch_value.clone().flatten();
@@ -22,6 +22,7 @@ use risingwave_rpc_client::error::RpcError;
 use risingwave_storage::error::StorageError;
 
 use super::Barrier;
+use crate::task::ActorId;
 
 #[derive(thiserror::Error, Debug)]
 enum StreamExecutorErrorInner {
@@ -53,6 +54,23 @@ enum StreamExecutorErrorInner {
     #[error("Channel `{0}` closed")]
     ChannelClosed(String),
 
+    #[error(
+        "Upstream actor {upstream_actor_id} of actor {actor_id} closed unexpectedly: {}",
+        .upstream_error.as_deref().unwrap_or("no error reported, please check the log of the upstream actor")
+    )]
+    UpstreamClosed {
+        actor_id: ActorId,
+        upstream_actor_id: ActorId,
+        upstream_error: Option<String>,
+    },
+
+    #[error("Upstream actor {upstream_actor_id} of actor {actor_id} failed: {source}")]
+    UpstreamFailed {
+        actor_id: ActorId,
+        upstream_actor_id: ActorId,
+        source: Box<StreamExecutorError>,
+    },
+
     #[error("Failed to align barrier: expected {0:?} but got {1:?}")]
     AlignBarrier(Box<Barrier>, Box<Barrier>),
 
@@ -80,6 +98,34 @@ impl StreamExecutorError {
         StreamExecutorErrorInner::ChannelClosed(name.into()).into()
     }
 
+    /// The channel from `upstream_actor_id` to `actor_id` is closed without a stop barrier, where
+    /// `upstream_error` is the error the upstream actor failed with, if known.
+    pub fn upstream_closed(
+        actor_id: ActorId,
+        upstream_actor_id: ActorId,
+        upstream_error: Option<String>,
+    ) -> Self {
+        StreamExecutorErrorInner::UpstreamClosed {
+            actor_id,
+            upstream_actor_id,
+            upstream_error,
+        }
+        .into()
+    }
+
+    pub fn upstream_failed(
+        actor_id: ActorId,
+        upstream_actor_id: ActorId,
+        error: StreamExecutorError,
+    ) -> Self {
+        StreamExecutorErrorInner::UpstreamFailed {
+            actor_id,
+            upstream_actor_id,
+            source: error.into(),
+        }
+        .into()
+    }
+
     pub fn align_barrier(expected: Barrier, received: Barrier) -> Self {
         StreamExecutorErrorInner::AlignBarrier(expected.into(), received.into()).into()
     }
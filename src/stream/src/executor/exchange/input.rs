@@ -15,10 +15,11 @@
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use futures::Stream;
+use futures::{ready, Stream};
 use futures_async_stream::try_stream;
 use madsim::time::Instant;
 use pin_project::pin_project;
+use prometheus::IntCounter;
 use risingwave_common::bail;
 use risingwave_common::error::Result;
 use risingwave_common::util::addr::{is_local_address, HostAddr};
@@ -51,17 +52,39 @@ pub struct LocalInput {
     channel: Receiver<Message>,
 
     actor_id: ActorId,
+
+    /// The number of rows received through the channel.
+    recv_row_count: IntCounter,
+
+    /// The estimated size of the chunks received through the channel.
+    recv_chunk_bytes: IntCounter,
 }
 
 impl LocalInput {
-    fn new(channel: Receiver<Message>, actor_id: ActorId) -> Self {
-        Self { channel, actor_id }
+    pub fn new(
+        channel: Receiver<Message>,
+        up_down_ids: UpDownActorIds,
+        metrics: Arc<StreamingMetrics>,
+    ) -> Self {
+        let recv_row_count = metrics
+            .exchange_recv_row_count
+            .with_label_values(&[&up_down_ids.0.to_string(), &up_down_ids.1.to_string()]);
+        let recv_chunk_bytes = metrics
+            .exchange_recv_chunk_bytes
+            .with_label_values(&[&up_down_ids.0.to_string(), &up_down_ids.1.to_string()]);
+        Self {
+            channel,
+            actor_id: up_down_ids.0,
+            recv_row_count,
+            recv_chunk_bytes,
+        }
     }
 
     #[cfg(test)]
     pub fn for_test(channel: Receiver<Message>) -> BoxedInput {
-        // `actor_id` is currently only used by configuration change, use a dummy value.
-        Self::new(channel, 0).boxed_input()
+        // `actor_id` is currently only used by configuration change and watermark alignment, use a
+        // dummy value.
+        Self::new(channel, (0, 0), StreamingMetrics::unused().into()).boxed_input()
     }
 }
 
@@ -70,7 +93,12 @@ impl Stream for LocalInput {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         // TODO: shall we pass the error with local exchange?
-        self.channel.poll_recv(cx).map(|m| m.map(Ok))
+        let message = ready!(self.channel.poll_recv(cx));
+        if let Some(Message::Chunk(chunk)) = &message {
+            self.recv_row_count.inc_by(chunk.cardinality() as u64);
+            self.recv_chunk_bytes.inc_by(chunk.estimated_size() as u64);
+        }
+        Poll::Ready(message.map(Ok))
     }
 }
 
@@ -131,6 +159,12 @@ impl RemoteInput {
         let down_actor_id = up_down_ids.1.to_string();
         let up_fragment_id = up_down_frag.0.to_string();
         let down_fragment_id = up_down_frag.1.to_string();
+        let recv_row_count = metrics
+            .exchange_recv_row_count
+            .with_label_values(&[&up_actor_id, &down_actor_id]);
+        let recv_chunk_bytes = metrics
+            .exchange_recv_chunk_bytes
+            .with_label_values(&[&up_actor_id, &down_actor_id]);
 
         let mut rr = 0;
         const SAMPLING_FREQUENCY: u64 = 100;
@@ -167,7 +201,13 @@ impl RemoteInput {
                     rr += 1;
 
                     match msg_res {
-                        Ok(msg) => yield msg,
+                        Ok(msg) => {
+                            if let Message::Chunk(chunk) = &msg {
+                                recv_row_count.inc_by(chunk.cardinality() as u64);
+                                recv_chunk_bytes.inc_by(chunk.estimated_size() as u64);
+                            }
+                            yield msg;
+                        }
                         Err(e) => bail!("RemoteInput decode message error: {}", e),
                     }
                 }
@@ -209,7 +249,8 @@ pub(crate) fn new_input(
     let input = if is_local_address(&context.addr, &upstream_addr) {
         LocalInput::new(
             context.take_receiver(&(upstream_actor_id, actor_id))?,
-            upstream_actor_id,
+            (upstream_actor_id, actor_id),
+            metrics,
         )
         .boxed_input()
     } else {
@@ -30,6 +30,11 @@ pub trait Output: Debug + Send + Sync + 'static {
     /// The downstream actor id.
     fn actor_id(&self) -> ActorId;
 
+    /// The fraction of the buffer to the downstream actor that is in use, from 0 to 1.
+    fn buffer_fullness(&self) -> f64 {
+        0.0
+    }
+
     fn boxed(self) -> BoxedOutput
     where
         Self: Sized + 'static,
@@ -45,6 +50,8 @@ pub struct LocalOutput {
     actor_id: ActorId,
 
     ch: Sender<Message>,
+
+    max_capacity: usize,
 }
 
 impl Debug for LocalOutput {
@@ -57,7 +64,13 @@ impl Debug for LocalOutput {
 
 impl LocalOutput {
     pub fn new(actor_id: ActorId, ch: Sender<Message>) -> Self {
-        Self { actor_id, ch }
+        // Nothing has been sent through the channel yet, so all of it is available.
+        let max_capacity = ch.capacity();
+        Self {
+            actor_id,
+            ch,
+            max_capacity,
+        }
     }
 }
 
@@ -73,6 +86,10 @@ impl Output for LocalOutput {
     fn actor_id(&self) -> ActorId {
         self.actor_id
     }
+
+    fn buffer_fullness(&self) -> f64 {
+        buffer_fullness(&self.ch, self.max_capacity)
+    }
 }
 
 /// `RemoteOutput` compacts the data and send to a local buffer channel, which will be further sent
@@ -84,6 +101,8 @@ pub struct RemoteOutput {
     actor_id: ActorId,
 
     ch: Sender<Message>,
+
+    max_capacity: usize,
 }
 
 impl Debug for RemoteOutput {
@@ -96,7 +115,13 @@ impl Debug for RemoteOutput {
 
 impl RemoteOutput {
     pub fn new(actor_id: ActorId, ch: Sender<Message>) -> Self {
-        Self { actor_id, ch }
+        // Nothing has been sent through the channel yet, so all of it is available.
+        let max_capacity = ch.capacity();
+        Self {
+            actor_id,
+            ch,
+            max_capacity,
+        }
     }
 }
 
@@ -117,6 +142,17 @@ impl Output for RemoteOutput {
     fn actor_id(&self) -> ActorId {
         self.actor_id
     }
+
+    fn buffer_fullness(&self) -> f64 {
+        buffer_fullness(&self.ch, self.max_capacity)
+    }
+}
+
+fn buffer_fullness(ch: &Sender<Message>, max_capacity: usize) -> f64 {
+    if max_capacity == 0 {
+        return 0.0;
+    }
+    max_capacity.saturating_sub(ch.capacity()) as f64 / max_capacity as f64
 }
 
 /// Create a [`LocalOutput`] or [`RemoteOutput`] instance for the current actor id and the
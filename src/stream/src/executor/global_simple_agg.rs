@@ -283,6 +283,8 @@ impl<S: StateStore> GlobalSimpleAggExecutor<S> {
                     )
                     .await?;
                 }
+                // The output of simple aggregation has no column to carry watermarks.
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     let next_epoch = barrier.epoch.curr;
                     if let Some(chunk) =
@@ -12,12 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap};
 
 use async_trait::async_trait;
 use risingwave_common::array::{Op, Row, StreamChunk};
 use risingwave_common::catalog::{Schema, TableId};
-use risingwave_common::types::DataType;
+use risingwave_common::types::{DataType, Datum};
 use risingwave_common::util::ordered::{OrderedRow, OrderedRowDeserializer};
 use risingwave_common::util::sort_util::{OrderPair, OrderType};
 use risingwave_storage::StateStore;
@@ -165,7 +165,7 @@ impl<S: StateStore> InnerGroupTopNExecutorNew<S> {
             info: ExecutorInfo {
                 schema: input_info.schema,
                 pk_indices: input_info.pk_indices,
-                identity: format!("TopNExecutorNew {:X}", executor_id),
+                identity: format!("GroupTopNExecutor {:X}", executor_id),
             },
             schema,
             offset: offset_and_limit.0,
@@ -212,8 +212,8 @@ impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutorNew<S> {
         let mut res_ops = Vec::with_capacity(self.limit.unwrap_or(1024));
         let mut res_rows = Vec::with_capacity(self.limit.unwrap_or(1024));
 
-        // Record all groups encountered in the input
-        let mut unique_group_keys = HashSet::new();
+        // The rows in the range of each group encountered in the input, before applying the input.
+        let mut old_rows_by_group: HashMap<Vec<Datum>, Vec<TopNStateRow>> = HashMap::new();
         let datum_num_in_group_key = self.group_by.len();
 
         // Iterate over all the input, identify which groups they cover.
@@ -224,7 +224,7 @@ impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutorNew<S> {
                 group_key.push(row[col_id].clone());
             }
             // The group is encountered for the first time in this input
-            if !unique_group_keys.contains(&group_key) {
+            if !old_rows_by_group.contains_key(&group_key) {
                 // Because our state table is already set up to sort by the column value which is
                 // specified by `self.group_by` . Therefore, the first row of the current group
                 //  can be directly obtained by prefix scanning
@@ -233,9 +233,7 @@ impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutorNew<S> {
                     .managed_state
                     .find_range(Some(&prefix_key), self.offset, self.limit, epoch)
                     .await?;
-                // TODO: Don't delete all the previous data and then insert new data,
-                emit_multi_rows(&mut res_ops, &mut res_rows, Op::Delete, old_rows);
-                unique_group_keys.insert(group_key);
+                old_rows_by_group.insert(group_key, old_rows);
             }
 
             let pk_row = row_ref.row_by_indices(&self.internal_key_indices);
@@ -251,14 +249,14 @@ impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutorNew<S> {
             }
         }
 
-        // Generate new messages based on the current state table
-        for group_keys in unique_group_keys {
-            let prefix_key = Row::new(group_keys);
+        // Compare the new range of each group with the old one, and only emit the changes.
+        for (group_key, old_rows) in old_rows_by_group {
+            let prefix_key = Row::new(group_key);
             let new_rows = self
                 .managed_state
                 .find_range(Some(&prefix_key), self.offset, self.limit, epoch)
                 .await?;
-            emit_multi_rows(&mut res_ops, &mut res_rows, Op::Insert, new_rows);
+            emit_changes(&mut res_ops, &mut res_rows, old_rows, new_rows);
         }
 
         generate_output(res_rows, res_ops, &self.schema)
@@ -281,20 +279,44 @@ impl<S: StateStore> TopNExecutorBase for InnerGroupTopNExecutorNew<S> {
     }
 }
 
-fn emit_multi_rows(
+/// Emits the rows leaving the range as deletes, followed by the rows entering the range as inserts.
+/// The rows staying in the range are not emitted.
+fn emit_changes(
     res_ops: &mut Vec<Op>,
     res_rows: &mut Vec<Row>,
-    op: Op,
-    rows: Vec<TopNStateRow>,
+    old_rows: Vec<TopNStateRow>,
+    new_rows: Vec<TopNStateRow>,
 ) {
-    rows.into_iter().for_each(|topn_row| {
-        res_ops.push(op);
-        res_rows.push(topn_row.row);
-    })
+    let mut old_rows: BTreeMap<OrderedRow, Row> = old_rows
+        .into_iter()
+        .map(|topn_row| (topn_row.ordered_key, topn_row.row))
+        .collect();
+    let mut inserted_rows = Vec::with_capacity(new_rows.len());
+    for topn_row in new_rows {
+        match old_rows.remove(&topn_row.ordered_key) {
+            Some(old_row) if old_row == topn_row.row => {}
+            Some(old_row) => {
+                res_ops.push(Op::Delete);
+                res_rows.push(old_row);
+                inserted_rows.push(topn_row.row);
+            }
+            None => inserted_rows.push(topn_row.row),
+        }
+    }
+    for old_row in old_rows.into_values() {
+        res_ops.push(Op::Delete);
+        res_rows.push(old_row);
+    }
+    for new_row in inserted_rows {
+        res_ops.push(Op::Insert);
+        res_rows.push(new_row);
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use assert_matches::assert_matches;
     use futures::StreamExt;
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
@@ -457,14 +479,10 @@ mod tests {
             res.as_chunk().unwrap(),
             &StreamChunk::from_pretty(
                 "  I I I
-                -  9 1 1
-                - 10 1 1
-                -  7 8 2
-                -  8 8 2
                 - 10 9 1
-                +  9 1 1
-                +  8 1 3
-                +  7 8 2",
+                -  8 8 2
+                - 10 1 1
+                +  8 1 3",
             ),
         );
 
@@ -543,10 +561,8 @@ mod tests {
             res.as_chunk().unwrap(),
             &StreamChunk::from_pretty(
                 "  I I I
-                - 10 1 1
-                -  8 1 3
                 -  8 8 2
-                +  8 1 3",
+                - 10 1 1",
             ),
         );
 
@@ -628,13 +644,7 @@ mod tests {
                 "  I I I
                 - 10 9 1
                 -  8 8 2
-                -  7 8 2
-                -  9 1 1
-                - 10 1 1
-                -  8 1 3
-                +  9 1 1
-                +  8 1 3
-                +  7 8 2",
+                - 10 1 1",
             ),
         );
 
@@ -719,11 +729,7 @@ mod tests {
                 "  I I I
                 - 10 9 1
                 -  8 8 2
-                -  7 8 2
-                -  9 1 1
-                - 10 1 1
-                +  9 1 1
-                +  7 8 2",
+                - 10 1 1",
             ),
         );
 
@@ -760,4 +766,74 @@ mod tests {
             ),
         );
     }
+
+    #[tokio::test]
+    async fn test_delete_first_row_in_range() {
+        let schema = create_schema();
+        let source = Box::new(MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Barrier(Barrier::new_test_barrier(1)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    "  I I I
+                    + 1 1 1
+                    + 2 1 2
+                    + 3 1 3
+                    + 4 1 4",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(2)),
+                Message::Chunk(StreamChunk::from_pretty(
+                    "  I I I
+                    - 1 1 1",
+                )),
+                Message::Barrier(Barrier::new_test_barrier(3)),
+            ],
+        ));
+        let top_n_executor = Box::new(
+            GroupTopNExecutor::new(
+                source as Box<dyn Executor>,
+                create_order_pairs(),
+                (0, Some(3)),
+                vec![],
+                MemoryStateStore::new(),
+                TableId::from(0x2333),
+                0,
+                1,
+                vec![],
+                vec![1],
+            )
+            .unwrap(),
+        );
+        let mut top_n_executor = top_n_executor.execute();
+
+        // consume the init barrier
+        top_n_executor.next().await.unwrap().unwrap();
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I I I
+                + 1 1 1
+                + 2 1 2
+                + 3 1 3",
+            ),
+        );
+
+        // barrier
+        assert_matches!(
+            top_n_executor.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        );
+        // The deleted first row is retracted, and the fourth row enters the range.
+        let res = top_n_executor.next().await.unwrap().unwrap();
+        assert_eq!(
+            res.as_chunk().unwrap(),
+            &StreamChunk::from_pretty(
+                "  I I I
+                - 1 1 1
+                + 4 1 4",
+            ),
+        );
+    }
 }
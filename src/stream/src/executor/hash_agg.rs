@@ -435,6 +435,8 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 Message::Chunk(chunk) => {
                     Self::apply_chunk(&mut extra, &mut state_map, chunk, epoch).await?;
                 }
+                // TODO: derive watermarks on the group key once aggregation supports them.
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     let next_epoch = barrier.epoch.curr;
                     assert_eq!(epoch, barrier.epoch.prev);
@@ -20,9 +20,10 @@ use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::{Op, Row, RowRef, StreamChunk};
 use risingwave_common::bail;
+use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
 use risingwave_common::hash::HashKey;
-use risingwave_common::types::{DataType, ToOwnedDatum};
+use risingwave_common::types::{DataType, ScalarImpl, ToOwnedDatum};
 use risingwave_expr::expr::BoxedExpression;
 use risingwave_storage::table::state_table::RowBasedStateTable;
 use risingwave_storage::StateStore;
@@ -31,7 +32,9 @@ use super::barrier_align::*;
 use super::error::{StreamExecutorError, StreamExecutorResult};
 use super::managed_state::join::*;
 use super::monitor::StreamingMetrics;
-use super::{BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef, Watermark,
+};
 use crate::common::StreamChunkBuilder;
 use crate::executor::PROCESSING_WINDOW_SIZE;
 
@@ -132,6 +135,20 @@ struct JoinSide<K: HashKey, S: StateStore> {
     col_types: Vec<DataType>,
     /// The start position for the side in output new columns
     start_pos: usize,
+    /// Whether the stream of this side is append-only
+    append_only: bool,
+    /// Whether the join key contains the pk, so there's at most one row for each join key
+    key_contains_pk: bool,
+    /// The latest watermark of the first join key column
+    watermark: Option<ScalarImpl>,
+}
+
+impl<K: HashKey, S: StateStore> JoinSide<K, S> {
+    /// Whether a row of this side is never retracted, nor followed by another row with the same
+    /// join key.
+    fn is_unique_append_only(&self) -> bool {
+        self.append_only && self.key_contains_pk
+    }
 }
 
 impl<K: HashKey, S: StateStore> std::fmt::Debug for JoinSide<K, S> {
@@ -141,6 +158,8 @@ impl<K: HashKey, S: StateStore> std::fmt::Debug for JoinSide<K, S> {
             .field("pk_indices", &self.pk_indices)
             .field("col_types", &self.col_types)
             .field("start_pos", &self.start_pos)
+            .field("append_only", &self.append_only)
+            .field("key_contains_pk", &self.key_contains_pk)
             .finish()
     }
 }
@@ -185,6 +204,11 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
     side_r: JoinSide<K, S>,
     /// Optional non-equi join conditions
     cond: Option<BoxedExpression>,
+    /// Derives the bound of the first join key from the watermarks of both sides. The rows with a
+    /// first join key below the bound are cleaned from the states, and dropped on arrival.
+    state_clean_expr: Option<BoxedExpression>,
+    /// The bound the states were last cleaned to
+    state_clean_bound: Option<ScalarImpl>,
     /// Identity string
     identity: String,
     /// Epoch
@@ -194,9 +218,6 @@ pub struct HashJoinExecutor<K: HashKey, S: StateStore, const T: JoinTypePrimitiv
     /// Logical Operator Info
     op_info: String,
 
-    /// Whether the logic can be optimized for append-only stream
-    append_only_optimize: bool,
-
     actor_id: u64,
     metrics: Arc<StreamingMetrics>,
 }
@@ -386,7 +407,9 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         op_info: String,
         mut state_table_l: RowBasedStateTable<S>,
         mut state_table_r: RowBasedStateTable<S>,
-        is_append_only: bool,
+        is_append_only_l: bool,
+        is_append_only_r: bool,
+        state_clean_expr: Option<BoxedExpression>,
         metrics: Arc<StreamingMetrics>,
     ) -> Self {
         // TODO: enable sanity check for hash join executor <https://github.com/singularity-data/risingwave/issues/3887>
@@ -425,24 +448,11 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         let pk_indices_l = input_l.pk_indices().to_vec();
         let pk_indices_r = input_r.pk_indices().to_vec();
 
-        // check whether join key contains pk in both side
-        let append_only_optimize = if is_append_only {
-            let join_key_l = HashSet::<usize>::from_iter(params_l.key_indices.clone());
-            let join_key_r = HashSet::<usize>::from_iter(params_r.key_indices.clone());
-            let pk_contained_l = pk_indices_l.len()
-                == pk_indices_l
-                    .iter()
-                    .filter(|x| join_key_l.contains(x))
-                    .count();
-            let pk_contained_r = pk_indices_r.len()
-                == pk_indices_r
-                    .iter()
-                    .filter(|x| join_key_r.contains(x))
-                    .count();
-            pk_contained_l && pk_contained_r
-        } else {
-            false
-        };
+        // check whether join key contains pk in each side
+        let join_key_l = HashSet::<usize>::from_iter(params_l.key_indices.clone());
+        let join_key_r = HashSet::<usize>::from_iter(params_r.key_indices.clone());
+        let pk_contained_l = pk_indices_l.iter().all(|x| join_key_l.contains(x));
+        let pk_contained_r = pk_indices_r.iter().all(|x| join_key_r.contains(x));
 
         let original_schema = Schema {
             fields: schema_fields,
@@ -471,6 +481,9 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 col_types: col_l_datatypes,
                 pk_indices: pk_indices_l,
                 start_pos: 0,
+                append_only: is_append_only_l,
+                key_contains_pk: pk_contained_l,
+                watermark: None,
             },
             side_r: JoinSide {
                 ht: JoinHashMap::new(
@@ -487,14 +500,18 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 col_types: col_r_datatypes,
                 pk_indices: pk_indices_r,
                 start_pos: side_l_column_n,
+                append_only: is_append_only_r,
+                key_contains_pk: pk_contained_r,
+                watermark: None,
             },
             pk_indices,
             output_indices,
             cond,
+            state_clean_expr,
+            state_clean_bound: None,
             identity: format!("HashJoinExecutor {:X}", executor_id),
             op_info,
             epoch: 0,
-            append_only_optimize,
             actor_id,
             metrics,
         }
@@ -514,6 +531,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         for msg in aligned_stream {
             match msg? {
                 AlignedMessage::Left(chunk) => {
+                    let chunk = self.drop_late_rows(chunk, SideType::Left)?;
                     #[for_await]
                     for chunk in Self::eq_join_oneside::<{ SideType::Left }>(
                         &mut self.side_l,
@@ -521,17 +539,17 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         &self.output_data_types,
                         &mut self.cond,
                         chunk,
-                        self.append_only_optimize,
                     ) {
                         yield chunk.map(|v| match v {
                             Message::Chunk(chunk) => {
                                 Message::Chunk(chunk.reorder_columns(&self.output_indices))
                             }
-                            barrier @ Message::Barrier(_) => barrier,
+                            msg @ (Message::Barrier(_) | Message::Watermark(_)) => msg,
                         })?;
                     }
                 }
                 AlignedMessage::Right(chunk) => {
+                    let chunk = self.drop_late_rows(chunk, SideType::Right)?;
                     #[for_await]
                     for chunk in Self::eq_join_oneside::<{ SideType::Right }>(
                         &mut self.side_l,
@@ -539,17 +557,24 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         &self.output_data_types,
                         &mut self.cond,
                         chunk,
-                        self.append_only_optimize,
                     ) {
                         yield chunk.map(|v| match v {
                             Message::Chunk(chunk) => {
                                 Message::Chunk(chunk.reorder_columns(&self.output_indices))
                             }
-                            barrier @ Message::Barrier(_) => barrier,
+                            msg @ (Message::Barrier(_) | Message::Watermark(_)) => msg,
                         })?;
                     }
                 }
+                // No watermark is derived for the output for now.
+                AlignedMessage::WatermarkLeft(watermark) => {
+                    Self::update_watermark(&mut self.side_l, watermark)
+                }
+                AlignedMessage::WatermarkRight(watermark) => {
+                    Self::update_watermark(&mut self.side_r, watermark)
+                }
                 AlignedMessage::Barrier(barrier) => {
+                    self.clean_states().await?;
                     self.flush_data().await?;
                     let epoch = barrier.epoch.curr;
                     self.side_l.ht.update_epoch(epoch);
@@ -561,6 +586,82 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         }
     }
 
+    fn update_watermark(side: &mut JoinSide<K, S>, watermark: Watermark) {
+        if watermark.col_idx == side.key_indices[0] {
+            side.watermark = Some(watermark.val);
+        }
+    }
+
+    /// Cleans the rows below the bound derived from the watermarks of both sides.
+    async fn clean_states(&mut self) -> StreamExecutorResult<()> {
+        let expr = match &self.state_clean_expr {
+            Some(expr) => expr,
+            None => return Ok(()),
+        };
+        // A row can still be matched by the rows to come from the side with the lower watermark.
+        let watermark = match (&self.side_l.watermark, &self.side_r.watermark) {
+            (Some(l), Some(r)) => l.min(r).clone(),
+            _ => return Ok(()),
+        };
+        // The bound saturates to the minimum of the key type on overflow, below which there's
+        // nothing to clean.
+        let bound = match expr.eval_row(&Row(vec![Some(watermark)])) {
+            Ok(Some(bound)) => bound,
+            Ok(None) => return Ok(()),
+            Err(e) => {
+                tracing::debug!(
+                    "{}: skip cleaning the states as the bound is out of range: {}",
+                    self.identity,
+                    e
+                );
+                return Ok(());
+            }
+        };
+        if self.state_clean_bound.as_ref().map_or(true, |b| &bound > b) {
+            self.side_l.ht.clean_below(&bound).await?;
+            self.side_r.ht.clean_below(&bound).await?;
+            self.state_clean_bound = Some(bound);
+        }
+        Ok(())
+    }
+
+    /// Hides the rows whose first join key is below the bound the states were cleaned to, as the
+    /// rows they would match are gone.
+    fn drop_late_rows(
+        &self,
+        chunk: StreamChunk,
+        side_type: SideTypePrimitive,
+    ) -> StreamExecutorResult<StreamChunk> {
+        let bound = match &self.state_clean_bound {
+            Some(bound) => bound,
+            None => return Ok(chunk),
+        };
+        let (key_idx, side_name) = if side_type == SideType::Left {
+            (self.side_l.key_indices[0], "left")
+        } else {
+            (self.side_r.key_indices[0], "right")
+        };
+
+        let chunk = chunk.compact()?;
+        let visibility: Bitmap = chunk
+            .column_at(key_idx)
+            .array_ref()
+            .iter()
+            .map(|key| !matches!(key, Some(key) if key.into_scalar_impl() < *bound))
+            .collect();
+        let late_row_count = chunk.capacity() - visibility.num_high_bits();
+        if late_row_count == 0 {
+            return Ok(chunk);
+        }
+        self.metrics
+            .join_late_row_count
+            .with_label_values(&[&self.actor_id.to_string(), side_name])
+            .inc_by(late_row_count as u64);
+
+        let (ops, columns, _) = chunk.into_inner();
+        Ok(StreamChunk::new(ops, columns, Some(visibility)))
+    }
+
     async fn flush_data(&mut self) -> StreamExecutorResult<()> {
         self.side_l.ht.flush().await?;
         self.side_r.ht.flush().await?;
@@ -610,7 +711,6 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
         output_data_types: &'a [DataType],
         cond: &'a mut Option<BoxedExpression>,
         chunk: StreamChunk,
-        append_only_optimize: bool,
     ) {
         let chunk = chunk.compact()?;
         let (data_chunk, ops) = chunk.into_parts();
@@ -656,6 +756,15 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
             Ok(cond_match)
         };
 
+        // If the matched side is unique and append-only, the row of the update side doesn't need to
+        // be kept once the row of the matched side is there, as it will never be retracted nor
+        // followed by another row to match.
+        let skip_update_if_matched = side_match.is_unique_append_only();
+        // If the update side is unique and append-only, and the matched side is append-only, the
+        // matched rows can be removed, since there will be no other rows to match them nor
+        // retractions of them.
+        let remove_matched = side_update.is_unique_append_only() && side_match.append_only;
+
         let keys = K::build(&side_update.key_indices, &data_chunk)?;
         for (idx, (row, op)) in data_chunk.rows().zip_eq(ops.iter()).enumerate() {
             let key = &keys[idx];
@@ -666,6 +775,7 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                 Op::Insert | Op::UpdateInsert => {
                     let mut degree = 0;
                     let mut append_only_matched_rows = Vec::with_capacity(1);
+                    let mut is_matched = false;
                     if let Some(mut matched_rows) = matched_rows {
                         for matched_row in matched_rows.values_mut() {
                             if check_join_condition(&row, &matched_row.row)? {
@@ -679,10 +789,8 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                                 }
                                 side_match.ht.inc_degree(matched_row)?;
                             }
-                            // If the stream is append-only and the join key covers pk in both side,
-                            // then we can remove matched rows since pk is unique and will not be
-                            // inserted again
-                            if append_only_optimize {
+                            is_matched = true;
+                            if remove_matched {
                                 append_only_matched_rows.push(matched_row.clone());
                             }
                         }
@@ -705,20 +813,11 @@ impl<K: HashKey, S: StateStore, const T: JoinTypePrimitive> HashJoinExecutor<K,
                         yield Message::Chunk(chunk);
                     }
 
-                    if append_only_optimize {
-                        if !append_only_matched_rows.is_empty() {
-                            // Since join key contains pk and pk is unique, there should be only
-                            // one row if matched
-                            debug_assert_eq!(1, append_only_matched_rows.len());
-                            let row = append_only_matched_rows.remove(0);
-                            let pk = row.row_by_indices(&side_match.pk_indices);
-                            side_match.ht.delete(key, pk, row)?;
-                        } else {
-                            side_update
-                                .ht
-                                .insert(key, pk, JoinRow::new(value, degree))?;
-                        }
-                    } else {
+                    for row in append_only_matched_rows {
+                        let pk = row.row_by_indices(&side_match.pk_indices);
+                        side_match.ht.delete(key, pk, row)?;
+                    }
+                    if !(skip_update_if_matched && is_matched) {
                         side_update
                             .ht
                             .insert(key, pk, JoinRow::new(value, degree))?;
@@ -778,7 +877,7 @@ mod tests {
     use risingwave_common::hash::{Key128, Key64};
     use risingwave_common::util::sort_util::OrderType;
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
-    use risingwave_expr::expr::InputRefExpression;
+    use risingwave_expr::expr::{InputRefExpression, LiteralExpression};
     use risingwave_pb::expr::expr_node::Type;
     use risingwave_storage::memory::MemoryStateStore;
 
@@ -832,6 +931,13 @@ mod tests {
 
     fn create_executor<const T: JoinTypePrimitive>(
         with_condition: bool,
+    ) -> (MessageSender, MessageSender, BoxedMessageStream) {
+        create_executor_with_state_clean_expr::<T>(with_condition, None)
+    }
+
+    fn create_executor_with_state_clean_expr<const T: JoinTypePrimitive>(
+        with_condition: bool,
+        state_clean_expr: Option<BoxedExpression>,
     ) -> (MessageSender, MessageSender, BoxedMessageStream) {
         let schema = Schema {
             fields: vec![
@@ -869,6 +975,8 @@ mod tests {
             mem_state_l,
             mem_state_r,
             false,
+            false,
+            state_clean_expr,
             Arc::new(StreamingMetrics::unused()),
         );
         (tx_l, tx_r, Box::new(executor).execute())
@@ -876,6 +984,14 @@ mod tests {
 
     fn create_append_only_executor<const T: JoinTypePrimitive>(
         with_condition: bool,
+    ) -> (MessageSender, MessageSender, BoxedMessageStream) {
+        create_executor_with_append_only_sides::<T>(with_condition, true, true)
+    }
+
+    fn create_executor_with_append_only_sides<const T: JoinTypePrimitive>(
+        with_condition: bool,
+        is_append_only_l: bool,
+        is_append_only_r: bool,
     ) -> (MessageSender, MessageSender, BoxedMessageStream) {
         let schema = Schema {
             fields: vec![
@@ -922,7 +1038,9 @@ mod tests {
             "HashJoinExecutor".to_string(),
             mem_state_l,
             mem_state_r,
-            true,
+            is_append_only_l,
+            is_append_only_r,
+            None,
             Arc::new(StreamingMetrics::unused()),
         );
         (tx_l, tx_r, Box::new(executor).execute())
@@ -1003,6 +1121,68 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_with_state_clean() {
+        // Rows with a join key 10 below the watermark are cleaned.
+        let state_clean_expr = new_binary_expr(
+            Type::Subtract,
+            DataType::Int64,
+            Box::new(InputRefExpression::new(DataType::Int64, 0)),
+            Box::new(LiteralExpression::new(
+                DataType::Int64,
+                Some(ScalarImpl::Int64(10)),
+            )),
+        );
+        let (mut tx_l, mut tx_r, mut hash_join) = create_executor_with_state_clean_expr::<
+            { JoinType::Inner },
+        >(false, Some(state_clean_expr));
+
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 4
+             + 20 5",
+        ));
+        hash_join.next().await.unwrap().unwrap();
+
+        // The bound is derived from the lower watermark of both sides.
+        tx_l.push_watermark(0, DataType::Int64, ScalarImpl::Int64(30));
+        tx_r.push_watermark(0, DataType::Int64, ScalarImpl::Int64(15));
+        tx_l.push_barrier(2, false);
+        tx_r.push_barrier(2, false);
+        assert!(matches!(
+            hash_join.next().await.unwrap().unwrap(),
+            Message::Barrier(_)
+        ));
+
+        // `1` on the left is cleaned, and `1` on the right arrives too late to be kept.
+        tx_r.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 6
+             + 20 7",
+        ));
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().compact().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I
+                + 20 5 20 7"
+            )
+        );
+        tx_l.push_chunk(StreamChunk::from_pretty(
+            "  I I
+             + 1 8",
+        ));
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap().compact().unwrap(),
+            StreamChunk::from_pretty(" I I I I")
+        );
+    }
+
     #[tokio::test]
     async fn test_streaming_hash_left_semi_join() {
         let chunk_l1 = StreamChunk::from_pretty(
@@ -1192,6 +1372,80 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_hash_inner_join_one_side_append_only() {
+        let chunk_l1 = StreamChunk::from_pretty(
+            "  I I I
+             + 1 4 1
+             + 2 5 2",
+        );
+        let chunk_l2 = StreamChunk::from_pretty(
+            "  I I I
+             - 1 4 1
+             - 2 5 2",
+        );
+        let chunk_r1 = StreamChunk::from_pretty(
+            "  I I I
+             + 1 4 7",
+        );
+        let chunk_r2 = StreamChunk::from_pretty(
+            "  I I I
+             + 2 5 8",
+        );
+
+        // Only the right side is append-only, so the left rows matched on insertion are not kept,
+        // while the right rows are never removed.
+        let (mut tx_l, mut tx_r, mut hash_join) =
+            create_executor_with_append_only_sides::<{ JoinType::Inner }>(false, false, true);
+
+        // push the init barrier for left and right
+        tx_l.push_barrier(1, false);
+        tx_r.push_barrier(1, false);
+        hash_join.next().await.unwrap().unwrap();
+
+        // push the 1st right chunk
+        tx_r.push_chunk(chunk_r1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty("I I I I I I")
+        );
+
+        // push the 1st left chunk
+        tx_l.push_chunk(chunk_l1);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I I I
+                + 1 4 1 1 4 7"
+            )
+        );
+
+        // push the 2nd right chunk
+        tx_r.push_chunk(chunk_r2);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I I I
+                + 2 5 2 2 5 8"
+            )
+        );
+
+        // push the 2nd left chunk
+        tx_l.push_chunk(chunk_l2);
+        let chunk = hash_join.next().await.unwrap().unwrap();
+        assert_eq!(
+            chunk.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I I I I I
+                - 1 4 1 1 4 7
+                - 2 5 2 2 5 8"
+            )
+        );
+    }
+
     #[tokio::test]
     async fn test_streaming_hash_left_semi_join_append_only() {
         let chunk_l1 = StreamChunk::from_pretty(
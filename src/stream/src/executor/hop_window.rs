@@ -227,6 +227,9 @@ impl HopWindowExecutor {
                     let new_chunk = StreamChunk::new(ops.clone(), new_cols, None);
                     yield Message::Chunk(new_chunk);
                 }
+            } else if let Message::Watermark(_) = msg {
+                // TODO: derive the watermarks of the window columns from the time column.
+                continue;
             } else {
                 yield msg;
                 continue;
@@ -189,6 +189,7 @@ async fn test_merger_sum_aggr() {
             Box::new(InputRefExpression::new(DataType::Int64, 1)),
         ],
         3,
+        vec![],
     );
 
     let items = Arc::new(Mutex::new(vec![]));
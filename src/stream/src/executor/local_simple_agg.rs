@@ -14,12 +14,14 @@
 
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use itertools::Itertools;
 use risingwave_common::array::column::Column;
 use risingwave_common::array::{Op, StreamChunk};
 use risingwave_common::catalog::Schema;
+use risingwave_expr::expr::AggKind;
 
 use super::aggregation::{
     agg_call_filter_res, create_streaming_agg_state, generate_agg_schema, AggCall,
@@ -28,6 +30,9 @@ use super::aggregation::{
 use super::error::StreamExecutorError;
 use super::*;
 
+/// [`LocalSimpleAggExecutor`] computes the partial results of simple aggregation in memory, and
+/// emits them as inserts on each barrier. The partial min/max can't be retracted, so they are only
+/// supported on append-only input.
 pub struct LocalSimpleAggExecutor {
     pub(super) input: Box<dyn Executor>,
     pub(super) info: ExecutorInfo,
@@ -106,6 +111,8 @@ impl LocalSimpleAggExecutor {
                     Self::apply_chunk(&agg_calls, &mut states, chunk)?;
                     is_dirty = true;
                 }
+                // The output of simple aggregation has no column to carry watermarks.
+                Message::Watermark(_) => {}
                 m @ Message::Barrier(_) => {
                     if is_dirty {
                         is_dirty = false;
@@ -147,6 +154,17 @@ impl LocalSimpleAggExecutor {
         pk_indices: PkIndices,
         executor_id: u64,
     ) -> StreamExecutorResult<Self> {
+        if let Some(agg_call) = agg_calls
+            .iter()
+            .find(|c| matches!(c.kind, AggKind::Min | AggKind::Max) && !c.append_only)
+        {
+            return Err(anyhow!(
+                "LocalSimpleAggExecutor only supports {:?} on append-only input",
+                agg_call.kind
+            )
+            .into());
+        }
+
         let schema = generate_agg_schema(input.as_ref(), &agg_calls, None);
         let info = ExecutorInfo {
             schema,
@@ -171,7 +189,6 @@ mod tests {
     use risingwave_common::catalog::schema_test_utils;
     use risingwave_common::error::Result;
     use risingwave_common::types::DataType;
-    use risingwave_expr::expr::AggKind;
 
     use super::*;
     use crate::executor::aggregation::{AggArgs, AggCall};
@@ -304,4 +321,100 @@ mod tests {
 
         Ok(())
     }
+    #[tokio::test]
+    async fn test_local_simple_agg_min_max() -> Result<()> {
+        let schema = schema_test_utils::iii();
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+        tx.push_barrier(1, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            "   I   I    I
+            + 100 200 1001
+            +  10  14 1002
+            +   4 300 1003",
+        ));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty(
+            "   I   I    I
+            +  50  70 1004",
+        ));
+        tx.push_barrier(3, false);
+
+        let agg_calls = vec![
+            AggCall {
+                kind: AggKind::Count,
+                args: AggArgs::None,
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: true,
+                filter: None,
+            },
+            AggCall {
+                kind: AggKind::Min,
+                args: AggArgs::Unary(DataType::Int64, 0),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: true,
+                filter: None,
+            },
+            AggCall {
+                kind: AggKind::Max,
+                args: AggArgs::Unary(DataType::Int64, 1),
+                return_type: DataType::Int64,
+                order_pairs: vec![],
+                append_only: true,
+                filter: None,
+            },
+        ];
+
+        let simple_agg = Box::new(LocalSimpleAggExecutor::new(
+            Box::new(source),
+            agg_calls,
+            vec![],
+            1,
+        )?);
+        let mut simple_agg = simple_agg.execute();
+
+        // Consume the init barrier
+        simple_agg.next().await.unwrap().unwrap();
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I   I
+                + 3 4 300"
+            )
+        );
+
+        assert_matches!(
+            simple_agg.next().await.unwrap().unwrap(),
+            Message::Barrier { .. }
+        );
+
+        // The partial results are reset after each barrier.
+        let msg = simple_agg.next().await.unwrap().unwrap();
+        assert_eq!(
+            msg.into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I  I  I
+                + 1 50 70"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_local_simple_agg_min_max_not_append_only() {
+        let schema = schema_test_utils::ii();
+        let (_tx, source) = MockSource::channel(schema, vec![1]);
+        let agg_calls = vec![AggCall {
+            kind: AggKind::Min,
+            args: AggArgs::Unary(DataType::Int64, 0),
+            return_type: DataType::Int64,
+            order_pairs: vec![],
+            append_only: false,
+            filter: None,
+        }];
+        assert!(LocalSimpleAggExecutor::new(Box::new(source), agg_calls, vec![], 1).is_err());
+    }
 }
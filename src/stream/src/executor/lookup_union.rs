@@ -113,6 +113,8 @@ impl LookupUnionExecutor {
                     end = false;
                     match msg {
                         msg @ Message::Chunk(_) => yield msg,
+                        // TODO: forward the minimum watermark across inputs.
+                        Message::Watermark(_) => {}
                         Message::Barrier(barrier) => {
                             if let Some(this_barrier) = &this_barrier {
                                 if this_barrier != &barrier {
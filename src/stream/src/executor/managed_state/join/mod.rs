@@ -297,6 +297,27 @@ impl<K: HashKey, S: StateStore> JoinHashMap<K, S> {
         Ok(JoinEntryState::with_cached(cached))
     }
 
+    /// Removes the rows whose first join key is below `bound`. As the first join key leads the pk
+    /// of the state table, they are removed with a range deletion. Only the cached entries of these
+    /// keys are evicted from the memory. Must be called right before `flush`.
+    pub async fn clean_below(&mut self, bound: &ScalarImpl) -> StreamExecutorResult<()> {
+        self.state_table
+            .delete_range_below(&Some(bound.clone()), self.current_epoch)
+            .await?;
+
+        let mut expired_keys = vec![];
+        for key in self.inner.iter().map(|(key, _)| key) {
+            let key_row = key.clone().deserialize(self.join_key_data_types.iter())?;
+            if matches!(&key_row[0], Some(key) if key < bound) {
+                expired_keys.push(key.clone());
+            }
+        }
+        for key in expired_keys {
+            self.inner.pop(&key);
+        }
+        Ok(())
+    }
+
     pub async fn flush(&mut self) -> StreamExecutorResult<()> {
         self.metrics.flush();
         self.state_table.commit(self.current_epoch).await?;
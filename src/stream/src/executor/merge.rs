@@ -85,26 +85,33 @@ impl MergeExecutor {
 
     #[cfg(test)]
     pub fn for_test(inputs: Vec<tokio::sync::mpsc::Receiver<Message>>) -> Self {
-        use super::exchange::input::LocalInput;
+        use super::exchange::input::{Input, LocalInput};
 
+        let metrics = Arc::new(StreamingMetrics::unused());
         Self::new(
             Schema::default(),
             vec![],
             114,
             514,
             1919,
-            inputs.into_iter().map(LocalInput::for_test).collect(),
+            inputs
+                .into_iter()
+                .enumerate()
+                .map(|(idx, input)| {
+                    LocalInput::new(input, (idx as ActorId, 114), metrics.clone()).boxed_input()
+                })
+                .collect(),
             SharedContext::for_test().into(),
             ActorContext::create(),
             810,
-            StreamingMetrics::unused().into(),
+            metrics,
         )
     }
 
     #[try_stream(ok = Message, error = StreamExecutorError)]
     async fn execute_inner(mut self: Box<Self>) {
         // Futures of all active upstreams.
-        let select_all = SelectReceivers::new(self.actor_id, self.upstreams);
+        let select_all = SelectReceivers::new(self.actor_id, self.upstreams, self.context.clone());
         let actor_id_str = self.actor_id.to_string();
 
         // Channels that're blocked by the barrier to align.
@@ -120,6 +127,7 @@ impl MergeExecutor {
                         .with_label_values(&[&actor_id_str])
                         .inc_by(chunk.cardinality() as _);
                 }
+                Message::Watermark(_) => {}
                 Message::Barrier(barrier) => {
                     if let Some(update) = barrier.as_update_merge(self.actor_id) {
                         // Create new upstreams receivers.
@@ -141,7 +149,11 @@ impl MergeExecutor {
 
                         // Poll the first barrier from the new upstreams. It must be the same as the
                         // one we polled from original upstreams.
-                        let mut select_new = SelectReceivers::new(self.actor_id, new_upstreams);
+                        let mut select_new = SelectReceivers::new(
+                            self.actor_id,
+                            new_upstreams,
+                            self.context.clone(),
+                        );
                         let new_barrier = expect_first_barrier(&mut select_new).await?;
                         assert_eq!(barrier, &new_barrier);
 
@@ -185,16 +197,55 @@ pub struct SelectReceivers {
     barrier: Option<Barrier>,
     last_base: usize,
     actor_id: u32,
+    /// Used to look up the error of an upstream actor when its channel is closed.
+    context: Arc<SharedContext>,
+    /// The latest watermark received from each upstream, keyed by the column index and then the
+    /// upstream actor id.
+    upstream_watermarks: HashMap<usize, HashMap<ActorId, Watermark>>,
+    /// The last watermark yielded of each column.
+    watermarks: HashMap<usize, Watermark>,
 }
 
 impl SelectReceivers {
-    fn new(actor_id: u32, upstreams: Vec<BoxedInput>) -> Self {
+    fn new(actor_id: u32, upstreams: Vec<BoxedInput>, context: Arc<SharedContext>) -> Self {
         Self {
             blocks: Vec::with_capacity(upstreams.len()),
             upstreams,
             last_base: 0,
             actor_id,
+            context,
             barrier: None,
+            upstream_watermarks: HashMap::new(),
+            watermarks: HashMap::new(),
+        }
+    }
+
+    /// Records the watermark from the upstream, and returns the minimum watermark of the column
+    /// across all upstreams if it advances.
+    fn handle_watermark(
+        &mut self,
+        upstream_actor_id: ActorId,
+        watermark: Watermark,
+    ) -> Option<Watermark> {
+        let col_idx = watermark.col_idx;
+        let upstream_watermarks = self.upstream_watermarks.entry(col_idx).or_default();
+        upstream_watermarks.insert(upstream_actor_id, watermark);
+        if upstream_watermarks.len() < self.upstreams.len() + self.blocks.len() {
+            // Some upstreams haven't sent any watermark of the column yet.
+            return None;
+        }
+
+        let min_watermark = upstream_watermarks
+            .values()
+            .min_by(|a, b| a.val.cmp(&b.val))
+            .cloned()
+            .unwrap();
+        match self.watermarks.get(&col_idx) {
+            Some(watermark) if watermark.val >= min_watermark.val => None,
+            _ => {
+                self.watermarks.insert(col_idx, min_watermark.clone());
+                Some(min_watermark)
+            }
         }
     }
 
@@ -214,6 +265,9 @@ impl SelectReceivers {
 
         self.upstreams
             .retain(|u| !upstream_actor_ids.contains(&u.actor_id()));
+        for upstream_watermarks in self.upstream_watermarks.values_mut() {
+            upstream_watermarks.retain(|actor_id, _| !upstream_actor_ids.contains(actor_id));
+        }
         self.last_base = 0;
     }
 }
@@ -231,9 +285,26 @@ impl Stream for SelectReceivers {
                     continue;
                 }
                 Poll::Ready(item) => {
-                    let message = item
-                        .expect("upstream closed unexpectedly, please check error in upstream executors")
-                        .expect("upstream error");
+                    let upstream_actor_id = self.upstreams[idx].actor_id();
+                    let message = match item {
+                        Some(Ok(message)) => message,
+                        Some(Err(e)) => {
+                            return Poll::Ready(Some(Err(StreamExecutorError::upstream_failed(
+                                self.actor_id,
+                                upstream_actor_id,
+                                e,
+                            ))));
+                        }
+                        // The upstream never closes the channel before a stop barrier, unless it
+                        // has failed.
+                        None => {
+                            return Poll::Ready(Some(Err(StreamExecutorError::upstream_closed(
+                                self.actor_id,
+                                upstream_actor_id,
+                                self.context.get_actor_failure(upstream_actor_id),
+                            ))));
+                        }
+                    };
 
                     match message {
                         Message::Barrier(barrier) => {
@@ -258,6 +329,14 @@ impl Stream for SelectReceivers {
                             self.last_base = (idx + 1) % self.upstreams.len();
                             return Poll::Ready(Some(Ok(message)));
                         }
+                        Message::Watermark(watermark) => {
+                            if let Some(watermark) =
+                                self.handle_watermark(upstream_actor_id, watermark)
+                            {
+                                self.last_base = (idx + 1) % self.upstreams.len();
+                                return Poll::Ready(Some(Ok(Message::Watermark(watermark))));
+                            }
+                        }
                     }
                 }
             }
@@ -377,6 +456,54 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_merger_watermark() {
+        let (tx1, rx1) = tokio::sync::mpsc::channel(16);
+        let (tx2, rx2) = tokio::sync::mpsc::channel(16);
+        let merger = MergeExecutor::for_test(vec![rx1, rx2]);
+        let mut merger = merger.boxed().execute();
+
+        let watermark =
+            |val| Message::Watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(val)));
+
+        // No watermark is yielded until both upstreams send one.
+        tx1.send(watermark(2)).await.unwrap();
+        tx1.send(watermark(5)).await.unwrap();
+        tx2.send(watermark(3)).await.unwrap();
+        tx2.send(watermark(4)).await.unwrap();
+        tx1.send(Message::Barrier(Barrier::new_test_barrier(1)))
+            .await
+            .unwrap();
+        tx2.send(Message::Barrier(Barrier::new_test_barrier(1)))
+            .await
+            .unwrap();
+
+        assert_eq!(merger.next().await.unwrap().unwrap(), watermark(3));
+        assert_eq!(merger.next().await.unwrap().unwrap(), watermark(4));
+        assert_matches!(merger.next().await.unwrap().unwrap(), Message::Barrier(_));
+    }
+
+    #[tokio::test]
+    async fn test_merger_upstream_closed() {
+        let (tx1, rx1) = tokio::sync::mpsc::channel(16);
+        let (tx2, rx2) = tokio::sync::mpsc::channel(16);
+        let merger = MergeExecutor::for_test(vec![rx1, rx2]);
+        let mut merger = merger.boxed().execute();
+
+        // The second upstream closes without a stop barrier.
+        tx1.send(Message::Barrier(Barrier::new_test_barrier(1)))
+            .await
+            .unwrap();
+        drop(tx2);
+
+        let err = merger.next().await.unwrap().unwrap_err();
+        assert!(
+            err.to_string().contains("Upstream actor 1 of actor 114"),
+            "{}",
+            err
+        );
+    }
+
     #[tokio::test]
     async fn test_configuration_change() {
         let schema = Schema { fields: vec![] };
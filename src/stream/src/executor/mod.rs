@@ -24,8 +24,9 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayImpl, ArrayRef, DataChunk, StreamChunk};
 use risingwave_common::buffer::Bitmap;
 use risingwave_common::catalog::Schema;
-use risingwave_common::error::{Result, ToRwResult};
-use risingwave_common::types::DataType;
+use risingwave_common::error::{internal_error, Result, ToRwResult};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_common::util::value_encoding::{deserialize_datum, serialize_datum};
 use risingwave_connector::source::{ConnectorState, SplitImpl};
 use risingwave_pb::data::Epoch as ProstEpoch;
 use risingwave_pb::stream_plan::add_mutation::Dispatchers;
@@ -35,7 +36,7 @@ use risingwave_pb::stream_plan::update_mutation::{DispatcherUpdate, MergeUpdate}
 use risingwave_pb::stream_plan::{
     AddMutation, Barrier as ProstBarrier, Dispatcher as ProstDispatcher, PauseMutation,
     ResumeMutation, SourceChangeSplitMutation, StopMutation, StreamMessage as ProstStreamMessage,
-    UpdateMutation,
+    UpdateMutation, Watermark as ProstWatermark,
 };
 use smallvec::SmallVec;
 use tracing::trace_span;
@@ -66,12 +67,15 @@ mod managed_state;
 mod merge;
 pub mod monitor;
 mod mview;
+mod over_window;
 mod project;
 mod project_set;
 mod rearranged_chain;
 mod receiver;
+mod session_window;
 mod simple;
 mod sink;
+mod sort;
 mod source;
 mod top_n;
 mod top_n_appendonly;
@@ -93,6 +97,7 @@ pub use error::StreamExecutorResult;
 pub use expand::ExpandExecutor;
 pub use filter::FilterExecutor;
 pub use global_simple_agg::GlobalSimpleAggExecutor;
+pub use group_top_n::GroupTopNExecutor;
 pub use hash_agg::HashAggExecutor;
 pub use hash_join::*;
 pub use hop_window::HopWindowExecutor;
@@ -101,13 +106,16 @@ pub use lookup::*;
 pub use lookup_union::LookupUnionExecutor;
 pub use merge::MergeExecutor;
 pub use mview::*;
+pub use over_window::OverWindowExecutor;
 pub use project::ProjectExecutor;
 pub use project_set::*;
 pub use rearranged_chain::RearrangedChainExecutor;
 pub use receiver::ReceiverExecutor;
 use risingwave_pb::source::{ConnectorSplit, ConnectorSplits};
+pub use session_window::SessionWindowExecutor;
 use simple::{SimpleExecutor, SimpleExecutorWrapper};
 pub use sink::SinkExecutor;
+pub use sort::SortExecutor;
 pub use source::*;
 pub use top_n::TopNExecutor;
 pub use top_n_appendonly::AppendOnlyTopNExecutor;
@@ -482,10 +490,50 @@ impl Barrier {
     }
 }
 
+/// A watermark of a column promises that no row with a smaller value in the column will arrive
+/// afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Watermark {
+    pub col_idx: usize,
+    pub data_type: DataType,
+    pub val: ScalarImpl,
+}
+
+impl Watermark {
+    pub fn new(col_idx: usize, data_type: DataType, val: ScalarImpl) -> Self {
+        Self {
+            col_idx,
+            data_type,
+            val,
+        }
+    }
+
+    /// Returns the same watermark on the column at `col_idx`.
+    pub fn with_idx(self, col_idx: usize) -> Self {
+        Self { col_idx, ..self }
+    }
+
+    pub fn to_protobuf(&self) -> Result<ProstWatermark> {
+        Ok(ProstWatermark {
+            col_idx: self.col_idx as u32,
+            data_type: Some(self.data_type.to_protobuf()),
+            val: serialize_datum(&Some(self.val.clone()))?,
+        })
+    }
+
+    pub fn from_protobuf(prost: &ProstWatermark) -> Result<Self> {
+        let data_type = DataType::from(prost.get_data_type()?);
+        let val = deserialize_datum(prost.get_val().as_slice(), &data_type)?
+            .ok_or_else(|| internal_error("watermark value must not be null"))?;
+        Ok(Self::new(prost.col_idx as usize, data_type, val))
+    }
+}
+
 #[derive(Debug, EnumAsInner, PartialEq)]
 pub enum Message {
     Chunk(StreamChunk),
     Barrier(Barrier),
+    Watermark(Watermark),
 }
 
 impl<'a> TryFrom<&'a Message> for &'a Barrier {
@@ -493,7 +541,7 @@ impl<'a> TryFrom<&'a Message> for &'a Barrier {
 
     fn try_from(m: &'a Message) -> std::result::Result<Self, Self::Error> {
         match m {
-            Message::Chunk(_) => Err(()),
+            Message::Chunk(_) | Message::Watermark(_) => Err(()),
             Message::Barrier(b) => Ok(b),
         }
     }
@@ -522,6 +570,7 @@ impl Message {
                 StreamMessage::StreamChunk(prost_stream_chunk)
             }
             Self::Barrier(barrier) => StreamMessage::Barrier(barrier.clone().to_protobuf()),
+            Self::Watermark(watermark) => StreamMessage::Watermark(watermark.to_protobuf()?),
         };
         let prost_stream_msg = ProstStreamMessage {
             stream_message: Some(prost),
@@ -537,6 +586,9 @@ impl Message {
             StreamMessage::Barrier(ref barrier) => {
                 Message::Barrier(Barrier::from_protobuf(barrier)?)
             }
+            StreamMessage::Watermark(ref watermark) => {
+                Message::Watermark(Watermark::from_protobuf(watermark)?)
+            }
         };
         Ok(res)
     }
@@ -26,6 +26,7 @@ pub struct StreamingMetrics {
     pub actor_barrier_time: GenericGaugeVec<AtomicF64>,
     pub actor_execution_time: GenericGaugeVec<AtomicF64>,
     pub actor_output_buffer_blocking_duration_ns: GenericCounterVec<AtomicU64>,
+    pub actor_output_buffer_fullness: GenericGaugeVec<AtomicF64>,
     pub actor_scheduled_duration: GenericGaugeVec<AtomicF64>,
     pub actor_scheduled_cnt: GenericGaugeVec<AtomicI64>,
     pub actor_fast_poll_duration: GenericGaugeVec<AtomicF64>,
@@ -42,9 +43,14 @@ pub struct StreamingMetrics {
     pub source_output_row_count: GenericCounterVec<AtomicU64>,
     pub exchange_recv_size: GenericCounterVec<AtomicU64>,
     pub exchange_frag_recv_size: GenericCounterVec<AtomicU64>,
+    pub exchange_recv_row_count: GenericCounterVec<AtomicU64>,
+    pub exchange_recv_chunk_bytes: GenericCounterVec<AtomicU64>,
     pub join_lookup_miss_count: GenericCounterVec<AtomicU64>,
     pub join_total_lookup_count: GenericCounterVec<AtomicU64>,
     pub join_barrier_align_duration: HistogramVec,
+    pub sort_late_row_count: GenericCounterVec<AtomicU64>,
+    pub join_late_row_count: GenericCounterVec<AtomicU64>,
+    pub session_window_late_row_count: GenericCounterVec<AtomicU64>,
 }
 
 impl StreamingMetrics {
@@ -97,6 +103,14 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let actor_output_buffer_fullness = register_gauge_vec_with_registry!(
+            "stream_actor_output_buffer_fullness",
+            "Fraction of the fullest output buffer of an actor after dispatching a chunk",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
         let exchange_recv_size = register_int_counter_vec_with_registry!(
             "stream_exchange_recv_size",
             "Total size of messages that have been received from upstream Actor",
@@ -113,6 +127,22 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let exchange_recv_row_count = register_int_counter_vec_with_registry!(
+            "stream_exchange_recv_row_count",
+            "Total number of rows that have been received from upstream Actor, through either local or remote channels",
+            &["up_actor_id", "down_actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let exchange_recv_chunk_bytes = register_int_counter_vec_with_registry!(
+            "stream_exchange_recv_chunk_bytes",
+            "Total estimated in-memory size of chunks that have been received from upstream Actor, through either local or remote channels",
+            &["up_actor_id", "down_actor_id"],
+            registry
+        )
+        .unwrap();
+
         let actor_fast_poll_duration = register_gauge_vec_with_registry!(
             "stream_actor_fast_poll_duration",
             "tokio's metrics",
@@ -242,6 +272,30 @@ impl StreamingMetrics {
             register_histogram_vec_with_registry!(opts, &["actor_id", "wait_side"], registry)
                 .unwrap();
 
+        let sort_late_row_count = register_int_counter_vec_with_registry!(
+            "stream_sort_late_row_count",
+            "Total number of rows dropped by sort executor for arriving behind the watermark",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
+        let join_late_row_count = register_int_counter_vec_with_registry!(
+            "stream_join_late_row_count",
+            "Total number of rows dropped by hash join executor for arriving after the rows they would match are cleaned",
+            &["actor_id", "side"],
+            registry
+        )
+        .unwrap();
+
+        let session_window_late_row_count = register_int_counter_vec_with_registry!(
+            "stream_session_window_late_row_count",
+            "Total number of rows dropped by session window executor for arriving after their session is closed",
+            &["actor_id"],
+            registry
+        )
+        .unwrap();
+
         Self {
             registry,
             executor_row_count,
@@ -249,6 +303,7 @@ impl StreamingMetrics {
             actor_barrier_time,
             actor_execution_time,
             actor_output_buffer_blocking_duration_ns,
+            actor_output_buffer_fullness,
             actor_scheduled_duration,
             actor_scheduled_cnt,
             actor_fast_poll_duration,
@@ -265,9 +320,14 @@ impl StreamingMetrics {
             source_output_row_count,
             exchange_recv_size,
             exchange_frag_recv_size,
+            exchange_recv_row_count,
+            exchange_recv_chunk_bytes,
             join_lookup_miss_count,
             join_total_lookup_count,
             join_barrier_align_duration,
+            sort_late_row_count,
+            join_late_row_count,
+            session_window_late_row_count,
         }
     }
 
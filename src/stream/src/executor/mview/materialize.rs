@@ -155,6 +155,7 @@ impl<S: StateStore> MaterializeExecutor<S> {
                     self.state_table.commit(b.epoch.prev).await?;
                     Message::Barrier(b)
                 }
+                m @ Message::Watermark(_) => m,
             }
         }
     }
@@ -0,0 +1,299 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use itertools::Itertools;
+use risingwave_common::array::{Op, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{DataType, ScalarImpl};
+use risingwave_storage::table::state_table::RowBasedStateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::{
+    expect_first_barrier, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices,
+    PkIndicesRef, StreamExecutorResult,
+};
+use crate::common::StreamChunkBuilder;
+use crate::executor::PROCESSING_WINDOW_SIZE;
+
+/// [`OverWindowExecutor`] appends `row_number() OVER (PARTITION BY .. ORDER BY ..)` to each row.
+///
+/// All rows are kept in the state table, whose pk starts with the partition key followed by the
+/// window order, so the rows of a partition can be read in order. For each chunk, the partitions
+/// it touches are numbered again, and the rows whose number changed are retracted and emitted
+/// again.
+pub struct OverWindowExecutor<S: StateStore> {
+    input: Option<BoxedExecutor>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    partition_key_indices: Vec<usize>,
+    state_table: RowBasedStateTable<S>,
+}
+
+impl<S: StateStore> OverWindowExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        partition_key_indices: Vec<usize>,
+        state_table: RowBasedStateTable<S>,
+    ) -> Self {
+        let schema = input
+            .schema()
+            .clone()
+            .into_fields()
+            .into_iter()
+            .chain([Field::with_name(DataType::Int64, "row_number")])
+            .collect();
+        Self {
+            input: Some(input),
+            schema,
+            pk_indices,
+            identity: format!("OverWindowExecutor {:X}", executor_id),
+            partition_key_indices,
+            state_table,
+        }
+    }
+
+    /// Returns the rows of the partition in the window order.
+    async fn partition_rows(
+        &self,
+        partition_key: &Row,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        let state_table_iter = self
+            .state_table
+            .iter_with_pk_prefix(partition_key, epoch)
+            .await?;
+        pin_mut!(state_table_iter);
+
+        let mut rows = vec![];
+        while let Some(row) = state_table_iter.next().await {
+            rows.push(row?.into_owned());
+        }
+        Ok(rows)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let mut input = self.input.take().unwrap().execute();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        let mut epoch = barrier.epoch.curr;
+        yield Message::Barrier(barrier);
+
+        let mut stream_chunk_builder =
+            StreamChunkBuilder::new(PROCESSING_WINDOW_SIZE, &self.schema.data_types(), 0, 0)?;
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    // The partitions touched by the chunk, in the order they first appear.
+                    let mut seen = HashSet::new();
+                    let mut partition_keys = vec![];
+                    for (_, row) in chunk.rows() {
+                        let partition_key = row.row_by_indices(&self.partition_key_indices);
+                        if seen.insert(partition_key.clone()) {
+                            partition_keys.push(partition_key);
+                        }
+                    }
+
+                    let mut old_partitions = Vec::with_capacity(partition_keys.len());
+                    for partition_key in &partition_keys {
+                        old_partitions.push(self.partition_rows(partition_key, epoch).await?);
+                    }
+
+                    for (op, row) in chunk.rows() {
+                        match op {
+                            Op::Insert | Op::UpdateInsert => {
+                                self.state_table.insert(row.to_owned_row())?
+                            }
+                            Op::Delete | Op::UpdateDelete => {
+                                self.state_table.delete(row.to_owned_row())?
+                            }
+                        }
+                    }
+
+                    for (partition_key, old_rows) in partition_keys.iter().zip_eq(old_partitions) {
+                        let new_rows = self.partition_rows(partition_key, epoch).await?;
+                        // Rows keep their number only if they stay at the same position. All the
+                        // retractions go first, as a row may move to another position.
+                        let changed = |i: usize| old_rows.get(i) != new_rows.get(i);
+                        for (op, rows) in [(Op::Delete, &old_rows), (Op::Insert, &new_rows)] {
+                            for (i, row) in rows.iter().enumerate() {
+                                if !changed(i) {
+                                    continue;
+                                }
+                                let mut datums = row.0.clone();
+                                datums.push(Some(ScalarImpl::Int64(i as i64 + 1)));
+                                if let Some(chunk) =
+                                    stream_chunk_builder.append_row_matched(op, &Row(datums))?
+                                {
+                                    yield Message::Chunk(chunk);
+                                }
+                            }
+                        }
+                    }
+                    if let Some(chunk) = stream_chunk_builder.take()? {
+                        yield Message::Chunk(chunk);
+                    }
+                }
+                // No watermark is derived for the output for now.
+                Message::Watermark(_) => {}
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(epoch).await?;
+                    epoch = barrier.epoch.curr;
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for OverWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+
+    #[tokio::test]
+    async fn test_over_window() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+
+        // The pk of the state table is the partition key, the window order and the input pk.
+        let state_table = RowBasedStateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(2), DataType::Int64),
+            ],
+            vec![
+                OrderType::Ascending,
+                OrderType::Descending,
+                OrderType::Ascending,
+            ],
+            vec![0, 1, 2],
+        );
+        let executor = OverWindowExecutor::new(Box::new(source), vec![2], 1, vec![0], state_table);
+        let mut over_window = Box::new(executor).execute();
+
+        tx.push_barrier(1, false);
+        over_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I  I
+            + 1 10 1
+            + 1 30 2
+            + 2 20 3",
+        ));
+        assert_eq!(
+            over_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I I
+                + 1 30 2 1
+                + 1 10 1 2
+                + 2 20 3 1",
+            )
+        );
+
+        // `20` goes between `30` and `10`, so `10` moves down, while `30` keeps its number.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I  I
+            + 1 20 4",
+        ));
+        assert_eq!(
+            over_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I I
+                - 1 10 1 2
+                + 1 20 4 2
+                + 1 10 1 3",
+            )
+        );
+
+        // Removing `30` moves all the others up.
+        tx.push_barrier(2, false);
+        over_window.next().await.unwrap().unwrap();
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I  I
+            - 1 30 2",
+        ));
+        assert_eq!(
+            over_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I I  I I
+                - 1 30 2 1
+                - 1 20 4 2
+                - 1 10 1 3
+                + 1 20 4 1
+                + 1 10 1 2",
+            )
+        );
+    }
+}
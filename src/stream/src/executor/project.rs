@@ -22,7 +22,7 @@ use risingwave_expr::expr::BoxedExpression;
 
 use super::{
     Executor, ExecutorInfo, PkIndices, PkIndicesRef, SimpleExecutor, SimpleExecutorWrapper,
-    StreamExecutorResult,
+    StreamExecutorResult, Watermark,
 };
 
 pub type ProjectExecutor = SimpleExecutorWrapper<SimpleProjectExecutor>;
@@ -33,6 +33,7 @@ impl ProjectExecutor {
         pk_indices: PkIndices,
         exprs: Vec<BoxedExpression>,
         execuotr_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
     ) -> Self {
         let info = ExecutorInfo {
             schema: input.schema().to_owned(),
@@ -41,7 +42,7 @@ impl ProjectExecutor {
         };
         SimpleExecutorWrapper {
             input,
-            inner: SimpleProjectExecutor::new(info, exprs, execuotr_id),
+            inner: SimpleProjectExecutor::new(info, exprs, execuotr_id, watermark_derivations),
         }
     }
 }
@@ -54,10 +55,19 @@ pub struct SimpleProjectExecutor {
 
     /// Expressions of the current projection.
     exprs: Vec<BoxedExpression>,
+
+    /// Pairs of the input column index and the output column index, for the expressions that
+    /// select an input column directly. The watermarks on these input columns are passed through.
+    watermark_derivations: Vec<(usize, usize)>,
 }
 
 impl SimpleProjectExecutor {
-    pub fn new(input_info: ExecutorInfo, exprs: Vec<BoxedExpression>, executor_id: u64) -> Self {
+    pub fn new(
+        input_info: ExecutorInfo,
+        exprs: Vec<BoxedExpression>,
+        executor_id: u64,
+        watermark_derivations: Vec<(usize, usize)>,
+    ) -> Self {
         let schema = Schema {
             fields: exprs
                 .iter()
@@ -71,6 +81,7 @@ impl SimpleProjectExecutor {
                 identity: format!("ProjectExecutor {:X}", executor_id),
             },
             exprs,
+            watermark_derivations,
         }
     }
 }
@@ -102,6 +113,14 @@ impl SimpleExecutor for SimpleProjectExecutor {
         Ok(Some(new_chunk))
     }
 
+    fn handle_watermark(&self, watermark: Watermark) -> Vec<Watermark> {
+        self.watermark_derivations
+            .iter()
+            .filter(|(input_idx, _)| *input_idx == watermark.col_idx)
+            .map(|(_, output_idx)| watermark.clone().with_idx(*output_idx))
+            .collect()
+    }
+
     fn schema(&self) -> &Schema {
         &self.info.schema
     }
@@ -121,7 +140,7 @@ mod tests {
     use risingwave_common::array::stream_chunk::StreamChunkTestExt;
     use risingwave_common::array::StreamChunk;
     use risingwave_common::catalog::{Field, Schema};
-    use risingwave_common::types::DataType;
+    use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_expr::expr::expr_binary_nonnull::new_binary_expr;
     use risingwave_expr::expr::InputRefExpression;
     use risingwave_pb::expr::expr_node::Type;
@@ -165,6 +184,7 @@ mod tests {
             vec![],
             vec![test_expr],
             1,
+            vec![],
         ));
         let mut project = project.execute();
 
@@ -191,4 +211,47 @@ mod tests {
 
         assert!(project.next().await.unwrap().unwrap().is_stop());
     }
+
+    #[tokio::test]
+    async fn test_project_watermark() {
+        let schema = Schema {
+            fields: vec![
+                Field::unnamed(DataType::Int64),
+                Field::unnamed(DataType::Int64),
+            ],
+        };
+        let source = MockSource::with_messages(
+            schema,
+            PkIndices::new(),
+            vec![
+                Message::Watermark(Watermark::new(0, DataType::Int64, ScalarImpl::Int64(1))),
+                Message::Watermark(Watermark::new(1, DataType::Int64, ScalarImpl::Int64(2))),
+            ],
+        );
+
+        let project = Box::new(ProjectExecutor::new(
+            Box::new(source),
+            vec![],
+            vec![
+                Box::new(InputRefExpression::new(DataType::Int64, 1)),
+                Box::new(InputRefExpression::new(DataType::Int64, 0)),
+                Box::new(InputRefExpression::new(DataType::Int64, 1)),
+            ],
+            1,
+            vec![(1, 0), (0, 1), (1, 2)],
+        ));
+        let mut project = project.execute();
+
+        let watermark = |col_idx, val| {
+            Message::Watermark(Watermark::new(
+                col_idx,
+                DataType::Int64,
+                ScalarImpl::Int64(val),
+            ))
+        };
+        assert_eq!(project.next().await.unwrap().unwrap(), watermark(1, 1));
+        assert_eq!(project.next().await.unwrap().unwrap(), watermark(0, 2));
+        assert_eq!(project.next().await.unwrap().unwrap(), watermark(2, 2));
+        assert!(project.next().await.unwrap().unwrap().is_stop());
+    }
 }
@@ -186,6 +186,9 @@ impl ProjectSetExecutor {
 
                     yield Message::Chunk(StreamChunk::from_parts(ret_ops, chunk));
                 }
+                // The output columns are derived by table functions, which may not preserve the
+                // order of the input columns.
+                Message::Watermark(_) => {}
                 m => yield m,
             }
         }
@@ -16,7 +16,7 @@ use std::sync::Arc;
 
 use futures::channel::{mpsc, oneshot};
 use futures::stream::select_with_strategy;
-use futures::{stream, StreamExt};
+use futures::{future, stream, StreamExt};
 use futures_async_stream::try_stream;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
@@ -35,6 +35,13 @@ use crate::task::{ActorId, CreateMviewProgress};
 ///
 /// [`RearrangedChainExecutor`] resolves the latency problem when creating MV with a huge amount of
 /// existing data, by rearranging the barrier from the upstream. Check the design doc for details.
+/// The rearranged barriers are processed between the batches of the snapshot, whose size is
+/// configured by `backfill_batch_size`.
+///
+/// The snapshot is read at the epoch before the first barrier, and the upstream chunks are only
+/// those after it, so a row is never seen in both the snapshot and the upstream. The upstream
+/// chunks received during the backfill are buffered and forwarded after the snapshot, in the order
+/// of the phantom barriers.
 pub struct RearrangedChainExecutor {
     snapshot: BoxedExecutor,
 
@@ -49,7 +56,7 @@ pub struct RearrangedChainExecutor {
     info: ExecutorInfo,
 }
 
-fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
+fn mapping(upstream_indices: &[usize], msg: Message) -> Option<Message> {
     match msg {
         Message::Chunk(chunk) => {
             let (ops, columns, visibility) = chunk.into_inner();
@@ -57,9 +64,17 @@ fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
                 .iter()
                 .map(|&i| columns[i].clone())
                 .collect();
-            Message::Chunk(StreamChunk::new(ops, mapped_columns, visibility))
+            Some(Message::Chunk(StreamChunk::new(
+                ops,
+                mapped_columns,
+                visibility,
+            )))
         }
-        _ => msg,
+        Message::Watermark(watermark) => upstream_indices
+            .iter()
+            .position(|&i| i == watermark.col_idx)
+            .map(|idx| Message::Watermark(watermark.with_idx(idx))),
+        _ => Some(msg),
     }
 }
 
@@ -67,6 +82,7 @@ fn mapping(upstream_indices: &[usize], msg: Message) -> Message {
 enum RearrangedMessage {
     RearrangedBarrier(Barrier),
     PhantomBarrier(Barrier),
+    SnapshotChunk(StreamChunk),
     Chunk(StreamChunk),
 }
 
@@ -75,7 +91,9 @@ impl RearrangedMessage {
         match self {
             RearrangedMessage::RearrangedBarrier(_) => None,
             RearrangedMessage::PhantomBarrier(barrier) => Message::Barrier(barrier).into(),
-            RearrangedMessage::Chunk(chunk) => Message::Chunk(chunk).into(),
+            RearrangedMessage::SnapshotChunk(chunk) | RearrangedMessage::Chunk(chunk) => {
+                Message::Chunk(chunk).into()
+            }
         }
     }
 }
@@ -83,8 +101,9 @@ impl RearrangedMessage {
 impl RearrangedMessage {
     fn rearranged_from(msg: Message) -> Self {
         match msg {
-            Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
+            Message::Chunk(chunk) => RearrangedMessage::SnapshotChunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::RearrangedBarrier(barrier),
+            Message::Watermark(_) => unreachable!("snapshot should not contain watermarks"),
         }
     }
 
@@ -92,6 +111,7 @@ impl RearrangedMessage {
         match msg {
             Message::Chunk(chunk) => RearrangedMessage::Chunk(chunk),
             Message::Barrier(barrier) => RearrangedMessage::PhantomBarrier(barrier),
+            Message::Watermark(_) => unreachable!("watermarks should not be rearranged"),
         }
     }
 }
@@ -122,10 +142,13 @@ impl RearrangedChainExecutor {
     async fn execute_inner(mut self) {
         // 0. Project the upstream with `upstream_indices`.
         let upstream_indices = self.upstream_indices.clone();
-        let mut upstream = self
-            .upstream
-            .execute()
-            .map(move |result| result.map(|msg| mapping(&upstream_indices, msg)));
+        let mut upstream = self.upstream.execute().filter_map(move |result| {
+            future::ready(
+                result
+                    .map(|msg| mapping(&upstream_indices, msg))
+                    .transpose(),
+            )
+        });
 
         // 1. Poll the upstream to get the first barrier.
         let first_barrier = expect_first_barrier(&mut upstream).await?;
@@ -178,6 +201,9 @@ impl RearrangedChainExecutor {
             // Record the epoch of the last rearranged barrier we received.
             let mut last_rearranged_epoch = create_epoch;
             let mut stop_rearrange_tx = Some(stop_rearrange_tx);
+            // The number of rows consumed from the snapshot, and whether we're still on it.
+            let mut consumed_rows = 0;
+            let mut consuming_snapshot = true;
 
             // 6. Consume the merged `rearranged` stream.
             #[for_await]
@@ -189,9 +215,13 @@ impl RearrangedChainExecutor {
                     // Note that there's no phantom barrier in the snapshot. So we must have already
                     // consumed the whole snapshot and be on the upstream now.
                     RearrangedMessage::PhantomBarrier(barrier) => {
+                        consuming_snapshot = false;
                         // Update the progress since we've consumed all chunks before this phantom.
-                        self.progress
-                            .update(last_rearranged_epoch.curr, barrier.epoch.curr);
+                        self.progress.update(
+                            last_rearranged_epoch.curr,
+                            barrier.epoch.curr,
+                            consumed_rows,
+                        );
 
                         if barrier.epoch.curr >= last_rearranged_epoch.curr {
                             // Stop the background rearrangement task.
@@ -205,8 +235,16 @@ impl RearrangedChainExecutor {
                     // If we received a message, yield it.
                     RearrangedMessage::RearrangedBarrier(barrier) => {
                         last_rearranged_epoch = barrier.epoch;
+                        if consuming_snapshot {
+                            self.progress
+                                .update_snapshot(barrier.epoch.curr, consumed_rows);
+                        }
                         yield Message::Barrier(barrier);
                     }
+                    RearrangedMessage::SnapshotChunk(chunk) => {
+                        consumed_rows += chunk.cardinality() as u64;
+                        yield Message::Chunk(chunk);
+                    }
                     RearrangedMessage::Chunk(chunk) => yield Message::Chunk(chunk),
                 }
             }
@@ -221,7 +259,7 @@ impl RearrangedChainExecutor {
             // 8. Consume remainings.
             let mut finish_on_barrier = |msg: &Message| {
                 if let Some(barrier) = msg.as_barrier() {
-                    self.progress.finish(barrier.epoch.curr);
+                    self.progress.finish(barrier.epoch.curr, consumed_rows);
                 }
             };
 
@@ -287,6 +325,12 @@ impl RearrangedChainExecutor {
                 Either::Right((Some(msg), _)) => {
                     let msg = msg?;
 
+                    // The watermarks of the upstream don't hold for the snapshot being consumed,
+                    // so drop them until the backfill finishes.
+                    if matches!(msg, Message::Watermark(_)) {
+                        continue;
+                    }
+
                     // If we polled a barrier, rearrange it by yielding and leave a phantom barrier
                     // with `RearrangedMessage::phantom_from` in-place.
                     // If we polled a chunk, simply put it to the `upstream_tx`.
@@ -323,4 +367,103 @@ impl Executor for RearrangedChainExecutor {
     }
 }
 
-// TODO: add new unit tests for rearranged chain
+#[cfg(test)]
+mod tests {
+    use std::default::Default;
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{Field, Schema};
+    use risingwave_common::types::{DataType, ScalarImpl};
+    use risingwave_pb::stream_plan::Dispatcher;
+
+    use super::RearrangedChainExecutor;
+    use crate::executor::test_utils::MockSource;
+    use crate::executor::{Barrier, Executor, Message, Mutation, PkIndices};
+    use crate::task::{CreateMviewProgress, LocalBarrierManager};
+
+    #[tokio::test]
+    async fn test_handover() {
+        let barrier_manager = LocalBarrierManager::for_test();
+        let progress =
+            CreateMviewProgress::for_test(Arc::new(parking_lot::Mutex::new(barrier_manager)));
+        let actor_id = progress.actor_id();
+
+        let schema = Schema::new(vec![Field::unnamed(DataType::Int64)]);
+        let snapshot = Box::new(
+            MockSource::with_chunks(
+                schema.clone(),
+                PkIndices::new(),
+                vec![
+                    StreamChunk::from_pretty("I\n + 1"),
+                    StreamChunk::from_pretty("I\n + 2"),
+                ],
+            )
+            .stop_on_finish(false),
+        );
+
+        let (mut tx, upstream) = MockSource::channel(schema.clone(), PkIndices::new());
+        tx.push_message(Message::Barrier(
+            Barrier::new_test_barrier(1).with_mutation(Mutation::Add {
+                adds: maplit::hashmap! {
+                    0 => vec![Dispatcher {
+                        downstream_actor_id: vec![actor_id],
+                        ..Default::default()
+                    }],
+                },
+                splits: Default::default(),
+            }),
+        ));
+        tx.push_chunk(StreamChunk::from_pretty("I\n + 3"));
+        tx.push_barrier(2, false);
+        tx.push_chunk(StreamChunk::from_pretty("I\n + 4"));
+        tx.push_barrier(3, false);
+
+        let chain =
+            RearrangedChainExecutor::new(snapshot, Box::new(upstream), vec![0], progress, schema);
+        let mut chain = Box::new(chain).execute();
+
+        // Collect the messages until the backfill catches up with the upstream.
+        let mut rows = vec![];
+        let mut barriers = vec![];
+        while rows.len() < 4 || barriers.len() < 3 {
+            match chain.next().await.unwrap().unwrap() {
+                Message::Chunk(chunk) => {
+                    rows.extend(chunk.rows().map(|(_, row)| row.to_owned_row()));
+                }
+                Message::Barrier(barrier) => barriers.push(barrier.epoch.curr),
+                Message::Watermark(_) => unreachable!(),
+            }
+        }
+        let mut values = rows
+            .into_iter()
+            .map(|row| row[0].clone().unwrap())
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        // Each row of the snapshot and the upstream is seen exactly once.
+        assert_eq!(values, (1..=4).map(ScalarImpl::Int64).collect::<Vec<_>>());
+        assert!(barriers.windows(2).all(|w| w[0] < w[1]));
+
+        // The upstream messages after the handover are forwarded in order.
+        tx.push_chunk(StreamChunk::from_pretty("I\n + 5"));
+        tx.push_barrier(4, false);
+        assert_eq!(
+            chain.next().await.unwrap().unwrap().into_chunk().unwrap(),
+            StreamChunk::from_pretty("I\n + 5")
+        );
+        assert_eq!(
+            chain
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_barrier()
+                .unwrap()
+                .epoch
+                .curr,
+            Barrier::new_test_barrier(4).epoch.curr
+        );
+    }
+}
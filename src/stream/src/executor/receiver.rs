@@ -91,7 +91,7 @@ impl Executor for ReceiverExecutor {
                             .with_label_values(&[&actor_id_str])
                             .inc_by(chunk.cardinality() as _);
                     }
-                    Message::Barrier(_) => {}
+                    Message::Barrier(_) | Message::Watermark(_) => {}
                 };
                 status.next_message(msg);
             })
@@ -0,0 +1,530 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row};
+use risingwave_common::catalog::{Field, Schema};
+use risingwave_common::types::{
+    CheckedAdd, DataType, IntervalUnit, NaiveDateTimeWrapper, ScalarImpl, ScalarRefImpl,
+};
+use risingwave_storage::table::state_table::RowBasedStateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::monitor::StreamingMetrics;
+use super::{
+    expect_first_barrier, BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices,
+    PkIndicesRef, StreamExecutorResult,
+};
+use crate::common::StreamChunkBuilder;
+use crate::executor::PROCESSING_WINDOW_SIZE;
+
+/// An open session, covering the events from `start` to `last`. The session ends at `last + gap`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Session {
+    start: NaiveDateTimeWrapper,
+    last: NaiveDateTimeWrapper,
+}
+
+/// The open sessions of a partition, ordered by `start`. Sessions never overlap: an event within
+/// the gap of a session joins it, and merges all the sessions it bridges.
+#[derive(Debug, Default)]
+struct Sessions(Vec<Session>);
+
+impl Sessions {
+    fn add(&mut self, ts: NaiveDateTimeWrapper, gap: IntervalUnit) -> StreamExecutorResult<()> {
+        let ts_end = add_gap(ts, gap)?;
+        let mut merged = Session {
+            start: ts,
+            last: ts,
+        };
+        let mut sessions = Vec::with_capacity(self.0.len() + 1);
+        for session in self.0.drain(..) {
+            if ts < add_gap(session.last, gap)? && session.start < ts_end {
+                merged.start = merged.start.min(session.start);
+                merged.last = merged.last.max(session.last);
+            } else {
+                sessions.push(session);
+            }
+        }
+        let pos = sessions.partition_point(|session| session.start < merged.start);
+        sessions.insert(pos, merged);
+        self.0 = sessions;
+        Ok(())
+    }
+
+    /// Whether an event at `ts` would join an open session, i.e. it's within the gap of one.
+    fn can_join(&self, ts: NaiveDateTimeWrapper, gap: IntervalUnit) -> StreamExecutorResult<bool> {
+        let ts_end = add_gap(ts, gap)?;
+        for session in &self.0 {
+            if ts < add_gap(session.last, gap)? && session.start < ts_end {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Removes and returns the sessions ending no later than `watermark`.
+    fn close(
+        &mut self,
+        watermark: NaiveDateTimeWrapper,
+        gap: IntervalUnit,
+    ) -> StreamExecutorResult<Vec<Session>> {
+        // The sessions don't overlap, so they are ordered by the end as well.
+        let mut closed = 0;
+        while closed < self.0.len() && add_gap(self.0[closed].last, gap)? <= watermark {
+            closed += 1;
+        }
+        Ok(self.0.drain(..closed).collect())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn add_gap(
+    ts: NaiveDateTimeWrapper,
+    gap: IntervalUnit,
+) -> StreamExecutorResult<NaiveDateTimeWrapper> {
+    ts.checked_add(gap)
+        .ok_or_else(|| anyhow!("timestamp {} + {} out of range", ts, gap).into())
+}
+
+/// [`SessionWindowExecutor`] groups the events of an append-only input into sessions per
+/// partition key. A session is closed when no event arrives within `gap` after its last event.
+///
+/// The rows are buffered in the state table, whose pk is the partition key followed by the time
+/// column and the input pk. Once the watermark on the time column passes the end of a session, the
+/// rows of the session are emitted on the next barrier, with the bounds of the session appended as
+/// `window_start` and `window_end`. Aggregations over the sessions are done downstream by grouping
+/// on the window columns.
+///
+/// A row whose own gap has passed the watermark still joins an open session of its partition if
+/// it's within the gap of one. Otherwise its session would have been closed, so it's dropped, and
+/// so is a row with a null time column. They are counted in
+/// `stream_session_window_late_row_count`.
+///
+/// The open sessions are kept in memory and rebuilt from the state table on recovery, while the
+/// watermark starts over after recovery.
+pub struct SessionWindowExecutor<S: StateStore> {
+    input: Option<BoxedExecutor>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    time_col_idx: usize,
+    gap: IntervalUnit,
+    partition_key_indices: Vec<usize>,
+    state_table: RowBasedStateTable<S>,
+    actor_id: u64,
+    metrics: Arc<StreamingMetrics>,
+}
+
+impl<S: StateStore> SessionWindowExecutor<S> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: BoxedExecutor,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        time_col_idx: usize,
+        gap: IntervalUnit,
+        partition_key_indices: Vec<usize>,
+        state_table: RowBasedStateTable<S>,
+        actor_id: u64,
+        metrics: Arc<StreamingMetrics>,
+    ) -> Self {
+        let schema = input
+            .schema()
+            .clone()
+            .into_fields()
+            .into_iter()
+            .chain([
+                Field::with_name(DataType::Timestamp, "window_start"),
+                Field::with_name(DataType::Timestamp, "window_end"),
+            ])
+            .collect();
+        Self {
+            input: Some(input),
+            schema,
+            pk_indices,
+            identity: format!("SessionWindowExecutor {:X}", executor_id),
+            time_col_idx,
+            gap,
+            partition_key_indices,
+            state_table,
+            actor_id,
+            metrics,
+        }
+    }
+
+    fn time_of(&self, row: &Row) -> NaiveDateTimeWrapper {
+        match &row[self.time_col_idx] {
+            Some(ScalarImpl::NaiveDateTime(ts)) => *ts,
+            _ => unreachable!("only rows with a non-null time column are buffered"),
+        }
+    }
+
+    /// Rebuilds the open sessions from the buffered rows.
+    async fn recover_sessions(&self, epoch: u64) -> StreamExecutorResult<HashMap<Row, Sessions>> {
+        let state_table_iter = self.state_table.iter(epoch).await?;
+        pin_mut!(state_table_iter);
+
+        let mut partitions: HashMap<Row, Sessions> = HashMap::new();
+        while let Some(row) = state_table_iter.next().await {
+            let row = row?;
+            partitions
+                .entry(row.by_indices(&self.partition_key_indices))
+                .or_default()
+                .add(self.time_of(&row), self.gap)?;
+        }
+        Ok(partitions)
+    }
+
+    /// Returns the buffered rows of the partition up to `last`, in the order of the time column.
+    async fn rows_until(
+        &self,
+        partition_key: &Row,
+        last: NaiveDateTimeWrapper,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        let state_table_iter = self
+            .state_table
+            .iter_with_pk_prefix(partition_key, epoch)
+            .await?;
+        pin_mut!(state_table_iter);
+
+        let mut rows = vec![];
+        while let Some(row) = state_table_iter.next().await {
+            let row = row?;
+            if self.time_of(&row) > last {
+                break;
+            }
+            rows.push(row.into_owned());
+        }
+        Ok(rows)
+    }
+
+    /// Closes the sessions ending no later than `watermark`, and returns their rows with the
+    /// window columns appended.
+    async fn close_sessions(
+        &mut self,
+        partitions: &mut HashMap<Row, Sessions>,
+        watermark: NaiveDateTimeWrapper,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        let mut output = vec![];
+        for (partition_key, sessions) in partitions.iter_mut() {
+            let closed = sessions.close(watermark, self.gap)?;
+            let Some(last_closed) = closed.last() else {
+                continue;
+            };
+            // The open sessions start after the closed ones end, so all the rows up to the last
+            // closed session belong to the closed sessions.
+            let rows = self
+                .rows_until(partition_key, last_closed.last, epoch)
+                .await?;
+            let mut closed = closed.iter();
+            let mut session = closed.next().unwrap();
+            for row in rows {
+                let ts = self.time_of(&row);
+                while session.last < ts {
+                    session = closed.next().unwrap();
+                }
+                self.state_table.delete(row.clone())?;
+
+                let Row(mut datums) = row;
+                datums.push(Some(ScalarImpl::NaiveDateTime(session.start)));
+                datums.push(Some(ScalarImpl::NaiveDateTime(add_gap(
+                    session.last,
+                    self.gap,
+                )?)));
+                output.push(Row(datums));
+            }
+        }
+        partitions.retain(|_, sessions| !sessions.is_empty());
+        Ok(output)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let mut input = self.input.take().unwrap().execute();
+        let actor_id_str = self.actor_id.to_string();
+
+        let barrier = expect_first_barrier(&mut input).await?;
+        let mut epoch = barrier.epoch.curr;
+        let mut partitions = self.recover_sessions(epoch).await?;
+        yield Message::Barrier(barrier);
+
+        // All sessions ending no later than the watermark can be closed.
+        let mut watermark: Option<NaiveDateTimeWrapper> = None;
+
+        let mut stream_chunk_builder =
+            StreamChunkBuilder::new(PROCESSING_WINDOW_SIZE, &self.schema.data_types(), 0, 0)?;
+
+        #[for_await]
+        for msg in input {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let mut late_row_count = 0;
+                    for (op, row) in chunk.rows() {
+                        if op != Op::Insert {
+                            return Err(anyhow!(
+                                "SessionWindowExecutor only supports append-only input, got {:?}",
+                                op
+                            )
+                            .into());
+                        }
+                        let ts = match row.value_at(self.time_col_idx) {
+                            Some(ScalarRefImpl::NaiveDateTime(ts)) => ts,
+                            _ => {
+                                late_row_count += 1;
+                                continue;
+                            }
+                        };
+                        let row = row.to_owned_row();
+                        let partition_key = row.by_indices(&self.partition_key_indices);
+                        if let Some(watermark) = watermark {
+                            let can_join = match partitions.get(&partition_key) {
+                                Some(sessions) => sessions.can_join(ts, self.gap)?,
+                                None => false,
+                            };
+                            if add_gap(ts, self.gap)? <= watermark && !can_join {
+                                late_row_count += 1;
+                                continue;
+                            }
+                        }
+                        partitions
+                            .entry(partition_key)
+                            .or_default()
+                            .add(ts, self.gap)?;
+                        self.state_table.insert(row)?;
+                    }
+                    if late_row_count > 0 {
+                        self.metrics
+                            .session_window_late_row_count
+                            .with_label_values(&[&actor_id_str])
+                            .inc_by(late_row_count);
+                    }
+                }
+                Message::Watermark(w) if w.col_idx == self.time_col_idx => {
+                    if let ScalarImpl::NaiveDateTime(val) = w.val {
+                        watermark = watermark.max(Some(val));
+                    }
+                }
+                // The output is append-only, and no watermark is derived for now.
+                Message::Watermark(_) => {}
+                Message::Barrier(barrier) => {
+                    if let Some(watermark) = watermark {
+                        for row in self
+                            .close_sessions(&mut partitions, watermark, epoch)
+                            .await?
+                        {
+                            if let Some(chunk) =
+                                stream_chunk_builder.append_row_matched(Op::Insert, &row)?
+                            {
+                                yield Message::Chunk(chunk);
+                            }
+                        }
+                        if let Some(chunk) = stream_chunk_builder.take()? {
+                            yield Message::Chunk(chunk);
+                        }
+                    }
+
+                    self.state_table.commit(epoch).await?;
+                    epoch = barrier.epoch.curr;
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SessionWindowExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, TableId};
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+
+    fn ts(minute: u32) -> NaiveDateTimeWrapper {
+        NaiveDateTimeWrapper::new(chrono::NaiveDate::from_ymd(2022, 7, 1).and_hms(0, minute, 0))
+    }
+
+    #[test]
+    fn test_sessions() {
+        let gap = IntervalUnit::from_minutes(10);
+        let mut sessions = Sessions::default();
+        sessions.add(ts(0), gap).unwrap();
+        sessions.add(ts(30), gap).unwrap();
+        sessions.add(ts(15), gap).unwrap();
+        assert_eq!(sessions.0.len(), 3);
+
+        // `22` bridges the sessions at `15` and `30`.
+        sessions.add(ts(22), gap).unwrap();
+        assert_eq!(
+            sessions.0,
+            vec![
+                Session {
+                    start: ts(0),
+                    last: ts(0)
+                },
+                Session {
+                    start: ts(15),
+                    last: ts(30)
+                }
+            ]
+        );
+
+        assert!(sessions.can_join(ts(5), gap).unwrap());
+        assert!(!sessions.can_join(ts(40), gap).unwrap());
+
+        let closed = sessions.close(ts(39), gap).unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(sessions.0.len(), 1);
+        assert_eq!(sessions.close(ts(40), gap).unwrap().len(), 1);
+        assert!(sessions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_window() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Timestamp),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx, source) = MockSource::channel(schema, vec![2]);
+
+        // The pk of the state table is the partition key, the time column and the input pk.
+        let state_table = RowBasedStateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Timestamp),
+                ColumnDesc::unnamed(ColumnId::new(2), DataType::Int64),
+            ],
+            vec![
+                OrderType::Ascending,
+                OrderType::Ascending,
+                OrderType::Ascending,
+            ],
+            vec![0, 1, 2],
+        );
+        let metrics = Arc::new(StreamingMetrics::unused());
+        let executor = SessionWindowExecutor::new(
+            Box::new(source),
+            vec![2],
+            1,
+            1,
+            IntervalUnit::from_minutes(10),
+            vec![0],
+            state_table,
+            1,
+            metrics.clone(),
+        );
+        let mut session_window = Box::new(executor).execute();
+
+        tx.push_barrier(1, false);
+        session_window.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  TS                  I
+            + 1 2022-07-01T00:00:00 1
+            + 1 2022-07-01T00:05:00 2
+            + 2 2022-07-01T00:01:00 3
+            + 1 2022-07-01T00:20:00 4",
+        ));
+        tx.push_watermark(1, DataType::Timestamp, ScalarImpl::NaiveDateTime(ts(12)));
+        tx.push_barrier(2, false);
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I  TS                  I TS                  TS
+                + 2 2022-07-01T00:01:00 3 2022-07-01T00:01:00 2022-07-01T00:11:00",
+            )
+        );
+        session_window.next().await.unwrap().unwrap();
+
+        // `00:01` of partition 1 is behind the watermark but joins the open session from `00:00`
+        // to `00:05`, and `00:12` merges the open sessions. `00:00` of partition 2 is late and
+        // dropped, since the session of partition 2 has been closed.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I  TS                  I
+            + 1 2022-07-01T00:01:00 5
+            + 1 2022-07-01T00:12:00 6
+            + 2 2022-07-01T00:00:00 7",
+        ));
+        tx.push_watermark(1, DataType::Timestamp, ScalarImpl::NaiveDateTime(ts(30)));
+        tx.push_barrier(3, false);
+        assert_eq!(
+            session_window
+                .next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_chunk()
+                .unwrap(),
+            StreamChunk::from_pretty(
+                " I  TS                  I TS                  TS
+                + 1 2022-07-01T00:00:00 1 2022-07-01T00:00:00 2022-07-01T00:30:00
+                + 1 2022-07-01T00:01:00 5 2022-07-01T00:00:00 2022-07-01T00:30:00
+                + 1 2022-07-01T00:05:00 2 2022-07-01T00:00:00 2022-07-01T00:30:00
+                + 1 2022-07-01T00:12:00 6 2022-07-01T00:00:00 2022-07-01T00:30:00
+                + 1 2022-07-01T00:20:00 4 2022-07-01T00:00:00 2022-07-01T00:30:00",
+            )
+        );
+        session_window.next().await.unwrap().unwrap();
+        assert_eq!(
+            metrics
+                .session_window_late_row_count
+                .with_label_values(&["1"])
+                .get(),
+            1
+        );
+    }
+}
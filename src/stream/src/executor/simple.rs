@@ -17,7 +17,9 @@ use futures_async_stream::try_stream;
 use risingwave_common::catalog::Schema;
 
 use super::error::{StreamExecutorError, StreamExecutorResult};
-use super::{BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef, StreamChunk};
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndicesRef, StreamChunk, Watermark,
+};
 
 /// Executor which can handle [`StreamChunk`]s one by one.
 pub trait SimpleExecutor: Send + 'static {
@@ -25,6 +27,12 @@ pub trait SimpleExecutor: Send + 'static {
     fn map_filter_chunk(&mut self, chunk: StreamChunk)
         -> StreamExecutorResult<Option<StreamChunk>>;
 
+    /// Convert a watermark of the input to the watermarks of the output. By default the watermark
+    /// is forwarded unchanged, which is correct for executors that keep the input columns.
+    fn handle_watermark(&self, watermark: Watermark) -> Vec<Watermark> {
+        vec![watermark]
+    }
+
     /// See [`super::Executor::schema`].
     fn schema(&self) -> &Schema;
 
@@ -78,6 +86,11 @@ where
                     Some(new_chunk) => yield Message::Chunk(new_chunk),
                     None => continue,
                 },
+                Message::Watermark(watermark) => {
+                    for watermark in inner.handle_watermark(watermark) {
+                        yield Message::Watermark(watermark);
+                    }
+                }
                 m => yield m,
             }
         }
@@ -126,6 +126,7 @@ impl<S: StateStore> SinkExecutor<S> {
                     epoch = barrier.epoch.curr;
                     yield Message::Barrier(barrier);
                 }
+                Message::Watermark(watermark) => yield Message::Watermark(watermark),
             }
         }
     }
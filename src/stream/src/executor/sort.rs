@@ -0,0 +1,295 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use futures::{pin_mut, StreamExt};
+use futures_async_stream::try_stream;
+use risingwave_common::array::{Op, Row};
+use risingwave_common::catalog::Schema;
+use risingwave_common::types::{ScalarImpl, ToOwnedDatum};
+use risingwave_storage::table::state_table::RowBasedStateTable;
+use risingwave_storage::StateStore;
+
+use super::error::StreamExecutorError;
+use super::monitor::StreamingMetrics;
+use super::{
+    BoxedExecutor, BoxedMessageStream, Executor, Message, PkIndices, PkIndicesRef,
+    StreamExecutorResult,
+};
+use crate::common::StreamChunkBuilder;
+use crate::executor::PROCESSING_WINDOW_SIZE;
+
+/// [`SortExecutor`] reorders an append-only input by the sort column.
+///
+/// The rows are buffered in the state table, whose pk starts with the sort column. When a
+/// watermark on the sort column arrives from upstream, all buffered rows with a sort key below it
+/// are emitted in order and removed from the state table, and the watermark is forwarded.
+///
+/// Rows arriving later with a sort key below the watermark (or a null sort key) can't be emitted
+/// in order any more, so they are dropped and counted in `stream_sort_late_row_count`.
+///
+/// The watermark is kept in memory only. After recovery it starts over, so the late rows received
+/// right after recovery may be emitted out of order.
+pub struct SortExecutor<S: StateStore> {
+    input: Option<BoxedExecutor>,
+    schema: Schema,
+    pk_indices: PkIndices,
+    identity: String,
+    sort_column_index: usize,
+    state_table: RowBasedStateTable<S>,
+    actor_id: u64,
+    metrics: Arc<StreamingMetrics>,
+}
+
+impl<S: StateStore> SortExecutor<S> {
+    pub fn new(
+        input: BoxedExecutor,
+        pk_indices: PkIndices,
+        executor_id: u64,
+        sort_column_index: usize,
+        state_table: RowBasedStateTable<S>,
+        actor_id: u64,
+        metrics: Arc<StreamingMetrics>,
+    ) -> Self {
+        let schema = input.schema().clone();
+        Self {
+            input: Some(input),
+            schema,
+            pk_indices,
+            identity: format!("SortExecutor {:X}", executor_id),
+            sort_column_index,
+            state_table,
+            actor_id,
+            metrics,
+        }
+    }
+
+    /// Returns the buffered rows with a sort key below `watermark`, in the order of the sort key.
+    async fn rows_below(
+        &self,
+        watermark: &ScalarImpl,
+        epoch: u64,
+    ) -> StreamExecutorResult<Vec<Row>> {
+        let state_table_iter = self.state_table.iter(epoch).await?;
+        pin_mut!(state_table_iter);
+
+        let mut rows = vec![];
+        while let Some(row) = state_table_iter.next().await {
+            let row = row?;
+            match &row[self.sort_column_index] {
+                Some(key) if key < watermark => rows.push(row.into_owned()),
+                _ => break,
+            }
+        }
+        Ok(rows)
+    }
+
+    #[try_stream(ok = Message, error = StreamExecutorError)]
+    async fn into_stream(mut self) {
+        let input = self.input.take().unwrap();
+        let actor_id_str = self.actor_id.to_string();
+
+        // All rows with a sort key below the watermark have been emitted.
+        let mut watermark: Option<ScalarImpl> = None;
+        let mut epoch: u64 = 0;
+
+        let mut stream_chunk_builder =
+            StreamChunkBuilder::new(PROCESSING_WINDOW_SIZE, &self.schema.data_types(), 0, 0)?;
+
+        #[for_await]
+        for msg in input.execute() {
+            match msg? {
+                Message::Chunk(chunk) => {
+                    let mut late_row_count = 0;
+                    for (op, row) in chunk.rows() {
+                        if op != Op::Insert {
+                            return Err(anyhow!(
+                                "SortExecutor only supports append-only input, got {:?}",
+                                op
+                            )
+                            .into());
+                        }
+                        let key = match row.value_at(self.sort_column_index).to_owned_datum() {
+                            Some(key) if watermark.as_ref().map_or(true, |w| &key >= w) => key,
+                            _ => {
+                                late_row_count += 1;
+                                continue;
+                            }
+                        };
+                        self.state_table.insert(row.to_owned_row())?;
+                    }
+                    if late_row_count > 0 {
+                        self.metrics
+                            .sort_late_row_count
+                            .with_label_values(&[&actor_id_str])
+                            .inc_by(late_row_count);
+                    }
+                }
+                Message::Watermark(w) if w.col_idx == self.sort_column_index => {
+                    if watermark
+                        .as_ref()
+                        .map_or(false, |watermark| &w.val <= watermark)
+                    {
+                        continue;
+                    }
+                    for row in self.rows_below(&w.val, epoch).await? {
+                        if let Some(chunk) =
+                            stream_chunk_builder.append_row_matched(Op::Insert, &row)?
+                        {
+                            yield Message::Chunk(chunk);
+                        }
+                        self.state_table.delete(row)?;
+                    }
+                    if let Some(chunk) = stream_chunk_builder.take()? {
+                        yield Message::Chunk(chunk);
+                    }
+                    watermark = Some(w.val.clone());
+                    yield Message::Watermark(w);
+                }
+                // The output is only ordered by the sort column.
+                Message::Watermark(_) => {}
+                Message::Barrier(barrier) => {
+                    self.state_table.commit(epoch).await?;
+                    epoch = barrier.epoch.curr;
+
+                    yield Message::Barrier(barrier);
+                }
+            }
+        }
+    }
+}
+
+impl<S: StateStore> Executor for SortExecutor<S> {
+    fn execute(self: Box<Self>) -> BoxedMessageStream {
+        self.into_stream().boxed()
+    }
+
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    fn pk_indices(&self) -> PkIndicesRef {
+        &self.pk_indices
+    }
+
+    fn identity(&self) -> &str {
+        self.identity.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::array::stream_chunk::StreamChunkTestExt;
+    use risingwave_common::array::StreamChunk;
+    use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, TableId};
+    use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
+    use risingwave_storage::memory::MemoryStateStore;
+
+    use super::*;
+    use crate::executor::test_utils::MockSource;
+
+    #[tokio::test]
+    async fn test_sort() {
+        let schema = Schema::new(vec![
+            Field::unnamed(DataType::Int64),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let (mut tx, source) = MockSource::channel(schema, vec![1]);
+
+        // The pk of the state table is the sort key followed by the input pk.
+        let state_table = RowBasedStateTable::new_without_distribution(
+            MemoryStateStore::new(),
+            TableId::new(0),
+            vec![
+                ColumnDesc::unnamed(ColumnId::new(0), DataType::Int64),
+                ColumnDesc::unnamed(ColumnId::new(1), DataType::Int64),
+            ],
+            vec![OrderType::Ascending, OrderType::Ascending],
+            vec![0, 1],
+        );
+        let metrics = Arc::new(StreamingMetrics::unused());
+        let executor = SortExecutor::new(
+            Box::new(source),
+            vec![1],
+            1,
+            0,
+            state_table,
+            1,
+            metrics.clone(),
+        );
+        let mut sort = Box::new(executor).execute();
+
+        tx.push_barrier(1, false);
+        sort.next().await.unwrap().unwrap();
+
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 3 1
+            + 1 2
+            + 5 3
+            + 2 4",
+        ));
+        // Nothing is emitted before the watermark arrives.
+        tx.push_barrier(2, false);
+        assert!(sort.next().await.unwrap().unwrap().is_barrier());
+
+        tx.push_watermark(0, DataType::Int64, ScalarImpl::Int64(3));
+        assert_eq!(
+            sort.next().await.unwrap().unwrap().into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                + 1 2
+                + 2 4",
+            )
+        );
+        assert_eq!(
+            sort.next()
+                .await
+                .unwrap()
+                .unwrap()
+                .into_watermark()
+                .unwrap()
+                .val,
+            ScalarImpl::Int64(3)
+        );
+
+        // `2` is below the watermark `3` and is dropped.
+        tx.push_chunk(StreamChunk::from_pretty(
+            " I I
+            + 6 5
+            + 2 6
+            + 5 7",
+        ));
+        tx.push_watermark(0, DataType::Int64, ScalarImpl::Int64(6));
+        assert_eq!(
+            sort.next().await.unwrap().unwrap().into_chunk().unwrap(),
+            StreamChunk::from_pretty(
+                " I I
+                + 3 1
+                + 5 3
+                + 5 7",
+            )
+        );
+        sort.next().await.unwrap().unwrap();
+        tx.push_barrier(3, false);
+        sort.next().await.unwrap().unwrap();
+        assert_eq!(
+            metrics.sort_late_row_count.with_label_values(&["1"]).get(),
+            1
+        );
+    }
+}
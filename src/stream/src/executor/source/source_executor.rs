@@ -22,7 +22,7 @@ use risingwave_common::array::column::Column;
 use risingwave_common::array::{ArrayBuilder, I64ArrayBuilder, StreamChunk};
 use risingwave_common::bail;
 use risingwave_common::catalog::{ColumnId, Schema, TableId};
-use risingwave_common::error::Result;
+use risingwave_common::error::{ErrorCode, Result};
 use risingwave_connector::source::{ConnectorState, SplitImpl, SplitMetaData};
 use risingwave_source::connector_source::SourceContext;
 use risingwave_source::*;
@@ -134,25 +134,41 @@ impl<S: StateStore> SourceExecutor<S> {
 }
 
 impl<S: StateStore> SourceExecutor<S> {
-    fn get_diff(&self, rhs: ConnectorState) -> ConnectorState {
-        // rhs can not be None because we do not support split number reduction
+    /// Returns the new state of the splits if the assignment of the actor changes, where `None`
+    /// means the actor is left with no split.
+    ///
+    /// The offset of a split follows the cache if it is polled in this epoch, and otherwise the
+    /// state store, where a split moved from another actor has the offset committed by that
+    /// actor. The offset given by the meta is only used for the splits never consumed.
+    async fn get_diff(
+        &self,
+        rhs: ConnectorState,
+        epoch: u64,
+    ) -> StreamExecutorResult<Option<Vec<SplitImpl>>> {
+        let split_change = rhs.unwrap_or_default();
+        let prev_split_ids: HashSet<_> = self.stream_source_splits.iter().map(|s| s.id()).collect();
+        if split_change.len() == prev_split_ids.len()
+            && split_change
+                .iter()
+                .all(|s| prev_split_ids.contains(&s.id()))
+        {
+            return Ok(None);
+        }
 
-        let split_change = rhs.unwrap();
         let mut target_state: Vec<SplitImpl> = Vec::with_capacity(split_change.len());
-        let mut no_change_flag = true;
         for sc in &split_change {
-            // SplitImpl is identified by its id, target_state always follows offsets in cache
-            // here we introduce a hypothesis that every split is polled at least once in one epoch
-            match self.state_cache.get(&sc.id()) {
-                Some(s) => target_state.push(s.clone()),
-                None => {
-                    no_change_flag = false;
-                    target_state.push(sc.clone())
-                }
-            }
+            let state = match self.state_cache.get(&sc.id()) {
+                Some(s) => s.clone(),
+                None => self
+                    .split_state_store
+                    .try_recover_from_state_store(sc, epoch)
+                    .await?
+                    .unwrap_or_else(|| sc.clone()),
+            };
+            target_state.push(state);
         }
 
-        (!no_change_flag).then_some(target_state)
+        Ok(Some(target_state))
     }
 
     async fn take_snapshot(&mut self, epoch: u64) -> StreamExecutorResult<()> {
@@ -241,7 +257,9 @@ impl<S: StateStore> SourceExecutor<S> {
                         match mutation {
                             Mutation::SourceChangeSplit(mapping) => {
                                 if let Some(target_splits) = mapping.get(&self.actor_id).cloned() {
-                                    if let Some(target_state) = self.get_diff(target_splits) {
+                                    if let Some(target_state) =
+                                        self.get_diff(target_splits, epoch).await?
+                                    {
                                         log::info!(
                                             "actor {:?} apply source split change to {:?}",
                                             self.actor_id,
@@ -251,7 +269,10 @@ impl<S: StateStore> SourceExecutor<S> {
                                         // Replace the source reader with a new one of the new
                                         // state.
                                         let reader = self
-                                            .build_stream_source_reader(Some(target_state.clone()))
+                                            .build_stream_source_reader(
+                                                (!target_state.is_empty())
+                                                    .then(|| target_state.clone()),
+                                            )
                                             .await?;
                                         stream.replace_source_chunk_reader(reader);
 
@@ -293,7 +314,13 @@ impl<S: StateStore> SourceExecutor<S> {
                                 } else {
                                     Ok::<_, StreamExecutorError>((
                                         split.clone(),
-                                        origin_split_impl[0].update(offset.clone()),
+                                        origin_split_impl[0].update(offset.clone()).map_err(
+                                            |e| {
+                                                StreamExecutorError::source_error(
+                                                    ErrorCode::ConnectorError(e.to_string()),
+                                                )
+                                            },
+                                        )?,
                                     ))
                                 }
                             })
@@ -480,6 +507,7 @@ mod tests {
                 Message::Barrier(barrier) => {
                     assert_eq!(barrier.epoch, Epoch::new_test_epoch(1))
                 }
+                _ => unreachable!(),
             }
         }
 
@@ -15,11 +15,12 @@
 use futures::StreamExt;
 use futures_async_stream::try_stream;
 use risingwave_common::catalog::{Schema, TableId};
+use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_storage::memory::MemoryStateStore;
 use tokio::sync::mpsc;
 
 use super::error::StreamExecutorError;
-use super::{Barrier, Executor, Message, PkIndices, StreamChunk};
+use super::{Barrier, Executor, Message, PkIndices, StreamChunk, Watermark};
 
 pub struct MockSource {
     schema: Schema,
@@ -34,6 +35,11 @@ pub struct MockSource {
 pub struct MessageSender(mpsc::UnboundedSender<Message>);
 
 impl MessageSender {
+    #[allow(dead_code)]
+    pub fn push_message(&mut self, msg: Message) {
+        self.0.send(msg).unwrap();
+    }
+
     #[allow(dead_code)]
     pub fn push_chunk(&mut self, chunk: StreamChunk) {
         self.0.send(Message::Chunk(chunk)).unwrap();
@@ -47,6 +53,13 @@ impl MessageSender {
         }
         self.0.send(Message::Barrier(barrier)).unwrap();
     }
+
+    #[allow(dead_code)]
+    pub fn push_watermark(&mut self, col_idx: usize, data_type: DataType, val: ScalarImpl) {
+        self.0
+            .send(Message::Watermark(Watermark::new(col_idx, data_type, val)))
+            .unwrap();
+    }
 }
 
 impl std::fmt::Debug for MockSource {
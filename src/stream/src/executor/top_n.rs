@@ -641,6 +641,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_top_n_executor_with_offset_beyond_row_count() {
+        let order_types = create_order_pairs();
+        let source = create_source();
+        let top_n_executor = Box::new(
+            TopNExecutor::new(
+                source as Box<dyn Executor>,
+                order_types,
+                (10, Some(3)),
+                vec![0, 1],
+                MemoryStateStore::new(),
+                TableId::from(0x2333),
+                0,
+                1,
+                vec![],
+            )
+            .unwrap(),
+        );
+        let mut top_n_executor = top_n_executor.execute();
+
+        // consume the init barrier
+        top_n_executor.next().await.unwrap().unwrap();
+        // There are never more than 10 rows, so all of them are skipped by the offset.
+        for _ in 0..4 {
+            let res = top_n_executor.next().await.unwrap().unwrap();
+            assert_eq!(res.as_chunk().unwrap().cardinality(), 0);
+            assert_matches!(
+                top_n_executor.next().await.unwrap().unwrap(),
+                Message::Barrier(_)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_top_n_executor_with_limit() {
         let order_types = create_order_pairs();
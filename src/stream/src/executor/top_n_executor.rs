@@ -98,6 +98,8 @@ where
                 Message::Chunk(chunk) => {
                     yield Message::Chunk(self.inner.apply_chunk(chunk, epoch).await?)
                 }
+                // Top-N may retract rows behind a watermark, so watermarks can't be forwarded.
+                Message::Watermark(_) => continue,
                 Message::Barrier(barrier) => {
                     self.inner.flush_data(epoch).await?;
                     epoch = barrier.epoch.curr;
@@ -81,6 +81,8 @@ pub fn merge(inputs: Vec<BoxedMessageStream>) -> BoxedMessageStream {
             for item in input {
                 match item? {
                     msg @ Message::Chunk(_) => yield msg,
+                    // TODO: forward the minimum watermark across inputs.
+                    Message::Watermark(_) => {}
                     msg @ Message::Barrier(_) => {
                         if barrier.wait().await.is_leader() {
                             // one leader is responsible for sending barrier
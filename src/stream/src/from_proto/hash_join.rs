@@ -35,7 +35,8 @@ impl ExecutorBuilder for HashJoinExecutorBuilder {
         _stream: &mut LocalStreamManagerCore,
     ) -> Result<BoxedExecutor> {
         let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::HashJoin)?;
-        let is_append_only = node.is_append_only;
+        let is_append_only_l = node.is_left_append_only;
+        let is_append_only_r = node.is_right_append_only;
         let vnodes = Arc::new(params.vnode_bitmap.expect("vnodes not set for hash join"));
 
         let source_l = params.input.remove(0);
@@ -77,6 +78,11 @@ impl ExecutorBuilder for HashJoinExecutorBuilder {
         };
         trace!("Join non-equi condition: {:?}", condition);
 
+        let state_clean_expr = match node.get_state_clean_expr() {
+            Ok(expr_prost) => Some(build_from_prost(expr_prost)?),
+            Err(_) => None,
+        };
+
         macro_rules! impl_create_hash_join_executor {
             ([], $( { $join_type_proto:ident, $join_type:ident } ),*) => {
                 fn create_hash_join_executor<S: StateStore>(
@@ -131,7 +137,9 @@ impl ExecutorBuilder for HashJoinExecutorBuilder {
             op_info: params.op_info,
             state_table_l,
             state_table_r,
-            is_append_only,
+            is_append_only_l,
+            is_append_only_r,
+            state_clean_expr,
             actor_id: params.actor_id as u64,
             metrics: params.executor_stats,
         };
@@ -156,7 +164,9 @@ struct HashJoinExecutorDispatcherArgs<S: StateStore> {
     op_info: String,
     state_table_l: RowBasedStateTable<S>,
     state_table_r: RowBasedStateTable<S>,
-    is_append_only: bool,
+    is_append_only_l: bool,
+    is_append_only_r: bool,
+    state_clean_expr: Option<BoxedExpression>,
     actor_id: u64,
     metrics: Arc<StreamingMetrics>,
 }
@@ -181,7 +191,9 @@ impl<S: StateStore, const T: JoinTypePrimitive> HashKeyDispatcher
             args.op_info,
             args.state_table_l,
             args.state_table_r,
-            args.is_append_only,
+            args.is_append_only_l,
+            args.is_append_only_r,
+            args.state_clean_expr,
             args.metrics,
         )))
     }
@@ -21,6 +21,7 @@ mod dynamic_filter;
 mod expand;
 mod filter;
 mod global_simple_agg;
+mod group_top_n;
 mod hash_agg;
 mod hash_join;
 mod hop_window;
@@ -29,9 +30,12 @@ mod lookup;
 mod lookup_union;
 mod merge;
 mod mview;
+mod over_window;
 mod project;
 mod project_set;
+mod session_window;
 mod sink;
+mod sort;
 mod source;
 mod top_n;
 mod top_n_appendonly;
@@ -51,6 +55,7 @@ use self::dynamic_filter::*;
 use self::expand::*;
 use self::filter::*;
 use self::global_simple_agg::*;
+use self::group_top_n::*;
 use self::hash_agg::*;
 use self::hash_join::*;
 use self::hop_window::*;
@@ -59,9 +64,12 @@ use self::lookup::*;
 use self::lookup_union::*;
 use self::merge::*;
 use self::mview::*;
+use self::over_window::*;
 use self::project::*;
 use self::project_set::*;
+use self::session_window::*;
 use self::sink::*;
+use self::sort::*;
 use self::source::*;
 use self::top_n::*;
 use self::top_n_appendonly::*;
@@ -126,5 +134,9 @@ pub fn create_executor(
         NodeBody::Expand => ExpandExecutorBuilder,
         NodeBody::DynamicFilter => DynamicFilterExecutorBuilder,
         NodeBody::ProjectSet => ProjectSetExecutorBuilder,
+        NodeBody::Sort => SortExecutorBuilder,
+        NodeBody::SessionWindow => SessionWindowExecutorBuilder,
+        NodeBody::GroupTopN => GroupTopNExecutorBuilder,
+        NodeBody::OverWindow => OverWindowExecutorBuilder,
     }
 }
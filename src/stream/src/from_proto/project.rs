@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use risingwave_expr::expr::build_from_prost;
+use risingwave_pb::expr::expr_node::RexNode;
 
 use super::*;
 use crate::executor::ProjectExecutor;
@@ -32,12 +33,24 @@ impl ExecutorBuilder for ProjectExecutorBuilder {
             .iter()
             .map(build_from_prost)
             .try_collect()?;
+        let watermark_derivations = node
+            .get_select_list()
+            .iter()
+            .enumerate()
+            .filter_map(|(output_idx, expr)| match expr.get_rex_node() {
+                Ok(RexNode::InputRef(input_ref)) => {
+                    Some((input_ref.column_idx as usize, output_idx))
+                }
+                _ => None,
+            })
+            .collect();
 
         Ok(ProjectExecutor::new(
             params.input.remove(0),
             params.pk_indices,
             project_exprs,
             params.executor_id,
+            watermark_derivations,
         )
         .boxed())
     }
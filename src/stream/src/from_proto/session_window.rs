@@ -0,0 +1,57 @@
+// Copyright 2022 Singularity Data
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::error::Result;
+use risingwave_storage::table::state_table::RowBasedStateTable;
+
+use super::*;
+use crate::executor::SessionWindowExecutor;
+
+pub struct SessionWindowExecutorBuilder;
+
+impl ExecutorBuilder for SessionWindowExecutorBuilder {
+    fn new_boxed_executor(
+        mut params: ExecutorParams,
+        node: &StreamNode,
+        store: impl StateStore,
+        _stream: &mut LocalStreamManagerCore,
+    ) -> Result<BoxedExecutor> {
+        let node = try_match_expand!(node.get_node_body().unwrap(), NodeBody::SessionWindow)?;
+        let input = params.input.remove(0);
+        let partition_key_indices = node
+            .get_partition_key_indices()
+            .iter()
+            .map(|&x| x as usize)
+            .collect_vec();
+        let state_table = RowBasedStateTable::from_table_catalog(
+            node.get_state_table()?,
+            store,
+            params.vnode_bitmap.map(Arc::new),
+        );
+
+        Ok(Box::new(SessionWindowExecutor::new(
+            input,
+            params.pk_indices,
+            params.executor_id,
+            node.get_time_col()?.column_idx as usize,
+            node.get_gap()?.into(),
+            partition_key_indices,
+            state_table,
+            params.actor_id as u64,
+            params.executor_stats,
+        )))
+    }
+}
@@ -67,6 +67,10 @@ pub struct LocalBarrierManager {
 
     /// Save collect rx
     collect_complete_receiver: HashMap<u64, Option<oneshot::Receiver<CollectResult>>>,
+
+    /// The `curr` epoch of the last barrier injected in managed mode. Meta retries the injection
+    /// on transport errors, so a barrier may arrive again after it has been injected.
+    last_injected_epoch: Option<u64>,
 }
 
 impl Default for LocalBarrierManager {
@@ -82,6 +86,7 @@ impl LocalBarrierManager {
             span: tracing::Span::none(),
             state,
             collect_complete_receiver: HashMap::default(),
+            last_injected_epoch: None,
         }
     }
 
@@ -102,13 +107,28 @@ impl LocalBarrierManager {
     }
 
     /// Broadcast a barrier to all senders. Save a receiver which will get notified when this
-    /// barrier is finished, in managed mode.
+    /// barrier is finished, in managed mode. A barrier whose epoch is not newer than the last
+    /// injected one is a retried injection and is ignored in managed mode.
     pub fn send_barrier(
         &mut self,
         barrier: &Barrier,
         actor_ids_to_send: impl IntoIterator<Item = ActorId>,
         actor_ids_to_collect: impl IntoIterator<Item = ActorId>,
     ) -> Result<()> {
+        if let BarrierState::Managed(_) = &self.state {
+            if self
+                .last_injected_epoch
+                .map_or(false, |last| barrier.epoch.curr <= last)
+            {
+                tracing::warn!(
+                    epoch = barrier.epoch.curr,
+                    "ignore the barrier injected again"
+                );
+                return Ok(());
+            }
+            self.last_injected_epoch = Some(barrier.epoch.curr);
+        }
+
         let to_send = {
             let to_send: HashSet<ActorId> = actor_ids_to_send.into_iter().collect();
             match &self.state {
@@ -80,11 +80,14 @@ impl ManagedBarrierState {
                 .into_iter()
                 .map(|(actor, state)| CreateMviewProgress {
                     chain_actor_id: actor,
-                    done: matches!(state, ChainState::Done),
+                    done: matches!(state, ChainState::Done(_)),
                     consumed_epoch: match state {
-                        ChainState::ConsumingUpstream(consumed_epoch) => consumed_epoch,
-                        ChainState::Done => curr_epoch,
+                        // Nothing of the upstream is consumed yet.
+                        ChainState::ConsumingSnapshot(_) => 0,
+                        ChainState::ConsumingUpstream(consumed_epoch, _) => consumed_epoch,
+                        ChainState::Done(_) => curr_epoch,
                     },
+                    consumed_rows: state.consumed_rows(),
                 })
                 .collect();
 
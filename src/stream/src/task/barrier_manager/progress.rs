@@ -18,11 +18,23 @@ use super::{BarrierState, LocalBarrierManager};
 use crate::task::{ActorId, SharedContext};
 
 type ConsumedEpoch = u64;
+type ConsumedRows = u64;
 
 #[derive(Debug, Clone, Copy)]
 pub(super) enum ChainState {
-    ConsumingUpstream(ConsumedEpoch),
-    Done,
+    ConsumingSnapshot(ConsumedRows),
+    ConsumingUpstream(ConsumedEpoch, ConsumedRows),
+    Done(ConsumedRows),
+}
+
+impl ChainState {
+    pub(super) fn consumed_rows(&self) -> ConsumedRows {
+        match self {
+            ChainState::ConsumingSnapshot(rows)
+            | ChainState::ConsumingUpstream(_, rows)
+            | ChainState::Done(rows) => *rows,
+        }
+    }
 }
 
 impl LocalBarrierManager {
@@ -87,27 +99,50 @@ impl CreateMviewProgress {
         );
     }
 
-    /// Update the progress to `ConsumingUpstream(consumed_epoch)`. The epoch must be monotonically
-    /// increasing.
+    /// Update the progress to `ConsumingSnapshot(consumed_rows)`, where `consumed_rows` is the
+    /// number of rows consumed from the snapshot so far.
     /// `current_epoch` should be provided to locate the barrier under concurrent checkpoint.
-    pub fn update(&mut self, current_epoch: u64, consumed_epoch: ConsumedEpoch) {
+    pub fn update_snapshot(&mut self, current_epoch: u64, consumed_rows: ConsumedRows) {
         match self.state {
-            Some(ChainState::ConsumingUpstream(last)) => {
-                assert!(last < consumed_epoch);
+            Some(ChainState::ConsumingSnapshot(last)) => {
+                assert!(last <= consumed_rows);
             }
-            Some(ChainState::Done) => unreachable!(),
+            Some(ChainState::ConsumingUpstream(..) | ChainState::Done(_)) => unreachable!(),
             None => {}
         }
-        self.update_inner(current_epoch, ChainState::ConsumingUpstream(consumed_epoch));
+        self.update_inner(current_epoch, ChainState::ConsumingSnapshot(consumed_rows));
+    }
+
+    /// Update the progress to `ConsumingUpstream(consumed_epoch, consumed_rows)`. The epoch must be
+    /// monotonically increasing.
+    /// `current_epoch` should be provided to locate the barrier under concurrent checkpoint.
+    pub fn update(
+        &mut self,
+        current_epoch: u64,
+        consumed_epoch: ConsumedEpoch,
+        consumed_rows: ConsumedRows,
+    ) {
+        match self.state {
+            Some(ChainState::ConsumingUpstream(last, _)) => {
+                assert!(last < consumed_epoch);
+            }
+            Some(ChainState::Done(_)) => unreachable!(),
+            Some(ChainState::ConsumingSnapshot(_)) | None => {}
+        }
+        self.update_inner(
+            current_epoch,
+            ChainState::ConsumingUpstream(consumed_epoch, consumed_rows),
+        );
     }
 
-    /// Finish the progress. If the progress is already finished, then perform no-op.
+    /// Finish the progress with the total number of rows consumed from the snapshot. If the
+    /// progress is already finished, then perform no-op.
     /// `current_epoch` should be provided to locate the barrier under concurrent checkpoint.
-    pub fn finish(&mut self, current_epoch: u64) {
-        if let Some(ChainState::Done) = self.state {
+    pub fn finish(&mut self, current_epoch: u64, consumed_rows: ConsumedRows) {
+        if let Some(ChainState::Done(_)) = self.state {
             return;
         }
-        self.update_inner(current_epoch, ChainState::Done);
+        self.update_inner(current_epoch, ChainState::Done(consumed_rows));
     }
 }
 
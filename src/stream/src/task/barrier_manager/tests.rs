@@ -126,3 +126,32 @@ async fn test_managed_barrier_collection_before_send_request() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_managed_barrier_injected_again() -> Result<()> {
+    let mut manager = LocalBarrierManager::new();
+
+    let actor_id = 233;
+    let (barrier_tx, mut barrier_rx) = unbounded_channel();
+    manager.register_sender(actor_id, barrier_tx);
+
+    // Inject the same barrier twice, as meta does when retrying the injection
+    let epoch = 114514;
+    let barrier = Barrier::new_test_barrier(epoch);
+    for _ in 0..2 {
+        manager
+            .send_barrier(&barrier, once(actor_id), once(actor_id))
+            .unwrap();
+    }
+    let mut collect_rx = manager.remove_collect_rx(barrier.epoch.prev);
+
+    // The actor receives the barrier only once
+    let received = barrier_rx.try_recv().unwrap();
+    assert_eq!(received.epoch.curr, epoch);
+    assert!(barrier_rx.try_recv().is_err());
+
+    manager.collect(actor_id, &received).unwrap();
+    assert!(collect_rx.try_recv().is_ok());
+
+    Ok(())
+}
@@ -16,11 +16,12 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use parking_lot::{Mutex, MutexGuard, RwLock};
+use risingwave_common::config::StreamingConfig;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::addr::HostAddr;
 use risingwave_pb::common::ActorInfo;
 use risingwave_rpc_client::ComputeClientPool;
-use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::mpsc::{channel, Receiver, Sender};
 
 use crate::executor::Message;
 
@@ -32,9 +33,6 @@ pub use barrier_manager::*;
 pub use env::*;
 pub use stream_manager::*;
 
-/// Default capacity of channel if two actors are on the same node
-pub const LOCAL_OUTPUT_CHANNEL_SIZE: usize = 16;
-
 pub type ConsumableChannelPair = (Option<Sender<Message>>, Option<Receiver<Message>>);
 pub type ActorId = u32;
 pub type FragmentId = u32;
@@ -79,6 +77,13 @@ pub struct SharedContext {
     pub(crate) compute_client_pool: ComputeClientPool,
 
     pub(crate) barrier_manager: Arc<Mutex<LocalBarrierManager>>,
+
+    /// The capacity of the channels created by [`SharedContext::new_channel`].
+    pub(crate) local_output_channel_size: usize,
+
+    /// The errors the actors failed with, so that the downstream actors can report them when they
+    /// find their upstream channels closed.
+    pub(crate) actor_failures: Mutex<HashMap<ActorId, String>>,
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -90,19 +95,27 @@ impl std::fmt::Debug for SharedContext {
 }
 
 impl SharedContext {
-    pub fn new(addr: HostAddr) -> Self {
+    pub fn new(addr: HostAddr, config: &StreamingConfig) -> Self {
         Self {
             channel_map: Default::default(),
             actor_infos: Default::default(),
             addr,
             compute_client_pool: ComputeClientPool::new(u64::MAX),
             barrier_manager: Arc::new(Mutex::new(LocalBarrierManager::new())),
+            local_output_channel_size: config.local_output_channel_size,
+            actor_failures: Default::default(),
         }
     }
 
     #[cfg(test)]
     pub fn for_test() -> Self {
-        Self::new(LOCAL_TEST_ADDR.clone())
+        Self::new(LOCAL_TEST_ADDR.clone(), &StreamingConfig::default())
+    }
+
+    /// Creates a channel with the configured capacity, to be registered with
+    /// [`SharedContext::add_channel_pairs`].
+    pub fn new_channel(&self) -> (Sender<Message>, Receiver<Message>) {
+        channel(self.local_output_channel_size)
     }
 
     #[inline]
@@ -171,6 +184,19 @@ impl SharedContext {
             .retain(|up_down_ids, _| f(up_down_ids));
     }
 
+    /// Records the error `actor_id` failed with. It should be called before the outputs of the
+    /// actor are dropped.
+    pub fn report_actor_failure(&self, actor_id: ActorId, error: &impl std::fmt::Display) {
+        self.actor_failures
+            .lock()
+            .insert(actor_id, error.to_string());
+    }
+
+    /// Returns the error `actor_id` failed with, if it has failed.
+    pub fn get_actor_failure(&self, actor_id: ActorId) -> Option<String> {
+        self.actor_failures.lock().get(&actor_id).cloned()
+    }
+
     pub fn get_actor_info(&self, actor_id: &ActorId) -> Result<ActorInfo> {
         self.actor_infos
             .read()
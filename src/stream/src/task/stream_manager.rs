@@ -24,21 +24,18 @@ use risingwave_common::buffer::Bitmap;
 use risingwave_common::config::StreamingConfig;
 use risingwave_common::error::{ErrorCode, Result, RwError};
 use risingwave_common::util::addr::HostAddr;
-use risingwave_hummock_sdk::LocalSstableInfo;
+use risingwave_hummock_sdk::{HummockEpoch, LocalSstableInfo};
 use risingwave_pb::common::ActorInfo;
 use risingwave_pb::{stream_plan, stream_service};
 use risingwave_storage::{dispatch_state_store, StateStore, StateStoreImpl};
-use tokio::sync::mpsc::{channel, Receiver};
+use tokio::sync::mpsc::Receiver;
 use tokio::task::JoinHandle;
 
 use super::{unique_executor_id, unique_operator_id, CollectResult};
 use crate::executor::monitor::StreamingMetrics;
 use crate::executor::*;
 use crate::from_proto::create_executor;
-use crate::task::{
-    ActorId, FragmentId, SharedContext, StreamEnvironment, UpDownActorIds,
-    LOCAL_OUTPUT_CHANNEL_SIZE,
-};
+use crate::task::{ActorId, FragmentId, SharedContext, StreamEnvironment, UpDownActorIds};
 
 #[cfg(test)]
 lazy_static::lazy_static! {
@@ -74,6 +71,10 @@ pub struct LocalStreamManagerCore {
 /// `LocalStreamManager` manages all stream executors in this project.
 pub struct LocalStreamManager {
     core: Mutex<LocalStreamManagerCore>,
+
+    /// Epochs that are collected but not synced yet. They will be synced along with the next
+    /// checkpoint barrier.
+    unsynced_epochs: Mutex<Vec<u64>>,
 }
 
 pub struct ExecutorParams {
@@ -127,6 +128,7 @@ impl LocalStreamManager {
     fn with_core(core: LocalStreamManagerCore) -> Self {
         Self {
             core: Mutex::new(core),
+            unsynced_epochs: Mutex::new(vec![]),
         }
     }
 
@@ -181,20 +183,43 @@ impl LocalStreamManager {
         rx.await.unwrap()
     }
 
-    pub async fn sync_epoch(&self, epoch: u64) -> Vec<LocalSstableInfo> {
-        dispatch_state_store!(self.state_store(), store, {
-            match store.sync(Some(epoch)).await {
-                Ok(_) => store.get_uncommitted_ssts(epoch),
-                // TODO: Handle sync failure by propagating it back to global barrier manager
-                Err(e) => panic!(
-                    "Failed to sync state store after receiving barrier prev_epoch {:?} due to {}",
-                    epoch, e
-                ),
-            }
-        })
+    /// Sync the data written in `epoch` and all the unsynced epochs before it, and return the
+    /// SSTs to be committed along with the epoch each of them is written in.
+    pub async fn sync_epoch(&self, epoch: u64) -> Vec<(HummockEpoch, LocalSstableInfo)> {
+        let epochs = {
+            let mut unsynced_epochs = self.unsynced_epochs.lock();
+            unsynced_epochs.push(epoch);
+            std::mem::take(&mut *unsynced_epochs)
+        };
+        let mut synced_sstables = vec![];
+        for epoch in epochs {
+            dispatch_state_store!(self.state_store(), store, {
+                match store.sync(Some(epoch)).await {
+                    Ok(_) => synced_sstables.extend(
+                        store
+                            .get_uncommitted_ssts(epoch)
+                            .into_iter()
+                            .map(|sst| (epoch, sst))
+                    ),
+                    // TODO: Handle sync failure by propagating it back to global barrier manager
+                    Err(e) => panic!(
+                        "Failed to sync state store after receiving barrier prev_epoch {:?} due to {}",
+                        epoch, e
+                    ),
+                }
+            })
+        }
+        synced_sstables
+    }
+
+    /// Skip syncing the data written in `epoch`, which will be synced by the next checkpoint
+    /// barrier.
+    pub fn skip_sync_epoch(&self, epoch: u64) {
+        self.unsynced_epochs.lock().push(epoch);
     }
 
     pub async fn clear_storage_buffer(&self) {
+        self.unsynced_epochs.lock().clear();
         dispatch_state_store!(self.state_store(), store, {
             store.clear_shared_buffer().await.unwrap();
         });
@@ -305,7 +330,7 @@ impl LocalStreamManager {
 fn update_upstreams(context: &SharedContext, ids: &[UpDownActorIds]) {
     ids.iter()
         .map(|id| {
-            let (tx, rx) = channel(LOCAL_OUTPUT_CHANNEL_SIZE);
+            let (tx, rx) = context.new_channel();
             context.add_channel_pairs(*id, (Some(tx), Some(rx)));
         })
         .count();
@@ -318,7 +343,7 @@ impl LocalStreamManagerCore {
         streaming_metrics: Arc<StreamingMetrics>,
         config: StreamingConfig,
     ) -> Self {
-        let context = SharedContext::new(addr);
+        let context = SharedContext::new(addr, &config);
         Self::new_inner(state_store, context, streaming_metrics, config)
     }
 
@@ -604,7 +629,11 @@ impl LocalStreamManagerCore {
         let mut actor_infos = self.context.actor_infos.write();
         for actor in req.get_info() {
             let ret = actor_infos.insert(actor.get_actor_id(), actor.clone());
-            if let Some(prev_actor) = ret && actor != &prev_actor{
+            // A remote actor may have been migrated to another worker.
+            if let Some(prev_actor) = ret
+                && actor != &prev_actor
+                && self.handles.contains_key(&actor.get_actor_id())
+            {
                 return Err(ErrorCode::InternalError(format!(
                     "actor info mismatch when broadcasting {}",
                     actor.get_actor_id()
@@ -622,6 +651,7 @@ impl LocalStreamManagerCore {
         self.context.retain_channel(|&(up_id, _)| up_id != actor_id);
         self.actor_monitor_tasks.remove(&actor_id).unwrap().abort();
         self.context.actor_infos.write().remove(&actor_id);
+        self.context.actor_failures.lock().remove(&actor_id);
         self.actors.remove(&actor_id);
         // Task should have already stopped when this method is invoked.
         handle.abort();
@@ -638,6 +668,7 @@ impl LocalStreamManagerCore {
             handle.abort();
         }
         self.context.actor_infos.write().clear();
+        self.context.actor_failures.lock().clear();
     }
 
     fn update_actors(
@@ -676,7 +707,7 @@ impl LocalStreamManagerCore {
                     }),
                 ) => {
                     let up_down_ids = (*up_id, *down_id);
-                    let (tx, rx) = channel(LOCAL_OUTPUT_CHANNEL_SIZE);
+                    let (tx, rx) = self.context.new_channel();
                     self.context
                         .add_channel_pairs(up_down_ids, (Some(tx), Some(rx)));
                 }
@@ -701,7 +732,7 @@ pub mod test_utils {
 
     pub fn add_local_channels(ctx: Arc<SharedContext>, up_down_ids: Vec<(u32, u32)>) {
         for up_down_id in up_down_ids {
-            let (tx, rx) = channel(LOCAL_OUTPUT_CHANNEL_SIZE);
+            let (tx, rx) = ctx.new_channel();
             ctx.add_channel_pairs(up_down_id, (Some(tx), Some(rx)));
         }
     }
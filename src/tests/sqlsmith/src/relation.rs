@@ -63,6 +63,7 @@ impl<'a, R: Rng> SqlGenerator<'a, R> {
                 columns: vec![],
             }),
             args: vec![],
+            as_of: None,
         };
         table.name = alias; // Rename the table.
         let columns = table.get_qualified_columns();
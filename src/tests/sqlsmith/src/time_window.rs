@@ -96,6 +96,7 @@ fn create_tvf(name: &str, alias: TableAlias, args: Vec<FunctionArg>) -> TableWit
         name: ObjectName(vec![name.into()]),
         alias: Some(alias),
         args,
+        as_of: None,
     };
     TableWithJoins {
         relation: factor,
@@ -60,6 +60,7 @@ pub(crate) fn create_table_factor_from_table(table: &Table) -> TableFactor {
         name: ObjectName(vec![Ident::new(&table.name)]),
         alias: None,
         args: vec![],
+        as_of: None,
     }
 }
 